@@ -0,0 +1,352 @@
+//! Direct event recording for agents and wrappers that own their loop.
+//!
+//! [`EventRecorder`] turns calls like [`EventRecorder::record_message`] into
+//! [`RawEvent`]s for one conversation and sends them through the same T0
+//! ingest as session files, either straight into a [`MindStore`] or to
+//! `POST /v1/raw-events` on `aoc serve`. First-party integrations skip
+//! writing a log only for an adapter to parse it back.
+//!
+//! ```no_run
+//! use aoc_agent_sdk::{ConversationRole, EventRecorder, ToolExecutionStatus};
+//!
+//! let mut recorder = EventRecorder::open_store(".aoc/mind/project.sqlite", "my-agent", "conv-1")?;
+//! recorder.record_message(ConversationRole::User, "fix the flaky parser test")?;
+//! recorder.record_tool_result("cargo test", ToolExecutionStatus::Success, Some("ok"), Some(840))?;
+//! recorder.record_task_signal(Some("mind"), &["149"], Some("in-progress"))?;
+//! # Ok::<(), aoc_agent_sdk::RecorderError>(())
+//! ```
+
+use aoc_core::mind_contracts::{
+    canonical_lineage_attrs, normalize_agent_id, ConversationLineageMetadata, MessageEvent,
+    RawEvent, RawEventBody, T0CompactionPolicy, TaskSignalEvent,
+};
+use aoc_core::{ErrorCode, ErrorCoded};
+use aoc_mind::{ingest_raw_event, DistillationConfig, T0IngestConfig, T0IngestError};
+use aoc_storage::{MindStore, StorageError};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, path::Path, time::Duration};
+use thiserror::Error;
+
+pub use aoc_core::mind_contracts::{ConversationRole, ToolExecutionStatus, ToolResultEvent};
+
+/// `signal_source` on task signals from [`EventRecorder::record_task_signal`].
+pub const SDK_SIGNAL_SOURCE: &str = "agent_sdk";
+/// Agent registry `kind` for agents that record through a local store.
+pub const SDK_AGENT_KIND: &str = "sdk";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error)]
+pub enum RecorderError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+    #[error(transparent)]
+    Ingest(#[from] T0IngestError),
+    #[error("server rejected {event_id}: {reason}")]
+    Rejected { event_id: String, reason: String },
+    #[error("server request failed: {0}")]
+    Http(String),
+}
+
+impl ErrorCoded for RecorderError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Ingest(err) => err.error_code(),
+            Self::Rejected { .. } => ErrorCode::InvalidInput,
+            Self::Http(_) => ErrorCode::Remote,
+        }
+    }
+}
+
+/// One recorded event. `inserted` is false when the event id was already
+/// stored, so re-sending after a timeout is safe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recorded {
+    pub event_id: String,
+    pub inserted: bool,
+}
+
+enum Target {
+    Store(MindStore),
+    Server {
+        agent: ureq::Agent,
+        url: String,
+        token: Option<String>,
+    },
+}
+
+pub struct EventRecorder {
+    target: Target,
+    agent_id: String,
+    conversation_id: String,
+    attrs: BTreeMap<String, Value>,
+    ingest: T0IngestConfig,
+    /// Unique per recorder so event ids never collide across restarts.
+    id_prefix: String,
+    sequence: u64,
+}
+
+impl EventRecorder {
+    pub fn open_store(
+        path: impl AsRef<Path>,
+        agent_id: impl Into<String>,
+        conversation_id: impl Into<String>,
+    ) -> Result<Self, RecorderError> {
+        Self::with_store(MindStore::open(path.as_ref())?, agent_id, conversation_id)
+    }
+
+    /// Registers the agent as `sdk` unless the registry already knows it.
+    pub fn with_store(
+        store: MindStore,
+        agent_id: impl Into<String>,
+        conversation_id: impl Into<String>,
+    ) -> Result<Self, RecorderError> {
+        let recorder = Self::new(
+            Target::Store(store),
+            agent_id.into(),
+            conversation_id.into(),
+        );
+        if let Target::Store(store) = &recorder.target {
+            store.ensure_agent(&recorder.agent_id, SDK_AGENT_KIND, Utc::now())?;
+        }
+        Ok(recorder)
+    }
+
+    /// Records through an `aoc serve` instance; `token` is its write token.
+    pub fn remote(
+        base_url: &str,
+        token: Option<String>,
+        agent_id: impl Into<String>,
+        conversation_id: impl Into<String>,
+    ) -> Self {
+        let target = Target::Server {
+            agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+            url: format!("{}/v1/raw-events", base_url.trim_end_matches('/')),
+            token,
+        };
+        Self::new(target, agent_id.into(), conversation_id.into())
+    }
+
+    fn new(target: Target, agent_id: String, conversation_id: String) -> Self {
+        let distillation = DistillationConfig::default();
+        let started = Utc::now();
+        let mut recorder = Self {
+            target,
+            id_prefix: format!(
+                "sdk:{conversation_id}:{}",
+                started.timestamp_nanos_opt().unwrap_or_default()
+            ),
+            agent_id: normalize_agent_id(&agent_id),
+            conversation_id: conversation_id.clone(),
+            attrs: BTreeMap::new(),
+            ingest: T0IngestConfig {
+                policy: T0CompactionPolicy::default(),
+                t1_target_tokens: distillation.t1_target_tokens,
+                t1_hard_cap_tokens: distillation.t1_hard_cap_tokens,
+            },
+            sequence: 0,
+        };
+        recorder.attrs = canonical_lineage_attrs(&ConversationLineageMetadata {
+            session_id: conversation_id.clone(),
+            parent_conversation_id: None,
+            root_conversation_id: conversation_id,
+        });
+        recorder
+    }
+
+    /// Places the conversation in a session tree; the default is a root
+    /// conversation that is its own session.
+    pub fn with_lineage(mut self, lineage: ConversationLineageMetadata) -> Self {
+        self.attrs.extend(canonical_lineage_attrs(&lineage));
+        self
+    }
+
+    /// Adds an attribute stored on every later event.
+    pub fn with_attr(mut self, key: impl Into<String>, value: Value) -> Self {
+        self.attrs.insert(key.into(), value);
+        self
+    }
+
+    pub fn conversation_id(&self) -> &str {
+        &self.conversation_id
+    }
+
+    pub fn record_message(
+        &mut self,
+        role: ConversationRole,
+        text: impl Into<String>,
+    ) -> Result<Recorded, RecorderError> {
+        self.record(RawEventBody::Message(MessageEvent {
+            role,
+            text: text.into(),
+        }))
+    }
+
+    /// Output is secret-scrubbed on ingest; use [`Self::record`] with a
+    /// full [`ToolResultEvent`] to also set an exit code.
+    pub fn record_tool_result(
+        &mut self,
+        tool_name: impl Into<String>,
+        status: ToolExecutionStatus,
+        output: Option<&str>,
+        latency_ms: Option<u64>,
+    ) -> Result<Recorded, RecorderError> {
+        self.record(RawEventBody::ToolResult(ToolResultEvent {
+            tool_name: tool_name.into(),
+            status,
+            latency_ms,
+            exit_code: None,
+            output: output.map(str::to_string),
+            redacted: false,
+            touched_paths: Vec::new(),
+            cwd: None,
+            output_digest: None,
+            retry_count: 0,
+        }))
+    }
+
+    pub fn record_task_signal(
+        &mut self,
+        active_tag: Option<&str>,
+        task_ids: &[&str],
+        lifecycle: Option<&str>,
+    ) -> Result<Recorded, RecorderError> {
+        self.record(RawEventBody::TaskSignal(TaskSignalEvent {
+            active_tag: active_tag.map(str::to_string),
+            task_ids: task_ids.iter().map(|id| id.to_string()).collect(),
+            lifecycle: lifecycle.map(str::to_string),
+            signal_source: Some(SDK_SIGNAL_SOURCE.to_string()),
+        }))
+    }
+
+    pub fn record(&mut self, body: RawEventBody) -> Result<Recorded, RecorderError> {
+        self.record_at(Utc::now(), body)
+    }
+
+    /// Records with an explicit timestamp, e.g. when replaying a buffer.
+    pub fn record_at(
+        &mut self,
+        ts: DateTime<Utc>,
+        body: RawEventBody,
+    ) -> Result<Recorded, RecorderError> {
+        self.sequence += 1;
+        let raw = RawEvent {
+            event_id: format!("{}:{}", self.id_prefix, self.sequence),
+            conversation_id: self.conversation_id.clone(),
+            agent_id: self.agent_id.clone(),
+            ts,
+            body,
+            attrs: self.attrs.clone(),
+        };
+        let inserted = match &self.target {
+            Target::Store(store) => ingest_raw_event(store, &raw, &self.ingest)?.inserted_raw,
+            Target::Server { agent, url, token } => post_event(agent, url, token.as_deref(), &raw)?,
+        };
+        Ok(Recorded {
+            event_id: raw.event_id,
+            inserted,
+        })
+    }
+}
+
+fn post_event(
+    agent: &ureq::Agent,
+    url: &str,
+    token: Option<&str>,
+    raw: &RawEvent,
+) -> Result<bool, RecorderError> {
+    let mut request = agent.post(url).set("Content-Type", "application/json");
+    if let Some(token) = token {
+        request = request.set("Authorization", &format!("Bearer {token}"));
+    }
+    let body = json!({ "events": [raw] }).to_string();
+    let summary = match request.send_string(&body) {
+        Ok(response) => response
+            .into_string()
+            .map_err(|err| RecorderError::Http(err.to_string()))?,
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            let message = serde_json::from_str::<Value>(&body)
+                .ok()
+                .and_then(|body| body["error"].as_str().map(str::to_string))
+                .unwrap_or(body);
+            return Err(RecorderError::Http(format!("HTTP {status}: {message}")));
+        }
+        Err(err) => return Err(RecorderError::Http(err.to_string())),
+    };
+    let summary: Value =
+        serde_json::from_str(&summary).map_err(|err| RecorderError::Http(err.to_string()))?;
+    if summary["rejected"].as_u64().unwrap_or(0) > 0 {
+        let reason = summary["errors"][0]
+            .as_str()
+            .unwrap_or("rejected")
+            .trim_start_matches(&format!("{}: ", raw.event_id))
+            .to_string();
+        return Err(RecorderError::Rejected {
+            event_id: raw.event_id.clone(),
+            reason,
+        });
+    }
+    Ok(summary["inserted"].as_u64().unwrap_or(0) > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::mind_contracts::parse_conversation_lineage_metadata;
+
+    #[test]
+    fn records_messages_tools_and_task_signals_straight_into_the_store() {
+        let store = MindStore::open_in_memory().expect("store");
+        let mut recorder = EventRecorder::with_store(store, "SDK Agent", "conv-sdk")
+            .expect("recorder")
+            .with_attr("harness", json!("unit-test"));
+
+        let first = recorder
+            .record_message(ConversationRole::User, "fix the flaky parser test")
+            .expect("message");
+        assert!(first.inserted);
+        recorder
+            .record_tool_result(
+                "cargo test",
+                ToolExecutionStatus::Failure,
+                Some("1 failed"),
+                Some(840),
+            )
+            .expect("tool result");
+        recorder
+            .record_task_signal(Some("mind"), &["149"], Some("in-progress"))
+            .expect("task signal");
+
+        let Target::Store(store) = &recorder.target else {
+            unreachable!("store recorder");
+        };
+        assert_eq!(
+            store
+                .agent("sdk-agent")
+                .expect("agent")
+                .map(|agent| agent.kind),
+            Some(SDK_AGENT_KIND.to_string())
+        );
+        let raw = store
+            .raw_events_for_conversation("conv-sdk")
+            .expect("raw events");
+        assert_eq!(raw.len(), 3);
+        assert!(raw.iter().all(|event| event.agent_id == "sdk-agent"));
+        assert_ne!(raw[0].event_id, raw[1].event_id);
+        assert_eq!(raw[0].attrs["harness"], "unit-test");
+        let lineage = parse_conversation_lineage_metadata(&raw[0].attrs, "conv-sdk", "sdk-agent")
+            .expect("lineage")
+            .expect("explicit lineage");
+        assert_eq!(lineage.root_conversation_id, "conv-sdk");
+        let RawEventBody::TaskSignal(signal) = &raw[2].body else {
+            panic!("expected task signal, got {:?}", raw[2].body);
+        };
+        assert_eq!(signal.signal_source.as_deref(), Some(SDK_SIGNAL_SOURCE));
+
+        let t0 = store.t0_events_for_conversation("conv-sdk").expect("t0");
+        assert_eq!(t0.len(), 2, "message and tool result compact to T0");
+    }
+}
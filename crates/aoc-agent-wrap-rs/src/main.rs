@@ -16,8 +16,8 @@ use aoc_core::{
     },
     mind_contracts::{
         build_compaction_t0_slice, canonical_lineage_attrs, text_contains_unredacted_secret,
-        ConversationLineageMetadata, ConversationRole, MessageEvent, RawEvent, RawEventBody,
-        T0CompactionPolicy, ToolExecutionStatus, ToolResultEvent,
+        tool_output_digest, ConversationLineageMetadata, ConversationRole, MessageEvent, RawEvent,
+        RawEventBody, T0CompactionPolicy, ToolExecutionStatus, ToolResultEvent,
     },
     mind_observer_feed::{
         MindInjectionPayload, MindInjectionTriggerKind, MindObserverFeedEvent,
@@ -1685,6 +1685,7 @@ fn mind_observer_event(
         latency_ms: None,
         reason,
         failure_kind: None,
+        error_code: None,
         enqueued_at: Some(now.clone()),
         started_at: None,
         completed_at: None,
@@ -2213,6 +2214,12 @@ enum MindIngestBody {
         output: Option<String>,
         #[serde(default)]
         redacted: Option<bool>,
+        #[serde(default)]
+        touched_paths: Vec<String>,
+        #[serde(default)]
+        cwd: Option<String>,
+        #[serde(default)]
+        retry_count: u32,
     },
 }
 
@@ -2300,6 +2307,7 @@ impl MindRuntime {
             t3_lock_path: resolve_t3_lock_path(cfg),
             debounce_run_ms: MIND_DEBOUNCE_RUN_MS,
             t3_max_attempts: MIND_T3_MAX_ATTEMPTS,
+            clock: aoc_storage::system_clock(),
         })?;
         let insight_detached = if detached_worker_boot {
             DetachedInsightRuntime::new_without_recovery(&cfg.project_root, canonical_path.clone())
@@ -2360,6 +2368,9 @@ impl MindRuntime {
                 exit_code,
                 output,
                 redacted,
+                touched_paths,
+                cwd,
+                retry_count,
             } => RawEventBody::ToolResult(ToolResultEvent {
                 tool_name,
                 status: ToolExecutionStatus::from(!is_error),
@@ -2367,8 +2378,12 @@ impl MindRuntime {
                 exit_code,
                 // Never persist tool result output into Mind. Mind keeps tool-call
                 // metadata only; transcript text is limited to user/assistant messages.
+                output_digest: output.as_deref().map(tool_output_digest),
                 output: None,
                 redacted: redacted.unwrap_or(false) || output.is_some(),
+                touched_paths,
+                cwd,
+                retry_count,
             }),
         };
 
@@ -2940,8 +2955,10 @@ impl MindRuntime {
     }
 
     fn insight_retrieve(&mut self, request: InsightRetrievalRequest) -> InsightRetrievalResult {
-        let result =
-            compile_insight_retrieval(&self.core.project_root().to_string_lossy(), request);
+        let project_root = self.core.project_root().to_string_lossy().to_string();
+        let result = compile_insight_retrieval(&project_root, request);
+        let _ =
+            aoc_mind::record_retrieval_metrics(self.core.store(), &project_root, &result.metrics);
         self.insight_health.last_tick_ms = Some(Utc::now().timestamp_millis());
         if result.fallback_used {
             self.insight_health.last_error = Some("insight retrieval fallback".to_string());
@@ -4641,6 +4658,7 @@ fn build_pulse_command_response(
                         latency_ms: None,
                         reason: Some("t0 updated".to_string()),
                         failure_kind: None,
+                        error_code: None,
                         enqueued_at: Some(Utc::now().to_rfc3339()),
                         started_at: None,
                         completed_at: None,
@@ -11465,6 +11483,7 @@ mod tests {
                 fallback_reason: None,
                 failure_kind: None,
                 created_at: now + chrono::Duration::seconds(4),
+                redactions: Vec::new(),
             })
             .expect("upsert provenance");
         store
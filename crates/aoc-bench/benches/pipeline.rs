@@ -0,0 +1,103 @@
+//! Criterion suite over the synthetic corpora.
+//!
+//! Runs the 10k corpus by default; set `AOC_BENCH_LARGE=1` to add 100k.
+//! Larger corpora are single-pass only: `aoc bench --events 1m`.
+//!
+//! `conversation_reads` always runs against a 100k-artifact store; each
+//! iteration is one conversation's feed reads and should stay well under a
+//! millisecond.
+
+use aoc_bench::{
+    corpus_conversation_ids, distill_conversations, ingest_sessions, read_conversation_feeds,
+    retrieve_artifacts, route_conversations, seed_conversation_artifacts, SyntheticCorpus,
+};
+use aoc_mind::DistillationConfig;
+use aoc_segment_routing::{SegmentRouter, SegmentRoutingConfig};
+use aoc_storage::MindStore;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use std::path::PathBuf;
+
+fn corpus_sizes() -> Vec<usize> {
+    let mut sizes = vec![10_000];
+    if std::env::var("AOC_BENCH_LARGE").is_ok_and(|value| value == "1") {
+        sizes.push(100_000);
+    }
+    sizes
+}
+
+fn corpus_dir(events: usize) -> PathBuf {
+    std::env::temp_dir().join(format!("aoc-bench-criterion-{events}"))
+}
+
+fn pipeline(c: &mut Criterion) {
+    let distill_config = DistillationConfig::default();
+    let router = SegmentRouter::new(SegmentRoutingConfig::default());
+
+    for events in corpus_sizes() {
+        let corpus = SyntheticCorpus::new(events);
+        let sessions = corpus
+            .write_sessions(&corpus_dir(events))
+            .expect("write corpus");
+        let conversation_ids = corpus_conversation_ids(&sessions);
+        let mut group = c.benchmark_group(format!("pipeline/{events}"));
+        group.sample_size(10);
+
+        group.throughput(Throughput::Elements(events as u64));
+        group.bench_function("ingest", |b| {
+            b.iter_batched(
+                || MindStore::open_in_memory().expect("store"),
+                |store| ingest_sessions(&store, &sessions).expect("ingest"),
+                BatchSize::PerIteration,
+            )
+        });
+
+        let store = MindStore::open_in_memory().expect("store");
+        ingest_sessions(&store, &sessions).expect("ingest");
+        group.throughput(Throughput::Elements(conversation_ids.len() as u64));
+        group.bench_function("distill", |b| {
+            b.iter(|| distill_conversations(&store, &conversation_ids, &distill_config))
+        });
+        group.bench_function("route", |b| {
+            b.iter(|| route_conversations(&store, &conversation_ids, &router))
+        });
+
+        group.throughput(Throughput::Elements(1));
+        group.bench_function("retrieve", |b| {
+            b.iter(|| retrieve_artifacts(&store, 1).expect("retrieve"))
+        });
+        group.finish();
+    }
+}
+
+/// Artifacts in the store behind `conversation_reads`.
+const READ_BENCH_ARTIFACTS: usize = 100_000;
+
+fn conversation_reads(c: &mut Criterion) {
+    let store = MindStore::open_in_memory().expect("store");
+    let conversation_ids =
+        seed_conversation_artifacts(&store, READ_BENCH_ARTIFACTS).expect("seed artifacts");
+    let mut group = c.benchmark_group(format!("conversation_reads/{READ_BENCH_ARTIFACTS}"));
+    group.throughput(Throughput::Elements(1));
+
+    let mut next = conversation_ids.iter().cycle();
+    group.bench_function("feed", |b| {
+        b.iter(|| {
+            let conversation_id = next.next().expect("conversation");
+            read_conversation_feeds(&store, std::slice::from_ref(conversation_id)).expect("read")
+        })
+    });
+    let mut next = conversation_ids.iter().cycle();
+    group.bench_function("artifacts_for_conversation", |b| {
+        b.iter(|| {
+            let conversation_id = next.next().expect("conversation");
+            store
+                .artifacts_for_conversation(conversation_id)
+                .expect("artifacts")
+                .len()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, pipeline, conversation_reads);
+criterion_main!(benches);
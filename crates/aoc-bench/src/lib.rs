@@ -0,0 +1,470 @@
+//! Synthetic corpora and timed workloads for the Mind storage hot paths.
+//!
+//! The same workloads back the criterion suite in `benches/pipeline.rs` and
+//! `aoc bench`: the suite gives statistically sound numbers for the small
+//! corpora, the command gives single-pass wall-clock numbers for any size
+//! (including 1M events) on a real on-disk store.
+
+use aoc_core::{ErrorCode, ErrorCoded};
+use aoc_mind::{evaluate_t1_token_threshold, DeterministicDistiller, DistillationConfig};
+use aoc_pi_adapter::{IngestionOptions, PiAdapterError, PiSessionIngestor};
+use aoc_segment_routing::{RoutingError, SegmentRouter, SegmentRoutingConfig};
+use aoc_storage::{ArtifactQuery, ConversationArtifactFilter, MindStore, StorageError};
+use chrono::{Duration as ChronoDuration, TimeZone, Utc};
+use serde::Serialize;
+use std::{
+    fs,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+pub const BENCH_AGENT_ID: &str = "aoc-bench";
+
+/// Events per synthetic session file; also the conversation size.
+pub const DEFAULT_EVENTS_PER_CONVERSATION: usize = 200;
+
+/// Queries issued by the retrieval workload, one per pass.
+const RETRIEVAL_TERMS: &[&str] = &["parser", "migration", "component", "latency", "rollback"];
+
+const TOPICS: &[&str] = &[
+    "the parser rewrite for the api layer",
+    "a db migration that needs a rollback path",
+    "the ui component library and its css tokens",
+    "observation latency in the taskmaster sync",
+    "flaky integration tests on the release branch",
+];
+
+#[derive(Debug, Error)]
+pub enum BenchError {
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
+    #[error("ingest error: {0}")]
+    Ingest(#[from] PiAdapterError),
+    #[error("distillation error: {0}")]
+    Distill(String),
+    #[error("routing error: {0}")]
+    Routing(#[from] RoutingError),
+    #[error("invalid corpus size '{0}' (expected e.g. 10k, 100k, 1m)")]
+    InvalidSize(String),
+}
+
+impl ErrorCoded for BenchError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Io(_) => ErrorCode::Io,
+            Self::Storage(err) => err.error_code(),
+            Self::Ingest(err) => err.error_code(),
+            Self::Distill(_) => ErrorCode::Internal,
+            Self::Routing(err) => err.error_code(),
+            Self::InvalidSize(_) => ErrorCode::BadRequest,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BenchStage {
+    Ingest,
+    Distill,
+    Route,
+    Retrieve,
+}
+
+impl BenchStage {
+    /// Pipeline order; each stage reads what the previous one wrote.
+    pub const ALL: [BenchStage; 4] = [
+        BenchStage::Ingest,
+        BenchStage::Distill,
+        BenchStage::Route,
+        BenchStage::Retrieve,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BenchStage::Ingest => "ingest",
+            BenchStage::Distill => "distill",
+            BenchStage::Route => "route",
+            BenchStage::Retrieve => "retrieve",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|stage| stage.as_str().eq_ignore_ascii_case(value.trim()))
+    }
+}
+
+/// Shape of a generated corpus: `events` Pi messages split into sessions of
+/// `events_per_conversation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SyntheticCorpus {
+    pub events: usize,
+    pub events_per_conversation: usize,
+}
+
+impl SyntheticCorpus {
+    pub fn new(events: usize) -> Self {
+        Self {
+            events,
+            events_per_conversation: DEFAULT_EVENTS_PER_CONVERSATION,
+        }
+    }
+
+    /// Parses `10k`, `100k`, `1m`, or a plain count.
+    pub fn parse_size(value: &str) -> Result<Self, BenchError> {
+        let raw = value.trim().to_ascii_lowercase();
+        let (digits, scale) = match raw.chars().last() {
+            Some('k') => (&raw[..raw.len() - 1], 1_000),
+            Some('m') => (&raw[..raw.len() - 1], 1_000_000),
+            _ => (raw.as_str(), 1),
+        };
+        digits
+            .parse::<usize>()
+            .ok()
+            .filter(|count| *count > 0)
+            .map(|count| Self::new(count * scale))
+            .ok_or_else(|| BenchError::InvalidSize(value.to_string()))
+    }
+
+    pub fn conversations(&self) -> usize {
+        self.events.div_ceil(self.events_per_conversation.max(1))
+    }
+
+    /// Writes one Pi session file per conversation under `dir`. Messages cycle
+    /// through a few topics so routing and retrieval have keywords to match,
+    /// and every tenth message mentions a task.
+    pub fn write_sessions(&self, dir: &Path) -> Result<Vec<PathBuf>, BenchError> {
+        fs::create_dir_all(dir)?;
+        let base = Utc
+            .with_ymd_and_hms(2026, 1, 5, 9, 0, 0)
+            .single()
+            .expect("valid base timestamp");
+        let per_conversation = self.events_per_conversation.max(1);
+        let mut paths = Vec::with_capacity(self.conversations());
+        let mut remaining = self.events;
+        for index in 0..self.conversations() {
+            let events = remaining.min(per_conversation);
+            remaining -= events;
+            let session_id = format!("bench-{index:06}");
+            let path = dir.join(format!("{session_id}.jsonl"));
+            let mut out = BufWriter::new(fs::File::create(&path)?);
+            let start = base + ChronoDuration::minutes(index as i64 * 30);
+            writeln!(
+                out,
+                "{}",
+                serde_json::json!({
+                    "type": "session",
+                    "version": 3,
+                    "id": session_id,
+                    "timestamp": start.to_rfc3339(),
+                    "cwd": "/bench/project",
+                })
+            )?;
+            let mut parent: Option<String> = None;
+            for event in 0..events {
+                let id = format!("{session_id}-m{event}");
+                let ts = start + ChronoDuration::seconds(event as i64 + 1);
+                let topic = TOPICS[(index + event / 8) % TOPICS.len()];
+                let text = if event % 10 == 0 {
+                    format!("Working on task #{} about {topic}.", 1 + index % 40)
+                } else {
+                    format!("Step {event} on {topic}: checked the logs and adjusted the config.")
+                };
+                let message = if event % 2 == 0 {
+                    serde_json::json!({ "role": "user", "content": text })
+                } else {
+                    serde_json::json!({
+                        "role": "assistant",
+                        "content": [{ "type": "text", "text": text }],
+                    })
+                };
+                writeln!(
+                    out,
+                    "{}",
+                    serde_json::json!({
+                        "type": "message",
+                        "id": id,
+                        "parentId": parent,
+                        "timestamp": ts.to_rfc3339(),
+                        "message": message,
+                    })
+                )?;
+                parent = Some(id);
+            }
+            out.flush()?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+/// Timing for one stage over the whole corpus.
+#[derive(Debug, Clone, Serialize)]
+pub struct StageTiming {
+    pub stage: BenchStage,
+    /// Units of work done: events ingested, conversations distilled or
+    /// routed, or queries answered.
+    pub items: usize,
+    pub elapsed_ms: u128,
+    pub items_per_sec: f64,
+}
+
+impl StageTiming {
+    fn new(stage: BenchStage, items: usize, elapsed: Duration) -> Self {
+        let secs = elapsed.as_secs_f64();
+        Self {
+            stage,
+            items,
+            elapsed_ms: elapsed.as_millis(),
+            items_per_sec: if secs > 0.0 { items as f64 / secs } else { 0.0 },
+        }
+    }
+}
+
+/// Ingests every session file; returns raw events processed.
+pub fn ingest_sessions(store: &MindStore, sessions: &[PathBuf]) -> Result<usize, BenchError> {
+    let ingestor = PiSessionIngestor::new(IngestionOptions::default());
+    let mut events = 0;
+    for path in sessions {
+        events += ingestor
+            .ingest_session_file(store, BENCH_AGENT_ID, path)?
+            .processed_raw_events;
+    }
+    Ok(events)
+}
+
+/// Threshold check plus deterministic T1/T2 planning for every conversation;
+/// returns T1 batches planned.
+pub fn distill_conversations(
+    store: &MindStore,
+    conversation_ids: &[String],
+    config: &DistillationConfig,
+) -> Result<usize, BenchError> {
+    let distiller = DeterministicDistiller::new(config.clone());
+    let mut batches = 0;
+    for conversation_id in conversation_ids {
+        evaluate_t1_token_threshold(
+            store,
+            conversation_id,
+            config.t1_target_tokens,
+            config.t1_hard_cap_tokens,
+        )
+        .map_err(|err| BenchError::Distill(err.to_string()))?;
+        batches += distiller
+            .distill_conversation(store, conversation_id)
+            .map_err(|err| BenchError::Distill(err.to_string()))?
+            .t1_batches_planned;
+    }
+    Ok(batches)
+}
+
+/// Routes every conversation; returns routes written.
+pub fn route_conversations(
+    store: &MindStore,
+    conversation_ids: &[String],
+    router: &SegmentRouter,
+) -> Result<usize, BenchError> {
+    let mut routes = 0;
+    for conversation_id in conversation_ids {
+        routes += router
+            .route_conversation(store, conversation_id)?
+            .routes_written;
+    }
+    Ok(routes)
+}
+
+/// Runs `passes` text queries over the artifact index; returns hits.
+pub fn retrieve_artifacts(store: &MindStore, passes: usize) -> Result<usize, BenchError> {
+    let mut hits = 0;
+    for pass in 0..passes {
+        let query = ArtifactQuery {
+            text: Some(RETRIEVAL_TERMS[pass % RETRIEVAL_TERMS.len()].to_string()),
+            limit: 20,
+            ..ArtifactQuery::default()
+        };
+        hits += store.query_artifacts(&query)?.artifacts.len();
+    }
+    Ok(hits)
+}
+
+/// Artifacts per conversation written by [`seed_conversation_artifacts`];
+/// every fourth one is a T2 reflection.
+pub const ARTIFACTS_PER_CONVERSATION: usize = 50;
+
+/// Writes `artifacts` T1/T2 rows spread over conversations of
+/// [`ARTIFACTS_PER_CONVERSATION`]; returns the conversation ids. Seeds the per-conversation read workload without
+/// running distillation.
+pub fn seed_conversation_artifacts(
+    store: &MindStore,
+    artifacts: usize,
+) -> Result<Vec<String>, BenchError> {
+    let base = Utc
+        .with_ymd_and_hms(2026, 1, 5, 9, 0, 0)
+        .single()
+        .expect("valid base timestamp");
+    let conversations = artifacts.div_ceil(ARTIFACTS_PER_CONVERSATION);
+    let conversation_ids = (0..conversations)
+        .map(|index| format!("bench-artifacts-{index:06}"))
+        .collect::<Vec<_>>();
+    for index in 0..artifacts {
+        let conversation_id = &conversation_ids[index / ARTIFACTS_PER_CONVERSATION];
+        let ts = base + ChronoDuration::seconds(index as i64);
+        let topic = TOPICS[index % TOPICS.len()];
+        if index % 4 == 3 {
+            store.insert_reflection(
+                &format!("ref:{index:07}"),
+                conversation_id,
+                ts,
+                &format!("Reflection on {topic}."),
+                &[],
+            )?;
+        } else {
+            store.insert_observation(
+                &format!("obs:{index:07}"),
+                conversation_id,
+                ts,
+                &format!("Observed progress on {topic}."),
+                &[],
+            )?;
+        }
+    }
+    Ok(conversation_ids)
+}
+
+/// The observer feed's per-conversation reads: the summary, the id list,
+/// and the newest T1 artifact. Returns artifacts seen.
+pub fn read_conversation_feeds(
+    store: &MindStore,
+    conversation_ids: &[String],
+) -> Result<usize, BenchError> {
+    let latest_t1 = ConversationArtifactFilter {
+        kind: Some("t1".to_string()),
+        newest_first: true,
+        limit: Some(1),
+    };
+    let mut seen = 0;
+    for conversation_id in conversation_ids {
+        let summary = store.conversation_feed_summary(conversation_id)?;
+        seen += (summary.t1_active + summary.t2_active) as usize;
+        seen += store.artifact_ids_for_conversation(conversation_id)?.len();
+        seen += store
+            .conversation_artifacts(conversation_id, &latest_t1)?
+            .len();
+    }
+    Ok(seen)
+}
+
+/// Conversation ids the Pi adapter derives for the corpus sessions.
+pub fn corpus_conversation_ids(sessions: &[PathBuf]) -> Vec<String> {
+    sessions
+        .iter()
+        .filter_map(|path| path.file_stem())
+        .map(|stem| format!("pi:{}", stem.to_string_lossy()))
+        .collect()
+}
+
+/// Queries issued by [`run_pipeline_bench`]'s retrieval stage.
+pub const RETRIEVAL_PASSES: usize = 200;
+
+/// Generates the corpus under `work_dir`, then times each requested stage in
+/// pipeline order against a store at `work_dir/bench.sqlite`. Stages that were
+/// not requested still run (untimed) when a later stage needs their output.
+pub fn run_pipeline_bench(
+    corpus: SyntheticCorpus,
+    stages: &[BenchStage],
+    work_dir: &Path,
+) -> Result<Vec<StageTiming>, BenchError> {
+    let sessions = corpus.write_sessions(&work_dir.join("sessions"))?;
+    let store_path = work_dir.join("bench.sqlite");
+    if store_path.exists() {
+        fs::remove_file(&store_path)?;
+    }
+    let store = MindStore::open(&store_path)?;
+    let conversation_ids = corpus_conversation_ids(&sessions);
+    let distill_config = DistillationConfig::default();
+    let router = SegmentRouter::new(SegmentRoutingConfig::default());
+    let last = stages.iter().max().copied();
+
+    let mut timings = Vec::new();
+    for stage in BenchStage::ALL {
+        if Some(stage) > last {
+            break;
+        }
+        let started = Instant::now();
+        let items = match stage {
+            BenchStage::Ingest => ingest_sessions(&store, &sessions)?,
+            BenchStage::Distill => {
+                distill_conversations(&store, &conversation_ids, &distill_config)?;
+                conversation_ids.len()
+            }
+            BenchStage::Route => {
+                route_conversations(&store, &conversation_ids, &router)?;
+                conversation_ids.len()
+            }
+            BenchStage::Retrieve => {
+                retrieve_artifacts(&store, RETRIEVAL_PASSES)?;
+                RETRIEVAL_PASSES
+            }
+        };
+        if stages.contains(&stage) {
+            timings.push(StageTiming::new(stage, items, started.elapsed()));
+        }
+    }
+    Ok(timings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_corpus_runs_every_stage() {
+        let dir = std::env::temp_dir().join(format!("aoc-bench-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let corpus = SyntheticCorpus {
+            events: 50,
+            events_per_conversation: 20,
+        };
+        assert_eq!(corpus.conversations(), 3);
+
+        let timings = run_pipeline_bench(corpus, &[BenchStage::Ingest, BenchStage::Retrieve], &dir)
+            .expect("bench");
+        assert_eq!(
+            timings.iter().map(|t| t.stage).collect::<Vec<_>>(),
+            vec![BenchStage::Ingest, BenchStage::Retrieve]
+        );
+        assert_eq!(timings[0].items, 50);
+
+        let store = MindStore::open(dir.join("bench.sqlite")).expect("reopen");
+        assert!(retrieve_artifacts(&store, RETRIEVAL_TERMS.len()).expect("retrieve") > 0);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(SyntheticCorpus::parse_size("100k").unwrap().events, 100_000);
+        assert_eq!(SyntheticCorpus::parse_size("1M").unwrap().events, 1_000_000);
+        assert!(SyntheticCorpus::parse_size("0").is_err());
+        assert_eq!(BenchStage::parse("Route"), Some(BenchStage::Route));
+    }
+
+    #[test]
+    fn seeded_artifacts_feed_conversation_reads() {
+        let store = MindStore::open_in_memory().expect("store");
+        let conversation_ids = seed_conversation_artifacts(&store, 120).expect("seed");
+        assert_eq!(conversation_ids.len(), 3);
+
+        let summary = store
+            .conversation_feed_summary(&conversation_ids[2])
+            .expect("summary");
+        assert_eq!(summary.t1_active + summary.t2_active, 20);
+        assert_eq!(summary.latest_t2_id.as_deref(), Some("ref:0000119"));
+        // Summary counts, id list, and one newest T1 per conversation.
+        assert_eq!(
+            read_conversation_feeds(&store, &conversation_ids).expect("read"),
+            120 * 2 + 3
+        );
+    }
+}
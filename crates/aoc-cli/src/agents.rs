@@ -0,0 +1,132 @@
+use anyhow::{Context, Result};
+use aoc_core::mind_contracts::normalize_agent_id;
+use aoc_storage::{AgentRecord, AgentRollup};
+use chrono::Utc;
+use clap::{Args, Subcommand};
+use serde_json::{json, Value};
+
+use crate::{
+    mind_store::StoreArgs,
+    output::{json_mode, print_change, print_json},
+    query::parse_time_arg,
+};
+
+#[derive(Subcommand, Debug)]
+pub enum AgentsCommand {
+    /// Compare agents by activity, artifacts, fallback rate, and tasks worked
+    List(AgentListArgs),
+    /// Register an agent or relabel its kind, model, and owner
+    Register(AgentRegisterArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct AgentListArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Only activity since a relative window (`7d`) or RFC3339 timestamp.
+    #[arg(long)]
+    pub since: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct AgentRegisterArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Normalized before storing, the same way adapters normalize it.
+    pub agent_id: String,
+    /// Harness driving the agent, e.g. `pi`, `opencode`, or `sdk`.
+    #[arg(long)]
+    pub kind: String,
+    #[arg(long)]
+    pub model: Option<String>,
+    #[arg(long)]
+    pub owner: Option<String>,
+}
+
+pub fn handle_agents_command(command: AgentsCommand) -> Result<()> {
+    match command {
+        AgentsCommand::List(args) => handle_list(args),
+        AgentsCommand::Register(args) => handle_register(args),
+    }
+}
+
+fn handle_list(args: AgentListArgs) -> Result<()> {
+    let since = args
+        .since
+        .as_deref()
+        .map(|value| parse_time_arg("--since", value, Utc::now()))
+        .transpose()?;
+    let (store, _) = args.store.open()?;
+    let rollups = store.agent_rollups(since).context("load agent rollups")?;
+    if json_mode() {
+        return print_json(&rollups.iter().map(rollup_json).collect::<Vec<_>>());
+    }
+    if rollups.is_empty() {
+        println!("No agents.");
+        return Ok(());
+    }
+    println!(
+        "{:<24} {:<10} {:>6} {:>8} {:>9} {:>9} {:>6}  last seen",
+        "agent", "kind", "convs", "events", "artifacts", "fallback", "tasks"
+    );
+    for rollup in &rollups {
+        println!(
+            "{:<24} {:<10} {:>6} {:>8} {:>9} {:>9} {:>6}  {}",
+            rollup.agent_id,
+            rollup.kind.as_deref().unwrap_or("-"),
+            rollup.conversations,
+            rollup.raw_events,
+            rollup.artifacts,
+            rollup.fallback_rate_bps().map_or_else(
+                || "-".to_string(),
+                |bps| format!("{:.1}%", bps as f64 / 100.0)
+            ),
+            rollup.tasks_worked,
+            rollup.last_seen.map_or_else(
+                || "-".to_string(),
+                |ts| ts.format("%Y-%m-%d %H:%M").to_string()
+            ),
+        );
+    }
+    Ok(())
+}
+
+fn handle_register(args: AgentRegisterArgs) -> Result<()> {
+    let (store, _) = args.store.open()?;
+    let agent_id = normalize_agent_id(&args.agent_id);
+    let now = Utc::now();
+    let registered_at = store
+        .agent(&agent_id)?
+        .map_or(now, |existing| existing.registered_at);
+    let record = AgentRecord {
+        agent_id,
+        kind: args.kind.trim().to_string(),
+        model: args.model,
+        owner: args.owner,
+        registered_at,
+        updated_at: now,
+    };
+    store.upsert_agent(&record).context("register agent")?;
+    print_change(
+        "agents.register",
+        format!("Registered {} ({})", record.agent_id, record.kind),
+        serde_json::to_value(&record)?,
+    )
+}
+
+fn rollup_json(rollup: &AgentRollup) -> Value {
+    json!({
+        "agent_id": rollup.agent_id,
+        "kind": rollup.kind,
+        "model": rollup.model,
+        "owner": rollup.owner,
+        "conversations": rollup.conversations,
+        "raw_events": rollup.raw_events,
+        "last_seen": rollup.last_seen,
+        "artifacts": rollup.artifacts,
+        "semantic_artifacts": rollup.semantic_artifacts,
+        "fallback_artifacts": rollup.fallback_artifacts,
+        "fallback_rate_bps": rollup.fallback_rate_bps(),
+        "tasks_worked": rollup.tasks_worked,
+    })
+}
@@ -0,0 +1,211 @@
+use anyhow::{anyhow, Context, Result};
+use aoc_storage::{AuditChainHead, AuditFinding};
+use chrono::Utc;
+use clap::{Args, Subcommand};
+use serde_json::json;
+
+use crate::{
+    mind_store::StoreArgs,
+    output::{json_mode, print_change, print_json, Severity, SeverityExit},
+};
+
+#[derive(Subcommand, Debug)]
+pub enum AuditCommand {
+    /// Start the tamper-evident hash chain over artifacts and canon
+    /// revisions, sealing what the store already holds
+    Enable(AuditStoreArgs),
+    /// Print the chain head; record it elsewhere to anchor later verifies
+    Head(AuditStoreArgs),
+    /// Re-derive the chain and compare it with the stored rows
+    Verify(AuditVerifyArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct AuditStoreArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct AuditVerifyArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// A head printed by `aoc audit head` earlier, as `<seq>:<root>`. Catches
+    /// a chain rebuilt from scratch after rows were edited.
+    #[arg(long)]
+    pub anchor: Option<String>,
+}
+
+pub fn handle_audit_command(command: AuditCommand) -> Result<()> {
+    match command {
+        AuditCommand::Enable(args) => handle_enable(args),
+        AuditCommand::Head(args) => handle_head(args),
+        AuditCommand::Verify(args) => handle_verify(args),
+    }
+}
+
+fn handle_enable(args: AuditStoreArgs) -> Result<()> {
+    let (store, store_path) = args.store.open_writer()?;
+    let already = store.audit_chain_enabled()?;
+    let sealed = store
+        .enable_audit_chain(Utc::now())
+        .context("enable audit chain")?;
+    let head = store.audit_chain_head()?;
+    let message = if already {
+        format!("Audit chain is already enabled on {}", store_path.display())
+    } else {
+        format!(
+            "Enabled the audit chain on {}; sealed {sealed} existing row(s)",
+            store_path.display()
+        )
+    };
+    print_change(
+        "audit.enable",
+        message,
+        json!({
+            "store_path": store_path,
+            "already_enabled": already,
+            "sealed": sealed,
+            "head": head,
+        }),
+    )
+}
+
+fn handle_head(args: AuditStoreArgs) -> Result<()> {
+    let (store, store_path) = args.store.open()?;
+    let enabled = store.audit_chain_enabled()?;
+    let head = store.audit_chain_head()?;
+    if json_mode() {
+        return print_json(&json!({
+            "store_path": store_path,
+            "enabled": enabled,
+            "head": head,
+        }));
+    }
+    match head {
+        Some(head) => println!("{}", anchor_label(&head)),
+        None if enabled => println!("audit chain is enabled but empty"),
+        None => println!("audit chain is off; run `aoc audit enable`"),
+    }
+    Ok(())
+}
+
+fn handle_verify(args: AuditVerifyArgs) -> Result<()> {
+    let anchor = args.anchor.as_deref().map(parse_anchor).transpose()?;
+    let (store, store_path) = args.store.open()?;
+    let report = store
+        .verify_audit_chain(anchor.as_ref())
+        .context("verify audit chain")?;
+    if json_mode() {
+        print_json(&json!({
+            "store_path": store_path,
+            "enabled": report.enabled,
+            "intact": report.intact(),
+            "entries": report.entries,
+            "head": report.head,
+            "findings": report.findings,
+        }))?;
+    } else {
+        println!(
+            "audit chain: {} entr{}, head {}",
+            report.entries,
+            if report.entries == 1 { "y" } else { "ies" },
+            report
+                .head
+                .as_ref()
+                .map(anchor_label)
+                .unwrap_or_else(|| "-".to_string())
+        );
+        if !report.enabled {
+            println!("audit chain is off; run `aoc audit enable`");
+        }
+        for finding in &report.findings {
+            println!("  {}", describe_finding(finding));
+        }
+        if report.intact() && report.enabled {
+            println!("intact");
+        }
+    }
+    if !report.intact() {
+        return Err(SeverityExit::new(
+            Severity::Error,
+            format!(
+                "audit chain has {} finding(s); the mind's history was modified",
+                report.findings.len()
+            ),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn parse_anchor(value: &str) -> Result<AuditChainHead> {
+    let (seq, root) = value
+        .split_once(':')
+        .ok_or_else(|| anyhow!("--anchor wants <seq>:<root>, got '{value}'"))?;
+    let seq = seq
+        .trim()
+        .parse()
+        .with_context(|| format!("--anchor sequence '{seq}' is not a number"))?;
+    Ok(AuditChainHead {
+        seq,
+        root: root.trim().to_string(),
+    })
+}
+
+fn anchor_label(head: &AuditChainHead) -> String {
+    format!("{}:{}", head.seq, head.root)
+}
+
+fn describe_finding(finding: &AuditFinding) -> String {
+    match finding {
+        AuditFinding::BrokenLink { seq, detail } => format!("entry {seq}: {detail}"),
+        AuditFinding::Modified {
+            entry_kind,
+            entry_id,
+            seq,
+        } => format!(
+            "{} {entry_id} changed since entry {seq}",
+            entry_kind.as_str()
+        ),
+        AuditFinding::Missing {
+            entry_kind,
+            entry_id,
+            seq,
+        } => format!(
+            "{} {entry_id} deleted without an entry (last at {seq})",
+            entry_kind.as_str()
+        ),
+        AuditFinding::Unchained {
+            entry_kind,
+            entry_id,
+        } => format!(
+            "{} {entry_id} was written outside the chain",
+            entry_kind.as_str()
+        ),
+        AuditFinding::AnchorMismatch {
+            seq,
+            expected_root,
+            actual_root,
+        } => format!(
+            "anchor {seq}:{expected_root} does not match the chain ({})",
+            actual_root.as_deref().unwrap_or("no such entry")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchors_round_trip_through_their_label() {
+        let head = AuditChainHead {
+            seq: 42,
+            root: "ab".repeat(32),
+        };
+        assert_eq!(parse_anchor(&anchor_label(&head)).expect("parse"), head);
+        assert!(parse_anchor("42").is_err());
+        assert!(parse_anchor("x:ab").is_err());
+    }
+}
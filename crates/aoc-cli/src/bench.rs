@@ -0,0 +1,74 @@
+use anyhow::{anyhow, Context, Result};
+use aoc_bench::{run_pipeline_bench, BenchStage, SyntheticCorpus};
+use clap::Args;
+use serde_json::json;
+use std::{fs, path::PathBuf};
+
+use crate::output::{json_mode, print_json};
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    /// Corpus size in events: 10k, 100k, 1m, or a plain count.
+    #[arg(long, default_value = "10k")]
+    pub events: String,
+    /// Stage to time (repeatable): ingest, distill, route, retrieve. Earlier
+    /// stages still run untimed when a later one needs their output.
+    #[arg(long = "stage", value_parser = parse_stage)]
+    pub stages: Vec<BenchStage>,
+    /// Directory for the generated sessions and store; a temp dir by default.
+    #[arg(long)]
+    pub work_dir: Option<PathBuf>,
+    /// Leave the generated corpus and store in place.
+    #[arg(long, default_value_t = false)]
+    pub keep: bool,
+}
+
+fn parse_stage(value: &str) -> Result<BenchStage, String> {
+    BenchStage::parse(value)
+        .ok_or_else(|| format!("unknown stage '{value}' (ingest, distill, route, retrieve)"))
+}
+
+pub fn handle_bench_command(args: BenchArgs) -> Result<()> {
+    let corpus = SyntheticCorpus::parse_size(&args.events).map_err(|err| anyhow!(err))?;
+    let stages = if args.stages.is_empty() {
+        BenchStage::ALL.to_vec()
+    } else {
+        args.stages.clone()
+    };
+    let work_dir = args
+        .work_dir
+        .clone()
+        .unwrap_or_else(|| std::env::temp_dir().join(format!("aoc-bench-{}", std::process::id())));
+    if !json_mode() {
+        println!(
+            "corpus: {} events in {} conversations\nwork dir: {}",
+            corpus.events,
+            corpus.conversations(),
+            work_dir.display()
+        );
+    }
+
+    let result = run_pipeline_bench(corpus, &stages, &work_dir);
+    if !args.keep {
+        let _ = fs::remove_dir_all(&work_dir);
+    }
+    let timings = result.context("run benchmark")?;
+
+    if json_mode() {
+        return print_json(&json!({ "corpus": corpus, "stages": timings }));
+    }
+    println!(
+        "{:<10} {:>10} {:>12} {:>14}",
+        "stage", "items", "elapsed ms", "items/sec"
+    );
+    for timing in &timings {
+        println!(
+            "{:<10} {:>10} {:>12} {:>14.1}",
+            timing.stage.as_str(),
+            timing.items,
+            timing.elapsed_ms,
+            timing.items_per_sec
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,353 @@
+use anyhow::{anyhow, bail, Context, Result};
+use aoc_storage::{CanonEntryRevision, CanonRevisionState, MindStore};
+use chrono::Utc;
+use clap::{Args, Subcommand};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use crate::{
+    mind_store::StoreArgs,
+    output::{json_mode, print_change, print_json},
+};
+
+#[derive(Subcommand, Debug)]
+pub enum CanonCommand {
+    /// Print active canon entries, optionally for one segment
+    Show(CanonShowArgs),
+    /// List canon revisions, newest first
+    History(CanonHistoryArgs),
+    /// Make an earlier revision (`<entry-id>#<revision>`) the active one
+    Promote(CanonPromoteArgs),
+    /// Re-activate the revision before an entry's current one
+    Revert(CanonRevertArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct CanonShowArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Segment (canon topic) to show.
+    pub segment: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct CanonHistoryArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Only this entry's revisions.
+    pub entry_id: Option<String>,
+    #[arg(long)]
+    pub segment: Option<String>,
+    #[arg(long, default_value_t = 50)]
+    pub limit: usize,
+}
+
+#[derive(Args, Debug)]
+pub struct CanonPromoteArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Revision key as printed by `aoc canon history`, e.g. `canon:abc#2`.
+    pub revision: String,
+    /// Apply without the confirmation prompt.
+    #[arg(long, short = 'y', default_value_t = false)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct CanonRevertArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    pub entry_id: String,
+    /// Apply without the confirmation prompt.
+    #[arg(long, short = 'y', default_value_t = false)]
+    pub yes: bool,
+}
+
+pub fn handle_canon_command(command: CanonCommand) -> Result<()> {
+    match command {
+        CanonCommand::Show(args) => handle_show(args),
+        CanonCommand::History(args) => handle_history(args),
+        CanonCommand::Promote(args) => handle_promote(args),
+        CanonCommand::Revert(args) => handle_revert(args),
+    }
+}
+
+fn handle_show(args: CanonShowArgs) -> Result<()> {
+    let (store, _) = args.store.open()?;
+    let entries = store
+        .active_canon_entries(args.segment.as_deref())
+        .context("load active canon")?;
+    if json_mode() {
+        return print_json(&entries.iter().map(revision_json).collect::<Vec<_>>());
+    }
+    if entries.is_empty() {
+        println!("No active canon.");
+    }
+    let mut topic = None;
+    for entry in &entries {
+        if topic != Some(entry.topic.as_deref()) {
+            topic = Some(entry.topic.as_deref());
+            println!("## {}", entry.topic.as_deref().unwrap_or("(no segment)"));
+        }
+        println!(
+            "- {} conf:{} fresh:{} refs:{}",
+            revision_key(entry),
+            entry.confidence_bps,
+            entry.freshness_score,
+            entry.evidence_refs.len()
+        );
+        for line in entry.summary.lines() {
+            println!("    {line}");
+        }
+    }
+    Ok(())
+}
+
+fn handle_history(args: CanonHistoryArgs) -> Result<()> {
+    let (store, _) = args.store.open()?;
+    let mut revisions = match args.entry_id.as_deref() {
+        Some(entry_id) => store.canon_entry_revisions(entry_id),
+        None => store.canon_revision_history(args.segment.as_deref(), args.limit),
+    }
+    .context("load canon history")?;
+    revisions.truncate(args.limit);
+    if json_mode() {
+        return print_json(&revisions.iter().map(revision_json).collect::<Vec<_>>());
+    }
+    if revisions.is_empty() {
+        println!("No canon revisions.");
+    }
+    for revision in &revisions {
+        println!(
+            "{} {:<10} {:<12} {} {}",
+            revision.created_at.format("%Y-%m-%d %H:%M"),
+            revision.state.as_str(),
+            revision.topic.as_deref().unwrap_or("-"),
+            revision_key(revision),
+            revision.summary.lines().next().unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+fn handle_promote(args: CanonPromoteArgs) -> Result<()> {
+    let (entry_id, revision) = parse_revision_key(&args.revision)?;
+    let (store, _) = args.store.open()?;
+    let target = store
+        .canon_entry_revision(entry_id, revision)?
+        .ok_or_else(|| anyhow!("canon revision {} not found", args.revision))?;
+    apply_revision(&store, target, args.yes, "canon.promote")
+}
+
+fn handle_revert(args: CanonRevertArgs) -> Result<()> {
+    let (store, _) = args.store.open()?;
+    let target = previous_revision(&store, &args.entry_id)?;
+    apply_revision(&store, target, args.yes, "canon.revert")
+}
+
+/// The newest revision older than the entry's latest one.
+fn previous_revision(store: &MindStore, entry_id: &str) -> Result<CanonEntryRevision> {
+    let revisions = store.canon_entry_revisions(entry_id)?;
+    if revisions.is_empty() {
+        bail!("canon entry {entry_id} not found");
+    }
+    revisions
+        .into_iter()
+        .nth(1)
+        .ok_or_else(|| anyhow!("canon entry {entry_id} has no earlier revision to revert to"))
+}
+
+fn apply_revision(
+    store: &MindStore,
+    target: CanonEntryRevision,
+    yes: bool,
+    action: &str,
+) -> Result<()> {
+    let current = store.latest_canon_revision(&target.entry_id)?;
+    if let Some(current) = current.as_ref() {
+        if current.revision == target.revision && current.state == CanonRevisionState::Active {
+            bail!("{} is already the active revision", revision_key(&target));
+        }
+    }
+    let diff = canon_diff(current.as_ref(), &target);
+    confirm(&target, &diff, yes)?;
+
+    let applied = promote_revision(store, &target)?;
+    print_change(
+        action,
+        format!(
+            "{} is active again as {}",
+            revision_key(&target),
+            revision_key(&applied)
+        ),
+        json!({
+            "from": revision_key(&target),
+            "revision": revision_json(&applied),
+            "diff": diff,
+        }),
+    )
+}
+
+/// Copies `target` forward as a new active revision so history stays
+/// append-only; the storage upsert supersedes whatever was active.
+fn promote_revision(store: &MindStore, target: &CanonEntryRevision) -> Result<CanonEntryRevision> {
+    store
+        .upsert_canon_entry_revision(
+            &target.entry_id,
+            target.topic.as_deref(),
+            &target.summary,
+            target.confidence_bps,
+            target.freshness_score,
+            None,
+            &target.evidence_refs,
+            Utc::now(),
+        )
+        .context("write canon revision")
+}
+
+fn confirm(target: &CanonEntryRevision, diff: &[String], yes: bool) -> Result<()> {
+    if yes {
+        return Ok(());
+    }
+    let interactive = !json_mode() && io::stdin().is_terminal();
+    if !json_mode() {
+        println!("Activating {}:", revision_key(target));
+        for line in diff {
+            println!("  {line}");
+        }
+    }
+    if !interactive {
+        bail!("refusing to change canon without --yes");
+    }
+    print!("Apply? [y/N]: ");
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    if !matches!(answer.trim(), "y" | "Y" | "yes") {
+        bail!("aborted; canon unchanged");
+    }
+    Ok(())
+}
+
+/// Field-by-field `-`/`+` lines between the current revision and `target`.
+fn canon_diff(current: Option<&CanonEntryRevision>, target: &CanonEntryRevision) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut field = |name: &str, old: Option<String>, new: String| {
+        if old.as_deref() == Some(new.as_str()) {
+            return;
+        }
+        if let Some(old) = old {
+            lines.push(format!("- {name}: {old}"));
+        }
+        lines.push(format!("+ {name}: {new}"));
+    };
+    field(
+        "topic",
+        current.map(|row| row.topic.clone().unwrap_or_default()),
+        target.topic.clone().unwrap_or_default(),
+    );
+    field(
+        "confidence_bps",
+        current.map(|row| row.confidence_bps.to_string()),
+        target.confidence_bps.to_string(),
+    );
+    field(
+        "freshness_score",
+        current.map(|row| row.freshness_score.to_string()),
+        target.freshness_score.to_string(),
+    );
+    field(
+        "evidence_refs",
+        current.map(|row| row.evidence_refs.join(", ")),
+        target.evidence_refs.join(", "),
+    );
+    if current.map(|row| row.summary.as_str()) != Some(target.summary.as_str()) {
+        if let Some(current) = current {
+            lines.extend(current.summary.lines().map(|line| format!("- {line}")));
+        }
+        lines.extend(target.summary.lines().map(|line| format!("+ {line}")));
+    }
+    lines
+}
+
+fn parse_revision_key(key: &str) -> Result<(&str, i64)> {
+    let (entry_id, revision) = key
+        .rsplit_once('#')
+        .ok_or_else(|| anyhow!("expected <entry-id>#<revision>, got '{key}'"))?;
+    let revision = revision
+        .parse::<i64>()
+        .with_context(|| format!("invalid revision number in '{key}'"))?;
+    Ok((entry_id, revision))
+}
+
+fn revision_key(revision: &CanonEntryRevision) -> String {
+    format!("{}#{}", revision.entry_id, revision.revision)
+}
+
+fn revision_json(revision: &CanonEntryRevision) -> Value {
+    json!({
+        "key": revision_key(revision),
+        "entry_id": revision.entry_id,
+        "revision": revision.revision,
+        "state": revision.state.as_str(),
+        "topic": revision.topic,
+        "summary": revision.summary,
+        "confidence_bps": revision.confidence_bps,
+        "freshness_score": revision.freshness_score,
+        "supersedes_entry_id": revision.supersedes_entry_id,
+        "evidence_refs": revision.evidence_refs,
+        "created_at": revision.created_at.to_rfc3339(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn promote_and_revert_append_a_new_active_revision() {
+        let store = MindStore::open_in_memory().expect("store");
+        let now = Utc::now();
+        for (summary, refs) in [("Use sqlite", "obs:1"), ("Use postgres", "obs:2")] {
+            store
+                .upsert_canon_entry_revision(
+                    "canon:db",
+                    Some("infra"),
+                    summary,
+                    8_000,
+                    9_000,
+                    None,
+                    &[refs.to_string()],
+                    now,
+                )
+                .expect("seed revision");
+        }
+
+        let target = previous_revision(&store, "canon:db").expect("previous revision");
+        assert_eq!(target.revision, 1);
+        let current = store.latest_canon_revision("canon:db").expect("latest");
+        assert_eq!(
+            canon_diff(current.as_ref(), &target),
+            vec![
+                "- evidence_refs: obs:2",
+                "+ evidence_refs: obs:1",
+                "- Use postgres",
+                "+ Use sqlite",
+            ]
+        );
+
+        let applied = promote_revision(&store, &target).expect("promote");
+        assert_eq!(applied.revision, 3);
+        assert_eq!(applied.summary, "Use sqlite");
+        let active = store.active_canon_entries(Some("infra")).expect("active");
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].revision, 3);
+
+        assert_eq!(
+            parse_revision_key("canon:db#2").expect("key"),
+            ("canon:db", 2)
+        );
+        assert!(parse_revision_key("canon:db").is_err());
+        assert!(previous_revision(&store, "canon:missing").is_err());
+    }
+}
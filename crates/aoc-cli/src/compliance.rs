@@ -0,0 +1,169 @@
+use anyhow::{bail, Context, Result};
+use aoc_mind::{
+    delete_compliance_subject, export_compliance_bundle, verify_compliance_bundle,
+    ComplianceManifest, DataSubject, COMPLIANCE_MANIFEST_FILE, COMPLIANCE_RECEIPT_FILE,
+};
+use chrono::Utc;
+use clap::{Args, Subcommand};
+use serde_json::json;
+use std::path::PathBuf;
+
+use crate::{
+    mind_store::StoreArgs,
+    output::{json_mode, print_change, print_json},
+};
+
+#[derive(Subcommand, Debug)]
+pub enum ComplianceCommand {
+    /// Write everything the Mind holds about a conversation or person to a
+    /// bundle with a manifest of sha256 hashes
+    Export(ComplianceExportArgs),
+    /// Re-hash a bundle's files and rows against its manifest
+    Verify(ComplianceVerifyArgs),
+    /// Delete exactly what a verified bundle holds and write a receipt
+    Delete(ComplianceDeleteArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ComplianceExportArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Export one conversation.
+    #[arg(long, conflicts_with = "subject", required_unless_present = "subject")]
+    pub conv: Option<String>,
+    /// Export every conversation mentioning this person identifier (email,
+    /// handle, agent or session id).
+    #[arg(long)]
+    pub subject: Option<String>,
+    /// Bundle directory; must not already hold a bundle.
+    #[arg(long)]
+    pub out: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct ComplianceVerifyArgs {
+    /// Bundle directory written by `aoc compliance export`.
+    pub dir: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct ComplianceDeleteArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Bundle directory written by `aoc compliance export`.
+    pub dir: PathBuf,
+    /// Delete the rows; without it only report what would go.
+    #[arg(long, default_value_t = false)]
+    pub apply: bool,
+}
+
+pub fn handle_compliance_command(command: ComplianceCommand) -> Result<()> {
+    match command {
+        ComplianceCommand::Export(args) => handle_export(args),
+        ComplianceCommand::Verify(args) => handle_verify(args),
+        ComplianceCommand::Delete(args) => handle_delete(args),
+    }
+}
+
+fn handle_export(args: ComplianceExportArgs) -> Result<()> {
+    let subject = match (args.conv, args.subject) {
+        (Some(conv), None) => DataSubject::Conversation(conv),
+        (None, Some(subject)) if !subject.trim().is_empty() => DataSubject::Identifier(subject),
+        _ => bail!("pass exactly one of --conv or a non-empty --subject"),
+    };
+    let (store, store_path) = args.store.open()?;
+    let manifest = export_compliance_bundle(&store, &subject, &args.out, Utc::now())
+        .with_context(|| format!("export {} to {}", subject.label(), args.out.display()))?;
+    print_change(
+        "compliance.export",
+        format!(
+            "Exported {} record(s) from {} conversation(s) for {} to {}",
+            manifest.total_records(),
+            manifest.conversation_ids.len(),
+            subject.label(),
+            args.out.display()
+        ),
+        json!({
+            "store_path": store_path,
+            "out": args.out,
+            "manifest": args.out.join(COMPLIANCE_MANIFEST_FILE),
+            "summary": manifest_json(&manifest),
+        }),
+    )
+}
+
+fn handle_verify(args: ComplianceVerifyArgs) -> Result<()> {
+    let manifest = verify_compliance_bundle(&args.dir)
+        .with_context(|| format!("verify bundle {}", args.dir.display()))?;
+    if json_mode() {
+        return print_json(&json!({
+            "dir": args.dir,
+            "verified": true,
+            "summary": manifest_json(&manifest),
+        }));
+    }
+    println!(
+        "verified: {} file(s), {} record(s) for {} (exported {})",
+        manifest.files.len(),
+        manifest.total_records(),
+        manifest.subject.label(),
+        manifest.generated_at.to_rfc3339()
+    );
+    for file in &manifest.files {
+        println!("  {:<32} {:>6}  {}", file.path, file.records, file.sha256);
+    }
+    Ok(())
+}
+
+fn handle_delete(args: ComplianceDeleteArgs) -> Result<()> {
+    let dry_run = !args.apply;
+    let (store, store_path) = args.store.open_writer()?;
+    let deletion = delete_compliance_subject(&store, &args.dir, dry_run, Utc::now())
+        .with_context(|| format!("delete subject of bundle {}", args.dir.display()))?;
+    let total = deletion.deleted.values().sum::<usize>();
+    let data = json!({
+        "store_path": store_path,
+        "dir": args.dir,
+        "dry_run": dry_run,
+        "subject": deletion.subject.label(),
+        "manifest_sha256": deletion.manifest_sha256,
+        "conversation_ids": deletion.conversation_ids,
+        "deleted": deletion.deleted,
+        "verified": deletion.verified,
+        "receipt": (!dry_run).then(|| args.dir.join(COMPLIANCE_RECEIPT_FILE)),
+    });
+    if dry_run {
+        if json_mode() {
+            return print_json(&data);
+        }
+        for (table, rows) in &deletion.deleted {
+            println!("{table:<32} {rows:>6}");
+        }
+        println!(
+            "dry run: {total} row(s) for {}; pass --apply to delete",
+            deletion.subject.label()
+        );
+        return Ok(());
+    }
+    print_change(
+        "compliance.delete",
+        format!(
+            "Deleted {total} row(s) for {} from {}; receipt in {}",
+            deletion.subject.label(),
+            store_path.display(),
+            args.dir.join(COMPLIANCE_RECEIPT_FILE).display()
+        ),
+        data,
+    )
+}
+
+fn manifest_json(manifest: &ComplianceManifest) -> serde_json::Value {
+    json!({
+        "subject": manifest.subject.label(),
+        "generated_at": manifest.generated_at,
+        "schema_version": manifest.schema_version,
+        "conversation_ids": manifest.conversation_ids,
+        "files": manifest.files,
+        "records": manifest.total_records(),
+    })
+}
@@ -0,0 +1,47 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use serde_json::json;
+
+use crate::{
+    mind_store::StoreArgs,
+    output::{json_mode, print_json},
+};
+
+#[derive(Args, Debug)]
+pub struct ConfigArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+}
+
+/// Prints the effective settings after every aoc.toml layer and `AOC_*`
+/// override has been applied, along with where they came from.
+pub fn handle_config_command(args: ConfigArgs) -> Result<()> {
+    let config = args.store.config()?;
+    if json_mode() {
+        return print_json(&json!({
+            "profile": config.active_profile,
+            "profiles": config.profile_names(),
+            "sources": config.sources,
+            "config": config,
+        }));
+    }
+    println!(
+        "# profile: {} (available: {})",
+        config.active_profile.as_deref().unwrap_or("<none>"),
+        config.profile_names().join(", ")
+    );
+    if config.sources.is_empty() {
+        println!("# sources: built-in defaults");
+    } else {
+        println!("# sources (lowest precedence first):");
+        for source in &config.sources {
+            println!("#   {source}");
+        }
+    }
+    println!();
+    print!(
+        "{}",
+        toml::to_string_pretty(&config).context("serialize effective config")?
+    );
+    Ok(())
+}
@@ -0,0 +1,297 @@
+use anyhow::{anyhow, bail, Context, Result};
+use aoc_mind::project_scope_key;
+use aoc_segment_routing::SegmentRouter;
+use aoc_storage::{MemDecision, MindStore};
+use chrono::{DateTime, Utc};
+use clap::{Args, Subcommand};
+use serde_json::{json, Value};
+
+use crate::{
+    mind_store::StoreArgs,
+    output::{json_mode, print_change, print_json},
+};
+
+#[derive(Subcommand, Debug)]
+pub enum DecisionsCommand {
+    /// Record a decision; routed to a segment unless --segment is given
+    Add(DecisionAddArgs),
+    /// List current decisions, newest first
+    List(DecisionListArgs),
+    /// Record a decision that replaces an earlier one
+    Supersede(DecisionSupersedeArgs),
+    /// Print one decision with its supersede chain
+    Show(DecisionShowArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct DecisionAddArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    pub text: String,
+    #[arg(long)]
+    pub segment: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct DecisionListArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    #[arg(long)]
+    pub segment: Option<String>,
+    /// Include decisions that have been superseded.
+    #[arg(long, default_value_t = false)]
+    pub all: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct DecisionSupersedeArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Decision being replaced.
+    pub decision_id: String,
+    pub text: String,
+    /// Segment for the new decision; defaults to the replaced decision's.
+    #[arg(long)]
+    pub segment: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct DecisionShowArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    pub decision_id: String,
+}
+
+pub fn handle_decisions_command(command: DecisionsCommand) -> Result<()> {
+    match command {
+        DecisionsCommand::Add(args) => handle_add(args),
+        DecisionsCommand::List(args) => handle_list(args),
+        DecisionsCommand::Supersede(args) => handle_supersede(args),
+        DecisionsCommand::Show(args) => handle_show(args),
+    }
+}
+
+fn handle_add(args: DecisionAddArgs) -> Result<()> {
+    let project_id = project_scope_key(&args.store.project_root()?);
+    let router = SegmentRouter::new(args.store.config()?.routing);
+    let (store, _) = args.store.open()?;
+    let (segment_id, routed_by) = match args.segment {
+        Some(segment) => (segment, "flag".to_string()),
+        None => route_decision(&router, &args.text),
+    };
+    let decision = record_decision(
+        &store,
+        project_id,
+        Some(segment_id),
+        &args.text,
+        None,
+        Utc::now(),
+    )?;
+    print_change(
+        "decisions.add",
+        format!(
+            "Recorded {} in segment {} ({routed_by})",
+            decision.decision_id,
+            decision.segment_id.as_deref().unwrap_or("-")
+        ),
+        json!({ "decision": decision_json(&decision), "routed_by": routed_by }),
+    )
+}
+
+fn handle_supersede(args: DecisionSupersedeArgs) -> Result<()> {
+    let (store, _) = args.store.open()?;
+    let previous = store
+        .mem_decision(&args.decision_id)?
+        .ok_or_else(|| anyhow!("decision {} not found", args.decision_id))?;
+    let segment_id = args.segment.or(previous.segment_id.clone());
+    let decision = record_decision(
+        &store,
+        previous.project_id.clone(),
+        segment_id,
+        &args.text,
+        Some(previous.decision_id.clone()),
+        Utc::now(),
+    )?;
+    print_change(
+        "decisions.supersede",
+        format!(
+            "{} supersedes {}",
+            decision.decision_id, previous.decision_id
+        ),
+        json!({
+            "decision": decision_json(&decision),
+            "superseded": decision_json(&previous),
+        }),
+    )
+}
+
+fn handle_list(args: DecisionListArgs) -> Result<()> {
+    let project_id = project_scope_key(&args.store.project_root()?);
+    let (store, _) = args.store.open()?;
+    let decisions = store
+        .mem_decisions(&project_id, args.segment.as_deref(), args.all)
+        .context("load decisions")?;
+    if json_mode() {
+        return print_json(&decisions.iter().map(decision_json).collect::<Vec<_>>());
+    }
+    if decisions.is_empty() {
+        println!("No decisions recorded.");
+    }
+    for decision in &decisions {
+        println!(
+            "{} {} [{}] {}",
+            decision.ts.format("%Y-%m-%d %H:%M"),
+            decision.decision_id,
+            decision.segment_id.as_deref().unwrap_or("-"),
+            decision.text.lines().next().unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+fn handle_show(args: DecisionShowArgs) -> Result<()> {
+    let (store, _) = args.store.open()?;
+    let decision = store
+        .mem_decision(&args.decision_id)?
+        .ok_or_else(|| anyhow!("decision {} not found", args.decision_id))?;
+    let superseded_by = store.mem_decision_successor(&decision.decision_id)?;
+    let mut earlier = Vec::new();
+    let mut cursor = decision.supersedes_id.clone();
+    while let Some(id) = cursor {
+        let Some(previous) = store.mem_decision(&id)? else {
+            break;
+        };
+        cursor = previous.supersedes_id.clone();
+        earlier.push(previous);
+    }
+
+    if json_mode() {
+        return print_json(&json!({
+            "decision": decision_json(&decision),
+            "superseded_by": superseded_by.as_ref().map(decision_json),
+            "supersedes": earlier.iter().map(decision_json).collect::<Vec<_>>(),
+        }));
+    }
+    println!("{}", decision.decision_id);
+    println!("  recorded: {}", decision.ts.to_rfc3339());
+    println!(
+        "  segment:  {}",
+        decision.segment_id.as_deref().unwrap_or("-")
+    );
+    if let Some(successor) = &superseded_by {
+        println!("  status:   superseded by {}", successor.decision_id);
+    } else {
+        println!("  status:   current");
+    }
+    println!();
+    for line in decision.text.lines() {
+        println!("  {line}");
+    }
+    if !earlier.is_empty() {
+        println!();
+        println!("Supersedes:");
+        for previous in &earlier {
+            println!(
+                "  {} {} {}",
+                previous.ts.format("%Y-%m-%d %H:%M"),
+                previous.decision_id,
+                previous.text.lines().next().unwrap_or_default()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Keyword route for the decision text, falling back to the global segment.
+fn route_decision(router: &SegmentRouter, text: &str) -> (String, String) {
+    match router.route_text(text) {
+        Some(candidate) => (
+            candidate.segment_id,
+            format!("routed {}", candidate.evidence.join(", ")),
+        ),
+        None => (router.global_segment(), "no segment match".to_string()),
+    }
+}
+
+fn record_decision(
+    store: &MindStore,
+    project_id: String,
+    segment_id: Option<String>,
+    text: &str,
+    supersedes_id: Option<String>,
+    now: DateTime<Utc>,
+) -> Result<MemDecision> {
+    let text = text.trim();
+    if text.is_empty() {
+        bail!("decision text is empty");
+    }
+    let decision = MemDecision {
+        decision_id: format!("dec:{:x}", now.timestamp_millis()),
+        ts: now,
+        project_id,
+        segment_id,
+        text: text.to_string(),
+        supersedes_id,
+    };
+    store
+        .insert_mem_decision(&decision)
+        .context("record decision")?;
+    Ok(decision)
+}
+
+fn decision_json(decision: &MemDecision) -> Value {
+    json!({
+        "decision_id": decision.decision_id,
+        "ts": decision.ts.to_rfc3339(),
+        "project_id": decision.project_id,
+        "segment_id": decision.segment_id,
+        "text": decision.text,
+        "supersedes_id": decision.supersedes_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_segment_routing::SegmentRoutingConfig;
+
+    #[test]
+    fn recorded_decisions_route_and_chain() {
+        let store = MindStore::open_in_memory().expect("store");
+        let router = SegmentRouter::new(SegmentRoutingConfig::default());
+
+        let (segment, _) = route_decision(&router, "Keep the db migration reversible");
+        assert_eq!(segment, "backend");
+        let (segment, reason) = route_decision(&router, "Ship on Fridays");
+        assert_eq!(segment, "global");
+        assert_eq!(reason, "no segment match");
+
+        let now = Utc::now();
+        let first = record_decision(
+            &store,
+            "project:/repo".to_string(),
+            Some(segment),
+            "Ship on Fridays",
+            None,
+            now,
+        )
+        .expect("record");
+        let second = record_decision(
+            &store,
+            "project:/repo".to_string(),
+            first.segment_id.clone(),
+            "  Never ship on Fridays ",
+            Some(first.decision_id.clone()),
+            now + chrono::Duration::milliseconds(1),
+        )
+        .expect("supersede");
+        assert_eq!(second.text, "Never ship on Fridays");
+        assert_ne!(first.decision_id, second.decision_id);
+
+        let current = store
+            .mem_decisions("project:/repo", None, false)
+            .expect("list");
+        assert_eq!(current, vec![second]);
+        assert!(record_decision(&store, "p".to_string(), None, "  ", None, now).is_err());
+    }
+}
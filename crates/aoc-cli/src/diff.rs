@@ -0,0 +1,157 @@
+use anyhow::{Context, Result};
+use aoc_storage::{MindStore, StoreFingerprint};
+use clap::Args;
+use serde::Serialize;
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use crate::output::{json_mode, print_json, Severity, SeverityExit};
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// Baseline Mind database.
+    pub db_a: PathBuf,
+    /// Database compared against the baseline.
+    pub db_b: PathBuf,
+    /// Ids listed per section and change kind in text output.
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+}
+
+/// Ids added, removed, or changed in one section going from `a` to `b`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+struct SectionDiff {
+    section: &'static str,
+    added: Vec<String>,
+    removed: Vec<String>,
+    changed: Vec<String>,
+}
+
+impl SectionDiff {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+pub fn handle_diff_command(args: DiffArgs) -> Result<()> {
+    let a = fingerprint(&args.db_a)?;
+    let b = fingerprint(&args.db_b)?;
+    let sections = diff_fingerprints(&a, &b);
+    let differing = sections
+        .iter()
+        .filter(|section| !section.is_empty())
+        .count();
+
+    if json_mode() {
+        print_json(&json!({
+            "db_a": args.db_a,
+            "db_b": args.db_b,
+            "identical": differing == 0,
+            "sections": sections,
+        }))?;
+    } else {
+        println!("a: {}\nb: {}", args.db_a.display(), args.db_b.display());
+        for section in &sections {
+            println!(
+                "{:<14} +{} -{} ~{}",
+                section.section,
+                section.added.len(),
+                section.removed.len(),
+                section.changed.len()
+            );
+            for (marker, ids) in [
+                ('+', &section.added),
+                ('-', &section.removed),
+                ('~', &section.changed),
+            ] {
+                for id in ids.iter().take(args.limit) {
+                    println!("  {marker} {id}");
+                }
+                if ids.len() > args.limit {
+                    println!("  {marker} ... {} more", ids.len() - args.limit);
+                }
+            }
+        }
+        if differing == 0 {
+            println!("stores are identical");
+        }
+    }
+
+    if differing > 0 {
+        return Err(SeverityExit::new(
+            Severity::Warning,
+            format!("stores differ in {differing} section(s)"),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn fingerprint(path: &Path) -> Result<StoreFingerprint> {
+    let store =
+        MindStore::open_read_only(path).with_context(|| format!("open {}", path.display()))?;
+    store
+        .fingerprint()
+        .with_context(|| format!("fingerprint {}", path.display()))
+}
+
+fn diff_fingerprints(a: &StoreFingerprint, b: &StoreFingerprint) -> Vec<SectionDiff> {
+    vec![
+        diff_section("conversations", &a.conversations, &b.conversations),
+        diff_section("artifacts", &a.artifacts, &b.artifacts),
+        diff_section("canon", &a.canon, &b.canon),
+        diff_section("decisions", &a.decisions, &b.decisions),
+    ]
+}
+
+fn diff_section(
+    section: &'static str,
+    a: &BTreeMap<String, String>,
+    b: &BTreeMap<String, String>,
+) -> SectionDiff {
+    let mut diff = SectionDiff {
+        section,
+        ..SectionDiff::default()
+    };
+    for (id, hash) in a {
+        match b.get(id) {
+            None => diff.removed.push(id.clone()),
+            Some(other) if other != hash => diff.changed.push(id.clone()),
+            Some(_) => {}
+        }
+    }
+    diff.added = b
+        .keys()
+        .filter(|id| !a.contains_key(*id))
+        .cloned()
+        .collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_section_splits_added_removed_and_changed_ids() {
+        let a = BTreeMap::from([
+            ("keep".to_string(), "h1".to_string()),
+            ("edit".to_string(), "h2".to_string()),
+            ("gone".to_string(), "h3".to_string()),
+        ]);
+        let b = BTreeMap::from([
+            ("keep".to_string(), "h1".to_string()),
+            ("edit".to_string(), "h2b".to_string()),
+            ("new".to_string(), "h4".to_string()),
+        ]);
+
+        let diff = diff_section("artifacts", &a, &b);
+        assert_eq!(diff.added, vec!["new"]);
+        assert_eq!(diff.removed, vec!["gone"]);
+        assert_eq!(diff.changed, vec!["edit"]);
+        assert!(diff_section("artifacts", &a, &a).is_empty());
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::{Context, Result};
+use aoc_storage::{IntegrityFinding, IntegrityReport, IntegritySeverity, SafeFixReport};
+use chrono::Utc;
+use clap::Args;
+use serde_json::{json, Value};
+
+use crate::{
+    mind_store::StoreArgs,
+    output::{json_mode, print_json, Severity, SeverityExit},
+};
+
+#[derive(Args, Debug)]
+pub struct DoctorArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Release expired leases and requeue orphaned job claims, then re-check.
+    #[arg(long, default_value_t = false)]
+    pub apply_safe_fixes: bool,
+}
+
+pub fn handle_doctor_command(args: DoctorArgs) -> Result<()> {
+    let (store, store_path) = args.store.open()?;
+    let now = Utc::now();
+    let mut report = store.check_integrity(now).context("check mind store")?;
+    let mut fixes = None;
+    if args.apply_safe_fixes && report.findings.iter().any(|finding| finding.safe_fix) {
+        fixes = Some(
+            store
+                .apply_safe_integrity_fixes(now)
+                .context("apply safe fixes")?,
+        );
+        report = store.check_integrity(now).context("re-check mind store")?;
+    }
+
+    if json_mode() {
+        let payload = json!({
+            "store_path": store_path,
+            "checked_at": now.to_rfc3339(),
+            "healthy": !report.has_errors(),
+            "fixes": fixes.map(|fixes| fixes_json(&fixes)),
+            "findings": report.findings.iter().map(finding_json).collect::<Vec<_>>(),
+        });
+        print_json(&payload)?;
+    } else {
+        print_report(&store_path.display().to_string(), &report, fixes);
+    }
+
+    let errors = error_count(&report);
+    if errors > 0 {
+        return Err(SeverityExit::new(
+            Severity::Error,
+            format!("mind store has {errors} unresolved error finding(s)"),
+        )
+        .into());
+    }
+    if !report.is_clean() {
+        return Err(SeverityExit::new(
+            Severity::Warning,
+            format!(
+                "mind store has {} warning finding(s)",
+                report.findings.len()
+            ),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+pub fn print_report(store_path: &str, report: &IntegrityReport, fixes: Option<SafeFixReport>) {
+    println!("store: {store_path}");
+    if let Some(fixes) = fixes {
+        println!(
+            "applied safe fixes: released_leases={} requeued_claims={}",
+            fixes.released_leases, fixes.requeued_claims
+        );
+    }
+    if report.is_clean() {
+        println!("doctor: ok");
+        return;
+    }
+    for finding in &report.findings {
+        println!(
+            "[{}] {} {}: {}{}",
+            finding.severity.as_str(),
+            finding.check.as_str(),
+            finding.subject,
+            finding.detail,
+            if finding.safe_fix { " (safe fix)" } else { "" }
+        );
+        println!("    fix: {}", finding.suggestion);
+    }
+    let safe = report
+        .findings
+        .iter()
+        .filter(|finding| finding.safe_fix)
+        .count();
+    println!(
+        "doctor: {} finding(s), {} error(s), {safe} safe to fix",
+        report.findings.len(),
+        error_count(report)
+    );
+    if safe > 0 {
+        println!("rerun with --apply-safe-fixes to repair them");
+    }
+}
+
+pub fn error_count(report: &IntegrityReport) -> usize {
+    report
+        .findings
+        .iter()
+        .filter(|finding| finding.severity == IntegritySeverity::Error)
+        .count()
+}
+
+pub fn finding_json(finding: &IntegrityFinding) -> Value {
+    json!({
+        "check": finding.check.as_str(),
+        "severity": finding.severity.as_str(),
+        "subject": finding.subject,
+        "detail": finding.detail,
+        "suggestion": finding.suggestion,
+        "safe_fix": finding.safe_fix,
+    })
+}
+
+fn fixes_json(fixes: &SafeFixReport) -> Value {
+    json!({
+        "released_leases": fixes.released_leases,
+        "requeued_claims": fixes.requeued_claims,
+    })
+}
@@ -9,6 +9,8 @@ use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::output::json_mode;
+
 const SCHEMA_VERSION: &str = "aoc.dox.v1";
 const DOX_DIR: &str = ".aoc/dox";
 const MAP_PATH: &str = ".aoc/dox/map.json";
@@ -43,8 +45,6 @@ pub enum DoxCommand {
 
 #[derive(Args, Debug, Clone)]
 pub struct MapArgs {
-    #[arg(long)]
-    pub json: bool,
     #[arg(long)]
     pub no_codegraph: bool,
     #[arg(long, default_value_t = 7)]
@@ -59,8 +59,6 @@ pub struct MapArgs {
 
 #[derive(Args, Debug, Clone)]
 pub struct ReviewArgs {
-    #[arg(long)]
-    pub json: bool,
     #[arg(long)]
     pub packet: bool,
     #[arg(long)]
@@ -74,22 +72,14 @@ pub struct ApplyArgs {
     #[arg(long)]
     pub yes: bool,
     #[arg(long)]
-    pub json: bool,
-    #[arg(long)]
     pub include_content: bool,
 }
 
 #[derive(Args, Debug, Clone)]
-pub struct DoctorArgs {
-    #[arg(long)]
-    pub json: bool,
-}
+pub struct DoctorArgs {}
 
 #[derive(Args, Debug, Clone)]
-pub struct EvalArgs {
-    #[arg(long)]
-    pub json: bool,
-}
+pub struct EvalArgs {}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct DoxEnvelope<T> {
@@ -316,7 +306,7 @@ fn handle_map(args: MapArgs) -> Result<()> {
     fs::write(project_root.join(REPORT_PATH), render_report(&map_env, &candidates_env, &budgets_env)?)
         .context("write dox report")?;
 
-    if args.json {
+    if json_mode() {
         println!("{}", serde_json::to_string_pretty(&map_env)?);
     } else {
         println!(
@@ -347,7 +337,7 @@ fn handle_review(args: ReviewArgs) -> Result<()> {
             }
             fs::write(&packet_path, &packet).with_context(|| format!("write {}", REVIEW_PACKET_PATH))?;
         }
-        if args.json {
+        if json_mode() {
             let value = serde_json::json!({
                 "schema": SCHEMA_VERSION,
                 "packet_path": REVIEW_PACKET_PATH,
@@ -361,7 +351,7 @@ fn handle_review(args: ReviewArgs) -> Result<()> {
         return Ok(());
     }
 
-    if args.json {
+    if json_mode() {
         let value = serde_json::json!({
             "schema": SCHEMA_VERSION,
             "map": map,
@@ -392,7 +382,7 @@ fn handle_review(args: ReviewArgs) -> Result<()> {
 }
 
 fn handle_apply(args: ApplyArgs) -> Result<()> {
-    if args.include_content && (!args.dry_run || !args.json) {
+    if args.include_content && (!args.dry_run || !json_mode()) {
         bail!("--include-content is only valid with --dry-run --json");
     }
 
@@ -416,7 +406,7 @@ fn handle_apply(args: ApplyArgs) -> Result<()> {
     }
 
     if args.dry_run {
-        if args.json {
+        if json_mode() {
             let items: Vec<_> = rendered
                 .iter()
                 .map(|(path, content)| {
@@ -461,7 +451,7 @@ fn handle_apply(args: ApplyArgs) -> Result<()> {
         written.push(target);
     }
 
-    if args.json {
+    if json_mode() {
         println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "schema": SCHEMA_VERSION, "written": written }))?);
     } else {
         for path in written {
@@ -472,7 +462,7 @@ fn handle_apply(args: ApplyArgs) -> Result<()> {
 }
 
 
-fn handle_doctor(args: DoctorArgs) -> Result<()> {
+fn handle_doctor(_args: DoctorArgs) -> Result<()> {
     let project_root = std::env::current_dir().context("resolve project root")?;
     let map: DoxEnvelope<DoxMapData> = read_json(project_root.join(MAP_PATH))?;
     let candidates: DoxEnvelope<DoxCandidatesData> = read_json(project_root.join(CANDIDATES_PATH))?;
@@ -509,7 +499,7 @@ fn handle_doctor(args: DoctorArgs) -> Result<()> {
         warnings.push("active AGENTS chain is over target budget".to_string());
     }
 
-    if args.json {
+    if json_mode() {
         println!(
             "{}",
             serde_json::to_string_pretty(&serde_json::json!({
@@ -538,7 +528,7 @@ fn handle_doctor(args: DoctorArgs) -> Result<()> {
     }
 }
 
-fn handle_eval(args: EvalArgs) -> Result<()> {
+fn handle_eval(_args: EvalArgs) -> Result<()> {
     let project_root = std::env::current_dir().context("resolve project root")?;
     fs::create_dir_all(project_root.join(DOX_DIR)).context("create .aoc/dox")?;
     let data = EvalMatrixData {
@@ -564,7 +554,7 @@ fn handle_eval(args: EvalArgs) -> Result<()> {
     };
     let env = envelope(&project_root, data);
     write_json(project_root.join(EVAL_MATRIX_PATH), &env)?;
-    if args.json {
+    if json_mode() {
         println!("{}", serde_json::to_string_pretty(&env)?);
     } else {
         println!("Eval matrix scaffold written; task runner integration is not implemented in v1.");
@@ -1676,7 +1666,13 @@ mod tests {
             DoxCandidate { path: "specific".to_string(), decision: CandidateDecision::Reject, score: 0, confidence: 0.0, reason: String::new(), contracts: vec![], risks: vec![], verification: vec![], evidence: vec![], target_agents_path: None },
             DoxCandidate { path: "scripts".to_string(), decision: CandidateDecision::Reject, score: 7, confidence: 0.0, reason: String::new(), contracts: vec![], risks: vec![], verification: vec![], evidence: vec![], target_agents_path: None },
         ];
-        let args = MapArgs { json: false, no_codegraph: true, min_score: 7, max_codegraph_chars: 12000, active_chain_target_bytes: 16384, active_chain_hard_bytes: 24576 };
+        let args = MapArgs {
+            no_codegraph: true,
+            min_score: 7,
+            max_codegraph_chars: 12000,
+            active_chain_target_bytes: 16384,
+            active_chain_hard_bytes: 24576,
+        };
         let coverage = build_coverage(&root, &dirs, &candidates, &args).unwrap();
         assert_eq!(coverage.iter().find(|item| item.path == ".").unwrap().coverage, CoverageLevel::Specific);
         assert_eq!(coverage.iter().find(|item| item.path == "parent/inherited").unwrap().coverage, CoverageLevel::Inherited);
@@ -0,0 +1,310 @@
+use anyhow::{bail, Context, Result};
+use aoc_mind::{
+    build_knowledge_graph, export_artifacts, export_star_schema, export_vault, project_scope_key,
+    ArtifactExportFormat, ArtifactExportOptions, ArtifactExportScope, GraphExportFormat,
+    GraphScope, VaultExportOptions,
+};
+use chrono::Utc;
+use clap::{Args, ValueEnum};
+use serde_json::json;
+use std::path::PathBuf;
+
+use crate::{mind_store::StoreArgs, output::json_mode, query::parse_time_arg};
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    #[arg(long, value_enum, default_value_t = ExportFormatArg::Jsonl)]
+    pub format: ExportFormatArg,
+    #[arg(long, value_enum, default_value_t = ExportScopeArg::All)]
+    pub scope: ExportScopeArg,
+    /// Conversation id, tag, or segment id selected by --scope.
+    #[arg(long)]
+    pub id: Option<String>,
+    /// Output directory; rerunning against it resumes the export.
+    #[arg(long)]
+    pub out: PathBuf,
+    /// Artifacts per chunk file.
+    #[arg(long, default_value_t = ArtifactExportOptions::default().chunk_size)]
+    pub chunk_size: usize,
+    /// With --format vault: ignore the watermarks and rewrite every note.
+    #[arg(long)]
+    pub full: bool,
+    /// With a graph format: only artifacts at or after this (30m, 12h, 7d,
+    /// 2w, or RFC3339).
+    #[arg(long)]
+    pub since: Option<String>,
+    /// With a graph format: only artifacts before this (same forms as --since).
+    #[arg(long)]
+    pub until: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormatArg {
+    Jsonl,
+    Parquet,
+    Markdown,
+    /// Obsidian-style markdown vault, one note per artifact, canon
+    /// revision, and decision.
+    Vault,
+    /// Star schema of Parquet fact/dimension tables plus a DuckDB
+    /// `schema.sql`.
+    Analytics,
+    /// Knowledge graph as Graphviz DOT.
+    Dot,
+    /// Knowledge graph as GraphML (Gephi, yEd).
+    Graphml,
+    /// Knowledge graph as JSON nodes and edges.
+    GraphJson,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportScopeArg {
+    Conv,
+    Tag,
+    Segment,
+    All,
+}
+
+pub fn handle_export_command(args: ExportArgs) -> Result<()> {
+    let scope = resolve_scope(args.scope, args.id.as_deref())?;
+    let format = match args.format {
+        ExportFormatArg::Jsonl => ArtifactExportFormat::Jsonl,
+        ExportFormatArg::Parquet => ArtifactExportFormat::Parquet,
+        ExportFormatArg::Markdown => ArtifactExportFormat::Markdown,
+        ExportFormatArg::Vault => return handle_vault_export(&args, &scope),
+        ExportFormatArg::Analytics => return handle_analytics_export(&args, &scope),
+        ExportFormatArg::Dot => return handle_graph_export(&args, &scope, GraphExportFormat::Dot),
+        ExportFormatArg::Graphml => {
+            return handle_graph_export(&args, &scope, GraphExportFormat::Graphml)
+        }
+        ExportFormatArg::GraphJson => {
+            return handle_graph_export(&args, &scope, GraphExportFormat::Json)
+        }
+    };
+    if args.full {
+        bail!("--full only applies to --format vault");
+    }
+    if args.since.is_some() || args.until.is_some() {
+        bail!("--since/--until only apply to graph formats (dot, graphml, graph-json)");
+    }
+    let (store, store_path) = args.store.open()?;
+    let report = export_artifacts(
+        &store,
+        &scope,
+        format,
+        &args.out,
+        ArtifactExportOptions {
+            chunk_size: args.chunk_size,
+        },
+    )
+    .with_context(|| format!("export {} to {}", scope.label(), args.out.display()))?;
+
+    if json_mode() {
+        let payload = json!({
+            "store_path": store_path,
+            "out": args.out,
+            "scope": scope.label(),
+            "format": format.extension(),
+            "resumed": report.resumed,
+            "chunks_skipped": report.chunks_skipped,
+            "chunks_written": report.chunks_written,
+            "artifacts_exported": report.artifacts_exported,
+            "artifacts_total": report.artifacts_total,
+        });
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(());
+    }
+
+    if report.resumed {
+        println!(
+            "resuming export in {} ({} chunk(s) already written)",
+            args.out.display(),
+            report.chunks_skipped
+        );
+    }
+    for chunk in &report.chunks_written {
+        println!("wrote {}", chunk.display());
+    }
+    println!(
+        "exported {} new artifact(s) of {} for {}",
+        report.artifacts_exported,
+        report.artifacts_total,
+        scope.label()
+    );
+    Ok(())
+}
+
+fn handle_vault_export(args: &ExportArgs, scope: &ArtifactExportScope) -> Result<()> {
+    if *scope != ArtifactExportScope::All {
+        bail!("--format vault exports the whole mind; drop --scope/--id");
+    }
+    if args.since.is_some() || args.until.is_some() {
+        bail!("--since/--until only apply to graph formats (dot, graphml, graph-json)");
+    }
+    let project_id = project_scope_key(&args.store.project_root()?);
+    let (store, store_path) = args.store.open()?;
+    let report = export_vault(
+        &store,
+        &args.out,
+        &VaultExportOptions {
+            project_id: project_id.clone(),
+            full: args.full,
+        },
+    )
+    .with_context(|| format!("export vault to {}", args.out.display()))?;
+
+    if json_mode() {
+        let payload = json!({
+            "store_path": store_path,
+            "out": args.out,
+            "format": "vault",
+            "project_id": project_id,
+            "incremental": report.incremental,
+            "observations": report.observations,
+            "reflections": report.reflections,
+            "canon_revisions": report.canon_revisions,
+            "decisions": report.decisions,
+            "notes_written": report.notes_written,
+        });
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(());
+    }
+
+    println!(
+        "{} vault {}: {} observation(s), {} reflection(s), {} canon revision(s), {} decision(s)",
+        if report.incremental {
+            "refreshed"
+        } else {
+            "wrote"
+        },
+        args.out.display(),
+        report.observations,
+        report.reflections,
+        report.canon_revisions,
+        report.decisions
+    );
+    Ok(())
+}
+
+fn handle_analytics_export(args: &ExportArgs, scope: &ArtifactExportScope) -> Result<()> {
+    if *scope != ArtifactExportScope::All {
+        bail!("--format analytics exports the whole mind; drop --scope/--id");
+    }
+    if args.full {
+        bail!("--full only applies to --format vault");
+    }
+    if args.since.is_some() || args.until.is_some() {
+        bail!("--since/--until only apply to graph formats (dot, graphml, graph-json)");
+    }
+    let (store, store_path) = args.store.open()?;
+    let report = export_star_schema(&store, &args.out)
+        .with_context(|| format!("export star schema to {}", args.out.display()))?;
+
+    if json_mode() {
+        let tables = report
+            .tables
+            .iter()
+            .map(|(name, rows)| json!({ "table": name, "rows": rows }))
+            .collect::<Vec<_>>();
+        let payload = json!({
+            "store_path": store_path,
+            "out": args.out,
+            "format": "analytics",
+            "tables": tables,
+            "files_written": report.files_written,
+        });
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(());
+    }
+
+    for (name, rows) in &report.tables {
+        println!("{name}: {rows} row(s)");
+    }
+    println!(
+        "wrote star schema to {}; load it with `duckdb < schema.sql` from that directory",
+        args.out.display()
+    );
+    Ok(())
+}
+
+fn handle_graph_export(
+    args: &ExportArgs,
+    scope: &ArtifactExportScope,
+    format: GraphExportFormat,
+) -> Result<()> {
+    let tag = match scope {
+        ArtifactExportScope::All => None,
+        ArtifactExportScope::Tag(tag) => Some(tag.clone()),
+        _ => bail!("graph formats accept --scope all or --scope tag"),
+    };
+    if args.full {
+        bail!("--full only applies to --format vault");
+    }
+    let now = Utc::now();
+    let since = args
+        .since
+        .as_deref()
+        .map(|value| parse_time_arg("--since", value, now))
+        .transpose()?;
+    let until = args
+        .until
+        .as_deref()
+        .map(|value| parse_time_arg("--until", value, now))
+        .transpose()?;
+    if let (Some(since), Some(until)) = (since, until) {
+        if since >= until {
+            bail!("--since must be earlier than --until");
+        }
+    }
+
+    let graph_scope = GraphScope {
+        tag,
+        since,
+        until,
+        project_id: Some(project_scope_key(&args.store.project_root()?)),
+    };
+    let (store, store_path) = args.store.open()?;
+    let graph = build_knowledge_graph(&store, &graph_scope)
+        .with_context(|| format!("build knowledge graph for {}", scope.label()))?;
+    std::fs::create_dir_all(&args.out).with_context(|| format!("create {}", args.out.display()))?;
+    let path = args.out.join(format!("mind-graph.{}", format.extension()));
+    std::fs::write(&path, graph.render(format))
+        .with_context(|| format!("write {}", path.display()))?;
+
+    if json_mode() {
+        let payload = json!({
+            "store_path": store_path,
+            "out": path,
+            "scope": scope.label(),
+            "format": format.extension(),
+            "since": since.map(|ts| ts.to_rfc3339()),
+            "until": until.map(|ts| ts.to_rfc3339()),
+            "nodes": graph.nodes.len(),
+            "edges": graph.edges.len(),
+        });
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(());
+    }
+    println!(
+        "wrote {} ({} node(s), {} edge(s)) for {}",
+        path.display(),
+        graph.nodes.len(),
+        graph.edges.len(),
+        scope.label()
+    );
+    Ok(())
+}
+
+fn resolve_scope(scope: ExportScopeArg, id: Option<&str>) -> Result<ArtifactExportScope> {
+    let id = id.map(str::trim).filter(|value| !value.is_empty());
+    Ok(match (scope, id) {
+        (ExportScopeArg::All, None) => ArtifactExportScope::All,
+        (ExportScopeArg::All, Some(_)) => bail!("--id is not used with --scope all"),
+        (_, None) => bail!("--id is required unless --scope is all"),
+        (ExportScopeArg::Conv, Some(id)) => ArtifactExportScope::Conversation(id.to_string()),
+        (ExportScopeArg::Tag, Some(id)) => ArtifactExportScope::Tag(id.to_string()),
+        (ExportScopeArg::Segment, Some(id)) => ArtifactExportScope::Segment(id.to_string()),
+    })
+}
@@ -0,0 +1,213 @@
+use anyhow::{bail, Context, Result};
+use aoc_mind::{
+    import_third_party_memories, parse_third_party_export, ThirdPartyImportReport, ThirdPartySource,
+};
+use aoc_storage::{legacy_import_tables, LegacyImportOptions, LegacyImportReport, MindStore};
+use chrono::Utc;
+use clap::{Args, ValueEnum};
+use serde_json::{json, Value};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    doctor::{error_count, finding_json, print_report},
+    mind_store::StoreArgs,
+    output::{json_mode, print_json, Severity, SeverityExit},
+};
+
+#[derive(Args, Debug)]
+pub struct ImportArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Format of the source being imported.
+    #[arg(long, value_enum)]
+    pub from: ImportSourceArg,
+    /// Legacy Mind database, mem0 export JSON, or Letta agent file.
+    pub path: PathBuf,
+    /// Report what would be imported without writing to the store.
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+    /// Legacy table to copy (repeatable); all tables by default.
+    #[arg(long = "table")]
+    pub tables: Vec<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportSourceArg {
+    Legacy,
+    Mem0,
+    Letta,
+}
+
+enum ImportOutcome {
+    Legacy(LegacyImportReport),
+    ThirdParty(ThirdPartySource, ThirdPartyImportReport),
+}
+
+pub fn handle_import_command(args: ImportArgs) -> Result<()> {
+    if !args.path.exists() {
+        bail!("import source {} does not exist", args.path.display());
+    }
+    let (store, store_path) = args.store.open_writer()?;
+    let outcome = match args.from {
+        ImportSourceArg::Legacy => {
+            if let Some(unknown) = args
+                .tables
+                .iter()
+                .find(|table| !legacy_import_tables().contains(&table.as_str()))
+            {
+                bail!(
+                    "unknown table '{unknown}'; legacy imports copy: {}",
+                    legacy_import_tables().join(", ")
+                );
+            }
+            if args.path == store_path {
+                bail!("refusing to import the store into itself");
+            }
+            ImportOutcome::Legacy(
+                store
+                    .import_legacy_store_with(
+                        &args.path,
+                        &LegacyImportOptions {
+                            tables: args.tables.clone(),
+                            dry_run: args.dry_run,
+                        },
+                    )
+                    .with_context(|| format!("import {}", args.path.display()))?,
+            )
+        }
+        ImportSourceArg::Mem0 | ImportSourceArg::Letta => {
+            if !args.tables.is_empty() {
+                bail!("--table only applies to --from legacy");
+            }
+            let source = match args.from {
+                ImportSourceArg::Mem0 => ThirdPartySource::Mem0,
+                _ => ThirdPartySource::Letta,
+            };
+            ImportOutcome::ThirdParty(
+                source,
+                import_third_party(&store, source, &args.path, args.dry_run)?,
+            )
+        }
+    };
+
+    let summary = outcome_json(&outcome);
+    if args.dry_run {
+        if json_mode() {
+            return print_json(&json!({ "store_path": store_path, "import": summary }));
+        }
+        print_outcome(&outcome);
+        println!("dry run: nothing written");
+        return Ok(());
+    }
+
+    let report = store
+        .check_integrity(Utc::now())
+        .context("check store after import")?;
+    if json_mode() {
+        print_json(&json!({
+            "store_path": store_path,
+            "import": summary,
+            "healthy": !report.has_errors(),
+            "findings": report.findings.iter().map(finding_json).collect::<Vec<_>>(),
+        }))?;
+    } else {
+        print_outcome(&outcome);
+        print_report(&store_path.display().to_string(), &report, None);
+    }
+    let errors = error_count(&report);
+    if errors > 0 {
+        return Err(SeverityExit::new(
+            Severity::Error,
+            format!("import finished but doctor found {errors} error finding(s)"),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn import_third_party(
+    store: &MindStore,
+    source: ThirdPartySource,
+    path: &Path,
+    dry_run: bool,
+) -> Result<ThirdPartyImportReport> {
+    let body = fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+    let memories = parse_third_party_export(source, &body, Utc::now())?;
+    Ok(import_third_party_memories(
+        store, source, &memories, dry_run,
+    )?)
+}
+
+fn print_outcome(outcome: &ImportOutcome) {
+    match outcome {
+        ImportOutcome::Legacy(report) => {
+            println!(
+                "legacy: {} row(s) from {} of {} table(s)",
+                report.rows_imported, report.tables_imported, report.tables_scanned
+            );
+            for (table, rows) in &report.rows_by_table {
+                println!("  {table:<28} {rows:>8}");
+            }
+        }
+        ImportOutcome::ThirdParty(source, report) => println!(
+            "{}: {} memories read, {} imported into {} conversation(s), {} already present",
+            source.as_str(),
+            report.memories,
+            report.imported,
+            report.conversations,
+            report.duplicates
+        ),
+    }
+}
+
+fn outcome_json(outcome: &ImportOutcome) -> Value {
+    match outcome {
+        ImportOutcome::Legacy(report) => json!({
+            "source": "legacy",
+            "dry_run": report.dry_run,
+            "tables_scanned": report.tables_scanned,
+            "tables_imported": report.tables_imported,
+            "rows_imported": report.rows_imported,
+            "rows_by_table": report
+                .rows_by_table
+                .iter()
+                .map(|(table, rows)| json!({ "table": table, "rows": rows }))
+                .collect::<Vec<_>>(),
+        }),
+        ImportOutcome::ThirdParty(source, report) => json!({
+            "source": source.as_str(),
+            "dry_run": report.dry_run,
+            "memories": report.memories,
+            "imported": report.imported,
+            "duplicates": report.duplicates,
+            "conversations": report.conversations,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcome_json_lists_legacy_rows_per_table() {
+        let outcome = ImportOutcome::Legacy(LegacyImportReport {
+            tables_scanned: 2,
+            tables_imported: 1,
+            rows_imported: 3,
+            rows_by_table: vec![("raw_events", 3)],
+            dry_run: true,
+        });
+        let value = outcome_json(&outcome);
+        assert_eq!(value["source"], "legacy");
+        assert_eq!(value["rows_by_table"][0]["table"], "raw_events");
+        assert_eq!(value["rows_by_table"][0]["rows"], 3);
+
+        let outcome =
+            ImportOutcome::ThirdParty(ThirdPartySource::Letta, ThirdPartyImportReport::default());
+        assert_eq!(outcome_json(&outcome)["source"], "letta");
+    }
+}
@@ -0,0 +1,318 @@
+use anyhow::{bail, Context, Result};
+use aoc_segment_routing::SegmentRoutingConfig;
+use aoc_storage::IntegritySeverity;
+use chrono::Utc;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    doctor::finding_json,
+    mind_store::StoreArgs,
+    output::{json_mode, print_change, Severity, SeverityExit},
+};
+
+const CONFIG_PATH: &str = ".aoc/aoc.toml";
+const MAX_BPS: u16 = 10_000;
+
+#[derive(Args, Debug)]
+pub struct InitArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Session adapter that feeds the Mind pipeline.
+    #[arg(long, value_enum)]
+    pub adapter: Option<AdapterKind>,
+    /// Session directory for the adapter; the adapter default is used when omitted.
+    #[arg(long)]
+    pub sessions_dir: Option<PathBuf>,
+    /// Segment to register (repeatable). Defaults to the built-in segment set.
+    #[arg(long = "segment")]
+    pub segments: Vec<String>,
+    /// Accept defaults for anything not given on the command line.
+    #[arg(long, short = 'y', default_value_t = false)]
+    pub yes: bool,
+    /// Overwrite an existing config and write despite doctor errors.
+    #[arg(long, default_value_t = false)]
+    pub force: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdapterKind {
+    Pi,
+    Opencode,
+    None,
+}
+
+impl AdapterKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            AdapterKind::Pi => "pi",
+            AdapterKind::Opencode => "opencode",
+            AdapterKind::None => "none",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ProjectConfig {
+    mind: MindSection,
+    adapter: AdapterSection,
+    routing: SegmentRoutingConfig,
+}
+
+#[derive(Debug, Serialize)]
+struct MindSection {
+    store_path: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+struct AdapterSection {
+    kind: AdapterKind,
+    sessions_dir: Option<PathBuf>,
+}
+
+pub fn handle_init_command(args: InitArgs) -> Result<()> {
+    let project_root = args.store.project_root()?;
+    let config_path = project_root.join(CONFIG_PATH);
+    if config_path.exists() && !args.force {
+        bail!(
+            "{} already exists (use --force to overwrite)",
+            config_path.display()
+        );
+    }
+
+    let interactive = !args.yes && !json_mode() && io::stdin().is_terminal();
+    // An existing config may be the thing being repaired, so do not read it.
+    let default_store = args
+        .store
+        .explicit_store_path()
+        .unwrap_or_else(|| aoc_mind::mind_store_path(&project_root));
+    let store_path = if interactive {
+        PathBuf::from(prompt("Mind store path", &default_store.to_string_lossy())?)
+    } else {
+        default_store
+    };
+    let adapter = match args.adapter {
+        Some(adapter) => adapter,
+        None if interactive => {
+            let answer = prompt("Session adapter (pi/opencode/none)", "pi")?;
+            AdapterKind::from_str(&answer, true)
+                .map_err(|_| anyhow::anyhow!("unknown adapter '{answer}'"))?
+        }
+        None => AdapterKind::Pi,
+    };
+    let mut segments = args.segments.clone();
+    if segments.is_empty() && interactive {
+        let defaults = default_segments().join(",");
+        segments = prompt("Segments (comma separated)", &defaults)?
+            .split(',')
+            .map(str::to_string)
+            .collect();
+    }
+
+    let routing = routing_config(&segments);
+    let problems = validate_routing(&routing);
+    if !problems.is_empty() {
+        bail!("invalid routing config: {}", problems.join("; "));
+    }
+    if let Some(dir) = args.sessions_dir.as_ref().filter(|dir| !dir.is_dir()) {
+        bail!("sessions directory {} does not exist", dir.display());
+    }
+
+    let store_args = StoreArgs {
+        store: Some(store_path.clone()),
+        project_root: Some(project_root.clone()),
+    };
+    let (store, store_path) = store_args.open()?;
+    let report = store
+        .check_integrity(Utc::now())
+        .context("check mind store")?;
+    let errors = report
+        .findings
+        .iter()
+        .filter(|finding| finding.severity == IntegritySeverity::Error)
+        .count();
+    if errors > 0 && !args.force {
+        if !json_mode() {
+            for finding in &report.findings {
+                eprintln!(
+                    "[{}] {} {}: {}",
+                    finding.severity.as_str(),
+                    finding.check.as_str(),
+                    finding.subject,
+                    finding.detail
+                );
+            }
+        }
+        return Err(SeverityExit::new(
+            Severity::Error,
+            format!(
+                "mind store {} has {errors} error finding(s); run `aoc doctor` or pass --force",
+                store_path.display()
+            ),
+        )
+        .into());
+    }
+
+    let config = ProjectConfig {
+        mind: MindSection {
+            store_path: store_path.clone(),
+        },
+        adapter: AdapterSection {
+            kind: adapter,
+            sessions_dir: args.sessions_dir.clone(),
+        },
+        routing,
+    };
+    write_config(&config_path, &config)?;
+
+    let segment_ids = config
+        .routing
+        .segment_keywords
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    print_change(
+        "init",
+        format!(
+            "config: {}\nstore: {}\nadapter: {}\nsegments: {}\ndoctor: {}",
+            config_path.display(),
+            store_path.display(),
+            adapter.as_str(),
+            segment_ids.join(", "),
+            if report.is_clean() {
+                "ok".to_string()
+            } else {
+                format!("{} finding(s)", report.findings.len())
+            }
+        ),
+        json!({
+            "config_path": config_path,
+            "config": config,
+            "findings": report.findings.iter().map(finding_json).collect::<Vec<_>>(),
+        }),
+    )
+}
+
+fn prompt(label: &str, default: &str) -> Result<String> {
+    print!("{label} [{default}]: ");
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    let answer = line.trim();
+    Ok(if answer.is_empty() {
+        default.to_string()
+    } else {
+        answer.to_string()
+    })
+}
+
+fn default_segments() -> Vec<String> {
+    SegmentRoutingConfig::default()
+        .segment_keywords
+        .into_keys()
+        .collect()
+}
+
+/// Built-in keywords carry over for known segments; new segments start with
+/// their own name as the only keyword.
+fn routing_config(segments: &[String]) -> SegmentRoutingConfig {
+    let mut config = SegmentRoutingConfig::default();
+    let mut segments = segments
+        .iter()
+        .map(|segment| segment.trim().to_ascii_lowercase())
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    segments.sort();
+    segments.dedup();
+    if segments.is_empty() {
+        return config;
+    }
+    let mut defaults = std::mem::take(&mut config.segment_keywords);
+    config.segment_keywords = segments
+        .into_iter()
+        .map(|segment| {
+            let keywords = defaults
+                .remove(&segment)
+                .unwrap_or_else(|| vec![segment.clone()]);
+            (segment, keywords)
+        })
+        .collect::<BTreeMap<_, _>>();
+    config
+        .tag_to_segment
+        .retain(|_, segment| config.segment_keywords.contains_key(segment));
+    config
+}
+
+fn validate_routing(config: &SegmentRoutingConfig) -> Vec<String> {
+    let mut problems = Vec::new();
+    if config.low_confidence_threshold_bps > MAX_BPS {
+        problems.push(format!("low_confidence_threshold_bps must be <= {MAX_BPS}"));
+    }
+    if config.ambiguous_delta_bps > MAX_BPS {
+        problems.push(format!("ambiguous_delta_bps must be <= {MAX_BPS}"));
+    }
+    if config.default_global_segment == config.default_uncertain_segment {
+        problems.push("default_global_segment and default_uncertain_segment must differ".into());
+    }
+    for (segment, keywords) in &config.segment_keywords {
+        if !segment
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        {
+            problems.push(format!(
+                "segment '{segment}' may only use letters, digits, '-' and '_'"
+            ));
+        }
+        if keywords.iter().all(|keyword| keyword.trim().is_empty()) {
+            problems.push(format!("segment '{segment}' has no keywords"));
+        }
+    }
+    problems
+}
+
+fn write_config(path: &Path, config: &ProjectConfig) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let body = toml::to_string_pretty(config).context("serialize project config")?;
+    fs::write(path, format!("# Generated by `aoc init`.\n\n{body}"))
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routing_config_keeps_known_keywords_and_rejects_bad_segment_ids() {
+        let config = routing_config(&["Mind".to_string(), "infra".to_string()]);
+        assert_eq!(
+            config.segment_keywords.keys().collect::<Vec<_>>(),
+            vec!["infra", "mind"]
+        );
+        assert_eq!(config.segment_keywords["infra"], vec!["infra"]);
+        assert!(config.segment_keywords["mind"].contains(&"reflection".to_string()));
+        assert_eq!(
+            config.tag_to_segment.get("mind").map(String::as_str),
+            Some("mind")
+        );
+        assert!(validate_routing(&config).is_empty());
+
+        let config = routing_config(&["backend".to_string()]);
+        assert!(config.tag_to_segment.is_empty());
+
+        let mut config = routing_config(&["ops team".to_string()]);
+        config.ambiguous_delta_bps = 12_000;
+        let problems = validate_routing(&config);
+        assert_eq!(problems.len(), 2, "{problems:?}");
+    }
+}
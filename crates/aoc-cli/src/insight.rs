@@ -11,6 +11,7 @@ use aoc_core::{
     provenance_contracts::{MindProvenanceExport, MindProvenanceQueryRequest},
 };
 
+use crate::output::json_mode;
 use crate::overseer::{
     request_command_result, resolve_pulse_socket_path, resolve_session_id, CommandResultView,
 };
@@ -39,9 +40,6 @@ pub struct RetrieveArgs {
     /// Read timeout in milliseconds.
     #[arg(long, default_value_t = 3000)]
     pub timeout_ms: u64,
-    /// Print raw JSON payload.
-    #[arg(long, default_value_t = false)]
-    pub json: bool,
     #[arg(long, value_enum, default_value_t = ScopeArg::Auto)]
     pub scope: ScopeArg,
     #[arg(long, value_enum, default_value_t = ModeArg::Brief)]
@@ -68,9 +66,6 @@ pub struct ProvenanceArgs {
     /// Read timeout in milliseconds.
     #[arg(long, default_value_t = 3000)]
     pub timeout_ms: u64,
-    /// Print raw JSON payload.
-    #[arg(long, default_value_t = false)]
-    pub json: bool,
     #[arg(long)]
     pub project_root: Option<String>,
     #[arg(long)]
@@ -111,9 +106,6 @@ pub struct StatusArgs {
     /// Read timeout in milliseconds.
     #[arg(long, default_value_t = 3000)]
     pub timeout_ms: u64,
-    /// Print raw JSON payload.
-    #[arg(long, default_value_t = false)]
-    pub json: bool,
 }
 
 #[derive(Clone, Copy, Debug, ValueEnum)]
@@ -177,7 +169,7 @@ fn handle_retrieve(args: RetrieveArgs) -> Result<()> {
             max_results: args.max_results,
         })?,
     )?;
-    if args.json {
+    if json_mode() {
         print_command_result_json(&result)?;
         return Ok(());
     }
@@ -230,7 +222,7 @@ fn handle_provenance(args: ProvenanceArgs) -> Result<()> {
             max_edges: args.max_edges,
         })?,
     )?;
-    if args.json {
+    if json_mode() {
         print_command_result_json(&result)?;
         return Ok(());
     }
@@ -268,7 +260,7 @@ fn handle_status(args: StatusArgs) -> Result<()> {
         "insight_status",
         serde_json::json!({}),
     )?;
-    if args.json {
+    if json_mode() {
         print_command_result_json(&result)?;
         return Ok(());
     }
@@ -0,0 +1,539 @@
+//! `aoc live`: the whole pipeline in the foreground while you work.
+//!
+//! Every poll tails changed session files into the store, stamps the
+//! conversations that moved with the project's Taskmaster tag and tasks, runs
+//! the observer once a conversation crosses its T1 token threshold, then
+//! re-attributes and re-routes them, promoting globally routed artifacts
+//! when a global Mind is layered in. `--routing` points at a routing TOML
+//! that is re-read whenever it changes; a broken edit is reported and the
+//! previous routing stays in effect. With `[[webhooks]]` or
+//! `[[event_sinks]]` configured it also publishes dead-lettered jobs, canon
+//! revisions, exhausted budgets, and (to targets that ask for it) a daily
+//! digest through each of them: webhooks as AOC JSON or Slack/Discord
+//! messages, NATS subjects, or Redis streams. Each step prints one feed line; in
+//! JSON mode each line is a standalone JSON object (JSONL), since the command
+//! never finishes a single document.
+
+use anyhow::{bail, Context, Result};
+use aoc_config::AocConfig;
+use aoc_mind::{
+    evaluate_t1_token_threshold, DeterministicDistiller, DistillationConfig, EventSink,
+    LayeredMind, PipelineEventWatcher, SessionFileWatcher, T1ThresholdDecision, TaskmasterWatcher,
+    WebhookDispatcher,
+};
+use aoc_pi_adapter::{IngestionOptions, PiSessionIngestor};
+use aoc_segment_routing::{RoutingConfigWatcher, SegmentRouter};
+use aoc_storage::MindStore;
+use aoc_task_attribution::TaskAttributionEngine;
+use chrono::{DateTime, Utc};
+use clap::Args;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{collections::BTreeSet, path::PathBuf, thread, time::Duration};
+
+use crate::{mind_store::StoreArgs, output::json_mode};
+
+#[derive(Args, Debug)]
+pub struct LiveArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Session directory (or single session file) to tail.
+    #[arg(long)]
+    pub watch: PathBuf,
+    #[arg(long, default_value = "aoc-live")]
+    pub agent_id: String,
+    #[arg(long, default_value_t = 2_000)]
+    pub interval_ms: u64,
+    /// Run a single pass and exit.
+    #[arg(long, default_value_t = false)]
+    pub once: bool,
+    /// Routing TOML (the keys of aoc.toml's `[routing]`), reloaded when it
+    /// changes.
+    #[arg(long)]
+    pub routing: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LiveStage {
+    Ingest,
+    Taskmaster,
+    Observer,
+    Attribution,
+    Routing,
+    Webhook,
+    Sink,
+}
+
+impl LiveStage {
+    fn as_str(self) -> &'static str {
+        match self {
+            LiveStage::Ingest => "ingest",
+            LiveStage::Taskmaster => "taskmaster",
+            LiveStage::Observer => "observer",
+            LiveStage::Attribution => "attribution",
+            LiveStage::Routing => "routing",
+            LiveStage::Webhook => "webhook",
+            LiveStage::Sink => "sink",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveEvent {
+    pub at: DateTime<Utc>,
+    pub stage: LiveStage,
+    pub conversation_id: Option<String>,
+    pub summary: String,
+    pub detail: Value,
+}
+
+struct LivePipeline {
+    agent_id: String,
+    watcher: SessionFileWatcher,
+    taskmaster: TaskmasterWatcher,
+    ingestor: PiSessionIngestor,
+    distill: DistillationConfig,
+    engine: TaskAttributionEngine,
+    router: SegmentRouter,
+    routing_file: Option<RoutingConfigWatcher>,
+    /// Global Mind that globally routed artifacts are promoted into.
+    global: Option<MindStore>,
+    pipeline_events: PipelineEventWatcher,
+    sinks: Vec<Box<dyn EventSink>>,
+}
+
+impl LivePipeline {
+    fn new(
+        watch: PathBuf,
+        project_root: PathBuf,
+        agent_id: String,
+        config: AocConfig,
+    ) -> Result<Self> {
+        let mut sinks: Vec<Box<dyn EventSink>> = Vec::new();
+        if !config.webhooks.is_empty() {
+            sinks.push(Box::new(WebhookDispatcher::new(config.webhooks.clone())));
+        }
+        for (index, sink) in config.event_sinks.iter().enumerate() {
+            let sink = sink
+                .build()
+                .map_err(|err| anyhow::anyhow!("event_sinks[{index}]: {err}"))?;
+            sinks.push(sink);
+        }
+        Ok(Self {
+            agent_id,
+            watcher: SessionFileWatcher::new(Some(watch)),
+            taskmaster: TaskmasterWatcher::new(project_root),
+            ingestor: PiSessionIngestor::new(IngestionOptions {
+                policy: config.compaction_policy()?,
+            }),
+            engine: TaskAttributionEngine::new(config.attribution_config()?),
+            router: SegmentRouter::new(config.routing),
+            routing_file: None,
+            global: None,
+            distill: config.distillation,
+            pipeline_events: PipelineEventWatcher::default(),
+            sinks,
+        })
+    }
+
+    /// Applies the routing file if it changed since the last pass.
+    fn reload_routing(&mut self) -> Option<LiveEvent> {
+        let watcher = self.routing_file.as_mut()?;
+        let path = watcher.path().display().to_string();
+        match watcher.poll()? {
+            Ok(config) => {
+                self.router.set_config(config);
+                Some(live_event(
+                    LiveStage::Routing,
+                    None,
+                    format!("routing config reloaded from {path}"),
+                    json!({ "path": path }),
+                ))
+            }
+            Err(err) => Some(error_event(
+                LiveStage::Routing,
+                None,
+                format!("{err}; keeping the previous routing config"),
+            )),
+        }
+    }
+
+    /// One ingest → observer → attribution → routing sweep over whatever
+    /// changed since the previous pass. Stage failures become feed events so a
+    /// bad file does not stop the loop.
+    fn pass(&mut self, store: &MindStore) -> Vec<LiveEvent> {
+        let mut events = Vec::new();
+        events.extend(self.reload_routing());
+        let mut touched = BTreeSet::new();
+        let files = self.watcher.poll();
+        // One span per pass that saw changes, so ingest through routing for
+        // the same files lands in a single trace.
+        let _pass = (!files.is_empty()).then(|| {
+            tracing::info_span!("live_pass", stage = "live", files = files.len()).entered()
+        });
+        for file in files {
+            match self
+                .ingestor
+                .ingest_session_file(store, &self.agent_id, &file)
+            {
+                Ok(report) if report.processed_raw_events == 0 => {}
+                Ok(report) => {
+                    touched.insert(report.conversation_id.clone());
+                    events.push(live_event(
+                        LiveStage::Ingest,
+                        Some(&report.conversation_id),
+                        format!(
+                            "+{} raw, +{} t0 from {}",
+                            report.processed_raw_events,
+                            report.produced_t0_events,
+                            file.display()
+                        ),
+                        json!({ "path": file, "report": report }),
+                    ));
+                }
+                Err(err) => events.push(error_event(
+                    LiveStage::Ingest,
+                    None,
+                    format!("{}: {err}", file.display()),
+                )),
+            }
+        }
+
+        match self
+            .taskmaster
+            .poll(store, touched.iter().map(String::as_str))
+        {
+            Ok(0) => {}
+            Ok(written) => {
+                let snapshot = self.taskmaster.snapshot();
+                events.push(live_event(
+                    LiveStage::Taskmaster,
+                    None,
+                    format!(
+                        "tag {} ({} in progress) -> {written} conversation(s)",
+                        snapshot.map_or("-", |snapshot| snapshot.active_tag.as_str()),
+                        snapshot.map_or(0, |snapshot| snapshot.in_progress.len())
+                    ),
+                    json!({
+                        "active_tag": snapshot.map(|snapshot| &snapshot.active_tag),
+                        "in_progress": snapshot.map(|snapshot| &snapshot.in_progress),
+                        "conversations": written,
+                    }),
+                ));
+            }
+            Err(err) => events.push(error_event(LiveStage::Taskmaster, None, err.to_string())),
+        }
+
+        let distiller = DeterministicDistiller::new(self.distill.clone());
+        for conversation_id in &touched {
+            let decision = evaluate_t1_token_threshold(
+                store,
+                conversation_id,
+                self.distill.t1_target_tokens,
+                self.distill.t1_hard_cap_tokens,
+            );
+            match decision {
+                Ok(T1ThresholdDecision::NeedsRun { progress, reason }) => {
+                    match distiller.distill_conversation(store, conversation_id) {
+                        Ok(report) => events.push(live_event(
+                            LiveStage::Observer,
+                            Some(conversation_id),
+                            format!(
+                                "{reason}: +{} t1, +{} t2",
+                                report.t1_artifacts_written, report.t2_artifacts_written
+                            ),
+                            json!({ "reason": reason, "progress": progress, "report": report }),
+                        )),
+                        Err(err) => events.push(error_event(
+                            LiveStage::Observer,
+                            Some(conversation_id),
+                            err.to_string(),
+                        )),
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => events.push(error_event(
+                    LiveStage::Observer,
+                    Some(conversation_id),
+                    err.to_string(),
+                )),
+            }
+
+            match self.engine.attribute_conversation(store, conversation_id) {
+                Ok(report) if report.links_written == 0 => {}
+                Ok(report) => events.push(live_event(
+                    LiveStage::Attribution,
+                    Some(conversation_id),
+                    format!(
+                        "{} link(s) over {} artifact(s)",
+                        report.links_written, report.artifacts_processed
+                    ),
+                    json!({ "report": report }),
+                )),
+                Err(err) => events.push(error_event(
+                    LiveStage::Attribution,
+                    Some(conversation_id),
+                    err.to_string(),
+                )),
+            }
+
+            match self.router.route_conversation(store, conversation_id) {
+                Ok(report) if report.routes_written == 0 => {}
+                Ok(report) => {
+                    let mut summary = format!(
+                        "{} route(s), {} uncertain",
+                        report.routes_written, report.uncertain_fallbacks
+                    );
+                    let mut detail = json!({ "report": report });
+                    match LayeredMind::new(store, self.global.as_ref())
+                        .promote_global_routes(conversation_id, &self.router.global_segment())
+                    {
+                        Ok(promotion) if promotion.promoted + promotion.updated == 0 => {}
+                        Ok(promotion) => {
+                            summary.push_str(&format!(
+                                ", {} promoted to global",
+                                promotion.promoted + promotion.updated
+                            ));
+                            detail["promotion"] = json!(promotion);
+                        }
+                        Err(err) => events.push(error_event(
+                            LiveStage::Routing,
+                            Some(conversation_id),
+                            err.to_string(),
+                        )),
+                    }
+                    events.push(live_event(
+                        LiveStage::Routing,
+                        Some(conversation_id),
+                        summary,
+                        detail,
+                    ))
+                }
+                Err(err) => events.push(error_event(
+                    LiveStage::Routing,
+                    Some(conversation_id),
+                    err.to_string(),
+                )),
+            }
+        }
+        if !self.sinks.is_empty() {
+            self.publish_pipeline_events(store, &mut events);
+        }
+        events
+    }
+
+    fn publish_pipeline_events(&mut self, store: &MindStore, events: &mut Vec<LiveEvent>) {
+        let pipeline_events = match self.pipeline_events.poll(store, Utc::now()) {
+            Ok(pipeline_events) => pipeline_events,
+            Err(err) => {
+                events.push(error_event(LiveStage::Sink, None, err.to_string()));
+                return;
+            }
+        };
+        for pipeline_event in pipeline_events {
+            let deliveries = self
+                .sinks
+                .iter_mut()
+                .flat_map(|sink| sink.publish(&pipeline_event))
+                .collect::<Vec<_>>();
+            for delivery in deliveries {
+                let outcome = if delivery.delivered {
+                    format!("delivered after {} attempt(s)", delivery.attempts)
+                } else {
+                    format!(
+                        "failed: {}",
+                        delivery.error.as_deref().unwrap_or("no response")
+                    )
+                };
+                let stage = if delivery.sink == "webhook" {
+                    LiveStage::Webhook
+                } else {
+                    LiveStage::Sink
+                };
+                events.push(live_event(
+                    stage,
+                    None,
+                    format!(
+                        "{} {} -> {} {outcome}",
+                        delivery.kind.as_str(),
+                        delivery.subject,
+                        delivery.target
+                    ),
+                    json!({ "delivery": delivery }),
+                ));
+            }
+        }
+    }
+}
+
+fn live_event(
+    stage: LiveStage,
+    conversation_id: Option<&str>,
+    summary: String,
+    detail: Value,
+) -> LiveEvent {
+    LiveEvent {
+        at: Utc::now(),
+        stage,
+        conversation_id: conversation_id.map(str::to_string),
+        summary,
+        detail,
+    }
+}
+
+fn error_event(stage: LiveStage, conversation_id: Option<&str>, error: String) -> LiveEvent {
+    live_event(
+        stage,
+        conversation_id,
+        format!("error: {error}"),
+        json!({ "error": error }),
+    )
+}
+
+fn print_live_event(event: &LiveEvent) -> Result<()> {
+    if json_mode() {
+        println!("{}", serde_json::to_string(event)?);
+    } else {
+        println!(
+            "{} {:<11} {} {}",
+            event.at.format("%H:%M:%S"),
+            event.stage.as_str(),
+            event.conversation_id.as_deref().unwrap_or("-"),
+            event.summary
+        );
+    }
+    Ok(())
+}
+
+pub fn handle_live_command(args: LiveArgs) -> Result<()> {
+    if !args.watch.exists() {
+        bail!("watch path {} does not exist", args.watch.display());
+    }
+    let config = args.store.config()?;
+    let (store, store_path) = args.store.open()?;
+    if !json_mode() {
+        println!(
+            "store: {}\nwatching: {}",
+            store_path.display(),
+            args.watch.display()
+        );
+    }
+    let mut pipeline = LivePipeline::new(
+        args.watch.clone(),
+        args.store.project_root()?,
+        args.agent_id.clone(),
+        config,
+    )?;
+    pipeline.global = args.store.open_global()?;
+    if let Some(path) = args.routing {
+        let mut watcher = RoutingConfigWatcher::new(path);
+        if let Some(config) = watcher.poll() {
+            pipeline
+                .router
+                .set_config(config.context("load routing file")?);
+        }
+        pipeline.routing_file = Some(watcher);
+    }
+    let interval = Duration::from_millis(args.interval_ms.max(100));
+    loop {
+        for event in pipeline.pass(&store) {
+            print_live_event(&event).context("write live feed")?;
+        }
+        if args.once {
+            return Ok(());
+        }
+        thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const SESSION: &str = r#"{"type":"session","version":3,"id":"live-1","timestamp":"2024-12-03T14:00:00.000Z","cwd":"/tmp/proj"}
+{"type":"message","id":"u1","parentId":null,"timestamp":"2024-12-03T14:00:01.000Z","message":{"role":"user","content":"hello"}}
+"#;
+
+    #[test]
+    fn pass_feeds_only_new_session_lines() {
+        let dir = std::env::temp_dir().join(format!("aoc-cli-live-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("create dir");
+        let session = dir.join("live-1.jsonl");
+        fs::write(&session, SESSION).expect("write session");
+
+        let store = MindStore::open_in_memory().expect("store");
+        let mut pipeline = LivePipeline::new(
+            dir.clone(),
+            dir.clone(),
+            "test".to_string(),
+            AocConfig::default(),
+        )
+        .expect("pipeline");
+        let events = pipeline.pass(&store);
+        assert_eq!(events[0].stage, LiveStage::Ingest);
+        assert_eq!(events[0].conversation_id.as_deref(), Some("pi:live-1"));
+        assert!(events
+            .iter()
+            .all(|event| !event.summary.starts_with("error")));
+        assert!(pipeline.pass(&store).is_empty());
+
+        let reply = r#"{"type":"message","id":"a1","parentId":"u1","timestamp":"2024-12-03T14:00:02.000Z","message":{"role":"assistant","content":[{"type":"text","text":"hi"}]}}"#;
+        fs::write(&session, format!("{SESSION}{reply}\n")).expect("append reply");
+        let events = pipeline.pass(&store);
+        assert_eq!(events[0].stage, LiveStage::Ingest);
+        assert!(events[0].summary.starts_with("+1 raw"), "{events:?}");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn routing_file_reloads_and_bad_edits_keep_the_previous_config() {
+        let dir = std::env::temp_dir().join(format!("aoc-cli-routing-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("create dir");
+        let routing = dir.join("routing.toml");
+        fs::write(&routing, "[segment_keywords]\ninfra = [\"terraform\"]\n").expect("write");
+
+        let store = MindStore::open_in_memory().expect("store");
+        let mut pipeline = LivePipeline::new(
+            dir.clone(),
+            dir.clone(),
+            "test".to_string(),
+            AocConfig::default(),
+        )
+        .expect("pipeline");
+        pipeline.routing_file = Some(RoutingConfigWatcher::new(&routing));
+        let events = pipeline.pass(&store);
+        assert_eq!(events[0].stage, LiveStage::Routing);
+        assert!(events[0].summary.starts_with("routing config reloaded"));
+        assert!(pipeline
+            .router
+            .config()
+            .segment_keywords
+            .contains_key("infra"));
+        assert!(pipeline.pass(&store).is_empty(), "unchanged file");
+
+        fs::write(&routing, "default_uncertain_segment = \"global\"\n").expect("rewrite");
+        let events = pipeline.pass(&store);
+        assert!(
+            events[0]
+                .summary
+                .contains("keeping the previous routing config"),
+            "{events:?}"
+        );
+        assert_eq!(
+            pipeline.router.config().default_uncertain_segment,
+            "uncertain"
+        );
+        assert!(pipeline
+            .router
+            .config()
+            .segment_keywords
+            .contains_key("infra"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,163 @@
+//! Diagnostic logs and progress for long-running commands.
+//!
+//! Logs go to stderr so stdout only ever holds the command's report. The
+//! level comes from `--log-level`, then `AOC_LOG`, then `warn`; any
+//! `tracing` filter directive works (`aoc_mind=debug,info`). Library spans
+//! carry `stage`, `conversation_id`, and `job_id` fields; builds with the
+//! `otel` feature can also export them over OTLP (see `telemetry`).
+
+use clap::ValueEnum;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::{
+    io::IsTerminal,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+use crate::output::json_mode;
+
+pub const LOG_ENV: &str = "AOC_LOG";
+pub const LOG_FORMAT_ENV: &str = "AOC_LOG_FORMAT";
+
+static JSON_LOGS: AtomicBool = AtomicBool::new(false);
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    Human,
+    Json,
+}
+
+/// Resolves the format from the flag, falling back to `AOC_LOG_FORMAT`.
+pub fn resolve_log_format(flag: Option<LogFormat>) -> LogFormat {
+    flag.or_else(|| {
+        std::env::var(LOG_FORMAT_ENV)
+            .ok()
+            .and_then(|value| LogFormat::from_str(value.trim(), true).ok())
+    })
+    .unwrap_or(LogFormat::Human)
+}
+
+/// Keeps span export alive for the life of the process; drop it last so
+/// buffered spans are flushed.
+#[must_use]
+pub struct LoggingGuard {
+    #[cfg(feature = "otel")]
+    _telemetry: Option<crate::telemetry::TelemetryGuard>,
+}
+
+pub fn init_logging(format: LogFormat, level: Option<&str>) -> LoggingGuard {
+    let filter = level
+        .map(EnvFilter::new)
+        .or_else(|| EnvFilter::try_from_env(LOG_ENV).ok())
+        .unwrap_or_else(|| EnvFilter::new("warn"));
+    let fmt = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
+    let fmt = match format {
+        LogFormat::Human => fmt.boxed(),
+        LogFormat::Json => fmt.json().flatten_event(true).boxed(),
+    };
+    JSON_LOGS.store(format == LogFormat::Json, Ordering::Relaxed);
+    let registry = tracing_subscriber::registry().with(fmt.with_filter(filter));
+
+    #[cfg(feature = "otel")]
+    let (otel, telemetry) = match crate::telemetry::otel_layer() {
+        Some((layer, guard)) => (Some(layer), Some(guard)),
+        None => (None, None),
+    };
+    #[cfg(feature = "otel")]
+    let registry = registry.with(otel);
+
+    // A second init (tests, embedded use) keeps the first subscriber.
+    let _ = registry.try_init();
+    LoggingGuard {
+        #[cfg(feature = "otel")]
+        _telemetry: telemetry,
+    }
+}
+
+/// Counts items through a long operation. Draws a bar on an interactive
+/// stderr; otherwise (JSON output or logs, pipes) emits one `info` event per
+/// item so structured logs still show how far the run got.
+pub struct Progress {
+    stage: &'static str,
+    total: u64,
+    done: u64,
+    bar: Option<ProgressBar>,
+}
+
+impl Progress {
+    pub fn new(stage: &'static str, total: usize) -> Self {
+        let total = total as u64;
+        let interactive =
+            !json_mode() && !JSON_LOGS.load(Ordering::Relaxed) && std::io::stderr().is_terminal();
+        let bar = interactive.then(|| {
+            let bar = ProgressBar::new(total);
+            bar.set_style(
+                ProgressStyle::with_template("{prefix:>10} [{bar:30}] {pos}/{len} {wide_msg}")
+                    .expect("valid progress template")
+                    .progress_chars("=> "),
+            );
+            bar.set_prefix(stage);
+            bar
+        });
+        Self {
+            stage,
+            total,
+            done: 0,
+            bar,
+        }
+    }
+
+    /// Never draws a bar, e.g. for unattended runs on a terminal.
+    pub fn headless(mut self) -> Self {
+        if let Some(bar) = self.bar.take() {
+            bar.finish_and_clear();
+        }
+        self
+    }
+
+    pub fn advance(&mut self, item: &str) {
+        self.done += 1;
+        match &self.bar {
+            Some(bar) => {
+                bar.set_message(item.to_string());
+                bar.inc(1);
+            }
+            None => tracing::info!(
+                stage = self.stage,
+                done = self.done,
+                total = self.total,
+                item,
+                "progress"
+            ),
+        }
+    }
+
+    /// Prints a line without tearing the bar.
+    pub fn println(&self, line: impl AsRef<str>) {
+        match &self.bar {
+            Some(bar) => bar.println(line.as_ref()),
+            None => println!("{}", line.as_ref()),
+        }
+    }
+
+    pub fn finish(self) {
+        if let Some(bar) = self.bar {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_log_format_and_headless_progress() {
+        assert_eq!(resolve_log_format(Some(LogFormat::Json)), LogFormat::Json);
+        let mut progress = Progress::new("test", 2);
+        progress.advance("a");
+        progress.advance("b");
+        assert_eq!(progress.done, progress.total);
+        progress.finish();
+    }
+}
@@ -1,17 +1,60 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use serde_json::json;
+use std::process::ExitCode;
 
+mod agents;
+mod audit;
+mod bench;
+mod canon;
+mod compliance;
+mod config;
+mod decisions;
+mod diff;
+mod doctor;
 mod dox;
+mod export;
+mod import;
+mod init;
 mod insight;
+mod live;
+mod logging;
 mod map;
+mod memory_tool;
+mod migrate;
+mod mind_store;
+mod output;
 mod overseer;
+mod pipeline;
+mod prune;
+mod query;
+mod replay;
 mod rlm;
+mod serve;
+mod status;
+mod sync;
 mod task;
+mod tasks;
+#[cfg(feature = "otel")]
+mod telemetry;
+mod writer;
 
 #[derive(Parser)]
 #[command(name = "aoc")]
 #[command(about = "Agent Ops Cockpit CLI", long_about = None)]
 struct Cli {
+    /// Print one JSON document per command; exit code reflects severity.
+    #[arg(long, global = true, default_value_t = false)]
+    json: bool,
+    /// Config profile to apply (e.g. cheap, quality, offline). Overrides AOC_PROFILE.
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    /// Diagnostic log format on stderr. Overrides AOC_LOG_FORMAT.
+    #[arg(long, global = true, value_enum)]
+    log_format: Option<logging::LogFormat>,
+    /// Log filter, e.g. `info` or `aoc_mind=debug`. Overrides AOC_LOG.
+    #[arg(long, global = true)]
+    log_level: Option<String>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -23,6 +66,11 @@ enum Commands {
         #[command(subcommand)]
         action: task::TaskCommand,
     },
+    /// Summarize what the Mind recorded against each task
+    Tasks {
+        #[command(subcommand)]
+        action: tasks::TasksCommand,
+    },
     /// Manage memory
     Mem {
         #[command(subcommand)]
@@ -54,6 +102,67 @@ enum Commands {
         #[command(subcommand)]
         action: map::MapCommand,
     },
+    /// Inspect project canon and promote or revert its revisions
+    Canon {
+        #[command(subcommand)]
+        action: canon::CanonCommand,
+    },
+    /// Record, list, and supersede project decisions in the Mind decision log
+    Decisions {
+        #[command(subcommand)]
+        action: decisions::DecisionsCommand,
+    },
+    /// Register agents and compare their activity in the Mind
+    Agents {
+        #[command(subcommand)]
+        action: agents::AgentsCommand,
+    },
+    /// Answer an Anthropic memory tool call from the Mind
+    MemoryTool(memory_tool::MemoryToolArgs),
+    /// Set up project config, Mind store, routing, and adapter for this repo
+    Init(init::InitArgs),
+    /// Print the effective aoc.toml settings and where they came from
+    Config(config::ConfigArgs),
+    /// Tail a session directory through ingest, observer, attribution, and routing
+    Live(live::LiveArgs),
+    /// Print an at-a-glance health view of the project Mind store
+    Status(status::StatusArgs),
+    /// Check Mind store integrity and pipeline consistency
+    Doctor(doctor::DoctorArgs),
+    /// Back up the Mind store, apply pending schema migrations, and verify with doctor
+    Migrate(migrate::MigrateArgs),
+    /// Report and reclaim raw events and archive stale artifacts under a retention policy
+    Prune(prune::PruneArgs),
+    /// Compare conversations, artifacts, canon, and decisions between two Mind databases
+    Diff(diff::DiffArgs),
+    /// Search Mind artifacts across conversations
+    Query(query::QueryArgs),
+    /// Export Mind artifacts as chunked files, a markdown vault, a star schema, or a knowledge graph
+    Export(export::ExportArgs),
+    /// Enable and verify the tamper-evident hash chain over artifacts and canon
+    Audit {
+        #[command(subcommand)]
+        action: audit::AuditCommand,
+    },
+    /// Export, verify, and delete everything the Mind holds about a data subject
+    Compliance {
+        #[command(subcommand)]
+        action: compliance::ComplianceCommand,
+    },
+    /// Import a legacy Mind store or a mem0/Letta memory export, then run doctor
+    Import(import::ImportArgs),
+    /// Two-way sync with another Mind database or an `aoc serve` URL
+    Sync(sync::SyncArgs),
+    /// Time ingest, distillation, routing, and retrieval on a synthetic corpus
+    Bench(bench::BenchArgs),
+    /// Re-run compaction and distillation for a conversation in shadow mode
+    Replay(replay::ReplayArgs),
+    /// Serve the Mind over HTTP (read-only unless started with a token)
+    Serve(serve::ServeArgs),
+    /// Show which process holds the Mind write lease, or take over a crashed one
+    Writer(writer::WriterArgs),
+    #[command(flatten)]
+    Pipeline(pipeline::PipelineCommand),
 }
 
 #[derive(Subcommand)]
@@ -61,21 +170,59 @@ enum MemCommands {
     Add { content: String },
 }
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
     let cli = Cli::parse();
+    output::set_json_mode(cli.json);
+    let _logging = logging::init_logging(
+        logging::resolve_log_format(cli.log_format),
+        cli.log_level.as_deref(),
+    );
+    if let Some(profile) = &cli.profile {
+        // Exported rather than threaded through every handler so child
+        // processes (aocd, agent wrappers) pick the same profile up.
+        std::env::set_var(aoc_config::PROFILE_ENV, profile);
+    }
+    output::finish(run(cli.command))
+}
 
-    match cli.command {
+fn run(command: Commands) -> Result<()> {
+    match command {
         Commands::Task { action } => task::handle_task_command(action),
+        Commands::Tasks { action } => tasks::handle_tasks_command(action),
         Commands::Mem { action } => match action {
-            MemCommands::Add { content } => {
-                println!("Adding memory: {}", content);
-                Ok(())
-            }
+            MemCommands::Add { content } => output::print_change(
+                "mem.add",
+                format!("Adding memory: {}", content),
+                json!({ "content": content }),
+            ),
         },
         Commands::Dox { action } => dox::handle_dox_command(action),
         Commands::Rlm { action } => rlm::handle_rlm_command(action),
         Commands::Insight { action } => insight::handle_insight_command(action),
         Commands::Overseer { action } => overseer::handle_overseer_command(action),
         Commands::Map { action } => map::handle_map_command(action),
+        Commands::Canon { action } => canon::handle_canon_command(action),
+        Commands::Decisions { action } => decisions::handle_decisions_command(action),
+        Commands::Agents { action } => agents::handle_agents_command(action),
+        Commands::MemoryTool(args) => memory_tool::handle_memory_tool_command(args),
+        Commands::Init(args) => init::handle_init_command(args),
+        Commands::Config(args) => config::handle_config_command(args),
+        Commands::Live(args) => live::handle_live_command(args),
+        Commands::Status(args) => status::handle_status_command(args),
+        Commands::Doctor(args) => doctor::handle_doctor_command(args),
+        Commands::Migrate(args) => migrate::handle_migrate_command(args),
+        Commands::Prune(args) => prune::handle_prune_command(args),
+        Commands::Diff(args) => diff::handle_diff_command(args),
+        Commands::Query(args) => query::handle_query_command(args),
+        Commands::Export(args) => export::handle_export_command(args),
+        Commands::Audit { action } => audit::handle_audit_command(action),
+        Commands::Compliance { action } => compliance::handle_compliance_command(action),
+        Commands::Import(args) => import::handle_import_command(args),
+        Commands::Sync(args) => sync::handle_sync_command(args),
+        Commands::Bench(args) => bench::handle_bench_command(args),
+        Commands::Replay(args) => replay::handle_replay_command(args),
+        Commands::Serve(args) => serve::handle_serve_command(args),
+        Commands::Writer(args) => writer::handle_writer_command(args),
+        Commands::Pipeline(action) => pipeline::handle_pipeline_command(action),
     }
 }
@@ -2,11 +2,14 @@ use anyhow::{anyhow, bail, Context, Result};
 use chrono::Utc;
 use clap::{Args, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::output::{json_mode, print_change};
+
 const MAP_DIR: &str = ".aoc/map";
 const PAGES_DIR: &str = ".aoc/map/pages";
 const DIAGRAM_FILES_DIR: &str = ".aoc/map/diagrams";
@@ -88,10 +91,7 @@ pub struct NewArgs {
 }
 
 #[derive(Args, Debug)]
-pub struct ListArgs {
-    #[arg(long)]
-    pub json: bool,
-}
+pub struct ListArgs {}
 
 #[derive(Args, Debug)]
 pub struct BuildArgs {}
@@ -329,8 +329,11 @@ fn handle_init(args: InitArgs) -> Result<()> {
     write_if_missing_or_forced(&paths.readme_path, &starter_readme(), args.force)?;
     build_index(&paths)?;
 
-    println!("initialized {}", paths.map_dir.display());
-    Ok(())
+    print_change(
+        "map.init",
+        format!("initialized {}", paths.map_dir.display()),
+        json!({ "map_dir": paths.map_dir }),
+    )
 }
 
 fn handle_new(args: NewArgs) -> Result<()> {
@@ -399,18 +402,28 @@ fn handle_new(args: NewArgs) -> Result<()> {
         .with_context(|| format!("failed to write {}", diagram_path.display()))?;
 
     build_index(&paths)?;
-    println!("created {}", page_path.display());
-    println!("created {}", diagram_path.display());
-    Ok(())
+    print_change(
+        "map.new",
+        format!(
+            "created {}\ncreated {}",
+            page_path.display(),
+            diagram_path.display()
+        ),
+        json!({
+            "page_path": page_path,
+            "diagram_path": diagram_path,
+            "record": record,
+        }),
+    )
 }
 
-fn handle_list(args: ListArgs) -> Result<()> {
+fn handle_list(_args: ListArgs) -> Result<()> {
     let paths = MapPaths::from_root(resolve_project_root()?);
     migrate_legacy_workspace(&paths)?;
     let manifest = load_and_normalize_manifest(&paths)?;
     let diagrams = collect_diagrams(&paths, &manifest)?;
 
-    if args.json {
+    if json_mode() {
         println!("{}", serde_json::to_string_pretty(&diagrams)?);
         return Ok(());
     }
@@ -438,8 +451,11 @@ fn handle_build(_args: BuildArgs) -> Result<()> {
     migrate_legacy_workspace(&paths)?;
     ensure_dirs(&paths)?;
     build_index(&paths)?;
-    println!("built {}", paths.index_path.display());
-    Ok(())
+    print_change(
+        "map.build",
+        format!("built {}", paths.index_path.display()),
+        json!({ "index_path": paths.index_path }),
+    )
 }
 
 fn handle_serve(args: ServeArgs) -> Result<()> {
@@ -456,8 +472,15 @@ fn handle_serve(args: ServeArgs) -> Result<()> {
     let python = find_python()
         .ok_or_else(|| anyhow!("python3/python is required to serve AOC Map via aoc-map"))?;
 
-    println!("serving {} at {}", paths.map_dir.display(), url);
-    println!("press Ctrl-C to stop");
+    print_change(
+        "map.serve",
+        format!(
+            "serving {} at {}\npress Ctrl-C to stop",
+            paths.map_dir.display(),
+            url
+        ),
+        json!({ "map_dir": paths.map_dir, "url": url }),
+    )?;
 
     let status = Command::new(python)
         .args([
@@ -0,0 +1,57 @@
+use anyhow::{Context, Result};
+use aoc_mind::{MemoryToolCommand, MemoryToolError, MemoryToolShim};
+use chrono::Utc;
+use clap::Args;
+use serde_json::json;
+use std::io::Read;
+
+use crate::{
+    mind_store::StoreArgs,
+    output::{json_mode, print_json, Severity, SeverityExit},
+};
+
+#[derive(Args, Debug)]
+pub struct MemoryToolArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Tool input JSON, e.g. `{"command":"view","path":"/memories"}`; read
+    /// from stdin when omitted.
+    pub input: Option<String>,
+    /// Agent the writes are attributed to.
+    #[arg(long, default_value = "claude")]
+    pub agent: String,
+}
+
+/// Answers one memory tool call. The result text goes to stdout either way
+/// so a tool handler can hand it back to the model; tool errors exit 4.
+pub fn handle_memory_tool_command(args: MemoryToolArgs) -> Result<()> {
+    let input = match args.input {
+        Some(input) => input,
+        None => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .context("read tool input from stdin")?;
+            input
+        }
+    };
+    let command: MemoryToolCommand =
+        serde_json::from_str(&input).context("parse memory tool input")?;
+    let project_root = args.store.project_root()?;
+    let (store, _) = args.store.open()?;
+    let shim = MemoryToolShim::new(&store, &project_root, &args.agent);
+    let (content, is_error) = match shim.execute(command, Utc::now()) {
+        Ok(content) => (content, false),
+        Err(err @ MemoryToolError::Storage(_)) => return Err(err.into()),
+        Err(err) => (err.to_string(), true),
+    };
+    if json_mode() {
+        print_json(&json!({ "content": content, "is_error": is_error }))?;
+    } else {
+        println!("{content}");
+    }
+    if is_error {
+        return Err(SeverityExit::new(Severity::Error, "memory tool call failed").into());
+    }
+    Ok(())
+}
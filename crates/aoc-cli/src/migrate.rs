@@ -0,0 +1,304 @@
+use anyhow::{bail, Context, Result};
+use aoc_storage::{pending_migrations, BlobUpgradeReport, MindStore, MIND_SCHEMA_VERSION};
+use chrono::Utc;
+use clap::Args;
+use serde_json::json;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    doctor::finding_json,
+    mind_store::{open_writer_at, StoreArgs},
+    output::{json_mode, print_change, print_json},
+};
+
+#[derive(Args, Debug)]
+pub struct MigrateArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Print the pending plan and exit.
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+    /// Where to write the pre-migration backup; defaults to a timestamped file
+    /// next to the store.
+    #[arg(long)]
+    pub backup: Option<PathBuf>,
+    /// Also rewrite JSON blobs stored at an older schema version, instead of
+    /// leaving them to be upgraded on every read.
+    #[arg(long, default_value_t = false)]
+    pub upgrade_blobs: bool,
+    /// Write raw events to one table per month from now on and move existing
+    /// rows into them, so old months can be dropped whole with
+    /// `aoc prune --drop-partitions-before`.
+    #[arg(long, default_value_t = false)]
+    pub partition_raw_events: bool,
+}
+
+pub fn handle_migrate_command(args: MigrateArgs) -> Result<()> {
+    let store_path = args.store.store_path()?;
+    if !store_path.exists() {
+        bail!(
+            "no mind store at {}; run `aoc init` to create one",
+            store_path.display()
+        );
+    }
+    let from = MindStore::stored_schema_version(&store_path).context("read schema version")?;
+    if from > MIND_SCHEMA_VERSION {
+        bail!(
+            "store schema {from} is newer than this aoc build supports ({MIND_SCHEMA_VERSION}); upgrade aoc"
+        );
+    }
+    let plan = pending_migrations(from);
+    let steps = plan
+        .iter()
+        .map(|step| json!({ "version": step.version, "name": step.name }))
+        .collect::<Vec<_>>();
+
+    if plan.is_empty() && (args.upgrade_blobs || args.partition_raw_events) && !args.dry_run {
+        let extras = apply_extras(&store_path, &args)?;
+        return print_change(
+            "migrate",
+            format!("Schema is up to date; {}", extras.describe().join("; ")),
+            json!({
+                "store_path": store_path,
+                "from": from,
+                "to": MIND_SCHEMA_VERSION,
+                "applied": [],
+                "blobs_upgraded": extras.blobs.map(|report| report.upgraded),
+                "raw_events_partitioned": extras.partitioned,
+            }),
+        );
+    }
+
+    if plan.is_empty() || args.dry_run {
+        // Stale blob counts need the version columns, so only once migrated.
+        let stale_blobs = if plan.is_empty() {
+            Some(
+                MindStore::open(&store_path)
+                    .and_then(|store| store.stale_json_blobs())
+                    .context("count stale json blobs")?,
+            )
+        } else {
+            None
+        };
+        if json_mode() {
+            return print_json(&json!({
+                "store_path": store_path,
+                "from": from,
+                "to": MIND_SCHEMA_VERSION,
+                "pending": steps,
+                "applied": false,
+                "stale_blobs": stale_blobs,
+            }));
+        }
+        println!("store: {} (schema {from})", store_path.display());
+        if plan.is_empty() {
+            println!("schema is up to date");
+        }
+        for step in plan {
+            println!("  pending {:>3} {}", step.version, step.name);
+        }
+        for (kind, count) in stale_blobs.iter().flatten() {
+            if *count > 0 {
+                println!("  stale   {count:>3} {} blobs", kind.as_str());
+            }
+        }
+        return Ok(());
+    }
+
+    if !json_mode() {
+        println!("store: {} (schema {from})", store_path.display());
+        for step in plan {
+            println!("  apply {:>3} {}", step.version, step.name);
+        }
+    }
+    let backup = args
+        .backup
+        .clone()
+        .unwrap_or_else(|| default_backup_path(&store_path, from));
+    MindStore::backup_database(&store_path, &backup)
+        .with_context(|| format!("back up store to {}", backup.display()))?;
+
+    let migrated = apply_and_check(&store_path).and_then(|()| apply_extras(&store_path, &args));
+    match migrated {
+        Ok(extras) => {
+            let mut message = format!(
+                "Migrated schema {from} -> {MIND_SCHEMA_VERSION}; backup kept at {}",
+                backup.display()
+            );
+            for part in extras.describe() {
+                message.push_str(&format!("; {part}"));
+            }
+            print_change(
+                "migrate",
+                message,
+                json!({
+                    "store_path": store_path,
+                    "from": from,
+                    "to": MIND_SCHEMA_VERSION,
+                    "applied": steps,
+                    "backup": backup,
+                    "blobs_upgraded": extras.blobs.map(|report| report.upgraded),
+                    "raw_events_partitioned": extras.partitioned,
+                }),
+            )
+        }
+        Err(err) => {
+            restore_backup(&backup, &store_path).with_context(|| {
+                format!(
+                    "migration failed ({err:#}) and restoring {} also failed",
+                    backup.display()
+                )
+            })?;
+            Err(err.context(format!(
+                "migration rolled back; store restored from {}",
+                backup.display()
+            )))
+        }
+    }
+}
+
+/// Opens (and so migrates) the store, then requires a doctor run without
+/// error findings.
+fn apply_and_check(store_path: &Path) -> Result<()> {
+    let store = open_writer_at(store_path).context("apply migrations")?;
+    let report = store
+        .check_integrity(Utc::now())
+        .context("check migrated store")?;
+    if report.has_errors() {
+        let findings = report
+            .findings
+            .iter()
+            .map(|finding| finding_json(finding).to_string())
+            .collect::<Vec<_>>();
+        bail!(
+            "doctor found errors after migrating:\n  {}",
+            findings.join("\n  ")
+        );
+    }
+    Ok(())
+}
+
+/// Optional rewrites requested next to the schema migration.
+#[derive(Debug, Default)]
+struct MigrateExtras {
+    blobs: Option<BlobUpgradeReport>,
+    /// Raw events moved into monthly partitions.
+    partitioned: Option<usize>,
+}
+
+impl MigrateExtras {
+    fn describe(&self) -> Vec<String> {
+        let mut parts = Vec::new();
+        if let Some(report) = &self.blobs {
+            parts.push(describe_blob_upgrade(report));
+        }
+        if let Some(moved) = self.partitioned {
+            parts.push(format!("raw events partitioned by month ({moved} moved)"));
+        }
+        parts
+    }
+}
+
+fn apply_extras(store_path: &Path, args: &MigrateArgs) -> Result<MigrateExtras> {
+    Ok(MigrateExtras {
+        blobs: args
+            .upgrade_blobs
+            .then(|| upgrade_blobs(store_path))
+            .transpose()?,
+        partitioned: args
+            .partition_raw_events
+            .then(|| partition_raw_events(store_path))
+            .transpose()?,
+    })
+}
+
+fn partition_raw_events(store_path: &Path) -> Result<usize> {
+    let store = open_writer_at(store_path)?;
+    let now = Utc::now();
+    store
+        .enable_raw_event_partitioning(now)
+        .context("enable raw event partitioning")?;
+    store
+        .move_raw_events_into_partitions(now)
+        .context("move raw events into partitions")
+}
+
+fn upgrade_blobs(store_path: &Path) -> Result<BlobUpgradeReport> {
+    let store = open_writer_at(store_path)?;
+    store.upgrade_json_blobs().context("upgrade json blobs")
+}
+
+fn describe_blob_upgrade(report: &BlobUpgradeReport) -> String {
+    let total = report.upgraded.values().sum::<usize>();
+    if total == 0 {
+        return "no stale JSON blobs".to_string();
+    }
+    let parts = report
+        .upgraded
+        .iter()
+        .filter(|(_, count)| **count > 0)
+        .map(|(kind, count)| format!("{count} {}", kind.as_str()))
+        .collect::<Vec<_>>();
+    format!("upgraded {total} JSON blobs ({})", parts.join(", "))
+}
+
+fn restore_backup(backup: &Path, store_path: &Path) -> Result<()> {
+    fs::copy(backup, store_path)?;
+    // Stale WAL/SHM files would replay migrated pages over the restored copy.
+    for suffix in ["-wal", "-shm"] {
+        let sidecar = PathBuf::from(format!("{}{suffix}", store_path.display()));
+        if sidecar.exists() {
+            fs::remove_file(sidecar)?;
+        }
+    }
+    Ok(())
+}
+
+fn default_backup_path(store_path: &Path, from: i64) -> PathBuf {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ");
+    PathBuf::from(format!(
+        "{}.pre-migrate-v{from}-{stamp}.bak",
+        store_path.display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restore_puts_the_backup_back() {
+        let dir = std::env::temp_dir().join(format!("aoc-cli-migrate-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("create dir");
+        let store_path = dir.join("mind.sqlite");
+        // A zero-length file is an empty SQLite database at schema 0.
+        fs::write(&store_path, b"").expect("write empty store");
+
+        let backup = default_backup_path(&store_path, 0);
+        MindStore::backup_database(&store_path, &backup).expect("backup");
+        apply_and_check(&store_path).expect("migrate");
+        assert_eq!(
+            MindStore::stored_schema_version(&store_path).expect("version"),
+            MIND_SCHEMA_VERSION
+        );
+        let blobs = upgrade_blobs(&store_path).expect("upgrade blobs");
+        assert_eq!(describe_blob_upgrade(&blobs), "no stale JSON blobs");
+        assert_eq!(partition_raw_events(&store_path).expect("partition"), 0);
+        assert!(MindStore::open(&store_path)
+            .and_then(|store| store.raw_event_partitioning_enabled())
+            .expect("partitioning flag"));
+
+        fs::write(format!("{}-wal", store_path.display()), b"stale").expect("wal");
+        restore_backup(&backup, &store_path).expect("restore");
+        assert_eq!(
+            MindStore::stored_schema_version(&store_path).expect("version"),
+            0
+        );
+        assert!(!Path::new(&format!("{}-wal", store_path.display())).exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,206 @@
+use anyhow::{Context, Result};
+use aoc_config::AocConfig;
+use aoc_storage::{
+    MindStore, MindWriterGuard, WriterClaim, DEFAULT_WRITER_LEASE_TTL_MS, DEFAULT_WRITER_WAIT,
+};
+use clap::Args;
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+/// Store discovery flags shared by every command that opens the project Mind store.
+#[derive(Args, Debug, Clone, Default)]
+pub struct StoreArgs {
+    /// Mind store path. Falls back to AOC_MIND_STORE_PATH, `mind.store_path` in
+    /// aoc.toml, then the project store.
+    #[arg(long)]
+    pub store: Option<PathBuf>,
+    /// Project root. Falls back to AOC_PROJECT_ROOT, then the nearest .aoc/.git ancestor.
+    #[arg(long)]
+    pub project_root: Option<PathBuf>,
+}
+
+/// Reads an environment variable; tests substitute a map for the process
+/// environment.
+type EnvLookup<'a> = &'a dyn Fn(&str) -> Option<String>;
+
+impl StoreArgs {
+    pub fn project_root(&self) -> Result<PathBuf> {
+        self.project_root_from(&process_env)
+    }
+
+    /// Store path given on the command line or in the environment, ignoring
+    /// aoc.toml.
+    pub fn explicit_store_path(&self) -> Option<PathBuf> {
+        self.explicit_store_path_from(&process_env)
+    }
+
+    pub fn store_path(&self) -> Result<PathBuf> {
+        self.store_path_from(&process_env)
+    }
+
+    fn project_root_from(&self, env: EnvLookup) -> Result<PathBuf> {
+        if let Some(root) = self.project_root.clone() {
+            return Ok(root);
+        }
+        if let Some(root) = env_path(env, "AOC_PROJECT_ROOT") {
+            return Ok(root);
+        }
+        let cwd = env::current_dir().context("resolve project root")?;
+        Ok(discover_project_root(&cwd))
+    }
+
+    fn explicit_store_path_from(&self, env: EnvLookup) -> Option<PathBuf> {
+        self.store
+            .clone()
+            .or_else(|| env_path(env, "AOC_MIND_STORE_PATH"))
+    }
+
+    fn store_path_from(&self, env: EnvLookup) -> Result<PathBuf> {
+        if let Some(path) = self.explicit_store_path_from(env) {
+            return Ok(path);
+        }
+        let root = self.project_root_from(env)?;
+        let config = AocConfig::load(&root).context("load aoc.toml")?;
+        if let Some(path) = config.mind.store_path {
+            return Ok(path);
+        }
+        Ok(aoc_mind::mind_store_path(&root))
+    }
+
+    /// Global store to layer under the project store: AOC_MIND_GLOBAL_STORE_PATH,
+    /// then `mind.global_store_path`/`mind.global` in aoc.toml. `None` when
+    /// it is not enabled or would be the project store itself.
+    pub fn global_store_path(&self) -> Result<Option<PathBuf>> {
+        let path = match env_path(&process_env, "AOC_MIND_GLOBAL_STORE_PATH") {
+            Some(path) => Some(path),
+            None => self.config()?.mind.global_store(),
+        };
+        let project = self.store_path()?;
+        Ok(path.filter(|path| *path != project))
+    }
+
+    /// Opens the global store if one is enabled, creating it on first use.
+    pub fn open_global(&self) -> Result<Option<MindStore>> {
+        let Some(path) = self.global_store_path()? else {
+            return Ok(None);
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("create store directory {}", parent.display()))?;
+        }
+        let store = MindStore::open(&path)
+            .with_context(|| format!("open global mind store {}", path.display()))?;
+        Ok(Some(store))
+    }
+
+    /// Layered aoc.toml settings for this project.
+    pub fn config(&self) -> Result<AocConfig> {
+        let root = self.project_root()?;
+        AocConfig::load(&root).context("load aoc.toml")
+    }
+
+    pub fn open(&self) -> Result<(MindStore, PathBuf)> {
+        let path = self.store_path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("create store directory {}", parent.display()))?;
+        }
+        let store = MindStore::open(&path)
+            .with_context(|| format!("open mind store {}", path.display()))?;
+        Ok((store, path))
+    }
+
+    /// Like [`open`](Self::open), but holds the store's single write lease
+    /// until the guard drops, waiting briefly for another writer to finish.
+    pub fn open_writer(&self) -> Result<(MindWriterGuard, PathBuf)> {
+        let path = self.store_path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("create store directory {}", parent.display()))?;
+        }
+        let store = open_writer_at(&path)?;
+        Ok((store, path))
+    }
+}
+
+/// Write lease for a store path the CLI resolved some other way.
+pub fn open_writer_at(path: &Path) -> Result<MindWriterGuard> {
+    MindWriterGuard::acquire_waiting(
+        path,
+        WriterClaim::for_current_process("cli"),
+        DEFAULT_WRITER_LEASE_TTL_MS,
+        DEFAULT_WRITER_WAIT,
+    )
+    .with_context(|| format!("open mind store {} for writing", path.display()))
+}
+
+fn process_env(key: &str) -> Option<String> {
+    env::var(key).ok()
+}
+
+fn env_path(env: EnvLookup, key: &str) -> Option<PathBuf> {
+    env(key)
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+fn discover_project_root(cwd: &Path) -> PathBuf {
+    cwd.ancestors()
+        .find(|candidate| candidate.join(".aoc").exists() || candidate.join(".git").exists())
+        .unwrap_or(cwd)
+        .to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_win_over_environment_which_wins_over_discovery() {
+        let repo = env::temp_dir().join(format!("aoc-cli-store-args-{}", std::process::id()));
+        let nested = repo.join("src/deep");
+        std::fs::create_dir_all(&nested).expect("nested dir");
+        std::fs::create_dir_all(repo.join(".aoc")).expect("aoc dir");
+        assert_eq!(discover_project_root(&nested), repo);
+
+        let vars = |store: &'static str| {
+            let repo = repo.to_string_lossy().into_owned();
+            move |key: &str| match key {
+                "AOC_PROJECT_ROOT" => Some(repo.clone()),
+                "AOC_MIND_STORE_PATH" => Some(store.to_string()),
+                _ => None,
+            }
+        };
+        let from_env = StoreArgs::default();
+        let blank = vars("  ");
+        assert_eq!(from_env.project_root_from(&blank).expect("env root"), repo);
+        assert_eq!(
+            from_env.explicit_store_path_from(&blank),
+            None,
+            "blank is unset"
+        );
+        assert_eq!(
+            from_env.store_path_from(&blank).expect("default store"),
+            aoc_mind::mind_store_path(&repo)
+        );
+
+        let set = vars("/env/mind.sqlite");
+        assert_eq!(
+            from_env.store_path_from(&set).expect("env store"),
+            PathBuf::from("/env/mind.sqlite")
+        );
+        let flags = StoreArgs {
+            store: Some(PathBuf::from("/flag/mind.sqlite")),
+            project_root: Some(nested.clone()),
+        };
+        assert_eq!(flags.project_root_from(&set).expect("flag root"), nested);
+        assert_eq!(
+            flags.store_path_from(&set).expect("flag store"),
+            PathBuf::from("/flag/mind.sqlite")
+        );
+        let _ = std::fs::remove_dir_all(&repo);
+    }
+}
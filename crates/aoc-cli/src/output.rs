@@ -0,0 +1,260 @@
+//! Process-wide output conventions shared by every `aoc` subcommand.
+//!
+//! `--json` is a global flag: handlers ask [`json_mode`] and print exactly one
+//! JSON document on stdout. Exit codes carry severity so wrappers can branch
+//! without parsing either format:
+//!
+//! | code | meaning |
+//! |------|---------|
+//! | 0 | success, nothing to report |
+//! | 1 | command failed |
+//! | 2 | usage error (emitted by clap) |
+//! | 3 | completed, warnings reported |
+//! | 4 | completed, errors reported |
+//! | 5 | completed, every unit of work failed |
+//!
+//! JSON errors also carry the shared `aoc_core::ErrorCode` of the first coded
+//! cause, with its retryability, so scripts need not match on messages.
+
+use anyhow::Result;
+use aoc_core::{ErrorCode, ErrorCoded};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{
+    fmt,
+    process::ExitCode,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+pub const EXIT_FAILURE: u8 = 1;
+pub const EXIT_WARNINGS: u8 = 3;
+pub const EXIT_ERRORS: u8 = 4;
+pub const EXIT_ALL_FAILED: u8 = 5;
+
+static JSON_MODE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_json_mode(enabled: bool) {
+    JSON_MODE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn json_mode() -> bool {
+    JSON_MODE.load(Ordering::Relaxed)
+}
+
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Reports a mutation: the message in text mode, `{action, message, data}` in
+/// JSON mode so scripts get the record that was written.
+pub fn print_change(action: &str, message: impl fmt::Display, data: Value) -> Result<()> {
+    if json_mode() {
+        print_json(&json!({
+            "action": action,
+            "message": message.to_string(),
+            "data": data,
+        }))
+    } else {
+        println!("{message}");
+        Ok(())
+    }
+}
+
+/// A report as one line of `key=value` pairs for text mode. Nested objects
+/// flatten into dotted keys, lists show their length, and nulls are left out.
+pub fn report_line<T: Serialize + ?Sized>(report: &T) -> Result<String> {
+    let mut fields = Vec::new();
+    push_fields(&mut fields, "", &serde_json::to_value(report)?);
+    Ok(fields.join(" "))
+}
+
+fn push_fields(fields: &mut Vec<String>, key: &str, value: &Value) {
+    match value {
+        Value::Null => {}
+        Value::Object(map) => {
+            for (name, value) in map {
+                let key = if key.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{key}.{name}")
+                };
+                push_fields(fields, &key, value);
+            }
+        }
+        Value::Array(items) => fields.push(format!("{key}={}", items.len())),
+        Value::String(text) => fields.push(format!("{key}={text}")),
+        other => fields.push(format!("{key}={other}")),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Warning,
+    Error,
+    /// Nothing the command attempted succeeded.
+    Failure,
+}
+
+impl Severity {
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Severity::Warning => EXIT_WARNINGS,
+            Severity::Error => EXIT_ERRORS,
+            Severity::Failure => EXIT_ALL_FAILED,
+        }
+    }
+}
+
+/// A command that ran to completion but found problems. The report has
+/// already been printed; this only selects the exit code.
+#[derive(Debug)]
+pub struct SeverityExit {
+    pub severity: Severity,
+    pub summary: String,
+}
+
+impl SeverityExit {
+    pub fn new(severity: Severity, summary: impl Into<String>) -> Self {
+        Self {
+            severity,
+            summary: summary.into(),
+        }
+    }
+}
+
+impl fmt::Display for SeverityExit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.summary)
+    }
+}
+
+impl std::error::Error for SeverityExit {}
+
+pub fn exit_code_for(err: &anyhow::Error) -> u8 {
+    err.downcast_ref::<SeverityExit>()
+        .map(|exit| exit.severity.exit_code())
+        .unwrap_or(EXIT_FAILURE)
+}
+
+/// The shared code of the first cause in `err`'s chain that has one. Report
+/// exits carry none: their findings were already printed.
+pub fn error_code_for(err: &anyhow::Error) -> Option<ErrorCode> {
+    err.chain().find_map(coded_cause)
+}
+
+fn coded_cause(cause: &(dyn std::error::Error + 'static)) -> Option<ErrorCode> {
+    macro_rules! first_coded {
+        ($($ty:ty),+ $(,)?) => {
+            $(if let Some(err) = cause.downcast_ref::<$ty>() {
+                return Some(err.error_code());
+            })+
+        };
+    }
+    first_coded!(
+        aoc_storage::StorageError,
+        aoc_config::ConfigError,
+        aoc_core::mind_contracts::MindContractError,
+        aoc_core::pulse_ipc::FrameError,
+        aoc_pi_adapter::PiAdapterError,
+        aoc_segment_routing::RoutingError,
+        aoc_task_attribution::AttributionError,
+        aoc_bench::BenchError,
+        aoc_mind::DistillationError,
+        aoc_mind::ArtifactExportError,
+        aoc_mind::ComplianceError,
+        aoc_mind::LayeredMindError,
+        aoc_mind::MemoryToolError,
+        aoc_mind::ReflectorRuntimeError,
+        aoc_mind::ReplayError,
+        aoc_mind::StandaloneMindError,
+        aoc_mind::T0IngestError,
+        aoc_mind::T1ThresholdError,
+        aoc_mind::T3RuntimeError,
+        aoc_mind::TaskmasterSyncError,
+        aoc_mind::ThirdPartyImportError,
+    );
+    None
+}
+
+/// Prints a failed command's error and maps it to its exit code. JSON mode
+/// writes `{"error": {...}}` to stderr so stdout only ever holds the report.
+pub fn finish(result: Result<()>) -> ExitCode {
+    let Err(err) = result else {
+        return ExitCode::SUCCESS;
+    };
+    let code = exit_code_for(&err);
+    let severity_exit = err.downcast_ref::<SeverityExit>();
+    if json_mode() {
+        let error_code = error_code_for(&err);
+        let payload = json!({
+            "error": {
+                "exit_code": code,
+                "severity": severity_exit.map(|exit| exit.severity).unwrap_or(Severity::Error),
+                "code": error_code,
+                "retryable": error_code.is_some_and(ErrorCode::retryable),
+                "message": format!("{err:#}"),
+            }
+        });
+        eprintln!("{payload}");
+    } else if severity_exit.is_some() {
+        eprintln!("{err}");
+    } else {
+        eprintln!("Error: {err:?}");
+    }
+    ExitCode::from(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_codes_follow_severity() {
+        let warning = anyhow::Error::new(SeverityExit::new(Severity::Warning, "1 warning"));
+        let error = anyhow::Error::new(SeverityExit::new(Severity::Error, "2 errors"));
+        let failure = anyhow::anyhow!("store missing");
+
+        assert_eq!(exit_code_for(&warning), EXIT_WARNINGS);
+        assert_eq!(exit_code_for(&error), EXIT_ERRORS);
+        assert_eq!(exit_code_for(&failure), EXIT_FAILURE);
+        assert_eq!(exit_code_for(&failure.context("open store")), EXIT_FAILURE);
+        assert_eq!(exit_code_for(&warning.context("doctor")), EXIT_WARNINGS);
+    }
+
+    #[test]
+    fn report_lines_flatten_nested_fields() {
+        let line = report_line(&json!({
+            "t1_written": 2,
+            "chunked": false,
+            "session_id": "s-1",
+            "promotion": { "routes": 1, "skipped": null },
+            "issues": ["a", "b"],
+        }))
+        .expect("render");
+        assert_eq!(
+            line,
+            "chunked=false issues=2 promotion.routes=1 session_id=s-1 t1_written=2"
+        );
+    }
+
+    #[test]
+    fn error_codes_come_from_the_first_coded_cause() {
+        let busy = anyhow::Error::new(aoc_storage::StorageError::WriterBusy(
+            "held by cli".to_string(),
+        ))
+        .context("open mind store");
+        assert_eq!(error_code_for(&busy), Some(ErrorCode::WriterBusy));
+
+        let nested = anyhow::Error::new(aoc_mind::ReplayError::Storage(
+            aoc_storage::StorageError::Remote("timeout".to_string()),
+        ))
+        .context("replay");
+        assert_eq!(error_code_for(&nested), Some(ErrorCode::Remote));
+
+        let report = anyhow::Error::new(SeverityExit::new(Severity::Error, "2 errors"));
+        assert_eq!(error_code_for(&report), None);
+        assert_eq!(error_code_for(&anyhow::anyhow!("plain")), None);
+    }
+}
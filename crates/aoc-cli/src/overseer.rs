@@ -27,6 +27,8 @@ use aoc_core::{
 };
 use aoc_storage::{CompactionCheckpoint, MindStore};
 
+use crate::output::json_mode;
+
 #[derive(Subcommand, Debug)]
 pub enum OverseerCommand {
     /// Read the current overseer snapshot for a session
@@ -44,9 +46,6 @@ pub struct OverseerQueryArgs {
     /// Session id to inspect. Falls back to AOC_SESSION_ID.
     #[arg(long)]
     pub session_id: Option<String>,
-    /// Print raw JSON payload.
-    #[arg(long, default_value_t = false)]
-    pub json: bool,
     /// Socket path override. Falls back to AOC_PULSE_SOCK or the default runtime path.
     #[arg(long)]
     pub socket_path: Option<PathBuf>,
@@ -78,9 +77,6 @@ pub struct ConsultArgs {
     /// Read timeout in milliseconds.
     #[arg(long, default_value_t = 3000)]
     pub timeout_ms: u64,
-    /// Print raw JSON payload.
-    #[arg(long, default_value_t = false)]
-    pub json: bool,
     #[arg(value_enum, default_value_t = ConsultationPacketKindArg::Summary)]
     pub kind: ConsultationPacketKindArg,
 }
@@ -99,9 +95,6 @@ pub struct CommandArgs {
     /// Read timeout in milliseconds.
     #[arg(long, default_value_t = 3000)]
     pub timeout_ms: u64,
-    /// Print raw JSON payload.
-    #[arg(long, default_value_t = false)]
-    pub json: bool,
     #[arg(value_enum)]
     pub kind: OverseerCommandKindArg,
     /// Task id to switch focus to (switch-focus only).
@@ -150,7 +143,7 @@ fn handle_snapshot(args: OverseerQueryArgs) -> Result<()> {
         &socket_path,
         Duration::from_millis(args.timeout_ms),
     )?;
-    if args.json {
+    if json_mode() {
         println!("{}", serde_json::to_string_pretty(&snapshot)?);
         return Ok(());
     }
@@ -188,7 +181,7 @@ fn handle_timeline(args: TimelineArgs) -> Result<()> {
         &socket_path,
         Duration::from_millis(args.query.timeout_ms),
     )?;
-    if args.query.json {
+    if json_mode() {
         println!("{}", serde_json::to_string_pretty(&payload)?);
         return Ok(());
     }
@@ -231,7 +224,7 @@ fn handle_consult(args: ConsultArgs) -> Result<()> {
         now_ms() as i64,
     );
 
-    if args.json {
+    if json_mode() {
         println!("{}", serde_json::to_string_pretty(&packet)?);
         return Ok(());
     }
@@ -255,7 +248,7 @@ fn handle_command(args: CommandArgs) -> Result<()> {
         command,
         command_args,
     )?;
-    if args.json {
+    if json_mode() {
         println!("{}", serde_json::to_string_pretty(&result)?);
         return Ok(());
     }
@@ -0,0 +1,592 @@
+//! `aoc ingest|distill|route|attribute`: run one pipeline stage by hand.
+//! `aoc reroute` replays routing over artifacts that are already routed, for
+//! after a routing config change.
+//!
+//! Interactive runs stop at the first failing item. With `--batch` a stage
+//! never draws progress or waits on a terminal, keeps going past failed
+//! items, writes a JSON run report to `--report`, and exits with the code of
+//! its outcome class ([`BatchOutcome`]) so schedulers can branch on it.
+
+use anyhow::{bail, Context, Result};
+use aoc_core::mind_contracts::SemanticRuntimeMode;
+use aoc_mind::{
+    DeterministicDistiller, GatewayObserverInvoker, LayeredMind, PiObserverAdapter,
+    PromotionReport, SemanticObserverDistiller,
+};
+use aoc_pi_adapter::{IngestionOptions, PiSessionIngestor};
+use aoc_segment_routing::RoutingReport;
+use aoc_segment_routing::SegmentRouter;
+use aoc_task_attribution::TaskAttributionEngine;
+use chrono::{DateTime, Utc};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    logging::Progress,
+    mind_store::StoreArgs,
+    output::{
+        json_mode, print_change, print_json, report_line, Severity, SeverityExit, EXIT_FAILURE,
+    },
+    query::parse_time_arg,
+};
+
+const DEFAULT_AGENT_ID: &str = "aoc-cli";
+
+#[derive(Subcommand, Debug)]
+pub enum PipelineCommand {
+    /// Ingest a Pi session JSONL file, or every session file under a directory
+    Ingest(IngestArgs),
+    /// Distill T0 events of conversations into T1/T2 artifacts
+    Distill(ConversationArgs),
+    /// Route conversation artifacts to segments
+    Route(ConversationArgs),
+    /// Attribute conversation artifacts to tasks
+    Attribute(ConversationArgs),
+    /// Re-route already-routed artifacts under the current routing config
+    Reroute(RerouteArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct IngestArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    #[command(flatten)]
+    pub batch: BatchArgs,
+    #[arg(long, default_value = DEFAULT_AGENT_ID)]
+    pub agent_id: String,
+    /// Session file or directory of session files.
+    pub path: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct ConversationArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    #[command(flatten)]
+    pub batch: BatchArgs,
+    /// Conversation ids to process.
+    #[arg(required = true)]
+    pub conversation_ids: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct RerouteArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Only artifacts since a relative window (`7d`) or RFC3339 timestamp.
+    #[arg(long)]
+    pub since: Option<String>,
+}
+
+#[derive(Args, Debug, Clone, Default)]
+pub struct BatchArgs {
+    /// Run unattended: no progress bar, continue past failed items, and exit
+    /// with the outcome class code (0 ok, 1 aborted, 3 no input, 4 partial,
+    /// 5 all failed).
+    #[arg(long, requires = "report")]
+    pub batch: bool,
+    /// Where `--batch` writes its JSON run report.
+    #[arg(long, value_name = "PATH", requires = "batch")]
+    pub report: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchOutcome {
+    Succeeded,
+    /// Setup failed (config, store) before any item ran.
+    Aborted,
+    NoInput,
+    PartialFailure,
+    Failed,
+}
+
+impl BatchOutcome {
+    fn classify(aborted: bool, succeeded: usize, failed: usize) -> Self {
+        match (aborted, succeeded, failed) {
+            (true, _, _) => Self::Aborted,
+            (false, 0, 0) => Self::NoInput,
+            (false, _, 0) => Self::Succeeded,
+            (false, 0, _) => Self::Failed,
+            (false, _, _) => Self::PartialFailure,
+        }
+    }
+
+    fn severity(self) -> Option<Severity> {
+        match self {
+            Self::Succeeded | Self::Aborted => None,
+            Self::NoInput => Some(Severity::Warning),
+            Self::PartialFailure => Some(Severity::Error),
+            Self::Failed => Some(Severity::Failure),
+        }
+    }
+
+    fn exit_code(self) -> u8 {
+        match self {
+            Self::Aborted => EXIT_FAILURE,
+            other => other.severity().map_or(0, Severity::exit_code),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct BatchItem {
+    item: String,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    report: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// One stage invocation. Outside `--batch` it only forwards errors.
+struct StageRun {
+    stage: &'static str,
+    batch: BatchArgs,
+    started_at: DateTime<Utc>,
+    store_path: Option<PathBuf>,
+    items: Vec<BatchItem>,
+}
+
+impl StageRun {
+    fn new(stage: &'static str, batch: BatchArgs) -> Self {
+        Self {
+            stage,
+            batch,
+            started_at: Utc::now(),
+            store_path: None,
+            items: Vec::new(),
+        }
+    }
+
+    fn progress(&self, total: usize) -> Progress {
+        let progress = Progress::new(self.stage, total);
+        if self.batch.batch {
+            progress.headless()
+        } else {
+            progress
+        }
+    }
+
+    /// Interactive runs fail on the first error; batch runs record it and
+    /// return `None` so the loop moves on.
+    fn record<R: Serialize>(&mut self, item: &str, result: Result<R>) -> Result<Option<R>> {
+        if !self.batch.batch {
+            return result.map(Some);
+        }
+        let entry = match &result {
+            Ok(report) => BatchItem {
+                item: item.to_string(),
+                ok: true,
+                report: Some(serde_json::to_value(report)?),
+                error: None,
+            },
+            Err(err) => BatchItem {
+                item: item.to_string(),
+                ok: false,
+                report: None,
+                error: Some(format!("{err:#}")),
+            },
+        };
+        self.items.push(entry);
+        Ok(result.ok())
+    }
+
+    fn run(mut self, body: impl FnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        let result = body(&mut self);
+        let Some(path) = self.batch.report.clone().filter(|_| self.batch.batch) else {
+            return result;
+        };
+        let succeeded = self.items.iter().filter(|item| item.ok).count();
+        let failed = self.items.len() - succeeded;
+        let outcome = BatchOutcome::classify(result.is_err(), succeeded, failed);
+        let finished_at = Utc::now();
+        let report = json!({
+            "stage": self.stage,
+            "outcome": outcome,
+            "exit_code": outcome.exit_code(),
+            "started_at": self.started_at.to_rfc3339(),
+            "finished_at": finished_at.to_rfc3339(),
+            "duration_ms": (finished_at - self.started_at).num_milliseconds().max(0),
+            "store_path": self.store_path,
+            "succeeded": succeeded,
+            "failed": failed,
+            "error": result.as_ref().err().map(|err| format!("{err:#}")),
+            "items": self.items,
+        });
+        if let Some(parent) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("create report directory {}", parent.display()))?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(&report)? + "\n")
+            .with_context(|| format!("write batch report {}", path.display()))?;
+        result?;
+        match outcome.severity() {
+            None => Ok(()),
+            Some(severity) => Err(SeverityExit::new(
+                severity,
+                format!(
+                    "{}: {succeeded} succeeded, {failed} failed; report at {}",
+                    self.stage,
+                    path.display()
+                ),
+            )
+            .into()),
+        }
+    }
+}
+
+pub fn handle_pipeline_command(command: PipelineCommand) -> Result<()> {
+    match command {
+        PipelineCommand::Ingest(args) => handle_ingest(args),
+        PipelineCommand::Distill(args) => handle_distill(args),
+        PipelineCommand::Route(args) => handle_route(args),
+        PipelineCommand::Attribute(args) => handle_attribute(args),
+        PipelineCommand::Reroute(args) => handle_reroute(args),
+    }
+}
+
+fn handle_ingest(args: IngestArgs) -> Result<()> {
+    StageRun::new("ingest", args.batch.clone()).run(|run| {
+        let files = collect_session_files(&args.path)?;
+        if files.is_empty() {
+            if run.batch.batch {
+                return Ok(());
+            }
+            bail!("no session .jsonl files found at {}", args.path.display());
+        }
+        let (store, store_path) = args.store.open_writer()?;
+        if !json_mode() {
+            println!("store: {}", store_path.display());
+        }
+        run.store_path = Some(store_path.clone());
+        let ingestor = PiSessionIngestor::new(IngestionOptions {
+            policy: args.store.config()?.compaction_policy()?,
+        });
+        let mut reports = Vec::new();
+        let mut progress = run.progress(files.len());
+        for file in files {
+            let label = file.display().to_string();
+            let outcome = ingestor
+                .ingest_session_file(&store, &args.agent_id, &file)
+                .with_context(|| format!("ingest {label}"));
+            progress.advance(&label);
+            let Some(report) = run.record(&label, outcome)? else {
+                continue;
+            };
+            if json_mode() {
+                reports.push(json!({ "path": file, "report": report }));
+            } else {
+                progress.println(format!("{label}: {}", report_line(&report)?));
+            }
+        }
+        progress.finish();
+        if json_mode() {
+            print_json(&json!({ "store_path": store_path, "reports": reports }))?;
+        }
+        Ok(())
+    })
+}
+
+fn handle_distill(args: ConversationArgs) -> Result<()> {
+    StageRun::new("distill", args.batch.clone()).run(|run| {
+        let config = args.store.config()?;
+        let (store, store_path) = args.store.open_writer()?;
+        run.store_path = Some(store_path);
+        // A configured gateway runs the semantic observer; it still falls
+        // back to the deterministic path per batch when the gateway fails.
+        let semantic = config
+            .observer
+            .gateway
+            .clone()
+            .filter(|_| config.observer.mode != SemanticRuntimeMode::DeterministicOnly)
+            .map(|gateway| {
+                SemanticObserverDistiller::new(
+                    config.distillation.clone(),
+                    config.observer.clone(),
+                    PiObserverAdapter::new(GatewayObserverInvoker::new(gateway)),
+                )
+            });
+        let distiller = DeterministicDistiller::new(config.distillation);
+        let mut reports = Vec::new();
+        let mut progress = run.progress(args.conversation_ids.len());
+        for conversation_id in &args.conversation_ids {
+            let outcome = match &semantic {
+                Some(semantic) => semantic.distill_conversation(&store, conversation_id),
+                None => distiller.distill_conversation(&store, conversation_id),
+            }
+            .with_context(|| format!("distill {conversation_id}"));
+            progress.advance(conversation_id);
+            if let Some(report) = run.record(conversation_id, outcome)? {
+                reports.push((conversation_id.as_str(), report));
+            }
+        }
+        progress.finish();
+        print_conversation_reports(&reports)
+    })
+}
+
+fn handle_route(args: ConversationArgs) -> Result<()> {
+    StageRun::new("route", args.batch.clone()).run(|run| {
+        let config = args.store.config()?;
+        let (store, store_path) = args.store.open_writer()?;
+        run.store_path = Some(store_path);
+        let global = args.store.open_global()?;
+        let mind = LayeredMind::new(&store, global.as_ref());
+        let router = SegmentRouter::new(config.routing);
+        let global_segment = router.global_segment();
+        let mut reports = Vec::new();
+        let mut progress = run.progress(args.conversation_ids.len());
+        for conversation_id in &args.conversation_ids {
+            let outcome = router
+                .route_conversation(&store, conversation_id)
+                .map_err(anyhow::Error::from)
+                .and_then(|routing| {
+                    let promotion = mind.promote_global_routes(conversation_id, &global_segment)?;
+                    Ok(RouteStageReport { routing, promotion })
+                })
+                .with_context(|| format!("route {conversation_id}"));
+            progress.advance(conversation_id);
+            if let Some(report) = run.record(conversation_id, outcome)? {
+                reports.push((conversation_id.as_str(), report));
+            }
+        }
+        progress.finish();
+        print_conversation_reports(&reports)
+    })
+}
+
+/// Routing plus what was promoted into the global Mind, which stays all
+/// zeros when none is layered in.
+#[derive(Debug, Serialize)]
+struct RouteStageReport {
+    #[serde(flatten)]
+    routing: RoutingReport,
+    promotion: PromotionReport,
+}
+
+fn handle_reroute(args: RerouteArgs) -> Result<()> {
+    let since = args
+        .since
+        .as_deref()
+        .map(|value| parse_time_arg("--since", value, Utc::now()))
+        .transpose()?;
+    let config = args.store.config()?;
+    let (store, _) = args.store.open_writer()?;
+    let report = SegmentRouter::new(config.routing)
+        .reroute_all(&store, since)
+        .context("reroute artifacts")?;
+    print_change(
+        "reroute",
+        format!(
+            "Re-routed {} artifacts in {} conversations: {} routes changed, {} primaries moved",
+            report.artifacts_rerouted,
+            report.conversations,
+            report.routes_changed,
+            report.primaries_changed
+        ),
+        json!(report),
+    )
+}
+
+fn handle_attribute(args: ConversationArgs) -> Result<()> {
+    StageRun::new("attribute", args.batch.clone()).run(|run| {
+        let config = args.store.config()?;
+        let (store, store_path) = args.store.open_writer()?;
+        run.store_path = Some(store_path);
+        let engine = TaskAttributionEngine::new(config.attribution_config()?);
+        let mut reports = Vec::new();
+        let mut progress = run.progress(args.conversation_ids.len());
+        for conversation_id in &args.conversation_ids {
+            let outcome = engine
+                .attribute_conversation(&store, conversation_id)
+                .with_context(|| format!("attribute {conversation_id}"));
+            progress.advance(conversation_id);
+            if let Some(report) = run.record(conversation_id, outcome)? {
+                reports.push((conversation_id.as_str(), report));
+            }
+        }
+        progress.finish();
+        print_conversation_reports(&reports)
+    })
+}
+
+fn print_conversation_reports<R: Serialize>(reports: &[(&str, R)]) -> Result<()> {
+    if json_mode() {
+        let reports = reports
+            .iter()
+            .map(|(conversation_id, report)| {
+                json!({ "conversation_id": conversation_id, "report": report })
+            })
+            .collect::<Vec<_>>();
+        return print_json(&json!({ "reports": reports }));
+    }
+    for (conversation_id, report) in reports {
+        println!("{conversation_id}: {}", report_line(report)?);
+    }
+    Ok(())
+}
+
+fn collect_session_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = Vec::new();
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries =
+            fs::read_dir(&dir).with_context(|| format!("read directory {}", dir.display()))?;
+        for entry in entries {
+            let entry_path = entry?.path();
+            if entry_path.is_dir() {
+                pending.push(entry_path);
+            } else if entry_path.extension().and_then(|ext| ext.to_str()) == Some("jsonl") {
+                files.push(entry_path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser, Debug)]
+    struct PipelineCli {
+        #[command(subcommand)]
+        command: PipelineCommand,
+    }
+
+    fn parse(args: &[&str]) -> Result<PipelineCommand, clap::Error> {
+        PipelineCli::try_parse_from(std::iter::once("aoc").chain(args.iter().copied()))
+            .map(|cli| cli.command)
+    }
+
+    #[test]
+    fn stage_commands_parse_their_flags() {
+        let PipelineCommand::Ingest(ingest) =
+            parse(&["ingest", "--store", "/tmp/mind.sqlite", "sessions"]).expect("ingest")
+        else {
+            panic!("expected ingest");
+        };
+        assert_eq!(ingest.path, PathBuf::from("sessions"));
+        assert_eq!(ingest.agent_id, DEFAULT_AGENT_ID);
+        assert_eq!(ingest.store.store, Some(PathBuf::from("/tmp/mind.sqlite")));
+        assert!(!ingest.batch.batch);
+
+        let PipelineCommand::Distill(distill) = parse(&[
+            "distill", "--batch", "--report", "out.json", "conv-1", "conv-2",
+        ])
+        .expect("distill") else {
+            panic!("expected distill");
+        };
+        assert_eq!(distill.conversation_ids, ["conv-1", "conv-2"]);
+        assert_eq!(distill.batch.report, Some(PathBuf::from("out.json")));
+
+        let PipelineCommand::Route(route) =
+            parse(&["route", "--project-root", "/repo", "conv-1"]).expect("route")
+        else {
+            panic!("expected route");
+        };
+        assert_eq!(route.store.project_root, Some(PathBuf::from("/repo")));
+        assert!(matches!(
+            parse(&["attribute", "conv-1"]).expect("attribute"),
+            PipelineCommand::Attribute(_)
+        ));
+
+        assert!(parse(&["route"]).is_err(), "conversation ids are required");
+        assert!(parse(&["ingest"]).is_err(), "path is required");
+        assert!(
+            parse(&["attribute", "--batch", "conv-1"]).is_err(),
+            "--batch needs --report"
+        );
+    }
+
+    #[test]
+    fn collect_session_files_walks_directories_in_sorted_order() {
+        let root = std::env::temp_dir().join(format!("aoc-cli-ingest-{}", std::process::id()));
+        let nested = root.join("--repo--");
+        fs::create_dir_all(&nested).expect("create dirs");
+        fs::write(nested.join("b.jsonl"), "{}\n").expect("write b");
+        fs::write(root.join("a.jsonl"), "{}\n").expect("write a");
+        fs::write(root.join("notes.txt"), "skip").expect("write notes");
+
+        let files = collect_session_files(&root).expect("collect");
+        assert_eq!(files, vec![nested.join("b.jsonl"), root.join("a.jsonl")]);
+        assert_eq!(
+            collect_session_files(&root.join("a.jsonl")).expect("single file"),
+            vec![root.join("a.jsonl")]
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn batch_runs_record_failures_write_a_report_and_map_outcome_exit_codes() {
+        use crate::output::{exit_code_for, EXIT_ALL_FAILED, EXIT_ERRORS, EXIT_WARNINGS};
+
+        let root = std::env::temp_dir().join(format!("aoc-cli-batch-{}", std::process::id()));
+        let report_path = root.join("reports/distill.json");
+        let batch = BatchArgs {
+            batch: true,
+            report: Some(report_path.clone()),
+        };
+        let err = StageRun::new("distill", batch.clone())
+            .run(|run| {
+                assert_eq!(
+                    run.record("conv-ok", Ok(json!({ "t1": 1 })))?,
+                    Some(json!({ "t1": 1 }))
+                );
+                let failed: Result<Value> = Err(anyhow::anyhow!("no t0 events"));
+                assert_eq!(run.record("conv-bad", failed)?, None);
+                Ok(())
+            })
+            .expect_err("partial failure");
+        assert_eq!(exit_code_for(&err), EXIT_ERRORS);
+
+        let report: Value =
+            serde_json::from_str(&fs::read_to_string(&report_path).expect("report")).expect("json");
+        assert_eq!(report["outcome"], "partial_failure");
+        assert_eq!(report["exit_code"], EXIT_ERRORS);
+        assert_eq!(report["succeeded"], 1);
+        assert_eq!(report["items"][1]["error"], "no t0 events");
+
+        let no_input = StageRun::new("route", batch.clone())
+            .run(|_| Ok(()))
+            .expect_err("no input");
+        assert_eq!(exit_code_for(&no_input), EXIT_WARNINGS);
+        let all_failed = StageRun::new("route", batch.clone())
+            .run(|run| {
+                run.record::<Value>("conv", Err(anyhow::anyhow!("boom")))
+                    .map(drop)
+            })
+            .expect_err("all failed");
+        assert_eq!(exit_code_for(&all_failed), EXIT_ALL_FAILED);
+        let aborted = StageRun::new("route", batch)
+            .run(|_| bail!("open store"))
+            .expect_err("aborted");
+        assert_eq!(exit_code_for(&aborted), EXIT_FAILURE);
+        let report: Value =
+            serde_json::from_str(&fs::read_to_string(&report_path).expect("report")).expect("json");
+        assert_eq!(report["outcome"], "aborted");
+        assert_eq!(report["error"], "open store");
+
+        let interactive = StageRun::new("route", BatchArgs::default())
+            .run(|run| {
+                run.record::<Value>("conv", Err(anyhow::anyhow!("boom")))
+                    .map(drop)
+            })
+            .expect_err("interactive runs stop at the first error");
+        assert_eq!(exit_code_for(&interactive), EXIT_FAILURE);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,349 @@
+use anyhow::{bail, Context, Result};
+use aoc_mind::{run_artifact_archival, ArchivalPolicy, ArchivalReport};
+use aoc_storage::{MindStore, PartitionDropReport, RawPruneReport, RetentionPolicy};
+use chrono::Utc;
+use clap::Args;
+use serde_json::{json, Value};
+
+use crate::{
+    mind_store::StoreArgs,
+    output::{json_mode, print_change, print_json},
+};
+
+#[derive(Args, Debug)]
+pub struct PruneArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Report what would be reclaimed without changing the store (default).
+    #[arg(long, default_value_t = false, conflicts_with = "apply")]
+    pub dry_run: bool,
+    /// Delete and archive the rows the report lists.
+    #[arg(long, default_value_t = false)]
+    pub apply: bool,
+    /// Drop raw events older than this many days.
+    #[arg(long)]
+    pub max_raw_age_days: Option<u32>,
+    /// Keep only the newest N raw events of each conversation.
+    #[arg(long)]
+    pub max_raw_per_conversation: Option<usize>,
+    /// Drop whole monthly raw event partitions for months before this one
+    /// (`YYYY-MM`); see `aoc migrate --partition-raw-events`.
+    #[arg(long, value_name = "YYYY-MM")]
+    pub drop_partitions_before: Option<String>,
+    /// Drop retrieval metrics samples older than this many days.
+    #[arg(long)]
+    pub max_metrics_age_days: Option<u32>,
+    /// Move low-retention T1/T2 artifacts to the archive tier using the
+    /// `[retention]` policy from aoc.toml.
+    #[arg(long, default_value_t = false)]
+    pub archive: bool,
+    /// Override `retention.min_age_hours` for this run.
+    #[arg(long, requires = "archive")]
+    pub archive_min_age_hours: Option<u32>,
+    /// Override `retention.retention_floor_bps` for this run.
+    #[arg(long, requires = "archive")]
+    pub archive_floor_bps: Option<u16>,
+    /// Run VACUUM after applying so freed pages go back to the filesystem.
+    #[arg(long, default_value_t = false, requires = "apply")]
+    pub vacuum: bool,
+}
+
+/// One line of the reclaim report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TableReclaim {
+    table: &'static str,
+    action: &'static str,
+    rows: usize,
+    /// `None` when it is not measured: archive tiering only flags rows and
+    /// dropped partitions or metrics are not sized.
+    bytes: Option<u64>,
+}
+
+pub fn handle_prune_command(args: PruneArgs) -> Result<()> {
+    let raw_policy = RetentionPolicy {
+        max_raw_age: args
+            .max_raw_age_days
+            .map(|days| chrono::Duration::days(i64::from(days))),
+        max_raw_rows_per_conversation: args.max_raw_per_conversation,
+        max_metrics_age: args
+            .max_metrics_age_days
+            .map(|days| chrono::Duration::days(i64::from(days))),
+        ..RetentionPolicy::default()
+    };
+    let archival_policy = if args.archive {
+        let mut policy = args.store.config()?.retention;
+        if let Some(hours) = args.archive_min_age_hours {
+            policy.min_age_hours = hours;
+        }
+        if let Some(floor) = args.archive_floor_bps {
+            policy.retention_floor_bps = floor;
+        }
+        Some(policy)
+    } else {
+        None
+    };
+    if raw_policy == RetentionPolicy::default()
+        && archival_policy.is_none()
+        && args.drop_partitions_before.is_none()
+    {
+        bail!(
+            "nothing to prune: pass --max-raw-age-days, --max-raw-per-conversation, --drop-partitions-before, --max-metrics-age-days, or --archive"
+        );
+    }
+
+    let dry_run = !args.apply;
+    let (store, store_path) = args.store.open_writer()?;
+    let now = Utc::now();
+    let raw = store
+        .prune_raw_events(&raw_policy, now, dry_run)
+        .context("prune raw events")?;
+    let dropped = args
+        .drop_partitions_before
+        .as_deref()
+        .map(|before| drop_partitions_before(&store, before, dry_run))
+        .transpose()
+        .context("drop raw event partitions")?
+        .unwrap_or_default();
+    let metrics = raw_policy
+        .max_metrics_age
+        .map(|_| store.prune_retrieval_metrics(&raw_policy, now, dry_run))
+        .transpose()
+        .context("prune retrieval metrics")?;
+    let archival = archival_policy
+        .map(|policy| run_artifact_archival(&store, policy, now, dry_run))
+        .transpose()
+        .context("archive artifacts")?;
+    let freed = raw.rows > 0
+        || dropped.iter().any(|report| report.rows > 0)
+        || metrics.is_some_and(|rows| rows > 0);
+    if args.vacuum && freed {
+        store.vacuum().context("vacuum store")?;
+    }
+
+    let lines = reclaim_lines(&raw, &dropped, metrics, archival.as_ref());
+    let data = json!({
+        "store_path": store_path,
+        "dry_run": dry_run,
+        "policy": policy_json(&raw_policy, archival_policy.as_ref()),
+        "tables": lines.iter().map(reclaim_json).collect::<Vec<_>>(),
+        "raw_kept_referenced": raw.kept_referenced
+            + dropped.iter().map(|report| report.kept_referenced).sum::<usize>(),
+        "raw_kept_t0_sources": raw.kept_t0_sources
+            + dropped.iter().map(|report| report.kept_t0_sources).sum::<usize>(),
+        "raw_conversations": raw.conversations,
+        "partitions": dropped.iter().map(|report| &report.month).collect::<Vec<_>>(),
+        "vacuumed": args.vacuum && freed,
+    });
+    if dry_run && json_mode() {
+        return print_json(&data);
+    }
+    if !json_mode() {
+        print_reclaim_table(&lines);
+        let kept_referenced = raw.kept_referenced
+            + dropped
+                .iter()
+                .map(|report| report.kept_referenced)
+                .sum::<usize>();
+        if kept_referenced > 0 {
+            println!("kept {kept_referenced} raw event(s) still cited by T1/T2 traces");
+        }
+        let kept_t0_sources = raw.kept_t0_sources
+            + dropped
+                .iter()
+                .map(|report| report.kept_t0_sources)
+                .sum::<usize>();
+        if kept_t0_sources > 0 {
+            println!("kept {kept_t0_sources} raw event(s) still listed as T0 sources");
+        }
+        if dry_run {
+            println!("dry run: pass --apply to prune");
+            return Ok(());
+        }
+    }
+    let total_rows = lines.iter().map(|line| line.rows).sum::<usize>();
+    print_change(
+        "prune",
+        format!(
+            "Pruned {total_rows} row(s), {} byte(s) deleted from {}",
+            raw.bytes,
+            store_path.display()
+        ),
+        data,
+    )
+}
+
+/// Partitions for months before `before`, oldest first, dropped or (on a
+/// dry run) only measured.
+fn drop_partitions_before(
+    store: &MindStore,
+    before: &str,
+    dry_run: bool,
+) -> Result<Vec<PartitionDropReport>> {
+    let before = before.trim();
+    if chrono::NaiveDate::parse_from_str(&format!("{before}-01"), "%Y-%m-%d").is_err() {
+        bail!("--drop-partitions-before wants a YYYY-MM month, got '{before}'");
+    }
+    let mut reports = Vec::new();
+    for partition in store.raw_event_partitions()? {
+        if partition.month.as_str() >= before {
+            continue;
+        }
+        reports.extend(store.drop_raw_event_partition(&partition.month, dry_run)?);
+    }
+    Ok(reports)
+}
+
+fn reclaim_lines(
+    raw: &RawPruneReport,
+    dropped: &[PartitionDropReport],
+    metrics: Option<usize>,
+    archival: Option<&ArchivalReport>,
+) -> Vec<TableReclaim> {
+    let mut lines = vec![TableReclaim {
+        table: "raw_events",
+        action: "delete",
+        rows: raw.rows,
+        bytes: Some(raw.bytes),
+    }];
+    if !dropped.is_empty() {
+        // Whole tables go, so there is no per-row byte count.
+        lines.push(TableReclaim {
+            table: "raw_events_p*",
+            action: "drop",
+            rows: dropped.iter().map(|report| report.rows as usize).sum(),
+            bytes: None,
+        });
+    }
+    if let Some(rows) = metrics {
+        lines.push(TableReclaim {
+            table: "retrieval_metrics",
+            action: "delete",
+            rows,
+            bytes: None,
+        });
+    }
+    if let Some(report) = archival {
+        for (table, kind) in [("observations_t1", "t1"), ("reflections_t2", "t2")] {
+            lines.push(TableReclaim {
+                table,
+                action: "archive",
+                rows: report
+                    .archived
+                    .iter()
+                    .filter(|entry| entry.kind == kind)
+                    .count(),
+                bytes: None,
+            });
+        }
+    }
+    lines
+}
+
+fn print_reclaim_table(lines: &[TableReclaim]) {
+    println!(
+        "{:<18} {:<8} {:>10} {:>12}",
+        "table", "action", "rows", "bytes"
+    );
+    for line in lines {
+        let bytes = line
+            .bytes
+            .map(|bytes| bytes.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{:<18} {:<8} {:>10} {:>12}",
+            line.table, line.action, line.rows, bytes
+        );
+    }
+}
+
+fn reclaim_json(line: &TableReclaim) -> Value {
+    json!({
+        "table": line.table,
+        "action": line.action,
+        "rows": line.rows,
+        "bytes": line.bytes,
+    })
+}
+
+fn policy_json(raw: &RetentionPolicy, archival: Option<&ArchivalPolicy>) -> Value {
+    json!({
+        "max_raw_age_days": raw.max_raw_age.map(|age| age.num_days()),
+        "max_raw_per_conversation": raw.max_raw_rows_per_conversation,
+        "keep_t0_sources": raw.keep_t0_sources,
+        "max_metrics_age_days": raw.max_metrics_age.map(|age| age.num_days()),
+        "archive": archival.map(|policy| json!({
+            "min_age_hours": policy.min_age_hours,
+            "half_life_hours": policy.half_life_hours,
+            "retention_floor_bps": policy.retention_floor_bps,
+        })),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_storage::ArchivedArtifact;
+
+    #[test]
+    fn reclaim_lines_split_archived_artifacts_by_table() {
+        let raw = RawPruneReport {
+            rows: 3,
+            bytes: 420,
+            ..RawPruneReport::default()
+        };
+        let archived = |kind: &str| ArchivedArtifact {
+            artifact_id: format!("{kind}:1"),
+            conversation_id: "conv-1".to_string(),
+            kind: kind.to_string(),
+            retention_bps: 100,
+            reason: "test".to_string(),
+            archived_at: Utc::now(),
+        };
+        let report = ArchivalReport {
+            archived: vec![archived("t1"), archived("t1"), archived("t2")],
+            ..ArchivalReport::default()
+        };
+
+        let lines = reclaim_lines(&raw, &[], None, Some(&report));
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].bytes, Some(420));
+        assert_eq!((lines[1].table, lines[1].rows), ("observations_t1", 2));
+        assert_eq!(
+            (lines[2].table, lines[2].rows, lines[2].bytes),
+            ("reflections_t2", 1, None)
+        );
+
+        let raw_only = reclaim_lines(&RawPruneReport::default(), &[], None, None);
+        assert_eq!(raw_only.len(), 1);
+        assert_eq!(raw_only[0].rows, 0);
+
+        let dropped = |month: &str, rows| PartitionDropReport {
+            month: month.to_string(),
+            table_name: format!("raw_events_p{}", month.replace('-', "_")),
+            rows,
+            kept_referenced: 0,
+            kept_t0_sources: 0,
+        };
+        let partitions = reclaim_lines(
+            &RawPruneReport::default(),
+            &[dropped("2025-01", 40), dropped("2025-02", 2)],
+            None,
+            None,
+        );
+        assert_eq!(
+            (
+                partitions[1].table,
+                partitions[1].action,
+                partitions[1].rows
+            ),
+            ("raw_events_p*", "drop", 42)
+        );
+
+        let metrics = reclaim_lines(&RawPruneReport::default(), &[], Some(7), Some(&report));
+        assert_eq!(
+            (metrics[1].table, metrics[1].action, metrics[1].rows),
+            ("retrieval_metrics", "delete", 7)
+        );
+        assert_eq!(metrics[2].table, "observations_t1");
+    }
+}
@@ -0,0 +1,327 @@
+use anyhow::{bail, Context, Result};
+use aoc_mind::{LayeredArtifact, LayeredArtifactPage, LayeredMind, MindLayer};
+use aoc_storage::{ArtifactQuery, MindStore, RemoteMindStore, StoredArtifact};
+use chrono::{DateTime, Duration, Utc};
+use clap::Args;
+use serde_json::{json, Value};
+
+use crate::{mind_store::StoreArgs, output::json_mode};
+
+const TEXT_PREVIEW_CHARS: usize = 96;
+const TRACE_PREVIEW_CHARS: usize = 120;
+
+#[derive(Args, Debug)]
+pub struct QueryArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Words that must all appear in the artifact text.
+    pub text: Option<String>,
+    /// Only artifacts from conversations that ran under this active tag.
+    #[arg(long)]
+    pub tag: Option<String>,
+    /// Only artifacts attributed to this task id.
+    #[arg(long)]
+    pub task: Option<String>,
+    /// Only artifacts newer than this (e.g. 30m, 12h, 7d, 2w, or RFC3339).
+    #[arg(long)]
+    pub since: Option<String>,
+    /// Only artifacts older than this; same forms as `--since`.
+    #[arg(long)]
+    pub until: Option<String>,
+    /// Expand each hit's trace ids into the T0/T1 records they cite.
+    #[arg(long, default_value_t = false)]
+    pub show_trace: bool,
+    /// 1-based page number.
+    #[arg(long, default_value_t = 1)]
+    pub page: usize,
+    /// Hits per page.
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+    /// Query an `aoc serve` URL instead of opening a local store.
+    #[arg(long)]
+    pub remote: Option<String>,
+    /// Leave the global Mind out even when aoc.toml layers it in.
+    #[arg(long, default_value_t = false)]
+    pub project_only: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TraceCitation {
+    trace_id: String,
+    kind: &'static str,
+    text: Option<String>,
+}
+
+pub fn handle_query_command(args: QueryArgs) -> Result<()> {
+    if args.page == 0 {
+        bail!("--page starts at 1");
+    }
+    let now = Utc::now();
+    let since = args
+        .since
+        .as_deref()
+        .map(|value| parse_since(value, now))
+        .transpose()?;
+    let until = args
+        .until
+        .as_deref()
+        .map(|value| parse_time_arg("--until", value, now))
+        .transpose()?;
+    let limit = args.limit.max(1);
+    let query = ArtifactQuery {
+        text: args.text.clone(),
+        active_tag: args.tag.clone(),
+        task_id: args.task.clone(),
+        since,
+        until,
+        offset: (args.page - 1) * limit,
+        limit,
+        ..ArtifactQuery::default()
+    };
+
+    let (page, traces, source) = if let Some(remote) = &args.remote {
+        if args.show_trace {
+            bail!("--show-trace needs a local store; drop --remote");
+        }
+        let store = RemoteMindStore::new(remote.as_str());
+        let page = store
+            .query_artifacts(&query)
+            .with_context(|| format!("query artifacts on {remote}"))?;
+        let traces = vec![Vec::new(); page.artifacts.len()];
+        let page = LayeredArtifactPage {
+            artifacts: page
+                .artifacts
+                .into_iter()
+                .map(|artifact| LayeredArtifact {
+                    layer: MindLayer::Project,
+                    artifact,
+                })
+                .collect(),
+            total: page.total,
+            offset: page.offset,
+        };
+        (page, traces, json!({ "remote": remote }))
+    } else {
+        let (store, store_path) = args.store.open()?;
+        let global = if args.project_only {
+            None
+        } else {
+            args.store.open_global()?
+        };
+        let mind = LayeredMind::new(&store, global.as_ref());
+        let page = mind.query_artifacts(&query).context("query artifacts")?;
+        let mut traces = Vec::with_capacity(page.artifacts.len());
+        for hit in &page.artifacts {
+            let source = match hit.layer {
+                MindLayer::Global => global.as_ref().unwrap_or(&store),
+                MindLayer::Project => &store,
+            };
+            traces.push(if args.show_trace {
+                expand_traces(source, &hit.artifact)?
+            } else {
+                Vec::new()
+            });
+        }
+        let mut source = json!({ "store_path": store_path });
+        if global.is_some() {
+            source["global_store_path"] = json!(args.store.global_store_path()?);
+        }
+        (page, traces, source)
+    };
+    let next_page = page.next_offset().map(|_| args.page + 1);
+
+    if json_mode() {
+        let mut payload = json!({
+            "query": {
+                "text": query.text,
+                "tag": query.active_tag,
+                "task": query.task_id,
+                "since": query.since.map(|ts| ts.to_rfc3339()),
+                "until": query.until.map(|ts| ts.to_rfc3339()),
+            },
+            "page": args.page,
+            "limit": limit,
+            "total": page.total,
+            "next_page": next_page,
+            "artifacts": page
+                .artifacts
+                .iter()
+                .zip(&traces)
+                .map(|(hit, traces)| {
+                    let mut value = artifact_json(&hit.artifact, traces, args.show_trace);
+                    value["layer"] = json!(hit.layer.as_str());
+                    value
+                })
+                .collect::<Vec<_>>(),
+        });
+        if let (Some(payload), Value::Object(source)) = (payload.as_object_mut(), source) {
+            payload.extend(source);
+        }
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(());
+    }
+
+    if page.artifacts.is_empty() {
+        println!("no artifacts matched ({} total)", page.total);
+        return Ok(());
+    }
+    println!(
+        "{:<28} {:<4} {:<20} {:<24} TEXT",
+        "ARTIFACT", "KIND", "TS", "CONVERSATION"
+    );
+    for (hit, traces) in page.artifacts.iter().zip(&traces) {
+        let artifact = &hit.artifact;
+        let marker = match hit.layer {
+            MindLayer::Global => " [global]",
+            MindLayer::Project => "",
+        };
+        println!(
+            "{:<28} {:<4} {:<20} {:<24} {}{marker}",
+            artifact.artifact_id,
+            artifact.kind,
+            artifact.ts.format("%Y-%m-%d %H:%M:%S"),
+            artifact.conversation_id,
+            preview(&artifact.text, TEXT_PREVIEW_CHARS)
+        );
+        for trace in traces {
+            println!(
+                "    <- {} [{}] {}",
+                trace.trace_id,
+                trace.kind,
+                trace
+                    .text
+                    .as_deref()
+                    .map(|text| preview(text, TRACE_PREVIEW_CHARS))
+                    .unwrap_or_default()
+            );
+        }
+    }
+    let shown_from = page.offset + 1;
+    let shown_to = page.offset + page.artifacts.len();
+    match next_page {
+        Some(next) => println!(
+            "showing {shown_from}-{shown_to} of {}; next: --page {next}",
+            page.total
+        ),
+        None => println!("showing {shown_from}-{shown_to} of {}", page.total),
+    }
+    Ok(())
+}
+
+fn expand_traces(store: &MindStore, artifact: &StoredArtifact) -> Result<Vec<TraceCitation>> {
+    let mut citations = Vec::with_capacity(artifact.trace_ids.len());
+    for trace_id in &artifact.trace_ids {
+        let citation = if let Some(cited) = store.artifact_by_id(trace_id)? {
+            TraceCitation {
+                trace_id: trace_id.clone(),
+                kind: if cited.kind == "t2" { "t2" } else { "t1" },
+                text: Some(cited.text),
+            }
+        } else if let Some(event) = store.compact_event_by_id(trace_id)? {
+            TraceCitation {
+                trace_id: trace_id.clone(),
+                kind: "t0",
+                text: event.text.or_else(|| {
+                    event
+                        .tool_meta
+                        .map(|meta| serde_json::to_string(&meta).unwrap_or_default())
+                }),
+            }
+        } else {
+            TraceCitation {
+                trace_id: trace_id.clone(),
+                kind: "missing",
+                text: None,
+            }
+        };
+        citations.push(citation);
+    }
+    Ok(citations)
+}
+
+fn artifact_json(artifact: &StoredArtifact, traces: &[TraceCitation], show_trace: bool) -> Value {
+    let mut value = json!({
+        "artifact_id": artifact.artifact_id,
+        "kind": artifact.kind,
+        "conversation_id": artifact.conversation_id,
+        "ts": artifact.ts.to_rfc3339(),
+        "text": artifact.text,
+        "trace_ids": artifact.trace_ids,
+    });
+    if show_trace {
+        value["traces"] = traces
+            .iter()
+            .map(|trace| {
+                json!({
+                    "trace_id": trace.trace_id,
+                    "kind": trace.kind,
+                    "text": trace.text,
+                })
+            })
+            .collect();
+    }
+    value
+}
+
+fn parse_since(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    parse_time_arg("--since", value, now)
+}
+
+/// Accepts relative windows (`30m`, `12h`, `7d`, `2w`, meaning that long
+/// before `now`) or an RFC3339 timestamp; `flag` names the option in errors.
+pub(crate) fn parse_time_arg(flag: &str, value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Ok(ts.with_timezone(&Utc));
+    }
+    let Some((split, _)) = value.char_indices().last() else {
+        bail!("{flag} must not be empty");
+    };
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .with_context(|| format!("invalid {flag} value: {value}"))?;
+    let window = match unit {
+        "m" => Duration::minutes(amount),
+        "h" => Duration::hours(amount),
+        "d" => Duration::days(amount),
+        "w" => Duration::weeks(amount),
+        _ => bail!("invalid {flag} unit in {value}; use m, h, d, or w"),
+    };
+    Ok(now - window)
+}
+
+fn preview(text: &str, max_chars: usize) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() <= max_chars {
+        flat
+    } else {
+        format!("{}...", flat.chars().take(max_chars).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn parse_since_accepts_relative_windows_and_timestamps() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        assert_eq!(
+            parse_since("7d", now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 3, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_since("90m", now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 10, 10, 30, 0).unwrap()
+        );
+        assert_eq!(
+            parse_since("2026-03-01T00:00:00Z", now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap()
+        );
+        assert!(parse_since("7y", now).is_err());
+        assert!(parse_since("d", now).is_err());
+        assert!(parse_since("", now).is_err());
+    }
+}
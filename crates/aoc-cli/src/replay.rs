@@ -0,0 +1,128 @@
+use anyhow::{Context, Result};
+use aoc_mind::{replay_conversation, ReplayPolicy, ReplayReport, ReplayTierDiff};
+use clap::Args;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+use crate::{mind_store::StoreArgs, output::json_mode};
+
+const TEXT_PREVIEW_CHARS: usize = 120;
+
+#[derive(Args, Debug)]
+pub struct ReplayArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Conversation to re-run.
+    #[arg(long)]
+    pub conversation: String,
+    /// JSON replay policy (`compaction` and `distillation` overrides). Without
+    /// a `compaction` override the aoc.toml `[compaction]` policy is used.
+    #[arg(long)]
+    pub policy: Option<PathBuf>,
+    /// Print shadow text for added and changed artifacts.
+    #[arg(long, default_value_t = false)]
+    pub show_text: bool,
+}
+
+pub fn handle_replay_command(args: ReplayArgs) -> Result<()> {
+    let mut policy = match &args.policy {
+        Some(path) => ReplayPolicy::load(path)?,
+        None => ReplayPolicy::default(),
+    };
+    if policy.compaction.is_none() {
+        policy.compaction = Some(args.store.config()?.compaction_policy()?);
+    }
+    let (store, store_path) = args.store.open()?;
+    let report = replay_conversation(&store, &args.conversation, &policy)
+        .with_context(|| format!("replay conversation {}", args.conversation))?;
+
+    if json_mode() {
+        let payload = json!({
+            "store_path": store_path,
+            "conversation_id": report.conversation_id,
+            "policy_path": args.policy,
+            "policy_version": report.policy_version,
+            "raw_events": report.raw_events,
+            "t0": tier_json(&report.t0, None),
+            "t1": tier_json(&report.t1, Some(&report)),
+            "t2": tier_json(&report.t2, Some(&report)),
+        });
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(());
+    }
+
+    println!(
+        "shadow replay of {} ({} raw events, policy {})",
+        report.conversation_id, report.raw_events, report.policy_version
+    );
+    for (label, diff, texts) in [
+        ("t0", &report.t0, None),
+        ("t1", &report.t1, Some(&report)),
+        ("t2", &report.t2, Some(&report)),
+    ] {
+        println!(
+            "{label}: live={} shadow={} unchanged={} added={} removed={} changed={}",
+            diff.live,
+            diff.shadow,
+            diff.unchanged,
+            diff.added.len(),
+            diff.removed.len(),
+            diff.changed.len()
+        );
+        for (marker, ids) in [
+            ("+", &diff.added),
+            ("-", &diff.removed),
+            ("~", &diff.changed),
+        ] {
+            for id in ids {
+                println!("  {marker} {id}");
+                if args.show_text && marker != "-" {
+                    if let Some(text) = texts.and_then(|report| report.shadow_texts.get(id)) {
+                        println!("      {}", preview(text, TEXT_PREVIEW_CHARS));
+                    }
+                }
+            }
+        }
+    }
+    if [&report.t0, &report.t1, &report.t2]
+        .iter()
+        .all(|diff| diff.is_identical())
+    {
+        println!("no differences from live state");
+    }
+    Ok(())
+}
+
+fn tier_json(diff: &ReplayTierDiff, texts: Option<&ReplayReport>) -> Value {
+    let shadow_text = |id: &String| {
+        texts
+            .and_then(|report| report.shadow_texts.get(id))
+            .cloned()
+    };
+    json!({
+        "live": diff.live,
+        "shadow": diff.shadow,
+        "unchanged": diff.unchanged,
+        "identical": diff.is_identical(),
+        "added": diff
+            .added
+            .iter()
+            .map(|id| json!({ "id": id, "text": shadow_text(id) }))
+            .collect::<Vec<_>>(),
+        "removed": diff.removed,
+        "changed": diff
+            .changed
+            .iter()
+            .map(|id| json!({ "id": id, "text": shadow_text(id) }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn preview(text: &str, max_chars: usize) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() <= max_chars {
+        flat
+    } else {
+        format!("{}...", flat.chars().take(max_chars).collect::<String>())
+    }
+}
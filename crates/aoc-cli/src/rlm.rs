@@ -7,6 +7,8 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::output::{json_mode, print_json};
+
 const DEFAULT_CHUNK_SIZE: usize = 5000;
 const DEFAULT_CONTEXT_WINDOW: usize = 200;
 const MAX_PEEK_RESULTS: usize = 50;
@@ -142,6 +144,9 @@ pub fn handle_rlm_command(command: RlmCommand) -> Result<()> {
         }
         RlmCommand::Peek(args) => {
             let results = peek(&root, &args.query);
+            if json_mode() {
+                return print_json(&results);
+            }
             for line in results {
                 println!("{}", line);
             }
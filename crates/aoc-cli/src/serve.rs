@@ -0,0 +1,144 @@
+//! `aoc serve`: the Mind HTTP API for dashboards and editor plugins.
+//!
+//! Binds loopback unless `--allow-remote` is given. Without `--token` (or
+//! `AOC_SERVER_TOKEN`) every mutating endpoint answers 403, so a plain
+//! `aoc serve` is safe to leave running next to `aoc live`. Builds with the
+//! `grpc` feature can also serve the gRPC mirror on `--grpc-addr`, under the
+//! same token and bind rules. `--stdio` instead speaks read-only JSON-RPC
+//! on stdin/stdout for editor extensions and opens no port at all.
+
+use anyhow::{bail, Context, Result};
+use aoc_server::{ServerConfig, DEFAULT_ADDR, TOKEN_ENV};
+use aoc_storage::MindStore;
+use clap::Args;
+use serde_json::json;
+use std::net::SocketAddr;
+
+use crate::{
+    mind_store::StoreArgs,
+    output::{json_mode, print_json},
+};
+
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Address to listen on.
+    #[arg(long, default_value = DEFAULT_ADDR)]
+    pub addr: SocketAddr,
+    /// Bearer token that unlocks mutating endpoints. Falls back to
+    /// AOC_SERVER_TOKEN; without one the server is read-only.
+    #[arg(long)]
+    pub token: Option<String>,
+    /// Accept a non-loopback --addr.
+    #[arg(long, default_value_t = false)]
+    pub allow_remote: bool,
+    /// Speak JSON-RPC (LSP framing) on stdin/stdout instead of HTTP.
+    #[arg(long, default_value_t = false, conflicts_with_all = ["addr", "token", "allow_remote"])]
+    pub stdio: bool,
+    /// Also serve the gRPC API on this address.
+    #[cfg(feature = "grpc")]
+    #[arg(long)]
+    pub grpc_addr: Option<SocketAddr>,
+}
+
+pub fn handle_serve_command(args: ServeArgs) -> Result<()> {
+    if args.stdio {
+        return serve_stdio(args.store);
+    }
+    check_bind_addr(args.addr, args.allow_remote)?;
+    #[cfg(feature = "grpc")]
+    if let Some(grpc_addr) = args.grpc_addr {
+        check_bind_addr(grpc_addr, args.allow_remote)?;
+    }
+    let store_path = args.store.store_path()?;
+    MindStore::open_read_only(&store_path)
+        .with_context(|| format!("open mind store {}", store_path.display()))?;
+    let config = ServerConfig::new(&store_path)
+        .with_write_token(args.token.or_else(|| std::env::var(TOKEN_ENV).ok()));
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .context("start async runtime")?;
+    runtime.block_on(async move {
+        let listener = tokio::net::TcpListener::bind(args.addr)
+            .await
+            .with_context(|| format!("bind {}", args.addr))?;
+        let addr = listener.local_addr().context("resolve listen address")?;
+        if json_mode() {
+            print_json(&json!({
+                "addr": addr.to_string(),
+                "store_path": store_path,
+                "read_only": config.read_only(),
+            }))?;
+        } else {
+            println!(
+                "serving {} on http://{addr} ({})",
+                store_path.display(),
+                if config.read_only() {
+                    "read-only"
+                } else {
+                    "writes need the bearer token"
+                }
+            );
+        }
+        #[cfg(feature = "grpc")]
+        if let Some(grpc_addr) = args.grpc_addr {
+            let grpc_listener = tokio::net::TcpListener::bind(grpc_addr)
+                .await
+                .with_context(|| format!("bind {grpc_addr}"))?;
+            if !json_mode() {
+                println!("serving gRPC on {grpc_addr}");
+            }
+            let grpc = aoc_server::grpc::serve(grpc_listener, config.clone(), async {
+                let _ = tokio::signal::ctrl_c().await;
+            });
+            let http = aoc_server::serve(listener, config, async {
+                let _ = tokio::signal::ctrl_c().await;
+            });
+            let (http, grpc) = tokio::join!(http, grpc);
+            http.context("serve mind api")?;
+            return grpc.context("serve mind grpc api");
+        }
+        aoc_server::serve(listener, config, async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+        .context("serve mind api")
+    })
+}
+
+/// Stdout carries protocol frames only, so nothing is printed here.
+fn serve_stdio(store: StoreArgs) -> Result<()> {
+    let store_path = store.store_path()?;
+    MindStore::open_read_only(&store_path)
+        .with_context(|| format!("open mind store {}", store_path.display()))?;
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .context("start async runtime")?
+        .block_on(aoc_server::serve_stdio(ServerConfig::new(&store_path)))
+        .context("serve mind json-rpc")
+}
+
+fn check_bind_addr(addr: SocketAddr, allow_remote: bool) -> Result<()> {
+    if !addr.ip().is_loopback() && !allow_remote {
+        bail!("refusing to listen on non-loopback {addr}; pass --allow-remote to expose the API");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_loopback_addr_needs_allow_remote() {
+        let local: SocketAddr = DEFAULT_ADDR.parse().unwrap();
+        let remote: SocketAddr = "0.0.0.0:7700".parse().unwrap();
+        assert!(check_bind_addr(local, false).is_ok());
+        assert!(check_bind_addr(remote, false).is_err());
+        assert!(check_bind_addr(remote, true).is_ok());
+    }
+}
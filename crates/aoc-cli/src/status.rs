@@ -0,0 +1,123 @@
+use anyhow::{Context, Result};
+use aoc_storage::MindStoreStats;
+use chrono::{DateTime, Utc};
+use clap::Args;
+use serde_json::{json, Value};
+
+use crate::{mind_store::StoreArgs, output::json_mode};
+
+#[derive(Args, Debug)]
+pub struct StatusArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+}
+
+pub fn handle_status_command(args: StatusArgs) -> Result<()> {
+    let (store, store_path) = args.store.open()?;
+    let now = Utc::now();
+    let stats = store.stats(now).context("collect mind store stats")?;
+    if json_mode() {
+        let payload = json!({
+            "store_path": store_path,
+            "generated_at": now.to_rfc3339(),
+            "stats": stats_json(&stats),
+        });
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(());
+    }
+
+    println!("store: {}", store_path.display());
+    println!("schema_version: {}", stats.schema_version);
+    println!(
+        "pending_jobs: reflector={} t3={}",
+        stats.pending_reflector_jobs, stats.pending_t3_jobs
+    );
+    println!("tables:");
+    for (table, count) in &stats.table_counts {
+        println!("  {table:<28} {count}");
+    }
+    println!("leases:");
+    if stats.leases.is_empty() {
+        println!("  <none>");
+    }
+    for lease in &stats.leases {
+        println!(
+            "  {:<10} scope={} owner={} heartbeat={} {}",
+            lease.runtime,
+            lease.scope_id,
+            lease.owner_id,
+            age_label(now, lease.heartbeat_at),
+            if lease.expired { "EXPIRED" } else { "live" }
+        );
+    }
+    println!("observer:");
+    if stats.observer_activity.is_empty() {
+        println!("  <no observations>");
+    }
+    for activity in &stats.observer_activity {
+        println!(
+            "  {} last_run={} observations={} conversations={}",
+            activity.session_id,
+            age_label(now, activity.last_observation_at),
+            activity.observations,
+            activity.conversations
+        );
+    }
+    println!("watermarks:");
+    if stats.watermarks.is_empty() {
+        println!("  <none>");
+    }
+    for watermark in &stats.watermarks {
+        println!(
+            "  {} pending={} lag={}s",
+            watermark.scope_key, watermark.pending_artifacts, watermark.lag_seconds
+        );
+    }
+    Ok(())
+}
+
+fn stats_json(stats: &MindStoreStats) -> Value {
+    json!({
+        "schema_version": stats.schema_version,
+        "table_counts": stats
+            .table_counts
+            .iter()
+            .map(|(table, count)| (table.clone(), json!(count)))
+            .collect::<serde_json::Map<_, _>>(),
+        "pending_reflector_jobs": stats.pending_reflector_jobs,
+        "pending_t3_jobs": stats.pending_t3_jobs,
+        "leases": stats.leases.iter().map(|lease| json!({
+            "runtime": lease.runtime,
+            "scope_id": lease.scope_id,
+            "owner_id": lease.owner_id,
+            "owner_pid": lease.owner_pid,
+            "heartbeat_at": lease.heartbeat_at.to_rfc3339(),
+            "expires_at": lease.expires_at.to_rfc3339(),
+            "expired": lease.expired,
+        })).collect::<Vec<_>>(),
+        "observer_activity": stats.observer_activity.iter().map(|activity| json!({
+            "session_id": activity.session_id,
+            "conversations": activity.conversations,
+            "observations": activity.observations,
+            "last_observation_at": activity.last_observation_at.to_rfc3339(),
+        })).collect::<Vec<_>>(),
+        "watermarks": stats.watermarks.iter().map(|watermark| json!({
+            "scope_key": watermark.scope_key,
+            "last_artifact_ts": watermark.last_artifact_ts.map(|ts| ts.to_rfc3339()),
+            "latest_artifact_ts": watermark.latest_artifact_ts.map(|ts| ts.to_rfc3339()),
+            "pending_artifacts": watermark.pending_artifacts,
+            "lag_seconds": watermark.lag_seconds,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+fn age_label(now: DateTime<Utc>, at: DateTime<Utc>) -> String {
+    let seconds = (now - at).num_seconds();
+    match seconds {
+        s if s < 0 => at.to_rfc3339(),
+        s if s < 120 => format!("{s}s ago"),
+        s if s < 7_200 => format!("{}m ago", s / 60),
+        s if s < 172_800 => format!("{}h ago", s / 3_600),
+        s => format!("{}d ago", s / 86_400),
+    }
+}
@@ -0,0 +1,283 @@
+//! `aoc sync`: two-way sync with another Mind database or an `aoc serve` URL.
+//!
+//! Each run pulls the peer's journal since the last pull, pushes ours since
+//! the last push, then pulls once more so conflict winners the peer
+//! re-journaled come straight back. Watermarks are kept in the local store
+//! only, keyed by the peer's sync identity. Rows applied from the peer are
+//! journaled here too, so the next run sends them back once; the peer skips
+//! them by hash.
+
+use anyhow::{bail, Context, Result};
+use aoc_server::TOKEN_ENV;
+use aoc_storage::{
+    MindStore, MindWriterGuard, SyncApplyReport, SyncBundle, SyncConflict, SyncPeer,
+};
+use chrono::Utc;
+use clap::Args;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    mind_store::{open_writer_at, StoreArgs},
+    output::{json_mode, print_json, Severity, SeverityExit},
+};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Args, Debug)]
+pub struct SyncArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Peer Mind database path, or the base URL of an `aoc serve` instance.
+    pub remote: String,
+    /// Bearer token for pushing to a URL. Falls back to AOC_SERVER_TOKEN.
+    #[arg(long)]
+    pub token: Option<String>,
+    /// Journal entries per bundle.
+    #[arg(long, default_value_t = 500)]
+    pub batch: usize,
+    /// Give the local store a new sync identity first (for a database copied
+    /// from the peer).
+    #[arg(long, default_value_t = false)]
+    pub reset_identity: bool,
+}
+
+/// Totals across every bundle applied in one direction.
+#[derive(Debug, Default, Serialize)]
+struct DirectionTotals {
+    bundles: usize,
+    groups: usize,
+    inserted: usize,
+    unchanged: usize,
+    conflicts: Vec<SyncConflict>,
+}
+
+impl DirectionTotals {
+    fn add(&mut self, report: SyncApplyReport) {
+        self.bundles += 1;
+        self.groups += report.groups;
+        self.inserted += report.inserted;
+        self.unchanged += report.unchanged;
+        self.conflicts.extend(report.conflicts);
+    }
+}
+
+enum Remote {
+    Store(MindWriterGuard),
+    Http(HttpRemote),
+}
+
+impl Remote {
+    fn open(target: &str, token: Option<String>, local_path: &Path) -> Result<Self> {
+        if target.starts_with("http://") || target.starts_with("https://") {
+            return Ok(Self::Http(HttpRemote {
+                agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+                base: target.trim_end_matches('/').to_string(),
+                token: token.or_else(|| std::env::var(TOKEN_ENV).ok()),
+            }));
+        }
+        let path = PathBuf::from(target);
+        if !path.exists() {
+            bail!("peer store {} does not exist", path.display());
+        }
+        if path.canonicalize().ok() == local_path.canonicalize().ok() {
+            bail!("refusing to sync the store with itself");
+        }
+        Ok(Self::Store(open_writer_at(&path)?))
+    }
+
+    fn store_id(&self) -> Result<String> {
+        match self {
+            Self::Store(store) => Ok(store.sync_store_id()?),
+            Self::Http(http) => {
+                let identity: Value = http.get("/v1/sync")?;
+                identity["store_id"]
+                    .as_str()
+                    .map(str::to_string)
+                    .context("peer did not report a sync store id")
+            }
+        }
+    }
+
+    fn bundle(&self, since: i64, limit: usize) -> Result<SyncBundle> {
+        match self {
+            Self::Store(store) => Ok(store.sync_bundle_since(since, limit)?),
+            Self::Http(http) => http.get(&format!("/v1/sync/bundle?since={since}&limit={limit}")),
+        }
+    }
+
+    fn apply(&self, bundle: &SyncBundle) -> Result<SyncApplyReport> {
+        match self {
+            Self::Store(store) => Ok(store.apply_sync_bundle(bundle, Utc::now())?),
+            Self::Http(http) => http.post("/v1/sync/apply", bundle),
+        }
+    }
+}
+
+struct HttpRemote {
+    agent: ureq::Agent,
+    base: String,
+    token: Option<String>,
+}
+
+impl HttpRemote {
+    fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = format!("{}{path}", self.base);
+        read_response(&url, self.agent.get(&url).call())
+    }
+
+    fn post<T: DeserializeOwned>(&self, path: &str, body: &impl Serialize) -> Result<T> {
+        let url = format!("{}{path}", self.base);
+        let mut request = self
+            .agent
+            .post(&url)
+            .set("Content-Type", "application/json");
+        if let Some(token) = &self.token {
+            request = request.set("Authorization", &format!("Bearer {token}"));
+        }
+        read_response(&url, request.send_string(&serde_json::to_string(body)?))
+    }
+}
+
+fn read_response<T: DeserializeOwned>(
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<T> {
+    match response {
+        Ok(response) => {
+            let body = response
+                .into_string()
+                .with_context(|| format!("read {url}"))?;
+            serde_json::from_str(&body).with_context(|| format!("decode {url}"))
+        }
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            let message = serde_json::from_str::<Value>(&body)
+                .ok()
+                .and_then(|value| value["error"].as_str().map(str::to_string))
+                .unwrap_or(body);
+            bail!("{url} answered {status}: {message}")
+        }
+        Err(err) => Err(err).with_context(|| format!("request {url}")),
+    }
+}
+
+pub fn handle_sync_command(args: SyncArgs) -> Result<()> {
+    if args.batch == 0 {
+        bail!("--batch must be at least 1");
+    }
+    let (local, store_path) = args.store.open_writer()?;
+    if args.reset_identity {
+        local.reset_sync_store_id().context("reset sync identity")?;
+    }
+    let remote = Remote::open(&args.remote, args.token.clone(), &store_path)?;
+    let peer_id = remote.store_id()?;
+    if peer_id == local.sync_store_id()? {
+        bail!(
+            "{} shares this store's sync identity (copied database?); rerun with --reset-identity",
+            args.remote
+        );
+    }
+
+    let mut peer = local.sync_peer(&peer_id)?;
+    let mut pulled = DirectionTotals::default();
+    let mut pushed = DirectionTotals::default();
+    pull(&local, &remote, &mut peer, args.batch, &mut pulled)?;
+    push(&local, &remote, &mut peer, args.batch, &mut pushed)?;
+    pull(&local, &remote, &mut peer, args.batch, &mut pulled)?;
+
+    let conflicts = pulled.conflicts.len() + pushed.conflicts.len();
+    if json_mode() {
+        print_json(&json!({
+            "store_path": store_path,
+            "remote": args.remote,
+            "peer_store_id": peer_id,
+            "pulled": pulled,
+            "pushed": pushed,
+        }))?;
+    } else {
+        println!(
+            "synced {} with {} (peer {peer_id})",
+            store_path.display(),
+            args.remote
+        );
+        print_direction("pulled", &pulled);
+        print_direction("pushed", &pushed);
+    }
+    if conflicts > 0 {
+        return Err(SeverityExit::new(
+            Severity::Warning,
+            format!(
+                "{conflicts} conflicting group(s) resolved; losers kept in mind_sync_conflicts"
+            ),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn pull(
+    local: &MindStore,
+    remote: &Remote,
+    peer: &mut SyncPeer,
+    batch: usize,
+    totals: &mut DirectionTotals,
+) -> Result<()> {
+    loop {
+        let bundle = remote.bundle(peer.pulled_seq, batch)?;
+        if !bundle.groups.is_empty() {
+            totals.add(local.apply_sync_bundle(&bundle, Utc::now())?);
+        }
+        peer.pulled_seq = bundle.until_seq;
+        local.record_sync_peer(peer, Utc::now())?;
+        if !bundle.more {
+            return Ok(());
+        }
+    }
+}
+
+fn push(
+    local: &MindStore,
+    remote: &Remote,
+    peer: &mut SyncPeer,
+    batch: usize,
+    totals: &mut DirectionTotals,
+) -> Result<()> {
+    loop {
+        let bundle = local.sync_bundle_since(peer.pushed_seq, batch)?;
+        if !bundle.groups.is_empty() {
+            totals.add(remote.apply(&bundle)?);
+        }
+        peer.pushed_seq = bundle.until_seq;
+        local.record_sync_peer(peer, Utc::now())?;
+        if !bundle.more {
+            return Ok(());
+        }
+    }
+}
+
+fn print_direction(label: &str, totals: &DirectionTotals) {
+    println!(
+        "  {label}: {} group(s) in {} bundle(s), {} inserted, {} unchanged, {} conflict(s)",
+        totals.groups,
+        totals.bundles,
+        totals.inserted,
+        totals.unchanged,
+        totals.conflicts.len()
+    );
+    for conflict in &totals.conflicts {
+        println!(
+            "    {} {}: kept {}",
+            conflict.table,
+            conflict.key,
+            match conflict.kept {
+                aoc_storage::SyncSide::Local => "local",
+                aoc_storage::SyncSide::Remote => "remote",
+            }
+        );
+    }
+}
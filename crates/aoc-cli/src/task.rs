@@ -11,6 +11,8 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::output::{json_mode, print_change, print_json};
+
 #[derive(Subcommand, Debug)]
 #[command(rename_all = "kebab-case")]
 pub enum TaskCommand {
@@ -143,8 +145,6 @@ pub struct TaskListArgs {
     #[arg(long, alias = "query")]
     pub search: Option<String>,
     #[arg(long)]
-    pub json: bool,
-    #[arg(long)]
     pub all_tags: bool,
     #[arg(long)]
     pub include_parked: bool,
@@ -186,8 +186,6 @@ pub struct TaskShowArgs {
     pub id: String,
     #[arg(long)]
     pub tag: Option<String>,
-    #[arg(long)]
-    pub json: bool,
 }
 
 #[derive(Args, Debug)]
@@ -252,8 +250,6 @@ pub struct TaskStatusArgs {
 pub struct TaskNextArgs {
     #[arg(long)]
     pub tag: Option<String>,
-    #[arg(long)]
-    pub json: bool,
 }
 
 #[derive(Args, Debug)]
@@ -261,8 +257,6 @@ pub struct TaskReadyArgs {
     #[arg(long)]
     pub tag: Option<String>,
     #[arg(long)]
-    pub json: bool,
-    #[arg(long)]
     pub explain: bool,
     #[arg(long, default_value_t = 5)]
     pub limit: usize,
@@ -273,8 +267,6 @@ pub struct TaskSearchArgs {
     pub query: String,
     #[arg(long)]
     pub tag: Option<String>,
-    #[arg(long)]
-    pub json: bool,
 }
 
 #[derive(Args, Debug)]
@@ -332,8 +324,6 @@ pub struct TaskContextArgs {
     #[arg(long, value_enum, default_value = "coding")]
     pub mode: ContextMode,
     #[arg(long)]
-    pub json: bool,
-    #[arg(long)]
     pub expand_completed: bool,
 }
 
@@ -342,8 +332,6 @@ pub struct TaskOutcomeShowArgs {
     pub id: String,
     #[arg(long)]
     pub tag: Option<String>,
-    #[arg(long)]
-    pub json: bool,
 }
 
 #[derive(Args, Debug)]
@@ -351,8 +339,6 @@ pub struct TaskAuditOutcomesArgs {
     #[arg(long)]
     pub tag: Option<String>,
     #[arg(long)]
-    pub json: bool,
-    #[arg(long)]
     pub all_tags: bool,
 }
 
@@ -378,10 +364,7 @@ pub struct TaskSyncArgs {
 }
 
 #[derive(Args, Debug)]
-pub struct TagListArgs {
-    #[arg(long)]
-    pub json: bool,
-}
+pub struct TagListArgs {}
 
 #[derive(Args, Debug)]
 pub struct TagAddArgs {
@@ -405,17 +388,12 @@ pub struct TagSetArgs {
 }
 
 #[derive(Args, Debug)]
-pub struct TagCurrentArgs {
-    #[arg(long)]
-    pub json: bool,
-}
+pub struct TagCurrentArgs {}
 
 #[derive(Args, Debug)]
 pub struct TagPrdShowArgs {
     #[arg(long)]
     pub tag: Option<String>,
-    #[arg(long)]
-    pub json: bool,
 }
 
 #[derive(Args, Debug)]
@@ -503,8 +481,6 @@ pub struct PrdShowArgs {
     pub id: String,
     #[arg(long)]
     pub tag: Option<String>,
-    #[arg(long)]
-    pub json: bool,
 }
 
 #[derive(Args, Debug)]
@@ -641,7 +617,10 @@ pub fn handle_task_command(command: TaskCommand) -> Result<()> {
 
 impl TaskContext {
     fn new() -> Result<Self> {
-        let root = resolve_root()?;
+        Ok(Self::at(resolve_root()?))
+    }
+
+    fn at(root: PathBuf) -> Self {
         let tasks_path = root.join(".taskmaster/tasks/tasks.json");
         let config_path = root.join(".taskmaster/config.json");
         let state_path = root.join(".taskmaster/state.json");
@@ -653,11 +632,11 @@ impl TaskContext {
         };
         let config = load_config(&paths);
         let state = load_state(&paths);
-        Ok(Self {
+        Self {
             paths,
             config,
             state,
-        })
+        }
     }
 
     fn resolve_tag(&self, override_tag: Option<&str>) -> String {
@@ -708,6 +687,21 @@ impl TaskContext {
     }
 }
 
+/// Tasks of `tag` (or the current tag) in the Taskmaster file under `root`,
+/// with the tag that was resolved. Empty when the project has no tasks.json.
+pub(crate) fn taskmaster_tag_tasks(root: &Path, tag: Option<&str>) -> Result<(String, Vec<Task>)> {
+    let ctx = TaskContext::at(root.to_path_buf());
+    let tag = ctx.resolve_tag(tag);
+    let load = load_project(&ctx.paths)?;
+    let tasks = load
+        .project
+        .tags
+        .get(&tag)
+        .map(|tag_ctx| tag_ctx.tasks.clone())
+        .unwrap_or_default();
+    Ok((tag, tasks))
+}
+
 fn resolve_root() -> Result<PathBuf> {
     let cwd = std::env::current_dir()?;
     if let Some(override_root) = resolve_root_override(&cwd)? {
@@ -1068,11 +1062,14 @@ fn init_task_storage(ctx: &TaskContext, args: &TaskInitArgs) -> Result<()> {
         })?;
     }
 
-    println!(
-        "Initialized task storage at {}",
-        ctx.paths.tasks_path.display()
-    );
-    Ok(())
+    print_change(
+        "task.init",
+        format!(
+            "Initialized task storage at {}",
+            ctx.paths.tasks_path.display()
+        ),
+        json!({ "tasks_path": ctx.paths.tasks_path, "tag": tag }),
+    )
 }
 
 fn ensure_tag<'a>(project: &'a mut ProjectData, tag: &str) -> &'a mut TagContext {
@@ -1137,7 +1134,7 @@ fn list_tasks(ctx: &TaskContext, args: &TaskListArgs) -> Result<()> {
     }
 
     if args.all_tags {
-        if args.json {
+        if json_mode() {
             let filtered = filter_project_for_list(&project, args);
             let payload = serde_json::to_string_pretty(&filtered)?;
             println!("{}", payload);
@@ -1169,7 +1166,7 @@ fn list_tasks(ctx: &TaskContext, args: &TaskListArgs) -> Result<()> {
         }
     };
 
-    if args.json {
+    if json_mode() {
         let filtered = filter_tag_for_list(tag_ctx, args);
         let payload = serde_json::to_string_pretty(&filtered)?;
         println!("{}", payload);
@@ -1294,8 +1291,13 @@ fn add_task(ctx: &TaskContext, args: &TaskAddArgs) -> Result<()> {
     };
     tag_ctx.tasks.push(task);
     save_project(&ctx.paths, &load.project)?;
-    println!("Added task [{id}] to tag '{tag}'.");
-    Ok(())
+    print_task_change(
+        "task.add",
+        format!("Added task [{id}] to tag '{tag}'."),
+        &load.project,
+        &tag,
+        &id,
+    )
 }
 
 fn show_task(ctx: &TaskContext, args: &TaskShowArgs) -> Result<()> {
@@ -1312,7 +1314,7 @@ fn show_task(ctx: &TaskContext, args: &TaskShowArgs) -> Result<()> {
         .find(|task| task.id == args.id)
         .ok_or_else(|| anyhow::anyhow!("Task [{}] not found in tag '{tag}'.", args.id))?;
 
-    if args.json {
+    if json_mode() {
         println!("{}", serde_json::to_string_pretty(task)?);
         return Ok(());
     }
@@ -1417,8 +1419,13 @@ fn edit_task(ctx: &TaskContext, args: &TaskEditArgs) -> Result<()> {
     }
     task.updated_at = Some(now_timestamp());
     save_project(&ctx.paths, &load.project)?;
-    println!("Updated task [{}] in tag '{tag}'.", args.id);
-    Ok(())
+    print_task_change(
+        "task.edit",
+        format!("Updated task [{}] in tag '{tag}'.", args.id),
+        &load.project,
+        &tag,
+        &args.id,
+    )
 }
 
 fn remove_task(ctx: &TaskContext, args: &TaskRemoveArgs) -> Result<()> {
@@ -1431,10 +1438,13 @@ fn remove_task(ctx: &TaskContext, args: &TaskRemoveArgs) -> Result<()> {
         .ok_or_else(|| anyhow::anyhow!("No tag named '{tag}' found."))?;
     let task_idx = find_task_index(tag_ctx, &args.id)
         .ok_or_else(|| anyhow::anyhow!("Task [{}] not found in tag '{tag}'.", args.id))?;
-    tag_ctx.tasks.remove(task_idx);
+    let removed = tag_ctx.tasks.remove(task_idx);
     save_project(&ctx.paths, &load.project)?;
-    println!("Removed task [{}] from tag '{tag}'.", args.id);
-    Ok(())
+    print_change(
+        "task.remove",
+        format!("Removed task [{}] from tag '{tag}'.", args.id),
+        json!({ "tag": tag, "task": removed }),
+    )
 }
 
 fn set_task_status(ctx: &TaskContext, args: &TaskTargetArgs, status: TaskStatus) -> Result<()> {
@@ -1454,8 +1464,13 @@ fn set_task_status(ctx: &TaskContext, args: &TaskTargetArgs, status: TaskStatus)
         task.status.clone()
     };
     save_project(&ctx.paths, &load.project)?;
-    println!("Updated task [{}] to {}.", args.id, status_label);
-    Ok(())
+    print_task_change(
+        "task.status",
+        format!("Updated task [{}] to {}.", args.id, status_label),
+        &load.project,
+        &tag,
+        &args.id,
+    )
 }
 
 fn next_task(ctx: &TaskContext, args: &TaskNextArgs) -> Result<()> {
@@ -1472,12 +1487,13 @@ fn next_task(ctx: &TaskContext, args: &TaskNextArgs) -> Result<()> {
         .find(|task| !task.status.is_done() && !task.status.is_parked());
     match task {
         Some(task) => {
-            if args.json {
+            if json_mode() {
                 println!("{}", serde_json::to_string_pretty(task)?);
             } else {
                 println!("[{}] {}", task.id, task.title);
             }
         }
+        None if json_mode() => println!("null"),
         None => println!("No pending tasks in tag '{tag}'."),
     }
     Ok(())
@@ -1502,7 +1518,7 @@ fn ready_tasks(ctx: &TaskContext, args: &TaskReadyArgs) -> Result<()> {
     let limit = args.limit.max(1);
     let ready_limited: Vec<_> = ready.iter().take(limit).collect();
 
-    if args.json {
+    if json_mode() {
         let ready_json: Vec<_> = ready_limited
             .iter()
             .map(|item| readiness_item_json(item))
@@ -1711,7 +1727,6 @@ fn search_tasks(ctx: &TaskContext, args: &TaskSearchArgs) -> Result<()> {
         tag: args.tag.clone(),
         status: None,
         search: Some(args.query.clone()),
-        json: args.json,
         all_tags: false,
         include_parked: true,
         all_statuses: false,
@@ -1724,8 +1739,13 @@ fn move_task(ctx: &TaskContext, args: &TaskMoveArgs) -> Result<()> {
     let from_tag = ctx.resolve_tag(args.from.as_deref());
     let to_tag = args.to.trim();
     if from_tag == to_tag {
-        println!("Task [{}] already in tag '{to_tag}'.", args.id);
-        return Ok(());
+        return print_task_change(
+            "task.move",
+            format!("Task [{}] already in tag '{to_tag}'.", args.id),
+            &load.project,
+            to_tag,
+            &args.id,
+        );
     }
 
     let task = {
@@ -1742,8 +1762,13 @@ fn move_task(ctx: &TaskContext, args: &TaskMoveArgs) -> Result<()> {
     let to_ctx = ensure_tag(&mut load.project, to_tag);
     to_ctx.tasks.push(task);
     save_project(&ctx.paths, &load.project)?;
-    println!("Moved task [{}] from '{from_tag}' to '{to_tag}'.", args.id);
-    Ok(())
+    print_task_change(
+        "task.move",
+        format!("Moved task [{}] from '{from_tag}' to '{to_tag}'.", args.id),
+        &load.project,
+        to_tag,
+        &args.id,
+    )
 }
 
 fn toggle_agent(ctx: &TaskContext, args: &TaskAgentArgs) -> Result<()> {
@@ -1769,8 +1794,13 @@ fn toggle_agent(ctx: &TaskContext, args: &TaskAgentArgs) -> Result<()> {
         task.active_agent
     };
     save_project(&ctx.paths, &load.project)?;
-    println!("Task [{}] active_agent: {}", args.id, active_agent);
-    Ok(())
+    print_task_change(
+        "task.agent",
+        format!("Task [{}] active_agent: {}", args.id, active_agent),
+        &load.project,
+        &tag,
+        &args.id,
+    )
 }
 
 fn complete_task(ctx: &TaskContext, args: &TaskCompleteArgs) -> Result<()> {
@@ -1848,15 +1878,20 @@ fn complete_task(ctx: &TaskContext, args: &TaskCompleteArgs) -> Result<()> {
     task.aoc_outcome = Some(outcome);
     task.updated_at = Some(now_timestamp());
     save_project(&ctx.paths, &load.project)?;
-    println!("Completed task [{}] with status {}.", args.id, args.status);
-    Ok(())
+    print_task_change(
+        "task.complete",
+        format!("Completed task [{}] with status {}.", args.id, args.status),
+        &load.project,
+        &tag,
+        &args.id,
+    )
 }
 
 fn show_outcome(ctx: &TaskContext, args: &TaskOutcomeShowArgs) -> Result<()> {
     let load = load_project(&ctx.paths)?;
     let tag = ctx.resolve_tag(args.tag.as_deref());
     let task = find_task_in_project(&load.project, &tag, &args.id)?;
-    if args.json {
+    if json_mode() {
         println!("{}", serde_json::to_string_pretty(&task.aoc_outcome)?);
         return Ok(());
     }
@@ -1899,7 +1934,7 @@ fn show_task_context(ctx: &TaskContext, args: &TaskContextArgs) -> Result<()> {
         .map(|candidate| json!({"id": candidate.id, "title": candidate.title, "status": candidate.status}))
         .collect();
 
-    if args.json {
+    if json_mode() {
         let payload = json!({
             "id": task.id,
             "title": task.title,
@@ -2014,7 +2049,7 @@ fn audit_outcomes(ctx: &TaskContext, args: &TaskAuditOutcomesArgs) -> Result<()>
         }
     }
 
-    if args.json {
+    if json_mode() {
         println!("{}", serde_json::to_string_pretty(&findings)?);
     } else if findings.is_empty() {
         println!("No outcome audit findings.");
@@ -2026,6 +2061,18 @@ fn audit_outcomes(ctx: &TaskContext, args: &TaskAuditOutcomesArgs) -> Result<()>
     Ok(())
 }
 
+/// Reports a task mutation with the task as it was saved.
+fn print_task_change(
+    action: &str,
+    message: String,
+    project: &ProjectData,
+    tag: &str,
+    id: &str,
+) -> Result<()> {
+    let task = find_task_in_project(project, tag, id).ok();
+    print_change(action, message, json!({ "tag": tag, "task": task }))
+}
+
 fn find_task_in_project<'a>(project: &'a ProjectData, tag: &str, id: &str) -> Result<&'a Task> {
     let tag_ctx = project
         .tags
@@ -2104,11 +2151,11 @@ fn print_outcome(task: &Task, outcome: &TaskOutcome) {
     }
 }
 
-fn list_tags(ctx: &TaskContext, args: &TagListArgs) -> Result<()> {
+fn list_tags(ctx: &TaskContext, _args: &TagListArgs) -> Result<()> {
     let load = load_project(&ctx.paths)?;
     let mut tags: Vec<_> = load.project.tags.keys().cloned().collect();
     tags.sort();
-    if args.json {
+    if json_mode() {
         println!("{}", serde_json::to_string_pretty(&tags)?);
         return Ok(());
     }
@@ -2127,8 +2174,11 @@ fn add_tag(ctx: &TaskContext, args: &TagAddArgs) -> Result<()> {
     }
     ensure_tag(&mut load.project, &args.name);
     save_project(&ctx.paths, &load.project)?;
-    println!("Added tag '{}'", args.name);
-    Ok(())
+    print_change(
+        "tag.add",
+        format!("Added tag '{}'", args.name),
+        json!({ "tag": args.name }),
+    )
 }
 
 fn rename_tag(ctx: &TaskContext, args: &TagRenameArgs) -> Result<()> {
@@ -2143,8 +2193,11 @@ fn rename_tag(ctx: &TaskContext, args: &TagRenameArgs) -> Result<()> {
         load.project.tags.insert(args.to.clone(), ctx);
     }
     save_project(&ctx.paths, &load.project)?;
-    println!("Renamed tag '{}' -> '{}'.", args.from, args.to);
-    Ok(())
+    print_change(
+        "tag.rename",
+        format!("Renamed tag '{}' -> '{}'.", args.from, args.to),
+        json!({ "from": args.from, "to": args.to }),
+    )
 }
 
 fn remove_tag(ctx: &TaskContext, args: &TagRemoveArgs) -> Result<()> {
@@ -2153,17 +2206,23 @@ fn remove_tag(ctx: &TaskContext, args: &TagRemoveArgs) -> Result<()> {
         bail!("Tag '{}' not found.", args.name);
     }
     save_project(&ctx.paths, &load.project)?;
-    println!("Removed tag '{}'", args.name);
-    Ok(())
+    print_change(
+        "tag.remove",
+        format!("Removed tag '{}'", args.name),
+        json!({ "tag": args.name }),
+    )
 }
 
 fn set_tag(ctx: &TaskContext, args: &TagSetArgs) -> Result<()> {
     touch_state(&ctx.paths, Some(&args.name))?;
-    println!("Current tag set to '{}'", args.name);
-    Ok(())
+    print_change(
+        "tag.set",
+        format!("Current tag set to '{}'", args.name),
+        json!({ "tag": args.name }),
+    )
 }
 
-fn current_tag(ctx: &TaskContext, args: &TagCurrentArgs) -> Result<()> {
+fn current_tag(ctx: &TaskContext, _args: &TagCurrentArgs) -> Result<()> {
     let tag = ctx.resolve_tag(None);
     let ProjectLoad { project, exists } = load_project(&ctx.paths)?;
     let task_count = if exists {
@@ -2176,7 +2235,7 @@ fn current_tag(ctx: &TaskContext, args: &TagCurrentArgs) -> Result<()> {
         0
     };
 
-    if args.json {
+    if json_mode() {
         let payload = json!({
             "tag": tag,
             "task_count": task_count,
@@ -2199,7 +2258,7 @@ fn show_tag_prd(ctx: &TaskContext, args: &TagPrdShowArgs) -> Result<()> {
         .ok_or_else(|| anyhow::anyhow!("No tag named '{tag}' found."))?;
     let prd = read_tag_prd(tag_ctx, &tag)?;
 
-    if args.json {
+    if json_mode() {
         println!("{}", serde_json::to_string_pretty(&prd)?);
         return Ok(());
     }
@@ -2239,8 +2298,11 @@ fn set_tag_prd(ctx: &TaskContext, args: &TagPrdSetArgs) -> Result<()> {
     });
 
     save_project(&ctx.paths, &load.project)?;
-    println!("Linked tag '{}' to spec {}", tag, resolved.display());
-    Ok(())
+    print_change(
+        "tag.spec.set",
+        format!("Linked tag '{}' to spec {}", tag, resolved.display()),
+        json!({ "tag": tag, "spec": load.project.tags.get(&tag).and_then(|tag_ctx| tag_ctx.tag_prd()) }),
+    )
 }
 
 fn clear_tag_prd(ctx: &TaskContext, args: &TagPrdClearArgs) -> Result<()> {
@@ -2254,8 +2316,11 @@ fn clear_tag_prd(ctx: &TaskContext, args: &TagPrdClearArgs) -> Result<()> {
 
     tag_ctx.clear_tag_prd();
     save_project(&ctx.paths, &load.project)?;
-    println!("Cleared spec link for tag '{}'.", tag);
-    Ok(())
+    print_change(
+        "tag.spec.clear",
+        format!("Cleared spec link for tag '{}'.", tag),
+        json!({ "tag": tag, "spec": load.project.tags.get(&tag).and_then(|tag_ctx| tag_ctx.tag_prd()) }),
+    )
 }
 
 fn init_tag_prd(ctx: &TaskContext, args: &TagPrdInitArgs) -> Result<()> {
@@ -2296,12 +2361,15 @@ fn init_tag_prd(ctx: &TaskContext, args: &TagPrdInitArgs) -> Result<()> {
         extra: HashMap::new(),
     });
     save_project(&ctx.paths, &load.project)?;
-    println!(
-        "Initialized spec for tag '{}' at {}",
-        tag,
-        target_abs.display()
-    );
-    Ok(())
+    print_change(
+        "tag.spec.init",
+        format!(
+            "Initialized spec for tag '{}' at {}",
+            tag,
+            target_abs.display()
+        ),
+        json!({ "tag": tag, "spec": load.project.tags.get(&tag).and_then(|tag_ctx| tag_ctx.tag_prd()) }),
+    )
 }
 
 fn read_tag_prd(tag_ctx: &TagContext, tag: &str) -> Result<Option<TaskPrd>> {
@@ -2335,7 +2403,7 @@ fn show_task_prd(ctx: &TaskContext, args: &PrdShowArgs) -> Result<()> {
         .find(|task| task.id == args.id)
         .ok_or_else(|| anyhow::anyhow!("Task [{}] not found in tag '{tag}'.", args.id))?;
 
-    if args.json {
+    if json_mode() {
         println!("{}", serde_json::to_string_pretty(&task.aoc_prd)?);
         return Ok(());
     }
@@ -2386,8 +2454,13 @@ fn set_task_prd(ctx: &TaskContext, args: &PrdSetArgs) -> Result<()> {
         task.id.clone()
     };
     save_project(&ctx.paths, &load.project)?;
-    println!("Linked task [{}] to spec {}", task_id, resolved.display());
-    Ok(())
+    print_task_change(
+        "spec.set",
+        format!("Linked task [{}] to spec {}", task_id, resolved.display()),
+        &load.project,
+        &tag,
+        &task_id,
+    )
 }
 
 fn clear_task_prd(ctx: &TaskContext, args: &PrdClearArgs) -> Result<()> {
@@ -2407,8 +2480,13 @@ fn clear_task_prd(ctx: &TaskContext, args: &PrdClearArgs) -> Result<()> {
         task.id.clone()
     };
     save_project(&ctx.paths, &load.project)?;
-    println!("Cleared spec link for task [{}].", task_id);
-    Ok(())
+    print_task_change(
+        "spec.clear",
+        format!("Cleared spec link for task [{}].", task_id),
+        &load.project,
+        &tag,
+        &task_id,
+    )
 }
 
 fn init_task_prd(ctx: &TaskContext, args: &PrdInitArgs) -> Result<()> {
@@ -2462,12 +2540,17 @@ fn init_task_prd(ctx: &TaskContext, args: &PrdInitArgs) -> Result<()> {
         task.id.clone()
     };
     save_project(&ctx.paths, &load.project)?;
-    println!(
-        "Initialized spec for task [{}] at {}",
-        task_id,
-        target_abs.display()
-    );
-    Ok(())
+    print_task_change(
+        "spec.init",
+        format!(
+            "Initialized spec for task [{}] at {}",
+            task_id,
+            target_abs.display()
+        ),
+        &load.project,
+        &tag,
+        &task_id,
+    )
 }
 
 fn resolve_path_arg(root: &Path, path: &PathBuf) -> PathBuf {
@@ -2595,8 +2678,13 @@ fn add_subtask(ctx: &TaskContext, args: &SubAddArgs) -> Result<()> {
     task.subtasks.push(subtask);
     task.updated_at = Some(now_timestamp());
     save_project(&ctx.paths, &load.project)?;
-    println!("Added subtask [{}] to task [{}]", sub_id, args.task_id);
-    Ok(())
+    print_task_change(
+        "sub.add",
+        format!("Added subtask [{}] to task [{}]", sub_id, args.task_id),
+        &load.project,
+        &tag,
+        &args.task_id,
+    )
 }
 
 fn edit_subtask(ctx: &TaskContext, args: &SubEditArgs) -> Result<()> {
@@ -2634,11 +2722,16 @@ fn edit_subtask(ctx: &TaskContext, args: &SubEditArgs) -> Result<()> {
     }
     task.updated_at = Some(now_timestamp());
     save_project(&ctx.paths, &load.project)?;
-    println!(
-        "Updated subtask [{}] in task [{}]",
-        args.sub_id, args.task_id
-    );
-    Ok(())
+    print_task_change(
+        "sub.edit",
+        format!(
+            "Updated subtask [{}] in task [{}]",
+            args.sub_id, args.task_id
+        ),
+        &load.project,
+        &tag,
+        &args.task_id,
+    )
 }
 
 fn remove_subtask(ctx: &TaskContext, args: &SubRemoveArgs) -> Result<()> {
@@ -2662,11 +2755,16 @@ fn remove_subtask(ctx: &TaskContext, args: &SubRemoveArgs) -> Result<()> {
     task.subtasks.remove(sub_idx);
     task.updated_at = Some(now_timestamp());
     save_project(&ctx.paths, &load.project)?;
-    println!(
-        "Removed subtask [{}] from task [{}]",
-        args.sub_id, args.task_id
-    );
-    Ok(())
+    print_task_change(
+        "sub.remove",
+        format!(
+            "Removed subtask [{}] from task [{}]",
+            args.sub_id, args.task_id
+        ),
+        &load.project,
+        &tag,
+        &args.task_id,
+    )
 }
 
 fn set_subtask_status(ctx: &TaskContext, args: &SubTargetArgs, status: TaskStatus) -> Result<()> {
@@ -2700,11 +2798,16 @@ fn set_subtask_status_explicit(ctx: &TaskContext, args: &SubStatusArgs) -> Resul
     task.subtasks[sub_idx].status = args.status.clone();
     task.updated_at = Some(now_timestamp());
     save_project(&ctx.paths, &load.project)?;
-    println!(
-        "Updated subtask [{}] in task [{}] to {}",
-        args.sub_id, args.task_id, args.status
-    );
-    Ok(())
+    print_task_change(
+        "sub.status",
+        format!(
+            "Updated subtask [{}] in task [{}] to {}",
+            args.sub_id, args.task_id, args.status
+        ),
+        &load.project,
+        &tag,
+        &args.task_id,
+    )
 }
 
 fn sync_tasks(ctx: &TaskContext, args: &TaskSyncArgs) -> Result<()> {
@@ -2712,22 +2815,36 @@ fn sync_tasks(ctx: &TaskContext, args: &TaskSyncArgs) -> Result<()> {
         bail!("Specify --from or --to for sync.");
     }
 
-    if let Some(source) = args.from {
-        match source {
-            SyncSource::Claude => sync_from_claude(ctx, args)?,
-        }
-    }
+    let imported = match args.from {
+        Some(SyncSource::Claude) => Some(sync_from_claude(ctx, args)?),
+        None => None,
+    };
+    let exported = match args.to {
+        Some(SyncSource::Claude) => Some(sync_to_claude(ctx, args)?),
+        None => None,
+    };
 
-    if let Some(source) = args.to {
-        match source {
-            SyncSource::Claude => sync_to_claude(ctx, args)?,
-        }
+    if json_mode() {
+        print_json(&json!({
+            "action": "task.sync",
+            "dry_run": args.dry_run,
+            "imported": imported,
+            "exported": exported,
+        }))?;
     }
-
     Ok(())
 }
 
-fn sync_from_claude(ctx: &TaskContext, args: &TaskSyncArgs) -> Result<()> {
+/// What one sync direction touched; in dry runs `task_ids` holds the tasks
+/// that would be exported and stays empty for imports.
+#[derive(Debug, Serialize)]
+struct SyncSummary {
+    plans_dir: PathBuf,
+    count: usize,
+    task_ids: Vec<String>,
+}
+
+fn sync_from_claude(ctx: &TaskContext, args: &TaskSyncArgs) -> Result<SyncSummary> {
     let plans_dir = resolve_claude_plans_dir(ctx, args.path.as_ref())?;
     if !plans_dir.exists() {
         bail!("Claude plans directory not found: {}", plans_dir.display());
@@ -2739,13 +2856,18 @@ fn sync_from_claude(ctx: &TaskContext, args: &TaskSyncArgs) -> Result<()> {
     let tag_ctx = ensure_tag(&mut load.project, &tag);
     let plan_files = collect_plan_files(&plans_dir)?;
 
+    let mut summary = SyncSummary {
+        plans_dir,
+        count: 0,
+        task_ids: Vec::new(),
+    };
     if plan_files.is_empty() {
-        println!("No plan files found in {}", plans_dir.display());
-        return Ok(());
+        if !json_mode() {
+            println!("No plan files found in {}", summary.plans_dir.display());
+        }
+        return Ok(summary);
     }
 
-    let mut imported = 0usize;
-
     for plan_path in plan_files {
         let plan_key = canonicalize_display(&plan_path);
         if task_has_plan_file(tag_ctx, &plan_key) {
@@ -2767,12 +2889,14 @@ fn sync_from_claude(ctx: &TaskContext, args: &TaskSyncArgs) -> Result<()> {
         }
 
         if args.dry_run {
-            println!(
-                "Would import plan {} -> {}",
-                plan_path.display(),
-                parsed.title
-            );
-            imported += 1;
+            if !json_mode() {
+                println!(
+                    "Would import plan {} -> {}",
+                    plan_path.display(),
+                    parsed.title
+                );
+            }
+            summary.count += 1;
             continue;
         }
 
@@ -2800,32 +2924,42 @@ fn sync_from_claude(ctx: &TaskContext, args: &TaskSyncArgs) -> Result<()> {
             extra,
         };
         tag_ctx.tasks.push(task);
-        imported += 1;
+        summary.count += 1;
+        summary.task_ids.push(id);
     }
 
     if args.dry_run {
-        println!("Plans detected for import: {}", imported);
-        return Ok(());
+        if !json_mode() {
+            println!("Plans detected for import: {}", summary.count);
+        }
+        return Ok(summary);
     }
 
-    if imported > 0 {
+    if summary.count > 0 {
         save_project(&ctx.paths, &load.project)?;
-        println!("Imported {} plan(s) into tag '{tag}'.", imported);
+    }
+    if json_mode() {
+        return Ok(summary);
+    }
+    if summary.count > 0 {
+        println!("Imported {} plan(s) into tag '{tag}'.", summary.count);
     } else {
         println!("No new plans to import.");
     }
 
-    Ok(())
+    Ok(summary)
 }
 
-fn sync_to_claude(ctx: &TaskContext, args: &TaskSyncArgs) -> Result<()> {
+fn sync_to_claude(ctx: &TaskContext, args: &TaskSyncArgs) -> Result<SyncSummary> {
     let plans_dir = resolve_claude_plans_dir(ctx, args.path.as_ref())?;
     if !plans_dir.exists() {
         if args.dry_run {
-            println!(
-                "Would create Claude plans directory: {}",
-                plans_dir.display()
-            );
+            if !json_mode() {
+                println!(
+                    "Would create Claude plans directory: {}",
+                    plans_dir.display()
+                );
+            }
         } else {
             fs::create_dir_all(&plans_dir)
                 .with_context(|| format!("Failed to create {}", plans_dir.display()))?;
@@ -2840,20 +2974,27 @@ fn sync_to_claude(ctx: &TaskContext, args: &TaskSyncArgs) -> Result<()> {
         .get_mut(&tag)
         .ok_or_else(|| anyhow::anyhow!("No tag named '{tag}' found."))?;
 
-    let mut exported = 0usize;
+    let mut summary = SyncSummary {
+        plans_dir,
+        count: 0,
+        task_ids: Vec::new(),
+    };
 
     for task in &mut tag_ctx.tasks {
         let filename = format_plan_filename(task);
-        let plan_path = plans_dir.join(filename);
+        let plan_path = summary.plans_dir.join(filename);
         let plan_key = canonicalize_display(&plan_path);
 
         if plan_path.exists() && !args.force {
             continue;
         }
 
+        summary.count += 1;
+        summary.task_ids.push(task.id.clone());
         if args.dry_run {
-            println!("Would export task [{}] -> {}", task.id, plan_path.display());
-            exported += 1;
+            if !json_mode() {
+                println!("Would export task [{}] -> {}", task.id, plan_path.display());
+            }
             continue;
         }
 
@@ -2862,22 +3003,32 @@ fn sync_to_claude(ctx: &TaskContext, args: &TaskSyncArgs) -> Result<()> {
             .with_context(|| format!("Failed to write {}", plan_path.display()))?;
         task.extra
             .insert("claudePlanFile".to_string(), Value::String(plan_key));
-        exported += 1;
     }
 
     if args.dry_run {
-        println!("Plans prepared for export: {}", exported);
-        return Ok(());
+        if !json_mode() {
+            println!("Plans prepared for export: {}", summary.count);
+        }
+        return Ok(summary);
     }
 
-    if exported > 0 {
+    if summary.count > 0 {
         save_project(&ctx.paths, &load.project)?;
-        println!("Exported {} task(s) to {}", exported, plans_dir.display());
+    }
+    if json_mode() {
+        return Ok(summary);
+    }
+    if summary.count > 0 {
+        println!(
+            "Exported {} task(s) to {}",
+            summary.count,
+            summary.plans_dir.display()
+        );
     } else {
         println!("No tasks exported.");
     }
 
-    Ok(())
+    Ok(summary)
 }
 
 fn resolve_claude_plans_dir(ctx: &TaskContext, override_path: Option<&PathBuf>) -> Result<PathBuf> {
@@ -0,0 +1,425 @@
+//! `aoc tasks`: what the Mind knows about each Taskmaster task.
+//!
+//! Where `aoc task` edits tasks.json, this group reads the attribution links
+//! the pipeline wrote against a task and folds them into a brief: relation
+//! counts, the artifact timeline, and the reflections that mention the task.
+//! `--markdown` renders the same brief for pasting into a PR description.
+
+use anyhow::{Context, Result};
+use aoc_core::{mind_contracts::ArtifactTaskRelation, Task};
+use aoc_storage::{MindStore, StoredArtifact};
+use chrono::{DateTime, Utc};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    mind_store::StoreArgs,
+    output::{json_mode, print_json},
+    task::taskmaster_tag_tasks,
+};
+
+const RECENT_TASK_LIMIT: usize = 20;
+
+#[derive(Subcommand, Debug)]
+pub enum TasksCommand {
+    /// Print the memory brief for one task
+    Show(TasksShowArgs),
+    /// Summarize memory for every task in a tag
+    List(TasksListArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct TasksShowArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Task id (`107`, `#107`, or `task:107`).
+    pub id: String,
+    /// Taskmaster tag used to look up the title; defaults to the current tag.
+    #[arg(long)]
+    pub tag: Option<String>,
+    /// Timeline entries to print.
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+    #[arg(long, default_value_t = false)]
+    pub markdown: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct TasksListArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Taskmaster tag; defaults to the current tag. Without a tasks.json the
+    /// most recently linked tasks are listed instead.
+    #[arg(long)]
+    pub tag: Option<String>,
+    #[arg(long, default_value_t = false)]
+    pub markdown: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskBriefEntry {
+    pub ts: DateTime<Utc>,
+    pub relation: String,
+    pub artifact_id: String,
+    pub kind: Option<String>,
+    pub conversation_id: Option<String>,
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskBrief {
+    pub task_id: String,
+    pub title: Option<String>,
+    pub status: Option<String>,
+    /// Link count per relation (`active`, `worked_on`, `mentioned`, `completed`).
+    pub relations: BTreeMap<String, usize>,
+    pub conversations: usize,
+    pub first_seen: Option<DateTime<Utc>>,
+    pub last_seen: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub timeline: Vec<TaskBriefEntry>,
+    pub reflections: Vec<TaskBriefEntry>,
+}
+
+pub fn handle_tasks_command(command: TasksCommand) -> Result<()> {
+    match command {
+        TasksCommand::Show(args) => handle_show(args),
+        TasksCommand::List(args) => handle_list(args),
+    }
+}
+
+fn handle_show(args: TasksShowArgs) -> Result<()> {
+    let task_id = normalize_task_id(&args.id);
+    let root = args.store.project_root()?;
+    let (_, tasks) = taskmaster_tag_tasks(&root, args.tag.as_deref())?;
+    let (store, _) = args.store.open()?;
+    let task = tasks.iter().find(|task| task.id == task_id);
+    let mut brief = build_task_brief(&store, &task_id, task)?;
+    let skipped = brief.timeline.len().saturating_sub(args.limit);
+    brief.timeline.drain(..skipped);
+
+    if json_mode() {
+        return print_json(&brief);
+    }
+    if args.markdown {
+        print!("{}", render_brief_markdown(&brief));
+        return Ok(());
+    }
+    println!(
+        "Task {}{}",
+        brief.task_id,
+        brief
+            .title
+            .as_deref()
+            .map(|title| format!(": {title}"))
+            .unwrap_or_default()
+    );
+    if let Some(status) = &brief.status {
+        println!("  status:        {status}");
+    }
+    println!("  links:         {}", relation_summary(&brief.relations));
+    println!("  conversations: {}", brief.conversations);
+    if let (Some(first), Some(last)) = (brief.first_seen, brief.last_seen) {
+        println!(
+            "  seen:          {} .. {}",
+            first.format("%Y-%m-%d %H:%M"),
+            last.format("%Y-%m-%d %H:%M")
+        );
+    }
+    if let Some(completed_at) = brief.completed_at {
+        println!("  completed:     {}", completed_at.format("%Y-%m-%d %H:%M"));
+    }
+    if !brief.timeline.is_empty() {
+        println!();
+        println!("Timeline:");
+        if skipped > 0 {
+            println!("  ... {skipped} earlier entries");
+        }
+        for entry in &brief.timeline {
+            println!(
+                "  {} {:<10} {} {}",
+                entry.ts.format("%Y-%m-%d %H:%M"),
+                entry.relation,
+                entry.artifact_id,
+                entry
+                    .text
+                    .as_deref()
+                    .and_then(|text| text.lines().next())
+                    .unwrap_or("(artifact missing)")
+            );
+        }
+    }
+    if !brief.reflections.is_empty() {
+        println!();
+        println!("Reflections:");
+        for entry in &brief.reflections {
+            println!(
+                "  {} {}",
+                entry.artifact_id,
+                entry.text.as_deref().unwrap_or_default()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn handle_list(args: TasksListArgs) -> Result<()> {
+    let root = args.store.project_root()?;
+    let (tag, tasks) = taskmaster_tag_tasks(&root, args.tag.as_deref())?;
+    let (store, _) = args.store.open()?;
+    let briefs = if tasks.is_empty() && args.tag.is_none() {
+        store
+            .recent_linked_task_ids(RECENT_TASK_LIMIT)
+            .context("load recent tasks")?
+            .iter()
+            .map(|task_id| build_task_brief(&store, task_id, None))
+            .collect::<Result<Vec<_>>>()?
+    } else {
+        tasks
+            .iter()
+            .map(|task| build_task_brief(&store, &task.id, Some(task)))
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    if json_mode() {
+        return print_json(&serde_json::json!({ "tag": tag, "tasks": briefs }));
+    }
+    if args.markdown {
+        println!("## Tasks ({tag})\n");
+        println!("| Task | Status | Links | Last seen |");
+        println!("| --- | --- | --- | --- |");
+        for brief in &briefs {
+            println!(
+                "| {} | {} | {} | {} |",
+                task_heading(brief),
+                brief.status.as_deref().unwrap_or("-"),
+                relation_summary(&brief.relations),
+                brief
+                    .last_seen
+                    .map(|ts| ts.format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|| "-".to_string())
+            );
+        }
+        return Ok(());
+    }
+    if briefs.is_empty() {
+        println!("No tasks in tag '{tag}'.");
+    }
+    for brief in &briefs {
+        println!(
+            "{:<6} {:<12} {:<16} {:<28} {}",
+            brief.task_id,
+            brief.status.as_deref().unwrap_or("-"),
+            brief
+                .last_seen
+                .map(|ts| ts.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            relation_summary(&brief.relations),
+            brief.title.as_deref().unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+/// Accepts `107`, `#107` or `task:107`, like the Mission Control timeline.
+fn normalize_task_id(value: &str) -> String {
+    let value = value.trim();
+    let value = value.strip_prefix("task:").unwrap_or(value);
+    value.strip_prefix('#').unwrap_or(value).trim().to_string()
+}
+
+fn build_task_brief(store: &MindStore, task_id: &str, task: Option<&Task>) -> Result<TaskBrief> {
+    let links = store
+        .artifact_task_links_for_task(task_id)
+        .with_context(|| format!("load links for task {task_id}"))?;
+    let mut brief = TaskBrief {
+        task_id: task_id.to_string(),
+        title: task.map(|task| task.title.clone()),
+        status: task.map(|task| task.status.to_string()),
+        relations: BTreeMap::new(),
+        conversations: 0,
+        first_seen: None,
+        last_seen: None,
+        completed_at: None,
+        timeline: Vec::new(),
+        reflections: Vec::new(),
+    };
+    let mut artifacts = BTreeMap::<String, Option<StoredArtifact>>::new();
+    let mut conversations = BTreeSet::new();
+    for link in links {
+        let relation = relation_label(link.relation).to_string();
+        *brief.relations.entry(relation.clone()).or_default() += 1;
+        // Links come back oldest first.
+        brief.first_seen.get_or_insert(link.start_ts);
+        brief.last_seen = Some(link.start_ts);
+        if link.relation == ArtifactTaskRelation::Completed {
+            brief.completed_at = brief.completed_at.max(Some(link.start_ts));
+        }
+
+        if !artifacts.contains_key(&link.artifact_id) {
+            let artifact = store.artifact_by_id(&link.artifact_id)?;
+            artifacts.insert(link.artifact_id.clone(), artifact);
+        }
+        let artifact = artifacts[&link.artifact_id].as_ref();
+        if let Some(artifact) = artifact {
+            conversations.insert(artifact.conversation_id.clone());
+        }
+        let entry = TaskBriefEntry {
+            ts: link.start_ts,
+            relation,
+            artifact_id: link.artifact_id.clone(),
+            kind: artifact.map(|artifact| artifact.kind.clone()),
+            conversation_id: artifact.map(|artifact| artifact.conversation_id.clone()),
+            text: artifact.map(|artifact| artifact.text.clone()),
+        };
+        let is_reflection = entry.kind.as_deref() == Some("t2")
+            && !brief
+                .reflections
+                .iter()
+                .any(|known| known.artifact_id == entry.artifact_id);
+        if is_reflection {
+            brief.reflections.push(entry.clone());
+        }
+        brief.timeline.push(entry);
+    }
+    brief.conversations = conversations.len();
+    Ok(brief)
+}
+
+fn relation_label(relation: ArtifactTaskRelation) -> &'static str {
+    match relation {
+        ArtifactTaskRelation::Active => "active",
+        ArtifactTaskRelation::WorkedOn => "worked_on",
+        ArtifactTaskRelation::Mentioned => "mentioned",
+        ArtifactTaskRelation::Completed => "completed",
+    }
+}
+
+fn relation_summary(relations: &BTreeMap<String, usize>) -> String {
+    if relations.is_empty() {
+        return "-".to_string();
+    }
+    relations
+        .iter()
+        .map(|(relation, count)| format!("{relation}:{count}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn task_heading(brief: &TaskBrief) -> String {
+    match &brief.title {
+        Some(title) => format!("#{} {title}", brief.task_id),
+        None => format!("#{}", brief.task_id),
+    }
+}
+
+fn render_brief_markdown(brief: &TaskBrief) -> String {
+    let mut out = format!("### {}\n\n", task_heading(brief));
+    if let Some(status) = &brief.status {
+        out.push_str(&format!("- Status: {status}\n"));
+    }
+    out.push_str(&format!(
+        "- Memory: {} across {} conversation(s)\n",
+        relation_summary(&brief.relations),
+        brief.conversations
+    ));
+    if let (Some(first), Some(last)) = (brief.first_seen, brief.last_seen) {
+        out.push_str(&format!(
+            "- Worked: {} to {}\n",
+            first.format("%Y-%m-%d"),
+            last.format("%Y-%m-%d")
+        ));
+    }
+    if !brief.reflections.is_empty() {
+        out.push_str("\n**Reflections**\n\n");
+        for entry in &brief.reflections {
+            out.push_str(&format!(
+                "- {}\n",
+                entry.text.as_deref().unwrap_or_default().trim()
+            ));
+        }
+    }
+    let observations = brief
+        .timeline
+        .iter()
+        .filter(|entry| entry.kind.as_deref() == Some("t1"))
+        .collect::<Vec<_>>();
+    if !observations.is_empty() {
+        out.push_str("\n**Timeline**\n\n");
+        for entry in observations {
+            out.push_str(&format!(
+                "- {} ({}) {}\n",
+                entry.ts.format("%Y-%m-%d %H:%M"),
+                entry.relation,
+                entry
+                    .text
+                    .as_deref()
+                    .and_then(|text| text.lines().next())
+                    .unwrap_or_default()
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::mind_contracts::ArtifactTaskLink;
+    use chrono::TimeZone;
+
+    fn ts(min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, 10, min, 0)
+            .single()
+            .expect("valid timestamp")
+    }
+
+    fn link(artifact_id: &str, relation: ArtifactTaskRelation, min: u32) -> ArtifactTaskLink {
+        ArtifactTaskLink::new(
+            artifact_id.to_string(),
+            "107".to_string(),
+            relation,
+            9_000,
+            vec![],
+            "test".to_string(),
+            ts(min),
+            None,
+        )
+        .expect("link")
+    }
+
+    #[test]
+    fn brief_rolls_up_links_and_renders_markdown() {
+        let store = MindStore::open_in_memory().expect("store");
+        store
+            .insert_observation("obs-1", "conv-1", ts(1), "parser rewrite started", &[])
+            .expect("obs");
+        store
+            .insert_reflection("ref-1", "conv-2", ts(9), "parser rewrite landed", &[])
+            .expect("ref");
+        for link in [
+            link("obs-1", ArtifactTaskRelation::WorkedOn, 1),
+            link("ref-1", ArtifactTaskRelation::Mentioned, 9),
+            link("ref-1", ArtifactTaskRelation::Completed, 9),
+        ] {
+            store.upsert_artifact_task_link(&link).expect("link");
+        }
+
+        let brief = build_task_brief(&store, &normalize_task_id("#107"), None).expect("brief");
+        assert_eq!(
+            relation_summary(&brief.relations),
+            "completed:1 mentioned:1 worked_on:1"
+        );
+        assert_eq!(brief.conversations, 2);
+        assert_eq!(brief.first_seen, Some(ts(1)));
+        assert_eq!(brief.completed_at, Some(ts(9)));
+        assert_eq!(brief.timeline.len(), 3);
+        assert_eq!(brief.reflections.len(), 1);
+
+        let markdown = render_brief_markdown(&brief);
+        assert!(markdown.starts_with("### #107\n"));
+        assert!(markdown.contains("- parser rewrite landed\n"));
+        assert!(markdown.contains("(worked_on) parser rewrite started"));
+    }
+}
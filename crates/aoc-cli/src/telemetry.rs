@@ -0,0 +1,123 @@
+//! OTLP trace export, built with `--features otel`.
+//!
+//! Export turns on when `OTEL_EXPORTER_OTLP_ENDPOINT` (or the traces-only
+//! `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`) is set, so the same binary runs
+//! quietly without a collector. Spans go over OTLP/HTTP with the standard
+//! `OTEL_*` variables for headers, timeouts, and `OTEL_SERVICE_NAME`.
+//!
+//! The exported spans are the pipeline stage spans (`stage` = ingest,
+//! distill, attribute, route, t2, t3) with their `conversation_id`,
+//! `artifact_id`, and `job_id` fields, independent of `--log-level`.
+//! `AOC_OTEL_FILTER` takes a `tracing` filter directive to widen or narrow
+//! them.
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::SpanExporter;
+use opentelemetry_sdk::{trace::SdkTracerProvider, Resource};
+use tracing_subscriber::{registry::LookupSpan, EnvFilter, Layer};
+
+pub const OTEL_FILTER_ENV: &str = "AOC_OTEL_FILTER";
+const ENDPOINT_ENVS: [&str; 2] = [
+    "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+    "OTEL_EXPORTER_OTLP_ENDPOINT",
+];
+/// Pipeline crates at `debug`, where their stage spans live; everything
+/// else, including the exporter's own HTTP client, stays at `warn`.
+const DEFAULT_FILTER: &str = "warn,aoc_cli=debug,aoc_mind=debug,aoc_pi_adapter=debug,\
+aoc_segment_routing=debug,aoc_task_attribution=debug,aoc_storage=debug";
+
+/// Flushes buffered spans when dropped.
+pub struct TelemetryGuard {
+    provider: SdkTracerProvider,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Err(err) = self.provider.shutdown() {
+            eprintln!("aoc: flushing OTLP spans failed: {err}");
+        }
+    }
+}
+
+fn endpoint_configured(env: &dyn Fn(&str) -> Option<String>) -> bool {
+    ENDPOINT_ENVS
+        .iter()
+        .any(|name| env(name).is_some_and(|value| !value.trim().is_empty()))
+}
+
+/// The OTLP layer, or `None` when no endpoint is configured or the exporter
+/// cannot be built (reported on stderr, since logging is not up yet).
+pub fn otel_layer<S>() -> Option<(impl Layer<S>, TelemetryGuard)>
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+{
+    otel_layer_from(&|name| std::env::var(name).ok())
+}
+
+/// [`otel_layer`] reading the endpoint, service name, and filter variables
+/// through `env`; the exporter still takes its other `OTEL_*` settings from
+/// the process environment.
+fn otel_layer_from<S>(
+    env: &dyn Fn(&str) -> Option<String>,
+) -> Option<(impl Layer<S>, TelemetryGuard)>
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+{
+    if !endpoint_configured(env) {
+        return None;
+    }
+    let exporter = match SpanExporter::builder().with_http().build() {
+        Ok(exporter) => exporter,
+        Err(err) => {
+            eprintln!("aoc: OTLP export disabled: {err}");
+            return None;
+        }
+    };
+    let mut resource = Resource::builder().with_attribute(opentelemetry::KeyValue::new(
+        "service.version",
+        env!("CARGO_PKG_VERSION"),
+    ));
+    if env("OTEL_SERVICE_NAME").is_none() {
+        resource = resource.with_service_name("aoc");
+    }
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource.build())
+        .build();
+    let filter = env(OTEL_FILTER_ENV)
+        .and_then(|directives| EnvFilter::try_new(directives).ok())
+        .unwrap_or_else(|| EnvFilter::new(DEFAULT_FILTER));
+    let layer = tracing_opentelemetry::layer()
+        .with_tracer(provider.tracer("aoc"))
+        .with_filter(filter);
+    Some((layer, TelemetryGuard { provider }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::Registry;
+
+    #[test]
+    fn layer_is_built_only_when_an_endpoint_is_set() {
+        assert!(
+            otel_layer_from::<Registry>(&|_| None).is_none(),
+            "no endpoint, no export"
+        );
+        let blank = |name: &str| (name == "OTEL_EXPORTER_OTLP_ENDPOINT").then(|| "  ".to_string());
+        assert!(
+            otel_layer_from::<Registry>(&blank).is_none(),
+            "blank is unset"
+        );
+
+        for endpoint in ENDPOINT_ENVS {
+            let env = |name: &str| match name {
+                OTEL_FILTER_ENV => Some("aoc_mind=trace".to_string()),
+                name if name == endpoint => Some("http://127.0.0.1:4318".to_string()),
+                _ => None,
+            };
+            let (_layer, guard) = otel_layer_from::<Registry>(&env).expect("layer from endpoint");
+            drop(guard);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use anyhow::{Context, Result};
+use aoc_storage::{
+    writer_lock_path, MindWriter, MindWriterGuard, WriterClaim, DEFAULT_WRITER_LEASE_TTL_MS,
+};
+use chrono::Utc;
+use clap::Args;
+use serde_json::{json, Value};
+use std::path::Path;
+
+use crate::{
+    mind_store::StoreArgs,
+    output::{json_mode, print_change, print_json},
+};
+
+#[derive(Args, Debug)]
+pub struct WriterArgs {
+    #[command(flatten)]
+    pub store: StoreArgs,
+    /// Clear the lease of a writer that crashed without releasing it. Refuses
+    /// while a live process still holds the lock file.
+    #[arg(long, default_value_t = false)]
+    pub takeover: bool,
+}
+
+pub fn handle_writer_command(args: WriterArgs) -> Result<()> {
+    let (store, store_path) = args.store.open()?;
+    if args.takeover {
+        drop(store);
+        let previous = take_over(&store_path)?;
+        let message = match &previous {
+            Some(writer) => format!("Took over the writer lease from {}", writer_label(writer)),
+            None => "No writer lease to take over".to_string(),
+        };
+        return print_change(
+            "writer.takeover",
+            message,
+            json!({
+                "store_path": store_path,
+                "previous": previous.as_ref().map(writer_json),
+            }),
+        );
+    }
+
+    let writer = store.mind_writer().context("read writer lease")?;
+    let lock_path = writer_lock_path(&store_path);
+    if json_mode() {
+        return print_json(&json!({
+            "store_path": store_path,
+            "lock_path": lock_path,
+            "writer": writer.as_ref().map(writer_json),
+        }));
+    }
+    println!("store: {}", store_path.display());
+    println!("lock: {}", lock_path.display());
+    match writer {
+        Some(writer) => {
+            let state = if writer.expires_at <= Utc::now() {
+                "EXPIRED"
+            } else {
+                "live"
+            };
+            println!(
+                "writer: {} since {} heartbeat {} {state}",
+                writer_label(&writer),
+                writer.acquired_at.to_rfc3339(),
+                writer.heartbeat_at.to_rfc3339()
+            );
+        }
+        None => println!("writer: <none>"),
+    }
+    Ok(())
+}
+
+/// Acquires the lease over whatever row a crashed writer left, then releases
+/// it again on drop so the next writer starts clean.
+fn take_over(store_path: &Path) -> Result<Option<MindWriter>> {
+    let (guard, previous) = MindWriterGuard::take_over(
+        store_path,
+        WriterClaim::for_current_process("cli"),
+        DEFAULT_WRITER_LEASE_TTL_MS,
+    )
+    .with_context(|| format!("take over writer lease on {}", store_path.display()))?;
+    drop(guard);
+    Ok(previous)
+}
+
+fn writer_label(writer: &MindWriter) -> String {
+    match writer.owner_pid {
+        Some(pid) => format!("{} pid {pid} ({})", writer.label, writer.owner_id),
+        None => format!("{} ({})", writer.label, writer.owner_id),
+    }
+}
+
+fn writer_json(writer: &MindWriter) -> Value {
+    json!({
+        "owner_id": writer.owner_id,
+        "label": writer.label,
+        "owner_pid": writer.owner_pid,
+        "acquired_at": writer.acquired_at,
+        "heartbeat_at": writer.heartbeat_at,
+        "expires_at": writer.expires_at,
+        "expired": writer.expires_at <= Utc::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_storage::MindStore;
+
+    #[test]
+    fn takeover_clears_a_crashed_writer() {
+        let dir = std::env::temp_dir().join(format!("aoc-cli-writer-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("create dir");
+        let store_path = dir.join("mind.sqlite");
+        let crashed = WriterClaim {
+            owner_id: "daemon:1:dead".to_string(),
+            label: "daemon".to_string(),
+            owner_pid: Some(1),
+        };
+        MindStore::open(&store_path)
+            .and_then(|store| store.try_acquire_writer_lease(&crashed, Utc::now(), 60_000))
+            .expect("leave stale row");
+
+        let previous = take_over(&store_path).expect("take over");
+        assert_eq!(previous.expect("previous").owner_id, "daemon:1:dead");
+        assert!(MindStore::open(&store_path)
+            .and_then(|store| store.mind_writer())
+            .expect("writer")
+            .is_none());
+        assert!(take_over(&store_path).expect("idempotent").is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
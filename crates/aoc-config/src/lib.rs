@@ -0,0 +1,1138 @@
+//! Shared `aoc.toml` loading for every AOC crate.
+//!
+//! Layers, later wins: built-in defaults, the user file
+//! (`$XDG_CONFIG_HOME/aoc/aoc.toml`), the project file (`.aoc/aoc.toml`, or
+//! `$AOC_CONFIG` when set), then `AOC_<SECTION>__<KEY>` environment
+//! variables. Nested keys use a double underscore per level, e.g.
+//! `AOC_OBSERVER__GUARDRAILS__TIMEOUT_MS=4000`. Every error names the dotted
+//! key and the layer it came from.
+//!
+//! A named profile (`AOC_PROFILE`, or `--profile` on the CLI) is applied
+//! between the files and the environment. Profiles bundle observer model,
+//! guardrail, budget, and distillation settings under `[profiles.<name>]`;
+//! `offline`, `cheap`, and `quality` are built in and can be overridden key by
+//! key.
+
+use aoc_core::mind_contracts::{ConversationRole, SnippetRedaction, T0CompactionPolicy};
+use aoc_core::{ErrorCode, ErrorCoded};
+use aoc_mind::{
+    ArchivalPolicy, DistillationConfig, EventSinkConfig, SemanticObserverConfig, WebhookEndpoint,
+};
+use aoc_segment_routing::{RoutingError, SegmentRoutingConfig};
+use aoc_task_attribution::{compile_task_id_patterns, AttributionConfig, DEFAULT_TASK_ID_PATTERNS};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, fs,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+use toml::{Table, Value};
+
+pub const PROJECT_CONFIG_PATH: &str = ".aoc/aoc.toml";
+pub const CONFIG_PATH_ENV: &str = "AOC_CONFIG";
+pub const PROFILE_ENV: &str = "AOC_PROFILE";
+/// Sections a profile may set; everything else is per-project.
+pub const PROFILE_SECTIONS: [&str; 2] = ["observer", "distillation"];
+const ENV_PREFIX: &str = "AOC_";
+const ENV_SEPARATOR: &str = "__";
+const MAX_BPS: u16 = 10_000;
+
+const BUILTIN_PROFILES: &str = r#"
+[offline.observer]
+mode = "deterministic_only"
+
+[cheap.observer.profile]
+max_input_tokens = 8000
+max_output_tokens = 384
+
+[cheap.observer.guardrails]
+max_retries = 0
+max_budget_tokens = 2048
+
+[quality.observer.profile]
+max_input_tokens = 64000
+max_output_tokens = 1536
+
+[quality.observer.guardrails]
+timeout_ms = 20000
+max_retries = 2
+max_budget_tokens = 16384
+"#;
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("{path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("{origin}: {message}")]
+    Syntax { origin: String, message: String },
+    #[error("{origin}: `{key}`: {message}")]
+    Key {
+        origin: String,
+        key: String,
+        message: String,
+    },
+    #[error("`{key}`: {message}")]
+    Invalid { key: String, message: String },
+}
+
+impl ErrorCoded for ConfigError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Read { .. } | Self::Syntax { .. } | Self::Key { .. } | Self::Invalid { .. } => {
+                ErrorCode::Config
+            }
+        }
+    }
+}
+
+impl ConfigError {
+    fn invalid(key: &str, message: impl Into<String>) -> Self {
+        Self::Invalid {
+            key: key.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdapterKind {
+    #[default]
+    Pi,
+    Opencode,
+    None,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MindSettings {
+    /// Relative paths resolve against the project root.
+    pub store_path: Option<PathBuf>,
+    /// Layer the user-level global Mind under the project Mind.
+    #[serde(default)]
+    pub global: bool,
+    /// Global store location; setting it implies `global`. Defaults to the
+    /// shared store under the AOC state directory.
+    pub global_store_path: Option<PathBuf>,
+}
+
+impl MindSettings {
+    /// The global store to layer under the project store, if enabled.
+    pub fn global_store(&self) -> Option<PathBuf> {
+        self.global_store_path
+            .clone()
+            .or_else(|| self.global.then(aoc_mind::global_mind_store_path))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AdapterSettings {
+    pub kind: AdapterKind,
+    pub sessions_dir: Option<PathBuf>,
+}
+
+/// [`AttributionConfig`] in whole minutes, which is how it is written by hand.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AttributionSettings {
+    pub mention_window_before_minutes: i64,
+    pub mention_window_after_minutes: i64,
+    /// Replaces the default mention patterns; the first capture group (or
+    /// the whole match) is the task id, e.g. `"\\b[A-Z]{2,}-[0-9]+\\b"`.
+    pub task_id_patterns: Vec<String>,
+}
+
+impl Default for AttributionSettings {
+    fn default() -> Self {
+        let defaults = AttributionConfig::default();
+        Self {
+            mention_window_before_minutes: defaults.mention_window_before.num_minutes(),
+            mention_window_after_minutes: defaults.mention_window_after.num_minutes(),
+            task_id_patterns: DEFAULT_TASK_ID_PATTERNS
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl AttributionSettings {
+    pub fn to_attribution_config(&self) -> Result<AttributionConfig, ConfigError> {
+        Ok(AttributionConfig {
+            mention_window_before: chrono::Duration::minutes(self.mention_window_before_minutes),
+            mention_window_after: chrono::Duration::minutes(self.mention_window_after_minutes),
+            task_id_patterns: compile_task_id_patterns(&self.task_id_patterns).map_err(|err| {
+                ConfigError::invalid("attribution.task_id_patterns", err.to_string())
+            })?,
+        })
+    }
+}
+
+/// [`T0CompactionPolicy`] as written by hand. There is no version key: the
+/// policy version is derived from these rules, so editing any of them moves
+/// new compact ids onto a new version and replays stay comparable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompactionSettings {
+    pub keep_roles: BTreeSet<ConversationRole>,
+    /// Tool name to snippet budget in chars.
+    pub tool_snippets: BTreeMap<String, usize>,
+    pub drop_tools: BTreeSet<String>,
+    pub redaction_marker: String,
+    /// `[[compaction.redact]]` regex rewrites applied to tool snippets.
+    pub redact: Vec<SnippetRedaction>,
+    pub role_max_chars: BTreeMap<ConversationRole, usize>,
+}
+
+impl Default for CompactionSettings {
+    fn default() -> Self {
+        let defaults = T0CompactionPolicy::default();
+        Self {
+            keep_roles: defaults.keep_roles,
+            tool_snippets: defaults.tool_snippet_allowlist,
+            drop_tools: defaults.drop_tools,
+            redaction_marker: defaults.redaction_marker,
+            redact: defaults.snippet_redactions,
+            role_max_chars: defaults.role_max_chars,
+        }
+    }
+}
+
+impl CompactionSettings {
+    pub fn to_compaction_policy(&self) -> Result<T0CompactionPolicy, ConfigError> {
+        T0CompactionPolicy {
+            keep_roles: self.keep_roles.clone(),
+            tool_snippet_allowlist: self.tool_snippets.clone(),
+            drop_tools: self.drop_tools.clone(),
+            redaction_marker: self.redaction_marker.clone(),
+            snippet_redactions: self.redact.clone(),
+            role_max_chars: self.role_max_chars.clone(),
+            ..T0CompactionPolicy::default()
+        }
+        .with_content_version()
+        .map_err(|err| ConfigError::invalid("compaction", err.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AocConfig {
+    pub mind: MindSettings,
+    pub adapter: AdapterSettings,
+    pub distillation: DistillationConfig,
+    pub observer: SemanticObserverConfig,
+    pub routing: SegmentRoutingConfig,
+    pub attribution: AttributionSettings,
+    pub compaction: CompactionSettings,
+    pub retention: ArchivalPolicy,
+    /// `[[webhooks]]` endpoints notified of pipeline events by `aoc live`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<WebhookEndpoint>,
+    /// `[[event_sinks]]` (NATS, Redis Streams) that `aoc live` publishes
+    /// the same pipeline events to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub event_sinks: Vec<EventSinkConfig>,
+    /// User-defined profiles; built-ins are merged in at selection time.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Table>,
+    /// Layers that contributed, lowest precedence first.
+    #[serde(skip)]
+    pub sources: Vec<String>,
+    #[serde(skip)]
+    pub active_profile: Option<String>,
+}
+
+/// One layer of raw settings before it is merged over the defaults.
+#[derive(Debug, Clone)]
+pub struct ConfigLayer {
+    pub origin: String,
+    pub table: Table,
+}
+
+impl ConfigLayer {
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let text = fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_toml(path.display().to_string(), &text)
+    }
+
+    pub fn from_toml(origin: impl Into<String>, text: &str) -> Result<Self, ConfigError> {
+        let origin = origin.into();
+        let table = text.parse::<Table>().map_err(|err| ConfigError::Syntax {
+            origin: origin.clone(),
+            message: err.to_string(),
+        })?;
+        Ok(Self { origin, table })
+    }
+
+    /// Collects `AOC_<SECTION>__<KEY>` variables. Values are read as TOML
+    /// scalars or arrays, falling back to a plain string. Other `AOC_*`
+    /// variables (store paths, session dirs, ...) are left alone.
+    pub fn from_env(vars: impl IntoIterator<Item = (String, String)>) -> Option<Self> {
+        let mut table = Table::new();
+        let mut names = Vec::new();
+        for (name, raw) in vars {
+            let Some(rest) = name.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            if !rest.contains(ENV_SEPARATOR) {
+                continue;
+            }
+            let path = rest
+                .split(ENV_SEPARATOR)
+                .map(str::to_ascii_lowercase)
+                .collect::<Vec<_>>();
+            if path.iter().any(String::is_empty) {
+                continue;
+            }
+            insert_path(&mut table, &path, parse_env_value(&raw));
+            names.push(name);
+        }
+        if names.is_empty() {
+            return None;
+        }
+        names.sort();
+        Some(Self {
+            origin: format!("env ({})", names.join(", ")),
+            table,
+        })
+    }
+}
+
+fn parse_env_value(raw: &str) -> Value {
+    format!("value = {raw}")
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+fn insert_path(table: &mut Table, path: &[String], value: Value) {
+    let (last, parents) = path.split_last().expect("non-empty env key path");
+    let mut current = table;
+    for key in parents {
+        let entry = current
+            .entry(key.clone())
+            .or_insert_with(|| Value::Table(Table::new()));
+        if !entry.is_table() {
+            *entry = Value::Table(Table::new());
+        }
+        current = entry.as_table_mut().expect("table entry");
+    }
+    current.insert(last.clone(), value);
+}
+
+/// Tables merge key by key; anything else in `overlay` replaces `base`.
+fn merge_tables(base: &mut Table, overlay: &Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(key), value) {
+            (Some(Value::Table(base_table)), Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table)
+            }
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+fn defaults_table() -> Table {
+    match Value::try_from(AocConfig::default()) {
+        Ok(Value::Table(table)) => table,
+        _ => unreachable!("default config serializes to a table"),
+    }
+}
+
+/// Checks `layer` on its own before merging it, so a bad key is blamed on
+/// the file or variable that set it rather than on the merged result.
+fn merge_layer(merged: &mut Table, layer: &ConfigLayer) -> Result<(), ConfigError> {
+    let mut alone = defaults_table();
+    merge_tables(&mut alone, &layer.table);
+    deserialize_table(alone, &layer.origin)?;
+    merge_tables(merged, &layer.table);
+    Ok(())
+}
+
+fn deserialize_table(table: Table, origin: &str) -> Result<AocConfig, ConfigError> {
+    serde_path_to_error::deserialize(Value::Table(table)).map_err(|err| ConfigError::Key {
+        origin: origin.to_string(),
+        key: err.path().to_string(),
+        message: err.into_inner().to_string(),
+    })
+}
+
+fn builtin_profiles() -> Table {
+    BUILTIN_PROFILES
+        .parse::<Table>()
+        .expect("built-in profiles parse")
+}
+
+/// Built-in profiles with any `[profiles.<name>]` tables from `merged` laid
+/// over them key by key.
+fn resolve_profiles(merged: &Table) -> Result<Table, ConfigError> {
+    let mut profiles = builtin_profiles();
+    if let Some(Value::Table(custom)) = merged.get("profiles") {
+        merge_tables(&mut profiles, custom);
+    }
+    for (name, profile) in &profiles {
+        let origin = format!("profile '{name}'");
+        let Value::Table(profile) = profile else {
+            return Err(ConfigError::Key {
+                origin,
+                key: format!("profiles.{name}"),
+                message: "must be a table".to_string(),
+            });
+        };
+        if let Some(section) = profile
+            .keys()
+            .find(|section| !PROFILE_SECTIONS.contains(&section.as_str()))
+        {
+            return Err(ConfigError::Key {
+                origin,
+                key: format!("profiles.{name}.{section}"),
+                message: format!("profiles may only set {}", PROFILE_SECTIONS.join(", ")),
+            });
+        }
+        let mut alone = defaults_table();
+        merge_tables(&mut alone, profile);
+        deserialize_table(alone, &origin)?;
+    }
+    Ok(profiles)
+}
+
+pub fn project_config_path(project_root: &Path) -> PathBuf {
+    env::var_os(CONFIG_PATH_ENV)
+        .map(PathBuf::from)
+        .filter(|path| !path.as_os_str().is_empty())
+        .unwrap_or_else(|| project_root.join(PROJECT_CONFIG_PATH))
+}
+
+pub fn user_config_path() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join("aoc").join("aoc.toml"))
+}
+
+impl AocConfig {
+    /// Discovers the user and project files for `project_root`, applies the
+    /// `AOC_PROFILE` profile, overlays the process environment, and validates
+    /// the result.
+    pub fn load(project_root: &Path) -> Result<Self, ConfigError> {
+        let mut files = Vec::new();
+        let project = project_config_path(project_root);
+        for path in user_config_path().into_iter().chain([project]) {
+            if path.is_file() {
+                files.push(ConfigLayer::from_file(&path)?);
+            }
+        }
+        let profile = env::var(PROFILE_ENV)
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        let mut config = Self::from_layers(
+            files,
+            profile.as_deref(),
+            ConfigLayer::from_env(env::vars()),
+        )?;
+        for store_path in [
+            config.mind.store_path.as_mut(),
+            config.mind.global_store_path.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if store_path.is_relative() {
+                *store_path = project_root.join(&*store_path);
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn from_layers(
+        files: Vec<ConfigLayer>,
+        profile: Option<&str>,
+        env: Option<ConfigLayer>,
+    ) -> Result<Self, ConfigError> {
+        let mut merged = defaults_table();
+        let mut sources = Vec::new();
+        for layer in &files {
+            merge_layer(&mut merged, layer)?;
+            sources.push(layer.origin.clone());
+        }
+        let profiles = resolve_profiles(&merged)?;
+        if let Some(name) = profile {
+            let Some(Value::Table(selected)) = profiles.get(name) else {
+                return Err(ConfigError::invalid(
+                    "profile",
+                    format!(
+                        "unknown profile '{name}' (available: {})",
+                        profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+                    ),
+                ));
+            };
+            merge_tables(&mut merged, selected);
+            sources.push(format!("profile '{name}'"));
+        }
+        if let Some(layer) = &env {
+            merge_layer(&mut merged, layer)?;
+            sources.push(layer.origin.clone());
+        }
+        let mut config = deserialize_table(merged, "merged config")?;
+        config.validate()?;
+        config.sources = sources;
+        config.active_profile = profile.map(str::to_string);
+        Ok(config)
+    }
+
+    /// Built-in and user-defined profile names.
+    pub fn profile_names(&self) -> Vec<String> {
+        let mut names = builtin_profiles().keys().cloned().collect::<Vec<_>>();
+        names.extend(self.profiles.keys().cloned());
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    pub fn attribution_config(&self) -> Result<AttributionConfig, ConfigError> {
+        self.attribution.to_attribution_config()
+    }
+
+    pub fn compaction_policy(&self) -> Result<T0CompactionPolicy, ConfigError> {
+        self.compaction.to_compaction_policy()
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let distillation = &self.distillation;
+        if distillation.t1_target_tokens == 0 {
+            return Err(ConfigError::invalid(
+                "distillation.t1_target_tokens",
+                "must be greater than 0",
+            ));
+        }
+        if distillation.t1_hard_cap_tokens < distillation.t1_target_tokens {
+            return Err(ConfigError::invalid(
+                "distillation.t1_hard_cap_tokens",
+                format!(
+                    "must be at least distillation.t1_target_tokens ({})",
+                    distillation.t1_target_tokens
+                ),
+            ));
+        }
+        let profile = &self.observer.profile;
+        if profile.max_input_tokens == 0 || profile.max_output_tokens == 0 {
+            return Err(ConfigError::invalid(
+                "observer.profile",
+                "max_input_tokens and max_output_tokens must be greater than 0",
+            ));
+        }
+        if profile.model_id.trim().is_empty() {
+            return Err(ConfigError::invalid(
+                "observer.profile.model_id",
+                "must not be empty",
+            ));
+        }
+        if self.observer.workers == 0 {
+            return Err(ConfigError::invalid(
+                "observer.workers",
+                "must be greater than 0",
+            ));
+        }
+        if let Some(gateway) = &self.observer.gateway {
+            gateway
+                .validate()
+                .map_err(|message| ConfigError::invalid("observer.gateway", message))?;
+        }
+        self.observer
+            .redaction
+            .validate()
+            .map_err(|message| ConfigError::invalid("observer.redaction", message))?;
+        self.observer
+            .cache
+            .validate()
+            .map_err(|message| ConfigError::invalid("observer.cache", message))?;
+        self.observer
+            .budget
+            .validate()
+            .map_err(|message| ConfigError::invalid("observer.budget", message))?;
+        self.observer
+            .breaker
+            .validate()
+            .map_err(|message| ConfigError::invalid("observer.breaker", message))?;
+
+        self.routing.validate().map_err(|err| match err {
+            RoutingError::InvalidConfig { key, message } => {
+                ConfigError::invalid(&format!("routing.{key}"), message)
+            }
+            other => ConfigError::invalid("routing", other.to_string()),
+        })?;
+        if self.retention.retention_floor_bps > MAX_BPS {
+            return Err(ConfigError::invalid(
+                "retention.retention_floor_bps",
+                format!("must be <= {MAX_BPS}"),
+            ));
+        }
+
+        if self.attribution.mention_window_before_minutes < 0
+            || self.attribution.mention_window_after_minutes < 0
+        {
+            return Err(ConfigError::invalid(
+                "attribution",
+                "mention windows must not be negative",
+            ));
+        }
+        for (index, pattern) in self.attribution.task_id_patterns.iter().enumerate() {
+            compile_task_id_patterns(&[pattern]).map_err(|err| {
+                ConfigError::invalid(
+                    &format!("attribution.task_id_patterns[{index}]"),
+                    err.to_string(),
+                )
+            })?;
+        }
+        if self.compaction.keep_roles.is_empty() {
+            return Err(ConfigError::invalid(
+                "compaction.keep_roles",
+                "must keep at least one role",
+            ));
+        }
+        for (index, rule) in self.compaction.redact.iter().enumerate() {
+            rule.validate().map_err(|err| {
+                ConfigError::invalid(
+                    &format!("compaction.redact[{index}].pattern"),
+                    err.to_string(),
+                )
+            })?;
+        }
+        if self.retention.half_life_hours == 0 {
+            return Err(ConfigError::invalid(
+                "retention.half_life_hours",
+                "must be greater than 0",
+            ));
+        }
+        for (index, webhook) in self.webhooks.iter().enumerate() {
+            if !(webhook.url.starts_with("https://") || webhook.url.starts_with("http://")) {
+                return Err(ConfigError::invalid(
+                    &format!("webhooks[{index}].url"),
+                    "must be an http:// or https:// URL",
+                ));
+            }
+            if webhook.max_attempts == 0 {
+                return Err(ConfigError::invalid(
+                    &format!("webhooks[{index}].max_attempts"),
+                    "must be greater than 0",
+                ));
+            }
+        }
+        for (index, sink) in self.event_sinks.iter().enumerate() {
+            sink.validate().map_err(|message| {
+                ConfigError::invalid(&format!("event_sinks[{index}]"), message)
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::mind_contracts::SemanticRuntimeMode;
+    use aoc_mind::{PipelineEventKind, ReflectionScope, WebhookFormat};
+
+    fn layer(origin: &str, text: &str) -> ConfigLayer {
+        ConfigLayer::from_toml(origin, text).expect("parse layer")
+    }
+
+    fn env(pairs: &[(&str, &str)]) -> Option<ConfigLayer> {
+        ConfigLayer::from_env(
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string())),
+        )
+    }
+
+    #[test]
+    fn layers_merge_over_defaults_with_env_last() {
+        let user = layer(
+            "user",
+            r#"
+[distillation]
+t1_target_tokens = 1000
+t1_hard_cap_tokens = 4000
+
+[observer]
+mode = "deterministic_only"
+"#,
+        );
+        let project = layer(
+            "project",
+            r#"
+[adapter]
+kind = "opencode"
+
+[distillation]
+t1_target_tokens = 2000
+t2_scope = "session_tree"
+
+[routing.segment_keywords]
+infra = ["terraform"]
+"#,
+        );
+        let overrides = env(&[
+            ("AOC_DISTILLATION__T1_HARD_CAP_TOKENS", "5000"),
+            ("AOC_OBSERVER__GUARDRAILS__TIMEOUT_MS", "1500"),
+            ("AOC_PROJECT_ROOT", "/ignored"),
+        ])
+        .expect("env layer");
+
+        let config = AocConfig::from_layers(vec![user, project], None, Some(overrides))
+            .expect("load config");
+        assert_eq!(config.adapter.kind, AdapterKind::Opencode);
+        assert_eq!(config.distillation.t1_target_tokens, 2000);
+        assert_eq!(config.distillation.t1_hard_cap_tokens, 5000);
+        assert_eq!(config.distillation.t2_scope, ReflectionScope::SessionTree);
+        assert_eq!(config.observer.mode, SemanticRuntimeMode::DeterministicOnly);
+        assert_eq!(config.observer.guardrails.timeout_ms, 1500);
+        assert_eq!(
+            config.observer.guardrails.max_retries,
+            SemanticObserverConfig::default().guardrails.max_retries
+        );
+        assert_eq!(config.routing.segment_keywords["infra"], vec!["terraform"]);
+        assert!(config.routing.segment_keywords.contains_key("mind"));
+        assert_eq!(config.retention, ArchivalPolicy::default());
+        assert_eq!(config.sources.len(), 3);
+        assert!(config.sources[2].contains("AOC_DISTILLATION__T1_HARD_CAP_TOKENS"));
+    }
+
+    #[test]
+    fn errors_name_the_key_and_the_layer_that_set_it() {
+        let err = AocConfig::from_layers(
+            vec![
+                layer("user", "[distillation]\nt1_target_tokens = 10\n"),
+                layer("project", "[distillation]\nt1_target_tokns = 10\n"),
+            ],
+            None,
+            None,
+        )
+        .expect_err("unknown key");
+        let message = err.to_string();
+        assert!(
+            message.starts_with("project: `distillation.t1_target_tokns`"),
+            "{message}"
+        );
+
+        let err = AocConfig::from_layers(
+            Vec::new(),
+            None,
+            env(&[("AOC_RETENTION__HALF_LIFE_HOURS", "soon")]),
+        )
+        .expect_err("bad type");
+        assert!(
+            matches!(&err, ConfigError::Key { origin, key, .. }
+                if origin.contains("AOC_RETENTION__HALF_LIFE_HOURS") && key == "retention.half_life_hours"),
+            "{err}"
+        );
+
+        let err = AocConfig::from_layers(
+            vec![layer(
+                "project",
+                "[routing]\ndefault_uncertain_segment = \"global\"\n",
+            )],
+            None,
+            None,
+        )
+        .expect_err("invalid routing");
+        assert!(
+            err.to_string()
+                .starts_with("`routing.default_uncertain_segment`"),
+            "{err}"
+        );
+
+        let webhooks = |url: &str| {
+            layer(
+                "project",
+                &format!(
+                    "[[webhooks]]\nurl = \"{url}\"\nevents = [\"job_dead_lettered\"]\nsecret_env = \"AOC_HOOK_SECRET\"\n"
+                ),
+            )
+        };
+        let config =
+            AocConfig::from_layers(vec![webhooks("https://hooks.example/aoc")], None, None)
+                .expect("webhook config");
+        assert_eq!(config.webhooks.len(), 1);
+        assert_eq!(config.webhooks[0].max_attempts, 3);
+        assert_eq!(config.webhooks[0].format, WebhookFormat::Json);
+        let chat = layer(
+            "project",
+            "[[webhooks]]\nurl = \"https://discord.com/api/webhooks/1/x\"\nformat = \"discord\"\nevents = [\"daily_digest\"]\n",
+        );
+        let config = AocConfig::from_layers(vec![chat], None, None).expect("chat webhook");
+        assert_eq!(config.webhooks[0].format, WebhookFormat::Discord);
+        assert!(config.webhooks[0].accepts(PipelineEventKind::DailyDigest));
+
+        let sinks = layer(
+            "project",
+            "[[event_sinks]]\nkind = \"nats\"\nurl = \"nats://bus.internal\"\n\n[[event_sinks]]\nkind = \"redis_stream\"\nurl = \"redis://cache:6380/2\"\nmax_len = 10000\n",
+        );
+        let config = AocConfig::from_layers(vec![sinks], None, None).expect("event sinks");
+        assert_eq!(config.event_sinks.len(), 2);
+        assert!(matches!(
+            &config.event_sinks[0],
+            EventSinkConfig::Nats { subject, .. } if subject == "aoc.mind"
+        ));
+        let leaked = layer(
+            "project",
+            "[[event_sinks]]\nkind = \"redis_stream\"\nurl = \"redis://:hunter2@cache\"\n",
+        );
+        let err = AocConfig::from_layers(vec![leaked], None, None).expect_err("inline password");
+        assert!(err.to_string().starts_with("`event_sinks[0]`"), "{err}");
+        let err = AocConfig::from_layers(vec![webhooks("hooks.example/aoc")], None, None)
+            .expect_err("webhook without scheme");
+        assert!(err.to_string().starts_with("`webhooks[0].url`"), "{err}");
+
+        assert!(matches!(
+            ConfigLayer::from_toml("project", "[mind"),
+            Err(ConfigError::Syntax { .. })
+        ));
+    }
+
+    #[test]
+    fn profiles_sit_between_files_and_env() {
+        let project = layer(
+            "project",
+            r#"
+[observer.guardrails]
+timeout_ms = 3000
+
+[profiles.cheap.observer.profile]
+model_id = "local-small"
+
+[profiles.local.observer]
+mode = "deterministic_only"
+"#,
+        );
+        let config = AocConfig::from_layers(
+            vec![project.clone()],
+            Some("cheap"),
+            env(&[("AOC_OBSERVER__GUARDRAILS__MAX_RETRIES", "3")]),
+        )
+        .expect("cheap profile");
+        assert_eq!(config.active_profile.as_deref(), Some("cheap"));
+        assert_eq!(config.observer.profile.model_id, "local-small");
+        assert_eq!(config.observer.profile.max_output_tokens, 384);
+        assert_eq!(config.observer.guardrails.max_budget_tokens, 2048);
+        assert_eq!(config.observer.guardrails.timeout_ms, 3000);
+        assert_eq!(config.observer.guardrails.max_retries, 3);
+        assert_eq!(config.sources[1], "profile 'cheap'");
+        assert_eq!(
+            config.profile_names(),
+            vec!["cheap", "local", "offline", "quality"]
+        );
+
+        let offline =
+            AocConfig::from_layers(Vec::new(), Some("offline"), None).expect("offline profile");
+        assert_eq!(
+            offline.observer.mode,
+            SemanticRuntimeMode::DeterministicOnly
+        );
+
+        let err =
+            AocConfig::from_layers(vec![project], Some("fast"), None).expect_err("unknown profile");
+        assert!(err.to_string().contains("available: cheap, local"), "{err}");
+
+        let err = AocConfig::from_layers(
+            vec![layer(
+                "project",
+                "[profiles.team.routing]\nmax_secondary_segments = 1\n",
+            )],
+            None,
+            None,
+        )
+        .expect_err("profile outside its sections");
+        assert!(
+            err.to_string()
+                .starts_with("profile 'team': `profiles.team.routing`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn load_reads_project_file_and_resolves_relative_store_path() {
+        let root = tempfile::tempdir().expect("tempdir");
+        fs::create_dir_all(root.path().join(".aoc")).expect("create .aoc");
+        fs::write(
+            root.path().join(PROJECT_CONFIG_PATH),
+            "[mind]\nstore_path = \"state/mind.sqlite\"\nglobal_store_path = \"state/global.sqlite\"\n\n[retention]\nmin_age_hours = 48\n",
+        )
+        .expect("write config");
+
+        let config = AocConfig::load(root.path()).expect("load");
+        assert_eq!(
+            config.mind.store_path,
+            Some(root.path().join("state/mind.sqlite"))
+        );
+        assert_eq!(
+            config.mind.global_store(),
+            Some(root.path().join("state/global.sqlite"))
+        );
+        assert_eq!(config.retention.min_age_hours, 48);
+        assert!(config
+            .sources
+            .iter()
+            .any(|source| source.ends_with("aoc.toml")));
+    }
+
+    #[test]
+    fn observer_gateway_is_set_per_profile_and_validated() {
+        let project = layer(
+            "project",
+            r#"
+[observer.gateway]
+base_url = "http://localhost:4000"
+costs_path = "/model/info"
+
+[observer.gateway.model_aliases]
+observer = "gpt-4o-mini"
+
+[profiles.quality.observer.profile]
+model_id = "sonnet"
+
+[profiles.quality.observer.gateway]
+base_url = "https://openrouter.ai/api/v1"
+api_key_env = "OPENROUTER_API_KEY"
+
+[profiles.quality.observer.gateway.headers]
+X-Title = "aoc"
+
+[profiles.quality.observer.gateway.model_aliases]
+sonnet = "anthropic/claude-sonnet-4"
+
+[profiles.quality.observer.gateway.costs.sonnet]
+input_usd_per_mtok = 3.0
+output_usd_per_mtok = 15.0
+"#,
+        );
+        let config =
+            AocConfig::from_layers(vec![project.clone()], None, None).expect("project gateway");
+        let gateway = config.observer.gateway.expect("gateway");
+        assert_eq!(gateway.base_url, "http://localhost:4000");
+        assert_eq!(gateway.costs_path, "/model/info");
+        assert!(gateway.fetch_costs);
+        assert_eq!(gateway.resolve_model("observer"), "gpt-4o-mini");
+
+        let quality =
+            AocConfig::from_layers(vec![project], Some("quality"), None).expect("quality gateway");
+        let gateway = quality.observer.gateway.expect("gateway");
+        assert_eq!(gateway.base_url, "https://openrouter.ai/api/v1");
+        assert_eq!(gateway.headers["X-Title"], "aoc");
+        assert_eq!(
+            gateway.resolve_model(&quality.observer.profile.model_id),
+            "anthropic/claude-sonnet-4"
+        );
+        assert_eq!(gateway.costs["sonnet"].cost_micros(1_000, 100), 4_500);
+
+        let err = AocConfig::from_layers(
+            vec![layer(
+                "project",
+                "[observer.gateway]\nbase_url = \"openrouter.ai\"\n",
+            )],
+            None,
+            None,
+        )
+        .expect_err("gateway without scheme");
+        assert!(err.to_string().starts_with("`observer.gateway`"), "{err}");
+    }
+
+    #[test]
+    fn observer_redaction_cache_and_budget_are_validated() {
+        let config = AocConfig::from_layers(
+            vec![layer(
+                "project",
+                r#"
+[observer.redaction]
+emails = false
+
+[[observer.redaction.patterns]]
+category = "customer_id"
+regex = "CUST-[0-9]{6}"
+"#,
+            )],
+            None,
+            None,
+        )
+        .expect("redaction patterns");
+        assert!(config.observer.redaction.enabled);
+        assert!(!config.observer.redaction.emails);
+        assert_eq!(
+            config.observer.redaction.patterns[0].category,
+            "customer_id"
+        );
+
+        let err = AocConfig::from_layers(
+            vec![layer(
+                "project",
+                "[[observer.redaction.patterns]]\ncategory = \"ticket\"\nregex = \"(\"\n",
+            )],
+            None,
+            None,
+        )
+        .expect_err("unbalanced regex");
+        assert!(err.to_string().starts_with("`observer.redaction`"), "{err}");
+
+        let err = AocConfig::from_layers(
+            vec![layer("project", "[observer.cache]\nttl_hours = 0\n")],
+            None,
+            None,
+        )
+        .expect_err("zero cache ttl");
+        assert!(err.to_string().starts_with("`observer.cache`"), "{err}");
+
+        let config = AocConfig::from_layers(
+            vec![layer(
+                "project",
+                "[observer.budget]\nmax_cost_micros_per_day = 500000\n\n[observer.budget.providers.openrouter]\nmax_tokens_per_day = 200000\n",
+            )],
+            None,
+            None,
+        )
+        .expect("daily budget");
+        assert_eq!(config.observer.budget.max_cost_micros_per_day, 500_000);
+        assert_eq!(
+            config.observer.budget.providers["openrouter"].max_tokens_per_day,
+            200_000
+        );
+        let err = AocConfig::from_layers(
+            vec![layer(
+                "project",
+                "[observer.budget.providers.\"*\"]\nmax_tokens_per_day = 1\n",
+            )],
+            None,
+            None,
+        )
+        .expect_err("reserved provider name");
+        assert!(err.to_string().starts_with("`observer.budget`"), "{err}");
+
+        let err = AocConfig::from_layers(
+            vec![layer(
+                "project",
+                "[observer.breaker]\nfailure_threshold = 0\n",
+            )],
+            None,
+            None,
+        )
+        .expect_err("zero breaker threshold");
+        assert!(err.to_string().starts_with("`observer.breaker`"), "{err}");
+
+        let err = AocConfig::from_layers(
+            vec![layer("project", "[observer]\nworkers = 0\n")],
+            None,
+            None,
+        )
+        .expect_err("no observer workers");
+        assert!(err.to_string().starts_with("`observer.workers`"), "{err}");
+    }
+
+    #[test]
+    fn compaction_section_builds_a_content_versioned_policy() {
+        let defaults = AocConfig::from_layers(Vec::new(), None, None).expect("defaults");
+        assert_eq!(
+            defaults.compaction_policy().expect("policy"),
+            T0CompactionPolicy::default()
+        );
+
+        let project = layer(
+            "project",
+            r#"
+[compaction]
+drop_tools = ["ls"]
+
+[compaction.tool_snippets]
+bash = 200
+
+[compaction.role_max_chars]
+assistant = 4000
+
+[[compaction.redact]]
+pattern = "TICKET-\\d+"
+replacement = "[ticket]"
+"#,
+        );
+        let config = AocConfig::from_layers(vec![project], None, None).expect("compaction");
+        let policy = config.compaction_policy().expect("policy");
+        assert_eq!(policy.tool_snippet_allowlist["bash"], 200);
+        assert!(policy.drop_tools.contains("ls"));
+        assert_eq!(policy.role_max_chars[&ConversationRole::Assistant], 4000);
+        assert_eq!(
+            policy.snippet_redactions[0].replacement.as_deref(),
+            Some("[ticket]")
+        );
+        assert_eq!(policy.keep_roles, T0CompactionPolicy::default().keep_roles);
+        assert_eq!(
+            policy.policy_version,
+            policy.content_version().expect("version")
+        );
+        assert_ne!(
+            policy.policy_version,
+            T0CompactionPolicy::default().policy_version
+        );
+
+        let err = AocConfig::from_layers(
+            vec![layer("project", "[[compaction.redact]]\npattern = \"(\"\n")],
+            None,
+            None,
+        )
+        .expect_err("bad pattern");
+        assert!(
+            err.to_string()
+                .starts_with("`compaction.redact[0].pattern`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn attribution_task_id_patterns_replace_the_defaults_and_are_validated() {
+        let defaults = AocConfig::from_layers(Vec::new(), None, None).expect("defaults");
+        assert_eq!(
+            defaults
+                .attribution_config()
+                .expect("attribution")
+                .task_id_patterns
+                .len(),
+            DEFAULT_TASK_ID_PATTERNS.len()
+        );
+
+        let project = layer(
+            "project",
+            r#"
+[attribution]
+task_id_patterns = ["\b[A-Z]{2,}-[0-9]+\b", "\b[A-Z]{2,}-[0-9]+\b"]
+"#,
+        );
+        let config = AocConfig::from_layers(vec![project], None, None).expect("patterns");
+        let attribution = config.attribution_config().expect("attribution");
+        assert_eq!(attribution.task_id_patterns.len(), 1);
+        assert_eq!(attribution.mention_window_before.num_minutes(), 30);
+
+        let err = AocConfig::from_layers(
+            vec![layer(
+                "project",
+                "[attribution]\ntask_id_patterns = [\"task\", \"(a)(b)\"]\n",
+            )],
+            None,
+            None,
+        )
+        .expect_err("two capture groups");
+        assert!(
+            err.to_string()
+                .starts_with("`attribution.task_id_patterns[1]`"),
+            "{err}"
+        );
+    }
+}
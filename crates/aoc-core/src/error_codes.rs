@@ -0,0 +1,246 @@
+//! Stable, machine-readable error codes shared by every crate.
+//!
+//! Each crate keeps its own `thiserror` enum for context-rich messages and
+//! implements [`ErrorCoded`] to map each variant onto one [`ErrorCode`]. The
+//! CLI's `--json` errors, the server's HTTP/JSON-RPC bodies, and Mind feed
+//! events all carry the code, so callers branch on it instead of parsing
+//! messages. Codes are a wire contract: add variants, never rename them.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// A request or argument was malformed.
+    BadRequest,
+    /// Input parsed but broke a contract (caps, ranges, mixed batches).
+    InvalidInput,
+    /// Configuration could not be read or failed validation.
+    Config,
+    NotFound,
+    /// The operation conflicts with current state and will not succeed as-is.
+    Conflict,
+    Unauthorized,
+    ReadOnly,
+    /// No mind store exists at the configured path.
+    StoreMissing,
+    /// Another process holds the mind's writer lease.
+    WriterBusy,
+    /// SQLite reported the database busy or locked.
+    StorageBusy,
+    Storage,
+    /// Stored data could not be decoded (bad timestamps, corrupt pages).
+    CorruptData,
+    /// The store was written by a newer schema than this build supports.
+    SchemaUnsupported,
+    /// Content was refused because it carried secrets.
+    SecurityViolation,
+    /// A remote store or server failed or was unreachable.
+    Remote,
+    Io,
+    Serialization,
+    Timeout,
+    /// A semantic provider call failed.
+    Provider,
+    /// A semantic call was refused by a token or cost budget.
+    BudgetExceeded,
+    /// A semantic provider answered with output that failed validation.
+    InvalidOutput,
+    /// A semantic stage lost its job lease to another worker.
+    LockConflict,
+    /// A hash, signature, or bundle check did not match.
+    VerificationFailed,
+    /// State changed since it was read; refresh and try again.
+    Stale,
+    Internal,
+}
+
+/// How far a failure reaches. `Fatal` needs operator action before anything
+/// else on the store can succeed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorSeverity {
+    Warning,
+    Error,
+    Fatal,
+}
+
+impl ErrorSeverity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Fatal => "fatal",
+        }
+    }
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 25] = [
+        Self::BadRequest,
+        Self::InvalidInput,
+        Self::Config,
+        Self::NotFound,
+        Self::Conflict,
+        Self::Unauthorized,
+        Self::ReadOnly,
+        Self::StoreMissing,
+        Self::WriterBusy,
+        Self::StorageBusy,
+        Self::Storage,
+        Self::CorruptData,
+        Self::SchemaUnsupported,
+        Self::SecurityViolation,
+        Self::Remote,
+        Self::Io,
+        Self::Serialization,
+        Self::Timeout,
+        Self::Provider,
+        Self::BudgetExceeded,
+        Self::InvalidOutput,
+        Self::LockConflict,
+        Self::VerificationFailed,
+        Self::Stale,
+        Self::Internal,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BadRequest => "bad_request",
+            Self::InvalidInput => "invalid_input",
+            Self::Config => "config",
+            Self::NotFound => "not_found",
+            Self::Conflict => "conflict",
+            Self::Unauthorized => "unauthorized",
+            Self::ReadOnly => "read_only",
+            Self::StoreMissing => "store_missing",
+            Self::WriterBusy => "writer_busy",
+            Self::StorageBusy => "storage_busy",
+            Self::Storage => "storage",
+            Self::CorruptData => "corrupt_data",
+            Self::SchemaUnsupported => "schema_unsupported",
+            Self::SecurityViolation => "security_violation",
+            Self::Remote => "remote",
+            Self::Io => "io",
+            Self::Serialization => "serialization",
+            Self::Timeout => "timeout",
+            Self::Provider => "provider",
+            Self::BudgetExceeded => "budget_exceeded",
+            Self::InvalidOutput => "invalid_output",
+            Self::LockConflict => "lock_conflict",
+            Self::VerificationFailed => "verification_failed",
+            Self::Stale => "stale",
+            Self::Internal => "internal",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|code| code.as_str() == value)
+    }
+
+    pub fn severity(self) -> ErrorSeverity {
+        match self {
+            Self::WriterBusy
+            | Self::StorageBusy
+            | Self::Timeout
+            | Self::BudgetExceeded
+            | Self::LockConflict
+            | Self::Stale => ErrorSeverity::Warning,
+            Self::StoreMissing | Self::CorruptData | Self::SchemaUnsupported => {
+                ErrorSeverity::Fatal
+            }
+            _ => ErrorSeverity::Error,
+        }
+    }
+
+    /// Whether repeating the same call unchanged can succeed once the
+    /// contended resource frees up.
+    pub fn retryable(self) -> bool {
+        matches!(
+            self,
+            Self::WriterBusy
+                | Self::StorageBusy
+                | Self::Remote
+                | Self::Timeout
+                | Self::Provider
+                | Self::LockConflict
+        )
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A coded error flattened for JSON output.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ErrorInfo {
+    pub code: ErrorCode,
+    pub severity: ErrorSeverity,
+    pub retryable: bool,
+    pub message: String,
+}
+
+impl ErrorInfo {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            severity: code.severity(),
+            retryable: code.retryable(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Maps a crate error onto the shared taxonomy. Wrapping variants delegate
+/// to the wrapped error so a storage failure keeps its code however many
+/// layers it crosses.
+pub trait ErrorCoded: fmt::Display {
+    fn error_code(&self) -> ErrorCode;
+
+    fn error_info(&self) -> ErrorInfo {
+        ErrorInfo::new(self.error_code(), self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip_through_serde_and_parse() {
+        for code in ErrorCode::ALL {
+            let json = serde_json::to_string(&code).expect("serialize");
+            assert_eq!(json, format!("\"{}\"", code.as_str()));
+            assert_eq!(
+                serde_json::from_str::<ErrorCode>(&json).expect("deserialize"),
+                code
+            );
+            assert_eq!(ErrorCode::parse(code.as_str()), Some(code));
+        }
+        assert_eq!(ErrorCode::parse("nope"), None);
+    }
+
+    #[test]
+    fn error_info_carries_code_severity_and_retryability() {
+        let busy = ErrorInfo::new(ErrorCode::WriterBusy, "held by cli");
+        assert_eq!(busy.severity, ErrorSeverity::Warning);
+        assert!(busy.retryable);
+        assert_eq!(
+            serde_json::to_value(&busy).expect("json"),
+            serde_json::json!({
+                "code": "writer_busy",
+                "severity": "warning",
+                "retryable": true,
+                "message": "held by cli",
+            })
+        );
+
+        let schema = ErrorInfo::new(ErrorCode::SchemaUnsupported, "v99");
+        assert_eq!(schema.severity, ErrorSeverity::Fatal);
+        assert!(!schema.retryable);
+    }
+}
@@ -234,6 +234,59 @@ pub struct InsightRetrievalResult {
     pub hit_budget: usize,
     #[serde(default)]
     pub line_budget_per_hit: usize,
+    #[serde(default)]
+    pub metrics: RetrievalMetrics,
+}
+
+/// Latency budget the handshake/context-pack path is expected to meet.
+pub const RETRIEVAL_HANDSHAKE_SLO_MS: u64 = 200;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct RetrievalStageTimings {
+    #[serde(default)]
+    pub candidate_fetch_us: u64,
+    #[serde(default)]
+    pub rerank_us: u64,
+    #[serde(default)]
+    pub assembly_us: u64,
+    #[serde(default)]
+    pub total_us: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct RetrievalBudgetUsage {
+    #[serde(default)]
+    pub candidates_considered: usize,
+    #[serde(default)]
+    pub hit_budget: usize,
+    #[serde(default)]
+    pub hits_returned: usize,
+    #[serde(default)]
+    pub line_budget: usize,
+    #[serde(default)]
+    pub lines_used: usize,
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct RetrievalMetrics {
+    #[serde(default)]
+    pub pipeline: String,
+    #[serde(default)]
+    pub timings: RetrievalStageTimings,
+    #[serde(default)]
+    pub budget: RetrievalBudgetUsage,
+}
+
+impl RetrievalMetrics {
+    pub fn total_ms(&self) -> u64 {
+        self.timings.total_us / 1_000
+    }
+
+    pub fn within_slo(&self, slo_ms: u64) -> bool {
+        self.timings.total_us <= slo_ms.saturating_mul(1_000)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -491,6 +544,30 @@ mod tests {
         assert_eq!(request.max_gaps, 12);
     }
 
+    #[test]
+    fn retrieval_result_without_metrics_still_deserializes() {
+        let result: InsightRetrievalResult = serde_json::from_value(serde_json::json!({
+            "query": "canon",
+            "scope": "auto",
+            "resolved_scope": "project",
+            "mode": "brief",
+            "status": "ok"
+        }))
+        .expect("legacy retrieval payload");
+        assert_eq!(result.metrics, RetrievalMetrics::default());
+
+        let metrics = RetrievalMetrics {
+            pipeline: "insight_retrieval".to_string(),
+            timings: RetrievalStageTimings {
+                total_us: 199_999,
+                ..RetrievalStageTimings::default()
+            },
+            budget: RetrievalBudgetUsage::default(),
+        };
+        assert!(metrics.within_slo(RETRIEVAL_HANDSHAKE_SLO_MS));
+        assert_eq!(metrics.total_ms(), 199);
+    }
+
     #[test]
     fn parse_retrieve_defaults_scope_and_mode() {
         let command = InsightCommand::parse(
@@ -5,14 +5,19 @@ use std::fmt;
 use std::str::FromStr;
 
 pub mod consultation_contracts;
+pub mod error_codes;
 pub mod insight_contracts;
 pub mod mind_contracts;
 pub mod mind_observer_feed;
 pub mod provenance_contracts;
 pub mod pulse_ipc;
 pub mod session_overseer;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 pub mod zellij_cli;
 
+pub use error_codes::{ErrorCode, ErrorCoded, ErrorInfo, ErrorSeverity};
+
 pub const TAG_PRD_KEY: &str = "aocPrd";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -8,6 +8,8 @@ pub enum MindObserverFeedStatus {
     Success,
     Fallback,
     Error,
+    /// A semantic provider's circuit breaker changed state.
+    Breaker,
 }
 
 impl MindObserverFeedStatus {
@@ -18,6 +20,7 @@ impl MindObserverFeedStatus {
             Self::Success => "success",
             Self::Fallback => "fallback",
             Self::Error => "error",
+            Self::Breaker => "breaker",
         }
     }
 }
@@ -68,6 +71,9 @@ pub struct MindObserverFeedEvent {
     pub reason: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub failure_kind: Option<String>,
+    /// Shared code of the failure behind an `error`/`fallback` event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<crate::ErrorCode>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enqueued_at: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
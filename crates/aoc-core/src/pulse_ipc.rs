@@ -305,6 +305,17 @@ pub enum FrameError {
     Decode(String),
 }
 
+impl crate::ErrorCoded for FrameError {
+    fn error_code(&self) -> crate::ErrorCode {
+        match self {
+            Self::OversizedFrame { .. } | Self::OversizedBuffer { .. } => {
+                crate::ErrorCode::InvalidInput
+            }
+            Self::Encode(_) | Self::Decode(_) => crate::ErrorCode::Serialization,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DecodeReport<T> {
     pub frames: Vec<T>,
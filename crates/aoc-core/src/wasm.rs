@@ -0,0 +1,132 @@
+//! Browser bindings (`--features wasm`): validate Mind payloads and compute
+//! the same canonical JSON and hashes as the native pipeline.
+//!
+//! Every function takes and returns JSON text so the dashboard does not need
+//! generated TypeScript types; errors surface as JS `Error`s carrying the
+//! `MindContractError` message.
+
+use crate::mind_contracts::{
+    blake3_hex, canonical_json, canonical_payload_hash, sha256_hex, validate_t1_scope,
+    ArtifactTaskLink, MindContractError, ObserverOutput, RawEvent, ReflectorOutput, SegmentRoute,
+    T1Batch,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+
+/// Payload kinds accepted by [`validate_payload`].
+pub const PAYLOAD_KINDS: [&str; 6] = [
+    "raw_event",
+    "t1_batches",
+    "observer_output",
+    "reflector_output",
+    "segment_route",
+    "artifact_task_link",
+];
+
+fn parse<T: DeserializeOwned>(kind: &str, raw: &str) -> Result<T, MindContractError> {
+    serde_json::from_str(raw)
+        .map_err(|err| MindContractError::Serialization(format!("{kind}: {err}")))
+}
+
+/// Parses `raw` as the contract named by `kind` and runs the same checks the
+/// pipeline applies before persisting it.
+pub fn validate_payload_json(kind: &str, raw: &str) -> Result<(), MindContractError> {
+    match kind {
+        "raw_event" => parse::<RawEvent>(kind, raw).map(drop),
+        "t1_batches" => validate_t1_scope(&parse::<Vec<T1Batch>>(kind, raw)?),
+        "observer_output" => ObserverOutput::parse_json(raw).map(drop),
+        "reflector_output" => ReflectorOutput::parse_json(raw).map(drop),
+        "segment_route" => parse::<SegmentRoute>(kind, raw)?.validate(),
+        "artifact_task_link" => {
+            let link = parse::<ArtifactTaskLink>(kind, raw)?;
+            ArtifactTaskLink::new(
+                link.artifact_id,
+                link.task_id,
+                link.relation,
+                link.confidence_bps,
+                link.evidence_event_ids,
+                link.source,
+                link.start_ts,
+                link.end_ts,
+            )
+            .map(drop)
+        }
+        other => Err(MindContractError::Serialization(format!(
+            "unknown payload kind '{other}' (expected one of {})",
+            PAYLOAD_KINDS.join(", ")
+        ))),
+    }
+}
+
+/// Canonical JSON for arbitrary JSON text: object keys sorted, no whitespace.
+pub fn canonical_json_text(raw: &str) -> Result<String, MindContractError> {
+    canonical_json(&parse::<Value>("payload", raw)?)
+}
+
+/// `canonical_payload_hash` for arbitrary JSON text.
+pub fn canonical_hash_text(raw: &str) -> Result<String, MindContractError> {
+    canonical_payload_hash(&parse::<Value>("payload", raw)?)
+}
+
+fn js_error(err: MindContractError) -> JsError {
+    JsError::new(&err.to_string())
+}
+
+#[wasm_bindgen(js_name = validatePayload)]
+pub fn validate_payload(kind: &str, json: &str) -> Result<(), JsError> {
+    validate_payload_json(kind, json).map_err(js_error)
+}
+
+#[wasm_bindgen(js_name = canonicalJson)]
+pub fn canonical_json_js(json: &str) -> Result<String, JsError> {
+    canonical_json_text(json).map_err(js_error)
+}
+
+#[wasm_bindgen(js_name = canonicalPayloadHash)]
+pub fn canonical_payload_hash_js(json: &str) -> Result<String, JsError> {
+    canonical_hash_text(json).map_err(js_error)
+}
+
+#[wasm_bindgen(js_name = sha256Hex)]
+pub fn sha256_hex_js(text: &str) -> String {
+    sha256_hex(text.as_bytes())
+}
+
+#[wasm_bindgen(js_name = blake3Hex)]
+pub fn blake3_hex_js(text: &str) -> String {
+    blake3_hex(text.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_helpers_match_native_hashing_and_validation() {
+        let value = serde_json::json!({"b": [2, {"z": 1, "a": 0}], "a": "x"});
+        assert_eq!(
+            canonical_json_text(r#"{ "b": [2, {"z": 1, "a": 0}], "a": "x" }"#).unwrap(),
+            r#"{"a":"x","b":[2,{"a":0,"z":1}]}"#
+        );
+        assert_eq!(
+            canonical_hash_text(&value.to_string()).unwrap(),
+            canonical_payload_hash(&value).unwrap()
+        );
+
+        validate_payload_json("observer_output", r#"{"summary":"ok"}"#).unwrap();
+        assert!(validate_payload_json("observer_output", r#"{"summary":"  "}"#).is_err());
+        assert!(validate_payload_json(
+            "t1_batches",
+            r#"[{"conversation_id":"a","compact_event_ids":[],"estimated_tokens":1},
+                {"conversation_id":"b","compact_event_ids":[],"estimated_tokens":1}]"#
+        )
+        .is_err());
+        let link = r#"{"artifact_id":"obs:1","task_id":"7","relation":"active",
+            "confidence_bps":12000,"evidence_event_ids":[],"source":"ui",
+            "start_ts":"2026-03-01T10:00:00Z","end_ts":null}"#;
+        assert!(validate_payload_json("artifact_task_link", link).is_err());
+        let err = validate_payload_json("nope", "{}").unwrap_err();
+        assert!(err.to_string().contains("raw_event"));
+    }
+}
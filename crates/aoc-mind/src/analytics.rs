@@ -0,0 +1,770 @@
+//! Modeled analytical export: a star schema of Parquet tables plus a DuckDB
+//! `schema.sql`, so questions like "time per segment per week" are plain SQL
+//! instead of JSON spelunking. Table and column docs live in [`STAR_SCHEMA`]
+//! and are rendered into `schema.sql`; keep
+//! `docs/reference/aoc-mind-analytics.md` in step when they change.
+
+use crate::export::{write_atomically, ArtifactExportError, PARQUET_DISABLED};
+use aoc_core::mind_contracts::{ConversationRole, RawEventBody};
+use aoc_storage::{ArtifactQuery, MindStore, StoredArtifact};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use StarColumnType::{Bool, Date, Int, Text, Timestamp};
+
+const PAGE_SIZE: usize = 2_000;
+const SCHEMA_SQL_FILE: &str = "schema.sql";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StarColumnType {
+    Text,
+    Int,
+    Bool,
+    /// UTC instant, millisecond precision.
+    Timestamp,
+    Date,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StarColumn {
+    pub name: &'static str,
+    pub column_type: StarColumnType,
+    pub nullable: bool,
+    pub doc: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StarTable {
+    pub name: &'static str,
+    pub doc: &'static str,
+    pub columns: &'static [StarColumn],
+}
+
+const fn col(
+    name: &'static str,
+    column_type: StarColumnType,
+    nullable: bool,
+    doc: &'static str,
+) -> StarColumn {
+    StarColumn {
+        name,
+        column_type,
+        nullable,
+        doc,
+    }
+}
+
+/// Every table the analytical export writes, facts first.
+pub const STAR_SCHEMA: &[StarTable] = &[
+    StarTable {
+        name: "fact_events",
+        doc: "One row per raw event (message, tool result, task signal).",
+        columns: &[
+            col("event_id", Text, false, "Raw event id."),
+            col("conversation_id", Text, false, "FK dim_conversations."),
+            col("agent_id", Text, false, "Agent that produced the event."),
+            col("ts", Timestamp, false, "Event time (UTC)."),
+            col("day", Date, false, "UTC day of ts."),
+            col("week", Date, false, "Monday of the UTC week of ts."),
+            col(
+                "event_kind",
+                Text,
+                false,
+                "message, tool_result, task_signal, or other.",
+            ),
+            col("role", Text, true, "Message role (messages only)."),
+            col("tool_name", Text, true, "Tool name (tool results only)."),
+            col(
+                "tool_status",
+                Text,
+                true,
+                "Tool status (tool results only).",
+            ),
+            col("tool_latency_ms", Int, true, "Tool latency when reported."),
+            col(
+                "text_chars",
+                Int,
+                false,
+                "Characters of message text or tool output.",
+            ),
+        ],
+    },
+    StarTable {
+        name: "fact_artifacts",
+        doc: "One row per active T1 observation or T2 reflection.",
+        columns: &[
+            col("artifact_id", Text, false, "Artifact id."),
+            col("conversation_id", Text, false, "FK dim_conversations."),
+            col("kind", Text, false, "t1 or t2."),
+            col("ts", Timestamp, false, "Artifact time (UTC)."),
+            col("day", Date, false, "UTC day of ts."),
+            col("week", Date, false, "Monday of the UTC week of ts."),
+            col(
+                "span_start",
+                Timestamp,
+                false,
+                "Earliest traced event (T0 for t1, T1 spans for t2).",
+            ),
+            col("span_end", Timestamp, false, "Latest traced event."),
+            col(
+                "span_seconds",
+                Int,
+                false,
+                "span_end - span_start; the work the artifact covers.",
+            ),
+            col(
+                "primary_segment_id",
+                Text,
+                true,
+                "FK dim_segments; primary route, if routed.",
+            ),
+            col("trace_count", Int, false, "Number of trace ids."),
+            col("text_chars", Int, false, "Characters of artifact text."),
+        ],
+    },
+    StarTable {
+        name: "fact_artifact_tasks",
+        doc: "Artifact-to-task links (bridge between fact_artifacts and dim_tasks).",
+        columns: &[
+            col("artifact_id", Text, false, "FK fact_artifacts."),
+            col("task_id", Text, false, "FK dim_tasks."),
+            col(
+                "relation",
+                Text,
+                false,
+                "active, worked_on, mentioned, or completed.",
+            ),
+            col(
+                "confidence_bps",
+                Int,
+                false,
+                "Link confidence in basis points.",
+            ),
+            col("source", Text, false, "What produced the link."),
+            col("start_ts", Timestamp, false, "Link start (UTC)."),
+            col("end_ts", Timestamp, true, "Link end (UTC), if closed."),
+        ],
+    },
+    StarTable {
+        name: "fact_artifact_segments",
+        doc: "Artifact segment routes, primary and secondary candidates.",
+        columns: &[
+            col("artifact_id", Text, false, "FK fact_artifacts."),
+            col("segment_id", Text, false, "FK dim_segments."),
+            col("is_primary", Bool, false, "True for the primary route."),
+            col(
+                "confidence_bps",
+                Int,
+                false,
+                "Route confidence in basis points.",
+            ),
+            col(
+                "routed_by",
+                Text,
+                false,
+                "taskmaster, heuristic, or manual_override.",
+            ),
+        ],
+    },
+    StarTable {
+        name: "dim_conversations",
+        doc: "Every conversation with events or artifacts.",
+        columns: &[
+            col("conversation_id", Text, false, "Conversation id."),
+            col("session_id", Text, true, "Session from lineage metadata."),
+            col(
+                "parent_conversation_id",
+                Text,
+                true,
+                "Parent conversation for branches.",
+            ),
+            col(
+                "root_conversation_id",
+                Text,
+                true,
+                "Root of the session tree.",
+            ),
+            col("active_tag", Text, true, "Latest active Taskmaster tag."),
+            col("first_ts", Timestamp, false, "Earliest event or artifact."),
+            col("last_ts", Timestamp, false, "Latest event or artifact."),
+            col("events", Int, false, "Rows in fact_events."),
+            col("artifacts", Int, false, "Rows in fact_artifacts."),
+        ],
+    },
+    StarTable {
+        name: "dim_tasks",
+        doc: "Every task linked to at least one artifact.",
+        columns: &[
+            col("task_id", Text, false, "Task id."),
+            col("first_linked_ts", Timestamp, false, "Earliest link start."),
+            col("last_linked_ts", Timestamp, false, "Latest link start."),
+            col("artifacts", Int, false, "Distinct linked artifacts."),
+        ],
+    },
+    StarTable {
+        name: "dim_segments",
+        doc: "Every segment with at least one routed artifact.",
+        columns: &[
+            col("segment_id", Text, false, "Segment id."),
+            col("artifacts", Int, false, "Artifacts routed here (any rank)."),
+            col(
+                "primary_artifacts",
+                Int,
+                false,
+                "Artifacts with this as primary route.",
+            ),
+        ],
+    },
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StarSchemaReport {
+    /// `(table, rows)` in [`STAR_SCHEMA`] order.
+    pub tables: Vec<(&'static str, usize)>,
+    pub files_written: Vec<PathBuf>,
+}
+
+enum Values {
+    Text(Vec<Option<String>>),
+    Int(Vec<Option<i64>>),
+    Bool(Vec<Option<bool>>),
+    Timestamp(Vec<Option<i64>>),
+    Date(Vec<Option<i32>>),
+}
+
+impl Values {
+    fn new(column_type: StarColumnType) -> Self {
+        match column_type {
+            Text => Self::Text(Vec::new()),
+            Int => Self::Int(Vec::new()),
+            Bool => Self::Bool(Vec::new()),
+            Timestamp => Self::Timestamp(Vec::new()),
+            Date => Self::Date(Vec::new()),
+        }
+    }
+}
+
+/// Cell for [`TableBuilder::push`]; `Null` is only valid in nullable columns.
+enum Cell {
+    Text(String),
+    Int(i64),
+    Bool(bool),
+    Ts(DateTime<Utc>),
+    Date(NaiveDate),
+    Null,
+}
+
+impl From<&str> for Cell {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for Cell {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl<T: Into<Cell>> From<Option<T>> for Cell {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+struct TableBuilder {
+    table: &'static StarTable,
+    columns: Vec<Values>,
+    rows: usize,
+}
+
+impl TableBuilder {
+    fn new(name: &str) -> Self {
+        let table = STAR_SCHEMA
+            .iter()
+            .find(|table| table.name == name)
+            .expect("table declared in STAR_SCHEMA");
+        Self {
+            table,
+            columns: table
+                .columns
+                .iter()
+                .map(|column| Values::new(column.column_type))
+                .collect(),
+            rows: 0,
+        }
+    }
+
+    fn push(&mut self, row: Vec<Cell>) {
+        debug_assert_eq!(row.len(), self.columns.len(), "{}", self.table.name);
+        for (values, cell) in self.columns.iter_mut().zip(row) {
+            match (values, cell) {
+                (Values::Text(out), Cell::Text(value)) => out.push(Some(value)),
+                (Values::Int(out), Cell::Int(value)) => out.push(Some(value)),
+                (Values::Bool(out), Cell::Bool(value)) => out.push(Some(value)),
+                (Values::Timestamp(out), Cell::Ts(value)) => {
+                    out.push(Some(value.timestamp_millis()))
+                }
+                (Values::Date(out), Cell::Date(value)) => out.push(Some(days_since_epoch(value))),
+                (Values::Text(out), Cell::Null) => out.push(None),
+                (Values::Int(out), Cell::Null) => out.push(None),
+                (Values::Bool(out), Cell::Null) => out.push(None),
+                (Values::Timestamp(out), Cell::Null) => out.push(None),
+                (Values::Date(out), Cell::Null) => out.push(None),
+                _ => panic!("cell type does not match {} column", self.table.name),
+            }
+        }
+        self.rows += 1;
+    }
+}
+
+fn days_since_epoch(day: NaiveDate) -> i32 {
+    (day - NaiveDate::default()).num_days() as i32
+}
+
+fn week_start(day: NaiveDate) -> NaiveDate {
+    day - chrono::Duration::days(i64::from(day.weekday().num_days_from_monday()))
+}
+
+fn label<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[derive(Default)]
+struct ConversationDim {
+    first_ts: Option<DateTime<Utc>>,
+    last_ts: Option<DateTime<Utc>>,
+    events: i64,
+    artifacts: i64,
+}
+
+impl ConversationDim {
+    fn touch(&mut self, ts: DateTime<Utc>) {
+        self.first_ts = Some(self.first_ts.map_or(ts, |first| first.min(ts)));
+        self.last_ts = Some(self.last_ts.map_or(ts, |last| last.max(ts)));
+    }
+}
+
+/// Writes the [`STAR_SCHEMA`] tables as `<table>.parquet` under `out_dir`,
+/// plus a `schema.sql` that registers them as DuckDB views.
+///
+/// Covers every raw event and every active (non-archived) artifact; each
+/// run rewrites the tables in full.
+pub fn export_star_schema(
+    store: &MindStore,
+    out_dir: &Path,
+) -> Result<StarSchemaReport, ArtifactExportError> {
+    if !cfg!(feature = "parquet") {
+        return Err(ArtifactExportError::UnsupportedFormat(PARQUET_DISABLED));
+    }
+    fs::create_dir_all(out_dir)?;
+    let mut conversations = BTreeMap::<String, ConversationDim>::new();
+
+    let mut events = TableBuilder::new("fact_events");
+    let mut cursor: Option<(DateTime<Utc>, String)> = None;
+    loop {
+        let page = store.raw_events_after(
+            cursor.as_ref().map(|(ts, id)| (*ts, id.as_str())),
+            PAGE_SIZE,
+        )?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        cursor = Some((last.ts, last.event_id.clone()));
+        for event in page {
+            let day = event.ts.date_naive();
+            let (kind, role, tool_name, tool_status, latency, chars) = match &event.body {
+                RawEventBody::Message(message) => (
+                    "message",
+                    Some(label(&message.role)),
+                    None,
+                    None,
+                    None,
+                    message.text.chars().count(),
+                ),
+                RawEventBody::ToolResult(tool) => (
+                    "tool_result",
+                    None,
+                    Some(tool.tool_name.clone()),
+                    Some(label(&tool.status)),
+                    tool.latency_ms,
+                    tool.output
+                        .as_deref()
+                        .map_or(0, |output| output.chars().count()),
+                ),
+                RawEventBody::TaskSignal(_) => ("task_signal", None, None, None, None, 0),
+                RawEventBody::Error(error) => (
+                    "error",
+                    None,
+                    None,
+                    None,
+                    None,
+                    error.message.chars().count(),
+                ),
+                RawEventBody::PlanUpdate(_) => ("plan_update", None, None, None, None, 0),
+                RawEventBody::Reasoning(reasoning) => (
+                    "reasoning",
+                    Some(label(&ConversationRole::Assistant)),
+                    None,
+                    None,
+                    None,
+                    reasoning.summary.chars().count(),
+                ),
+                RawEventBody::Other { .. } => ("other", None, None, None, None, 0),
+            };
+            let dim = conversations
+                .entry(event.conversation_id.clone())
+                .or_default();
+            dim.touch(event.ts);
+            dim.events += 1;
+            events.push(vec![
+                event.event_id.into(),
+                event.conversation_id.into(),
+                event.agent_id.into(),
+                Cell::Ts(event.ts),
+                Cell::Date(day),
+                Cell::Date(week_start(day)),
+                kind.into(),
+                role.into(),
+                tool_name.into(),
+                tool_status.into(),
+                latency.map_or(Cell::Null, |ms| Cell::Int(ms as i64)),
+                Cell::Int(chars as i64),
+            ]);
+        }
+    }
+
+    let mut artifacts = TableBuilder::new("fact_artifacts");
+    let mut task_links = TableBuilder::new("fact_artifact_tasks");
+    let mut routes = TableBuilder::new("fact_artifact_segments");
+    let mut tasks = BTreeMap::<String, (DateTime<Utc>, DateTime<Utc>, Vec<String>)>::new();
+    let mut segments = BTreeMap::<String, (i64, i64)>::new();
+    // T2 spans are the union of their T1 spans, so observations go first.
+    let mut spans = HashMap::<String, (DateTime<Utc>, DateTime<Utc>)>::new();
+    for kind in ["t1", "t2"] {
+        let mut offset = 0;
+        loop {
+            let page = store.query_artifacts(&ArtifactQuery {
+                kind: Some(kind.to_string()),
+                oldest_first: true,
+                offset,
+                limit: PAGE_SIZE,
+                ..ArtifactQuery::default()
+            })?;
+            let next = page.next_offset();
+            offset += page.artifacts.len();
+            for artifact in page.artifacts {
+                let span = artifact_span(store, &spans, &artifact)?;
+                if kind == "t1" {
+                    spans.insert(artifact.artifact_id.clone(), span);
+                }
+
+                let route = store.segment_route_for_artifact(&artifact.artifact_id)?;
+                let primary_segment = route.as_ref().map(|route| route.primary.segment_id.clone());
+                if let Some(route) = route {
+                    let routed_by = label(&route.routed_by);
+                    let candidates = std::iter::once((true, route.primary))
+                        .chain(route.secondary.into_iter().map(|c| (false, c)));
+                    for (is_primary, candidate) in candidates {
+                        let counts = segments.entry(candidate.segment_id.clone()).or_default();
+                        counts.0 += 1;
+                        counts.1 += i64::from(is_primary);
+                        routes.push(vec![
+                            artifact.artifact_id.as_str().into(),
+                            candidate.segment_id.into(),
+                            Cell::Bool(is_primary),
+                            Cell::Int(i64::from(candidate.confidence_bps)),
+                            routed_by.as_str().into(),
+                        ]);
+                    }
+                }
+
+                for link in store.artifact_task_links_for_artifact(&artifact.artifact_id)? {
+                    let entry = tasks.entry(link.task_id.clone()).or_insert((
+                        link.start_ts,
+                        link.start_ts,
+                        Vec::new(),
+                    ));
+                    entry.0 = entry.0.min(link.start_ts);
+                    entry.1 = entry.1.max(link.start_ts);
+                    if !entry.2.contains(&link.artifact_id) {
+                        entry.2.push(link.artifact_id.clone());
+                    }
+                    task_links.push(vec![
+                        link.artifact_id.into(),
+                        link.task_id.into(),
+                        label(&link.relation).into(),
+                        Cell::Int(i64::from(link.confidence_bps)),
+                        link.source.into(),
+                        Cell::Ts(link.start_ts),
+                        link.end_ts.map_or(Cell::Null, Cell::Ts),
+                    ]);
+                }
+
+                let dim = conversations
+                    .entry(artifact.conversation_id.clone())
+                    .or_default();
+                dim.touch(artifact.ts);
+                dim.artifacts += 1;
+                let day = artifact.ts.date_naive();
+                artifacts.push(vec![
+                    artifact.artifact_id.into(),
+                    artifact.conversation_id.into(),
+                    artifact.kind.into(),
+                    Cell::Ts(artifact.ts),
+                    Cell::Date(day),
+                    Cell::Date(week_start(day)),
+                    Cell::Ts(span.0),
+                    Cell::Ts(span.1),
+                    Cell::Int((span.1 - span.0).num_seconds()),
+                    primary_segment.into(),
+                    Cell::Int(artifact.trace_ids.len() as i64),
+                    Cell::Int(artifact.text.chars().count() as i64),
+                ]);
+            }
+            if next.is_none() {
+                break;
+            }
+        }
+    }
+
+    let mut conversation_dim = TableBuilder::new("dim_conversations");
+    for (conversation_id, dim) in conversations {
+        let lineage = store.conversation_lineage(&conversation_id)?;
+        let active_tag = store
+            .latest_context_state(&conversation_id)?
+            .and_then(|state| state.active_tag);
+        let (Some(first_ts), Some(last_ts)) = (dim.first_ts, dim.last_ts) else {
+            continue;
+        };
+        conversation_dim.push(vec![
+            conversation_id.into(),
+            lineage.as_ref().map(|row| row.session_id.clone()).into(),
+            lineage
+                .as_ref()
+                .and_then(|row| row.parent_conversation_id.clone())
+                .into(),
+            lineage
+                .as_ref()
+                .map(|row| row.root_conversation_id.clone())
+                .into(),
+            active_tag.into(),
+            Cell::Ts(first_ts),
+            Cell::Ts(last_ts),
+            Cell::Int(dim.events),
+            Cell::Int(dim.artifacts),
+        ]);
+    }
+
+    let mut task_dim = TableBuilder::new("dim_tasks");
+    for (task_id, (first, last, artifact_ids)) in tasks {
+        task_dim.push(vec![
+            task_id.into(),
+            Cell::Ts(first),
+            Cell::Ts(last),
+            Cell::Int(artifact_ids.len() as i64),
+        ]);
+    }
+
+    let mut segment_dim = TableBuilder::new("dim_segments");
+    for (segment_id, (total, primary)) in segments {
+        segment_dim.push(vec![
+            segment_id.into(),
+            Cell::Int(total),
+            Cell::Int(primary),
+        ]);
+    }
+
+    let mut report = StarSchemaReport::default();
+    for table in [
+        events,
+        artifacts,
+        task_links,
+        routes,
+        conversation_dim,
+        task_dim,
+        segment_dim,
+    ] {
+        let path = out_dir.join(format!("{}.parquet", table.table.name));
+        write_atomically(&path, &render_parquet_table(&table)?)?;
+        report.tables.push((table.table.name, table.rows));
+        report.files_written.push(path);
+    }
+    let schema_path = out_dir.join(SCHEMA_SQL_FILE);
+    write_atomically(&schema_path, render_schema_sql().as_bytes())?;
+    report.files_written.push(schema_path);
+    Ok(report)
+}
+
+/// First and last timestamp of what the artifact traces: T0 events for an
+/// observation, observation spans for a reflection. Falls back to the
+/// artifact's own time when nothing resolves.
+fn artifact_span(
+    store: &MindStore,
+    t1_spans: &HashMap<String, (DateTime<Utc>, DateTime<Utc>)>,
+    artifact: &StoredArtifact,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), ArtifactExportError> {
+    let mut span: Option<(DateTime<Utc>, DateTime<Utc>)> = None;
+    let mut widen = |start: DateTime<Utc>, end: DateTime<Utc>| {
+        span = Some(span.map_or((start, end), |(lo, hi)| (lo.min(start), hi.max(end))));
+    };
+    for trace_id in &artifact.trace_ids {
+        if artifact.kind == "t2" {
+            if let Some((start, end)) = t1_spans.get(trace_id) {
+                widen(*start, *end);
+            }
+        } else if let Some(event) = store.compact_event_by_id(trace_id)? {
+            widen(event.ts, event.ts);
+        }
+    }
+    Ok(span.unwrap_or((artifact.ts, artifact.ts)))
+}
+
+/// DuckDB views over the Parquet files, with the schema docs as comments.
+pub fn render_schema_sql() -> String {
+    let mut out = String::from(
+        "-- AOC Mind analytical export (star schema).\n\
+         -- Run from this directory: duckdb mind.duckdb < schema.sql\n\n",
+    );
+    for table in STAR_SCHEMA {
+        out.push_str(&format!("-- {}: {}\n", table.name, table.doc));
+        for column in table.columns {
+            let sql_type = match column.column_type {
+                Text => "VARCHAR",
+                Int => "BIGINT",
+                Bool => "BOOLEAN",
+                Timestamp => "TIMESTAMPTZ",
+                Date => "DATE",
+            };
+            out.push_str(&format!(
+                "--   {} {}{}: {}\n",
+                column.name,
+                sql_type,
+                if column.nullable { "" } else { " NOT NULL" },
+                column.doc
+            ));
+        }
+        out.push_str(&format!(
+            "CREATE OR REPLACE VIEW {0} AS SELECT * FROM read_parquet('{0}.parquet');\n\n",
+            table.name
+        ));
+    }
+    out.push_str(
+        "-- Example: hours of covered work per primary segment per week.\n\
+         -- SELECT week, primary_segment_id, round(sum(span_seconds) / 3600.0, 1) AS hours\n\
+         -- FROM fact_artifacts WHERE kind = 't1'\n\
+         -- GROUP BY ALL ORDER BY week, hours DESC;\n",
+    );
+    out
+}
+
+#[cfg(feature = "parquet")]
+fn render_parquet_table(table: &TableBuilder) -> Result<Vec<u8>, ArtifactExportError> {
+    use parquet::data_type::{BoolType, ByteArray, ByteArrayType, Int32Type, Int64Type};
+    use parquet::file::{properties::WriterProperties, writer::SerializedFileWriter};
+    use parquet::schema::parser::parse_message_type;
+    use std::sync::Arc;
+
+    let parquet_err = |err: parquet::errors::ParquetError| {
+        ArtifactExportError::Serialization(format!("parquet {}: {err}", table.table.name))
+    };
+    let mut schema = format!("message {} {{\n", table.table.name);
+    for column in table.table.columns {
+        let repetition = if column.nullable {
+            "OPTIONAL"
+        } else {
+            "REQUIRED"
+        };
+        let physical = match column.column_type {
+            Text => "BYTE_ARRAY",
+            Int | Timestamp => "INT64",
+            Bool => "BOOLEAN",
+            Date => "INT32",
+        };
+        let logical = match column.column_type {
+            Text => " (UTF8)",
+            Timestamp => " (TIMESTAMP(MILLIS,true))",
+            Date => " (DATE)",
+            Int | Bool => "",
+        };
+        schema.push_str(&format!(
+            "  {repetition} {physical} {}{logical};\n",
+            column.name
+        ));
+    }
+    schema.push('}');
+
+    /// Non-null values plus definition levels (only for nullable columns).
+    fn split<T: Clone>(values: &[Option<T>], nullable: bool) -> (Vec<T>, Option<Vec<i16>>) {
+        let present = values.iter().flatten().cloned().collect();
+        let levels = nullable.then(|| {
+            values
+                .iter()
+                .map(|value| i16::from(value.is_some()))
+                .collect()
+        });
+        (present, levels)
+    }
+
+    let schema = Arc::new(parse_message_type(&schema).map_err(parquet_err)?);
+    let mut out = Vec::new();
+    let mut writer = SerializedFileWriter::new(
+        &mut out,
+        schema,
+        Arc::new(WriterProperties::builder().build()),
+    )
+    .map_err(parquet_err)?;
+    let mut row_group = writer.next_row_group().map_err(parquet_err)?;
+    let mut columns = table.table.columns.iter().zip(&table.columns);
+    while let Some(mut writer) = row_group.next_column().map_err(parquet_err)? {
+        let (spec, values) = columns.next().expect("column declared in schema");
+        match values {
+            Values::Text(values) => {
+                let (present, levels) = split(values, spec.nullable);
+                let present = present
+                    .into_iter()
+                    .map(|value| ByteArray::from(value.into_bytes()))
+                    .collect::<Vec<_>>();
+                writer
+                    .typed::<ByteArrayType>()
+                    .write_batch(&present, levels.as_deref(), None)
+            }
+            Values::Int(values) | Values::Timestamp(values) => {
+                let (present, levels) = split(values, spec.nullable);
+                writer
+                    .typed::<Int64Type>()
+                    .write_batch(&present, levels.as_deref(), None)
+            }
+            Values::Bool(values) => {
+                let (present, levels) = split(values, spec.nullable);
+                writer
+                    .typed::<BoolType>()
+                    .write_batch(&present, levels.as_deref(), None)
+            }
+            Values::Date(values) => {
+                let (present, levels) = split(values, spec.nullable);
+                writer
+                    .typed::<Int32Type>()
+                    .write_batch(&present, levels.as_deref(), None)
+            }
+        }
+        .map_err(parquet_err)?;
+        writer.close().map_err(parquet_err)?;
+    }
+    row_group.close().map_err(parquet_err)?;
+    writer.close().map_err(parquet_err)?;
+    Ok(out)
+}
+
+#[cfg(not(feature = "parquet"))]
+fn render_parquet_table(_table: &TableBuilder) -> Result<Vec<u8>, ArtifactExportError> {
+    Err(ArtifactExportError::UnsupportedFormat(PARQUET_DISABLED))
+}
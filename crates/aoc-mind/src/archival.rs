@@ -0,0 +1,154 @@
+use aoc_storage::{ArchivedArtifact, MindPinTargetKind, MindStore, StorageError, StoredArtifact};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+const T1_BASE_IMPORTANCE_BPS: u32 = 4_000;
+const T2_BASE_IMPORTANCE_BPS: u32 = 7_000;
+const TRACE_IMPORTANCE_BPS: u32 = 750;
+const TRACE_IMPORTANCE_MAX_REFS: u32 = 3;
+const TASK_LINK_IMPORTANCE_BPS: u32 = 1_500;
+
+/// Forgetting-curve thresholds for moving aged artifacts into the archived tier.
+///
+/// An artifact is archived once it is older than `min_age_hours` and its
+/// importance, decayed by half every `half_life_hours`, drops below
+/// `retention_floor_bps`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ArchivalPolicy {
+    pub min_age_hours: u32,
+    pub half_life_hours: u32,
+    pub retention_floor_bps: u16,
+}
+
+impl Default for ArchivalPolicy {
+    fn default() -> Self {
+        Self {
+            min_age_hours: 14 * 24,
+            half_life_hours: 7 * 24,
+            retention_floor_bps: 2_500,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchivalReport {
+    pub scanned: usize,
+    pub exempt: usize,
+    pub retained: usize,
+    pub archived: Vec<ArchivedArtifact>,
+    pub dry_run: bool,
+}
+
+/// Applies `policy` to every active artifact old enough to be considered.
+///
+/// Pinned artifacts and artifacts cited by active canon are never archived,
+/// since both feed the handshake directly.
+pub fn run_artifact_archival(
+    store: &MindStore,
+    policy: ArchivalPolicy,
+    now: DateTime<Utc>,
+    dry_run: bool,
+) -> Result<ArchivalReport, StorageError> {
+    let cutoff = now - chrono::Duration::hours(i64::from(policy.min_age_hours));
+    let candidates = store.active_artifacts_before(cutoff)?;
+
+    let mut exempt_ids = store
+        .pinned_target_ids(MindPinTargetKind::Artifact)?
+        .into_iter()
+        .collect::<HashSet<_>>();
+    for entry in store.active_canon_entries(None)? {
+        exempt_ids.extend(entry.evidence_refs);
+    }
+
+    let mut report = ArchivalReport {
+        scanned: candidates.len(),
+        dry_run,
+        ..ArchivalReport::default()
+    };
+    for artifact in candidates {
+        if exempt_ids.contains(&artifact.artifact_id) {
+            report.exempt += 1;
+            continue;
+        }
+        let importance_bps = artifact_importance_bps(store, &artifact)?;
+        let age_hours = (now - artifact.ts).num_minutes().max(0) as f64 / 60.0;
+        let retention_bps =
+            forgetting_curve_retention_bps(importance_bps, age_hours, policy.half_life_hours);
+        if retention_bps >= policy.retention_floor_bps {
+            report.retained += 1;
+            continue;
+        }
+
+        let entry = ArchivedArtifact {
+            artifact_id: artifact.artifact_id,
+            conversation_id: artifact.conversation_id,
+            kind: artifact.kind,
+            retention_bps,
+            reason: format!(
+                "retention {retention_bps}bps below floor {}bps after {:.0}h",
+                policy.retention_floor_bps, age_hours
+            ),
+            archived_at: now,
+        };
+        if !dry_run {
+            store.archive_artifact(&entry)?;
+        }
+        report.archived.push(entry);
+    }
+
+    tracing::info!(
+        stage = "archive",
+        scanned = report.scanned,
+        archived = report.archived.len(),
+        dry_run,
+        "artifact archival pass"
+    );
+    Ok(report)
+}
+
+/// Restores archived artifacts to the default tier and returns how many moved.
+pub fn restore_archived_artifacts(
+    store: &MindStore,
+    artifact_ids: &[String],
+) -> Result<usize, StorageError> {
+    let mut restored = 0;
+    for artifact_id in artifact_ids {
+        if store.restore_archived_artifact(artifact_id)? {
+            restored += 1;
+        }
+    }
+    Ok(restored)
+}
+
+fn artifact_importance_bps(
+    store: &MindStore,
+    artifact: &StoredArtifact,
+) -> Result<u16, StorageError> {
+    let mut importance = match artifact.kind.as_str() {
+        "t2" => T2_BASE_IMPORTANCE_BPS,
+        _ => T1_BASE_IMPORTANCE_BPS,
+    };
+    let trace_refs = (artifact.trace_ids.len() as u32).min(TRACE_IMPORTANCE_MAX_REFS);
+    importance += trace_refs * TRACE_IMPORTANCE_BPS;
+    if !store
+        .artifact_task_links_for_artifact(&artifact.artifact_id)?
+        .is_empty()
+    {
+        importance += TASK_LINK_IMPORTANCE_BPS;
+    }
+    Ok(importance.min(10_000) as u16)
+}
+
+pub(crate) fn forgetting_curve_retention_bps(
+    importance_bps: u16,
+    age_hours: f64,
+    half_life_hours: u32,
+) -> u16 {
+    if half_life_hours == 0 {
+        return 0;
+    }
+    let decay = 0.5_f64.powf(age_hours.max(0.0) / f64::from(half_life_hours));
+    (f64::from(importance_bps) * decay).round() as u16
+}
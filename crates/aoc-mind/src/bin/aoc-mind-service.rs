@@ -6,17 +6,19 @@ use aoc_core::{
 use aoc_mind::{
     compile_mind_context_pack, compile_mind_evidence_pack, compile_mind_provenance_export,
     compile_mnemopi_candidate_pack, default_pi_session_root, discover_latest_pi_session_file,
-    mind_progress_for_conversation, open_project_store, prepare_session_finalize_execution,
-    read_mind_service_health_snapshot, read_mind_service_lease, summarize_mind_service_status,
+    list_mind_pins, mind_progress_for_conversation, open_project_store, pin_mind_memory,
+    prepare_session_finalize_execution, read_mind_service_health_snapshot, read_mind_service_lease,
+    restore_archived_artifacts, run_artifact_archival, summarize_mind_service_status,
     sync_latest_pi_session_into_project_store, sync_session_file_into_project_store,
-    try_parse_mind_context_pack_mode, try_parse_mind_evidence_pack_mode, DistillationConfig,
-    MindContextPackProfile, MindContextPackRequest, MindEvidencePackRequest, MindProjectPaths,
-    MindRuntimeConfig, MindRuntimeCore, MindServiceHealthSnapshot,
-    SessionFinalizePreparationOutcome,
+    try_parse_mind_context_pack_mode, try_parse_mind_evidence_pack_mode, unpin_mind_memory,
+    ArchivalPolicy, DistillationConfig, MindContextPackProfile, MindContextPackRequest,
+    MindEvidencePackRequest, MindPinTarget, MindProjectPaths, MindRuntimeConfig, MindRuntimeCore,
+    MindServiceHealthSnapshot, SessionFinalizePreparationOutcome,
 };
+use aoc_storage::{ArchivedArtifact, MindPin, MindStore};
 use clap::{Parser, Subcommand};
 use serde_json::json;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::Duration;
 
@@ -208,6 +210,72 @@ enum Command {
         #[arg(long)]
         json: bool,
     },
+    /// Pin an artifact, aoc-mem decision, or note so it is always included in context packs.
+    Pin {
+        #[arg(long)]
+        project_root: PathBuf,
+        #[arg(long, conflicts_with_all = ["decision_id", "note"])]
+        artifact_id: Option<String>,
+        #[arg(long, conflicts_with = "note")]
+        decision_id: Option<String>,
+        #[arg(long)]
+        note: Option<String>,
+        #[arg(long)]
+        reason: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Remove a pinned memory by pin id.
+    Unpin {
+        #[arg(long)]
+        project_root: PathBuf,
+        #[arg(long)]
+        pin_id: String,
+        #[arg(long)]
+        json: bool,
+    },
+    /// List pinned memories for a project.
+    Pins {
+        #[arg(long)]
+        project_root: PathBuf,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Move aged, low-retention artifacts into the archived tier.
+    Archive {
+        #[arg(long)]
+        project_root: PathBuf,
+        #[arg(long)]
+        min_age_hours: Option<u32>,
+        #[arg(long)]
+        half_life_hours: Option<u32>,
+        #[arg(long)]
+        retention_floor_bps: Option<u16>,
+        #[arg(long)]
+        dry_run: bool,
+        #[arg(long)]
+        json: bool,
+    },
+    /// List archived artifacts, optionally for a single conversation.
+    Archived {
+        #[arg(long)]
+        project_root: PathBuf,
+        #[arg(long)]
+        conversation_id: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Restore archived artifacts to the default retrieval tier.
+    Restore {
+        #[arg(long)]
+        project_root: PathBuf,
+        #[arg(long = "artifact-id", required_unless_present = "all")]
+        artifact_ids: Vec<String>,
+        #[arg(long, conflicts_with = "artifact_ids")]
+        all: bool,
+        #[arg(long)]
+        json: bool,
+    },
     /// Finalize the current project-scoped Mind session slice without Pulse/wrapper transport.
     FinalizeSession {
         #[arg(long)]
@@ -370,6 +438,55 @@ fn main() {
             },
             json,
         ),
+        Command::Pin {
+            project_root,
+            artifact_id,
+            decision_id,
+            note,
+            reason,
+            json,
+        } => {
+            let target = artifact_id
+                .map(MindPinTarget::Artifact)
+                .or(decision_id.map(MindPinTarget::Decision))
+                .or(note.map(MindPinTarget::Note));
+            run_pin(&project_root, target, reason, json)
+        }
+        Command::Unpin {
+            project_root,
+            pin_id,
+            json,
+        } => run_unpin(&project_root, &pin_id, json),
+        Command::Pins { project_root, json } => run_pins(&project_root, json),
+        Command::Archive {
+            project_root,
+            min_age_hours,
+            half_life_hours,
+            retention_floor_bps,
+            dry_run,
+            json,
+        } => {
+            let defaults = ArchivalPolicy::default();
+            let policy = ArchivalPolicy {
+                min_age_hours: min_age_hours.unwrap_or(defaults.min_age_hours),
+                half_life_hours: half_life_hours.unwrap_or(defaults.half_life_hours),
+                retention_floor_bps: retention_floor_bps
+                    .unwrap_or(defaults.retention_floor_bps)
+                    .min(10_000),
+            };
+            run_archive(&project_root, policy, dry_run, json)
+        }
+        Command::Archived {
+            project_root,
+            conversation_id,
+            json,
+        } => run_archived(&project_root, conversation_id.as_deref(), json),
+        Command::Restore {
+            project_root,
+            artifact_ids,
+            all,
+            json,
+        } => run_restore(&project_root, artifact_ids, all, json),
         Command::FinalizeSession {
             project_root,
             session_id,
@@ -1012,6 +1129,7 @@ fn build_runtime(
         t3_lock_path: paths.t3_lock_path,
         debounce_run_ms: 250,
         t3_max_attempts: 3,
+        clock: aoc_storage::system_clock(),
     })
 }
 
@@ -1363,6 +1481,261 @@ fn run_provenance_query(
     }
 }
 
+fn pin_json(pin: &MindPin) -> serde_json::Value {
+    json!({
+        "pin_id": pin.pin_id,
+        "scope_key": pin.scope_key,
+        "target_kind": pin.target_kind.as_str(),
+        "target_id": pin.target_id,
+        "text": pin.text,
+        "reason": pin.reason,
+        "pinned_at": pin.pinned_at.to_rfc3339(),
+    })
+}
+
+fn open_service_store(project_root: &Path, label: &str, as_json: bool) -> Option<MindStore> {
+    match open_project_store(project_root, "standalone", "service", None) {
+        Ok(opened) => Some(opened.store),
+        Err(err) => {
+            if as_json {
+                print_json(
+                    json!({ "ok": false, "error": format!("mind store open failed: {err}") }),
+                );
+            } else {
+                eprintln!("{label} failed: mind store open failed: {err}");
+            }
+            None
+        }
+    }
+}
+
+fn run_pin(
+    project_root: &Path,
+    target: Option<MindPinTarget>,
+    reason: Option<String>,
+    as_json: bool,
+) -> i32 {
+    let Some(target) = target else {
+        let err = "pin requires one of --artifact-id, --decision-id, or --note";
+        if as_json {
+            print_json(json!({ "ok": false, "error": err }));
+        } else {
+            eprintln!("pin failed: {err}");
+        }
+        return 2;
+    };
+    let Some(store) = open_service_store(project_root, "pin", as_json) else {
+        return 1;
+    };
+    match pin_mind_memory(
+        &store,
+        &project_root.display().to_string(),
+        target,
+        reason,
+        chrono::Utc::now(),
+    ) {
+        Ok(pin) => {
+            if as_json {
+                print_json(json!({ "ok": true, "pin": pin_json(&pin) }));
+            } else {
+                println!("pinned: {}", pin.pin_id);
+            }
+            0
+        }
+        Err(err) => {
+            if as_json {
+                print_json(json!({ "ok": false, "error": err }));
+            } else {
+                eprintln!("pin failed: {err}");
+            }
+            1
+        }
+    }
+}
+
+fn run_unpin(project_root: &Path, pin_id: &str, as_json: bool) -> i32 {
+    let Some(store) = open_service_store(project_root, "unpin", as_json) else {
+        return 1;
+    };
+    match unpin_mind_memory(&store, pin_id) {
+        Ok(removed) => {
+            if as_json {
+                print_json(json!({ "ok": true, "pin_id": pin_id, "removed": removed }));
+            } else if removed {
+                println!("unpinned: {pin_id}");
+            } else {
+                println!("not pinned: {pin_id}");
+            }
+            0
+        }
+        Err(err) => {
+            if as_json {
+                print_json(json!({ "ok": false, "error": err }));
+            } else {
+                eprintln!("unpin failed: {err}");
+            }
+            1
+        }
+    }
+}
+
+fn run_pins(project_root: &Path, as_json: bool) -> i32 {
+    let Some(store) = open_service_store(project_root, "pins", as_json) else {
+        return 1;
+    };
+    match list_mind_pins(&store, &project_root.display().to_string()) {
+        Ok(pins) => {
+            if as_json {
+                print_json(json!({
+                    "ok": true,
+                    "pins": pins.iter().map(pin_json).collect::<Vec<_>>(),
+                }));
+            } else if pins.is_empty() {
+                println!("no pinned memories");
+            } else {
+                for pin in &pins {
+                    println!("{}\t{}\t{}", pin.pin_id, pin.target_kind.as_str(), pin.text);
+                }
+            }
+            0
+        }
+        Err(err) => {
+            if as_json {
+                print_json(json!({ "ok": false, "error": err }));
+            } else {
+                eprintln!("pins failed: {err}");
+            }
+            1
+        }
+    }
+}
+
+fn archived_artifact_json(entry: &ArchivedArtifact) -> serde_json::Value {
+    json!({
+        "artifact_id": entry.artifact_id,
+        "conversation_id": entry.conversation_id,
+        "kind": entry.kind,
+        "retention_bps": entry.retention_bps,
+        "reason": entry.reason,
+        "archived_at": entry.archived_at.to_rfc3339(),
+    })
+}
+
+fn print_store_error(label: &str, err: impl std::fmt::Display, as_json: bool) {
+    if as_json {
+        print_json(json!({ "ok": false, "error": err.to_string() }));
+    } else {
+        eprintln!("{label} failed: {err}");
+    }
+}
+
+fn run_archive(project_root: &Path, policy: ArchivalPolicy, dry_run: bool, as_json: bool) -> i32 {
+    let Some(store) = open_service_store(project_root, "archive", as_json) else {
+        return 1;
+    };
+    match run_artifact_archival(&store, policy, chrono::Utc::now(), dry_run) {
+        Ok(report) => {
+            if as_json {
+                print_json(json!({
+                    "ok": true,
+                    "dry_run": report.dry_run,
+                    "scanned": report.scanned,
+                    "exempt": report.exempt,
+                    "retained": report.retained,
+                    "archived": report.archived.iter().map(archived_artifact_json).collect::<Vec<_>>(),
+                }));
+            } else {
+                let verb = if report.dry_run {
+                    "would archive"
+                } else {
+                    "archived"
+                };
+                println!(
+                    "{verb}: {} (scanned={} exempt={} retained={})",
+                    report.archived.len(),
+                    report.scanned,
+                    report.exempt,
+                    report.retained
+                );
+                for entry in &report.archived {
+                    println!("  {}\t{}", entry.artifact_id, entry.reason);
+                }
+            }
+            0
+        }
+        Err(err) => {
+            print_store_error("archive", err, as_json);
+            1
+        }
+    }
+}
+
+fn run_archived(project_root: &Path, conversation_id: Option<&str>, as_json: bool) -> i32 {
+    let Some(store) = open_service_store(project_root, "archived", as_json) else {
+        return 1;
+    };
+    match store.archived_artifacts(conversation_id) {
+        Ok(entries) => {
+            if as_json {
+                print_json(json!({
+                    "ok": true,
+                    "archived": entries.iter().map(archived_artifact_json).collect::<Vec<_>>(),
+                }));
+            } else if entries.is_empty() {
+                println!("no archived artifacts");
+            } else {
+                for entry in &entries {
+                    println!(
+                        "{}\t{}\t{}\t{}",
+                        entry.artifact_id,
+                        entry.kind,
+                        entry.conversation_id,
+                        entry.archived_at.to_rfc3339()
+                    );
+                }
+            }
+            0
+        }
+        Err(err) => {
+            print_store_error("archived", err, as_json);
+            1
+        }
+    }
+}
+
+fn run_restore(project_root: &Path, artifact_ids: Vec<String>, all: bool, as_json: bool) -> i32 {
+    let Some(store) = open_service_store(project_root, "restore", as_json) else {
+        return 1;
+    };
+    let artifact_ids = if all {
+        match store.archived_artifacts(None) {
+            Ok(entries) => entries.into_iter().map(|entry| entry.artifact_id).collect(),
+            Err(err) => {
+                print_store_error("restore", err, as_json);
+                return 1;
+            }
+        }
+    } else {
+        artifact_ids
+    };
+    match restore_archived_artifacts(&store, &artifact_ids) {
+        Ok(restored) => {
+            if as_json {
+                print_json(
+                    json!({ "ok": true, "requested": artifact_ids.len(), "restored": restored }),
+                );
+            } else {
+                println!("restored: {restored}/{}", artifact_ids.len());
+            }
+            0
+        }
+        Err(err) => {
+            print_store_error("restore", err, as_json);
+            1
+        }
+    }
+}
+
 fn ensure_safe_export_text(payload: &str, label: &str) -> Result<(), String> {
     if text_contains_unredacted_secret(payload) {
         return Err(format!(
@@ -0,0 +1,165 @@
+use aoc_mind::{
+    daemon_socket_path, send_daemon_request, DaemonRequest, DaemonStage, MindDaemon,
+    MindDaemonConfig, MindPipeline,
+};
+use aoc_storage::SnapshotRotation;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::time::Duration;
+
+const DEFAULT_AGENT_ID: &str = "aoc-mind-daemon";
+
+#[derive(Debug, Parser)]
+#[command(name = "aocd")]
+#[command(about = "Run the project Mind pipeline unattended and control it over a unix socket")]
+struct Args {
+    #[arg(long, global = true)]
+    project_root: Option<PathBuf>,
+    /// Control socket; defaults to `aocd.sock` under the project Mind runtime root.
+    #[arg(long, global = true)]
+    socket: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run the daemon in the foreground (the default).
+    Run {
+        #[arg(long, default_value_t = 5_000)]
+        interval_ms: u64,
+        /// Session directory to watch instead of AOC_PI_SESSION_DIR / the Pi default.
+        #[arg(long)]
+        session_dir: Option<PathBuf>,
+        #[arg(long, default_value = "standalone")]
+        session_id: String,
+        #[arg(long, default_value = "service")]
+        pane_id: String,
+        #[arg(long, default_value = DEFAULT_AGENT_ID)]
+        agent_id: String,
+        /// Keep daily snapshots of the project store in this directory.
+        #[arg(long)]
+        snapshot_dir: Option<PathBuf>,
+        /// Daily snapshots to keep under --snapshot-dir.
+        #[arg(long, default_value_t = 7)]
+        keep_snapshots: usize,
+    },
+    /// Print daemon status.
+    Status,
+    /// Stop timed ticks; control requests and triggers still run.
+    Pause,
+    Resume,
+    /// Run one tick now.
+    Trigger {
+        /// Stage to run (repeatable); all stages when omitted.
+        #[arg(long = "stage", value_parser = parse_stage)]
+        stages: Vec<DaemonStage>,
+    },
+    /// Rebuild the runtime and re-resolve watched session directories.
+    Reload,
+    /// Ask the daemon to exit.
+    Stop,
+}
+
+fn parse_stage(value: &str) -> Result<DaemonStage, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|_| format!("unknown stage '{value}' (ingest, observer, reflector, t3)"))
+}
+
+fn main() {
+    let args = Args::parse();
+    let project_root = match args.project_root.clone() {
+        Some(root) => root,
+        None => match std::env::current_dir() {
+            Ok(root) => root,
+            Err(err) => {
+                eprintln!("aocd: cannot resolve project root: {err}");
+                std::process::exit(1);
+            }
+        },
+    };
+    let socket = args
+        .socket
+        .clone()
+        .unwrap_or_else(|| daemon_socket_path(&project_root));
+
+    let request = match args.command {
+        None => run_daemon(
+            socket,
+            5_000,
+            MindDaemonConfig {
+                project_root,
+                session_id: "standalone".to_string(),
+                pane_id: "service".to_string(),
+                agent_id: DEFAULT_AGENT_ID.to_string(),
+                session_root: None,
+                clock: aoc_storage::system_clock(),
+                snapshots: None,
+            },
+        ),
+        Some(Command::Run {
+            interval_ms,
+            session_dir,
+            session_id,
+            pane_id,
+            agent_id,
+            snapshot_dir,
+            keep_snapshots,
+        }) => run_daemon(
+            socket,
+            interval_ms,
+            MindDaemonConfig {
+                project_root,
+                session_id,
+                pane_id,
+                agent_id,
+                session_root: session_dir,
+                clock: aoc_storage::system_clock(),
+                snapshots: snapshot_dir.map(|dir| SnapshotRotation::new(dir, keep_snapshots)),
+            },
+        ),
+        Some(Command::Status) => DaemonRequest::Status,
+        Some(Command::Pause) => DaemonRequest::Pause,
+        Some(Command::Resume) => DaemonRequest::Resume,
+        Some(Command::Trigger { stages }) => DaemonRequest::Trigger { stages },
+        Some(Command::Reload) => DaemonRequest::Reload,
+        Some(Command::Stop) => DaemonRequest::Shutdown,
+    };
+
+    match send_daemon_request(&socket, &request) {
+        Ok(response) => {
+            match serde_json::to_string_pretty(&response) {
+                Ok(body) => println!("{body}"),
+                Err(err) => eprintln!("aocd: {err}"),
+            }
+            if !response.ok {
+                std::process::exit(1);
+            }
+        }
+        Err(err) => {
+            eprintln!("aocd: no daemon on {}: {err}", socket.display());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Runs until a stop request arrives, then exits the process.
+fn run_daemon(socket: PathBuf, interval_ms: u64, config: MindDaemonConfig) -> ! {
+    let project_root = config.project_root.clone();
+    let pipeline = match MindPipeline::new(config) {
+        Ok(pipeline) => pipeline,
+        Err(err) => {
+            eprintln!("aocd: {err}");
+            std::process::exit(1);
+        }
+    };
+    let interval = Duration::from_millis(interval_ms.max(250));
+    eprintln!("aocd: listening on {}", socket.display());
+    match MindDaemon::new(pipeline, project_root, interval).run(&socket) {
+        Ok(()) => std::process::exit(0),
+        Err(err) => {
+            eprintln!("aocd: {err}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,315 @@
+//! Short-circuiting a semantic provider that keeps failing.
+//!
+//! A [`CircuitBreaker`] counts consecutive provider failures (timeouts and
+//! provider errors, after retries) and opens once `failure_threshold` is
+//! reached. While open, the distillers skip the call and write their
+//! deterministic output. After `cooldown_ms` one trial call is let through
+//! (half-open): success closes the breaker, another failure reopens it.
+//! Every state change is kept as a [`BreakerTransition`] until taken, so the
+//! observer sidecar can put it in the feed.
+
+use aoc_core::mind_contracts::SemanticFailureKind;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// `[observer.breaker]` in aoc.toml.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CircuitBreakerConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Consecutive failed calls that open the breaker.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// How long an open breaker skips calls before a trial call.
+    #[serde(default = "default_cooldown_ms")]
+    pub cooldown_ms: u64,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_threshold: default_failure_threshold(),
+            cooldown_ms: default_cooldown_ms(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+fn default_cooldown_ms() -> u64 {
+    60_000
+}
+
+impl CircuitBreakerConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.enabled && self.failure_threshold == 0 {
+            return Err("failure_threshold must be > 0 when the breaker is enabled".to_string());
+        }
+        if self.enabled && self.cooldown_ms == 0 {
+            return Err("cooldown_ms must be > 0 when the breaker is enabled".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl BreakerState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakerTransition {
+    pub provider_name: String,
+    pub from: BreakerState,
+    pub to: BreakerState,
+    pub consecutive_failures: u32,
+    pub at: DateTime<Utc>,
+    pub reason: String,
+}
+
+#[derive(Debug)]
+struct BreakerInner {
+    state: BreakerState,
+    consecutive_failures: u32,
+    opened_at: Option<DateTime<Utc>>,
+    transitions: Vec<BreakerTransition>,
+}
+
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    provider_name: String,
+    config: CircuitBreakerConfig,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(provider_name: impl Into<String>, config: CircuitBreakerConfig) -> Self {
+        Self {
+            provider_name: provider_name.into(),
+            config,
+            inner: Mutex::new(BreakerInner {
+                state: BreakerState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                transitions: Vec::new(),
+            }),
+        }
+    }
+
+    pub fn state(&self) -> BreakerState {
+        self.lock().state
+    }
+
+    /// Why a call must not be made at `now`, or `None` if it may. An open
+    /// breaker whose cooldown has passed turns half-open and allows the call.
+    pub fn refusal(&self, now: DateTime<Utc>) -> Option<String> {
+        if !self.config.enabled {
+            return None;
+        }
+        let mut inner = self.lock();
+        if inner.state != BreakerState::Open {
+            return None;
+        }
+        let opened_at = inner.opened_at.unwrap_or(now);
+        let retry_at = opened_at
+            .checked_add_signed(chrono::Duration::milliseconds(
+                i64::try_from(self.config.cooldown_ms).unwrap_or(i64::MAX),
+            ))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        if now >= retry_at {
+            let reason = format!(
+                "cooldown of {}ms elapsed; trial call",
+                self.config.cooldown_ms
+            );
+            self.transition(&mut inner, BreakerState::HalfOpen, now, reason);
+            return None;
+        }
+        Some(format!(
+            "circuit breaker open for {} after {} consecutive failures; retrying after {}",
+            self.provider_name,
+            inner.consecutive_failures,
+            retry_at.to_rfc3339()
+        ))
+    }
+
+    /// Records how a call that reached the provider ended. Only timeouts and
+    /// provider errors count as failures; any other answer shows the
+    /// provider is up.
+    pub fn record(&self, failure: Option<SemanticFailureKind>, now: DateTime<Utc>) {
+        if !self.config.enabled {
+            return;
+        }
+        let mut inner = self.lock();
+        let failed = matches!(
+            failure,
+            Some(SemanticFailureKind::Timeout | SemanticFailureKind::ProviderError)
+        );
+        if !failed {
+            inner.consecutive_failures = 0;
+            if inner.state != BreakerState::Closed {
+                self.transition(
+                    &mut inner,
+                    BreakerState::Closed,
+                    now,
+                    "provider answered".to_string(),
+                );
+            }
+            return;
+        }
+
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        let trips = match inner.state {
+            BreakerState::HalfOpen => true,
+            BreakerState::Closed => inner.consecutive_failures >= self.config.failure_threshold,
+            BreakerState::Open => false,
+        };
+        if trips {
+            let kind = failure.map(SemanticFailureKind::as_str).unwrap_or_default();
+            let reason = format!(
+                "{} consecutive failures, last {kind}; deterministic for {}ms",
+                inner.consecutive_failures, self.config.cooldown_ms
+            );
+            inner.opened_at = Some(now);
+            self.transition(&mut inner, BreakerState::Open, now, reason);
+        }
+    }
+
+    /// State changes since the last call, oldest first.
+    pub fn take_transitions(&self) -> Vec<BreakerTransition> {
+        std::mem::take(&mut self.lock().transitions)
+    }
+
+    fn transition(
+        &self,
+        inner: &mut BreakerInner,
+        to: BreakerState,
+        at: DateTime<Utc>,
+        reason: String,
+    ) {
+        tracing::warn!(
+            provider = %self.provider_name,
+            from = inner.state.as_str(),
+            to = to.as_str(),
+            reason = %reason,
+            "semantic circuit breaker transition"
+        );
+        inner.transitions.push(BreakerTransition {
+            provider_name: self.provider_name.clone(),
+            from: inner.state,
+            to,
+            consecutive_failures: inner.consecutive_failures,
+            at,
+            reason,
+        });
+        inner.state = to;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerInner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(ms: i64) -> DateTime<Utc> {
+        DateTime::<Utc>::from_timestamp_millis(1_760_000_000_000 + ms).expect("ts")
+    }
+
+    #[test]
+    fn breaker_opens_after_threshold_and_recovers_through_half_open() {
+        let breaker = CircuitBreaker::new(
+            "pi",
+            CircuitBreakerConfig {
+                failure_threshold: 2,
+                cooldown_ms: 1_000,
+                ..CircuitBreakerConfig::default()
+            },
+        );
+        assert_eq!(breaker.refusal(at(0)), None);
+        breaker.record(Some(SemanticFailureKind::Timeout), at(0));
+        breaker.record(Some(SemanticFailureKind::InvalidOutput), at(10));
+        breaker.record(Some(SemanticFailureKind::ProviderError), at(20));
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        breaker.record(Some(SemanticFailureKind::ProviderError), at(30));
+        assert_eq!(breaker.state(), BreakerState::Open);
+
+        let refusal = breaker.refusal(at(500)).expect("open breaker refuses");
+        assert!(refusal.contains("2 consecutive failures"), "{refusal}");
+
+        // Trial call fails: straight back to open for another cooldown.
+        assert_eq!(breaker.refusal(at(1_030)), None);
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+        breaker.record(Some(SemanticFailureKind::Timeout), at(1_040));
+        assert!(breaker.refusal(at(1_500)).is_some());
+
+        assert_eq!(breaker.refusal(at(2_040)), None);
+        breaker.record(None, at(2_050));
+        assert_eq!(breaker.state(), BreakerState::Closed);
+
+        let path = breaker
+            .take_transitions()
+            .into_iter()
+            .map(|transition| transition.to)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            path,
+            vec![
+                BreakerState::Open,
+                BreakerState::HalfOpen,
+                BreakerState::Open,
+                BreakerState::HalfOpen,
+                BreakerState::Closed,
+            ]
+        );
+        assert!(breaker.take_transitions().is_empty());
+    }
+
+    #[test]
+    fn disabled_breaker_never_refuses_and_config_rejects_zero_threshold() {
+        let breaker = CircuitBreaker::new(
+            "pi",
+            CircuitBreakerConfig {
+                enabled: false,
+                failure_threshold: 1,
+                ..CircuitBreakerConfig::default()
+            },
+        );
+        breaker.record(Some(SemanticFailureKind::Timeout), at(0));
+        assert_eq!(breaker.refusal(at(1)), None);
+        assert!(breaker.take_transitions().is_empty());
+
+        let err = CircuitBreakerConfig {
+            failure_threshold: 0,
+            ..CircuitBreakerConfig::default()
+        }
+        .validate()
+        .expect_err("zero threshold");
+        assert!(err.contains("failure_threshold"), "{err}");
+    }
+}
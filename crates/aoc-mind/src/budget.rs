@@ -0,0 +1,254 @@
+//! Daily semantic spend caps shared by every session on a store.
+//!
+//! [`BudgetManager`] charges each provider call to `semantic_budget_ledger`
+//! under its UTC day and provider, and refuses a call whose projected spend
+//! would go past `[observer.budget]`: the all-providers cap or the provider's
+//! own. A refused call falls back to the deterministic distiller, like a call
+//! rejected by the per-call guardrails.
+
+use aoc_storage::{
+    MindStore, SemanticBudgetCharge, SemanticBudgetDay, StorageError, SEMANTIC_BUDGET_ALL_PROVIDERS,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// `[observer.budget]` in aoc.toml. Zero leaves a dimension unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DailyBudgetConfig {
+    #[serde(default)]
+    pub max_tokens_per_day: u64,
+    #[serde(default)]
+    pub max_cost_micros_per_day: u64,
+    /// Caps for a single provider, keyed by `profile.provider_name`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub providers: BTreeMap<String, ProviderDailyBudget>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProviderDailyBudget {
+    #[serde(default)]
+    pub max_tokens_per_day: u64,
+    #[serde(default)]
+    pub max_cost_micros_per_day: u64,
+}
+
+impl DailyBudgetConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        for provider in self.providers.keys() {
+            if provider.trim().is_empty() || provider == SEMANTIC_BUDGET_ALL_PROVIDERS {
+                return Err(format!("invalid provider name `{provider}`"));
+            }
+        }
+        Ok(())
+    }
+
+    /// `(tokens, cost_micros)` cap for a ledger row.
+    fn caps(&self, provider_name: &str) -> (u64, u64) {
+        if provider_name == SEMANTIC_BUDGET_ALL_PROVIDERS {
+            return (self.max_tokens_per_day, self.max_cost_micros_per_day);
+        }
+        self.providers
+            .get(provider_name)
+            .map(|budget| (budget.max_tokens_per_day, budget.max_cost_micros_per_day))
+            .unwrap_or_default()
+    }
+}
+
+/// Spend projected for calls admitted alongside each other but not yet
+/// charged, such as one observer wave's concurrent calls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BudgetReservation {
+    pub tokens: u64,
+    pub cost_micros: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BudgetDecision {
+    /// The call fits; its projection was added to the reservation.
+    Admit,
+    /// The call fits today's budget alone but not next to the reserved
+    /// calls; retry once they are charged.
+    Defer,
+    /// The call does not fit today's budget; the reason was counted.
+    Refuse(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BudgetManager {
+    config: DailyBudgetConfig,
+}
+
+impl BudgetManager {
+    pub fn new(config: DailyBudgetConfig) -> Self {
+        Self { config }
+    }
+
+    /// Whether no cap is configured at all.
+    pub fn is_unlimited(&self) -> bool {
+        self.config.max_tokens_per_day == 0
+            && self.config.max_cost_micros_per_day == 0
+            && self
+                .config
+                .providers
+                .values()
+                .all(|budget| budget.max_tokens_per_day == 0 && budget.max_cost_micros_per_day == 0)
+    }
+
+    /// Why a call to `provider_name` projected at `tokens` must not be made
+    /// today, or `None` if it fits. `cost_micros` prices the call and is only
+    /// asked when a cost cap applies. A refusal is counted in the ledger.
+    pub fn refusal(
+        &self,
+        store: &MindStore,
+        provider_name: &str,
+        tokens: u64,
+        cost_micros: impl FnOnce() -> u64,
+        now: DateTime<Utc>,
+    ) -> Result<Option<String>, StorageError> {
+        let mut reserved = BudgetReservation::default();
+        match self.admit(
+            store,
+            provider_name,
+            tokens,
+            cost_micros,
+            &mut reserved,
+            now,
+        )? {
+            BudgetDecision::Refuse(reason) => Ok(Some(reason)),
+            BudgetDecision::Admit | BudgetDecision::Defer => Ok(None),
+        }
+    }
+
+    /// [`Self::refusal`] for a call made alongside others: the call must also
+    /// fit next to the spend already in `reserved`, and its projection is
+    /// added there once admitted. Only a call that does not fit on its own
+    /// is refused and counted.
+    pub fn admit(
+        &self,
+        store: &MindStore,
+        provider_name: &str,
+        tokens: u64,
+        cost_micros: impl FnOnce() -> u64,
+        reserved: &mut BudgetReservation,
+        now: DateTime<Utc>,
+    ) -> Result<BudgetDecision, StorageError> {
+        if self.is_unlimited() {
+            return Ok(BudgetDecision::Admit);
+        }
+        let status = self.status(store, now)?;
+        let applies = |row: &SemanticBudgetDay| {
+            row.provider_name == SEMANTIC_BUDGET_ALL_PROVIDERS || row.provider_name == provider_name
+        };
+        let cost_micros = if status
+            .iter()
+            .any(|row| applies(row) && row.budget_cost_micros > 0)
+        {
+            cost_micros()
+        } else {
+            0
+        };
+        let exhausted = status
+            .iter()
+            .find(|row| applies(row) && row.would_exceed(tokens, cost_micros));
+        let Some(row) = exhausted else {
+            let with_reserved = (
+                tokens.saturating_add(reserved.tokens),
+                cost_micros.saturating_add(reserved.cost_micros),
+            );
+            if status
+                .iter()
+                .any(|row| applies(row) && row.would_exceed(with_reserved.0, with_reserved.1))
+            {
+                return Ok(BudgetDecision::Defer);
+            }
+            (reserved.tokens, reserved.cost_micros) = with_reserved;
+            return Ok(BudgetDecision::Admit);
+        };
+        let scope = if row.provider_name == SEMANTIC_BUDGET_ALL_PROVIDERS {
+            "daily semantic budget".to_string()
+        } else {
+            format!("daily budget for {provider_name}")
+        };
+        let reason = format!(
+            "{scope} exceeded: {} tokens / {} micros spent, call needs {tokens} / {cost_micros}",
+            row.spent_tokens, row.spent_cost_micros
+        );
+        self.write(store, provider_name, 0, 0, true, now)?;
+        Ok(BudgetDecision::Refuse(reason))
+    }
+
+    /// Adds a call's spend to today's row for `provider_name` and the
+    /// all-providers row.
+    pub fn charge(
+        &self,
+        store: &MindStore,
+        provider_name: &str,
+        tokens: u64,
+        cost_micros: u64,
+        now: DateTime<Utc>,
+    ) -> Result<(), StorageError> {
+        self.write(store, provider_name, tokens, cost_micros, false, now)
+    }
+
+    /// Today's spend against the configured caps, the all-providers row
+    /// first. Configured providers appear even before their first call.
+    pub fn status(
+        &self,
+        store: &MindStore,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<SemanticBudgetDay>, StorageError> {
+        let day = now.date_naive();
+        let mut rows = store
+            .semantic_budget_ledger(day)?
+            .into_iter()
+            .map(|row| (row.provider_name.clone(), row))
+            .collect::<BTreeMap<_, _>>();
+        for provider_name in std::iter::once(SEMANTIC_BUDGET_ALL_PROVIDERS)
+            .chain(self.config.providers.keys().map(String::as_str))
+        {
+            rows.entry(provider_name.to_string())
+                .or_insert_with(|| SemanticBudgetDay {
+                    day,
+                    provider_name: provider_name.to_string(),
+                    ..SemanticBudgetDay::default()
+                });
+        }
+        let mut status = rows
+            .into_values()
+            .map(|mut row| {
+                (row.budget_tokens, row.budget_cost_micros) = self.config.caps(&row.provider_name);
+                row
+            })
+            .collect::<Vec<_>>();
+        status.sort_by_key(|row| row.provider_name != SEMANTIC_BUDGET_ALL_PROVIDERS);
+        Ok(status)
+    }
+
+    fn write(
+        &self,
+        store: &MindStore,
+        provider_name: &str,
+        tokens: u64,
+        cost_micros: u64,
+        rejected: bool,
+        now: DateTime<Utc>,
+    ) -> Result<(), StorageError> {
+        for row in [provider_name, SEMANTIC_BUDGET_ALL_PROVIDERS] {
+            let (budget_tokens, budget_cost_micros) = self.config.caps(row);
+            store.charge_semantic_budget(&SemanticBudgetCharge {
+                day: now.date_naive(),
+                provider_name: row.to_string(),
+                budget_tokens,
+                budget_cost_micros,
+                tokens,
+                cost_micros,
+                rejected,
+                recorded_at: now,
+            })?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,362 @@
+//! T3 project-canon synthesis from T2 reflections.
+//!
+//! [`process_t3_backlog_job`](crate::process_t3_backlog_job) promotes the
+//! latest artifact per conversation into the canon one for one. The
+//! [`CanonSynthesizer`] instead claims the same backlog jobs and folds every
+//! reflection routed to a segment into one canon entry per segment: each
+//! job that brings new reflections writes the next revision of that entry,
+//! whose evidence is all reflections seen so far plus the observations they
+//! trace, and which supersedes the revision before it. Reflections without a
+//! segment route fall under the job's active tag.
+
+use crate::{
+    normalize_text, project_canon_evidence_refs, project_canon_freshness_score, truncate_chars,
+    DetachedT3Worker, T3BacklogJobError, T3RuntimeConfig, T3RuntimeError, T3TickReport,
+    MIND_T3_CANON_SUMMARY_MAX_CHARS,
+};
+use aoc_core::mind_contracts::canonical_payload_hash;
+use aoc_storage::{CanonRevisionState, MindStore, StoredArtifact, T3BacklogJob};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Segment for reflections with neither a route nor a job tag.
+pub const CANON_FALLBACK_SEGMENT: &str = "global";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CanonSynthesisReport {
+    pub reflections: usize,
+    pub segments: usize,
+    pub revisions_written: usize,
+    /// Segments whose entry already covered every reflection in the job.
+    pub segments_unchanged: usize,
+}
+
+impl CanonSynthesisReport {
+    fn absorb(&mut self, other: &Self) {
+        self.reflections += other.reflections;
+        self.segments += other.segments;
+        self.revisions_written += other.revisions_written;
+        self.segments_unchanged += other.segments_unchanged;
+    }
+}
+
+pub struct CanonSynthesizer {
+    worker: DetachedT3Worker,
+    project_root: String,
+}
+
+impl CanonSynthesizer {
+    pub fn new(project_root: impl Into<String>, config: T3RuntimeConfig) -> Self {
+        Self {
+            worker: DetachedT3Worker::new(config),
+            project_root: project_root.into(),
+        }
+    }
+
+    /// Claims backlog jobs under the T3 lock and lease and synthesizes each.
+    pub fn run_once(
+        &self,
+        store: &MindStore,
+        now: DateTime<Utc>,
+    ) -> Result<(T3TickReport, CanonSynthesisReport), T3RuntimeError> {
+        let mut synthesis = CanonSynthesisReport::default();
+        let tick = self.worker.run_once(store, now, |store, job| {
+            let report = self
+                .synthesize_job(store, job, now)
+                .map_err(|err| err.to_string())?;
+            synthesis.absorb(&report);
+            Ok(())
+        })?;
+        Ok((tick, synthesis))
+    }
+
+    pub fn synthesize_job(
+        &self,
+        store: &MindStore,
+        job: &T3BacklogJob,
+        now: DateTime<Utc>,
+    ) -> Result<CanonSynthesisReport, T3BacklogJobError> {
+        let mut resolved = 0usize;
+        let mut by_segment = BTreeMap::<String, Vec<StoredArtifact>>::new();
+        for artifact_id in &job.artifact_refs {
+            let Some(artifact) = store.artifact_by_id(artifact_id)? else {
+                continue;
+            };
+            resolved += 1;
+            if artifact.kind != "t2" {
+                continue;
+            }
+            let segment = self.segment_for(store, job, &artifact)?;
+            by_segment.entry(segment).or_default().push(artifact);
+        }
+        if resolved == 0 {
+            return Err(T3BacklogJobError::Internal(format!(
+                "t3 backlog job {} has no resolvable artifacts",
+                job.job_id
+            )));
+        }
+
+        let mut report = CanonSynthesisReport {
+            reflections: by_segment.values().map(Vec::len).sum(),
+            segments: by_segment.len(),
+            ..CanonSynthesisReport::default()
+        };
+        for (segment, reflections) in by_segment {
+            if self.synthesize_segment(store, &segment, &reflections, now)? {
+                report.revisions_written += 1;
+            } else {
+                report.segments_unchanged += 1;
+            }
+        }
+        Ok(report)
+    }
+
+    /// Canon entry a segment's reflections are folded into.
+    pub fn entry_id_for_segment(&self, segment: &str) -> Result<String, T3BacklogJobError> {
+        let digest = canonical_payload_hash(&(self.project_root.as_str(), "segment", segment))?;
+        Ok(format!("canon:segment:{}", &digest[..16]))
+    }
+
+    fn segment_for(
+        &self,
+        store: &MindStore,
+        job: &T3BacklogJob,
+        artifact: &StoredArtifact,
+    ) -> Result<String, T3BacklogJobError> {
+        if let Some(route) = store.segment_route_for_artifact(&artifact.artifact_id)? {
+            return Ok(route.primary.segment_id);
+        }
+        Ok(job
+            .active_tag
+            .as_deref()
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .unwrap_or(CANON_FALLBACK_SEGMENT)
+            .to_lowercase())
+    }
+
+    /// Writes the segment's next revision unless the active one already
+    /// cites every reflection; returns whether it wrote one.
+    fn synthesize_segment(
+        &self,
+        store: &MindStore,
+        segment: &str,
+        reflections: &[StoredArtifact],
+        now: DateTime<Utc>,
+    ) -> Result<bool, T3BacklogJobError> {
+        let entry_id = self.entry_id_for_segment(segment)?;
+        let previous = store
+            .latest_canon_revision(&entry_id)?
+            .filter(|revision| revision.state == CanonRevisionState::Active);
+
+        let mut evidence = previous
+            .as_ref()
+            .map(|revision| revision.evidence_refs.iter().cloned().collect())
+            .unwrap_or_else(BTreeSet::new);
+        let before = evidence.len();
+        for reflection in reflections {
+            evidence.extend(project_canon_evidence_refs(store, reflection)?);
+        }
+        if previous.is_some() && evidence.len() == before {
+            return Ok(false);
+        }
+
+        // Every reflection the entry cites, new and carried over, newest first.
+        let mut cited = Vec::new();
+        for artifact_id in &evidence {
+            if let Some(artifact) = store.artifact_by_id(artifact_id)? {
+                if artifact.kind == "t2" {
+                    cited.push(artifact);
+                }
+            }
+        }
+        cited.sort_by(|left, right| {
+            right
+                .ts
+                .cmp(&left.ts)
+                .then(right.artifact_id.cmp(&left.artifact_id))
+        });
+        let Some(latest) = cited.first() else {
+            return Ok(false);
+        };
+
+        let evidence = evidence.into_iter().collect::<Vec<_>>();
+        store.upsert_canon_entry_revision(
+            &entry_id,
+            Some(segment),
+            &segment_summary(segment, &cited),
+            segment_confidence_bps(cited.len()),
+            project_canon_freshness_score(now, latest.ts),
+            None,
+            &evidence,
+            now,
+        )?;
+        tracing::debug!(
+            entry_id = %entry_id,
+            segment,
+            reflections = cited.len(),
+            "wrote canon revision"
+        );
+        Ok(true)
+    }
+}
+
+fn segment_summary(segment: &str, reflections: &[StoredArtifact]) -> String {
+    let conversations = reflections
+        .iter()
+        .map(|reflection| reflection.conversation_id.as_str())
+        .collect::<BTreeSet<_>>();
+    let previews = reflections
+        .iter()
+        .map(|reflection| normalize_text(&reflection.text))
+        .collect::<Vec<_>>();
+    truncate_chars(
+        format!(
+            "Segment {segment}: {} reflections across {} conversations. {}",
+            reflections.len(),
+            conversations.len(),
+            previews.join(" | ")
+        ),
+        MIND_T3_CANON_SUMMARY_MAX_CHARS,
+    )
+}
+
+/// Reflections start at the confidence a single promoted one gets and gain
+/// for each one that agrees on the segment.
+fn segment_confidence_bps(reflections: usize) -> u16 {
+    let boost = (reflections.saturating_sub(1) as u16)
+        .saturating_mul(300)
+        .min(1_500);
+    8_200u16.saturating_add(boost).min(10_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::mind_contracts::{RouteOrigin, SegmentCandidate, SegmentRoute};
+    use chrono::TimeZone;
+    use std::path::PathBuf;
+
+    fn ts(min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, 23, 10, min, 0).unwrap()
+    }
+
+    fn temp_lock_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "aoc-mind-canon-{name}-{}-{}.lock",
+            std::process::id(),
+            Utc::now().timestamp_millis()
+        ))
+    }
+
+    fn reflection(
+        store: &MindStore,
+        id: &str,
+        conversation: &str,
+        min: u32,
+        segment: Option<&str>,
+    ) {
+        let observation = format!("obs:{id}");
+        store
+            .insert_observation(&observation, conversation, ts(min), "observed work", &[])
+            .expect("observation");
+        store
+            .insert_reflection(
+                id,
+                conversation,
+                ts(min),
+                &format!("reflection {id}"),
+                &[observation],
+            )
+            .expect("reflection");
+        if let Some(segment) = segment {
+            store
+                .replace_segment_route(&SegmentRoute {
+                    artifact_id: id.to_string(),
+                    primary: SegmentCandidate::new(segment.to_string(), 9_000).unwrap(),
+                    secondary: Vec::new(),
+                    routed_by: RouteOrigin::Heuristic,
+                    reason: "test".to_string(),
+                    overridden_by: None,
+                })
+                .expect("route");
+        }
+    }
+
+    fn enqueue(store: &MindStore, refs: &[&str], min: u32) {
+        let refs = refs.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        store
+            .enqueue_t3_backlog_job(
+                "/repo",
+                "session-a",
+                "1",
+                Some("Mind"),
+                refs.first().map(String::as_str),
+                refs.last().map(String::as_str),
+                &refs,
+                ts(min),
+            )
+            .expect("enqueue");
+    }
+
+    #[test]
+    fn synthesizer_folds_reflections_per_segment_into_revisions() {
+        let store = MindStore::open_in_memory().expect("db");
+        let synthesizer = CanonSynthesizer::new(
+            "/repo",
+            T3RuntimeConfig::with_lock_path("project:/repo", "owner-a", temp_lock_path("fold")),
+        );
+
+        reflection(&store, "ref:a1", "conv-a", 1, Some("storage"));
+        reflection(&store, "ref:b1", "conv-b", 2, Some("storage"));
+        reflection(&store, "ref:c1", "conv-c", 3, None);
+        enqueue(&store, &["ref:a1", "ref:b1", "ref:c1", "obs:ref:a1"], 4);
+
+        let (tick, report) = synthesizer.run_once(&store, ts(5)).expect("first tick");
+        assert_eq!(tick.jobs_completed, 1);
+        assert_eq!(report.reflections, 3);
+        assert_eq!(report.segments, 2);
+        assert_eq!(report.revisions_written, 2);
+
+        let storage_entry = synthesizer.entry_id_for_segment("storage").unwrap();
+        let first = store
+            .latest_canon_revision(&storage_entry)
+            .unwrap()
+            .expect("storage entry");
+        assert_eq!(first.revision, 1);
+        assert_eq!(first.topic.as_deref(), Some("storage"));
+        assert!(first.summary.starts_with(
+            "Segment storage: 2 reflections across 2 conversations. reflection ref:b1"
+        ));
+        assert_eq!(
+            first.evidence_refs,
+            vec!["obs:ref:a1", "obs:ref:b1", "ref:a1", "ref:b1"]
+        );
+        let tagged = synthesizer.entry_id_for_segment("mind").unwrap();
+        assert!(store.latest_canon_revision(&tagged).unwrap().is_some());
+
+        reflection(&store, "ref:a2", "conv-a", 6, Some("storage"));
+        enqueue(&store, &["ref:a2"], 7);
+        enqueue(&store, &["ref:b1"], 8);
+        let (_, report) = synthesizer.run_once(&store, ts(9)).expect("second tick");
+        assert_eq!(report.revisions_written, 1);
+        assert_eq!(report.segments_unchanged, 1);
+
+        let history = store.canon_entry_revisions(&storage_entry).unwrap();
+        assert_eq!(history.len(), 2);
+        let latest = history
+            .iter()
+            .find(|rev| rev.revision == 2)
+            .expect("revision 2");
+        assert_eq!(latest.state, CanonRevisionState::Active);
+        assert_eq!(
+            latest.supersedes_entry_id.as_deref(),
+            Some(storage_entry.as_str())
+        );
+        assert!(latest.summary.contains("3 reflections"));
+        assert!(latest.evidence_refs.contains(&"ref:a1".to_string()));
+        assert!(latest.evidence_refs.contains(&"ref:a2".to_string()));
+        let superseded = history.iter().find(|rev| rev.revision == 1).unwrap();
+        assert_eq!(superseded.state, CanonRevisionState::Superseded);
+    }
+}
@@ -1,5 +1,12 @@
-use crate::{load_latest_session_export_manifest, project_scope_key, SessionExportManifest};
+use crate::{
+    load_latest_session_export_manifest,
+    pins::{list_mind_pins, render_pinned_line},
+    project_scope_key,
+    retrieval::{elapsed_us, record_retrieval_metrics},
+    SessionExportManifest,
+};
 use aoc_core::{
+    insight_contracts::{RetrievalBudgetUsage, RetrievalMetrics, RetrievalStageTimings},
     mind_contracts::{compose_context_pack, ContextLayer, ContextPackInput},
     mind_observer_feed::MindInjectionTriggerKind,
     provenance_contracts::{
@@ -16,6 +23,7 @@ use std::{
     collections::{BTreeSet, HashSet},
     path::{Path, PathBuf},
     process::Command,
+    time::Instant,
 };
 
 const MIND_CONTEXT_PACK_SCHEMA_VERSION: u16 = 1;
@@ -24,6 +32,8 @@ const MIND_CONTEXT_PACK_EXPANDED_MAX_LINES: usize = 48;
 const MIND_CONTEXT_PACK_COMPACT_SOURCE_MAX_LINES: usize = 5;
 const MIND_CONTEXT_PACK_EXPANDED_SOURCE_MAX_LINES: usize = 10;
 
+pub const MIND_CONTEXT_PACK_PIPELINE: &str = "context_pack";
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum MindContextPackMode {
@@ -76,6 +86,8 @@ pub struct MindContextPack {
     pub sections: Vec<MindContextPackSection>,
     pub citations: Vec<MindContextPackCitation>,
     pub generated_at: String,
+    #[serde(default)]
+    pub metrics: RetrievalMetrics,
 }
 
 #[derive(Debug, Clone)]
@@ -259,6 +271,7 @@ pub fn compile_mind_context_pack(
     request: MindContextPackRequest,
     overrides: Option<&MindContextPackSourceOverrides>,
 ) -> Result<MindContextPack, String> {
+    let started_at = Instant::now();
     let active_tag = request
         .active_tag
         .as_deref()
@@ -290,6 +303,20 @@ pub fn compile_mind_context_pack(
     let mut sections = Vec::new();
     let mut citations = Vec::new();
 
+    // Pins bypass the per-source line cap: they are the content the caller
+    // has asked to keep regardless of what else competes for the budget.
+    if let Some(pins) = store.and_then(|store| list_mind_pins(store, project_root).ok()) {
+        push_context_pack_section(
+            &mut sections,
+            &mut citations,
+            ContextLayer::Pinned,
+            "pinned",
+            "Pinned memory",
+            "mind:pins",
+            (pins.iter().map(render_pinned_line).collect(), false),
+        );
+    }
+
     if let Some(text) = overrides
         .and_then(|value| value.aoc_mem.clone())
         .or_else(|| load_context_cli_output(project_root, "aoc-mem", &["read"]))
@@ -433,7 +460,9 @@ pub fn compile_mind_context_pack(
     if sections.is_empty() {
         return Err("no context-pack sources available".to_string());
     }
+    let candidate_fetch_us = elapsed_us(started_at);
 
+    let rerank_started_at = Instant::now();
     let inputs = sections
         .iter()
         .map(|section| ContextPackInput {
@@ -443,7 +472,32 @@ pub fn compile_mind_context_pack(
         .collect::<Vec<_>>();
     let composed = compose_context_pack(&inputs, line_budget)
         .map_err(|err| format!("compose context pack failed: {err}"))?;
+    let rerank_us = elapsed_us(rerank_started_at);
+
+    let assembly_started_at = Instant::now();
     let section_truncated = sections.iter().any(|section| section.truncated);
+    let truncated = composed.truncated || section_truncated;
+    let metrics = RetrievalMetrics {
+        pipeline: MIND_CONTEXT_PACK_PIPELINE.to_string(),
+        timings: RetrievalStageTimings {
+            candidate_fetch_us,
+            rerank_us,
+            assembly_us: elapsed_us(assembly_started_at),
+            total_us: elapsed_us(started_at),
+        },
+        budget: RetrievalBudgetUsage {
+            candidates_considered: sections.len(),
+            hit_budget: sections.len(),
+            hits_returned: context_pack_sections_emitted(&inputs, composed.lines.len()),
+            line_budget,
+            lines_used: composed.lines.len(),
+            truncated,
+        },
+    };
+    if let Some(store) = store {
+        // Best effort: a metrics write must never fail the handshake itself.
+        let _ = record_retrieval_metrics(store, project_root, &metrics);
+    }
 
     Ok(MindContextPack {
         schema_version: MIND_CONTEXT_PACK_SCHEMA_VERSION,
@@ -453,11 +507,12 @@ pub fn compile_mind_context_pack(
         active_tag,
         reason,
         line_budget,
-        truncated: composed.truncated || section_truncated,
+        truncated,
         rendered_lines: composed.lines,
         sections,
         citations,
         generated_at: Utc::now().to_rfc3339(),
+        metrics,
     })
 }
 pub fn try_parse_mind_evidence_pack_mode(
@@ -658,12 +713,14 @@ fn evidence_matches(text: &str, terms: &[String], mode: MindEvidencePackMode) ->
 
 fn evidence_kind_for_section(section: &MindContextPackSection) -> String {
     match section.source_id.as_str() {
+        "pinned" => "pinned",
         "aoc_mem" => "aoc_mem",
         "aoc_stm" => "stm",
         "t3_handshake" | "t3_canon" => "t3_canon",
         "session_t2" => "t2_reflection",
         "session_t1" => "t1_observation",
         _ => match section.layer {
+            ContextLayer::Pinned => "pinned",
             ContextLayer::AocMem => "aoc_mem",
             ContextLayer::AocStm => "stm",
             ContextLayer::AocMind => "provenance_node",
@@ -674,6 +731,7 @@ fn evidence_kind_for_section(section: &MindContextPackSection) -> String {
 
 fn evidence_confidence_for_section(section: &MindContextPackSection) -> f32 {
     match section.source_id.as_str() {
+        "pinned" => 0.90,
         "t3_handshake" | "t3_canon" => 0.84,
         "session_t2" => 0.76,
         "session_t1" => 0.62,
@@ -765,6 +823,23 @@ fn push_context_pack_section(
     });
 }
 
+fn context_pack_sections_emitted(inputs: &[ContextPackInput], lines_used: usize) -> usize {
+    let mut ordered = inputs.iter().enumerate().collect::<Vec<_>>();
+    ordered.sort_by_key(|(index, input)| (input.layer.precedence(), *index));
+    let mut remaining = lines_used;
+    let mut emitted = 0;
+    for (_, input) in ordered {
+        if remaining == 0 {
+            break;
+        }
+        if !input.lines.is_empty() {
+            emitted += 1;
+        }
+        remaining = remaining.saturating_sub(input.lines.len());
+    }
+    emitted
+}
+
 fn render_context_pack_section(section: &MindContextPackSection) -> Vec<String> {
     let mut lines = vec![format!("{} {}", section.citation, section.title)];
     lines.extend(section.lines.iter().cloned());
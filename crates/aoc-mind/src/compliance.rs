@@ -0,0 +1,307 @@
+//! Data-subject export and verified delete.
+//!
+//! An export writes every stored row of a subject's conversations (raw
+//! events, T0-T2 artifacts, archives, provenance, links, checkpoints) to one
+//! `<table>.jsonl` file per table, then `manifest.json` with the sha256 of
+//! every file and every row. The delete flow only removes rows the manifest
+//! accounts for, so what was handed over is exactly what was forgotten, and
+//! it leaves a `deletion-receipt.json` that names the manifest by hash.
+
+use aoc_core::{ErrorCode, ErrorCoded};
+use aoc_storage::{MindStore, StorageError, SubjectRow};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+use thiserror::Error;
+
+pub const COMPLIANCE_MANIFEST_FILE: &str = "manifest.json";
+pub const COMPLIANCE_RECEIPT_FILE: &str = "deletion-receipt.json";
+const COMPLIANCE_MANIFEST_VERSION: u32 = 1;
+
+/// Whose data to export: one conversation, or every conversation that
+/// mentions a person identifier such as an email address or handle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum DataSubject {
+    Conversation(String),
+    Identifier(String),
+}
+
+impl DataSubject {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Conversation(id) => format!("conversation {id}"),
+            Self::Identifier(identifier) => format!("identifier {identifier}"),
+        }
+    }
+
+    fn conversation_ids(&self, store: &MindStore) -> Result<Vec<String>, StorageError> {
+        match self {
+            Self::Conversation(id) => Ok(vec![id.clone()]),
+            Self::Identifier(identifier) => store.subject_conversation_ids(identifier),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplianceFile {
+    /// Relative to the bundle directory.
+    pub path: String,
+    pub sha256: String,
+    pub records: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplianceManifest {
+    pub version: u32,
+    pub subject: DataSubject,
+    pub generated_at: DateTime<Utc>,
+    pub schema_version: i64,
+    pub conversation_ids: Vec<String>,
+    pub files: Vec<ComplianceFile>,
+    /// Row hashes per table, in file order.
+    pub records: BTreeMap<String, Vec<String>>,
+}
+
+impl ComplianceManifest {
+    pub fn total_records(&self) -> usize {
+        self.files.iter().map(|file| file.records).sum()
+    }
+}
+
+/// Written next to the manifest once a delete has been applied and checked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplianceDeletion {
+    pub subject: DataSubject,
+    pub manifest_sha256: String,
+    pub conversation_ids: Vec<String>,
+    /// Rows per table that were (or, on a dry run, would be) deleted.
+    pub deleted: BTreeMap<String, usize>,
+    pub dry_run: bool,
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// A re-read after the delete found no rows left for the subject.
+    pub verified: bool,
+}
+
+#[derive(Debug, Error)]
+pub enum ComplianceError {
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("serialization error: {0}")]
+    Serialization(String),
+    #[error("bundle verification failed: {0}")]
+    Verification(String),
+    #[error("store changed since export: {0}; export the subject again before deleting")]
+    Stale(String),
+}
+
+impl ErrorCoded for ComplianceError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Io(_) => ErrorCode::Io,
+            Self::Serialization(_) => ErrorCode::Serialization,
+            Self::Verification(_) => ErrorCode::VerificationFailed,
+            Self::Stale(_) => ErrorCode::Stale,
+        }
+    }
+}
+
+/// Writes the subject's rows and a hashed manifest to `out_dir`, which must
+/// not already hold a bundle.
+pub fn export_compliance_bundle(
+    store: &MindStore,
+    subject: &DataSubject,
+    out_dir: &Path,
+    now: DateTime<Utc>,
+) -> Result<ComplianceManifest, ComplianceError> {
+    let manifest_path = out_dir.join(COMPLIANCE_MANIFEST_FILE);
+    if manifest_path.exists() {
+        return Err(ComplianceError::Verification(format!(
+            "{} already holds a bundle; use a new --out directory",
+            out_dir.display()
+        )));
+    }
+    fs::create_dir_all(out_dir)?;
+
+    let conversation_ids = subject.conversation_ids(store)?;
+    let mut lines_by_table = BTreeMap::<String, Vec<String>>::new();
+    for row in store.subject_rows(&conversation_ids)? {
+        lines_by_table
+            .entry(row.table.clone())
+            .or_default()
+            .push(row_line(&row)?);
+    }
+
+    let mut files = Vec::with_capacity(lines_by_table.len());
+    let mut records = BTreeMap::new();
+    for (table, lines) in lines_by_table {
+        let path = format!("{table}.jsonl");
+        let mut bytes = Vec::new();
+        for line in &lines {
+            bytes.extend_from_slice(line.as_bytes());
+            bytes.push(b'\n');
+        }
+        fs::write(out_dir.join(&path), &bytes)?;
+        files.push(ComplianceFile {
+            path,
+            sha256: sha256_hex(&bytes),
+            records: lines.len(),
+        });
+        records.insert(
+            table,
+            lines
+                .iter()
+                .map(|line| sha256_hex(line.as_bytes()))
+                .collect(),
+        );
+    }
+
+    let manifest = ComplianceManifest {
+        version: COMPLIANCE_MANIFEST_VERSION,
+        subject: subject.clone(),
+        generated_at: now,
+        schema_version: store.schema_version()?,
+        conversation_ids,
+        files,
+        records,
+    };
+    // The manifest goes last so a bundle without one is visibly incomplete.
+    let payload = serde_json::to_vec_pretty(&manifest)
+        .map_err(|err| ComplianceError::Serialization(err.to_string()))?;
+    let tmp = manifest_path.with_extension("tmp");
+    fs::write(&tmp, payload)?;
+    fs::rename(&tmp, &manifest_path)?;
+    Ok(manifest)
+}
+
+/// Re-hashes every file and row in the bundle at `dir` against its manifest.
+pub fn verify_compliance_bundle(dir: &Path) -> Result<ComplianceManifest, ComplianceError> {
+    let raw = fs::read(dir.join(COMPLIANCE_MANIFEST_FILE))?;
+    let manifest: ComplianceManifest = serde_json::from_slice(&raw)
+        .map_err(|err| ComplianceError::Serialization(err.to_string()))?;
+    if manifest.version != COMPLIANCE_MANIFEST_VERSION {
+        return Err(ComplianceError::Verification(format!(
+            "unsupported manifest version {}",
+            manifest.version
+        )));
+    }
+    for file in &manifest.files {
+        let bytes = fs::read(dir.join(&file.path))?;
+        let actual = sha256_hex(&bytes);
+        if actual != file.sha256 {
+            return Err(ComplianceError::Verification(format!(
+                "{} hashes to {actual}, manifest says {}",
+                file.path, file.sha256
+            )));
+        }
+        let table = file.path.trim_end_matches(".jsonl");
+        let row_hashes = String::from_utf8_lossy(&bytes)
+            .lines()
+            .map(|line| sha256_hex(line.as_bytes()))
+            .collect::<Vec<_>>();
+        if row_hashes.len() != file.records || manifest.records.get(table) != Some(&row_hashes) {
+            return Err(ComplianceError::Verification(format!(
+                "row hashes of {} do not match the manifest",
+                file.path
+            )));
+        }
+    }
+    Ok(manifest)
+}
+
+/// Deletes the subject of the verified bundle at `dir` from `store`.
+///
+/// Refuses when the store holds rows (or, for identifier subjects,
+/// conversations) the manifest does not list, since deleting them would
+/// forget data the subject never received. With `dry_run` nothing is deleted
+/// or written; otherwise the receipt lands next to the manifest.
+pub fn delete_compliance_subject(
+    store: &MindStore,
+    dir: &Path,
+    dry_run: bool,
+    now: DateTime<Utc>,
+) -> Result<ComplianceDeletion, ComplianceError> {
+    let manifest = verify_compliance_bundle(dir)?;
+    let manifest_sha256 = sha256_hex(&fs::read(dir.join(COMPLIANCE_MANIFEST_FILE))?);
+
+    let exported = manifest.conversation_ids.iter().collect::<BTreeSet<_>>();
+    if let Some(extra) = manifest
+        .subject
+        .conversation_ids(store)?
+        .into_iter()
+        .find(|id| !exported.contains(id))
+    {
+        return Err(ComplianceError::Stale(format!(
+            "conversation {extra} now matches {}",
+            manifest.subject.label()
+        )));
+    }
+
+    let mut pending = BTreeMap::<String, usize>::new();
+    for row in store.subject_rows(&manifest.conversation_ids)? {
+        let hash = sha256_hex(row_line(&row)?.as_bytes());
+        let listed = manifest
+            .records
+            .get(&row.table)
+            .is_some_and(|hashes| hashes.contains(&hash));
+        if !listed {
+            return Err(ComplianceError::Stale(format!(
+                "a {} row is not in the manifest",
+                row.table
+            )));
+        }
+        *pending.entry(row.table).or_insert(0) += 1;
+    }
+
+    if dry_run {
+        return Ok(ComplianceDeletion {
+            subject: manifest.subject,
+            manifest_sha256,
+            conversation_ids: manifest.conversation_ids,
+            deleted: pending,
+            dry_run: true,
+            deleted_at: None,
+            verified: false,
+        });
+    }
+
+    let deleted = store.delete_subject_rows(&manifest.conversation_ids)?;
+    let verified = store.subject_rows(&manifest.conversation_ids)?.is_empty();
+    let receipt = ComplianceDeletion {
+        subject: manifest.subject,
+        manifest_sha256,
+        conversation_ids: manifest.conversation_ids,
+        deleted,
+        dry_run: false,
+        deleted_at: Some(now),
+        verified,
+    };
+    let payload = serde_json::to_vec_pretty(&receipt)
+        .map_err(|err| ComplianceError::Serialization(err.to_string()))?;
+    fs::write(dir.join(COMPLIANCE_RECEIPT_FILE), payload)?;
+    if !verified {
+        return Err(ComplianceError::Verification(
+            "subject rows remained after delete".to_string(),
+        ));
+    }
+    Ok(receipt)
+}
+
+fn row_line(row: &SubjectRow) -> Result<String, ComplianceError> {
+    serde_json::to_string(&row.row).map_err(|err| ComplianceError::Serialization(err.to_string()))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
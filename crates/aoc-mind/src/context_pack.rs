@@ -0,0 +1,464 @@
+//! Per-conversation context packs for agent handshakes.
+//!
+//! [`build_handshake_export`](crate::build_handshake_export) renders the
+//! project canon alone. A [`HandshakeBuilder`] packs what an agent resuming a
+//! conversation needs, in rank order: the leading canon revision for the
+//! conversation's active tag, that tag's newest reflections, the newest
+//! observations, and the tasks the conversation's artifacts link to that are
+//! still open. Items are added in that order while the rendered pack fits
+//! the token budget; one that does not fit is dropped and smaller ones after
+//! it may still make it. Packs persist as `conversation` handshake snapshots.
+
+use crate::{
+    active_tag_for_ts, estimate_text_tokens, normalize_text, ranked_handshake_entries,
+    truncate_chars, T3ExportError, MIND_T3_HANDSHAKE_TOKEN_BUDGET,
+};
+use aoc_core::mind_contracts::{canonical_payload_hash, ArtifactTaskLink, ArtifactTaskRelation};
+use aoc_storage::{ConversationArtifactFilter, ConversationContextState, MindStore};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// `handshake_snapshots.scope` of persisted context packs.
+pub const CONTEXT_PACK_SCOPE: &str = "conversation";
+const CONTEXT_PACK_ITEM_MAX_CHARS: usize = 240;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextPackSection {
+    Canon,
+    Reflection,
+    Observation,
+    OpenTask,
+}
+
+impl ContextPackSection {
+    fn heading(self) -> &'static str {
+        match self {
+            Self::Canon => "Canon",
+            Self::Reflection => "Reflections",
+            Self::Observation => "Recent observations",
+            Self::OpenTask => "Open tasks",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContextPackItem {
+    pub section: ContextPackSection,
+    /// Canon entry, artifact, or task id.
+    pub id: String,
+    pub text: String,
+    pub ts: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContextPack {
+    pub conversation_id: String,
+    pub active_tag: Option<String>,
+    pub generated_at: DateTime<Utc>,
+    pub token_budget: u32,
+    pub items: Vec<ContextPackItem>,
+    /// Candidates left out because they did not fit the budget.
+    pub dropped_items: usize,
+    pub token_estimate: u32,
+    pub payload_hash: String,
+    #[serde(skip)]
+    pub markdown: String,
+    /// Set once the pack is persisted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot_id: Option<String>,
+}
+
+impl ContextPack {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        value["markdown"] = serde_json::Value::String(self.markdown.clone());
+        value
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HandshakeBuilder {
+    token_budget: u32,
+    max_reflections: usize,
+    max_observations: usize,
+    max_open_tasks: usize,
+}
+
+impl Default for HandshakeBuilder {
+    fn default() -> Self {
+        Self::new(MIND_T3_HANDSHAKE_TOKEN_BUDGET)
+    }
+}
+
+impl HandshakeBuilder {
+    pub fn new(token_budget: u32) -> Self {
+        Self {
+            token_budget,
+            max_reflections: 4,
+            max_observations: 4,
+            max_open_tasks: 6,
+        }
+    }
+
+    pub fn max_reflections(mut self, max: usize) -> Self {
+        self.max_reflections = max;
+        self
+    }
+
+    pub fn max_observations(mut self, max: usize) -> Self {
+        self.max_observations = max;
+        self
+    }
+
+    pub fn max_open_tasks(mut self, max: usize) -> Self {
+        self.max_open_tasks = max;
+        self
+    }
+
+    pub fn build(
+        &self,
+        store: &MindStore,
+        conversation_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<ContextPack, T3ExportError> {
+        let context_states = store.context_states(conversation_id)?;
+        let active_tag = active_tag_for_ts(&context_states, now).map(|tag| tag.to_lowercase());
+        let candidates = self.candidates(
+            store,
+            conversation_id,
+            &context_states,
+            active_tag.as_deref(),
+        )?;
+
+        let mut items = Vec::new();
+        let mut dropped_items = 0;
+        let mut markdown = render_context_pack(conversation_id, active_tag.as_deref(), now, &items);
+        for candidate in candidates {
+            items.push(candidate);
+            let candidate_markdown =
+                render_context_pack(conversation_id, active_tag.as_deref(), now, &items);
+            if estimate_text_tokens(&candidate_markdown) <= self.token_budget {
+                markdown = candidate_markdown;
+            } else {
+                items.pop();
+                dropped_items += 1;
+            }
+        }
+
+        Ok(ContextPack {
+            conversation_id: conversation_id.to_string(),
+            active_tag,
+            generated_at: now,
+            token_budget: self.token_budget,
+            items,
+            dropped_items,
+            token_estimate: estimate_text_tokens(&markdown),
+            payload_hash: canonical_payload_hash(&markdown)?,
+            markdown,
+            snapshot_id: None,
+        })
+    }
+
+    /// Builds the pack and stores it in `handshake_snapshots`; an unchanged
+    /// pack maps to the snapshot already stored.
+    pub fn build_and_persist(
+        &self,
+        store: &MindStore,
+        conversation_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<ContextPack, T3ExportError> {
+        let mut pack = self.build(store, conversation_id, now)?;
+        let (snapshot_id, _) = store.upsert_handshake_snapshot(
+            CONTEXT_PACK_SCOPE,
+            conversation_id,
+            &pack.markdown,
+            &pack.payload_hash,
+            pack.token_estimate,
+            now,
+        )?;
+        pack.snapshot_id = Some(snapshot_id);
+        Ok(pack)
+    }
+
+    fn candidates(
+        &self,
+        store: &MindStore,
+        conversation_id: &str,
+        context_states: &[ConversationContextState],
+        active_tag: Option<&str>,
+    ) -> Result<Vec<ContextPackItem>, T3ExportError> {
+        let mut candidates = Vec::new();
+
+        let mut canon = store.active_canon_entries(active_tag)?;
+        if canon.is_empty() {
+            canon = store.active_canon_entries(None)?;
+        }
+        if let Some(entry) = ranked_handshake_entries(&canon, active_tag).first() {
+            candidates.push(ContextPackItem {
+                section: ContextPackSection::Canon,
+                id: format!("{} r{}", entry.entry_id, entry.revision),
+                text: item_text(&entry.summary),
+                ts: entry.created_at,
+            });
+        }
+
+        // Newest first; artifacts are tagged by the context state at their ts.
+        let artifacts = store.conversation_artifacts(
+            conversation_id,
+            &ConversationArtifactFilter {
+                newest_first: true,
+                ..ConversationArtifactFilter::default()
+            },
+        )?;
+        let reflections = artifacts
+            .iter()
+            .filter(|artifact| artifact.kind == "t2")
+            .filter(|artifact| {
+                active_tag.is_none_or(|tag| {
+                    active_tag_for_ts(context_states, artifact.ts)
+                        .is_some_and(|artifact_tag| artifact_tag.eq_ignore_ascii_case(tag))
+                })
+            })
+            .take(self.max_reflections);
+        let observations = artifacts
+            .iter()
+            .filter(|artifact| artifact.kind == "t1")
+            .take(self.max_observations);
+        for (section, artifact) in reflections
+            .map(|artifact| (ContextPackSection::Reflection, artifact))
+            .chain(observations.map(|artifact| (ContextPackSection::Observation, artifact)))
+        {
+            candidates.push(ContextPackItem {
+                section,
+                id: artifact.artifact_id.clone(),
+                text: item_text(&artifact.text),
+                ts: artifact.ts,
+            });
+        }
+
+        // A task is open while some link to it has no end and is not a
+        // completion; the strongest such link describes it.
+        let mut open_tasks = BTreeMap::new();
+        let mut closed_tasks = Vec::new();
+        for artifact in &artifacts {
+            for link in store.artifact_task_links_for_artifact(&artifact.artifact_id)? {
+                if link.relation == ArtifactTaskRelation::Completed {
+                    closed_tasks.push(link.task_id);
+                    continue;
+                }
+                if link.end_ts.is_some() {
+                    continue;
+                }
+                let stronger =
+                    open_tasks
+                        .get(&link.task_id)
+                        .is_none_or(|current: &ArtifactTaskLink| {
+                            link.confidence_bps > current.confidence_bps
+                        });
+                if stronger {
+                    open_tasks.insert(link.task_id.clone(), link);
+                }
+            }
+        }
+        for task_id in closed_tasks {
+            open_tasks.remove(&task_id);
+        }
+        let mut open_tasks = open_tasks.into_values().collect::<Vec<_>>();
+        open_tasks.sort_by(|left, right| {
+            right
+                .start_ts
+                .cmp(&left.start_ts)
+                .then(left.task_id.cmp(&right.task_id))
+        });
+        for link in open_tasks.into_iter().take(self.max_open_tasks) {
+            candidates.push(ContextPackItem {
+                section: ContextPackSection::OpenTask,
+                id: link.task_id.clone(),
+                text: format!(
+                    "{} ({} bps) via {}",
+                    task_relation_label(link.relation),
+                    link.confidence_bps,
+                    link.artifact_id
+                ),
+                ts: link.start_ts,
+            });
+        }
+
+        Ok(candidates)
+    }
+}
+
+fn item_text(text: &str) -> String {
+    truncate_chars(normalize_text(text), CONTEXT_PACK_ITEM_MAX_CHARS)
+}
+
+fn task_relation_label(relation: ArtifactTaskRelation) -> &'static str {
+    match relation {
+        ArtifactTaskRelation::Active => "active",
+        ArtifactTaskRelation::WorkedOn => "worked on",
+        ArtifactTaskRelation::Mentioned => "mentioned",
+        ArtifactTaskRelation::Completed => "completed",
+    }
+}
+
+fn render_context_pack(
+    conversation_id: &str,
+    active_tag: Option<&str>,
+    generated_at: DateTime<Utc>,
+    items: &[ContextPackItem],
+) -> String {
+    let mut lines = vec![
+        "# Context Pack".to_string(),
+        String::new(),
+        format!("- conversation: {conversation_id}"),
+        format!("- active_tag: {}", active_tag.unwrap_or("none")),
+        format!("- generated_at: {}", generated_at.to_rfc3339()),
+    ];
+    let mut section = None;
+    for item in items {
+        if section != Some(item.section) {
+            section = Some(item.section);
+            lines.push(String::new());
+            lines.push(format!("## {}", item.section.heading()));
+        }
+        lines.push(format!("- [{}] {}", item.id, item.text));
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn ts(min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, 23, 11, min, 0).unwrap()
+    }
+
+    fn link(artifact_id: &str, task_id: &str, relation: ArtifactTaskRelation) -> ArtifactTaskLink {
+        ArtifactTaskLink::new(
+            artifact_id.to_string(),
+            task_id.to_string(),
+            relation,
+            8_000,
+            vec![],
+            "test".to_string(),
+            ts(1),
+            None,
+        )
+        .expect("link")
+    }
+
+    fn seeded_store() -> MindStore {
+        let store = MindStore::open_in_memory().expect("db");
+        for (min, tag) in [(0, "other"), (4, "Mind")] {
+            store
+                .append_context_state(&ConversationContextState {
+                    conversation_id: "conv-1".to_string(),
+                    ts: ts(min),
+                    active_tag: Some(tag.to_string()),
+                    active_tasks: vec![],
+                    lifecycle: None,
+                    signal_task_ids: vec![],
+                    signal_source: "test".to_string(),
+                })
+                .expect("context");
+        }
+        for (id, min) in [("obs:1", 1), ("obs:2", 5)] {
+            store
+                .insert_observation(id, "conv-1", ts(min), &format!("observed {id}"), &[])
+                .expect("observation");
+        }
+        store
+            .insert_reflection(
+                "ref:old",
+                "conv-1",
+                ts(2),
+                "reflection under other tag",
+                &[],
+            )
+            .expect("reflection");
+        store
+            .insert_reflection(
+                "ref:mind",
+                "conv-1",
+                ts(6),
+                "reflection under mind tag",
+                &[],
+            )
+            .expect("reflection");
+        store
+            .upsert_canon_entry_revision(
+                "canon:mind",
+                Some("mind"),
+                "canon summary for mind",
+                9_000,
+                9_000,
+                None,
+                &["ref:mind".to_string()],
+                ts(7),
+            )
+            .expect("canon");
+        for task_link in [
+            link("obs:1", "12", ArtifactTaskRelation::Active),
+            link("obs:2", "13", ArtifactTaskRelation::WorkedOn),
+            link("obs:2", "12", ArtifactTaskRelation::Completed),
+        ] {
+            store.upsert_artifact_task_link(&task_link).expect("link");
+        }
+        store
+    }
+
+    #[test]
+    fn pack_ranks_sources_for_the_active_tag_and_persists() {
+        let store = seeded_store();
+        let pack = HandshakeBuilder::default()
+            .build_and_persist(&store, "conv-1", ts(10))
+            .expect("pack");
+
+        assert_eq!(pack.active_tag.as_deref(), Some("mind"));
+        let ids = pack
+            .items
+            .iter()
+            .map(|item| (item.section, item.id.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                (ContextPackSection::Canon, "canon:mind r1"),
+                (ContextPackSection::Reflection, "ref:mind"),
+                (ContextPackSection::Observation, "obs:2"),
+                (ContextPackSection::Observation, "obs:1"),
+                (ContextPackSection::OpenTask, "13"),
+            ]
+        );
+        assert!(pack.markdown.contains("## Open tasks\n- [13] worked on"));
+        assert_eq!(pack.to_json()["items"][0]["section"], "canon");
+
+        let snapshot = store
+            .latest_handshake_snapshot(CONTEXT_PACK_SCOPE, "conv-1")
+            .expect("snapshot")
+            .expect("persisted");
+        assert_eq!(Some(snapshot.snapshot_id), pack.snapshot_id);
+        assert_eq!(snapshot.payload_text, pack.markdown);
+        assert_eq!(snapshot.payload_hash, pack.payload_hash);
+    }
+
+    #[test]
+    fn pack_drops_items_that_exceed_the_budget() {
+        let store = seeded_store();
+        let full = HandshakeBuilder::default()
+            .build(&store, "conv-1", ts(10))
+            .expect("full");
+        let tight = HandshakeBuilder::new(full.token_estimate - 5)
+            .build(&store, "conv-1", ts(10))
+            .expect("tight");
+
+        assert!(tight.token_estimate <= full.token_estimate - 5);
+        assert!(tight.dropped_items >= 1);
+        assert_eq!(tight.items.len() + tight.dropped_items, full.items.len());
+        assert_eq!(tight.items[0].section, ContextPackSection::Canon);
+    }
+}
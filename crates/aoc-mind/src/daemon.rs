@@ -0,0 +1,698 @@
+//! `aocd`: the whole project Mind pipeline in one unattended process.
+//!
+//! Each tick syncs changed Pi session files, runs the observer sidecar on the
+//! conversations they touched, then drains the reflector (T2) and T3 queues,
+//! and, when configured, keeps a rotation of daily store snapshots.
+//! A unix socket beside the project store accepts one JSON request per line
+//! (status, pause/resume, trigger, reload, shutdown) and answers with one
+//! JSON [`DaemonResponse`] line.
+
+use crate::{
+    default_pi_session_root, mind_runtime_root, read_mind_service_health_snapshot,
+    sync_session_file_into_project_store, MindProjectPaths, MindRuntimeConfig, MindRuntimeCore,
+    MindServiceHealthSnapshot, DISTILL_RUN_STAGE,
+};
+use aoc_core::mind_observer_feed::MindObserverFeedTriggerKind;
+use aoc_storage::{
+    MindWriterGuard, SharedClock, SnapshotRotation, WriterClaim, DEFAULT_WRITER_LEASE_TTL_MS,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+pub const DAEMON_SOCKET_FILE: &str = "aocd.sock";
+const CONTROL_POLL: Duration = Duration::from_millis(100);
+const CONTROL_IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub fn daemon_socket_path(project_root: &Path) -> PathBuf {
+    mind_runtime_root(project_root).join(DAEMON_SOCKET_FILE)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DaemonStage {
+    Ingest,
+    Observer,
+    Reflector,
+    T3,
+}
+
+impl DaemonStage {
+    pub const ALL: [DaemonStage; 4] = [
+        DaemonStage::Ingest,
+        DaemonStage::Observer,
+        DaemonStage::Reflector,
+        DaemonStage::T3,
+    ];
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum DaemonRequest {
+    Status,
+    Pause,
+    Resume,
+    /// Run one tick now, even while paused. No stages means all of them.
+    Trigger {
+        #[serde(default)]
+        stages: Vec<DaemonStage>,
+    },
+    /// Rebuild the runtime and re-resolve watched session directories.
+    Reload,
+    Shutdown,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonTickReport {
+    pub at: Option<DateTime<Utc>>,
+    pub stages: Vec<DaemonStage>,
+    pub ingested_files: Vec<PathBuf>,
+    pub processed_raw_events: usize,
+    pub produced_t0_events: usize,
+    pub observer_events: usize,
+    pub reflector_jobs_completed: usize,
+    pub reflector_jobs_failed: usize,
+    pub t3_jobs_completed: usize,
+    pub t3_jobs_failed: usize,
+    /// Distillations a crashed writer left running, re-run from their journal.
+    #[serde(default)]
+    pub resumed_runs: usize,
+    /// Daily snapshot written this tick.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<PathBuf>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    pub project_root: PathBuf,
+    pub pid: u32,
+    pub started_at: Option<DateTime<Utc>>,
+    pub paused: bool,
+    pub ticks: u64,
+    pub reloads: u64,
+    pub watched_files: usize,
+    pub last_error: Option<String>,
+    pub last_tick: Option<DaemonTickReport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonResponse {
+    pub ok: bool,
+    pub message: String,
+    pub status: DaemonStatus,
+}
+
+/// The work a daemon drives; split out so the control plane can be exercised
+/// without a live Mind store.
+pub trait DaemonPipeline {
+    fn tick(&mut self, stages: &[DaemonStage]) -> DaemonTickReport;
+    fn reload(&mut self) -> Result<(), String>;
+    fn watched_files(&self) -> usize;
+}
+
+/// Tracks session JSONL files under a root by size and mtime so only files
+/// that changed since the last poll are re-ingested.
+#[derive(Debug, Default)]
+pub struct SessionFileWatcher {
+    root: Option<PathBuf>,
+    seen: BTreeMap<PathBuf, (u64, SystemTime)>,
+}
+
+impl SessionFileWatcher {
+    pub fn new(root: Option<PathBuf>) -> Self {
+        Self {
+            root,
+            seen: BTreeMap::new(),
+        }
+    }
+
+    pub fn root(&self) -> Option<&Path> {
+        self.root.as_deref()
+    }
+
+    pub fn watched(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let Some(root) = self.root.as_ref() else {
+            return Vec::new();
+        };
+        let mut changed = Vec::new();
+        let mut present = BTreeSet::new();
+        let mut stack = vec![root.clone()];
+        while let Some(dir) = stack.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let Ok(meta) = entry.metadata() else {
+                    continue;
+                };
+                if meta.is_dir() {
+                    stack.push(path);
+                    continue;
+                }
+                if path.extension().and_then(|ext| ext.to_str()) != Some("jsonl") {
+                    continue;
+                }
+                let stamp = (meta.len(), meta.modified().unwrap_or(UNIX_EPOCH));
+                present.insert(path.clone());
+                if self.seen.get(&path) != Some(&stamp) {
+                    self.seen.insert(path.clone(), stamp);
+                    changed.push(path);
+                }
+            }
+        }
+        self.seen.retain(|path, _| present.contains(path));
+        changed.sort();
+        changed
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MindDaemonConfig {
+    pub project_root: PathBuf,
+    pub session_id: String,
+    pub pane_id: String,
+    pub agent_id: String,
+    /// Overrides AOC_PI_SESSION_DIR and the default Pi session bucket.
+    pub session_root: Option<PathBuf>,
+    /// Stamps ticks and times the runtime and writer leases.
+    pub clock: SharedClock,
+    /// Daily snapshots of the project store, taken by the first tick of
+    /// each UTC day.
+    pub snapshots: Option<SnapshotRotation>,
+}
+
+impl MindDaemonConfig {
+    fn resolve_session_root(&self) -> Option<PathBuf> {
+        self.session_root
+            .clone()
+            .or_else(|| {
+                env::var("AOC_PI_SESSION_DIR")
+                    .ok()
+                    .map(|value| PathBuf::from(value.trim()))
+                    .filter(|path| !path.as_os_str().is_empty())
+            })
+            .or_else(|| default_pi_session_root(&self.project_root))
+    }
+
+    fn build_runtime(&self) -> Result<MindRuntimeCore, String> {
+        let paths = MindProjectPaths::for_project_root(&self.project_root);
+        MindRuntimeCore::new(MindRuntimeConfig {
+            project_root: self.project_root.display().to_string(),
+            session_id: self.session_id.clone(),
+            pane_id: self.pane_id.clone(),
+            agent_key: self.agent_id.clone(),
+            store_path_override: None,
+            reflector_lock_path: paths.reflector_lock_path,
+            t3_lock_path: paths.t3_lock_path,
+            debounce_run_ms: 250,
+            t3_max_attempts: 3,
+            clock: self.clock.clone(),
+        })
+    }
+}
+
+/// Session watcher, observer sidecar, and reflector/T3 workers over one
+/// [`MindRuntimeCore`].
+pub struct MindPipeline {
+    config: MindDaemonConfig,
+    runtime: Option<MindRuntimeCore>,
+    watcher: SessionFileWatcher,
+    snapshot: MindServiceHealthSnapshot,
+}
+
+impl MindPipeline {
+    pub fn new(config: MindDaemonConfig) -> Result<Self, String> {
+        let runtime = config.build_runtime()?;
+        let watcher = SessionFileWatcher::new(config.resolve_session_root());
+        let mut snapshot = read_mind_service_health_snapshot(&config.project_root)
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        snapshot.lifecycle = "running".to_string();
+        snapshot.reflector_enabled = true;
+        snapshot.t3_enabled = true;
+        Ok(Self {
+            config,
+            runtime: Some(runtime),
+            watcher,
+            snapshot,
+        })
+    }
+}
+
+impl DaemonPipeline for MindPipeline {
+    fn tick(&mut self, stages: &[DaemonStage]) -> DaemonTickReport {
+        let now = self.config.clock.now();
+        let mut report = DaemonTickReport {
+            at: Some(now),
+            stages: stages.to_vec(),
+            ..DaemonTickReport::default()
+        };
+        let Some(runtime) = self.runtime.as_mut() else {
+            report
+                .errors
+                .push("runtime unavailable; reload to retry".to_string());
+            return report;
+        };
+        // Hold the store's write lease for the whole tick; when the CLI or a
+        // server is writing, skip this tick and retry on the next one.
+        let store_path = MindProjectPaths::for_project_root(&self.config.project_root).store_path;
+        let _writer = match MindWriterGuard::acquire_with_clock(
+            &store_path,
+            WriterClaim::for_current_process("daemon"),
+            DEFAULT_WRITER_LEASE_TTL_MS,
+            self.config.clock.clone(),
+        ) {
+            Ok(guard) => guard,
+            Err(err) => {
+                report.errors.push(format!("writer: {err}"));
+                return report;
+            }
+        };
+        self.snapshot.supervisor_runs = self.snapshot.supervisor_runs.saturating_add(1);
+
+        let mut conversations = BTreeSet::new();
+        if stages.contains(&DaemonStage::Ingest) {
+            for file in self.watcher.poll() {
+                match sync_session_file_into_project_store(
+                    &self.config.project_root,
+                    &self.config.agent_id,
+                    &file,
+                ) {
+                    Ok(sync) => {
+                        report.processed_raw_events += sync.report.processed_raw_events;
+                        report.produced_t0_events += sync.report.produced_t0_events;
+                        conversations.insert(sync.report.conversation_id);
+                        report.ingested_files.push(file);
+                    }
+                    Err(err) => report
+                        .errors
+                        .push(format!("ingest {}: {err}", file.display())),
+                }
+            }
+        }
+        if let Some(latest) = conversations.iter().next_back() {
+            runtime.set_latest_conversation_id(Some(latest.clone()));
+        }
+
+        if stages.contains(&DaemonStage::Observer) {
+            // This tick holds the writer lease, so a run still marked running
+            // belongs to a writer that died mid-distillation.
+            match runtime.store().interrupted_pipeline_runs() {
+                Ok(runs) => {
+                    for run in runs.iter().filter(|run| run.stage == DISTILL_RUN_STAGE) {
+                        report.observer_events += runtime
+                            .enqueue_observer_events(
+                                &run.scope,
+                                MindObserverFeedTriggerKind::ManualShortcut,
+                                Some(format!("resume interrupted run {}", run.run_id)),
+                            )
+                            .len();
+                        report.resumed_runs += 1;
+                    }
+                }
+                Err(err) => report.errors.push(format!("resume: {err}")),
+            }
+            if conversations.is_empty() {
+                conversations.extend(runtime.latest_conversation_id().map(str::to_string));
+            }
+            for conversation_id in &conversations {
+                report.observer_events += runtime
+                    .maybe_run_token_threshold_events(conversation_id)
+                    .len();
+            }
+        }
+
+        if stages.contains(&DaemonStage::Reflector) {
+            runtime.begin_reflector_tick(&mut self.snapshot, now);
+            match runtime.run_reflector_tick(now) {
+                Ok(tick) => {
+                    report.reflector_jobs_completed = tick.jobs_completed;
+                    report.reflector_jobs_failed = tick.jobs_failed;
+                    report.observer_events += runtime
+                        .apply_reflector_tick_effects(&mut self.snapshot, &tick, now)
+                        .observer_events
+                        .len();
+                }
+                Err(err) => report.errors.push(format!("reflector: {err}")),
+            }
+        }
+
+        if stages.contains(&DaemonStage::T3) {
+            runtime.begin_t3_tick(&mut self.snapshot, now);
+            match runtime.run_t3_tick(now, |_store, _project_root, _active_tag, _now| Ok(())) {
+                Ok(tick) => {
+                    report.t3_jobs_completed = tick.jobs_completed;
+                    report.t3_jobs_failed = tick.jobs_failed;
+                    report.observer_events += runtime
+                        .apply_t3_tick_effects(&mut self.snapshot, &tick, now)
+                        .observer_events
+                        .len();
+                }
+                Err(err) => report.errors.push(format!("t3: {err}")),
+            }
+        }
+
+        if let Some(rotation) = &self.config.snapshots {
+            match runtime.store().rotate_daily_snapshot(rotation, now) {
+                Ok(snapshot) => report.snapshot = snapshot.created,
+                Err(err) => report.errors.push(format!("snapshot: {err}")),
+            }
+        }
+
+        runtime.refresh_queue_depths(&mut self.snapshot);
+        if !report.errors.is_empty() {
+            self.snapshot.supervisor_failures = self.snapshot.supervisor_failures.saturating_add(1);
+            self.snapshot.last_error = report.errors.last().cloned();
+        }
+        if let Err(err) = runtime.heartbeat_service(&mut self.snapshot) {
+            report.errors.push(format!("heartbeat: {err}"));
+        }
+        report
+    }
+
+    fn reload(&mut self) -> Result<(), String> {
+        // The runtime holds the project service lease; release it before
+        // building the replacement.
+        self.runtime = None;
+        self.runtime = Some(self.config.build_runtime()?);
+        self.watcher = SessionFileWatcher::new(self.config.resolve_session_root());
+        Ok(())
+    }
+
+    fn watched_files(&self) -> usize {
+        self.watcher.watched()
+    }
+}
+
+pub struct MindDaemon<P> {
+    pipeline: P,
+    status: DaemonStatus,
+    interval: Duration,
+    shutdown: bool,
+}
+
+impl<P: DaemonPipeline> MindDaemon<P> {
+    pub fn new(pipeline: P, project_root: PathBuf, interval: Duration) -> Self {
+        Self {
+            pipeline,
+            status: DaemonStatus {
+                project_root,
+                pid: std::process::id(),
+                started_at: Some(Utc::now()),
+                ..DaemonStatus::default()
+            },
+            interval,
+            shutdown: false,
+        }
+    }
+
+    pub fn status(&self) -> &DaemonStatus {
+        &self.status
+    }
+
+    pub fn shutdown_requested(&self) -> bool {
+        self.shutdown
+    }
+
+    pub fn run_tick(&mut self, stages: &[DaemonStage]) {
+        let report = self.pipeline.tick(stages);
+        self.status.ticks += 1;
+        self.status.watched_files = self.pipeline.watched_files();
+        if let Some(error) = report.errors.last() {
+            self.status.last_error = Some(error.clone());
+        }
+        self.status.last_tick = Some(report);
+    }
+
+    pub fn handle_request(&mut self, request: DaemonRequest) -> DaemonResponse {
+        let (ok, message) = match request {
+            DaemonRequest::Status => (true, "running".to_string()),
+            DaemonRequest::Pause => {
+                self.status.paused = true;
+                (true, "paused".to_string())
+            }
+            DaemonRequest::Resume => {
+                self.status.paused = false;
+                (true, "resumed".to_string())
+            }
+            DaemonRequest::Trigger { stages } => {
+                let stages = if stages.is_empty() {
+                    DaemonStage::ALL.to_vec()
+                } else {
+                    stages
+                };
+                self.run_tick(&stages);
+                let errors = self
+                    .status
+                    .last_tick
+                    .as_ref()
+                    .map(|tick| tick.errors.len())
+                    .unwrap_or_default();
+                (errors == 0, format!("tick complete ({errors} error(s))"))
+            }
+            DaemonRequest::Reload => match self.pipeline.reload() {
+                Ok(()) => {
+                    self.status.reloads += 1;
+                    (true, "reloaded".to_string())
+                }
+                Err(err) => {
+                    self.status.last_error = Some(err.clone());
+                    (false, format!("reload failed: {err}"))
+                }
+            },
+            DaemonRequest::Shutdown => {
+                self.shutdown = true;
+                (true, "shutting down".to_string())
+            }
+        };
+        DaemonResponse {
+            ok,
+            message,
+            status: self.status.clone(),
+        }
+    }
+
+    /// Answers one control connection: a single request line, a single
+    /// response line.
+    pub fn serve_connection(&mut self, stream: UnixStream) -> io::Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(CONTROL_IO_TIMEOUT))?;
+        stream.set_write_timeout(Some(CONTROL_IO_TIMEOUT))?;
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+        let response = match serde_json::from_str::<DaemonRequest>(line.trim()) {
+            Ok(request) => self.handle_request(request),
+            Err(err) => DaemonResponse {
+                ok: false,
+                message: format!("invalid request: {err}"),
+                status: self.status.clone(),
+            },
+        };
+        let mut stream = stream;
+        let mut payload = serde_json::to_vec(&response).map_err(io::Error::other)?;
+        payload.push(b'\n');
+        stream.write_all(&payload)
+    }
+
+    /// Binds the control socket and loops until a shutdown request arrives.
+    pub fn run(mut self, socket_path: &Path) -> io::Result<()> {
+        let listener = bind_control_socket(socket_path)?;
+        listener.set_nonblocking(true)?;
+        let mut next_tick = Instant::now();
+        while !self.shutdown {
+            loop {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        if let Err(err) = self.serve_connection(stream) {
+                            self.status.last_error = Some(format!("control: {err}"));
+                        }
+                    }
+                    Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                    Err(err) => return Err(err),
+                }
+            }
+            if Instant::now() >= next_tick {
+                if !self.status.paused {
+                    self.run_tick(&DaemonStage::ALL);
+                }
+                next_tick = Instant::now() + self.interval;
+            }
+            thread::sleep(CONTROL_POLL);
+        }
+        let _ = fs::remove_file(socket_path);
+        Ok(())
+    }
+}
+
+/// Refuses to start over a live daemon, but clears a socket left behind by a
+/// crashed one.
+fn bind_control_socket(socket_path: &Path) -> io::Result<UnixListener> {
+    if socket_path.exists() {
+        if UnixStream::connect(socket_path).is_ok() {
+            return Err(io::Error::new(
+                ErrorKind::AddrInUse,
+                format!("aocd already running on {}", socket_path.display()),
+            ));
+        }
+        fs::remove_file(socket_path)?;
+    }
+    if let Some(parent) = socket_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    UnixListener::bind(socket_path)
+}
+
+pub fn send_daemon_request(
+    socket_path: &Path,
+    request: &DaemonRequest,
+) -> io::Result<DaemonResponse> {
+    let mut stream = UnixStream::connect(socket_path)?;
+    stream.set_read_timeout(Some(CONTROL_IO_TIMEOUT * 12))?;
+    let mut payload = serde_json::to_vec(request).map_err(io::Error::other)?;
+    payload.push(b'\n');
+    stream.write_all(&payload)?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    serde_json::from_str(line.trim()).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct FakePipeline {
+        ticks: Vec<Vec<DaemonStage>>,
+        reloads: usize,
+    }
+
+    impl DaemonPipeline for FakePipeline {
+        fn tick(&mut self, stages: &[DaemonStage]) -> DaemonTickReport {
+            self.ticks.push(stages.to_vec());
+            DaemonTickReport {
+                stages: stages.to_vec(),
+                ..DaemonTickReport::default()
+            }
+        }
+
+        fn reload(&mut self) -> Result<(), String> {
+            self.reloads += 1;
+            Ok(())
+        }
+
+        fn watched_files(&self) -> usize {
+            2
+        }
+    }
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aocd-{label}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn control_requests_pause_trigger_reload_and_shutdown() {
+        let mut daemon = MindDaemon::new(
+            FakePipeline::default(),
+            PathBuf::from("/repo"),
+            Duration::from_secs(5),
+        );
+
+        let paused = daemon.handle_request(DaemonRequest::Pause);
+        assert!(paused.ok && paused.status.paused);
+
+        let triggered = daemon.handle_request(
+            serde_json::from_str(r#"{"command":"trigger","stages":["reflector"]}"#)
+                .expect("parse trigger"),
+        );
+        assert!(triggered.ok);
+        assert_eq!(triggered.status.ticks, 1);
+        assert_eq!(triggered.status.watched_files, 2);
+        daemon.handle_request(DaemonRequest::Trigger { stages: Vec::new() });
+        assert_eq!(
+            daemon.pipeline.ticks,
+            vec![vec![DaemonStage::Reflector], DaemonStage::ALL.to_vec()]
+        );
+
+        let reloaded = daemon.handle_request(DaemonRequest::Reload);
+        assert_eq!(reloaded.status.reloads, 1);
+        assert!(!daemon.handle_request(DaemonRequest::Resume).status.paused);
+        assert!(!daemon.shutdown_requested());
+        daemon.handle_request(DaemonRequest::Shutdown);
+        assert!(daemon.shutdown_requested());
+    }
+
+    #[test]
+    fn control_socket_round_trips_requests() {
+        let dir = temp_dir("socket");
+        let socket_path = dir.join(DAEMON_SOCKET_FILE);
+        let daemon = MindDaemon::new(
+            FakePipeline::default(),
+            dir.clone(),
+            Duration::from_secs(3600),
+        );
+        let server_socket = socket_path.clone();
+        let server = thread::spawn(move || daemon.run(&server_socket));
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let status = loop {
+            match send_daemon_request(&socket_path, &DaemonRequest::Status) {
+                Ok(response) => break response,
+                Err(_) if Instant::now() < deadline => thread::sleep(Duration::from_millis(20)),
+                Err(err) => panic!("daemon never answered: {err}"),
+            }
+        };
+        assert!(status.ok);
+        assert_eq!(status.status.project_root, dir);
+
+        let response =
+            send_daemon_request(&socket_path, &DaemonRequest::Shutdown).expect("shutdown request");
+        assert_eq!(response.message, "shutting down");
+        server.join().expect("daemon thread").expect("daemon run");
+        assert!(!socket_path.exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn session_watcher_reports_new_and_grown_files_once() {
+        let dir = temp_dir("watch");
+        let nested = dir.join("--repo--");
+        fs::create_dir_all(&nested).expect("create nested");
+        fs::write(nested.join("a.jsonl"), "{}\n").expect("write a");
+        fs::write(dir.join("notes.txt"), "skip").expect("write notes");
+
+        let mut watcher = SessionFileWatcher::new(Some(dir.clone()));
+        assert_eq!(watcher.poll(), vec![nested.join("a.jsonl")]);
+        assert!(watcher.poll().is_empty());
+
+        fs::write(nested.join("a.jsonl"), "{}\n{}\n").expect("grow a");
+        fs::write(dir.join("b.jsonl"), "{}\n").expect("write b");
+        assert_eq!(
+            watcher.poll(),
+            vec![nested.join("a.jsonl"), dir.join("b.jsonl")]
+        );
+        assert_eq!(watcher.watched(), 2);
+
+        fs::remove_file(dir.join("b.jsonl")).expect("remove b");
+        assert!(watcher.poll().is_empty());
+        assert_eq!(watcher.watched(), 1);
+        assert!(SessionFileWatcher::new(None).poll().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,452 @@
+//! Fan-out of pipeline events to message buses.
+//!
+//! [`EventSink`] is what `aoc live` publishes every [`PipelineEvent`]
+//! through. Webhooks implement it via [`WebhookDispatcher`]; [`NatsSink`]
+//! and [`RedisStreamSink`] speak just enough of the NATS core protocol and
+//! RESP (`XADD`) over a plain TCP connection to publish, so other services
+//! can subscribe instead of polling the SQLite store. Connections are kept
+//! between events and re-established once when a write fails.
+
+use crate::webhooks::{PipelineEvent, PipelineEventKind, WebhookDispatcher, WebhookTransport};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+pub const DEFAULT_NATS_SUBJECT: &str = "aoc.mind";
+pub const DEFAULT_REDIS_STREAM: &str = "aoc:mind:events";
+
+const NATS_DEFAULT_PORT: u16 = 4222;
+const REDIS_DEFAULT_PORT: u16 = 6379;
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Outcome of handing one event to one target of a sink.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SinkDelivery {
+    /// `webhook`, `nats`, or `redis_stream`.
+    pub sink: &'static str,
+    /// URL, `nats://host:port/<subject>`, or `redis://host:port/<stream>`.
+    pub target: String,
+    pub kind: PipelineEventKind,
+    pub subject: String,
+    pub attempts: u32,
+    pub delivered: bool,
+    pub error: Option<String>,
+}
+
+pub trait EventSink {
+    /// Publishes `event` to every target that wants it. Failures are reported
+    /// in the returned deliveries rather than raised, so one unreachable bus
+    /// does not hold back the others.
+    fn publish(&mut self, event: &PipelineEvent) -> Vec<SinkDelivery>;
+}
+
+impl<T: WebhookTransport> EventSink for WebhookDispatcher<T> {
+    fn publish(&mut self, event: &PipelineEvent) -> Vec<SinkDelivery> {
+        self.dispatch(event)
+            .into_iter()
+            .map(|delivery| SinkDelivery {
+                sink: "webhook",
+                target: delivery.url,
+                kind: delivery.kind,
+                subject: delivery.subject,
+                attempts: delivery.attempts,
+                delivered: delivery.delivered,
+                error: delivery.error,
+            })
+            .collect()
+    }
+}
+
+/// One `[[event_sinks]]` entry in aoc.toml.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum EventSinkConfig {
+    Nats {
+        /// `nats://host[:port]`.
+        url: String,
+        /// Events go to `<subject>.<event kind>`.
+        #[serde(default = "default_nats_subject")]
+        subject: String,
+        /// Same filter as webhooks: empty means every kind but the digest.
+        #[serde(default)]
+        events: Vec<PipelineEventKind>,
+        /// Environment variable holding the auth token.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token_env: Option<String>,
+    },
+    RedisStream {
+        /// `redis://host[:port][/db]`.
+        url: String,
+        #[serde(default = "default_redis_stream")]
+        stream: String,
+        /// Approximate cap passed as `XADD MAXLEN ~`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_len: Option<u64>,
+        #[serde(default)]
+        events: Vec<PipelineEventKind>,
+        /// Environment variable holding the `AUTH` password.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        password_env: Option<String>,
+    },
+}
+
+fn default_nats_subject() -> String {
+    DEFAULT_NATS_SUBJECT.to_string()
+}
+
+fn default_redis_stream() -> String {
+    DEFAULT_REDIS_STREAM.to_string()
+}
+
+impl EventSinkConfig {
+    /// Checks the URL shape without connecting.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Nats { url, subject, .. } => {
+                parse_endpoint(url, "nats", NATS_DEFAULT_PORT)?;
+                if subject.is_empty() || subject.contains(char::is_whitespace) {
+                    return Err("subject must be non-empty without whitespace".to_string());
+                }
+            }
+            Self::RedisStream { url, stream, .. } => {
+                parse_endpoint(url, "redis", REDIS_DEFAULT_PORT)?;
+                if stream.is_empty() {
+                    return Err("stream must not be empty".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn build(&self) -> Result<Box<dyn EventSink>, String> {
+        Ok(match self {
+            Self::Nats {
+                url,
+                subject,
+                events,
+                token_env,
+            } => {
+                let endpoint = parse_endpoint(url, "nats", NATS_DEFAULT_PORT)?;
+                Box::new(NatsSink {
+                    address: endpoint.address,
+                    subject: subject.clone(),
+                    events: events.clone(),
+                    token_env: token_env.clone(),
+                    connection: None,
+                })
+            }
+            Self::RedisStream {
+                url,
+                stream,
+                max_len,
+                events,
+                password_env,
+            } => {
+                let endpoint = parse_endpoint(url, "redis", REDIS_DEFAULT_PORT)?;
+                Box::new(RedisStreamSink {
+                    address: endpoint.address,
+                    database: endpoint.path.parse().ok(),
+                    stream: stream.clone(),
+                    max_len: *max_len,
+                    events: events.clone(),
+                    password_env: password_env.clone(),
+                    connection: None,
+                })
+            }
+        })
+    }
+}
+
+struct Endpoint {
+    address: String,
+    path: String,
+}
+
+fn parse_endpoint(url: &str, scheme: &str, default_port: u16) -> Result<Endpoint, String> {
+    let rest = url
+        .strip_prefix(scheme)
+        .and_then(|rest| rest.strip_prefix("://"))
+        .ok_or_else(|| format!("must be a {scheme}:// URL"))?;
+    let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+    if authority.is_empty() || authority.contains('@') {
+        return Err("expected host[:port]; keep credentials in the *_env setting".to_string());
+    }
+    let address = if authority
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| !port.is_empty() && port.bytes().all(|byte| byte.is_ascii_digit()))
+    {
+        authority.to_string()
+    } else {
+        format!("{authority}:{default_port}")
+    };
+    Ok(Endpoint {
+        address,
+        path: path.to_string(),
+    })
+}
+
+fn connect(address: &str) -> io::Result<TcpStream> {
+    let mut last_err = None;
+    for addr in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&addr, SOCKET_TIMEOUT) {
+            Ok(stream) => {
+                stream.set_read_timeout(Some(SOCKET_TIMEOUT))?;
+                stream.set_write_timeout(Some(SOCKET_TIMEOUT))?;
+                return Ok(stream);
+            }
+            Err(err) => last_err = Some(err),
+        }
+    }
+    Err(last_err.unwrap_or_else(|| io::Error::other(format!("{address} did not resolve"))))
+}
+
+fn read_env_secret(name: &Option<String>) -> Result<Option<String>, String> {
+    match name {
+        None => Ok(None),
+        Some(name) => match std::env::var(name) {
+            Ok(secret) if !secret.is_empty() => Ok(Some(secret)),
+            _ => Err(format!("secret env {name} is not set")),
+        },
+    }
+}
+
+/// Publishes with the NATS core text protocol; a trailing `PING` makes the
+/// server acknowledge (or reject) each `PUB` before the delivery is counted.
+pub struct NatsSink {
+    address: String,
+    subject: String,
+    events: Vec<PipelineEventKind>,
+    token_env: Option<String>,
+    connection: Option<BufReader<TcpStream>>,
+}
+
+impl NatsSink {
+    fn open(&self) -> Result<BufReader<TcpStream>, String> {
+        let token = read_env_secret(&self.token_env)?;
+        let stream = connect(&self.address).map_err(|err| err.to_string())?;
+        let mut reader = BufReader::new(stream);
+        let info = read_line(&mut reader)?;
+        if !info.starts_with("INFO") {
+            return Err(format!("unexpected NATS greeting: {info}"));
+        }
+        let mut options = json!({
+            "verbose": false,
+            "pedantic": false,
+            "name": "aoc",
+            "lang": "rust",
+            "version": env!("CARGO_PKG_VERSION"),
+        });
+        if let Some(token) = token {
+            options["auth_token"] = json!(token);
+        }
+        write_all(
+            &mut reader,
+            format!("CONNECT {options}\r\nPING\r\n").as_bytes(),
+        )?;
+        await_pong(&mut reader)?;
+        Ok(reader)
+    }
+
+    fn send(&mut self, subject: &str, body: &str) -> Result<(), String> {
+        if self.connection.is_none() {
+            self.connection = Some(self.open()?);
+        }
+        let reader = self.connection.as_mut().expect("connection opened above");
+        let frame = format!("PUB {subject} {}\r\n{body}\r\nPING\r\n", body.len());
+        let result = write_all(reader, frame.as_bytes()).and_then(|()| await_pong(reader));
+        if result.is_err() {
+            self.connection = None;
+        }
+        result
+    }
+}
+
+impl EventSink for NatsSink {
+    fn publish(&mut self, event: &PipelineEvent) -> Vec<SinkDelivery> {
+        if !event.kind.selected_by(&self.events) {
+            return Vec::new();
+        }
+        let subject = format!("{}.{}", self.subject, event.kind.as_str());
+        let body = event.body();
+        let target = format!("nats://{}/{subject}", self.address);
+        deliver("nats", target, event, || self.send(&subject, &body))
+    }
+}
+
+/// Appends each event to a Redis stream with `XADD`, one field per envelope
+/// member so consumers can filter without decoding the body.
+pub struct RedisStreamSink {
+    address: String,
+    database: Option<u32>,
+    stream: String,
+    max_len: Option<u64>,
+    events: Vec<PipelineEventKind>,
+    password_env: Option<String>,
+    connection: Option<BufReader<TcpStream>>,
+}
+
+impl RedisStreamSink {
+    fn open(&self) -> Result<BufReader<TcpStream>, String> {
+        let password = read_env_secret(&self.password_env)?;
+        let stream = connect(&self.address).map_err(|err| err.to_string())?;
+        let mut reader = BufReader::new(stream);
+        if let Some(password) = password {
+            redis_command(&mut reader, &["AUTH", &password])?;
+        }
+        if let Some(database) = self.database {
+            redis_command(&mut reader, &["SELECT", &database.to_string()])?;
+        }
+        Ok(reader)
+    }
+
+    fn send(&mut self, event: &PipelineEvent) -> Result<(), String> {
+        if self.connection.is_none() {
+            self.connection = Some(self.open()?);
+        }
+        let reader = self.connection.as_mut().expect("connection opened above");
+        let max_len = self.max_len.map(|max_len| max_len.to_string());
+        let delivery_id = event.delivery_id();
+        let at = event.at.to_rfc3339();
+        let body = event.body();
+        let mut args = vec!["XADD", self.stream.as_str()];
+        if let Some(max_len) = &max_len {
+            args.extend(["MAXLEN", "~", max_len.as_str()]);
+        }
+        args.extend([
+            "*",
+            "event",
+            event.kind.as_str(),
+            "delivery_id",
+            delivery_id.as_str(),
+            "subject",
+            event.subject.as_str(),
+            "at",
+            at.as_str(),
+            "body",
+            body.as_str(),
+        ]);
+        let result = redis_command(reader, &args).map(drop);
+        if result.is_err() {
+            self.connection = None;
+        }
+        result
+    }
+}
+
+impl EventSink for RedisStreamSink {
+    fn publish(&mut self, event: &PipelineEvent) -> Vec<SinkDelivery> {
+        if !event.kind.selected_by(&self.events) {
+            return Vec::new();
+        }
+        let target = format!("redis://{}/{}", self.address, self.stream);
+        deliver("redis_stream", target, event, || self.send(event))
+    }
+}
+
+/// Runs `send`, retrying once so a connection the server closed while idle
+/// is replaced instead of failing the event.
+fn deliver(
+    sink: &'static str,
+    target: String,
+    event: &PipelineEvent,
+    mut send: impl FnMut() -> Result<(), String>,
+) -> Vec<SinkDelivery> {
+    let mut delivery = SinkDelivery {
+        sink,
+        target,
+        kind: event.kind,
+        subject: event.subject.clone(),
+        attempts: 0,
+        delivered: false,
+        error: None,
+    };
+    for attempt in 1..=2 {
+        delivery.attempts = attempt;
+        match send() {
+            Ok(()) => {
+                delivery.delivered = true;
+                delivery.error = None;
+                break;
+            }
+            Err(err) => delivery.error = Some(err),
+        }
+    }
+    if !delivery.delivered {
+        tracing::warn!(
+            sink,
+            target = %delivery.target,
+            event = event.kind.as_str(),
+            error = delivery.error.as_deref().unwrap_or_default(),
+            "event sink publish failed"
+        );
+    }
+    vec![delivery]
+}
+
+fn write_all(reader: &mut BufReader<TcpStream>, bytes: &[u8]) -> Result<(), String> {
+    let stream = reader.get_mut();
+    stream
+        .write_all(bytes)
+        .and_then(|()| stream.flush())
+        .map_err(|err| err.to_string())
+}
+
+fn read_line(reader: &mut BufReader<TcpStream>) -> Result<String, String> {
+    let mut line = String::new();
+    match reader.read_line(&mut line) {
+        Ok(0) => Err("connection closed".to_string()),
+        Ok(_) => Ok(line.trim_end_matches(['\r', '\n']).to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn await_pong(reader: &mut BufReader<TcpStream>) -> Result<(), String> {
+    loop {
+        let line = read_line(reader)?;
+        match line.as_str() {
+            "PONG" => return Ok(()),
+            "PING" => write_all(reader, b"PONG\r\n")?,
+            "+OK" => {}
+            _ if line.starts_with("-ERR") => {
+                return Err(line.trim_start_matches("-ERR").trim().to_string())
+            }
+            // INFO updates and anything else unsolicited.
+            _ => {}
+        }
+    }
+}
+
+fn redis_command(reader: &mut BufReader<TcpStream>, args: &[&str]) -> Result<String, String> {
+    let mut frame = format!("*{}\r\n", args.len()).into_bytes();
+    for arg in args {
+        frame.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        frame.extend_from_slice(arg.as_bytes());
+        frame.extend_from_slice(b"\r\n");
+    }
+    write_all(reader, &frame)?;
+    let line = read_line(reader)?;
+    let (marker, rest) = line.split_at(line.len().min(1));
+    match marker {
+        "+" | ":" => Ok(rest.to_string()),
+        "-" => Err(rest.to_string()),
+        "$" => {
+            let len = rest
+                .parse::<i64>()
+                .map_err(|_| format!("bad bulk length: {rest}"))?;
+            if len < 0 {
+                return Ok(String::new());
+            }
+            let mut bulk = vec![0; len as usize + 2];
+            reader
+                .read_exact(&mut bulk)
+                .map_err(|err| err.to_string())?;
+            bulk.truncate(len as usize);
+            String::from_utf8(bulk).map_err(|err| err.to_string())
+        }
+        _ => Err(format!("unexpected Redis reply: {line}")),
+    }
+}
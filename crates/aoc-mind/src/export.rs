@@ -0,0 +1,404 @@
+use aoc_core::{ErrorCode, ErrorCoded};
+use aoc_storage::{ArtifactQuery, MindStore, StorageError, StoredArtifact};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+const EXPORT_STATE_FILE: &str = "export-state.json";
+const EXPORT_STATE_VERSION: u32 = 1;
+pub(crate) const PARQUET_DISABLED: &str =
+    "parquet support is not compiled in; rebuild with the `parquet` feature";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactExportFormat {
+    Jsonl,
+    Parquet,
+    Markdown,
+}
+
+impl ArtifactExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Jsonl => "jsonl",
+            Self::Parquet => "parquet",
+            Self::Markdown => "md",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum ArtifactExportScope {
+    Conversation(String),
+    Tag(String),
+    Segment(String),
+    All,
+}
+
+impl ArtifactExportScope {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Conversation(id) => format!("conversation {id}"),
+            Self::Tag(tag) => format!("tag {tag}"),
+            Self::Segment(segment) => format!("segment {segment}"),
+            Self::All => "all artifacts".to_string(),
+        }
+    }
+
+    fn query(&self) -> ArtifactQuery {
+        let mut query = ArtifactQuery {
+            oldest_first: true,
+            ..ArtifactQuery::default()
+        };
+        match self {
+            Self::Conversation(id) => query.conversation_id = Some(id.clone()),
+            Self::Tag(tag) => query.active_tag = Some(tag.clone()),
+            Self::Segment(segment) => query.segment_id = Some(segment.clone()),
+            Self::All => {}
+        }
+        query
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArtifactExportOptions {
+    /// Artifacts per output file; each file is written atomically.
+    pub chunk_size: usize,
+}
+
+impl Default for ArtifactExportOptions {
+    fn default() -> Self {
+        Self { chunk_size: 5_000 }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArtifactExportReport {
+    pub resumed: bool,
+    pub artifacts_exported: usize,
+    pub artifacts_total: usize,
+    pub chunks_written: Vec<PathBuf>,
+    pub chunks_skipped: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum ArtifactExportError {
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("serialization error: {0}")]
+    Serialization(String),
+    #[error("export state mismatch: {0}")]
+    StateMismatch(String),
+    #[error("unsupported export format: {0}")]
+    UnsupportedFormat(&'static str),
+}
+
+impl ErrorCoded for ArtifactExportError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Io(_) => ErrorCode::Io,
+            Self::Serialization(_) => ErrorCode::Serialization,
+            Self::StateMismatch(_) => ErrorCode::Conflict,
+            Self::UnsupportedFormat(_) => ErrorCode::BadRequest,
+        }
+    }
+}
+
+/// Resume checkpoint persisted next to the chunks after every completed file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ArtifactExportState {
+    version: u32,
+    scope: ArtifactExportScope,
+    format: ArtifactExportFormat,
+    chunk_size: usize,
+    chunks: usize,
+    exported: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ArtifactExportRecord<'a> {
+    artifact_id: &'a str,
+    kind: &'a str,
+    conversation_id: &'a str,
+    ts: String,
+    text: &'a str,
+    trace_ids: &'a [String],
+    segment_ids: Vec<String>,
+    task_ids: Vec<String>,
+}
+
+/// Writes the artifacts in `scope` to numbered chunk files under `out_dir`.
+///
+/// Artifacts are read oldest first, so rerunning against the same directory
+/// skips chunks recorded in `export-state.json` and only appends what is new
+/// or was interrupted.
+pub fn export_artifacts(
+    store: &MindStore,
+    scope: &ArtifactExportScope,
+    format: ArtifactExportFormat,
+    out_dir: &Path,
+    options: ArtifactExportOptions,
+) -> Result<ArtifactExportReport, ArtifactExportError> {
+    if format == ArtifactExportFormat::Parquet && !cfg!(feature = "parquet") {
+        return Err(ArtifactExportError::UnsupportedFormat(PARQUET_DISABLED));
+    }
+    let chunk_size = options.chunk_size.max(1);
+    fs::create_dir_all(out_dir)?;
+    let state_path = out_dir.join(EXPORT_STATE_FILE);
+
+    let mut report = ArtifactExportReport::default();
+    let mut state = match read_export_state(&state_path)? {
+        Some(state) => {
+            if state.scope != *scope || state.format != format || state.chunk_size != chunk_size {
+                return Err(ArtifactExportError::StateMismatch(format!(
+                    "{} holds a {:?} export of {} with chunk size {}; use a new --out directory",
+                    out_dir.display(),
+                    state.format,
+                    state.scope.label(),
+                    state.chunk_size
+                )));
+            }
+            report.resumed = true;
+            report.chunks_skipped = state.chunks;
+            state
+        }
+        None => ArtifactExportState {
+            version: EXPORT_STATE_VERSION,
+            scope: scope.clone(),
+            format,
+            chunk_size,
+            chunks: 0,
+            exported: 0,
+        },
+    };
+
+    let base_query = scope.query();
+    loop {
+        let page = store.query_artifacts(&ArtifactQuery {
+            offset: state.exported,
+            limit: chunk_size,
+            ..base_query.clone()
+        })?;
+        report.artifacts_total = page.total;
+        if page.artifacts.is_empty() {
+            break;
+        }
+
+        let chunk_path = out_dir.join(format!(
+            "artifacts-{:05}.{}",
+            state.chunks,
+            format.extension()
+        ));
+        let bytes = render_chunk(store, scope, format, state.chunks, &page.artifacts)?;
+        write_atomically(&chunk_path, &bytes)?;
+
+        state.chunks += 1;
+        state.exported += page.artifacts.len();
+        write_export_state(&state_path, &state)?;
+        report.artifacts_exported += page.artifacts.len();
+        report.chunks_written.push(chunk_path);
+    }
+
+    Ok(report)
+}
+
+fn render_chunk(
+    store: &MindStore,
+    scope: &ArtifactExportScope,
+    format: ArtifactExportFormat,
+    chunk_index: usize,
+    artifacts: &[StoredArtifact],
+) -> Result<Vec<u8>, ArtifactExportError> {
+    let mut segments = Vec::with_capacity(artifacts.len());
+    let mut tasks = Vec::with_capacity(artifacts.len());
+    for artifact in artifacts {
+        segments.push(
+            store
+                .segment_route_for_artifact(&artifact.artifact_id)?
+                .map(|route| {
+                    std::iter::once(route.primary)
+                        .chain(route.secondary)
+                        .map(|candidate| candidate.segment_id)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default(),
+        );
+        let mut task_ids = store
+            .artifact_task_links_for_artifact(&artifact.artifact_id)?
+            .into_iter()
+            .map(|link| link.task_id)
+            .collect::<Vec<_>>();
+        task_ids.sort();
+        task_ids.dedup();
+        tasks.push(task_ids);
+    }
+    let records = artifacts
+        .iter()
+        .zip(segments)
+        .zip(tasks)
+        .map(|((artifact, segment_ids), task_ids)| ArtifactExportRecord {
+            artifact_id: &artifact.artifact_id,
+            kind: &artifact.kind,
+            conversation_id: &artifact.conversation_id,
+            ts: artifact.ts.to_rfc3339(),
+            text: &artifact.text,
+            trace_ids: &artifact.trace_ids,
+            segment_ids,
+            task_ids,
+        })
+        .collect::<Vec<_>>();
+
+    match format {
+        ArtifactExportFormat::Jsonl => {
+            let mut out = Vec::new();
+            for record in &records {
+                serde_json::to_writer(&mut out, record)
+                    .map_err(|err| ArtifactExportError::Serialization(err.to_string()))?;
+                out.push(b'\n');
+            }
+            Ok(out)
+        }
+        ArtifactExportFormat::Markdown => {
+            Ok(render_markdown_chunk(scope, chunk_index, &records).into_bytes())
+        }
+        ArtifactExportFormat::Parquet => render_parquet_chunk(&records),
+    }
+}
+
+fn render_markdown_chunk(
+    scope: &ArtifactExportScope,
+    chunk_index: usize,
+    records: &[ArtifactExportRecord<'_>],
+) -> String {
+    let mut lines = vec![
+        format!("# Artifact export: {} (chunk {chunk_index})", scope.label()),
+        String::new(),
+    ];
+    for record in records {
+        lines.push(format!(
+            "## {} [{}] {} ({})",
+            record.artifact_id, record.kind, record.conversation_id, record.ts
+        ));
+        lines.push(record.text.trim().to_string());
+        if !record.segment_ids.is_empty() {
+            lines.push(format!("- segments: {}", record.segment_ids.join(", ")));
+        }
+        if !record.task_ids.is_empty() {
+            lines.push(format!("- tasks: {}", record.task_ids.join(", ")));
+        }
+        if !record.trace_ids.is_empty() {
+            lines.push(format!("- trace: {}", record.trace_ids.join(", ")));
+        }
+        lines.push(String::new());
+    }
+    lines.join("\n")
+}
+
+#[cfg(feature = "parquet")]
+fn render_parquet_chunk(
+    records: &[ArtifactExportRecord<'_>],
+) -> Result<Vec<u8>, ArtifactExportError> {
+    use parquet::data_type::{ByteArray, ByteArrayType};
+    use parquet::file::{properties::WriterProperties, writer::SerializedFileWriter};
+    use parquet::schema::parser::parse_message_type;
+    use std::sync::Arc;
+
+    const SCHEMA: &str = "
+        message mind_artifact {
+            REQUIRED BYTE_ARRAY artifact_id (UTF8);
+            REQUIRED BYTE_ARRAY kind (UTF8);
+            REQUIRED BYTE_ARRAY conversation_id (UTF8);
+            REQUIRED BYTE_ARRAY ts (UTF8);
+            REQUIRED BYTE_ARRAY text (UTF8);
+            REQUIRED BYTE_ARRAY trace_ids_json (UTF8);
+            REQUIRED BYTE_ARRAY segment_ids_json (UTF8);
+            REQUIRED BYTE_ARRAY task_ids_json (UTF8);
+        }
+    ";
+    let parquet_err = |err: parquet::errors::ParquetError| {
+        ArtifactExportError::Serialization(format!("parquet: {err}"))
+    };
+    let json_list = |values: &[String]| {
+        serde_json::to_string(values)
+            .map(|json| ByteArray::from(json.into_bytes()))
+            .map_err(|err| ArtifactExportError::Serialization(err.to_string()))
+    };
+
+    let mut columns: Vec<Vec<ByteArray>> =
+        (0..8).map(|_| Vec::with_capacity(records.len())).collect();
+    for record in records {
+        columns[0].push(ByteArray::from(record.artifact_id));
+        columns[1].push(ByteArray::from(record.kind));
+        columns[2].push(ByteArray::from(record.conversation_id));
+        columns[3].push(ByteArray::from(record.ts.as_str()));
+        columns[4].push(ByteArray::from(record.text));
+        columns[5].push(json_list(record.trace_ids)?);
+        columns[6].push(json_list(&record.segment_ids)?);
+        columns[7].push(json_list(&record.task_ids)?);
+    }
+
+    let schema = Arc::new(parse_message_type(SCHEMA).map_err(parquet_err)?);
+    let mut out = Vec::new();
+    let mut writer = SerializedFileWriter::new(
+        &mut out,
+        schema,
+        Arc::new(WriterProperties::builder().build()),
+    )
+    .map_err(parquet_err)?;
+    let mut row_group = writer.next_row_group().map_err(parquet_err)?;
+    let mut values = columns.into_iter();
+    while let Some(mut column) = row_group.next_column().map_err(parquet_err)? {
+        let batch = values.next().unwrap_or_default();
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(&batch, None, None)
+            .map_err(parquet_err)?;
+        column.close().map_err(parquet_err)?;
+    }
+    row_group.close().map_err(parquet_err)?;
+    writer.close().map_err(parquet_err)?;
+    Ok(out)
+}
+
+#[cfg(not(feature = "parquet"))]
+fn render_parquet_chunk(
+    _records: &[ArtifactExportRecord<'_>],
+) -> Result<Vec<u8>, ArtifactExportError> {
+    Err(ArtifactExportError::UnsupportedFormat(PARQUET_DISABLED))
+}
+
+fn read_export_state(path: &Path) -> Result<Option<ArtifactExportState>, ArtifactExportError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(path)?;
+    let state: ArtifactExportState = serde_json::from_str(&raw)
+        .map_err(|err| ArtifactExportError::Serialization(err.to_string()))?;
+    if state.version != EXPORT_STATE_VERSION {
+        return Err(ArtifactExportError::StateMismatch(format!(
+            "unsupported export state version {}",
+            state.version
+        )));
+    }
+    Ok(Some(state))
+}
+
+fn write_export_state(path: &Path, state: &ArtifactExportState) -> Result<(), ArtifactExportError> {
+    let payload = serde_json::to_vec_pretty(state)
+        .map_err(|err| ArtifactExportError::Serialization(err.to_string()))?;
+    write_atomically(path, &payload)
+}
+
+pub(crate) fn write_atomically(path: &Path, bytes: &[u8]) -> Result<(), ArtifactExportError> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, bytes)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
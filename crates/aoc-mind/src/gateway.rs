@@ -0,0 +1,365 @@
+//! Observer and reflector calls through an OpenAI-compatible gateway
+//! (OpenRouter, LiteLLM).
+//!
+//! `[observer.gateway]` in aoc.toml points the semantic observer at a
+//! gateway's `/chat/completions`. Profiles name models by alias and the
+//! gateway config maps aliases to gateway model ids, so switching a profile
+//! between providers is a config edit. Routing headers are sent on every
+//! request. Prices come from the gateway's model listing, fetched once per
+//! invoker, with `costs` entries taking precedence; usage falls back to the
+//! flat per-token estimate for models neither of them prices.
+//!
+//! The config and price parsing are always built; the HTTP invoker needs the
+//! `semantic` feature.
+
+#[cfg(feature = "semantic")]
+use crate::{PiObserverInvoker, PiReflectorInvoker};
+#[cfg(feature = "semantic")]
+use aoc_core::mind_contracts::{
+    observer_prompt_supports_sections, SemanticAdapterError, SemanticFailureKind,
+    SemanticGuardrails, SemanticModelProfile,
+};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "semantic")]
+use serde_json::json;
+use serde_json::Value;
+use std::collections::BTreeMap;
+#[cfg(feature = "semantic")]
+use std::{sync::OnceLock, time::Duration};
+
+/// Listing that OpenRouter and most OpenAI-compatible gateways serve;
+/// LiteLLM only publishes prices under `/model/info`.
+pub const DEFAULT_GATEWAY_COSTS_PATH: &str = "/models";
+
+#[cfg(feature = "semantic")]
+const COSTS_TIMEOUT: Duration = Duration::from_secs(10);
+#[cfg(feature = "semantic")]
+const OBSERVER_SYSTEM_PROMPT: &str = "You distill coding-agent transcripts. The user message is \
+a JSON observer input. Reply with only a JSON object: {\"summary\": string, \"key_points\": \
+[string], \"citations\": [string]}. Cite event ids from the input.";
+#[cfg(feature = "semantic")]
+const OBSERVER_SECTIONS_SYSTEM_PROMPT: &str = "You distill coding-agent transcripts. The user \
+message is a JSON observer input. Reply with only a JSON object: {\"summary\": string, \
+\"key_points\": [string], \"decisions\": [string], \"open_questions\": [string], \"risks\": \
+[string], \"action_items\": [{\"text\": string, \"task_ref\": string or omitted}], \
+\"citations\": [string]}. Leave a section empty when the transcript has nothing for it. Cite \
+event ids from the input.";
+#[cfg(feature = "semantic")]
+const REFLECTOR_SYSTEM_PROMPT: &str = "You consolidate observations of coding-agent work on one \
+workstream. The user message is a JSON reflector input. Reply with only a JSON object: \
+{\"reflection\": string, \"action_items\": [string], \"citations\": [string]}. Cite \
+observation ids from the input.";
+
+/// `[observer.gateway]` in aoc.toml.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GatewayConfig {
+    /// API root, e.g. `https://openrouter.ai/api/v1` or `http://localhost:4000`.
+    pub base_url: String,
+    /// Environment variable holding the bearer token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// Extra headers on every request, e.g. OpenRouter's `X-Title` or
+    /// LiteLLM's `x-litellm-tags`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Profile `model_id` -> gateway model id. Unmapped ids are sent as is.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub model_aliases: BTreeMap<String, String>,
+    /// Prices by alias or gateway model id; these win over fetched ones.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub costs: BTreeMap<String, ModelCost>,
+    /// Path under `base_url` that lists model prices.
+    #[serde(default = "default_costs_path")]
+    pub costs_path: String,
+    /// Set to false for gateways whose listing carries no prices.
+    #[serde(default = "default_fetch_costs")]
+    pub fetch_costs: bool,
+}
+
+fn default_costs_path() -> String {
+    DEFAULT_GATEWAY_COSTS_PATH.to_string()
+}
+
+fn default_fetch_costs() -> bool {
+    true
+}
+
+impl GatewayConfig {
+    /// Checks the URL and alias shape without connecting.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.base_url.starts_with("https://") || self.base_url.starts_with("http://")) {
+            return Err("base_url must be an http:// or https:// URL".to_string());
+        }
+        if let Some((alias, _)) = self
+            .model_aliases
+            .iter()
+            .find(|(alias, model)| alias.trim().is_empty() || model.trim().is_empty())
+        {
+            return Err(format!(
+                "model_aliases entry '{alias}' must map non-empty ids"
+            ));
+        }
+        if let Some((model, _)) = self.costs.iter().find(|(_, cost)| !cost.is_valid()) {
+            return Err(format!("costs.{model} must be finite and not negative"));
+        }
+        Ok(())
+    }
+
+    /// The gateway model id a profile `model_id` is sent as.
+    pub fn resolve_model<'a>(&'a self, model_id: &'a str) -> &'a str {
+        self.model_aliases
+            .get(model_id)
+            .map_or(model_id, String::as_str)
+    }
+
+    #[cfg(feature = "semantic")]
+    fn url(&self, path: &str) -> String {
+        format!(
+            "{}/{}",
+            self.base_url.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }
+}
+
+/// Price of a model in USD per million tokens, the unit gateways quote.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelCost {
+    pub input_usd_per_mtok: f64,
+    pub output_usd_per_mtok: f64,
+}
+
+impl ModelCost {
+    /// USD per million tokens times tokens is exactly micro-dollars.
+    pub fn cost_micros(&self, input_tokens: u32, output_tokens: u32) -> u64 {
+        let micros = f64::from(input_tokens) * self.input_usd_per_mtok
+            + f64::from(output_tokens) * self.output_usd_per_mtok;
+        micros.round().max(0.0) as u64
+    }
+
+    fn is_valid(&self) -> bool {
+        [self.input_usd_per_mtok, self.output_usd_per_mtok]
+            .iter()
+            .all(|price| price.is_finite() && *price >= 0.0)
+    }
+}
+
+/// Reads a model listing into prices keyed by model id. Understands
+/// OpenRouter's `pricing.prompt`/`pricing.completion` and LiteLLM's
+/// `input_cost_per_token`/`output_cost_per_token` (top level or under
+/// `model_info`), both in USD per token; entries without prices are skipped.
+pub fn parse_gateway_costs(listing: &Value) -> BTreeMap<String, ModelCost> {
+    let per_token = |value: &Value| match value {
+        Value::String(text) => text.trim().parse::<f64>().ok(),
+        other => other.as_f64(),
+    };
+    let mut costs = BTreeMap::new();
+    for model in listing["data"].as_array().into_iter().flatten() {
+        let Some(id) = model["id"]
+            .as_str()
+            .or_else(|| model["model_name"].as_str())
+        else {
+            continue;
+        };
+        let info = if model["model_info"].is_object() {
+            &model["model_info"]
+        } else {
+            model
+        };
+        let prices = per_token(&model["pricing"]["prompt"])
+            .zip(per_token(&model["pricing"]["completion"]))
+            .or_else(|| {
+                per_token(&info["input_cost_per_token"])
+                    .zip(per_token(&info["output_cost_per_token"]))
+            });
+        let Some((input, output)) = prices else {
+            continue;
+        };
+        let cost = ModelCost {
+            input_usd_per_mtok: input * 1_000_000.0,
+            output_usd_per_mtok: output * 1_000_000.0,
+        };
+        if cost.is_valid() {
+            costs.insert(id.to_string(), cost);
+        }
+    }
+    costs
+}
+
+/// [`PiObserverInvoker`] and [`PiReflectorInvoker`] that call a gateway's
+/// chat completions endpoint.
+#[cfg(feature = "semantic")]
+pub struct GatewayObserverInvoker {
+    config: GatewayConfig,
+    agent: ureq::Agent,
+    api_key: Option<String>,
+    fetched_costs: OnceLock<BTreeMap<String, ModelCost>>,
+}
+
+#[cfg(feature = "semantic")]
+impl GatewayObserverInvoker {
+    pub fn new(config: GatewayConfig) -> Self {
+        let api_key = config
+            .api_key_env
+            .as_deref()
+            .and_then(|name| std::env::var(name).ok())
+            .filter(|key| !key.trim().is_empty());
+        Self {
+            config,
+            agent: ureq::AgentBuilder::new().build(),
+            api_key,
+            fetched_costs: OnceLock::new(),
+        }
+    }
+
+    pub fn config(&self) -> &GatewayConfig {
+        &self.config
+    }
+
+    /// Configured price for `model_id` or its gateway id, else the gateway's.
+    pub fn model_cost(&self, model_id: &str) -> Option<ModelCost> {
+        let resolved = self.config.resolve_model(model_id);
+        self.config
+            .costs
+            .get(model_id)
+            .or_else(|| self.config.costs.get(resolved))
+            .copied()
+            .or_else(|| self.fetched_costs().get(resolved).copied())
+    }
+
+    /// Fetched once; an unreachable or unpriced listing leaves it empty so
+    /// usage falls back to the estimate instead of failing the observer.
+    fn fetched_costs(&self) -> &BTreeMap<String, ModelCost> {
+        self.fetched_costs.get_or_init(|| {
+            if !self.config.fetch_costs {
+                return BTreeMap::new();
+            }
+            self.request("GET", &self.config.costs_path)
+                .timeout(COSTS_TIMEOUT)
+                .call()
+                .ok()
+                .and_then(|response| response.into_string().ok())
+                .and_then(|body| serde_json::from_str::<Value>(&body).ok())
+                .map(|listing| parse_gateway_costs(&listing))
+                .unwrap_or_default()
+        })
+    }
+
+    /// One chat completion; the reply's message content is the raw output.
+    fn complete(
+        &self,
+        system_prompt: &str,
+        canonical_input_json: &str,
+        profile: &SemanticModelProfile,
+        guardrails: &SemanticGuardrails,
+    ) -> Result<String, SemanticAdapterError> {
+        let body = json!({
+            "model": self.config.resolve_model(&profile.model_id),
+            "max_tokens": profile.max_output_tokens,
+            "response_format": { "type": "json_object" },
+            "messages": [
+                { "role": "system", "content": system_prompt },
+                { "role": "user", "content": canonical_input_json },
+            ],
+        });
+        let response = self
+            .request("POST", "/chat/completions")
+            .timeout(Duration::from_millis(guardrails.timeout_ms.max(1)))
+            .set("Content-Type", "application/json")
+            .send_string(&body.to_string())
+            .map_err(|err| match err {
+                ureq::Error::Status(status, response) => SemanticAdapterError::new(
+                    SemanticFailureKind::ProviderError,
+                    format!(
+                        "gateway answered {status}: {}",
+                        response.into_string().unwrap_or_default().trim()
+                    ),
+                ),
+                ureq::Error::Transport(transport) => {
+                    let message = transport.to_string();
+                    let kind = if message.contains("timed out") {
+                        SemanticFailureKind::Timeout
+                    } else {
+                        SemanticFailureKind::ProviderError
+                    };
+                    SemanticAdapterError::new(kind, format!("gateway request failed: {message}"))
+                }
+            })?;
+        let completion = response
+            .into_string()
+            .map_err(|err| err.to_string())
+            .and_then(|body| serde_json::from_str::<Value>(&body).map_err(|err| err.to_string()))
+            .map_err(|err| {
+                SemanticAdapterError::new(
+                    SemanticFailureKind::InvalidOutput,
+                    format!("gateway response is not JSON: {err}"),
+                )
+            })?;
+        completion["choices"][0]["message"]["content"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| {
+                SemanticAdapterError::new(
+                    SemanticFailureKind::InvalidOutput,
+                    "gateway response has no message content",
+                )
+            })
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        let mut request = self.agent.request(method, &self.config.url(path));
+        if let Some(key) = &self.api_key {
+            request = request.set("Authorization", &format!("Bearer {key}"));
+        }
+        for (name, value) in &self.config.headers {
+            request = request.set(name, value);
+        }
+        request
+    }
+}
+
+#[cfg(feature = "semantic")]
+impl PiObserverInvoker for GatewayObserverInvoker {
+    fn invoke_observer(
+        &self,
+        canonical_input_json: &str,
+        profile: &SemanticModelProfile,
+        guardrails: &SemanticGuardrails,
+    ) -> Result<String, SemanticAdapterError> {
+        let system_prompt = if observer_prompt_supports_sections(&profile.prompt_version) {
+            OBSERVER_SECTIONS_SYSTEM_PROMPT
+        } else {
+            OBSERVER_SYSTEM_PROMPT
+        };
+        self.complete(system_prompt, canonical_input_json, profile, guardrails)
+    }
+
+    fn cost_micros(&self, model_id: &str, input_tokens: u32, output_tokens: u32) -> Option<u64> {
+        self.model_cost(model_id)
+            .map(|cost| cost.cost_micros(input_tokens, output_tokens))
+    }
+}
+
+#[cfg(feature = "semantic")]
+impl PiReflectorInvoker for GatewayObserverInvoker {
+    fn invoke_reflector(
+        &self,
+        canonical_input_json: &str,
+        profile: &SemanticModelProfile,
+        guardrails: &SemanticGuardrails,
+    ) -> Result<String, SemanticAdapterError> {
+        self.complete(
+            REFLECTOR_SYSTEM_PROMPT,
+            canonical_input_json,
+            profile,
+            guardrails,
+        )
+    }
+
+    fn cost_micros(&self, model_id: &str, input_tokens: u32, output_tokens: u32) -> Option<u64> {
+        self.model_cost(model_id)
+            .map(|cost| cost.cost_micros(input_tokens, output_tokens))
+    }
+}
@@ -0,0 +1,433 @@
+use aoc_storage::{ArtifactQuery, MindStore, StorageError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+const PAGE_SIZE: usize = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphExportFormat {
+    Dot,
+    Graphml,
+    Json,
+}
+
+impl GraphExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Dot => "dot",
+            Self::Graphml => "graphml",
+            Self::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphNodeKind {
+    Conversation,
+    Artifact,
+    Task,
+    Segment,
+    Canon,
+    Decision,
+}
+
+impl GraphNodeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Conversation => "conversation",
+            Self::Artifact => "artifact",
+            Self::Task => "task",
+            Self::Segment => "segment",
+            Self::Canon => "canon",
+            Self::Decision => "decision",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphEdgeKind {
+    /// Artifact to the conversation it was distilled from.
+    InConversation,
+    /// Reflection to observation, or canon revision to evidence artifact.
+    Trace,
+    /// Artifact to task, any link relation (kept in the `relation` attr).
+    WorkedOn,
+    /// Artifact or decision to segment.
+    RoutedTo,
+    /// Canon entry or decision to the one it replaced.
+    Supersedes,
+}
+
+impl GraphEdgeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::InConversation => "in_conversation",
+            Self::Trace => "trace",
+            Self::WorkedOn => "worked_on",
+            Self::RoutedTo => "routed_to",
+            Self::Supersedes => "supersedes",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// `<kind>:<id>`, unique across kinds.
+    pub id: String,
+    pub kind: GraphNodeKind,
+    pub label: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attrs: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: GraphEdgeKind,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attrs: BTreeMap<String, String>,
+}
+
+/// Which part of the mind [`build_knowledge_graph`] walks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphScope {
+    /// Only artifacts from conversations that recorded this Taskmaster tag.
+    pub tag: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// Project whose decisions are included (see `project_scope_key`).
+    pub project_id: Option<String>,
+}
+
+impl GraphScope {
+    fn contains(&self, ts: DateTime<Utc>) -> bool {
+        self.since.is_none_or(|since| ts >= since) && self.until.is_none_or(|until| ts < until)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnowledgeGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+#[derive(Default)]
+struct GraphBuilder {
+    nodes: BTreeMap<String, GraphNode>,
+    edges: BTreeSet<(String, String, GraphEdgeKind)>,
+    edge_attrs: BTreeMap<(String, String, GraphEdgeKind), BTreeMap<String, String>>,
+}
+
+impl GraphBuilder {
+    fn node(&mut self, kind: GraphNodeKind, id: &str, label: &str) -> String {
+        let node_id = format!("{}:{id}", kind.as_str());
+        self.nodes
+            .entry(node_id.clone())
+            .or_insert_with(|| GraphNode {
+                id: node_id.clone(),
+                kind,
+                label: label.to_string(),
+                attrs: BTreeMap::new(),
+            });
+        node_id
+    }
+
+    fn attr(&mut self, node_id: &str, key: &str, value: impl Into<String>) {
+        if let Some(node) = self.nodes.get_mut(node_id) {
+            node.attrs.insert(key.to_string(), value.into());
+        }
+    }
+
+    fn edge(&mut self, from: &str, to: &str, kind: GraphEdgeKind) {
+        self.edges.insert((from.to_string(), to.to_string(), kind));
+    }
+
+    fn edge_attr(&mut self, from: &str, to: &str, kind: GraphEdgeKind, key: &str, value: String) {
+        self.edge_attrs
+            .entry((from.to_string(), to.to_string(), kind))
+            .or_default()
+            .insert(key.to_string(), value);
+    }
+
+    fn finish(mut self) -> KnowledgeGraph {
+        let edges = self
+            .edges
+            .into_iter()
+            .filter(|(from, to, _)| self.nodes.contains_key(from) && self.nodes.contains_key(to))
+            .map(|key| GraphEdge {
+                attrs: self.edge_attrs.remove(&key).unwrap_or_default(),
+                from: key.0,
+                to: key.1,
+                kind: key.2,
+            })
+            .collect();
+        KnowledgeGraph {
+            nodes: self.nodes.into_values().collect(),
+            edges,
+        }
+    }
+}
+
+fn short_label(text: &str) -> String {
+    const MAX: usize = 60;
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() <= MAX {
+        flat
+    } else {
+        format!("{}...", flat.chars().take(MAX).collect::<String>())
+    }
+}
+
+fn enum_label<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Collects artifacts in `scope` with their conversations, tasks, and
+/// segments, then the canon revisions and decisions that touch them.
+///
+/// Edges only join nodes in the graph: a trace to an artifact outside the
+/// scope, or to T0 events, is dropped rather than dangling.
+pub fn build_knowledge_graph(
+    store: &MindStore,
+    scope: &GraphScope,
+) -> Result<KnowledgeGraph, StorageError> {
+    let mut graph = GraphBuilder::default();
+    let mut artifact_nodes = BTreeSet::new();
+    let mut segment_nodes = BTreeSet::new();
+
+    let mut offset = 0;
+    loop {
+        let page = store.query_artifacts(&ArtifactQuery {
+            active_tag: scope.tag.clone(),
+            since: scope.since,
+            oldest_first: true,
+            offset,
+            limit: PAGE_SIZE,
+            ..ArtifactQuery::default()
+        })?;
+        let next = page.next_offset();
+        offset += page.artifacts.len();
+        for artifact in page.artifacts {
+            if !scope.contains(artifact.ts) {
+                continue;
+            }
+            let node = graph.node(
+                GraphNodeKind::Artifact,
+                &artifact.artifact_id,
+                &short_label(&artifact.text),
+            );
+            graph.attr(&node, "tier", artifact.kind.clone());
+            graph.attr(&node, "ts", artifact.ts.to_rfc3339());
+            artifact_nodes.insert(artifact.artifact_id.clone());
+
+            let conversation = graph.node(
+                GraphNodeKind::Conversation,
+                &artifact.conversation_id,
+                &artifact.conversation_id,
+            );
+            graph.edge(&node, &conversation, GraphEdgeKind::InConversation);
+
+            for trace_id in &artifact.trace_ids {
+                let target = format!("{}:{trace_id}", GraphNodeKind::Artifact.as_str());
+                graph.edge(&node, &target, GraphEdgeKind::Trace);
+            }
+
+            for link in store.artifact_task_links_for_artifact(&artifact.artifact_id)? {
+                let task = graph.node(GraphNodeKind::Task, &link.task_id, &link.task_id);
+                graph.edge(&node, &task, GraphEdgeKind::WorkedOn);
+                graph.edge_attr(
+                    &node,
+                    &task,
+                    GraphEdgeKind::WorkedOn,
+                    "relation",
+                    enum_label(&link.relation),
+                );
+            }
+
+            if let Some(route) = store.segment_route_for_artifact(&artifact.artifact_id)? {
+                let candidates = std::iter::once((true, route.primary)).chain(
+                    route
+                        .secondary
+                        .into_iter()
+                        .map(|candidate| (false, candidate)),
+                );
+                for (primary, candidate) in candidates {
+                    let segment = graph.node(
+                        GraphNodeKind::Segment,
+                        &candidate.segment_id,
+                        &candidate.segment_id,
+                    );
+                    segment_nodes.insert(candidate.segment_id.clone());
+                    graph.edge(&node, &segment, GraphEdgeKind::RoutedTo);
+                    graph.edge_attr(
+                        &node,
+                        &segment,
+                        GraphEdgeKind::RoutedTo,
+                        "rank",
+                        if primary { "primary" } else { "secondary" }.to_string(),
+                    );
+                }
+            }
+        }
+        if next.is_none() {
+            break;
+        }
+    }
+
+    let scoped = scope.tag.is_some() || scope.since.is_some() || scope.until.is_some();
+    let mut latest_canon = BTreeMap::new();
+    for revision in store.canon_revision_history(None, i64::MAX as usize)? {
+        latest_canon
+            .entry(revision.entry_id.clone())
+            .or_insert(revision);
+    }
+    for revision in latest_canon.into_values() {
+        let cites_scope = revision
+            .evidence_refs
+            .iter()
+            .any(|evidence| artifact_nodes.contains(evidence));
+        if scoped && !(cites_scope && scope.contains(revision.created_at)) {
+            continue;
+        }
+        let node = graph.node(
+            GraphNodeKind::Canon,
+            &revision.entry_id,
+            &short_label(&revision.summary),
+        );
+        graph.attr(&node, "revision", revision.revision.to_string());
+        graph.attr(&node, "state", revision.state.as_str());
+        if let Some(topic) = revision.topic.as_deref() {
+            graph.attr(&node, "topic", topic);
+        }
+        for evidence in &revision.evidence_refs {
+            let target = format!("{}:{evidence}", GraphNodeKind::Artifact.as_str());
+            graph.edge(&node, &target, GraphEdgeKind::Trace);
+        }
+        if let Some(superseded) = revision.supersedes_entry_id.as_deref() {
+            let target = format!("{}:{superseded}", GraphNodeKind::Canon.as_str());
+            graph.edge(&node, &target, GraphEdgeKind::Supersedes);
+        }
+    }
+
+    if let Some(project_id) = scope.project_id.as_deref() {
+        for decision in store.mem_decisions(project_id, None, true)? {
+            if !scope.contains(decision.ts) {
+                continue;
+            }
+            if scope.tag.is_some()
+                && !decision
+                    .segment_id
+                    .as_ref()
+                    .is_some_and(|segment| segment_nodes.contains(segment))
+            {
+                continue;
+            }
+            let node = graph.node(
+                GraphNodeKind::Decision,
+                &decision.decision_id,
+                &short_label(&decision.text),
+            );
+            graph.attr(&node, "ts", decision.ts.to_rfc3339());
+            if let Some(segment_id) = decision.segment_id.as_deref() {
+                let segment = graph.node(GraphNodeKind::Segment, segment_id, segment_id);
+                graph.edge(&node, &segment, GraphEdgeKind::RoutedTo);
+            }
+            if let Some(superseded) = decision.supersedes_id.as_deref() {
+                let target = format!("{}:{superseded}", GraphNodeKind::Decision.as_str());
+                graph.edge(&node, &target, GraphEdgeKind::Supersedes);
+            }
+        }
+    }
+
+    Ok(graph.finish())
+}
+
+impl KnowledgeGraph {
+    pub fn render(&self, format: GraphExportFormat) -> String {
+        match format {
+            GraphExportFormat::Dot => self.render_dot(),
+            GraphExportFormat::Graphml => self.render_graphml(),
+            GraphExportFormat::Json => {
+                serde_json::to_string_pretty(self).expect("graph serializes to JSON")
+            }
+        }
+    }
+
+    fn render_dot(&self) -> String {
+        fn quote(value: &str) -> String {
+            format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+        let mut out = String::from("digraph mind {\n  rankdir=LR;\n  node [style=filled];\n");
+        for node in &self.nodes {
+            let (shape, color) = match node.kind {
+                GraphNodeKind::Conversation => ("folder", "lightgrey"),
+                GraphNodeKind::Artifact => ("note", "lightyellow"),
+                GraphNodeKind::Task => ("box", "lightblue"),
+                GraphNodeKind::Segment => ("hexagon", "palegreen"),
+                GraphNodeKind::Canon => ("doubleoctagon", "gold"),
+                GraphNodeKind::Decision => ("diamond", "salmon"),
+            };
+            out.push_str(&format!(
+                "  {} [label={}, kind={}, shape={shape}, fillcolor={color}];\n",
+                quote(&node.id),
+                quote(&node.label),
+                node.kind.as_str()
+            ));
+        }
+        for edge in &self.edges {
+            out.push_str(&format!(
+                "  {} -> {} [label={}];\n",
+                quote(&edge.from),
+                quote(&edge.to),
+                edge.kind.as_str()
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn render_graphml(&self) -> String {
+        fn escape(value: &str) -> String {
+            value
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;")
+        }
+        let mut out = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n  \
+             <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>\n  \
+             <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n  \
+             <key id=\"edge_kind\" for=\"edge\" attr.name=\"kind\" attr.type=\"string\"/>\n  \
+             <graph id=\"mind\" edgedefault=\"directed\">\n",
+        );
+        for node in &self.nodes {
+            out.push_str(&format!(
+                "    <node id=\"{}\">\n      <data key=\"kind\">{}</data>\n      \
+                 <data key=\"label\">{}</data>\n    </node>\n",
+                escape(&node.id),
+                node.kind.as_str(),
+                escape(&node.label)
+            ));
+        }
+        for (index, edge) in self.edges.iter().enumerate() {
+            out.push_str(&format!(
+                "    <edge id=\"e{index}\" source=\"{}\" target=\"{}\">\n      \
+                 <data key=\"edge_kind\">{}</data>\n    </edge>\n",
+                escape(&edge.from),
+                escape(&edge.to),
+                edge.kind.as_str()
+            ));
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+}
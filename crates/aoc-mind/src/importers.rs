@@ -0,0 +1,273 @@
+//! Importers for memory exports from other agent-memory systems.
+//!
+//! Each imported memory lands as a system message raw event plus a T1
+//! observation traced to it, so imported knowledge is retrievable and passes
+//! doctor's provenance checks like anything distilled locally.
+
+use aoc_core::mind_contracts::{ConversationRole, MessageEvent, RawEvent, RawEventBody};
+use aoc_core::{ErrorCode, ErrorCoded};
+use aoc_storage::{MindStore, StorageError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+pub const IMPORT_SOURCE_ATTR: &str = "import_source";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThirdPartySource {
+    /// mem0 `get_all`/export JSON: a list of memories or `{"results": [...]}`.
+    Mem0,
+    /// Letta agent file (`.af`): core memory blocks and archival passages.
+    Letta,
+}
+
+impl ThirdPartySource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Mem0 => "mem0",
+            Self::Letta => "letta",
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ThirdPartyImportError {
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
+    #[error("invalid {format} export: {detail}")]
+    InvalidExport {
+        format: &'static str,
+        detail: String,
+    },
+}
+
+impl ErrorCoded for ThirdPartyImportError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::InvalidExport { .. } => ErrorCode::InvalidInput,
+        }
+    }
+}
+
+/// One memory pulled out of a third-party export.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportedMemory {
+    /// Stable across re-imports of the same export; prefixed with the source.
+    pub memory_id: String,
+    pub conversation_id: String,
+    pub agent_id: String,
+    pub ts: DateTime<Utc>,
+    pub text: String,
+}
+
+impl ImportedMemory {
+    pub fn event_id(&self) -> String {
+        format!("import:{}", self.memory_id)
+    }
+
+    pub fn artifact_id(&self) -> String {
+        format!("obs:import:{}", self.memory_id)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThirdPartyImportReport {
+    pub memories: usize,
+    pub imported: usize,
+    /// Memories already present from an earlier import.
+    pub duplicates: usize,
+    pub conversations: usize,
+    pub dry_run: bool,
+}
+
+/// Parses an export into memories. `fallback_ts` stamps entries that carry no
+/// usable timestamp of their own.
+pub fn parse_third_party_export(
+    source: ThirdPartySource,
+    body: &str,
+    fallback_ts: DateTime<Utc>,
+) -> Result<Vec<ImportedMemory>, ThirdPartyImportError> {
+    let invalid = |detail: String| ThirdPartyImportError::InvalidExport {
+        format: source.as_str(),
+        detail,
+    };
+    let value: Value = serde_json::from_str(body).map_err(|err| invalid(err.to_string()))?;
+    let memories = match source {
+        ThirdPartySource::Mem0 => parse_mem0(&value, fallback_ts),
+        ThirdPartySource::Letta => parse_letta(&value, fallback_ts),
+    }
+    .map_err(invalid)?;
+    Ok(memories)
+}
+
+/// Writes parsed memories into `store`, skipping ones a previous import
+/// already created.
+pub fn import_third_party_memories(
+    store: &MindStore,
+    source: ThirdPartySource,
+    memories: &[ImportedMemory],
+    dry_run: bool,
+) -> Result<ThirdPartyImportReport, ThirdPartyImportError> {
+    let mut report = ThirdPartyImportReport {
+        memories: memories.len(),
+        dry_run,
+        ..ThirdPartyImportReport::default()
+    };
+    let mut conversations = BTreeMap::new();
+    for memory in memories {
+        if store.artifact_by_id(&memory.artifact_id())?.is_some() {
+            report.duplicates += 1;
+            continue;
+        }
+        *conversations
+            .entry(memory.conversation_id.as_str())
+            .or_insert(0) += 1;
+        report.imported += 1;
+        if dry_run {
+            continue;
+        }
+        let event = RawEvent {
+            event_id: memory.event_id(),
+            conversation_id: memory.conversation_id.clone(),
+            agent_id: memory.agent_id.clone(),
+            ts: memory.ts,
+            body: RawEventBody::Message(MessageEvent {
+                role: ConversationRole::System,
+                text: memory.text.clone(),
+            }),
+            attrs: BTreeMap::from([(
+                IMPORT_SOURCE_ATTR.to_string(),
+                Value::String(source.as_str().to_string()),
+            )]),
+        };
+        store.insert_raw_event(&event)?;
+        store.insert_observation(
+            &memory.artifact_id(),
+            &memory.conversation_id,
+            memory.ts,
+            &memory.text,
+            &[event.event_id],
+        )?;
+    }
+    report.conversations = conversations.len();
+    tracing::info!(
+        stage = "import",
+        source = source.as_str(),
+        imported = report.imported,
+        duplicates = report.duplicates,
+        dry_run,
+        "third-party memory import"
+    );
+    Ok(report)
+}
+
+fn parse_mem0(value: &Value, fallback_ts: DateTime<Utc>) -> Result<Vec<ImportedMemory>, String> {
+    let entries = value
+        .as_array()
+        .or_else(|| value.get("results").and_then(Value::as_array))
+        .or_else(|| value.get("memories").and_then(Value::as_array))
+        .ok_or("expected a list of memories or an object with `results`")?;
+    let mut memories = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let Some(text) = str_field(entry, &["memory", "text"]) else {
+            continue;
+        };
+        let id = str_field(entry, &["id"])
+            .map(str::to_string)
+            .unwrap_or_else(|| index.to_string());
+        let owner = str_field(entry, &["user_id", "agent_id", "run_id"]).unwrap_or("default");
+        memories.push(ImportedMemory {
+            memory_id: format!("mem0:{id}"),
+            conversation_id: format!("mem0:{owner}"),
+            agent_id: str_field(entry, &["agent_id"])
+                .unwrap_or("mem0")
+                .to_string(),
+            ts: ts_field(entry, &["updated_at", "created_at"]).unwrap_or(fallback_ts),
+            text: text.to_string(),
+        });
+    }
+    Ok(memories)
+}
+
+fn parse_letta(value: &Value, fallback_ts: DateTime<Utc>) -> Result<Vec<ImportedMemory>, String> {
+    let agents = match value.get("agents").and_then(Value::as_array) {
+        Some(agents) => agents.iter().collect::<Vec<_>>(),
+        None if value.is_object() => vec![value],
+        None => return Err("expected an agent file object".to_string()),
+    };
+    let shared_blocks = array_field(value, &["blocks"]);
+    let mut memories = Vec::new();
+    for agent in agents {
+        let agent_id = str_field(agent, &["id", "name"]).unwrap_or("agent");
+        let conversation_id = format!("letta:{agent_id}");
+        let agent_ts = ts_field(agent, &["updated_at", "created_at"]).unwrap_or(fallback_ts);
+        let blocks = array_field(agent, &["core_memory", "memory_blocks", "blocks"])
+            .or_else(|| shared_blocks.clone())
+            .unwrap_or_default();
+        for block in blocks {
+            let (Some(label), Some(text)) = (
+                str_field(block, &["label"]),
+                str_field(block, &["value"]).filter(|text| !text.trim().is_empty()),
+            ) else {
+                continue;
+            };
+            memories.push(ImportedMemory {
+                memory_id: format!("letta:{agent_id}:block:{label}"),
+                conversation_id: conversation_id.clone(),
+                agent_id: agent_id.to_string(),
+                ts: ts_field(block, &["updated_at", "created_at"]).unwrap_or(agent_ts),
+                text: format!("[{label}] {text}"),
+            });
+        }
+        let passages = array_field(agent, &["passages", "archival_memory"]).unwrap_or_default();
+        for (index, passage) in passages.into_iter().enumerate() {
+            let Some(text) = str_field(passage, &["text"]) else {
+                continue;
+            };
+            let id = str_field(passage, &["id"])
+                .map(str::to_string)
+                .unwrap_or_else(|| index.to_string());
+            memories.push(ImportedMemory {
+                memory_id: format!("letta:{agent_id}:passage:{id}"),
+                conversation_id: conversation_id.clone(),
+                agent_id: agent_id.to_string(),
+                ts: ts_field(passage, &["created_at"]).unwrap_or(agent_ts),
+                text: text.to_string(),
+            });
+        }
+    }
+    Ok(memories)
+}
+
+fn str_field<'a>(value: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .find_map(|key| value.get(*key).and_then(Value::as_str))
+}
+
+fn array_field<'a>(value: &'a Value, keys: &[&str]) -> Option<Vec<&'a Value>> {
+    keys.iter().find_map(|key| {
+        value
+            .get(*key)
+            .and_then(Value::as_array)
+            .map(|items| items.iter().collect())
+    })
+}
+
+fn ts_field(value: &Value, keys: &[&str]) -> Option<DateTime<Utc>> {
+    keys.iter().find_map(|key| {
+        let raw = value.get(*key)?.as_str()?;
+        DateTime::parse_from_rfc3339(raw)
+            .map(|ts| ts.with_timezone(&Utc))
+            .ok()
+            // Letta writes naive UTC timestamps.
+            .or_else(|| {
+                chrono::NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S%.f")
+                    .ok()
+                    .map(|ts| ts.and_utc())
+            })
+    })
+}
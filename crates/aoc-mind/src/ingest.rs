@@ -4,8 +4,9 @@ use aoc_core::{
         T0CompactionPolicy,
     },
     mind_observer_feed::MindObserverFeedProgress,
+    ErrorCode, ErrorCoded,
 };
-use aoc_storage::{MindStore, StorageError, StoredCompactEvent};
+use aoc_storage::{MindStoreBackend, StorageError, StoredCompactEvent};
 use thiserror::Error;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -30,8 +31,22 @@ pub enum T0IngestError {
     Contract(#[from] MindContractError),
 }
 
-pub fn ingest_raw_event(
-    store: &MindStore,
+impl ErrorCoded for T0IngestError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Contract(err) => err.error_code(),
+        }
+    }
+}
+
+#[tracing::instrument(
+    level = "trace",
+    skip_all,
+    fields(stage = "ingest", conversation_id = %raw.conversation_id, event_id = %raw.event_id)
+)]
+pub fn ingest_raw_event<S: MindStoreBackend + ?Sized>(
+    store: &S,
     raw: &RawEvent,
     config: &T0IngestConfig,
 ) -> Result<T0IngestReport, T0IngestError> {
@@ -58,8 +73,8 @@ pub fn ingest_raw_event(
     })
 }
 
-pub fn mind_progress_for_conversation(
-    store: &MindStore,
+pub fn mind_progress_for_conversation<S: MindStoreBackend + ?Sized>(
+    store: &S,
     conversation_id: &str,
     t1_target_tokens: u32,
     t1_hard_cap_tokens: u32,
@@ -98,6 +113,7 @@ fn estimate_text_tokens(text: &str) -> u32 {
 mod tests {
     use super::*;
     use aoc_core::mind_contracts::{ConversationRole, MessageEvent, RawEventBody};
+    use aoc_storage::MindStore;
     use chrono::Utc;
     use std::collections::BTreeMap;
 
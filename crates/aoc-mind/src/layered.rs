@@ -0,0 +1,285 @@
+//! A user-level global Mind layered under the project Mind.
+//!
+//! The global store holds what should follow the user across repositories:
+//! preferences, durable lessons, conventions. Writes always land in the
+//! project store; reads merge both layers, and when the same artifact id
+//! exists in both, or two hits share a timestamp, the project copy wins.
+//! Artifacts reach the global store only by promotion, which routing does for
+//! anything whose primary segment is the global segment.
+
+use aoc_core::{ErrorCode, ErrorCoded};
+use aoc_storage::{ArtifactQuery, MindStore, StorageError, StoredArtifact};
+use serde::Serialize;
+use std::{cmp::Reverse, collections::HashSet};
+use thiserror::Error;
+
+/// Rows fetched per layer are capped so a deep page cannot pull a whole
+/// store into memory.
+const LAYER_FETCH_CAP: usize = 5_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MindLayer {
+    Project,
+    Global,
+}
+
+impl MindLayer {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Project => "project",
+            Self::Global => "global",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayeredArtifact {
+    pub layer: MindLayer,
+    pub artifact: StoredArtifact,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayeredArtifactPage {
+    pub artifacts: Vec<LayeredArtifact>,
+    /// Matches across both layers; global copies shadowed by a project
+    /// artifact are discounted only when they fell inside the fetched window.
+    pub total: usize,
+    pub offset: usize,
+}
+
+impl LayeredArtifactPage {
+    pub fn next_offset(&self) -> Option<usize> {
+        let next = self.offset + self.artifacts.len();
+        (next < self.total && !self.artifacts.is_empty()).then_some(next)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromotionOutcome {
+    Promoted,
+    /// The global copy existed with different text and was replaced.
+    Updated,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PromotionReport {
+    pub candidates: usize,
+    pub promoted: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum LayeredMindError {
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
+    #[error("no global mind store is configured")]
+    NoGlobalStore,
+    #[error("artifact not found in the project mind: {0}")]
+    ArtifactNotFound(String),
+}
+
+impl ErrorCoded for LayeredMindError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::NoGlobalStore => ErrorCode::Config,
+            Self::ArtifactNotFound(_) => ErrorCode::NotFound,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct LayeredMind<'a> {
+    project: &'a MindStore,
+    global: Option<&'a MindStore>,
+}
+
+impl<'a> LayeredMind<'a> {
+    pub fn new(project: &'a MindStore, global: Option<&'a MindStore>) -> Self {
+        Self { project, global }
+    }
+
+    /// The store writes go to.
+    pub fn project(&self) -> &'a MindStore {
+        self.project
+    }
+
+    pub fn global(&self) -> Option<&'a MindStore> {
+        self.global
+    }
+
+    /// Runs `query` against both layers and merges the hits in the query's
+    /// order. Without a global store this is the project query as-is.
+    pub fn query_artifacts(
+        &self,
+        query: &ArtifactQuery,
+    ) -> Result<LayeredArtifactPage, StorageError> {
+        let Some(global) = self.global else {
+            let page = self.project.query_artifacts(query)?;
+            return Ok(LayeredArtifactPage {
+                artifacts: tag_layer(page.artifacts, MindLayer::Project),
+                total: page.total,
+                offset: page.offset,
+            });
+        };
+        let window = ArtifactQuery {
+            offset: 0,
+            limit: (query.offset + query.limit).clamp(1, LAYER_FETCH_CAP),
+            ..query.clone()
+        };
+        let project = self.project.query_artifacts(&window)?;
+        let global = global.query_artifacts(&window)?;
+
+        let project_ids = project
+            .artifacts
+            .iter()
+            .map(|artifact| artifact.artifact_id.clone())
+            .collect::<HashSet<_>>();
+        let mut shadowed = 0;
+        let mut merged = tag_layer(project.artifacts, MindLayer::Project);
+        for artifact in global.artifacts {
+            if project_ids.contains(&artifact.artifact_id)
+                || self
+                    .project
+                    .artifact_by_id(&artifact.artifact_id)?
+                    .is_some()
+            {
+                shadowed += 1;
+                continue;
+            }
+            merged.push(LayeredArtifact {
+                layer: MindLayer::Global,
+                artifact,
+            });
+        }
+        // Stable sorts keep the project layer ahead on equal timestamps.
+        if query.oldest_first {
+            merged.sort_by_key(|hit| (hit.artifact.ts, hit.layer));
+        } else {
+            merged.sort_by_key(|hit| (Reverse(hit.artifact.ts), hit.layer));
+        }
+        Ok(LayeredArtifactPage {
+            artifacts: merged
+                .into_iter()
+                .skip(query.offset)
+                .take(query.limit)
+                .collect(),
+            total: project.total + global.total - shadowed,
+            offset: query.offset,
+        })
+    }
+
+    /// The project copy if there is one, else the global copy.
+    pub fn artifact_by_id(
+        &self,
+        artifact_id: &str,
+    ) -> Result<Option<LayeredArtifact>, StorageError> {
+        if let Some(artifact) = self.project.artifact_by_id(artifact_id)? {
+            return Ok(Some(LayeredArtifact {
+                layer: MindLayer::Project,
+                artifact,
+            }));
+        }
+        let Some(global) = self.global else {
+            return Ok(None);
+        };
+        Ok(global
+            .artifact_by_id(artifact_id)?
+            .map(|artifact| LayeredArtifact {
+                layer: MindLayer::Global,
+                artifact,
+            }))
+    }
+
+    /// Copies a project artifact, with its segment route, into the global
+    /// store under the same id. Re-promoting unchanged text is a no-op.
+    pub fn promote_artifact(
+        &self,
+        artifact_id: &str,
+    ) -> Result<PromotionOutcome, LayeredMindError> {
+        let global = self.global.ok_or(LayeredMindError::NoGlobalStore)?;
+        let artifact = self
+            .project
+            .artifact_by_id(artifact_id)?
+            .ok_or_else(|| LayeredMindError::ArtifactNotFound(artifact_id.to_string()))?;
+        let outcome = match global.artifact_by_id(artifact_id)? {
+            Some(existing) if existing.text == artifact.text && existing.kind == artifact.kind => {
+                return Ok(PromotionOutcome::Unchanged)
+            }
+            Some(_) => PromotionOutcome::Updated,
+            None => PromotionOutcome::Promoted,
+        };
+        write_artifact(global, &artifact)?;
+        if let Some(route) = self.project.segment_route_for_artifact(artifact_id)? {
+            global.replace_segment_route(&route)?;
+        }
+        tracing::info!(
+            stage = "promote",
+            artifact_id = %artifact_id,
+            outcome = ?outcome,
+            "artifact promoted to global mind"
+        );
+        Ok(outcome)
+    }
+
+    /// Promotes every artifact of `conversation_id` whose stored route has
+    /// `global_segment` as its primary segment.
+    pub fn promote_global_routes(
+        &self,
+        conversation_id: &str,
+        global_segment: &str,
+    ) -> Result<PromotionReport, LayeredMindError> {
+        let mut report = PromotionReport::default();
+        if self.global.is_none() {
+            return Ok(report);
+        }
+        for artifact in self.project.artifacts_for_conversation(conversation_id)? {
+            let Some(route) = self
+                .project
+                .segment_route_for_artifact(&artifact.artifact_id)?
+            else {
+                continue;
+            };
+            if !route
+                .primary
+                .segment_id
+                .eq_ignore_ascii_case(global_segment.trim())
+            {
+                continue;
+            }
+            report.candidates += 1;
+            match self.promote_artifact(&artifact.artifact_id)? {
+                PromotionOutcome::Promoted => report.promoted += 1,
+                PromotionOutcome::Updated => report.updated += 1,
+                PromotionOutcome::Unchanged => report.unchanged += 1,
+            }
+        }
+        Ok(report)
+    }
+}
+
+fn tag_layer(artifacts: Vec<StoredArtifact>, layer: MindLayer) -> Vec<LayeredArtifact> {
+    artifacts
+        .into_iter()
+        .map(|artifact| LayeredArtifact { layer, artifact })
+        .collect()
+}
+
+fn write_artifact(store: &MindStore, artifact: &StoredArtifact) -> Result<(), StorageError> {
+    let write = match artifact.kind.as_str() {
+        "t2" => MindStore::insert_reflection,
+        _ => MindStore::insert_observation,
+    };
+    write(
+        store,
+        &artifact.artifact_id,
+        &artifact.conversation_id,
+        artifact.ts,
+        &artifact.text,
+        &artifact.trace_ids,
+    )
+}
@@ -1,14 +1,38 @@
+mod analytics;
+mod archival;
+mod breaker;
+mod budget;
+mod canon;
 mod compatibility_queries;
+mod compliance;
+mod context_pack;
+#[cfg(feature = "adapters")]
+mod daemon;
+mod event_sinks;
+mod export;
+mod gateway;
+mod graph_export;
+mod importers;
 mod ingest;
+mod layered;
+mod memory_tool;
+mod observer_feed;
 mod observer_runtime;
+mod pins;
 mod query;
+mod redaction;
 mod reflector_runtime;
+#[cfg(feature = "render")]
 pub mod render;
+mod replay;
 mod retrieval;
 mod runtime;
 mod standalone;
 mod t1;
 mod t3_runtime;
+mod taskmaster;
+mod vault;
+mod webhooks;
 
 // Ingest exports
 pub use ingest::{
@@ -18,21 +42,74 @@ pub use ingest::{
 pub use t1::{evaluate_t1_token_threshold, T1ThresholdDecision, T1ThresholdError};
 
 // Runtime exports
+pub use analytics::{
+    export_star_schema, render_schema_sql, StarColumn, StarColumnType, StarSchemaReport, StarTable,
+    STAR_SCHEMA,
+};
+pub use archival::{
+    restore_archived_artifacts, run_artifact_archival, ArchivalPolicy, ArchivalReport,
+};
+pub use breaker::{BreakerState, BreakerTransition, CircuitBreaker, CircuitBreakerConfig};
+pub use budget::{
+    BudgetDecision, BudgetManager, BudgetReservation, DailyBudgetConfig, ProviderDailyBudget,
+};
+pub use canon::{CanonSynthesisReport, CanonSynthesizer, CANON_FALLBACK_SEGMENT};
 pub use compatibility_queries::{
     compile_mind_context_pack, compile_mind_evidence_pack, compile_mind_provenance_export,
     compile_mind_provenance_graph, compile_mnemopi_candidate_pack,
     mind_context_pack_mode_for_trigger, parse_mind_context_pack_mode,
     parse_mind_context_pack_request, try_parse_mind_context_pack_mode,
     try_parse_mind_evidence_pack_mode, MindContextPack, MindContextPackCitation,
-    MindContextPackMode, MindContextPackProfile, MindContextPackRequest,
-    MindContextPackSection, MindContextPackSourceOverrides, MindEvidenceCitation,
-    MindEvidenceItem, MindEvidencePack, MindEvidencePackMode, MindEvidencePackRequest,
-    MindEvidenceQuery, MnemopiCandidateMemory, MnemopiCandidatePack,
+    MindContextPackMode, MindContextPackProfile, MindContextPackRequest, MindContextPackSection,
+    MindContextPackSourceOverrides, MindEvidenceCitation, MindEvidenceItem, MindEvidencePack,
+    MindEvidencePackMode, MindEvidencePackRequest, MindEvidenceQuery, MnemopiCandidateMemory,
+    MnemopiCandidatePack, MIND_CONTEXT_PACK_PIPELINE,
+};
+pub use compliance::{
+    delete_compliance_subject, export_compliance_bundle, verify_compliance_bundle,
+    ComplianceDeletion, ComplianceError, ComplianceFile, ComplianceManifest, DataSubject,
+    COMPLIANCE_MANIFEST_FILE, COMPLIANCE_RECEIPT_FILE,
+};
+pub use context_pack::{
+    ContextPack, ContextPackItem, ContextPackSection, HandshakeBuilder, CONTEXT_PACK_SCOPE,
+};
+#[cfg(feature = "adapters")]
+pub use daemon::{
+    daemon_socket_path, send_daemon_request, DaemonPipeline, DaemonRequest, DaemonResponse,
+    DaemonStage, DaemonStatus, DaemonTickReport, MindDaemon, MindDaemonConfig, MindPipeline,
+    SessionFileWatcher, DAEMON_SOCKET_FILE,
+};
+pub use event_sinks::{
+    EventSink, EventSinkConfig, NatsSink, RedisStreamSink, SinkDelivery, DEFAULT_NATS_SUBJECT,
+    DEFAULT_REDIS_STREAM,
+};
+pub use export::{
+    export_artifacts, ArtifactExportError, ArtifactExportFormat, ArtifactExportOptions,
+    ArtifactExportReport, ArtifactExportScope,
+};
+#[cfg(feature = "semantic")]
+pub use gateway::GatewayObserverInvoker;
+pub use gateway::{parse_gateway_costs, GatewayConfig, ModelCost, DEFAULT_GATEWAY_COSTS_PATH};
+pub use graph_export::{
+    build_knowledge_graph, GraphEdge, GraphEdgeKind, GraphExportFormat, GraphNode, GraphNodeKind,
+    GraphScope, KnowledgeGraph,
+};
+pub use importers::{
+    import_third_party_memories, parse_third_party_export, ImportedMemory, ThirdPartyImportError,
+    ThirdPartyImportReport, ThirdPartySource, IMPORT_SOURCE_ATTR,
+};
+pub use memory_tool::{
+    MemoryToolCommand, MemoryToolError, MemoryToolShim, MEMORY_ROOT, MEMORY_TOOL_AGENT_KIND,
+    MEMORY_TOOL_ATTR, MEMORY_TOOL_SESSION_ID,
+};
+pub use observer_feed::{
+    ObserverFeedPublisher, ObserverFeedSubscription, DEFAULT_OBSERVER_FEED_CAPACITY,
 };
 pub use observer_runtime::{
     ClaimedObserverRun, ObserverQueueConfig, ObserverTrigger, ObserverTriggerKind,
     ObserverTriggerPriority, SessionObserverQueue,
 };
+pub use redaction::{RedactionConfig, RedactionPattern, Redactor, REDACTED_MARKER};
 pub use reflector_runtime::{
     DetachedReflectorWorker, ReflectorRuntimeConfig, ReflectorRuntimeError, ReflectorTickReport,
 };
@@ -41,25 +118,48 @@ pub use runtime::{
     MindRuntimeConfig, MindRuntimeCore, MindTickEffects,
 };
 pub use t3_runtime::{DetachedT3Worker, T3RuntimeConfig, T3RuntimeError, T3TickReport};
+pub use taskmaster::{
+    read_taskmaster_snapshot, TaskmasterSnapshot, TaskmasterSyncError, TaskmasterWatcher,
+};
+pub use vault::{export_vault, VaultExportOptions, VaultExportReport};
+pub use webhooks::{
+    webhook_signature, HttpWebhookTransport, PipelineEvent, PipelineEventKind,
+    PipelineEventWatcher, WebhookDelivery, WebhookDispatcher, WebhookEndpoint, WebhookFormat,
+    WebhookTransport, WEBHOOK_DELIVERY_HEADER, WEBHOOK_EVENT_HEADER, WEBHOOK_SIGNATURE_HEADER,
+};
 
 // Query exports
+pub use layered::{
+    LayeredArtifact, LayeredArtifactPage, LayeredMind, LayeredMindError, MindLayer,
+    PromotionOutcome, PromotionReport,
+};
+pub use pins::{list_mind_pins, pin_mind_memory, unpin_mind_memory, MindPinTarget};
 pub use query::{
     canon_key, canon_stale_entries, collect_mind_search_hits, compaction_rebuildable_from_attrs,
-    load_mind_artifact_drilldown, mind_store_path, parse_handshake_entries,
+    global_mind_store_path, load_mind_artifact_drilldown, mind_store_path, parse_handshake_entries,
     parse_project_canon_entries, project_scope_key, MindArtifactDrilldown, MindCanonEntry,
     MindHandshakeEntry, MindSearchHit, MindSessionExportManifest,
 };
-pub use retrieval::compile_insight_retrieval;
+pub use replay::{
+    replay_conversation, ReplayDistillationOverrides, ReplayError, ReplayPolicy, ReplayReport,
+    ReplayTierDiff,
+};
+pub use retrieval::{
+    compile_insight_retrieval, record_retrieval_metrics, INSIGHT_RETRIEVAL_PIPELINE,
+};
 pub use standalone::{
     default_pi_session_root, discover_latest_pi_session_file, latest_pi_session_file,
     legacy_mind_store_path, mind_runtime_root, mind_store_path_with_override, open_project_store,
     read_mind_service_health_snapshot, read_mind_service_lease, reflector_dispatch_lock_path,
-    reflector_lock_path_with_override, summarize_mind_service_status,
+    reflector_lock_path_with_override, summarize_mind_service_status, t3_dispatch_lock_path,
+    t3_lock_path_with_override, write_mind_service_health_snapshot, MindProjectPaths,
+    MindServiceHealthSnapshot, MindServiceLease, MindServiceLeaseGuard, MindServiceStatusSummary,
+    OpenedMindProjectStore, StandaloneMindError, DEFAULT_MIND_SERVICE_STALE_AFTER_MS,
+};
+#[cfg(feature = "adapters")]
+pub use standalone::{
     sync_latest_pi_session_into_project_store, sync_session_file_into_project_store,
-    t3_dispatch_lock_path, t3_lock_path_with_override, write_mind_service_health_snapshot,
-    MindProjectPaths, MindServiceHealthSnapshot, MindServiceLease, MindServiceLeaseGuard,
-    MindServiceStatusSummary, OpenedMindProjectStore, StandaloneMindError, StandalonePiSyncReport,
-    DEFAULT_MIND_SERVICE_STALE_AFTER_MS,
+    StandalonePiSyncReport,
 };
 
 pub fn canonical_mind_command_name(command: &str) -> Option<&'static str> {
@@ -79,6 +179,7 @@ pub fn canonical_mind_command_name(command: &str) -> Option<&'static str> {
 }
 
 // Render exports
+#[cfg(feature = "render")]
 pub use render::{
     age_color, age_meter, detached_job_attention_color, detached_job_attention_label,
     detached_job_recovery_guidance, detached_job_status_color, detached_job_status_label,
@@ -97,25 +198,29 @@ use aoc_core::{
     mind_contracts::{
         build_compaction_t0_slice, build_t2_workstream_batch, canonical_json,
         canonical_payload_hash, validate_t1_scope, ConversationRole, MindContractError,
-        ObservationRef, ObserverAdapter, ObserverInput, ObserverOutput, SemanticAdapterError,
-        SemanticFailureKind, SemanticGuardrails, SemanticModelProfile, SemanticProvenance,
-        SemanticRuntime, SemanticRuntimeMode, SemanticStage, T1Batch, T1_PARSER_HARD_CAP_TOKENS,
-        T1_PARSER_TARGET_TOKENS,
+        ObservationRef, ObserverAdapter, ObserverInput, ObserverOutput, ReflectorAdapter,
+        ReflectorInput, ReflectorOutput, SemanticAdapterError, SemanticFailureKind,
+        SemanticGuardrails, SemanticModelProfile, SemanticProvenance, SemanticRuntime,
+        SemanticRuntimeMode, SemanticStage, T1Batch, ToolMetadataLine,
+        OBSERVER_SECTIONS_PROMPT_VERSION, T1_PARSER_HARD_CAP_TOKENS, T1_PARSER_TARGET_TOKENS,
     },
     mind_observer_feed::{
         MindInjectionTriggerKind, MindObserverFeedEvent, MindObserverFeedProgress,
         MindObserverFeedStatus, MindObserverFeedTriggerKind,
     },
+    ErrorCode, ErrorCoded,
 };
 use aoc_storage::{
-    CanonEntryRevision, CanonRevisionState, ConversationContextState, MindStore, ProjectWatermark,
-    ReflectorJob, StorageError, StoredArtifact, StoredCompactEvent, T3BacklogJob,
+    system_clock, CanonEntryRevision, CanonRevisionState, Clock, ConversationArtifactFilter,
+    ConversationContextState, ConversationLineage, DistillationCheckpoint, MindStore, PipelineRun,
+    ProjectWatermark, ReflectorJob, SemanticCacheEntry, SemanticUsageEntry, SharedClock,
+    StorageError, StoredArtifact, StoredCompactEvent, T3BacklogJob,
 };
 use aoc_task_attribution::{AttributionConfig, AttributionError, TaskAttributionEngine};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use thiserror::Error;
 
 const DEFAULT_T1_OUTPUT_MAX_CHARS: usize = 1_200;
@@ -123,11 +228,14 @@ const DEFAULT_T2_OUTPUT_MAX_CHARS: usize = 1_400;
 const DEFAULT_T2_TRIGGER_TOKENS: u32 = 300;
 const DEFAULT_PI_OBSERVER_PROVIDER: &str = "pi";
 const DEFAULT_PI_OBSERVER_MODEL: &str = "gpt-5.3-codex-spark";
-const DEFAULT_PI_OBSERVER_PROMPT_VERSION: &str = "pi.observer.v1";
+const DEFAULT_PI_OBSERVER_PROMPT_VERSION: &str = OBSERVER_SECTIONS_PROMPT_VERSION;
 const DEFAULT_PI_REFLECTOR_PROVIDER: &str = "pi";
 const DEFAULT_PI_REFLECTOR_MODEL: &str = "gpt-5.3-codex-spark";
 const DEFAULT_PI_REFLECTOR_PROMPT_VERSION: &str = "pi.reflector.v1";
 const DEFAULT_SEMANTIC_COST_MICROS_PER_TOKEN: u64 = 100;
+/// `pipeline_runs` stage journaling one conversation's distillation; its
+/// units are T1 artifact ids.
+pub const DISTILL_RUN_STAGE: &str = "distill";
 
 #[derive(Debug, Error)]
 pub enum ReflectorJobError {
@@ -139,6 +247,16 @@ pub enum ReflectorJobError {
     Internal(String),
 }
 
+impl ErrorCoded for ReflectorJobError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Contract(err) => err.error_code(),
+            Self::Internal(_) => ErrorCode::Internal,
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum T3BacklogJobError {
     #[error("storage error: {0}")]
@@ -151,6 +269,17 @@ pub enum T3BacklogJobError {
     Internal(String),
 }
 
+impl ErrorCoded for T3BacklogJobError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Contract(err) => err.error_code(),
+            Self::Export(_) => ErrorCode::Io,
+            Self::Internal(_) => ErrorCode::Internal,
+        }
+    }
+}
+
 pub const MIND_T3_CANON_SUMMARY_MAX_CHARS: usize = 280;
 pub const MIND_T3_CANON_STALE_AFTER_DAYS: i64 = 14;
 pub const MIND_T3_HANDSHAKE_TOKEN_BUDGET: u32 = 500;
@@ -310,6 +439,17 @@ pub enum DistillationError {
     Internal(String),
 }
 
+impl ErrorCoded for DistillationError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Contract(err) => err.error_code(),
+            Self::Attribution(err) => err.error_code(),
+            Self::Internal(_) => ErrorCode::Internal,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FinalizeArtifactPlan {
     pub conversation_ids: Vec<String>,
@@ -431,6 +571,15 @@ pub enum FinalizeArtifactPlanError {
     Contract(#[from] MindContractError),
 }
 
+impl ErrorCoded for FinalizeArtifactPlanError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Contract(err) => err.error_code(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SessionExportManifest {
     pub schema_version: u32,
@@ -505,6 +654,14 @@ pub enum SessionExportBundleError {
     Serialization(String),
 }
 
+impl ErrorCoded for SessionExportBundleError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Serialization(_) => ErrorCode::Serialization,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct HandshakeTaskCounts {
     pub total: u32,
@@ -553,6 +710,15 @@ pub enum T3ExportError {
     Contract(#[from] MindContractError),
 }
 
+impl ErrorCoded for T3ExportError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Contract(err) => err.error_code(),
+        }
+    }
+}
+
 pub fn build_session_export_bundle(
     plan: &FinalizeArtifactPlan,
     session_id: &str,
@@ -1481,7 +1647,8 @@ pub fn plan_session_finalize_artifacts(
     }))
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct DistillationConfig {
     pub t1_target_tokens: u32,
     pub t1_hard_cap_tokens: u32,
@@ -1489,6 +1656,21 @@ pub struct DistillationConfig {
     pub t1_output_max_chars: usize,
     pub t2_output_max_chars: usize,
     pub enable_attribution: bool,
+    #[serde(default)]
+    pub t2_scope: ReflectionScope,
+}
+
+/// Which observations a T2 reflection is built from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReflectionScope {
+    /// The distilled conversation's own observations.
+    #[default]
+    Conversation,
+    /// Every conversation in the distilled conversation's session tree,
+    /// grouped by active tag. The reflections belong to the tree's root
+    /// conversation; a conversation without lineage reflects on its own.
+    SessionTree,
 }
 
 impl Default for DistillationConfig {
@@ -1500,11 +1682,12 @@ impl Default for DistillationConfig {
             t1_output_max_chars: DEFAULT_T1_OUTPUT_MAX_CHARS,
             t2_output_max_chars: DEFAULT_T2_OUTPUT_MAX_CHARS,
             enable_attribution: true,
+            t2_scope: ReflectionScope::Conversation,
         }
     }
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
 pub struct DistillationReport {
     pub t0_events_processed: usize,
     pub t1_batches_planned: usize,
@@ -1512,6 +1695,10 @@ pub struct DistillationReport {
     pub t2_artifacts_written: usize,
     pub chunked_t1: bool,
     pub attribution_links_written: usize,
+    /// T1 batches an interrupted run had already committed; reused as-is.
+    pub t1_artifacts_resumed: usize,
+    /// T1 batches answered from the semantic cache without a provider call.
+    pub t1_cache_hits: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -1523,11 +1710,68 @@ struct ProducedObservation {
     estimated_tokens: u32,
 }
 
-#[derive(Debug, Clone)]
+/// What [`call_with_semantic_guardrails`] returns: the output, the attempt
+/// count, and the last call's latency.
+type SemanticCall<T> = (Result<T, SemanticAdapterError>, u16, Option<u64>);
+
+/// A T1 batch of a semantic wave, in batch order.
+enum T1WaveUnit<'a> {
+    /// Written by an earlier, interrupted run.
+    Resumed(ProducedObservation),
+    Prepared(Box<PreparedT1Batch<'a>>),
+}
+
+struct PreparedT1Batch<'a> {
+    batch_index: usize,
+    batch: &'a T1Batch,
+    batch_events: Vec<&'a StoredCompactEvent>,
+    ts: chrono::DateTime<chrono::Utc>,
+    active_tag: String,
+    artifact_id: String,
+    observer_input: ObserverInput,
+    redactions: Vec<String>,
+    cache_hit: bool,
+    /// Set when no call is needed: a cache hit or a refusal.
+    settled: Option<SemanticCall<ObserverOutput>>,
+}
+
+/// What a wave does with a T1 batch that needs a provider call.
+enum CallAdmission {
+    Call,
+    /// Written deterministically without a call.
+    Refused(SemanticAdapterError),
+    /// Ends the wave; the batch opens the next one.
+    Deferred,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct SemanticObserverConfig {
     pub mode: SemanticRuntimeMode,
     pub profile: SemanticModelProfile,
     pub guardrails: SemanticGuardrails,
+    /// OpenAI-compatible gateway the observer calls; without one only an
+    /// injected invoker can run the semantic path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<GatewayConfig>,
+    /// Scrubbing applied to payload lines before the provider sees them.
+    #[serde(default)]
+    pub redaction: RedactionConfig,
+    #[serde(default)]
+    pub cache: SemanticCacheConfig,
+    /// Daily caps across sessions; see [`BudgetManager`].
+    #[serde(default)]
+    pub budget: DailyBudgetConfig,
+    #[serde(default)]
+    pub breaker: CircuitBreakerConfig,
+    /// Batches of one conversation observed concurrently; 1 keeps them
+    /// serial. Artifacts are still written in batch order.
+    #[serde(default = "default_observer_workers")]
+    pub workers: usize,
+}
+
+fn default_observer_workers() -> usize {
+    1
 }
 
 impl Default for SemanticObserverConfig {
@@ -1536,6 +1780,92 @@ impl Default for SemanticObserverConfig {
             mode: SemanticRuntimeMode::SemanticWithFallback,
             profile: default_pi_observer_profile(),
             guardrails: SemanticGuardrails::default(),
+            gateway: None,
+            redaction: RedactionConfig::default(),
+            cache: SemanticCacheConfig::default(),
+            budget: DailyBudgetConfig::default(),
+            breaker: CircuitBreakerConfig::default(),
+            workers: default_observer_workers(),
+        }
+    }
+}
+
+/// `[observer.cache]`: reuse of provider output for identical observer
+/// inputs, keyed by stage, prompt version, and input hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SemanticCacheConfig {
+    #[serde(default = "default_semantic_cache_enabled")]
+    pub enabled: bool,
+    /// How long a cached response stays servable.
+    #[serde(default = "default_semantic_cache_ttl_hours")]
+    pub ttl_hours: u32,
+    /// Oldest entries beyond this are evicted after each write.
+    #[serde(default = "default_semantic_cache_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for SemanticCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_semantic_cache_enabled(),
+            ttl_hours: default_semantic_cache_ttl_hours(),
+            max_entries: default_semantic_cache_max_entries(),
+        }
+    }
+}
+
+impl SemanticCacheConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.enabled && self.ttl_hours == 0 {
+            return Err("ttl_hours must be > 0 when the cache is enabled".to_string());
+        }
+        if self.enabled && self.max_entries == 0 {
+            return Err("max_entries must be > 0 when the cache is enabled".to_string());
+        }
+        Ok(())
+    }
+
+    fn expires_at(&self, now: chrono::DateTime<Utc>) -> chrono::DateTime<Utc> {
+        now.checked_add_signed(chrono::Duration::hours(i64::from(self.ttl_hours)))
+            .unwrap_or(chrono::DateTime::<Utc>::MAX_UTC)
+    }
+}
+
+fn default_semantic_cache_enabled() -> bool {
+    true
+}
+
+fn default_semantic_cache_ttl_hours() -> u32 {
+    24 * 7
+}
+
+fn default_semantic_cache_max_entries() -> usize {
+    10_000
+}
+
+/// T2 counterpart of [`SemanticObserverConfig`] for the reflector worker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SemanticReflectorConfig {
+    pub mode: SemanticRuntimeMode,
+    pub profile: SemanticModelProfile,
+    pub guardrails: SemanticGuardrails,
+    /// Share the observer's caps to bound both stages by one daily budget.
+    #[serde(default)]
+    pub budget: DailyBudgetConfig,
+    #[serde(default)]
+    pub breaker: CircuitBreakerConfig,
+}
+
+impl Default for SemanticReflectorConfig {
+    fn default() -> Self {
+        Self {
+            mode: SemanticRuntimeMode::SemanticWithFallback,
+            profile: default_pi_reflector_profile(),
+            guardrails: SemanticGuardrails::default(),
+            budget: DailyBudgetConfig::default(),
+            breaker: CircuitBreakerConfig::default(),
         }
     }
 }
@@ -1567,6 +1897,11 @@ pub trait PiObserverInvoker {
         profile: &SemanticModelProfile,
         guardrails: &SemanticGuardrails,
     ) -> Result<String, SemanticAdapterError>;
+
+    /// See [`ObserverAdapter::cost_micros`].
+    fn cost_micros(&self, _model_id: &str, _input_tokens: u32, _output_tokens: u32) -> Option<u64> {
+        None
+    }
 }
 
 #[derive(Debug, Default)]
@@ -1626,30 +1961,150 @@ where
             .invoker
             .invoke_observer(&canonical_input_json, profile, guardrails)?;
 
-        ObserverOutput::parse_json(&raw).map_err(|err| {
+        ObserverOutput::parse_json(&raw)
+            .and_then(|output| {
+                output.validate_for_prompt(&profile.prompt_version)?;
+                Ok(output)
+            })
+            .map_err(|err| {
+                SemanticAdapterError::new(
+                    SemanticFailureKind::InvalidOutput,
+                    format!("failed to parse observer output: {err}"),
+                )
+            })
+    }
+
+    fn cost_micros(&self, model_id: &str, input_tokens: u32, output_tokens: u32) -> Option<u64> {
+        self.invoker
+            .cost_micros(model_id, input_tokens, output_tokens)
+    }
+}
+
+pub trait PiReflectorInvoker {
+    fn invoke_reflector(
+        &self,
+        canonical_input_json: &str,
+        profile: &SemanticModelProfile,
+        guardrails: &SemanticGuardrails,
+    ) -> Result<String, SemanticAdapterError>;
+
+    /// See [`ObserverAdapter::cost_micros`].
+    fn cost_micros(&self, _model_id: &str, _input_tokens: u32, _output_tokens: u32) -> Option<u64> {
+        None
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct NoopPiReflectorInvoker;
+
+impl PiReflectorInvoker for NoopPiReflectorInvoker {
+    fn invoke_reflector(
+        &self,
+        _canonical_input_json: &str,
+        _profile: &SemanticModelProfile,
+        _guardrails: &SemanticGuardrails,
+    ) -> Result<String, SemanticAdapterError> {
+        Err(SemanticAdapterError::new(
+            SemanticFailureKind::ProviderError,
+            "pi reflector runtime is not configured",
+        ))
+    }
+}
+
+#[derive(Debug)]
+pub struct PiReflectorAdapter<I = NoopPiReflectorInvoker> {
+    invoker: I,
+}
+
+impl Default for PiReflectorAdapter<NoopPiReflectorInvoker> {
+    fn default() -> Self {
+        Self {
+            invoker: NoopPiReflectorInvoker,
+        }
+    }
+}
+
+impl<I> PiReflectorAdapter<I> {
+    pub fn new(invoker: I) -> Self {
+        Self { invoker }
+    }
+}
+
+impl<I> ReflectorAdapter for PiReflectorAdapter<I>
+where
+    I: PiReflectorInvoker,
+{
+    fn reflect_t2(
+        &self,
+        input: &ReflectorInput,
+        profile: &SemanticModelProfile,
+        guardrails: &SemanticGuardrails,
+    ) -> Result<ReflectorOutput, SemanticAdapterError> {
+        let canonical_input_json = canonical_json(input).map_err(|err| {
+            SemanticAdapterError::new(
+                SemanticFailureKind::InvalidOutput,
+                format!("failed to serialize reflector input: {err}"),
+            )
+        })?;
+
+        let raw = self
+            .invoker
+            .invoke_reflector(&canonical_input_json, profile, guardrails)?;
+
+        ReflectorOutput::parse_json(&raw).map_err(|err| {
             SemanticAdapterError::new(
                 SemanticFailureKind::InvalidOutput,
-                format!("failed to parse observer output: {err}"),
+                format!("failed to parse reflector output: {err}"),
             )
         })
     }
+
+    fn cost_micros(&self, model_id: &str, input_tokens: u32, output_tokens: u32) -> Option<u64> {
+        self.invoker
+            .cost_micros(model_id, input_tokens, output_tokens)
+    }
 }
 
-pub struct SemanticObserverDistiller<A: ObserverAdapter> {
+pub struct SemanticObserverDistiller<A: ObserverAdapter + Sync> {
     config: DistillationConfig,
     semantic: SemanticObserverConfig,
+    budget: BudgetManager,
+    breaker: CircuitBreaker,
     adapter: A,
+    clock: SharedClock,
 }
 
-impl<A: ObserverAdapter> SemanticObserverDistiller<A> {
+impl<A: ObserverAdapter + Sync> SemanticObserverDistiller<A> {
     pub fn new(config: DistillationConfig, semantic: SemanticObserverConfig, adapter: A) -> Self {
         Self {
+            budget: BudgetManager::new(semantic.budget.clone()),
+            breaker: CircuitBreaker::new(
+                semantic.profile.provider_name.clone(),
+                semantic.breaker.clone(),
+            ),
             config,
             semantic,
             adapter,
+            clock: system_clock(),
         }
     }
 
+    /// Times guardrail calls against `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Circuit-breaker state changes since the last call, oldest first.
+    pub fn take_breaker_transitions(&self) -> Vec<BreakerTransition> {
+        self.breaker.take_transitions()
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(stage = "distill", conversation_id = %conversation_id, runtime = "semantic")
+    )]
     pub fn distill_conversation(
         &self,
         store: &MindStore,
@@ -1663,6 +2118,9 @@ impl<A: ObserverAdapter> SemanticObserverDistiller<A> {
         self.distill_with_semantic_t1(store, conversation_id)
     }
 
+    /// Calls the observer for `input` and records the outcome on the breaker
+    /// right away, so concurrent calls of the same wave see a trip: every
+    /// attempt first asks the breaker, and an open one ends the retries.
     fn observe_t1_with_guardrails(
         &self,
         input: &ObserverInput,
@@ -1671,69 +2129,212 @@ impl<A: ObserverAdapter> SemanticObserverDistiller<A> {
         u16,
         Option<u64>,
     ) {
-        let max_attempts = u16::from(self.semantic.guardrails.max_retries)
-            .saturating_add(1)
-            .max(1);
-        let mut attempt = 1_u16;
-
-        loop {
-            if let Err(error) = enforce_observer_budget_guardrails(
-                input.estimated_tokens,
-                None,
-                &self.semantic.profile,
-                &self.semantic.guardrails,
-            ) {
-                return (Err(error), attempt, None);
-            }
-
-            let started_at = Utc::now();
-            let observed =
-                self.adapter
-                    .observe_t1(input, &self.semantic.profile, &self.semantic.guardrails);
-            let latency_ms = (Utc::now() - started_at).num_milliseconds().max(0) as u64;
-
-            let guarded = observed.and_then(|output| {
-                enforce_observer_budget_guardrails(
-                    input.estimated_tokens,
-                    Some(estimate_observer_output_tokens(&output)),
-                    &self.semantic.profile,
-                    &self.semantic.guardrails,
-                )?;
-
-                if self.semantic.guardrails.timeout_ms > 0
-                    && latency_ms > self.semantic.guardrails.timeout_ms
-                {
+        let mut reached_provider = false;
+        let call = call_with_semantic_guardrails(
+            "observer",
+            input.estimated_tokens,
+            &self.semantic.profile,
+            &self.semantic.guardrails,
+            self.clock.as_ref(),
+            || {
+                if let Some(reason) = self.breaker.refusal(self.clock.now()) {
                     return Err(SemanticAdapterError::new(
-                        SemanticFailureKind::Timeout,
-                        format!(
-                            "semantic observer exceeded timeout guardrail: {latency_ms}ms > {}ms",
-                            self.semantic.guardrails.timeout_ms
-                        ),
+                        SemanticFailureKind::ProviderError,
+                        reason,
                     ));
                 }
+                reached_provider = true;
+                self.adapter
+                    .observe_t1(input, &self.semantic.profile, &self.semantic.guardrails)
+            },
+            estimate_observer_output_tokens,
+        );
+        if reached_provider {
+            self.breaker.record(
+                call.0.as_ref().err().map(|error| error.kind),
+                self.clock.now(),
+            );
+        }
+        call
+    }
 
-                Ok(output)
-            });
-
-            match guarded {
-                Ok(output) => return (Ok(output), attempt, Some(latency_ms)),
-                Err(error) => {
-                    let retryable = matches!(
-                        error.kind,
-                        SemanticFailureKind::Timeout
-                            | SemanticFailureKind::ProviderError
-                            | SemanticFailureKind::LockConflict
-                    );
+    fn record_observer_usage(
+        &self,
+        store: &MindStore,
+        artifact_id: &str,
+        input: &ObserverInput,
+        attempt_count: u16,
+        output_tokens: u32,
+        fallback_used: bool,
+    ) -> Result<(), StorageError> {
+        let input_tokens = input.estimated_tokens;
+        let cost_micros = self.cost_micros(input_tokens, output_tokens);
+        let now = self.clock.now();
+        store.record_semantic_usage(&SemanticUsageEntry {
+            artifact_id: artifact_id.to_string(),
+            attempt_count,
+            tag: input.active_tag.clone(),
+            provider_name: Some(self.semantic.profile.provider_name.clone()),
+            model_id: Some(self.semantic.profile.model_id.clone()),
+            input_tokens,
+            output_tokens,
+            cost_micros,
+            fallback_used,
+            recorded_at: now,
+        })?;
+        self.budget.charge(
+            store,
+            &self.semantic.profile.provider_name,
+            u64::from(input_tokens.saturating_add(output_tokens)),
+            cost_micros,
+            now,
+        )
+    }
 
-                    if retryable && attempt < max_attempts {
-                        attempt = attempt.saturating_add(1);
-                        continue;
-                    }
+    fn cost_micros(&self, input_tokens: u32, output_tokens: u32) -> u64 {
+        self.adapter
+            .cost_micros(&self.semantic.profile.model_id, input_tokens, output_tokens)
+            .unwrap_or_else(|| {
+                estimate_semantic_cost_micros(input_tokens.saturating_add(output_tokens))
+            })
+    }
 
-                    return (Err(error), attempt, Some(latency_ms));
+    /// Makes the provider calls of a wave, one scoped thread each when more
+    /// than one is needed. Results line up with `units`; `None` where no call
+    /// was made.
+    fn observe_t1_wave(
+        &self,
+        conversation_id: &str,
+        units: &[T1WaveUnit<'_>],
+    ) -> Vec<Option<SemanticCall<ObserverOutput>>> {
+        let parent = tracing::Span::current();
+        let observe = |prepared: &PreparedT1Batch<'_>| {
+            tracing::debug_span!(
+                parent: &parent,
+                "t1_observer",
+                stage = "distill",
+                conversation_id = %conversation_id,
+                artifact_id = %prepared.artifact_id
+            )
+            .in_scope(|| self.observe_t1_with_guardrails(&prepared.observer_input))
+        };
+        let calls = units
+            .iter()
+            .map(|unit| match unit {
+                T1WaveUnit::Prepared(prepared) if prepared.settled.is_none() => {
+                    Some(prepared.as_ref())
                 }
-            }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if calls.iter().flatten().count() <= 1 {
+            return calls
+                .into_iter()
+                .map(|prepared| prepared.map(observe))
+                .collect();
+        }
+        std::thread::scope(|scope| {
+            let handles = calls
+                .into_iter()
+                .map(|prepared| prepared.map(|prepared| scope.spawn(move || observe(prepared))))
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle.map(|handle| {
+                        handle
+                            .join()
+                            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Whether a call for `input_tokens` joins the current wave, after
+    /// `wave_calls` admitted calls that reserved `reserved` of today's
+    /// budget, projected at the profile's full output allowance. An open
+    /// breaker or a budget the call cannot fit refuses it; a half-open
+    /// breaker's single trial call, or a budget that only fits the call once
+    /// the wave is charged, defers it to the next wave.
+    fn admit_call(
+        &self,
+        store: &MindStore,
+        input_tokens: u32,
+        wave_calls: usize,
+        reserved: &mut BudgetReservation,
+    ) -> Result<CallAdmission, StorageError> {
+        if let Some(reason) = self.breaker.refusal(self.clock.now()) {
+            return Ok(CallAdmission::Refused(SemanticAdapterError::new(
+                SemanticFailureKind::ProviderError,
+                reason,
+            )));
+        }
+        if wave_calls > 0 && self.breaker.state() == BreakerState::HalfOpen {
+            return Ok(CallAdmission::Deferred);
+        }
+        let output_tokens = self.semantic.profile.max_output_tokens;
+        let decision = self.budget.admit(
+            store,
+            &self.semantic.profile.provider_name,
+            u64::from(input_tokens.saturating_add(output_tokens)),
+            || self.cost_micros(input_tokens, output_tokens),
+            reserved,
+            self.clock.now(),
+        )?;
+        Ok(match decision {
+            BudgetDecision::Admit => CallAdmission::Call,
+            BudgetDecision::Defer => CallAdmission::Deferred,
+            BudgetDecision::Refuse(reason) => CallAdmission::Refused(SemanticAdapterError::new(
+                SemanticFailureKind::BudgetExceeded,
+                reason,
+            )),
+        })
+    }
+
+    /// Output cached for an identical observer input, if the cache is on and
+    /// holds an unexpired entry. An entry that no longer parses counts as a
+    /// miss and is overwritten by the fresh call.
+    fn cached_observer_output(
+        &self,
+        store: &MindStore,
+        input: &ObserverInput,
+    ) -> Result<Option<ObserverOutput>, StorageError> {
+        if !self.semantic.cache.enabled {
+            return Ok(None);
         }
+        let entry = store.semantic_cache_lookup(
+            SemanticStage::T1Observer,
+            &self.semantic.profile.prompt_version,
+            &input.input_hash,
+            self.clock.now(),
+        )?;
+        Ok(entry.and_then(|entry| serde_json::from_str(&entry.output_json).ok()))
+    }
+
+    fn cache_observer_output(
+        &self,
+        store: &MindStore,
+        artifact_id: &str,
+        input: &ObserverInput,
+        output: &ObserverOutput,
+    ) -> Result<(), StorageError> {
+        if !self.semantic.cache.enabled {
+            return Ok(());
+        }
+        let now = self.clock.now();
+        store.put_semantic_cache(&SemanticCacheEntry {
+            stage: SemanticStage::T1Observer,
+            prompt_version: self.semantic.profile.prompt_version.clone(),
+            input_hash: input.input_hash.clone(),
+            artifact_id: artifact_id.to_string(),
+            output_json: serde_json::to_string(output)
+                .map_err(|err| StorageError::Serialization(err.to_string()))?,
+            created_at: now,
+            expires_at: self.semantic.cache.expires_at(now),
+        })?;
+        store.evict_semantic_cache(now, self.semantic.cache.max_entries)?;
+        Ok(())
     }
 
     fn distill_with_semantic_t1(
@@ -1741,7 +2342,8 @@ impl<A: ObserverAdapter> SemanticObserverDistiller<A> {
         store: &MindStore,
         conversation_id: &str,
     ) -> Result<DistillationReport, DistillationError> {
-        let t0_events = store.t0_events_for_conversation(conversation_id)?;
+        let pending = pending_t0_events(store, conversation_id)?;
+        let t0_events = &pending.events;
         if t0_events.is_empty() {
             return Ok(DistillationReport::default());
         }
@@ -1750,7 +2352,7 @@ impl<A: ObserverAdapter> SemanticObserverDistiller<A> {
         let semantic_input_limit = self.semantic.profile.max_input_tokens.max(1);
         let t1_target_tokens = self.config.t1_target_tokens.min(semantic_input_limit);
         let t1_hard_cap_tokens = self.config.t1_hard_cap_tokens.min(semantic_input_limit);
-        let batches = plan_t1_batches(&t0_events, t1_target_tokens, t1_hard_cap_tokens)?;
+        let batches = plan_t1_batches(t0_events, t1_target_tokens, t1_hard_cap_tokens)?;
         let event_lookup = t0_events
             .iter()
             .map(|event| (event.compact_id.clone(), event))
@@ -1763,169 +2365,616 @@ impl<A: ObserverAdapter> SemanticObserverDistiller<A> {
             ..DistillationReport::default()
         };
 
-        let mut observations = Vec::new();
-
-        for (batch_index, batch) in batches.iter().enumerate() {
-            let mut batch_events = Vec::with_capacity(batch.compact_event_ids.len());
-            for compact_id in &batch.compact_event_ids {
-                let event = event_lookup.get(compact_id).ok_or_else(|| {
-                    DistillationError::Internal(format!("missing compact event: {compact_id}"))
-                })?;
-                batch_events.push(*event);
-            }
-
-            let ts = batch_events
-                .last()
-                .map(|event| event.ts)
-                .ok_or_else(|| DistillationError::Internal("empty T1 batch".to_string()))?;
-            let active_tag = active_tag_for_ts(&context_states, ts)
-                .unwrap_or_else(|| "global".to_string())
-                .to_lowercase();
-            let artifact_id = deterministic_artifact_id(
-                "obs",
-                conversation_id,
-                &batch.compact_event_ids,
-                self.config.t1_output_max_chars as u64,
-            );
-
-            let observer_input = ObserverInput::new(
-                conversation_id,
-                active_tag.clone(),
-                batch.compact_event_ids.clone(),
-                observer_payload_lines(&batch_events),
-                batch.estimated_tokens,
-                self.semantic.profile.prompt_version.clone(),
-            )?;
-
-            let (semantic_result, semantic_attempts, latency_ms) =
-                self.observe_t1_with_guardrails(&observer_input);
+        let redactor = Redactor::new(&self.semantic.redaction)
+            .map_err(|err| DistillationError::Internal(format!("observer redaction: {err}")))?;
+
+        journaled_distill_run(store, conversation_id, batches.len(), |run, done| {
+            let mut observations = Vec::new();
+            let indexed_batches = batches.iter().enumerate().collect::<Vec<_>>();
+
+            // Each wave settles its inputs, cache hits, and refusals in batch
+            // order until it holds `workers` calls, makes those calls
+            // concurrently, then writes its artifacts in batch order on this
+            // thread. Admitted calls reserve their projected spend, so a wave
+            // never holds more calls than today's budget has room for.
+            let workers = self.semantic.workers.max(1);
+            let mut next_batch = 0;
+            while next_batch < indexed_batches.len() {
+                let mut units = Vec::with_capacity(workers);
+                let mut wave_calls = 0;
+                let mut reserved = BudgetReservation::default();
+                while wave_calls < workers && next_batch < indexed_batches.len() {
+                    let (batch_index, batch) = indexed_batches[next_batch];
+                    let mut batch_events = Vec::with_capacity(batch.compact_event_ids.len());
+                    for compact_id in &batch.compact_event_ids {
+                        let event = event_lookup.get(compact_id).ok_or_else(|| {
+                            DistillationError::Internal(format!(
+                                "missing compact event: {compact_id}"
+                            ))
+                        })?;
+                        batch_events.push(*event);
+                    }
 
-            match semantic_result {
-                Ok(output) => {
-                    let text = synthesize_semantic_observation_text(
-                        &output,
-                        self.config.t1_output_max_chars,
-                    );
-                    store.insert_observation(
-                        &artifact_id,
+                    let ts = batch_events
+                        .last()
+                        .map(|event| event.ts)
+                        .ok_or_else(|| DistillationError::Internal("empty T1 batch".to_string()))?;
+                    let active_tag = active_tag_for_ts(&context_states, ts)
+                        .unwrap_or_else(|| "global".to_string())
+                        .to_lowercase();
+                    let artifact_id = deterministic_artifact_id(
+                        "obs",
                         conversation_id,
-                        ts,
-                        &text,
                         &batch.compact_event_ids,
-                    )?;
+                        self.config.t1_output_max_chars as u64,
+                    );
 
-                    store.upsert_semantic_provenance(&SemanticProvenance {
-                        artifact_id: artifact_id.clone(),
-                        stage: SemanticStage::T1Observer,
-                        runtime: SemanticRuntime::PiSemantic,
-                        provider_name: Some(self.semantic.profile.provider_name.clone()),
-                        model_id: Some(self.semantic.profile.model_id.clone()),
-                        prompt_version: self.semantic.profile.prompt_version.clone(),
-                        input_hash: observer_input.input_hash,
-                        output_hash: Some(canonical_payload_hash(&output)?),
-                        latency_ms,
-                        attempt_count: semantic_attempts,
-                        fallback_used: false,
-                        fallback_reason: None,
-                        failure_kind: None,
-                        created_at: ts,
-                    })?;
+                    if let Some(observation) =
+                        resumed_observation(store, done, &artifact_id, &active_tag)?
+                    {
+                        units.push(T1WaveUnit::Resumed(observation));
+                        next_batch += 1;
+                        continue;
+                    }
 
-                    observations.push(ProducedObservation {
-                        artifact_id,
+                    let observer_input = ObserverInput::new(
+                        conversation_id,
+                        active_tag.clone(),
+                        batch.compact_event_ids.clone(),
+                        observer_payload_lines(&batch_events),
+                        batch.estimated_tokens,
+                        self.semantic.profile.prompt_version.clone(),
+                    )?;
+                    let (observer_input, redactions) =
+                        redactor.redact_observer_input(&observer_input)?;
+
+                    let cached_output = self.cached_observer_output(store, &observer_input)?;
+                    let cache_hit = cached_output.is_some();
+                    let settled = match cached_output {
+                        Some(output) => Some((Ok(output), 0, None)),
+                        None => match self.admit_call(
+                            store,
+                            observer_input.estimated_tokens,
+                            wave_calls,
+                            &mut reserved,
+                        )? {
+                            CallAdmission::Call => {
+                                wave_calls += 1;
+                                None
+                            }
+                            // Like a guardrail rejection: one attempt, never sent.
+                            CallAdmission::Refused(error) => Some((Err(error), 1, None)),
+                            CallAdmission::Deferred => break,
+                        },
+                    };
+                    units.push(T1WaveUnit::Prepared(Box::new(PreparedT1Batch {
+                        batch_index,
+                        batch,
+                        batch_events,
                         ts,
                         active_tag,
-                        estimated_tokens: estimate_tokens(&text),
-                        text,
-                    });
+                        artifact_id,
+                        observer_input,
+                        redactions,
+                        cache_hit,
+                        settled,
+                    })));
+                    next_batch += 1;
                 }
-                Err(error) => {
-                    let fallback_text = synthesize_observation_text(
-                        conversation_id,
-                        batch_index + 1,
-                        batches.len(),
+
+                let calls = self.observe_t1_wave(conversation_id, &units);
+                for (unit, call) in units.into_iter().zip(calls) {
+                    let PreparedT1Batch {
+                        batch_index,
                         batch,
-                        &batch_events,
-                        self.config.t1_output_max_chars,
-                    );
-                    store.insert_observation(
-                        &artifact_id,
-                        conversation_id,
+                        batch_events,
                         ts,
-                        &fallback_text,
-                        &batch.compact_event_ids,
+                        active_tag,
+                        artifact_id,
+                        observer_input,
+                        redactions,
+                        cache_hit,
+                        settled,
+                    } = match unit {
+                        T1WaveUnit::Resumed(observation) => {
+                            observations.push(observation);
+                            report.t1_artifacts_resumed += 1;
+                            continue;
+                        }
+                        T1WaveUnit::Prepared(prepared) => *prepared,
+                    };
+                    let (semantic_result, semantic_attempts, latency_ms) = match (settled, call) {
+                        (Some(settled), _) | (None, Some(settled)) => settled,
+                        (None, None) => {
+                            return Err(DistillationError::Internal(format!(
+                                "observer wave lost the call for {artifact_id}"
+                            )))
+                        }
+                    };
+
+                    let observation = store.complete_pipeline_unit(
+                        &run.run_id,
+                        &artifact_id,
+                        Utc::now(),
+                        |store| {
+                            match semantic_result {
+                                Ok(output) => {
+                                    let text = synthesize_semantic_observation_text(
+                                        &output,
+                                        self.config.t1_output_max_chars,
+                                    );
+                                    store.insert_observation(
+                                        &artifact_id,
+                                        conversation_id,
+                                        ts,
+                                        &text,
+                                        &batch.compact_event_ids,
+                                    )?;
+                                    let sections = output.sections();
+                                    if !sections.is_empty() {
+                                        store.upsert_observation_sections(
+                                            &artifact_id,
+                                            &self.semantic.profile.prompt_version,
+                                            &sections,
+                                            Utc::now(),
+                                        )?;
+                                    }
+                                    // A cache hit made no call: nothing to charge or store.
+                                    if !cache_hit {
+                                        self.record_observer_usage(
+                                            store,
+                                            &artifact_id,
+                                            &observer_input,
+                                            semantic_attempts,
+                                            estimate_observer_output_tokens(&output),
+                                            false,
+                                        )?;
+                                        self.cache_observer_output(
+                                            store,
+                                            &artifact_id,
+                                            &observer_input,
+                                            &output,
+                                        )?;
+                                    }
+
+                                    store.upsert_semantic_provenance(&SemanticProvenance {
+                                        artifact_id: artifact_id.clone(),
+                                        stage: SemanticStage::T1Observer,
+                                        runtime: SemanticRuntime::PiSemantic,
+                                        provider_name: Some(
+                                            self.semantic.profile.provider_name.clone(),
+                                        ),
+                                        model_id: Some(self.semantic.profile.model_id.clone()),
+                                        prompt_version: self
+                                            .semantic
+                                            .profile
+                                            .prompt_version
+                                            .clone(),
+                                        input_hash: observer_input.input_hash,
+                                        output_hash: Some(canonical_payload_hash(&output)?),
+                                        latency_ms,
+                                        attempt_count: semantic_attempts,
+                                        fallback_used: false,
+                                        fallback_reason: None,
+                                        failure_kind: None,
+                                        created_at: ts,
+                                        redactions,
+                                    })?;
+
+                                    Ok::<_, DistillationError>(ProducedObservation {
+                                        artifact_id: artifact_id.clone(),
+                                        ts,
+                                        active_tag,
+                                        estimated_tokens: estimate_tokens(&text),
+                                        text,
+                                    })
+                                }
+                                Err(error) => {
+                                    let fallback_text = synthesize_observation_text(
+                                        conversation_id,
+                                        batch_index + 1,
+                                        batches.len(),
+                                        batch,
+                                        &batch_events,
+                                        self.config.t1_output_max_chars,
+                                    );
+                                    tracing::warn!(
+                                        artifact_id = %artifact_id,
+                                        failure_kind = error.kind.as_str(),
+                                        error = %error.message,
+                                        "semantic observer failed; using deterministic T1"
+                                    );
+                                    store.insert_observation(
+                                        &artifact_id,
+                                        conversation_id,
+                                        ts,
+                                        &fallback_text,
+                                        &batch.compact_event_ids,
+                                    )?;
+
+                                    store.upsert_semantic_provenance(&SemanticProvenance {
+                                        artifact_id: artifact_id.clone(),
+                                        stage: SemanticStage::T1Observer,
+                                        runtime: SemanticRuntime::PiSemantic,
+                                        provider_name: Some(
+                                            self.semantic.profile.provider_name.clone(),
+                                        ),
+                                        model_id: Some(self.semantic.profile.model_id.clone()),
+                                        prompt_version: self
+                                            .semantic
+                                            .profile
+                                            .prompt_version
+                                            .clone(),
+                                        input_hash: observer_input.input_hash.clone(),
+                                        output_hash: None,
+                                        latency_ms,
+                                        attempt_count: semantic_attempts,
+                                        fallback_used: true,
+                                        fallback_reason: Some(error.message.clone()),
+                                        failure_kind: Some(error.kind),
+                                        created_at: ts,
+                                        redactions,
+                                    })?;
+
+                                    // Calls rejected by a guardrail before reaching the
+                                    // provider carry no latency and cost nothing.
+                                    if latency_ms.is_some() {
+                                        self.record_observer_usage(
+                                            store,
+                                            &artifact_id,
+                                            &observer_input,
+                                            semantic_attempts,
+                                            0,
+                                            true,
+                                        )?;
+                                    }
+
+                                    store.upsert_semantic_provenance(&SemanticProvenance {
+                                        artifact_id: artifact_id.clone(),
+                                        stage: SemanticStage::T1Observer,
+                                        runtime: SemanticRuntime::Deterministic,
+                                        provider_name: None,
+                                        model_id: None,
+                                        prompt_version: "deterministic.observer.v1".to_string(),
+                                        input_hash: canonical_payload_hash(&(
+                                            conversation_id,
+                                            &batch.compact_event_ids,
+                                            batch.estimated_tokens,
+                                        ))?,
+                                        output_hash: Some(canonical_payload_hash(&fallback_text)?),
+                                        latency_ms: None,
+                                        attempt_count: semantic_attempts.saturating_add(1),
+                                        fallback_used: true,
+                                        fallback_reason: Some(format!(
+                                            "semantic observer failed ({})",
+                                            error.kind.as_str()
+                                        )),
+                                        failure_kind: Some(error.kind),
+                                        created_at: ts,
+                                        redactions: Vec::new(),
+                                    })?;
+
+                                    Ok::<_, DistillationError>(ProducedObservation {
+                                        artifact_id: artifact_id.clone(),
+                                        ts,
+                                        active_tag,
+                                        estimated_tokens: estimate_tokens(&fallback_text),
+                                        text: fallback_text,
+                                    })
+                                }
+                            }
+                        },
                     )?;
+                    observations.push(observation);
 
-                    store.upsert_semantic_provenance(&SemanticProvenance {
-                        artifact_id: artifact_id.clone(),
-                        stage: SemanticStage::T1Observer,
-                        runtime: SemanticRuntime::PiSemantic,
-                        provider_name: Some(self.semantic.profile.provider_name.clone()),
-                        model_id: Some(self.semantic.profile.model_id.clone()),
-                        prompt_version: self.semantic.profile.prompt_version.clone(),
-                        input_hash: observer_input.input_hash.clone(),
-                        output_hash: None,
-                        latency_ms,
-                        attempt_count: semantic_attempts,
-                        fallback_used: true,
-                        fallback_reason: Some(error.message.clone()),
-                        failure_kind: Some(error.kind),
-                        created_at: ts,
-                    })?;
+                    report.t1_artifacts_written += 1;
+                    if cache_hit {
+                        report.t1_cache_hits += 1;
+                    }
+                }
+            }
 
-                    store.upsert_semantic_provenance(&SemanticProvenance {
-                        artifact_id: artifact_id.clone(),
-                        stage: SemanticStage::T1Observer,
-                        runtime: SemanticRuntime::Deterministic,
-                        provider_name: None,
-                        model_id: None,
-                        prompt_version: "deterministic.observer.v1".to_string(),
-                        input_hash: canonical_payload_hash(&(
-                            conversation_id,
-                            &batch.compact_event_ids,
-                            batch.estimated_tokens,
-                        ))?,
-                        output_hash: Some(canonical_payload_hash(&fallback_text)?),
-                        latency_ms: None,
-                        attempt_count: semantic_attempts.saturating_add(1),
-                        fallback_used: true,
-                        fallback_reason: Some(format!(
-                            "semantic observer failed ({})",
-                            error.kind.as_str()
-                        )),
-                        failure_kind: Some(error.kind),
-                        created_at: ts,
-                    })?;
+            let observations =
+                with_earlier_observations(store, &pending, &context_states, observations)?;
+            let deterministic = DeterministicDistiller::new(self.config.clone());
+            report.t2_artifacts_written = deterministic.emit_reflections(
+                store,
+                conversation_id,
+                &observations,
+                self.config.t2_trigger_tokens,
+                self.config.t2_output_max_chars,
+            )?;
 
-                    observations.push(ProducedObservation {
-                        artifact_id,
-                        ts,
-                        active_tag,
-                        estimated_tokens: estimate_tokens(&fallback_text),
-                        text: fallback_text,
-                    });
-                }
+            if self.config.enable_attribution {
+                let attribution = TaskAttributionEngine::new(AttributionConfig::default())
+                    .attribute_conversation(store, conversation_id)?;
+                report.attribution_links_written = attribution.links_written;
             }
 
-            report.t1_artifacts_written += 1;
+            advance_distillation_checkpoint(store, &pending)?;
+            Ok(report)
+        })
+    }
+}
+
+/// Semantic path for detached reflector jobs. Writes the same `ref:auto:*`
+/// artifact as [`process_reflector_job`], from the reflector's output when
+/// the call passes its guardrails and from the deterministic text when it
+/// does not, with T2 provenance for both runtimes on fallback.
+pub struct SemanticReflectorDistiller<A: ReflectorAdapter> {
+    semantic: SemanticReflectorConfig,
+    budget: BudgetManager,
+    breaker: CircuitBreaker,
+    adapter: A,
+    clock: SharedClock,
+}
+
+impl<A: ReflectorAdapter> SemanticReflectorDistiller<A> {
+    pub fn new(semantic: SemanticReflectorConfig, adapter: A) -> Self {
+        Self {
+            budget: BudgetManager::new(semantic.budget.clone()),
+            breaker: CircuitBreaker::new(
+                semantic.profile.provider_name.clone(),
+                semantic.breaker.clone(),
+            ),
+            semantic,
+            adapter,
+            clock: system_clock(),
         }
+    }
 
-        let deterministic = DeterministicDistiller::new(self.config.clone());
-        report.t2_artifacts_written = deterministic.emit_reflections(
-            store,
-            conversation_id,
-            &observations,
-            self.config.t2_trigger_tokens,
-            self.config.t2_output_max_chars,
+    /// Times guardrail calls against `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Circuit-breaker state changes since the last call, oldest first.
+    pub fn take_breaker_transitions(&self) -> Vec<BreakerTransition> {
+        self.breaker.take_transitions()
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(stage = "t2", job_id = %job.job_id, runtime = "semantic")
+    )]
+    pub fn process_reflector_job(
+        &self,
+        store: &MindStore,
+        job: &ReflectorJob,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), ReflectorJobError> {
+        if self.semantic.mode == SemanticRuntimeMode::DeterministicOnly {
+            return process_reflector_job(store, job, now);
+        }
+
+        let observations = load_reflector_job_observations(store, job)?;
+        if observations.is_empty() {
+            return Err(ReflectorJobError::Internal(
+                "no matching observations found for reflector job".to_string(),
+            ));
+        }
+
+        let job_hash = canonical_payload_hash(&(
+            &job.active_tag,
+            &job.observation_ids,
+            &job.conversation_ids,
+            job.estimated_tokens,
+        ))?;
+        let artifact_id = format!("ref:auto:{}", &job_hash[..16]);
+        let conversation_id = observations
+            .first()
+            .map(|artifact| artifact.conversation_id.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+        let trace_ids = observations
+            .iter()
+            .map(|artifact| artifact.artifact_id.clone())
+            .collect::<Vec<_>>();
+        let payload_lines = observations
+            .iter()
+            .map(|artifact| {
+                format!(
+                    "{}: {}",
+                    artifact.artifact_id,
+                    normalize_text(&artifact.text)
+                )
+            })
+            .collect::<Vec<_>>();
+        let reflector_input = ReflectorInput::new(
+            job.active_tag.clone(),
+            trace_ids.clone(),
+            job.conversation_ids.clone(),
+            payload_lines.clone(),
+            payload_lines.iter().map(|line| estimate_tokens(line)).sum(),
+            self.semantic.profile.prompt_version.clone(),
         )?;
 
-        if self.config.enable_attribution {
-            let attribution = TaskAttributionEngine::new(AttributionConfig::default())
-                .attribute_conversation(store, conversation_id)?;
-            report.attribution_links_written = attribution.links_written;
+        let input_tokens = reflector_input.estimated_tokens;
+        let output_tokens = self.semantic.profile.max_output_tokens;
+        let refusal = match self.breaker.refusal(now) {
+            Some(reason) => Some(SemanticAdapterError::new(
+                SemanticFailureKind::ProviderError,
+                reason,
+            )),
+            None => self
+                .budget
+                .refusal(
+                    store,
+                    &self.semantic.profile.provider_name,
+                    u64::from(input_tokens.saturating_add(output_tokens)),
+                    || self.cost_micros(input_tokens, output_tokens),
+                    now,
+                )?
+                .map(|reason| {
+                    SemanticAdapterError::new(SemanticFailureKind::BudgetExceeded, reason)
+                }),
+        };
+        let (result, attempts, latency_ms) = match refusal {
+            Some(error) => (Err(error), 1, None),
+            None => call_with_semantic_guardrails(
+                "reflector",
+                input_tokens,
+                &self.semantic.profile,
+                &self.semantic.guardrails,
+                self.clock.as_ref(),
+                || {
+                    self.adapter.reflect_t2(
+                        &reflector_input,
+                        &self.semantic.profile,
+                        &self.semantic.guardrails,
+                    )
+                },
+                estimate_reflector_output_tokens,
+            ),
+        };
+        if latency_ms.is_some() {
+            self.breaker
+                .record(result.as_ref().err().map(|error| error.kind), now);
         }
 
-        Ok(report)
+        match result {
+            Ok(output) => {
+                let text = synthesize_semantic_reflection_text(&output);
+                store.insert_reflection(&artifact_id, &conversation_id, now, &text, &trace_ids)?;
+                self.record_reflector_usage(
+                    store,
+                    &artifact_id,
+                    &reflector_input,
+                    attempts,
+                    estimate_reflector_output_tokens(&output),
+                    false,
+                    now,
+                )?;
+                store.upsert_semantic_provenance(&SemanticProvenance {
+                    artifact_id: artifact_id.clone(),
+                    stage: SemanticStage::T2Reflector,
+                    runtime: SemanticRuntime::PiSemantic,
+                    provider_name: Some(self.semantic.profile.provider_name.clone()),
+                    model_id: Some(self.semantic.profile.model_id.clone()),
+                    prompt_version: self.semantic.profile.prompt_version.clone(),
+                    input_hash: reflector_input.input_hash,
+                    output_hash: Some(canonical_payload_hash(&output)?),
+                    latency_ms,
+                    attempt_count: attempts,
+                    fallback_used: false,
+                    fallback_reason: None,
+                    failure_kind: None,
+                    created_at: now,
+                    redactions: Vec::new(),
+                })?;
+            }
+            Err(error) => {
+                let fallback_text =
+                    synthesize_reflector_job_text(&job.active_tag, &observations, usize::MAX);
+                tracing::warn!(
+                    artifact_id = %artifact_id,
+                    failure_kind = error.kind.as_str(),
+                    error = %error.message,
+                    "semantic reflector failed; using deterministic T2"
+                );
+                store.insert_reflection(
+                    &artifact_id,
+                    &conversation_id,
+                    now,
+                    &fallback_text,
+                    &trace_ids,
+                )?;
+                store.upsert_semantic_provenance(&SemanticProvenance {
+                    artifact_id: artifact_id.clone(),
+                    stage: SemanticStage::T2Reflector,
+                    runtime: SemanticRuntime::PiSemantic,
+                    provider_name: Some(self.semantic.profile.provider_name.clone()),
+                    model_id: Some(self.semantic.profile.model_id.clone()),
+                    prompt_version: self.semantic.profile.prompt_version.clone(),
+                    input_hash: reflector_input.input_hash.clone(),
+                    output_hash: None,
+                    latency_ms,
+                    attempt_count: attempts,
+                    fallback_used: true,
+                    fallback_reason: Some(error.message.clone()),
+                    failure_kind: Some(error.kind),
+                    created_at: now,
+                    redactions: Vec::new(),
+                })?;
+                // As for T1, calls a guardrail rejected up front cost nothing.
+                if latency_ms.is_some() {
+                    self.record_reflector_usage(
+                        store,
+                        &artifact_id,
+                        &reflector_input,
+                        attempts,
+                        0,
+                        true,
+                        now,
+                    )?;
+                }
+                store.upsert_semantic_provenance(&SemanticProvenance {
+                    artifact_id: artifact_id.clone(),
+                    stage: SemanticStage::T2Reflector,
+                    runtime: SemanticRuntime::Deterministic,
+                    provider_name: None,
+                    model_id: None,
+                    prompt_version: "deterministic.reflector.runtime.v1".to_string(),
+                    input_hash: job_hash,
+                    output_hash: Some(canonical_payload_hash(&fallback_text)?),
+                    latency_ms: None,
+                    attempt_count: attempts.saturating_add(1),
+                    fallback_used: true,
+                    fallback_reason: Some(format!(
+                        "semantic reflector failed ({})",
+                        error.kind.as_str()
+                    )),
+                    failure_kind: Some(error.kind),
+                    created_at: now,
+                    redactions: Vec::new(),
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn record_reflector_usage(
+        &self,
+        store: &MindStore,
+        artifact_id: &str,
+        input: &ReflectorInput,
+        attempt_count: u16,
+        output_tokens: u32,
+        fallback_used: bool,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), StorageError> {
+        let input_tokens = input.estimated_tokens;
+        let cost_micros = self.cost_micros(input_tokens, output_tokens);
+        store.record_semantic_usage(&SemanticUsageEntry {
+            artifact_id: artifact_id.to_string(),
+            attempt_count,
+            tag: input.active_tag.clone(),
+            provider_name: Some(self.semantic.profile.provider_name.clone()),
+            model_id: Some(self.semantic.profile.model_id.clone()),
+            input_tokens,
+            output_tokens,
+            cost_micros,
+            fallback_used,
+            recorded_at: now,
+        })?;
+        self.budget.charge(
+            store,
+            &self.semantic.profile.provider_name,
+            u64::from(input_tokens.saturating_add(output_tokens)),
+            cost_micros,
+            now,
+        )
+    }
+
+    fn cost_micros(&self, input_tokens: u32, output_tokens: u32) -> u64 {
+        self.adapter
+            .cost_micros(&self.semantic.profile.model_id, input_tokens, output_tokens)
+            .unwrap_or_else(|| {
+                estimate_semantic_cost_micros(input_tokens.saturating_add(output_tokens))
+            })
     }
 }
 
@@ -1938,6 +2987,8 @@ pub struct SessionObserverRunOutcome {
     pub started_at: chrono::DateTime<chrono::Utc>,
     pub report: Result<DistillationReport, DistillationError>,
     pub progress: Option<MindObserverFeedProgress>,
+    /// Observer circuit-breaker state changes during the run.
+    pub breaker_transitions: Vec<BreakerTransition>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1967,6 +3018,7 @@ pub fn observer_feed_event_from_outcome(
         latency_ms: None,
         reason: None,
         failure_kind: None,
+        error_code: None,
         enqueued_at: Some(outcome.enqueued_at.to_rfc3339()),
         started_at: Some(outcome.started_at.to_rfc3339()),
         completed_at: Some(completed_at.to_rfc3339()),
@@ -1977,6 +3029,7 @@ pub fn observer_feed_event_from_outcome(
         Err(error) => {
             event.status = MindObserverFeedStatus::Error;
             event.reason = Some(error.to_string());
+            event.error_code = Some(error.error_code());
             event
         }
         Ok(report) => {
@@ -1995,7 +3048,8 @@ pub fn observer_feed_event_from_outcome(
             event.attempt_count = Some(attempt_count);
             event.latency_ms = latency_ms;
             event.reason = reason;
-            event.failure_kind = failure_kind;
+            event.failure_kind = failure_kind.map(|kind| kind.as_str().to_string());
+            event.error_code = failure_kind.map(SemanticFailureKind::error_code);
             event.status = if fallback_used {
                 MindObserverFeedStatus::Fallback
             } else {
@@ -2006,16 +3060,58 @@ pub fn observer_feed_event_from_outcome(
     }
 }
 
-pub fn enqueue_observer_and_run_events<A: ObserverAdapter>(
+/// A `breaker` event for an observer circuit-breaker state change.
+pub fn observer_feed_breaker_event(
+    outcome: &SessionObserverRunOutcome,
+    transition: &BreakerTransition,
+) -> MindObserverFeedEvent {
+    MindObserverFeedEvent {
+        status: MindObserverFeedStatus::Breaker,
+        trigger: observer_feed_trigger(outcome.trigger.kind),
+        conversation_id: Some(outcome.conversation_id.clone()),
+        runtime: None,
+        attempt_count: None,
+        latency_ms: None,
+        reason: Some(format!(
+            "{} breaker {} -> {}: {}",
+            transition.provider_name,
+            transition.from.as_str(),
+            transition.to.as_str(),
+            transition.reason
+        )),
+        failure_kind: None,
+        error_code: None,
+        enqueued_at: Some(outcome.enqueued_at.to_rfc3339()),
+        started_at: Some(outcome.started_at.to_rfc3339()),
+        completed_at: Some(transition.at.to_rfc3339()),
+        progress: None,
+    }
+}
+
+/// How [`enqueue_observer_and_run_events`] queues one observer trigger.
+#[derive(Debug, Clone)]
+pub struct ObserverEnqueueOptions {
+    pub trigger: MindObserverFeedTriggerKind,
+    /// Carried on the `queued` feed event.
+    pub reason: Option<String>,
+    pub now: chrono::DateTime<chrono::Utc>,
+    /// How long past `now` a token-threshold trigger waits before it runs.
+    pub debounce_run_ms: i64,
+}
+
+pub fn enqueue_observer_and_run_events<A: ObserverAdapter + Sync>(
     sidecar: &mut SessionObserverSidecar<A>,
     store: &MindStore,
     session_id: &str,
     conversation_id: &str,
-    trigger: MindObserverFeedTriggerKind,
-    reason: Option<String>,
-    now: chrono::DateTime<chrono::Utc>,
-    debounce_run_ms: i64,
+    options: ObserverEnqueueOptions,
 ) -> Vec<MindObserverFeedEvent> {
+    let ObserverEnqueueOptions {
+        trigger,
+        reason,
+        now,
+        debounce_run_ms,
+    } = options;
     match trigger {
         MindObserverFeedTriggerKind::TokenThreshold => {
             sidecar.enqueue_token_threshold(session_id, conversation_id, now)
@@ -2044,6 +3140,7 @@ pub fn enqueue_observer_and_run_events<A: ObserverAdapter>(
         latency_ms: None,
         reason,
         failure_kind: None,
+        error_code: None,
         enqueued_at: Some(now.to_rfc3339()),
         started_at: None,
         completed_at: None,
@@ -2064,7 +3161,7 @@ pub fn enqueue_observer_and_run_events<A: ObserverAdapter>(
     events
 }
 
-pub fn run_observer_ready_events<A: ObserverAdapter>(
+pub fn run_observer_ready_events<A: ObserverAdapter + Sync>(
     sidecar: &mut SessionObserverSidecar<A>,
     store: &MindStore,
     run_at: chrono::DateTime<chrono::Utc>,
@@ -2073,7 +3170,7 @@ pub fn run_observer_ready_events<A: ObserverAdapter>(
     drain_observer_state(sidecar, store, None, run_at, completed_at).events
 }
 
-pub fn drain_observer_state<A: ObserverAdapter>(
+pub fn drain_observer_state<A: ObserverAdapter + Sync>(
     sidecar: &mut SessionObserverSidecar<A>,
     store: &MindStore,
     session_id: Option<&str>,
@@ -2092,11 +3189,18 @@ pub fn drain_observer_state<A: ObserverAdapter>(
             latency_ms: None,
             reason: None,
             failure_kind: None,
+            error_code: None,
             enqueued_at: Some(outcome.enqueued_at.to_rfc3339()),
             started_at: Some(outcome.started_at.to_rfc3339()),
             completed_at: None,
             progress: outcome.progress.clone(),
         });
+        events.extend(
+            outcome
+                .breaker_transitions
+                .iter()
+                .map(|transition| observer_feed_breaker_event(&outcome, transition)),
+        );
         events.push(observer_feed_event_from_outcome(
             store,
             &outcome,
@@ -2290,7 +3394,8 @@ fn collect_delta_artifacts(
 }
 
 fn resolve_session_active_tag(store: &MindStore, conversation_ids: &[String]) -> Option<String> {
-    let mut latest: Option<(chrono::DateTime<chrono::Utc>, String)> = None;
+    // Ranked by (authoritative, ts): Taskmaster state beats newer command signals.
+    let mut latest: Option<((bool, chrono::DateTime<chrono::Utc>), String)> = None;
     for conversation_id in conversation_ids {
         let states = match store.context_states(conversation_id) {
             Ok(value) => value,
@@ -2307,12 +3412,13 @@ fn resolve_session_active_tag(store: &MindStore, conversation_ids: &[String]) ->
                 continue;
             };
 
+            let rank = (state.is_authoritative(), state.ts);
             let should_update = latest
                 .as_ref()
-                .map(|(ts, _)| state.ts > *ts)
+                .map(|(current, _)| rank > *current)
                 .unwrap_or(true);
             if should_update {
-                latest = Some((state.ts, tag));
+                latest = Some((rank, tag));
             }
         }
     }
@@ -2350,15 +3456,12 @@ fn latest_t1_provenance_summary(
     Option<u64>,
     bool,
     Option<String>,
-    Option<String>,
+    Option<SemanticFailureKind>,
 )> {
-    let artifacts = store.artifacts_for_conversation(conversation_id).ok()?;
-    let artifact_id = artifacts
-        .iter()
-        .rev()
-        .find(|artifact| artifact.kind == "t1")?
-        .artifact_id
-        .clone();
+    let artifact_id = store
+        .conversation_feed_summary(conversation_id)
+        .ok()?
+        .latest_t1_id?;
     let provenance = store.semantic_provenance_for_artifact(&artifact_id).ok()?;
     if provenance.is_empty() {
         return None;
@@ -2377,10 +3480,7 @@ fn latest_t1_provenance_summary(
         .find_map(|row| row.fallback_reason.clone())
         .map(|value| value.trim().to_string())
         .filter(|value| !value.is_empty());
-    let failure_kind = provenance
-        .iter()
-        .rev()
-        .find_map(|row| row.failure_kind.map(|kind| kind.as_str().to_string()));
+    let failure_kind = provenance.iter().rev().find_map(|row| row.failure_kind);
 
     Some((
         runtime,
@@ -2402,12 +3502,12 @@ fn observer_feed_trigger(kind: ObserverTriggerKind) -> MindObserverFeedTriggerKi
     }
 }
 
-pub struct SessionObserverSidecar<A: ObserverAdapter> {
+pub struct SessionObserverSidecar<A: ObserverAdapter + Sync> {
     queue: SessionObserverQueue,
     distiller: SemanticObserverDistiller<A>,
 }
 
-impl<A: ObserverAdapter> SessionObserverSidecar<A> {
+impl<A: ObserverAdapter + Sync> SessionObserverSidecar<A> {
     pub fn new(config: DistillationConfig, semantic: SemanticObserverConfig, adapter: A) -> Self {
         let queue = SessionObserverQueue::new(ObserverQueueConfig {
             debounce_ms: semantic.guardrails.queue_debounce_ms,
@@ -2416,6 +3516,12 @@ impl<A: ObserverAdapter> SessionObserverSidecar<A> {
         Self { queue, distiller }
     }
 
+    /// Times the distiller's guardrail calls against `clock`.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.distiller = self.distiller.with_clock(clock);
+        self
+    }
+
     pub fn enqueue_turn(
         &mut self,
         session_id: impl Into<String>,
@@ -2518,6 +3624,7 @@ impl<A: ObserverAdapter> SessionObserverSidecar<A> {
                 started_at: run.started_at,
                 report,
                 progress,
+                breaker_transitions: self.distiller.take_breaker_transitions(),
             });
         }
 
@@ -2570,19 +3677,25 @@ impl DeterministicDistiller {
         Self { config }
     }
 
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(stage = "distill", conversation_id = %conversation_id)
+    )]
     pub fn distill_conversation(
         &self,
         store: &MindStore,
         conversation_id: &str,
     ) -> Result<DistillationReport, DistillationError> {
-        let t0_events = store.t0_events_for_conversation(conversation_id)?;
+        let pending = pending_t0_events(store, conversation_id)?;
+        let t0_events = &pending.events;
         if t0_events.is_empty() {
             return Ok(DistillationReport::default());
         }
         let context_states = store.context_states(conversation_id)?;
 
         let batches = plan_t1_batches(
-            &t0_events,
+            t0_events,
             self.config.t1_target_tokens,
             self.config.t1_hard_cap_tokens,
         )?;
@@ -2598,86 +3711,108 @@ impl DeterministicDistiller {
             ..DistillationReport::default()
         };
 
-        let mut observations = Vec::new();
-
-        for (batch_index, batch) in batches.iter().enumerate() {
-            let mut batch_events = Vec::with_capacity(batch.compact_event_ids.len());
-            for compact_id in &batch.compact_event_ids {
-                let event = event_lookup.get(compact_id).ok_or_else(|| {
-                    DistillationError::Internal(format!("missing compact event: {compact_id}"))
-                })?;
-                batch_events.push(*event);
-            }
+        journaled_distill_run(store, conversation_id, batches.len(), |run, done| {
+            let mut observations = Vec::new();
 
-            let text = synthesize_observation_text(
-                conversation_id,
-                batch_index + 1,
-                batches.len(),
-                batch,
-                &batch_events,
-                self.config.t1_output_max_chars,
-            );
-            let artifact_id = deterministic_artifact_id(
-                "obs",
-                conversation_id,
-                &batch.compact_event_ids,
-                self.config.t1_output_max_chars as u64,
-            );
-            let ts = batch_events
-                .last()
-                .map(|event| event.ts)
-                .ok_or_else(|| DistillationError::Internal("empty T1 batch".to_string()))?;
+            for (batch_index, batch) in batches.iter().enumerate() {
+                let mut batch_events = Vec::with_capacity(batch.compact_event_ids.len());
+                for compact_id in &batch.compact_event_ids {
+                    let event = event_lookup.get(compact_id).ok_or_else(|| {
+                        DistillationError::Internal(format!("missing compact event: {compact_id}"))
+                    })?;
+                    batch_events.push(*event);
+                }
 
-            store.insert_observation(
-                &artifact_id,
-                conversation_id,
-                ts,
-                &text,
-                &batch.compact_event_ids,
-            )?;
-            persist_deterministic_provenance(
-                store,
-                &artifact_id,
-                SemanticStage::T1Observer,
-                "deterministic.observer.v1",
-                canonical_payload_hash(&(
+                let artifact_id = deterministic_artifact_id(
+                    "obs",
                     conversation_id,
                     &batch.compact_event_ids,
-                    batch.estimated_tokens,
-                ))?,
-                Some(canonical_payload_hash(&text)?),
-                ts,
-            )?;
+                    self.config.t1_output_max_chars as u64,
+                );
+                let ts = batch_events
+                    .last()
+                    .map(|event| event.ts)
+                    .ok_or_else(|| DistillationError::Internal("empty T1 batch".to_string()))?;
+                let active_tag = active_tag_for_ts(&context_states, ts)
+                    .unwrap_or_else(|| "global".to_string())
+                    .to_lowercase();
+
+                if let Some(observation) =
+                    resumed_observation(store, done, &artifact_id, &active_tag)?
+                {
+                    observations.push(observation);
+                    report.t1_artifacts_resumed += 1;
+                    continue;
+                }
 
-            let active_tag = active_tag_for_ts(&context_states, ts)
-                .unwrap_or_else(|| "global".to_string())
-                .to_lowercase();
-
-            observations.push(ProducedObservation {
-                artifact_id,
-                ts,
-                active_tag,
-                estimated_tokens: estimate_tokens(&text),
-                text,
-            });
-            report.t1_artifacts_written += 1;
-        }
+                let text = synthesize_observation_text(
+                    conversation_id,
+                    batch_index + 1,
+                    batches.len(),
+                    batch,
+                    &batch_events,
+                    self.config.t1_output_max_chars,
+                );
+                store.complete_pipeline_unit(&run.run_id, &artifact_id, Utc::now(), |store| {
+                    store.insert_observation(
+                        &artifact_id,
+                        conversation_id,
+                        ts,
+                        &text,
+                        &batch.compact_event_ids,
+                    )?;
+                    persist_deterministic_provenance(
+                        store,
+                        &artifact_id,
+                        SemanticStage::T1Observer,
+                        "deterministic.observer.v1",
+                        canonical_payload_hash(&(
+                            conversation_id,
+                            &batch.compact_event_ids,
+                            batch.estimated_tokens,
+                        ))?,
+                        Some(canonical_payload_hash(&text)?),
+                        ts,
+                    )
+                })?;
+                tracing::debug!(artifact_id = %artifact_id, "wrote T1 observation");
 
-        report.t2_artifacts_written = self.emit_reflections(
-            store,
-            conversation_id,
-            &observations,
-            self.config.t2_trigger_tokens,
-            self.config.t2_output_max_chars,
-        )?;
+                observations.push(ProducedObservation {
+                    artifact_id,
+                    ts,
+                    active_tag,
+                    estimated_tokens: estimate_tokens(&text),
+                    text,
+                });
+                report.t1_artifacts_written += 1;
+            }
 
-        if self.config.enable_attribution {
-            let attribution = TaskAttributionEngine::new(AttributionConfig::default())
-                .attribute_conversation(store, conversation_id)?;
-            report.attribution_links_written = attribution.links_written;
-        }
+            let observations =
+                with_earlier_observations(store, &pending, &context_states, observations)?;
+            report.t2_artifacts_written = self.emit_reflections(
+                store,
+                conversation_id,
+                &observations,
+                self.config.t2_trigger_tokens,
+                self.config.t2_output_max_chars,
+            )?;
+
+            if self.config.enable_attribution {
+                let attribution = TaskAttributionEngine::new(AttributionConfig::default())
+                    .attribute_conversation(store, conversation_id)?;
+                report.attribution_links_written = attribution.links_written;
+            }
 
-        Ok(report)
+            advance_distillation_checkpoint(store, &pending)?;
+            tracing::debug!(
+                t0_events = report.t0_events_processed,
+                t1_written = report.t1_artifacts_written,
+                t1_resumed = report.t1_artifacts_resumed,
+                t2_written = report.t2_artifacts_written,
+                "distilled conversation"
+            );
+            Ok(report)
+        })
     }
 
     fn emit_reflections(
@@ -2691,6 +3826,16 @@ impl DeterministicDistiller {
         if observations.is_empty() || trigger_tokens == 0 {
             return Ok(0);
         }
+        if self.config.t2_scope == ReflectionScope::SessionTree {
+            if let Some(lineage) = store.conversation_lineage(conversation_id)? {
+                return self.emit_session_tree_reflections(
+                    store,
+                    &lineage,
+                    trigger_tokens,
+                    max_chars,
+                );
+            }
+        }
 
         let mut by_tag = BTreeMap::<String, Vec<&ProducedObservation>>::new();
         for observation in observations {
@@ -2741,6 +3886,7 @@ impl DeterministicDistiller {
                     deterministic_artifact_id("ref", conversation_id, &obs_ids, max_chars as u64);
 
                 store.insert_reflection(&artifact_id, conversation_id, ts, &text, &obs_ids)?;
+                tracing::debug!(artifact_id = %artifact_id, "wrote T2 reflection");
                 persist_deterministic_provenance(
                     store,
                     &artifact_id,
@@ -2756,6 +3902,302 @@ impl DeterministicDistiller {
 
         Ok(created)
     }
+
+    /// Reflections over the T1 observations in `lineage`'s session tree that
+    /// no session reflection traces yet: each active tag's observations,
+    /// across conversations and in time order, chunked like a single
+    /// conversation's and written under the tree's root conversation with
+    /// trace ids into each branch. Observations below the trigger wait for
+    /// a later run instead of being reflected again in a bigger chunk.
+    fn emit_session_tree_reflections(
+        &self,
+        store: &MindStore,
+        lineage: &ConversationLineage,
+        trigger_tokens: u32,
+        max_chars: usize,
+    ) -> Result<usize, DistillationError> {
+        let owner_id = lineage.root_conversation_id.as_str();
+        let reflected = store
+            .conversation_artifacts(
+                owner_id,
+                &ConversationArtifactFilter {
+                    kind: Some("t2".to_string()),
+                    ..ConversationArtifactFilter::default()
+                },
+            )?
+            .into_iter()
+            .flat_map(|reflection| reflection.trace_ids)
+            .collect::<BTreeSet<_>>();
+
+        let mut by_tag = BTreeMap::<String, Vec<(String, ProducedObservation)>>::new();
+        for tree_conversation_id in
+            store.session_tree_conversations(&lineage.session_id, &lineage.conversation_id)?
+        {
+            let context_states = store.context_states(&tree_conversation_id)?;
+            let artifacts = store.conversation_artifacts(
+                &tree_conversation_id,
+                &ConversationArtifactFilter {
+                    kind: Some("t1".to_string()),
+                    ..ConversationArtifactFilter::default()
+                },
+            )?;
+            for artifact in artifacts {
+                if reflected.contains(&artifact.artifact_id) {
+                    continue;
+                }
+                let active_tag = active_tag_for_ts(&context_states, artifact.ts)
+                    .unwrap_or_else(|| "global".to_string())
+                    .to_lowercase();
+                by_tag.entry(active_tag.clone()).or_default().push((
+                    tree_conversation_id.clone(),
+                    ProducedObservation {
+                        active_tag,
+                        estimated_tokens: estimate_tokens(&artifact.text),
+                        artifact_id: artifact.artifact_id,
+                        ts: artifact.ts,
+                        text: artifact.text,
+                    },
+                ));
+            }
+        }
+
+        let scope_key = format!("session:{}:{owner_id}", lineage.session_id);
+        let mut created = 0usize;
+        for (tag, mut tagged) in by_tag {
+            tagged.sort_by(|(_, left), (_, right)| {
+                left.ts
+                    .cmp(&right.ts)
+                    .then_with(|| left.artifact_id.cmp(&right.artifact_id))
+            });
+            let refs = tagged
+                .iter()
+                .map(|(tree_conversation_id, observation)| ObservationRef {
+                    artifact_id: observation.artifact_id.clone(),
+                    conversation_id: tree_conversation_id.clone(),
+                    active_tag: tag.clone(),
+                })
+                .collect::<Vec<_>>();
+            let workstream = build_t2_workstream_batch(&tag, &refs)?;
+
+            let tagged_observations = tagged
+                .iter()
+                .map(|(_, observation)| observation)
+                .collect::<Vec<_>>();
+            let total_tokens = tagged_observations
+                .iter()
+                .map(|observation| observation.estimated_tokens)
+                .sum::<u32>();
+            if total_tokens < trigger_tokens {
+                continue;
+            }
+
+            let chunks = chunk_observations_for_t2(&tagged_observations, trigger_tokens);
+            for (chunk_index, chunk) in chunks.iter().enumerate() {
+                let obs_ids = chunk
+                    .iter()
+                    .map(|observation| observation.artifact_id.clone())
+                    .collect::<Vec<_>>();
+                let text = synthesize_reflection_text(
+                    &tag,
+                    chunk_index + 1,
+                    chunks.len(),
+                    chunk,
+                    max_chars,
+                );
+                let ts = chunk
+                    .last()
+                    .map(|observation| observation.ts)
+                    .ok_or_else(|| DistillationError::Internal("empty T2 chunk".to_string()))?;
+                let artifact_id =
+                    deterministic_artifact_id("ref", &scope_key, &obs_ids, max_chars as u64);
+
+                store.insert_reflection(&artifact_id, owner_id, ts, &text, &obs_ids)?;
+                tracing::debug!(
+                    artifact_id = %artifact_id,
+                    conversations = workstream.conversation_ids.len(),
+                    "wrote session T2 reflection"
+                );
+                persist_deterministic_provenance(
+                    store,
+                    &artifact_id,
+                    SemanticStage::T2Reflector,
+                    "deterministic.reflector.session.v1",
+                    canonical_payload_hash(&(&scope_key, &tag, &obs_ids))?,
+                    Some(canonical_payload_hash(&text)?),
+                    ts,
+                )?;
+                created += 1;
+            }
+        }
+
+        Ok(created)
+    }
+}
+
+/// T0 events a distillation run still has to cover.
+struct PendingT0 {
+    conversation_id: String,
+    events: Vec<StoredCompactEvent>,
+    /// Events the checkpoint already covered; zero on a full run.
+    covered_before: u64,
+}
+
+/// The T0 events past the conversation's distillation checkpoint. Without a
+/// checkpoint, or when events landed at or before its cursor since it was
+/// written, every event is returned so the whole conversation is re-planned.
+fn pending_t0_events(store: &MindStore, conversation_id: &str) -> Result<PendingT0, StorageError> {
+    if let Some(checkpoint) = store.distillation_checkpoint(conversation_id)? {
+        let events = store.t0_events_after(
+            conversation_id,
+            checkpoint.last_ts,
+            &checkpoint.last_compact_id,
+        )?;
+        let total = store.t0_event_count(conversation_id)?.max(0) as u64;
+        if total.saturating_sub(events.len() as u64) == checkpoint.t0_events_covered {
+            return Ok(PendingT0 {
+                conversation_id: conversation_id.to_string(),
+                events,
+                covered_before: checkpoint.t0_events_covered,
+            });
+        }
+        tracing::info!(
+            conversation_id = %conversation_id,
+            covered = checkpoint.t0_events_covered,
+            "t0 events landed behind the distillation checkpoint; re-planning"
+        );
+    }
+    Ok(PendingT0 {
+        conversation_id: conversation_id.to_string(),
+        events: store.t0_events_for_conversation(conversation_id)?,
+        covered_before: 0,
+    })
+}
+
+/// Moves the checkpoint past the events a run just distilled.
+fn advance_distillation_checkpoint(
+    store: &MindStore,
+    pending: &PendingT0,
+) -> Result<(), StorageError> {
+    let Some(last) = pending.events.last() else {
+        return Ok(());
+    };
+    store.upsert_distillation_checkpoint(&DistillationCheckpoint {
+        conversation_id: pending.conversation_id.clone(),
+        last_compact_id: last.compact_id.clone(),
+        last_ts: last.ts,
+        t0_events_covered: pending.covered_before + pending.events.len() as u64,
+        updated_at: Utc::now(),
+    })
+}
+
+/// On an incremental run, puts the conversation's earlier T1 in front of
+/// this run's so T2 reflections still see the whole conversation.
+fn with_earlier_observations(
+    store: &MindStore,
+    pending: &PendingT0,
+    context_states: &[ConversationContextState],
+    observations: Vec<ProducedObservation>,
+) -> Result<Vec<ProducedObservation>, StorageError> {
+    if pending.covered_before == 0 {
+        return Ok(observations);
+    }
+    let current = observations
+        .iter()
+        .map(|observation| observation.artifact_id.as_str())
+        .collect::<BTreeSet<_>>();
+    let mut merged = store
+        .conversation_artifacts(
+            &pending.conversation_id,
+            &ConversationArtifactFilter {
+                kind: Some("t1".to_string()),
+                ..ConversationArtifactFilter::default()
+            },
+        )?
+        .into_iter()
+        .filter(|artifact| !current.contains(artifact.artifact_id.as_str()))
+        .map(|artifact| ProducedObservation {
+            active_tag: active_tag_for_ts(context_states, artifact.ts)
+                .unwrap_or_else(|| "global".to_string())
+                .to_lowercase(),
+            estimated_tokens: estimate_tokens(&artifact.text),
+            artifact_id: artifact.artifact_id,
+            ts: artifact.ts,
+            text: artifact.text,
+        })
+        .collect::<Vec<_>>();
+    merged.extend(observations);
+    Ok(merged)
+}
+
+/// Distills under a `pipeline_runs` journal entry: resumes the unfinished
+/// run a crash or failure left for this conversation, hands `body` the T1
+/// units that run already committed, then finishes or fails the run.
+fn journaled_distill_run<F>(
+    store: &MindStore,
+    conversation_id: &str,
+    total_units: usize,
+    body: F,
+) -> Result<DistillationReport, DistillationError>
+where
+    F: FnOnce(&PipelineRun, &BTreeSet<String>) -> Result<DistillationReport, DistillationError>,
+{
+    let run = store.begin_pipeline_run(
+        DISTILL_RUN_STAGE,
+        conversation_id,
+        total_units as u64,
+        Utc::now(),
+    )?;
+    let done = if run.resumed() {
+        tracing::info!(
+            run_id = %run.run_id,
+            completed_units = run.completed_units,
+            attempts = run.attempts,
+            "resuming interrupted distillation"
+        );
+        store.pipeline_run_units(&run.run_id)?
+    } else {
+        BTreeSet::new()
+    };
+    match body(&run, &done) {
+        Ok(report) => {
+            store.finish_pipeline_run(&run.run_id, Utc::now())?;
+            Ok(report)
+        }
+        Err(err) => {
+            if let Err(journal_err) =
+                store.fail_pipeline_run(&run.run_id, &err.to_string(), Utc::now())
+            {
+                tracing::warn!(
+                    run_id = %run.run_id,
+                    error = %journal_err,
+                    "failed to journal distillation failure"
+                );
+            }
+            Err(err)
+        }
+    }
+}
+
+/// The stored T1 artifact of a unit an earlier attempt committed, or `None`
+/// when the unit still has to be written.
+fn resumed_observation(
+    store: &MindStore,
+    done: &BTreeSet<String>,
+    artifact_id: &str,
+    active_tag: &str,
+) -> Result<Option<ProducedObservation>, DistillationError> {
+    if !done.contains(artifact_id) {
+        return Ok(None);
+    }
+    Ok(store
+        .artifact_by_id(artifact_id)?
+        .map(|artifact| ProducedObservation {
+            artifact_id: artifact.artifact_id,
+            ts: artifact.ts,
+            active_tag: active_tag.to_string(),
+            estimated_tokens: estimate_tokens(&artifact.text),
+            text: artifact.text,
+        }))
 }
 
 fn plan_t1_batches(
@@ -2885,55 +4327,66 @@ fn synthesize_observation_text(
 
     for event in events {
         if let Some(text) = event.text.as_deref() {
-            let role = event.role.map(role_label).unwrap_or("message");
-            lines.push(format!("{role}: {}", normalize_text(text)));
+            // Role-less text (errors, plan updates) carries its own label.
+            lines.push(match event.role {
+                Some(role) => format!("{}: {}", role_label(role), normalize_text(text)),
+                None => normalize_text(text),
+            });
             continue;
         }
 
         if let Some(tool_meta) = event.tool_meta.as_ref() {
-            lines.push(format!(
-                "tool:{} status={:?} latency_ms={} exit_code={} output_bytes={} redacted={}",
-                tool_meta.tool_name,
-                tool_meta.status,
-                tool_meta
-                    .latency_ms
-                    .map_or("na".to_string(), |value| value.to_string()),
-                tool_meta
-                    .exit_code
-                    .map_or("na".to_string(), |value| value.to_string()),
-                tool_meta.output_bytes,
-                tool_meta.redacted
-            ));
+            lines.push(tool_meta_line(tool_meta));
         }
     }
 
     truncate_chars(lines.join("\n"), max_chars)
 }
 
+/// One observer line per tool call; the path, retry, and digest fields are
+/// appended only when the harness reported them.
+fn tool_meta_line(tool_meta: &ToolMetadataLine) -> String {
+    let mut line = format!(
+        "tool:{} status={:?} latency_ms={} exit_code={} output_bytes={} redacted={}",
+        tool_meta.tool_name,
+        tool_meta.status,
+        tool_meta
+            .latency_ms
+            .map_or("na".to_string(), |value| value.to_string()),
+        tool_meta
+            .exit_code
+            .map_or("na".to_string(), |value| value.to_string()),
+        tool_meta.output_bytes,
+        tool_meta.redacted
+    );
+    if !tool_meta.touched_paths.is_empty() {
+        line.push_str(&format!(" paths={}", tool_meta.touched_paths.join(",")));
+    }
+    if tool_meta.retry_count > 0 {
+        line.push_str(&format!(" retries={}", tool_meta.retry_count));
+    }
+    if let Some(digest) = tool_meta.output_digest.as_deref() {
+        let short = digest.get(..12).unwrap_or(digest);
+        line.push_str(&format!(" output_digest={short}"));
+    }
+    line
+}
+
 fn observer_payload_lines(events: &[&StoredCompactEvent]) -> Vec<String> {
     let mut lines = Vec::new();
 
     for event in events {
         if let Some(text) = event.text.as_deref() {
-            let role = event.role.map(role_label).unwrap_or("message");
-            lines.push(format!("{role}: {}", normalize_text(text)));
+            // Role-less text (errors, plan updates) carries its own label.
+            lines.push(match event.role {
+                Some(role) => format!("{}: {}", role_label(role), normalize_text(text)),
+                None => normalize_text(text),
+            });
             continue;
         }
 
         if let Some(tool_meta) = event.tool_meta.as_ref() {
-            lines.push(format!(
-                "tool:{} status={:?} latency_ms={} exit_code={} output_bytes={} redacted={}",
-                tool_meta.tool_name,
-                tool_meta.status,
-                tool_meta
-                    .latency_ms
-                    .map_or("na".to_string(), |value| value.to_string()),
-                tool_meta
-                    .exit_code
-                    .map_or("na".to_string(), |value| value.to_string()),
-                tool_meta.output_bytes,
-                tool_meta.redacted
-            ));
+            lines.push(tool_meta_line(tool_meta));
         }
     }
 
@@ -2947,6 +4400,25 @@ fn synthesize_semantic_observation_text(output: &ObserverOutput, max_chars: usiz
     for point in &output.key_points {
         lines.push(format!("- {}", point.trim()));
     }
+    for decision in &output.decisions {
+        lines.push(format!("decision: {}", decision.trim()));
+    }
+    for question in &output.open_questions {
+        lines.push(format!("open question: {}", question.trim()));
+    }
+    for risk in &output.risks {
+        lines.push(format!("risk: {}", risk.trim()));
+    }
+    for item in &output.action_items {
+        match item.task_ref.as_deref() {
+            Some(task_ref) => lines.push(format!(
+                "action: {} ({})",
+                item.text.trim(),
+                task_ref.trim()
+            )),
+            None => lines.push(format!("action: {}", item.text.trim())),
+        }
+    }
 
     if !output.citations.is_empty() {
         lines.push(format!("citations: {}", output.citations.join(", ")));
@@ -2965,8 +4437,38 @@ fn estimate_observer_output_tokens(output: &ObserverOutput) -> u32 {
     chars += output
         .citations
         .iter()
-        .map(|citation| citation.chars().count() + 2)
+        .chain(&output.decisions)
+        .chain(&output.open_questions)
+        .chain(&output.risks)
+        .map(|line| line.chars().count() + 2)
         .sum::<usize>();
+    chars += output
+        .action_items
+        .iter()
+        .map(|item| item.text.chars().count() + item.task_ref.as_deref().map_or(0, str::len) + 2)
+        .sum::<usize>();
+    ((chars as u32) / 4).max(1)
+}
+
+fn synthesize_semantic_reflection_text(output: &ReflectorOutput) -> String {
+    let mut lines = vec![output.reflection.trim().to_string()];
+    for item in &output.action_items {
+        lines.push(format!("- {}", item.trim()));
+    }
+    if !output.citations.is_empty() {
+        lines.push(format!("citations: {}", output.citations.join(", ")));
+    }
+    lines.join("\n")
+}
+
+fn estimate_reflector_output_tokens(output: &ReflectorOutput) -> u32 {
+    let chars = output.reflection.chars().count()
+        + output
+            .action_items
+            .iter()
+            .chain(&output.citations)
+            .map(|line| line.chars().count() + 2)
+            .sum::<usize>();
     ((chars as u32) / 4).max(1)
 }
 
@@ -2974,7 +4476,80 @@ fn estimate_semantic_cost_micros(tokens: u32) -> u64 {
     u64::from(tokens).saturating_mul(DEFAULT_SEMANTIC_COST_MICROS_PER_TOKEN)
 }
 
-fn enforce_observer_budget_guardrails(
+/// Calls a semantic stage (`stage` names it in guardrail errors), checking
+/// the budgets before every call and on every output, and retrying timeouts,
+/// provider errors, and lock conflicts up to `guardrails.max_retries` times.
+/// Returns the attempt count and the last call's latency, read from
+/// `clock`, which is `None` when a budget check rejected the input before
+/// any call.
+fn call_with_semantic_guardrails<T>(
+    stage: &str,
+    input_tokens: u32,
+    profile: &SemanticModelProfile,
+    guardrails: &SemanticGuardrails,
+    clock: &dyn Clock,
+    mut call: impl FnMut() -> Result<T, SemanticAdapterError>,
+    output_tokens: impl Fn(&T) -> u32,
+) -> (Result<T, SemanticAdapterError>, u16, Option<u64>) {
+    let max_attempts = u16::from(guardrails.max_retries).saturating_add(1).max(1);
+    let mut attempt = 1_u16;
+
+    loop {
+        if let Err(error) =
+            enforce_semantic_budget_guardrails(stage, input_tokens, None, profile, guardrails)
+        {
+            return (Err(error), attempt, None);
+        }
+
+        let started_at = clock.now();
+        let called = call();
+        let latency_ms = (clock.now() - started_at).num_milliseconds().max(0) as u64;
+
+        let guarded = called.and_then(|output| {
+            enforce_semantic_budget_guardrails(
+                stage,
+                input_tokens,
+                Some(output_tokens(&output)),
+                profile,
+                guardrails,
+            )?;
+
+            if guardrails.timeout_ms > 0 && latency_ms > guardrails.timeout_ms {
+                return Err(SemanticAdapterError::new(
+                    SemanticFailureKind::Timeout,
+                    format!(
+                        "semantic {stage} exceeded timeout guardrail: {latency_ms}ms > {}ms",
+                        guardrails.timeout_ms
+                    ),
+                ));
+            }
+
+            Ok(output)
+        });
+
+        match guarded {
+            Ok(output) => return (Ok(output), attempt, Some(latency_ms)),
+            Err(error) => {
+                let retryable = matches!(
+                    error.kind,
+                    SemanticFailureKind::Timeout
+                        | SemanticFailureKind::ProviderError
+                        | SemanticFailureKind::LockConflict
+                );
+
+                if retryable && attempt < max_attempts {
+                    attempt = attempt.saturating_add(1);
+                    continue;
+                }
+
+                return (Err(error), attempt, Some(latency_ms));
+            }
+        }
+    }
+}
+
+fn enforce_semantic_budget_guardrails(
+    stage: &str,
     input_tokens: u32,
     output_tokens: Option<u32>,
     profile: &SemanticModelProfile,
@@ -2984,7 +4559,7 @@ fn enforce_observer_budget_guardrails(
         return Err(SemanticAdapterError::new(
             SemanticFailureKind::BudgetExceeded,
             format!(
-                "observer input tokens exceed profile limit: {input_tokens} > {}",
+                "{stage} input tokens exceed profile limit: {input_tokens} > {}",
                 profile.max_input_tokens
             ),
         ));
@@ -2995,7 +4570,7 @@ fn enforce_observer_budget_guardrails(
             return Err(SemanticAdapterError::new(
                 SemanticFailureKind::BudgetExceeded,
                 format!(
-                    "observer output tokens exceed profile limit: {output_tokens} > {}",
+                    "{stage} output tokens exceed profile limit: {output_tokens} > {}",
                     profile.max_output_tokens
                 ),
             ));
@@ -3012,7 +4587,7 @@ fn enforce_observer_budget_guardrails(
     if observed_tokens > budget_tokens {
         return Err(SemanticAdapterError::new(
             SemanticFailureKind::BudgetExceeded,
-            format!("observer token budget exceeded: {observed_tokens} > {budget_tokens}"),
+            format!("{stage} token budget exceeded: {observed_tokens} > {budget_tokens}"),
         ));
     }
 
@@ -3024,7 +4599,7 @@ fn enforce_observer_budget_guardrails(
             return Err(SemanticAdapterError::new(
                 SemanticFailureKind::BudgetExceeded,
                 format!(
-                    "observer cost budget exceeded: {projected_cost} > {} micros",
+                    "{stage} cost budget exceeded: {projected_cost} > {} micros",
                     guardrails.max_budget_cost_micros
                 ),
             ));
@@ -3040,9 +4615,15 @@ fn load_reflector_job_observations(
 ) -> Result<Vec<StoredArtifact>, StorageError> {
     let mut observations = Vec::new();
     for conversation_id in &job.conversation_ids {
-        let artifacts = store.artifacts_for_conversation(conversation_id)?;
+        let artifacts = store.conversation_artifacts(
+            conversation_id,
+            &ConversationArtifactFilter {
+                kind: Some("t1".to_string()),
+                ..ConversationArtifactFilter::default()
+            },
+        )?;
         for artifact in artifacts {
-            if artifact.kind == "t1" && job.observation_ids.contains(&artifact.artifact_id) {
+            if job.observation_ids.contains(&artifact.artifact_id) {
                 observations.push(artifact);
             }
         }
@@ -3145,6 +4726,7 @@ fn persist_deterministic_provenance(
         fallback_reason: None,
         failure_kind: None,
         created_at,
+        redactions: Vec::new(),
     })?;
     Ok(())
 }
@@ -3557,6 +5139,7 @@ fn active_tag_for_ts(
     ts: chrono::DateTime<chrono::Utc>,
 ) -> Option<String> {
     let mut active_tag = None;
+    let mut authoritative_tag = None;
     for context in context_states {
         if context.ts > ts {
             break;
@@ -3567,10 +5150,14 @@ fn active_tag_for_ts(
             .map(str::trim)
             .filter(|value| !value.is_empty())
         {
-            active_tag = Some(tag.to_string());
+            if context.is_authoritative() {
+                authoritative_tag = Some(tag.to_string());
+            } else {
+                active_tag = Some(tag.to_string());
+            }
         }
     }
-    active_tag
+    authoritative_tag.or(active_tag)
 }
 
 #[cfg(test)]
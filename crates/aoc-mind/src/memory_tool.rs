@@ -0,0 +1,602 @@
+//! Anthropic memory tool (`memory_20250818`) served from the Mind.
+//!
+//! The tool treats memory as files under `/memories` and sends `view`,
+//! `create`, `str_replace`, `insert`, `delete`, and `rename` commands.
+//! [`MemoryToolShim`] answers them from the store instead of a directory:
+//!
+//! - `/memories/decisions/*` are decision log chains; every write appends a
+//!   decision superseding the previous one.
+//! - `/memories/canon/<topic>.md` and `/memories/recall/<terms>.md` are
+//!   read-only views over active canon and artifact/decision search.
+//! - Any other file is a note: each write is a T1 observation traced to a
+//!   system message event, and older revisions are archived.
+//!
+//! Every write also records a raw event in the file's `memory:<path>`
+//! conversation, so doctor and `aoc query --show-trace` see where it came from.
+
+use crate::project_scope_key;
+use aoc_core::mind_contracts::{
+    canonical_lineage_attrs, normalize_agent_id, ConversationLineageMetadata, ConversationRole,
+    MessageEvent, RawEvent, RawEventBody,
+};
+use aoc_core::{ErrorCode, ErrorCoded};
+use aoc_storage::{ArchivedArtifact, ArtifactQuery, MemDecision, MindStore, StorageError};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeSet, path::Path};
+use thiserror::Error;
+
+pub const MEMORY_ROOT: &str = "/memories";
+/// Session every memory file conversation belongs to, so they can be listed.
+pub const MEMORY_TOOL_SESSION_ID: &str = "memory-tool";
+/// Raw event attribute holding the command, path, and outcome of a write.
+pub const MEMORY_TOOL_ATTR: &str = "memory_tool";
+/// Agent registry `kind` for agents writing through the shim.
+pub const MEMORY_TOOL_AGENT_KIND: &str = "memory_tool";
+
+const DECISIONS_DIR: &str = "/memories/decisions";
+const CANON_DIR: &str = "/memories/canon";
+const RECALL_DIR: &str = "/memories/recall";
+const RECALL_LIMIT: usize = 5;
+const SUPERSEDED_REASON: &str = "memory_tool_superseded";
+const DELETED_REASON: &str = "memory_tool_deleted";
+
+/// One tool call, as the model sends it.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum MemoryToolCommand {
+    View {
+        path: String,
+        #[serde(default)]
+        view_range: Option<(usize, isize)>,
+    },
+    Create {
+        path: String,
+        file_text: String,
+    },
+    StrReplace {
+        path: String,
+        old_str: String,
+        #[serde(default)]
+        new_str: String,
+    },
+    Insert {
+        path: String,
+        insert_line: usize,
+        insert_text: String,
+    },
+    Delete {
+        path: String,
+    },
+    Rename {
+        old_path: String,
+        new_path: String,
+    },
+}
+
+/// Display text is what the model sees as the tool error.
+#[derive(Debug, Error)]
+pub enum MemoryToolError {
+    #[error("The path {0} does not exist. Please provide a valid path.")]
+    NotFound(String),
+    #[error("Invalid path {path}: {reason}")]
+    InvalidPath { path: String, reason: &'static str },
+    #[error("{0} is read-only: it is generated from the project Mind.")]
+    ReadOnly(String),
+    #[error("{0}")]
+    InvalidInput(String),
+    #[error("memory store error: {0}")]
+    Storage(#[from] StorageError),
+}
+
+impl ErrorCoded for MemoryToolError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::NotFound(_) => ErrorCode::NotFound,
+            Self::InvalidPath { .. } | Self::InvalidInput(_) => ErrorCode::BadRequest,
+            Self::ReadOnly(_) => ErrorCode::ReadOnly,
+            Self::Storage(err) => err.error_code(),
+        }
+    }
+}
+
+enum MemoryFile {
+    Note,
+    Decision,
+    Canon(String),
+    Recall(String),
+}
+
+pub struct MemoryToolShim<'a> {
+    store: &'a MindStore,
+    project_id: String,
+    agent_id: String,
+}
+
+impl<'a> MemoryToolShim<'a> {
+    pub fn new(store: &'a MindStore, project_root: &Path, agent_id: &str) -> Self {
+        Self {
+            store,
+            project_id: project_scope_key(project_root),
+            agent_id: normalize_agent_id(agent_id),
+        }
+    }
+
+    /// Runs one command and returns the text result for the model.
+    pub fn execute(
+        &self,
+        command: MemoryToolCommand,
+        now: DateTime<Utc>,
+    ) -> Result<String, MemoryToolError> {
+        match command {
+            MemoryToolCommand::View { path, view_range } => self.view(&path, view_range),
+            MemoryToolCommand::Create { path, file_text } => {
+                let path = writable_path(&path)?;
+                self.write(&path, "create", &file_text, now)?;
+                Ok(format!("File created successfully at: {path}"))
+            }
+            MemoryToolCommand::StrReplace {
+                path,
+                old_str,
+                new_str,
+            } => {
+                let path = writable_path(&path)?;
+                let current = self.read(&path)?;
+                let text = replace_once(&path, &current, &old_str, &new_str)?;
+                self.write(&path, "str_replace", &text, now)?;
+                Ok("The memory file has been edited.".to_string())
+            }
+            MemoryToolCommand::Insert {
+                path,
+                insert_line,
+                insert_text,
+            } => {
+                let path = writable_path(&path)?;
+                let current = self.read(&path)?;
+                let mut lines = current.lines().collect::<Vec<_>>();
+                if insert_line > lines.len() {
+                    return Err(MemoryToolError::InvalidInput(format!(
+                        "Invalid `insert_line` parameter: {insert_line}. It should be within the range of lines of the file: [0, {}]",
+                        lines.len()
+                    )));
+                }
+                let inserted = insert_text.trim_end_matches('\n');
+                lines.insert(insert_line, inserted);
+                self.write(&path, "insert", &lines.join("\n"), now)?;
+                Ok(format!("The file {path} has been edited."))
+            }
+            MemoryToolCommand::Delete { path } => {
+                let path = writable_path(&path)?;
+                if is_decision(&path) {
+                    return Err(MemoryToolError::InvalidInput(format!(
+                        "{path} is in the append-only decision log; edit it to supersede the decision instead"
+                    )));
+                }
+                self.read(&path)?;
+                self.delete_note(&path, "delete", now)?;
+                Ok(format!("Successfully deleted {path}"))
+            }
+            MemoryToolCommand::Rename { old_path, new_path } => {
+                let old_path = writable_path(&old_path)?;
+                let new_path = writable_path(&new_path)?;
+                if is_decision(&old_path) || is_decision(&new_path) {
+                    return Err(MemoryToolError::InvalidInput(
+                        "decision files cannot be renamed".to_string(),
+                    ));
+                }
+                if self.exists(&new_path)? {
+                    return Err(MemoryToolError::InvalidInput(format!(
+                        "The destination {new_path} already exists"
+                    )));
+                }
+                let text = self.read(&old_path)?;
+                self.write(&new_path, "rename", &text, now)?;
+                self.delete_note(&old_path, "rename", now)?;
+                Ok(format!("Successfully renamed {old_path} to {new_path}"))
+            }
+        }
+    }
+
+    fn view(
+        &self,
+        path: &str,
+        view_range: Option<(usize, isize)>,
+    ) -> Result<String, MemoryToolError> {
+        let path = normalize_path(path)?;
+        if path == MEMORY_ROOT || path == DECISIONS_DIR || path == CANON_DIR || path == RECALL_DIR {
+            return self.list(&path);
+        }
+        let text = match classify(&path) {
+            MemoryFile::Canon(topic) => self.canon_text(&path, &topic)?,
+            MemoryFile::Recall(terms) => self.recall_text(&terms)?,
+            MemoryFile::Note | MemoryFile::Decision if !self.exists(&path)? => {
+                let dir_prefix = format!("{path}/");
+                if self
+                    .written_paths()?
+                    .iter()
+                    .any(|written| written.starts_with(&dir_prefix))
+                {
+                    return self.list(&path);
+                }
+                return Err(MemoryToolError::NotFound(path));
+            }
+            MemoryFile::Note | MemoryFile::Decision => self.read(&path)?,
+        };
+        let lines = text.lines().collect::<Vec<_>>();
+        let (start, end) = match view_range {
+            None => (1, lines.len()),
+            Some((start, end)) => {
+                let end = if end < 0 { lines.len() } else { end as usize };
+                if start == 0 || start > end.max(1) || end > lines.len() {
+                    return Err(MemoryToolError::InvalidInput(format!(
+                        "Invalid `view_range`: [{start}, {end}] for a file of {} lines",
+                        lines.len()
+                    )));
+                }
+                (start, end)
+            }
+        };
+        let mut out = format!("Here's the content of {path} with line numbers:");
+        for (index, line) in lines.iter().enumerate().take(end).skip(start - 1) {
+            out.push_str(&format!("\n{:>6}\t{line}", index + 1));
+        }
+        Ok(out)
+    }
+
+    fn list(&self, dir: &str) -> Result<String, MemoryToolError> {
+        let mut entries = BTreeSet::new();
+        if dir == MEMORY_ROOT {
+            for sub in [CANON_DIR, DECISIONS_DIR, RECALL_DIR] {
+                entries.insert(format!("{sub}/"));
+            }
+        }
+        if dir == MEMORY_ROOT || dir == CANON_DIR {
+            for entry in self.store.active_canon_entries(None)? {
+                if let Some(topic) = entry.topic {
+                    entries.insert(format!("{CANON_DIR}/{topic}.md"));
+                }
+            }
+        }
+        if dir != CANON_DIR && dir != RECALL_DIR {
+            for path in self.written_paths()? {
+                if dir == MEMORY_ROOT || path.starts_with(&format!("{dir}/")) {
+                    entries.insert(path);
+                }
+            }
+        }
+        let mut out = format!("Directory: {dir}");
+        if dir == RECALL_DIR {
+            out.push_str("\n(view recall/<search terms>.md to search project memory)");
+        }
+        for entry in entries {
+            out.push_str(&format!("\n- {entry}"));
+        }
+        Ok(out)
+    }
+
+    fn canon_text(&self, path: &str, topic: &str) -> Result<String, MemoryToolError> {
+        let entries = self.store.active_canon_entries(Some(topic))?;
+        if entries.is_empty() {
+            return Err(MemoryToolError::NotFound(path.to_string()));
+        }
+        Ok(entries
+            .iter()
+            .map(|entry| format!("- [{}] {}", entry.entry_id, entry.summary.trim()))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    fn recall_text(&self, terms: &str) -> Result<String, MemoryToolError> {
+        let artifacts = self.store.query_artifacts(&ArtifactQuery {
+            text: Some(terms.to_string()),
+            limit: RECALL_LIMIT,
+            ..ArtifactQuery::default()
+        })?;
+        let decisions = self
+            .store
+            .search_mem_decisions(terms, None, None, RECALL_LIMIT)?;
+        let mut lines = vec![format!("# Recall: {terms}")];
+        for decision in decisions {
+            if self
+                .store
+                .mem_decision_successor(&decision.decision_id)?
+                .is_some()
+            {
+                continue;
+            }
+            lines.push(format!(
+                "- decision {}: {}",
+                decision.decision_id,
+                first_line(&decision.text)
+            ));
+        }
+        for artifact in artifacts.artifacts {
+            lines.push(format!(
+                "- {} {}: {}",
+                artifact.kind,
+                artifact.artifact_id,
+                first_line(&artifact.text)
+            ));
+        }
+        if lines.len() == 1 {
+            lines.push("(no matches)".to_string());
+        }
+        Ok(lines.join("\n"))
+    }
+
+    fn exists(&self, path: &str) -> Result<bool, MemoryToolError> {
+        Ok(self.latest_write(path)?.is_some_and(|state| !state.deleted))
+    }
+
+    /// Current text of a note or decision file.
+    fn read(&self, path: &str) -> Result<String, MemoryToolError> {
+        let not_found = || MemoryToolError::NotFound(path.to_string());
+        let state = self
+            .latest_write(path)?
+            .filter(|state| !state.deleted)
+            .ok_or_else(not_found)?;
+        if let Some(mut decision_id) = state.decision_id {
+            // Follow supersedes made outside the tool, e.g. `aoc decisions`.
+            while let Some(next) = self.store.mem_decision_successor(&decision_id)? {
+                decision_id = next.decision_id;
+            }
+            return self
+                .store
+                .mem_decision_text(&decision_id)?
+                .ok_or_else(not_found);
+        }
+        self.store
+            .artifacts_for_conversation(&conversation_id(path))?
+            .pop()
+            .map(|artifact| artifact.text)
+            .ok_or_else(not_found)
+    }
+
+    fn written_paths(&self) -> Result<Vec<String>, MemoryToolError> {
+        let mut paths = Vec::new();
+        for conversation_id in self
+            .store
+            .conversation_ids_for_session(MEMORY_TOOL_SESSION_ID)?
+        {
+            let Some(path) = conversation_id.strip_prefix("memory:") else {
+                continue;
+            };
+            if self.exists(path)? {
+                paths.push(path.to_string());
+            }
+        }
+        Ok(paths)
+    }
+
+    fn latest_write(&self, path: &str) -> Result<Option<WriteState>, MemoryToolError> {
+        Ok(self
+            .store
+            .raw_events_for_conversation(&conversation_id(path))?
+            .last()
+            .map(|event| {
+                let attr = &event.attrs[MEMORY_TOOL_ATTR];
+                WriteState {
+                    deleted: attr["deleted"].as_bool().unwrap_or(false),
+                    decision_id: attr["decision_id"].as_str().map(str::to_string),
+                }
+            }))
+    }
+
+    fn write(
+        &self,
+        path: &str,
+        command: &str,
+        text: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), MemoryToolError> {
+        let conversation_id = conversation_id(path);
+        let previous = self.latest_write(path)?.filter(|state| !state.deleted);
+        let (event_id, revision_id) = self.next_ids(path)?;
+        let mut outcome = json!({ "command": command, "path": path, "deleted": false });
+        if is_decision(path) {
+            let decision_id = format!("memory:{revision_id}");
+            self.store.insert_mem_decision(&MemDecision {
+                decision_id: decision_id.clone(),
+                ts: now,
+                project_id: self.project_id.clone(),
+                segment_id: None,
+                text: text.to_string(),
+                supersedes_id: self.current_decision(previous)?,
+            })?;
+            outcome["decision_id"] = Value::String(decision_id);
+            self.record_event(&conversation_id, &event_id, text, outcome, now)?;
+        } else {
+            self.record_event(&conversation_id, &event_id, text, outcome, now)?;
+            self.archive_notes(path, SUPERSEDED_REASON, now)?;
+            self.store.insert_observation(
+                &format!("obs:memory:{revision_id}"),
+                &conversation_id,
+                now,
+                text,
+                &[event_id],
+            )?;
+        }
+        self.store
+            .ensure_agent(&self.agent_id, MEMORY_TOOL_AGENT_KIND, now)?;
+        Ok(())
+    }
+
+    fn current_decision(
+        &self,
+        previous: Option<WriteState>,
+    ) -> Result<Option<String>, MemoryToolError> {
+        let Some(mut decision_id) = previous.and_then(|state| state.decision_id) else {
+            return Ok(None);
+        };
+        while let Some(next) = self.store.mem_decision_successor(&decision_id)? {
+            decision_id = next.decision_id;
+        }
+        Ok(Some(decision_id))
+    }
+
+    fn delete_note(
+        &self,
+        path: &str,
+        command: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), MemoryToolError> {
+        let (event_id, _) = self.next_ids(path)?;
+        self.record_event(
+            &conversation_id(path),
+            &event_id,
+            "",
+            json!({ "command": command, "path": path, "deleted": true }),
+            now,
+        )?;
+        self.archive_notes(path, DELETED_REASON, now)
+    }
+
+    fn archive_notes(
+        &self,
+        path: &str,
+        reason: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), MemoryToolError> {
+        for artifact in self
+            .store
+            .artifacts_for_conversation(&conversation_id(path))?
+        {
+            self.store.archive_artifact(&ArchivedArtifact {
+                artifact_id: artifact.artifact_id,
+                conversation_id: artifact.conversation_id,
+                kind: artifact.kind,
+                retention_bps: 0,
+                reason: reason.to_string(),
+                archived_at: now,
+            })?;
+        }
+        Ok(())
+    }
+
+    fn record_event(
+        &self,
+        conversation_id: &str,
+        event_id: &str,
+        text: &str,
+        outcome: Value,
+        now: DateTime<Utc>,
+    ) -> Result<(), MemoryToolError> {
+        let mut attrs = canonical_lineage_attrs(&ConversationLineageMetadata {
+            session_id: MEMORY_TOOL_SESSION_ID.to_string(),
+            parent_conversation_id: None,
+            root_conversation_id: conversation_id.to_string(),
+        });
+        attrs.insert(MEMORY_TOOL_ATTR.to_string(), outcome);
+        self.store.insert_raw_event(&RawEvent {
+            event_id: event_id.to_string(),
+            conversation_id: conversation_id.to_string(),
+            agent_id: self.agent_id.clone(),
+            ts: now,
+            body: RawEventBody::Message(MessageEvent {
+                role: ConversationRole::System,
+                text: text.to_string(),
+            }),
+            attrs,
+        })?;
+        Ok(())
+    }
+
+    /// Event id and revision id of the next write to `path`.
+    fn next_ids(&self, path: &str) -> Result<(String, String), MemoryToolError> {
+        let revision = self
+            .store
+            .raw_events_for_conversation(&conversation_id(path))?
+            .len()
+            + 1;
+        let digest = Sha256::digest(path.as_bytes());
+        let key = digest[..8]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        let revision_id = format!("{key}:{revision}");
+        Ok((format!("memory:{revision_id}"), revision_id))
+    }
+}
+
+struct WriteState {
+    deleted: bool,
+    decision_id: Option<String>,
+}
+
+fn conversation_id(path: &str) -> String {
+    format!("memory:{path}")
+}
+
+fn is_decision(path: &str) -> bool {
+    matches!(classify(path), MemoryFile::Decision)
+}
+
+fn classify(path: &str) -> MemoryFile {
+    let file_stem = |rest: &str| rest.trim_end_matches(".md").to_string();
+    if let Some(rest) = path.strip_prefix("/memories/canon/") {
+        MemoryFile::Canon(file_stem(rest))
+    } else if let Some(rest) = path.strip_prefix("/memories/recall/") {
+        MemoryFile::Recall(file_stem(rest).replace(['-', '_'], " "))
+    } else if path.starts_with("/memories/decisions/") {
+        MemoryFile::Decision
+    } else {
+        MemoryFile::Note
+    }
+}
+
+fn normalize_path(path: &str) -> Result<String, MemoryToolError> {
+    let invalid = |reason| MemoryToolError::InvalidPath {
+        path: path.to_string(),
+        reason,
+    };
+    let trimmed = path.trim().trim_end_matches('/');
+    if trimmed != MEMORY_ROOT && !trimmed.starts_with("/memories/") {
+        return Err(invalid("memory paths must be under /memories"));
+    }
+    if trimmed
+        .split('/')
+        .any(|segment| segment == ".." || segment == ".")
+    {
+        return Err(invalid("relative segments are not allowed"));
+    }
+    Ok(trimmed.to_string())
+}
+
+fn writable_path(path: &str) -> Result<String, MemoryToolError> {
+    let path = normalize_path(path)?;
+    match classify(&path) {
+        MemoryFile::Canon(_) | MemoryFile::Recall(_) => Err(MemoryToolError::ReadOnly(path)),
+        _ if path == MEMORY_ROOT || path == DECISIONS_DIR => Err(MemoryToolError::InvalidPath {
+            path,
+            reason: "expected a file path",
+        }),
+        _ => Ok(path),
+    }
+}
+
+fn replace_once(
+    path: &str,
+    current: &str,
+    old_str: &str,
+    new_str: &str,
+) -> Result<String, MemoryToolError> {
+    let matches = current.match_indices(old_str).count();
+    match matches {
+        _ if old_str.is_empty() => Err(MemoryToolError::InvalidInput(
+            "`old_str` must not be empty".to_string(),
+        )),
+        0 => Err(MemoryToolError::InvalidInput(format!(
+            "No replacement was performed, old_str `{old_str}` did not appear verbatim in {path}."
+        ))),
+        1 => Ok(current.replacen(old_str, new_str, 1)),
+        _ => Err(MemoryToolError::InvalidInput(format!(
+            "No replacement was performed. Multiple occurrences of old_str `{old_str}` in {path}. Please ensure it is unique."
+        ))),
+    }
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default().trim()
+}
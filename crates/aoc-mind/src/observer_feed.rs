@@ -0,0 +1,185 @@
+//! In-process fan-out of observer feed events.
+//!
+//! [`ObserverFeedPublisher`] hands every [`MindObserverFeedEvent`] it is
+//! given to each live subscriber, so a server can stream observer progress
+//! (`aoc serve` exposes it as `GET /v1/observer/feed`) instead of dashboards
+//! polling provenance rows. Publishing never blocks: a subscriber whose
+//! buffer is full misses the event, and one whose receiving side is gone is
+//! dropped on the next publish. Clones share the same subscribers.
+
+use aoc_core::mind_observer_feed::MindObserverFeedEvent;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError},
+    Arc, Mutex, MutexGuard,
+};
+use std::time::Duration;
+
+/// Buffered events per [`ObserverFeedPublisher::subscribe`] subscriber.
+pub const DEFAULT_OBSERVER_FEED_CAPACITY: usize = 256;
+
+type Deliver = Box<dyn FnMut(&MindObserverFeedEvent) -> bool + Send>;
+
+#[derive(Clone, Default)]
+pub struct ObserverFeedPublisher {
+    subscribers: Arc<Mutex<Vec<Deliver>>>,
+}
+
+impl std::fmt::Debug for ObserverFeedPublisher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObserverFeedPublisher")
+            .field("subscribers", &self.subscriber_count())
+            .finish()
+    }
+}
+
+impl ObserverFeedPublisher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `deliver` with every event published from now on, until it
+    /// returns `false`. It runs under the publisher's lock, so it must not
+    /// block; async consumers should `try_send` into their own channel.
+    pub fn subscribe_with(
+        &self,
+        deliver: impl FnMut(&MindObserverFeedEvent) -> bool + Send + 'static,
+    ) {
+        self.lock().push(Box::new(deliver));
+    }
+
+    /// A subscription backed by a bounded channel of `capacity` events.
+    pub fn subscribe(&self, capacity: usize) -> ObserverFeedSubscription {
+        let (sender, receiver) = mpsc::sync_channel(capacity.max(1));
+        let missed = Arc::new(AtomicU64::new(0));
+        let counter = missed.clone();
+        self.subscribe_with(move |event| deliver_to(&sender, &counter, event));
+        ObserverFeedSubscription { receiver, missed }
+    }
+
+    /// Hands `event` to every subscriber; returns how many are still live.
+    pub fn publish(&self, event: &MindObserverFeedEvent) -> usize {
+        let mut subscribers = self.lock();
+        subscribers.retain_mut(|deliver| deliver(event));
+        subscribers.len()
+    }
+
+    pub fn publish_all<'a>(&self, events: impl IntoIterator<Item = &'a MindObserverFeedEvent>) {
+        for event in events {
+            self.publish(event);
+        }
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Deliver>> {
+        // A subscriber that panicked leaves the list itself intact.
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn deliver_to(
+    sender: &SyncSender<MindObserverFeedEvent>,
+    missed: &AtomicU64,
+    event: &MindObserverFeedEvent,
+) -> bool {
+    match sender.try_send(event.clone()) {
+        Ok(()) => true,
+        Err(TrySendError::Full(_)) => {
+            missed.fetch_add(1, Ordering::Relaxed);
+            true
+        }
+        Err(TrySendError::Disconnected(_)) => false,
+    }
+}
+
+/// Receiving side of [`ObserverFeedPublisher::subscribe`]; dropping it
+/// unsubscribes.
+#[derive(Debug)]
+pub struct ObserverFeedSubscription {
+    receiver: Receiver<MindObserverFeedEvent>,
+    missed: Arc<AtomicU64>,
+}
+
+impl ObserverFeedSubscription {
+    pub fn recv_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<MindObserverFeedEvent, RecvTimeoutError> {
+        self.receiver.recv_timeout(timeout)
+    }
+
+    /// Events already buffered, without waiting.
+    pub fn drain(&self) -> Vec<MindObserverFeedEvent> {
+        self.receiver.try_iter().collect()
+    }
+
+    /// Events dropped because the buffer was full.
+    pub fn missed(&self) -> u64 {
+        self.missed.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::mind_observer_feed::{MindObserverFeedStatus, MindObserverFeedTriggerKind};
+
+    fn event(conversation_id: &str) -> MindObserverFeedEvent {
+        MindObserverFeedEvent {
+            status: MindObserverFeedStatus::Success,
+            trigger: MindObserverFeedTriggerKind::ManualShortcut,
+            conversation_id: Some(conversation_id.to_string()),
+            runtime: None,
+            attempt_count: None,
+            latency_ms: None,
+            reason: None,
+            failure_kind: None,
+            error_code: None,
+            enqueued_at: None,
+            started_at: None,
+            completed_at: None,
+            progress: None,
+        }
+    }
+
+    #[test]
+    fn publisher_fans_out_counts_misses_and_drops_closed_subscribers() {
+        let publisher = ObserverFeedPublisher::new();
+        let fast = publisher.subscribe(8);
+        let slow = publisher.clone().subscribe(1);
+        let closed = publisher.subscribe(8);
+        drop(closed);
+
+        assert_eq!(publisher.publish(&event("conv-1")), 2);
+        publisher.publish_all([&event("conv-2"), &event("conv-3")]);
+        assert_eq!(publisher.subscriber_count(), 2);
+
+        let seen = fast
+            .drain()
+            .into_iter()
+            .filter_map(|event| event.conversation_id)
+            .collect::<Vec<_>>();
+        assert_eq!(seen, vec!["conv-1", "conv-2", "conv-3"]);
+        assert_eq!(
+            slow.recv_timeout(Duration::from_millis(10))
+                .expect("first event")
+                .conversation_id
+                .as_deref(),
+            Some("conv-1")
+        );
+        assert_eq!(slow.missed(), 2);
+
+        let mut remaining = 1;
+        publisher.subscribe_with(move |_| {
+            remaining -= 1;
+            remaining > 0
+        });
+        publisher.publish(&event("conv-4"));
+        assert_eq!(publisher.subscriber_count(), 2);
+    }
+}
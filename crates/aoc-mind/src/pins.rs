@@ -0,0 +1,121 @@
+use crate::project_scope_key;
+use aoc_storage::{MindPin, MindPinTargetKind, MindStore};
+use chrono::{DateTime, Utc};
+use sha2::Digest;
+use std::path::Path;
+
+const PINNED_LINE_MAX_CHARS: usize = 240;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MindPinTarget {
+    Artifact(String),
+    Decision(String),
+    Note(String),
+}
+
+/// Pins an artifact, aoc-mem decision, or free-form note into the project scope.
+///
+/// Re-pinning the same target refreshes its text and reason but keeps the
+/// original pin time, so pinned ordering stays stable across edits. Pin ids
+/// carry a digest of the project scope, so pinning one target from two
+/// projects keeps a pin in each.
+pub fn pin_mind_memory(
+    store: &MindStore,
+    project_root: &str,
+    target: MindPinTarget,
+    reason: Option<String>,
+    now: DateTime<Utc>,
+) -> Result<MindPin, String> {
+    let reason = reason
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let (target_kind, target_id, text) = match target {
+        MindPinTarget::Artifact(artifact_id) => {
+            let artifact = store
+                .artifact_by_id(&artifact_id)
+                .map_err(|err| format!("artifact lookup failed: {err}"))?
+                .ok_or_else(|| format!("artifact not found: {artifact_id}"))?;
+            (
+                MindPinTargetKind::Artifact,
+                Some(artifact_id),
+                artifact.text,
+            )
+        }
+        MindPinTarget::Decision(decision_id) => {
+            let text = store
+                .mem_decision_text(&decision_id)
+                .map_err(|err| format!("decision lookup failed: {err}"))?
+                .ok_or_else(|| format!("decision not found: {decision_id}"))?;
+            (MindPinTargetKind::Decision, Some(decision_id), text)
+        }
+        MindPinTarget::Note(text) => (MindPinTargetKind::Note, None, text),
+    };
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err("pinned memory text is empty".to_string());
+    }
+
+    let scope_key = project_scope_key(Path::new(project_root));
+    let scope = &format!("{:x}", sha2::Sha256::digest(scope_key.as_bytes()))[..8];
+    let pin_id = match target_id.as_deref() {
+        Some(id) => format!("pin:{}:{scope}:{id}", target_kind.as_str()),
+        None => {
+            let digest = sha2::Sha256::digest(text.as_bytes());
+            format!("pin:note:{scope}:{}", &format!("{:x}", digest)[..12])
+        }
+    };
+    // A pin from before scoped ids is replaced, keeping its pin time, so the
+    // target does not render twice in the scope.
+    let mut pinned_at = now;
+    if let Some(id) = target_id.as_deref() {
+        let legacy_id = format!("pin:{}:{id}", target_kind.as_str());
+        let legacy = store
+            .pinned_memories(&scope_key)
+            .map_err(|err| format!("pin listing failed: {err}"))?
+            .into_iter()
+            .find(|pin| pin.pin_id == legacy_id);
+        if let Some(legacy) = legacy {
+            pinned_at = legacy.pinned_at;
+            store
+                .unpin_memory(&legacy_id)
+                .map_err(|err| format!("unpin failed: {err}"))?;
+        }
+    }
+    let pin = MindPin {
+        pin_id,
+        scope_key,
+        target_kind,
+        target_id,
+        text,
+        reason,
+        pinned_at,
+    };
+    store
+        .pin_memory(&pin)
+        .map_err(|err| format!("pin failed: {err}"))?;
+    Ok(pin)
+}
+
+pub fn unpin_mind_memory(store: &MindStore, pin_id: &str) -> Result<bool, String> {
+    store
+        .unpin_memory(pin_id.trim())
+        .map_err(|err| format!("unpin failed: {err}"))
+}
+
+pub fn list_mind_pins(store: &MindStore, project_root: &str) -> Result<Vec<MindPin>, String> {
+    store
+        .pinned_memories(&project_scope_key(Path::new(project_root)))
+        .map_err(|err| format!("pin listing failed: {err}"))
+}
+
+pub(crate) fn render_pinned_line(pin: &MindPin) -> String {
+    let mut text = pin.text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > PINNED_LINE_MAX_CHARS {
+        text = text.chars().take(PINNED_LINE_MAX_CHARS).collect::<String>();
+        text.push('…');
+    }
+    match pin.target_id.as_deref() {
+        Some(target_id) => format!("- {text} ({}:{target_id})", pin.target_kind.as_str()),
+        None => format!("- {text}"),
+    }
+}
@@ -84,6 +84,15 @@ pub fn mind_store_path(project_root: &Path) -> PathBuf {
         .join("project.sqlite")
 }
 
+/// Returns the path to the user-level global Mind store shared by every
+/// project.
+pub fn global_mind_store_path() -> PathBuf {
+    resolve_aoc_state_home()
+        .join("aoc")
+        .join("mind")
+        .join("global.sqlite")
+}
+
 /// Compute the canonical key for a canon entry.
 pub fn canon_key(entry_id: &str, revision: u32) -> String {
     format!("{}#{}", entry_id.trim(), revision)
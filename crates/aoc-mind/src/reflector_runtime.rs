@@ -1,5 +1,6 @@
 use aoc_core::mind_contracts::SemanticGuardrails;
-use aoc_storage::{MindStore, ReflectorJob};
+use aoc_core::{ErrorCode, ErrorCoded};
+use aoc_storage::{MindStoreBackend, ReflectorJob};
 use chrono::{DateTime, Duration, Utc};
 use fs2::FileExt;
 use std::fs::{File, OpenOptions};
@@ -16,6 +17,11 @@ pub struct ReflectorRuntimeConfig {
     pub lease_ttl_ms: u64,
     pub max_jobs_per_tick: usize,
     pub requeue_on_error: bool,
+    /// Attempts before a failing job is dead-lettered as `failed`.
+    pub max_attempts: u16,
+    /// Backoff before the first retry; doubles with each further attempt.
+    pub retry_backoff_ms: u64,
+    pub max_retry_backoff_ms: u64,
 }
 
 impl ReflectorRuntimeConfig {
@@ -32,6 +38,9 @@ impl ReflectorRuntimeConfig {
             lease_ttl_ms: 30_000,
             max_jobs_per_tick: 4,
             requeue_on_error: false,
+            max_attempts: 3,
+            retry_backoff_ms: 5_000,
+            max_retry_backoff_ms: 300_000,
         }
     }
 
@@ -55,6 +64,8 @@ pub struct ReflectorTickReport {
     pub jobs_claimed: usize,
     pub jobs_completed: usize,
     pub jobs_failed: usize,
+    pub jobs_requeued: usize,
+    pub jobs_dead_lettered: usize,
 }
 
 #[derive(Debug, Error)]
@@ -65,6 +76,15 @@ pub enum ReflectorRuntimeError {
     Io(#[from] std::io::Error),
 }
 
+impl ErrorCoded for ReflectorRuntimeError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Io(_) => ErrorCode::Io,
+        }
+    }
+}
+
 struct AdvisoryReflectorFileLock {
     file: File,
 }
@@ -123,14 +143,15 @@ impl DetachedReflectorWorker {
         Self { config }
     }
 
-    pub fn run_once<F>(
+    pub fn run_once<S, F>(
         &self,
-        store: &MindStore,
+        store: &S,
         now: DateTime<Utc>,
         mut handler: F,
     ) -> Result<ReflectorTickReport, ReflectorRuntimeError>
     where
-        F: FnMut(&MindStore, &ReflectorJob) -> Result<(), String>,
+        S: MindStoreBackend + ?Sized,
+        F: FnMut(&S, &ReflectorJob) -> Result<(), String>,
     {
         let mut report = ReflectorTickReport::default();
 
@@ -171,6 +192,8 @@ impl DetachedReflectorWorker {
             };
 
             report.jobs_claimed += 1;
+            let _span =
+                tracing::info_span!("reflector_job", stage = "t2", job_id = %job.job_id).entered();
 
             match handler(store, &job) {
                 Ok(()) => {
@@ -178,13 +201,28 @@ impl DetachedReflectorWorker {
                     report.jobs_completed += 1;
                 }
                 Err(message) => {
-                    store.fail_reflector_job(
-                        &job.job_id,
-                        &self.config.owner_id,
-                        &message,
-                        now,
-                        self.config.requeue_on_error,
-                    )?;
+                    let will_requeue = self.config.requeue_on_error
+                        && job.attempts < self.config.max_attempts.max(1);
+                    tracing::warn!(error = %message, will_requeue, "reflector job failed");
+                    if will_requeue {
+                        store.retry_reflector_job(
+                            &job.job_id,
+                            &self.config.owner_id,
+                            &message,
+                            now,
+                            now + self.retry_backoff(job.attempts),
+                        )?;
+                        report.jobs_requeued += 1;
+                    } else {
+                        store.fail_reflector_job(
+                            &job.job_id,
+                            &self.config.owner_id,
+                            &message,
+                            now,
+                            false,
+                        )?;
+                        report.jobs_dead_lettered += 1;
+                    }
                     report.jobs_failed += 1;
                 }
             }
@@ -199,11 +237,23 @@ impl DetachedReflectorWorker {
 
         Ok(report)
     }
+
+    /// Wait before retrying a job that has failed `attempts` times.
+    fn retry_backoff(&self, attempts: u16) -> Duration {
+        let doublings = u32::from(attempts.saturating_sub(1)).min(32);
+        let backoff_ms = self
+            .config
+            .retry_backoff_ms
+            .saturating_mul(1_u64 << doublings)
+            .min(self.config.max_retry_backoff_ms);
+        Duration::milliseconds(backoff_ms.min(i64::MAX as u64) as i64)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_storage::MindStore;
     use chrono::TimeZone;
 
     fn ts(offset_ms: i64) -> DateTime<Utc> {
@@ -243,6 +293,9 @@ mod tests {
             lease_ttl_ms: 1_000,
             max_jobs_per_tick: 1,
             requeue_on_error: false,
+            max_attempts: 3,
+            retry_backoff_ms: 5_000,
+            max_retry_backoff_ms: 300_000,
         });
 
         let report = worker
@@ -283,6 +336,9 @@ mod tests {
             lease_ttl_ms: 1_000,
             max_jobs_per_tick: 2,
             requeue_on_error: false,
+            max_attempts: 3,
+            retry_backoff_ms: 5_000,
+            max_retry_backoff_ms: 300_000,
         });
 
         let report = worker
@@ -299,7 +355,7 @@ mod tests {
     }
 
     #[test]
-    fn worker_requeues_failures_when_enabled() {
+    fn worker_requeues_failures_with_backoff_then_dead_letters() {
         let store = MindStore::open_in_memory().expect("db");
         let lock_path = temp_lock_path("requeue");
 
@@ -307,7 +363,7 @@ mod tests {
         store
             .try_acquire_reflector_lease("scope-a", "owner-a", Some(1), now, 1_000)
             .expect("lease");
-        store
+        let job_id = store
             .enqueue_reflector_job(
                 "mind",
                 &["obs:1".to_string()],
@@ -325,15 +381,33 @@ mod tests {
             lease_ttl_ms: 1_000,
             max_jobs_per_tick: 1,
             requeue_on_error: true,
+            max_attempts: 2,
+            retry_backoff_ms: 100,
+            max_retry_backoff_ms: 1_000,
         });
+        let provider_timeout =
+            |_store: &MindStore, _job: &ReflectorJob| Err::<(), _>("provider timeout".to_string());
 
         let report = worker
-            .run_once(&store, now + Duration::milliseconds(10), |_store, _job| {
-                Err("provider timeout".to_string())
-            })
+            .run_once(&store, now + Duration::milliseconds(10), provider_timeout)
             .expect("run");
-
         assert_eq!(report.jobs_failed, 1);
+        assert_eq!(report.jobs_requeued, 1);
         assert_eq!(store.pending_reflector_jobs().expect("pending"), 1);
+
+        let early = worker
+            .run_once(&store, now + Duration::milliseconds(50), provider_timeout)
+            .expect("run during backoff");
+        assert_eq!(early.jobs_claimed, 0);
+
+        let report = worker
+            .run_once(&store, now + Duration::milliseconds(110), provider_timeout)
+            .expect("run after backoff");
+        assert_eq!(report.jobs_claimed, 1);
+        assert_eq!(report.jobs_dead_lettered, 1);
+        let dead = store.dead_letter_reflector_jobs(10).expect("dead letters");
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].job_id, job_id);
+        assert_eq!(dead[0].attempts, 2);
     }
 }
@@ -101,6 +101,23 @@ pub struct MindStatusRollup {
     pub error: usize,
 }
 
+impl MindStatusRollup {
+    /// Share of finished runs (ok, fallback, error) that fell back, in whole
+    /// percent; `None` until a run finishes.
+    pub fn fallback_rate_percent(&self) -> Option<usize> {
+        let finished = self.success + self.fallback + self.error;
+        (finished > 0).then(|| self.fallback * 100 / finished)
+    }
+
+    /// `fb:<count>`, plus the fallback rate once a run has finished.
+    pub fn fallback_label(&self) -> String {
+        match self.fallback_rate_percent() {
+            Some(rate) => format!("fb:{}({rate}%)", self.fallback),
+            None => format!("fb:{}", self.fallback),
+        }
+    }
+}
+
 // --- Lane classification ---
 
 pub fn mind_event_is_t3(event: &MindObserverFeedEvent) -> bool {
@@ -196,6 +213,8 @@ pub fn mind_status_rollup(rows: &[MindObserverRow]) -> MindStatusRollup {
             MindObserverFeedStatus::Success => rollup.success += 1,
             MindObserverFeedStatus::Fallback => rollup.fallback += 1,
             MindObserverFeedStatus::Error => rollup.error += 1,
+            // A breaker transition is not a run.
+            MindObserverFeedStatus::Breaker => {}
         }
     }
     rollup
@@ -222,6 +241,7 @@ pub fn mind_status_label(status: MindObserverFeedStatus) -> &'static str {
         MindObserverFeedStatus::Success => "success",
         MindObserverFeedStatus::Fallback => "fallback",
         MindObserverFeedStatus::Error => "error",
+        MindObserverFeedStatus::Breaker => "breaker",
     }
 }
 
@@ -232,6 +252,7 @@ pub fn mind_status_color(status: MindObserverFeedStatus, theme: MindTheme) -> Co
         MindObserverFeedStatus::Success => theme.ok,
         MindObserverFeedStatus::Fallback => theme.warn,
         MindObserverFeedStatus::Error => theme.critical,
+        MindObserverFeedStatus::Breaker => theme.warn,
     }
 }
 
@@ -404,7 +425,7 @@ pub fn render_mind_header_lines(
         ),
         Span::raw(" "),
         Span::styled(
-            format!("fb:{}", status_rollup.fallback),
+            status_rollup.fallback_label(),
             Style::default().fg(theme.warn),
         ),
         Span::raw(" "),
@@ -727,6 +748,7 @@ mod tests {
             latency_ms: None,
             reason: None,
             failure_kind: None,
+            error_code: None,
             enqueued_at: None,
             started_at: None,
             completed_at: None,
@@ -802,6 +824,15 @@ mod tests {
         assert_eq!(rollup.queued, 1);
         assert_eq!(rollup.success, 1);
         assert_eq!(rollup.error, 1);
+        assert_eq!(rollup.fallback_label(), "fb:0(0%)");
+        let mostly_semantic = MindStatusRollup {
+            success: 3,
+            fallback: 1,
+            ..MindStatusRollup::default()
+        };
+        assert_eq!(mostly_semantic.fallback_rate_percent(), Some(25));
+        assert_eq!(mostly_semantic.fallback_label(), "fb:1(25%)");
+        assert_eq!(MindStatusRollup::default().fallback_label(), "fb:0");
 
         let _lines = render_mind_header_lines(
             "t1",
@@ -0,0 +1,223 @@
+use crate::{
+    DeterministicDistiller, DistillationConfig, DistillationError, DistillationReport,
+    ReflectionScope,
+};
+use aoc_core::mind_contracts::{compact_raw_event_to_t0, MindContractError, T0CompactionPolicy};
+use aoc_core::{ErrorCode, ErrorCoded};
+use aoc_storage::{MindStore, StorageError};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+use thiserror::Error;
+
+/// Policy overrides applied to a shadow replay; unset fields keep the defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplayPolicy {
+    pub compaction: Option<T0CompactionPolicy>,
+    pub distillation: ReplayDistillationOverrides,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplayDistillationOverrides {
+    pub t1_target_tokens: Option<u32>,
+    pub t1_hard_cap_tokens: Option<u32>,
+    pub t2_trigger_tokens: Option<u32>,
+    pub t1_output_max_chars: Option<usize>,
+    pub t2_output_max_chars: Option<usize>,
+    pub t2_scope: Option<ReflectionScope>,
+}
+
+impl ReplayPolicy {
+    pub fn load(path: &Path) -> Result<Self, ReplayError> {
+        let raw = fs::read_to_string(path)?;
+        serde_json::from_str(&raw).map_err(|err| {
+            ReplayError::Policy(format!("invalid replay policy {}: {err}", path.display()))
+        })
+    }
+
+    pub fn distillation_config(&self) -> DistillationConfig {
+        let defaults = DistillationConfig::default();
+        let overrides = &self.distillation;
+        DistillationConfig {
+            t1_target_tokens: overrides
+                .t1_target_tokens
+                .unwrap_or(defaults.t1_target_tokens),
+            t1_hard_cap_tokens: overrides
+                .t1_hard_cap_tokens
+                .unwrap_or(defaults.t1_hard_cap_tokens),
+            t2_trigger_tokens: overrides
+                .t2_trigger_tokens
+                .unwrap_or(defaults.t2_trigger_tokens),
+            t1_output_max_chars: overrides
+                .t1_output_max_chars
+                .unwrap_or(defaults.t1_output_max_chars),
+            t2_output_max_chars: overrides
+                .t2_output_max_chars
+                .unwrap_or(defaults.t2_output_max_chars),
+            enable_attribution: defaults.enable_attribution,
+            t2_scope: overrides.t2_scope.unwrap_or(defaults.t2_scope),
+        }
+    }
+}
+
+/// Live-versus-shadow comparison for one tier, keyed by stable identity.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayTierDiff {
+    pub live: usize,
+    pub shadow: usize,
+    pub unchanged: usize,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+}
+
+impl ReplayTierDiff {
+    pub fn is_identical(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayReport {
+    pub conversation_id: String,
+    pub raw_events: usize,
+    pub policy_version: String,
+    pub t0: ReplayTierDiff,
+    pub t1: ReplayTierDiff,
+    pub t2: ReplayTierDiff,
+    pub shadow_distillation: DistillationReport,
+    /// Shadow T1/T2 text keyed by artifact id, for rendering added artifacts.
+    pub shadow_texts: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
+    #[error("contract error: {0}")]
+    Contract(#[from] MindContractError),
+    #[error("distillation error: {0}")]
+    Distillation(#[from] DistillationError),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("policy error: {0}")]
+    Policy(String),
+    #[error("conversation has no raw events: {0}")]
+    EmptyConversation(String),
+}
+
+impl ErrorCoded for ReplayError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Contract(err) => err.error_code(),
+            Self::Distillation(err) => err.error_code(),
+            Self::Io(_) => ErrorCode::Io,
+            Self::Policy(_) => ErrorCode::Config,
+            Self::EmptyConversation(_) => ErrorCode::NotFound,
+        }
+    }
+}
+
+/// Re-runs T0 compaction and deterministic distillation for one conversation
+/// against an in-memory copy, leaving `store` untouched.
+///
+/// T0 events are compared by the raw events they were compacted from, so a
+/// policy version bump that only re-hashes ids is not reported as churn.
+pub fn replay_conversation(
+    store: &MindStore,
+    conversation_id: &str,
+    policy: &ReplayPolicy,
+) -> Result<ReplayReport, ReplayError> {
+    let raw_events = store.raw_events_for_conversation(conversation_id)?;
+    if raw_events.is_empty() {
+        return Err(ReplayError::EmptyConversation(conversation_id.to_string()));
+    }
+    let compaction = policy.compaction.clone().unwrap_or_default();
+
+    let shadow = MindStore::open_in_memory()?;
+    for raw in &raw_events {
+        shadow.insert_raw_event(raw)?;
+        if let Some(compact) = compact_raw_event_to_t0(raw, &compaction)? {
+            shadow.upsert_t0_compact_event(&compact)?;
+        }
+    }
+    for state in store.context_states(conversation_id)? {
+        shadow.append_context_state(&state)?;
+    }
+    let shadow_distillation = DeterministicDistiller::new(policy.distillation_config())
+        .distill_conversation(&shadow, conversation_id)?;
+
+    let t0_key = |event: &aoc_storage::StoredCompactEvent| {
+        let text = event.text.clone().unwrap_or_else(|| {
+            event
+                .tool_meta
+                .as_ref()
+                .and_then(|meta| serde_json::to_string(meta).ok())
+                .unwrap_or_default()
+        });
+        (event.source_event_ids.join(","), text)
+    };
+    let live_t0 = store
+        .t0_events_for_conversation(conversation_id)?
+        .iter()
+        .map(t0_key)
+        .collect::<BTreeMap<_, _>>();
+    let shadow_t0 = shadow
+        .t0_events_for_conversation(conversation_id)?
+        .iter()
+        .map(t0_key)
+        .collect::<BTreeMap<_, _>>();
+
+    let split_artifacts = |artifacts: Vec<aoc_storage::StoredArtifact>| {
+        let mut t1 = BTreeMap::new();
+        let mut t2 = BTreeMap::new();
+        for artifact in artifacts {
+            let tier = if artifact.kind == "t2" {
+                &mut t2
+            } else {
+                &mut t1
+            };
+            tier.insert(artifact.artifact_id, artifact.text);
+        }
+        (t1, t2)
+    };
+    let (live_t1, live_t2) = split_artifacts(store.artifacts_for_conversation(conversation_id)?);
+    let (shadow_t1, shadow_t2) =
+        split_artifacts(shadow.artifacts_for_conversation(conversation_id)?);
+
+    let mut shadow_texts = shadow_t1.clone();
+    shadow_texts.extend(shadow_t2.clone());
+    Ok(ReplayReport {
+        conversation_id: conversation_id.to_string(),
+        raw_events: raw_events.len(),
+        policy_version: compaction.policy_version,
+        t0: diff_tier(&live_t0, &shadow_t0),
+        t1: diff_tier(&live_t1, &shadow_t1),
+        t2: diff_tier(&live_t2, &shadow_t2),
+        shadow_distillation,
+        shadow_texts,
+    })
+}
+
+fn diff_tier(live: &BTreeMap<String, String>, shadow: &BTreeMap<String, String>) -> ReplayTierDiff {
+    let mut diff = ReplayTierDiff {
+        live: live.len(),
+        shadow: shadow.len(),
+        ..ReplayTierDiff::default()
+    };
+    for (key, live_value) in live {
+        match shadow.get(key) {
+            Some(shadow_value) if shadow_value == live_value => diff.unchanged += 1,
+            Some(_) => diff.changed.push(key.clone()),
+            None => diff.removed.push(key.clone()),
+        }
+    }
+    diff.added = shadow
+        .keys()
+        .filter(|key| !live.contains_key(*key))
+        .cloned()
+        .collect();
+    diff
+}
@@ -1,9 +1,13 @@
-use crate::SessionExportManifest;
+use crate::{project_scope_key, SessionExportManifest};
 use aoc_core::insight_contracts::{
     InsightRetrievalCitation, InsightRetrievalDrilldownRef, InsightRetrievalHit,
     InsightRetrievalMode, InsightRetrievalRequest, InsightRetrievalResult, InsightRetrievalScope,
+    RetrievalBudgetUsage, RetrievalMetrics, RetrievalStageTimings,
 };
+use aoc_storage::{MindStore, StorageError};
+use chrono::Utc;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 const INSIGHT_RETRIEVAL_MAX_RESULTS_DEFAULT: usize = 4;
 const INSIGHT_RETRIEVAL_MAX_RESULTS_CAP: usize = 8;
@@ -12,6 +16,8 @@ const INSIGHT_RETRIEVAL_BRIEF_LINE_BUDGET: usize = 2;
 const INSIGHT_RETRIEVAL_REFS_LINE_BUDGET: usize = 0;
 const INSIGHT_RETRIEVAL_SNIPS_LINE_BUDGET: usize = 5;
 
+pub const INSIGHT_RETRIEVAL_PIPELINE: &str = "insight_retrieval";
+
 #[derive(Debug, Clone)]
 struct InsightRetrievalSource {
     source_id: String,
@@ -28,6 +34,7 @@ pub fn compile_insight_retrieval(
     project_root: &str,
     request: InsightRetrievalRequest,
 ) -> InsightRetrievalResult {
+    let started_at = Instant::now();
     let active_tag = request
         .active_tag
         .as_deref()
@@ -63,6 +70,10 @@ pub fn compile_insight_retrieval(
         .clamp(1, INSIGHT_RETRIEVAL_MAX_RESULTS_CAP);
     let sources =
         collect_insight_retrieval_sources(project_root, resolved_scope, active_tag.as_deref());
+    let candidates_considered = sources.len();
+    let candidate_fetch_us = elapsed_us(started_at);
+
+    let rerank_started_at = Instant::now();
     let mut hits = sources
         .into_iter()
         .filter_map(|source| {
@@ -70,10 +81,13 @@ pub fn compile_insight_retrieval(
         })
         .collect::<Vec<_>>();
     hits.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.label.cmp(&b.label)));
-    if hits.len() > max_results {
+    let hits_truncated = hits.len() > max_results;
+    if hits_truncated {
         hits.truncate(max_results);
     }
+    let rerank_us = elapsed_us(rerank_started_at);
 
+    let assembly_started_at = Instant::now();
     let citations = hits
         .iter()
         .flat_map(|hit| hit.citations.iter().cloned())
@@ -118,6 +132,25 @@ pub fn compile_insight_retrieval(
         }
     };
     let line_budget_per_hit = insight_retrieval_line_budget(&request.mode, max_results);
+    let assembly_us = elapsed_us(assembly_started_at);
+
+    let metrics = RetrievalMetrics {
+        pipeline: INSIGHT_RETRIEVAL_PIPELINE.to_string(),
+        timings: RetrievalStageTimings {
+            candidate_fetch_us,
+            rerank_us,
+            assembly_us,
+            total_us: elapsed_us(started_at),
+        },
+        budget: RetrievalBudgetUsage {
+            candidates_considered,
+            hit_budget: max_results,
+            hits_returned: hits.len(),
+            line_budget: line_budget_per_hit.saturating_mul(hits.len()),
+            lines_used: hits.iter().map(|hit| hit.lines.len()).sum(),
+            truncated: hits_truncated || hits.iter().any(|hit| hit.lines_truncated),
+        },
+    };
 
     InsightRetrievalResult {
         query: request.query,
@@ -131,9 +164,27 @@ pub fn compile_insight_retrieval(
         fallback_used,
         hit_budget: max_results,
         line_budget_per_hit,
+        metrics,
     }
 }
 
+/// Persists a retrieval run's stage timings so latency can be aggregated per project.
+pub fn record_retrieval_metrics(
+    store: &MindStore,
+    project_root: &str,
+    metrics: &RetrievalMetrics,
+) -> Result<(), StorageError> {
+    store.record_retrieval_metrics(
+        &project_scope_key(Path::new(project_root)),
+        metrics,
+        Utc::now(),
+    )
+}
+
+pub(crate) fn elapsed_us(started_at: Instant) -> u64 {
+    u64::try_from(started_at.elapsed().as_micros()).unwrap_or(u64::MAX)
+}
+
 fn export_matches_active_tag(export_tag: Option<&str>, requested_tag: Option<&str>) -> bool {
     match (
         export_tag.map(str::trim).filter(|value| !value.is_empty()),
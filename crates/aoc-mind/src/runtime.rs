@@ -4,9 +4,9 @@ use crate::{
     process_t3_backlog_job, project_scope_key, t3_scope_id_for_project_root,
     write_mind_service_health_snapshot, DetachedReflectorWorker, DetachedT3Worker,
     DistillationConfig, FinalizeDrainDecision, IdleFinalizeDecision, MindServiceHealthSnapshot,
-    MindServiceLeaseGuard, ObserverDrainState, PiObserverAdapter, ReflectorRuntimeConfig,
-    ReflectorTickReport, SemanticObserverConfig, SessionObserverSidecar, T1ThresholdDecision,
-    T3RuntimeConfig, T3TickReport,
+    MindServiceLeaseGuard, ObserverDrainState, ObserverEnqueueOptions, PiObserverAdapter,
+    ReflectorRuntimeConfig, ReflectorTickReport, SemanticObserverConfig, SessionObserverSidecar,
+    T1ThresholdDecision, T3RuntimeConfig, T3TickReport,
 };
 use aoc_core::{
     insight_contracts::{
@@ -17,9 +17,9 @@ use aoc_core::{
     mind_observer_feed::{
         MindObserverFeedEvent, MindObserverFeedStatus, MindObserverFeedTriggerKind,
     },
+    ErrorCode,
 };
-use aoc_storage::MindStore;
-use chrono::Utc;
+use aoc_storage::{MindStore, SharedClock};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
@@ -33,6 +33,8 @@ pub struct MindRuntimeConfig {
     pub t3_lock_path: PathBuf,
     pub debounce_run_ms: i64,
     pub t3_max_attempts: u16,
+    /// Time source for debounce, drain deadlines, and the service lease.
+    pub clock: SharedClock,
 }
 
 pub struct MindFinalizeDrainOutcome {
@@ -72,6 +74,7 @@ pub struct MindRuntimeCore {
     t3_worker: DetachedT3Worker,
     debounce_run_ms: i64,
     service_lease: MindServiceLeaseGuard,
+    clock: SharedClock,
 }
 
 const DETACHED_JOB_OUTPUT_MAX_CHARS: usize = 320;
@@ -95,13 +98,15 @@ impl MindRuntimeCore {
         distill.t1_target_tokens = distill.t1_target_tokens.min(semantic_input_limit);
         distill.t1_hard_cap_tokens = distill.t1_hard_cap_tokens.min(semantic_input_limit);
         let sidecar =
-            SessionObserverSidecar::new(distill.clone(), semantic, PiObserverAdapter::default());
+            SessionObserverSidecar::new(distill.clone(), semantic, PiObserverAdapter::default())
+                .with_clock(cfg.clock.clone());
         let service_lease = MindServiceLeaseGuard::acquire(
             Path::new(&cfg.project_root),
             &cfg.agent_key,
             &cfg.session_id,
             &cfg.pane_id,
             30_000,
+            cfg.clock.clone(),
         )
         .map_err(|err| format!("mind service lease acquire failed: {err}"))?;
         let reflector_worker = DetachedReflectorWorker::new(ReflectorRuntimeConfig {
@@ -112,6 +117,9 @@ impl MindRuntimeCore {
             lease_ttl_ms: 30_000,
             max_jobs_per_tick: 2,
             requeue_on_error: true,
+            max_attempts: 3,
+            retry_backoff_ms: 5_000,
+            max_retry_backoff_ms: 300_000,
         });
         let t3_worker = DetachedT3Worker::new(T3RuntimeConfig {
             scope_id: t3_scope_id_for_project_root(&cfg.project_root),
@@ -139,6 +147,7 @@ impl MindRuntimeCore {
             t3_worker,
             debounce_run_ms: cfg.debounce_run_ms,
             service_lease,
+            clock: cfg.clock,
         })
     }
 
@@ -146,6 +155,10 @@ impl MindRuntimeCore {
         &self.store
     }
 
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
     pub fn sidecar_mut(&mut self) -> &mut SessionObserverSidecar<PiObserverAdapter> {
         &mut self.sidecar
     }
@@ -221,7 +234,7 @@ impl MindRuntimeCore {
         if snapshot.lifecycle.is_empty() {
             snapshot.lifecycle = "running".to_string();
         }
-        snapshot.last_heartbeat_ms = Some(Utc::now().timestamp_millis());
+        snapshot.last_heartbeat_ms = Some(self.clock.now().timestamp_millis());
         snapshot.lease_expires_at_ms =
             Some(self.service_lease.lease().expires_at.timestamp_millis());
         write_mind_service_health_snapshot(&self.project_root, snapshot)
@@ -515,13 +528,21 @@ impl MindRuntimeCore {
         };
         let summary = if fallback_used {
             format!(
-                "mind t2 inline fallback processed claimed={} completed={} failed={}",
-                report.jobs_claimed, report.jobs_completed, report.jobs_failed
+                "mind t2 inline fallback processed claimed={} completed={} failed={} requeued={} dead_lettered={}",
+                report.jobs_claimed,
+                report.jobs_completed,
+                report.jobs_failed,
+                report.jobs_requeued,
+                report.jobs_dead_lettered
             )
         } else {
             format!(
-                "mind t2 detached worker processed claimed={} completed={} failed={}",
-                report.jobs_claimed, report.jobs_completed, report.jobs_failed
+                "mind t2 detached worker processed claimed={} completed={} failed={} requeued={} dead_lettered={}",
+                report.jobs_claimed,
+                report.jobs_completed,
+                report.jobs_failed,
+                report.jobs_requeued,
+                report.jobs_dead_lettered
             )
         };
         MindDetachedJobOutcome {
@@ -588,6 +609,7 @@ impl MindRuntimeCore {
                 latency_ms: None,
                 reason: Some("reflector tick failed".to_string()),
                 failure_kind: Some("runtime_error".to_string()),
+                error_code: Some(ErrorCode::Internal),
                 enqueued_at: None,
                 started_at: None,
                 completed_at: Some(now.to_rfc3339()),
@@ -613,6 +635,7 @@ impl MindRuntimeCore {
                 latency_ms: None,
                 reason: Some("t3 backlog tick failed".to_string()),
                 failure_kind: Some("runtime_error".to_string()),
+                error_code: Some(ErrorCode::Internal),
                 enqueued_at: None,
                 started_at: None,
                 completed_at: Some(now.to_rfc3339()),
@@ -733,13 +756,14 @@ impl MindRuntimeCore {
         deadline: chrono::DateTime<chrono::Utc>,
         reflector_pending: i64,
     ) -> MindFinalizeDrainOutcome {
-        let now = Utc::now();
+        let now = self.clock.now();
         let run_at = now + chrono::Duration::milliseconds(self.debounce_run_ms + 1);
         let observer = self.drain_observer_state(session_id, run_at, now);
         let observer_idle = observer.is_idle();
         let observer_events = observer.events;
 
-        match evaluate_finalize_drain(observer_idle, reflector_pending, Utc::now(), deadline) {
+        match evaluate_finalize_drain(observer_idle, reflector_pending, self.clock.now(), deadline)
+        {
             FinalizeDrainDecision::Settled => MindFinalizeDrainOutcome {
                 observer_events,
                 settled: true,
@@ -793,6 +817,7 @@ impl MindRuntimeCore {
                     report.jobs_completed
                 )),
                 failure_kind: None,
+                error_code: None,
                 enqueued_at: None,
                 started_at: None,
                 completed_at: Some(now.to_rfc3339()),
@@ -809,6 +834,7 @@ impl MindRuntimeCore {
                 latency_ms: None,
                 reason: Some(format!("t2 reflector failed {} job(s)", report.jobs_failed)),
                 failure_kind: Some("runtime_error".to_string()),
+                error_code: Some(ErrorCode::Internal),
                 enqueued_at: None,
                 started_at: None,
                 completed_at: Some(now.to_rfc3339()),
@@ -860,6 +886,7 @@ impl MindRuntimeCore {
                     report.jobs_completed
                 )),
                 failure_kind: None,
+                error_code: None,
                 enqueued_at: None,
                 started_at: None,
                 completed_at: Some(now.to_rfc3339()),
@@ -879,6 +906,7 @@ impl MindRuntimeCore {
                     report.jobs_failed, report.jobs_requeued, report.jobs_dead_lettered
                 )),
                 failure_kind: Some("runtime_error".to_string()),
+                error_code: Some(ErrorCode::Internal),
                 enqueued_at: None,
                 started_at: None,
                 completed_at: Some(now.to_rfc3339()),
@@ -939,10 +967,12 @@ impl MindRuntimeCore {
             &self.store,
             &self.session_id,
             conversation_id,
-            trigger,
-            reason,
-            Utc::now(),
-            self.debounce_run_ms,
+            ObserverEnqueueOptions {
+                trigger,
+                reason,
+                now: self.clock.now(),
+                debounce_run_ms: self.debounce_run_ms,
+            },
         )
     }
 
@@ -973,6 +1003,7 @@ impl MindRuntimeCore {
                 latency_ms: None,
                 reason: Some(format!("mind threshold check failed: {err}")),
                 failure_kind: None,
+                error_code: None,
                 enqueued_at: None,
                 started_at: None,
                 completed_at: None,
@@ -1,5 +1,7 @@
+use aoc_core::{ErrorCode, ErrorCoded};
+#[cfg(feature = "adapters")]
 use aoc_pi_adapter::{IngestionOptions, IngestionReport, PiAdapterError, PiSessionIngestor};
-use aoc_storage::{LegacyImportReport, MindStore, StorageError};
+use aoc_storage::{LegacyImportReport, MindStore, SharedClock, StorageError};
 use chrono::{DateTime, Duration, Utc};
 use fs2::FileExt;
 use serde::{Deserialize, Serialize};
@@ -52,6 +54,7 @@ impl MindProjectPaths {
     }
 }
 
+#[cfg(feature = "adapters")]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StandalonePiSyncReport {
     pub session_file: PathBuf,
@@ -234,6 +237,7 @@ pub struct MindServiceLeaseGuard {
     file: File,
     path: PathBuf,
     lease: MindServiceLease,
+    clock: SharedClock,
 }
 
 #[derive(Debug, Error)]
@@ -242,10 +246,22 @@ pub enum StandaloneMindError {
     Io(#[from] std::io::Error),
     #[error("storage error: {0}")]
     Storage(#[from] StorageError),
+    #[cfg(feature = "adapters")]
     #[error("pi adapter error: {0}")]
     PiAdapter(#[from] PiAdapterError),
 }
 
+impl ErrorCoded for StandaloneMindError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Io(_) => ErrorCode::Io,
+            Self::Storage(err) => err.error_code(),
+            #[cfg(feature = "adapters")]
+            Self::PiAdapter(err) => err.error_code(),
+        }
+    }
+}
+
 pub fn mind_runtime_root(project_root: &Path) -> PathBuf {
     resolve_aoc_state_home()
         .join("aoc")
@@ -395,6 +411,7 @@ impl MindServiceLeaseGuard {
         session_id: &str,
         pane_id: &str,
         ttl_ms: u64,
+        clock: SharedClock,
     ) -> Result<Self, StandaloneMindError> {
         let paths = MindProjectPaths::for_project_root(project_root);
         if let Some(parent) = paths.service_lock_path.parent() {
@@ -416,7 +433,7 @@ impl MindServiceLeaseGuard {
             )
         })?;
 
-        let now = Utc::now();
+        let now = clock.now();
         let lease = MindServiceLease {
             owner_id: owner_id.to_string(),
             owner_pid: Some(std::process::id() as i64),
@@ -429,6 +446,7 @@ impl MindServiceLeaseGuard {
             file,
             path: paths.service_lock_path,
             lease,
+            clock,
         };
         guard.write_metadata()?;
         Ok(guard)
@@ -440,7 +458,7 @@ impl MindServiceLeaseGuard {
 
     pub fn heartbeat(&mut self, ttl_ms: u64) -> Result<(), StandaloneMindError> {
         self.lease.expires_at =
-            Utc::now() + Duration::milliseconds(ttl_ms.min(i64::MAX as u64) as i64);
+            self.clock.now() + Duration::milliseconds(ttl_ms.min(i64::MAX as u64) as i64);
         self.write_metadata()
     }
 
@@ -502,6 +520,7 @@ pub fn read_mind_service_health_snapshot(
     Ok(Some(snapshot))
 }
 
+#[cfg(feature = "adapters")]
 pub fn sync_session_file_into_project_store(
     project_root: &Path,
     agent_id: &str,
@@ -516,6 +535,7 @@ pub fn sync_session_file_into_project_store(
     })
 }
 
+#[cfg(feature = "adapters")]
 pub fn sync_latest_pi_session_into_project_store(
     project_root: &Path,
     agent_id: &str,
@@ -626,6 +646,7 @@ mod tests {
         let _ = fs::remove_dir_all(&dir);
     }
 
+    #[cfg(feature = "adapters")]
     #[test]
     fn sync_session_file_into_project_store_ingests_pi_jsonl() {
         let _guard = env_guard();
@@ -813,19 +834,30 @@ mod tests {
         let previous_state = env::var("XDG_STATE_HOME").ok();
         env::set_var("XDG_STATE_HOME", &state_home);
 
+        let started = chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 2, 23, 12, 0, 0)
+            .single()
+            .expect("valid ts");
+        let clock = std::sync::Arc::new(aoc_storage::MockClock::new(started));
         let mut lease = MindServiceLeaseGuard::acquire(
             &project_root,
             "agent-test",
             "session-test",
             "pane-test",
             30_000,
+            clock.clone(),
         )
         .expect("acquire service lease");
+        let heartbeat_at = clock.advance(Duration::seconds(20));
         lease.heartbeat(30_000).expect("heartbeat lease");
 
         let persisted_lease = read_mind_service_lease(&project_root)
             .expect("read lease")
             .expect("lease present");
+        assert_eq!(persisted_lease.acquired_at, started);
+        assert_eq!(
+            persisted_lease.expires_at,
+            heartbeat_at + Duration::milliseconds(30_000)
+        );
         assert_eq!(persisted_lease.owner_id, "agent-test");
         assert_eq!(persisted_lease.session_id, "session-test");
         assert_eq!(persisted_lease.pane_id, "pane-test");
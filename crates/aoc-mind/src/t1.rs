@@ -1,6 +1,7 @@
 use crate::ingest::mind_progress_for_conversation;
 use aoc_core::mind_observer_feed::MindObserverFeedProgress;
-use aoc_storage::{MindStore, StorageError};
+use aoc_core::{ErrorCode, ErrorCoded};
+use aoc_storage::{MindStoreBackend, StorageError};
 use thiserror::Error;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -24,8 +25,16 @@ pub enum T1ThresholdError {
     Storage(#[from] StorageError),
 }
 
-pub fn evaluate_t1_token_threshold(
-    store: &MindStore,
+impl ErrorCoded for T1ThresholdError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+        }
+    }
+}
+
+pub fn evaluate_t1_token_threshold<S: MindStoreBackend + ?Sized>(
+    store: &S,
     conversation_id: &str,
     t1_target_tokens: u32,
     t1_hard_cap_tokens: u32,
@@ -64,6 +73,7 @@ mod tests {
         compact_raw_event_to_t0, ConversationRole, MessageEvent, RawEvent, RawEventBody,
         T0CompactionPolicy,
     };
+    use aoc_storage::MindStore;
     use chrono::Utc;
     use std::collections::BTreeMap;
 
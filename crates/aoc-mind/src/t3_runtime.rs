@@ -1,3 +1,4 @@
+use aoc_core::{ErrorCode, ErrorCoded};
 use aoc_storage::{MindStore, T3BacklogJob};
 use chrono::{DateTime, Duration, Utc};
 use fs2::FileExt;
@@ -59,6 +60,15 @@ pub enum T3RuntimeError {
     Io(#[from] std::io::Error),
 }
 
+impl ErrorCoded for T3RuntimeError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Io(_) => ErrorCode::Io,
+        }
+    }
+}
+
 struct AdvisoryT3FileLock {
     file: File,
 }
@@ -169,6 +179,7 @@ impl DetachedT3Worker {
             };
 
             report.jobs_claimed += 1;
+            let _span = tracing::info_span!("t3_job", stage = "t3", job_id = %job.job_id).entered();
 
             match handler(store, &job) {
                 Ok(()) => {
@@ -178,6 +189,7 @@ impl DetachedT3Worker {
                 Err(message) => {
                     let will_requeue = self.config.requeue_on_error
                         && job.attempts < self.config.max_attempts.max(1);
+                    tracing::warn!(error = %message, will_requeue, "t3 job failed");
                     store.fail_t3_backlog_job(
                         &job.job_id,
                         &self.config.owner_id,
@@ -0,0 +1,270 @@
+//! Authoritative tag and task state read straight from Taskmaster's files.
+//!
+//! [`TaskmasterWatcher`] re-reads `.taskmaster/tasks/tasks.json` and
+//! `.taskmaster/state.json` when either changes and writes the current tag
+//! and its in-progress tasks into `conversation_context_state` under
+//! [`TASKMASTER_SIGNAL_SOURCE`]. Attribution and routing prefer those states
+//! over ones parsed from `tm` command output, which only fill in for
+//! conversations the watcher has not stamped yet.
+
+use aoc_core::{ErrorCode, ErrorCoded, ProjectData, TagContext, Task, TaskStatus};
+use aoc_storage::{ConversationContextState, MindStore, StorageError, TASKMASTER_SIGNAL_SOURCE};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use thiserror::Error;
+
+const DEFAULT_TAG: &str = "master";
+/// Lifecycle on states that only restate the current tag and tasks.
+const SYNC_LIFECYCLE: &str = "taskmaster_sync";
+/// Lifecycle on states that also report tasks completed since the last read;
+/// attribution treats it as a completion signal.
+const DONE_LIFECYCLE: &str = "taskmaster_done";
+
+#[derive(Debug, Error)]
+pub enum TaskmasterSyncError {
+    #[error("read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("parse {path}: {message}")]
+    Parse { path: PathBuf, message: String },
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+}
+
+impl ErrorCoded for TaskmasterSyncError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Io { .. } => ErrorCode::Io,
+            Self::Parse { .. } => ErrorCode::Serialization,
+            Self::Storage(err) => err.error_code(),
+        }
+    }
+}
+
+/// The current tag and its task state as Taskmaster last wrote it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskmasterSnapshot {
+    pub active_tag: String,
+    /// In-progress task and subtask ids (`12`, `12.3`) of the active tag.
+    pub in_progress: Vec<String>,
+    pub done: BTreeSet<String>,
+    /// Latest modification time of the two files.
+    pub changed_at: DateTime<Utc>,
+}
+
+impl TaskmasterSnapshot {
+    fn state(&self) -> (&str, &[String], &BTreeSet<String>) {
+        (&self.active_tag, &self.in_progress, &self.done)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskmasterStateFile {
+    #[serde(default)]
+    current_tag: Option<String>,
+}
+
+/// Reads the snapshot under `root`; `None` when the project has no tasks.json.
+pub fn read_taskmaster_snapshot(
+    root: &Path,
+) -> Result<Option<TaskmasterSnapshot>, TaskmasterSyncError> {
+    let tasks_path = root.join(".taskmaster/tasks/tasks.json");
+    let state_path = root.join(".taskmaster/state.json");
+    let Some(tasks_modified) = modified(&tasks_path) else {
+        return Ok(None);
+    };
+    let project = parse_project(&tasks_path, &read(&tasks_path)?)?;
+    let current_tag = match modified(&state_path) {
+        Some(_) => serde_json::from_str::<TaskmasterStateFile>(&read(&state_path)?)
+            .map_err(|err| TaskmasterSyncError::Parse {
+                path: state_path.clone(),
+                message: err.to_string(),
+            })?
+            .current_tag
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty()),
+        None => None,
+    };
+    let active_tag = current_tag.unwrap_or_else(|| DEFAULT_TAG.to_string());
+    let changed_at = modified(&state_path)
+        .map_or(tasks_modified, |state_modified| {
+            state_modified.max(tasks_modified)
+        })
+        .into();
+
+    let mut in_progress = Vec::new();
+    let mut done = BTreeSet::new();
+    for task in project
+        .tags
+        .get(&active_tag)
+        .map(|tag| tag.tasks.as_slice())
+        .unwrap_or_default()
+    {
+        collect_task_state(task, &mut in_progress, &mut done);
+    }
+    Ok(Some(TaskmasterSnapshot {
+        active_tag,
+        in_progress,
+        done,
+        changed_at,
+    }))
+}
+
+/// Polls Taskmaster's files and stamps conversations with the current state.
+pub struct TaskmasterWatcher {
+    root: PathBuf,
+    seen_mtimes: Option<(Option<SystemTime>, Option<SystemTime>)>,
+    current: Option<TaskmasterSnapshot>,
+    /// Tasks completed by the latest change, reported once per conversation.
+    newly_done: Vec<String>,
+    /// Conversations already holding the current snapshot.
+    stamped: BTreeSet<String>,
+}
+
+impl TaskmasterWatcher {
+    /// `root` is the project directory that holds `.taskmaster/`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            seen_mtimes: None,
+            current: None,
+            newly_done: Vec::new(),
+            stamped: BTreeSet::new(),
+        }
+    }
+
+    pub fn snapshot(&self) -> Option<&TaskmasterSnapshot> {
+        self.current.as_ref()
+    }
+
+    /// Re-reads Taskmaster when its files changed, then writes the current
+    /// state into each of `conversation_ids` that does not hold it yet.
+    /// Returns the number of states written.
+    pub fn poll<'a>(
+        &mut self,
+        store: &MindStore,
+        conversation_ids: impl IntoIterator<Item = &'a str>,
+    ) -> Result<usize, TaskmasterSyncError> {
+        self.refresh()?;
+        let Some(snapshot) = &self.current else {
+            return Ok(0);
+        };
+        let mut written = 0;
+        for conversation_id in conversation_ids {
+            if self.stamped.contains(conversation_id) {
+                continue;
+            }
+            let (lifecycle, signal_task_ids) = if self.newly_done.is_empty() {
+                (SYNC_LIFECYCLE, snapshot.in_progress.clone())
+            } else {
+                (DONE_LIFECYCLE, self.newly_done.clone())
+            };
+            store.append_context_state(&ConversationContextState {
+                conversation_id: conversation_id.to_string(),
+                ts: snapshot.changed_at,
+                active_tag: Some(snapshot.active_tag.clone()),
+                active_tasks: snapshot.in_progress.clone(),
+                lifecycle: Some(lifecycle.to_string()),
+                signal_task_ids,
+                signal_source: TASKMASTER_SIGNAL_SOURCE.to_string(),
+            })?;
+            self.stamped.insert(conversation_id.to_string());
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    fn refresh(&mut self) -> Result<(), TaskmasterSyncError> {
+        let mtimes = (
+            modified(&self.root.join(".taskmaster/tasks/tasks.json")),
+            modified(&self.root.join(".taskmaster/state.json")),
+        );
+        if self.seen_mtimes == Some(mtimes) {
+            return Ok(());
+        }
+        let next = read_taskmaster_snapshot(&self.root)?;
+        self.seen_mtimes = Some(mtimes);
+        // A touched file with the same task state is not a new change.
+        if next.as_ref().map(TaskmasterSnapshot::state)
+            == self.current.as_ref().map(TaskmasterSnapshot::state)
+        {
+            return Ok(());
+        }
+        self.newly_done = match (&self.current, &next) {
+            (Some(previous), Some(next)) if previous.active_tag == next.active_tag => {
+                next.done.difference(&previous.done).cloned().collect()
+            }
+            _ => Vec::new(),
+        };
+        self.current = next;
+        self.stamped.clear();
+        Ok(())
+    }
+}
+
+fn collect_task_state(task: &Task, in_progress: &mut Vec<String>, done: &mut BTreeSet<String>) {
+    match task.status {
+        TaskStatus::InProgress => in_progress.push(task.id.clone()),
+        TaskStatus::Done => {
+            done.insert(task.id.clone());
+        }
+        _ => {}
+    }
+    for subtask in &task.subtasks {
+        let id = format!("{}.{}", task.id, subtask.id);
+        match subtask.status {
+            TaskStatus::InProgress => in_progress.push(id),
+            TaskStatus::Done => {
+                done.insert(id);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Tagged `{"<tag>": {"tasks": [...]}}` files, or the legacy untagged
+/// `{"tasks": [...]}` layout read as the `master` tag.
+fn parse_project(path: &Path, content: &str) -> Result<ProjectData, TaskmasterSyncError> {
+    let parse_error = |message: String| TaskmasterSyncError::Parse {
+        path: path.to_path_buf(),
+        message,
+    };
+    if let Ok(project) = serde_json::from_str::<ProjectData>(content) {
+        return Ok(project);
+    }
+    #[derive(Deserialize)]
+    struct LegacyProject {
+        tasks: Vec<Task>,
+    }
+    let legacy = serde_json::from_str::<LegacyProject>(content)
+        .map_err(|err| parse_error(err.to_string()))?;
+    Ok(ProjectData {
+        tags: HashMap::from([(
+            DEFAULT_TAG.to_string(),
+            TagContext {
+                tasks: legacy.tasks,
+                extra: HashMap::new(),
+            },
+        )]),
+    })
+}
+
+fn read(path: &Path) -> Result<String, TaskmasterSyncError> {
+    fs::read_to_string(path).map_err(|source| TaskmasterSyncError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
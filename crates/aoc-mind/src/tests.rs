@@ -4,17 +4,20 @@ use aoc_core::{
     insight_contracts::{InsightDetachedJob, InsightDetachedJobStatus, InsightDetachedWorkerKind},
     mind_contracts::{
         canonical_lineage_attrs, compact_raw_event_to_t0, ConversationLineageMetadata,
-        ConversationRole, MessageEvent, ObserverAdapter, ObserverInput, ObserverOutput, RawEvent,
-        RawEventBody, SemanticAdapterError, SemanticFailureKind, SemanticGuardrails,
-        SemanticModelProfile, T0CompactionPolicy,
+        ConversationRole, MessageEvent, ObserverActionItem, ObserverAdapter, ObserverInput,
+        ObserverOutput, RawEvent, RawEventBody, SemanticAdapterError, SemanticFailureKind,
+        SemanticGuardrails, SemanticModelProfile, T0CompactionPolicy,
     },
 };
 use aoc_storage::{
-    MindStore, ReflectorJob, ReflectorJobStatus, StoredArtifact, T3BacklogJob, T3BacklogJobStatus,
+    MindPin, MindPinTargetKind, MindStore, PipelineRunStatus, ReflectorJob, ReflectorJobStatus,
+    StoredArtifact, T3BacklogJob, T3BacklogJobStatus,
 };
 use chrono::{DateTime, TimeZone, Utc};
 use std::cell::RefCell;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
@@ -37,6 +40,10 @@ fn temp_project_root(label: &str) -> PathBuf {
 }
 
 fn runtime_for_test(label: &str) -> MindRuntimeCore {
+    runtime_with_clock(label, aoc_storage::system_clock())
+}
+
+fn runtime_with_clock(label: &str, clock: aoc_storage::SharedClock) -> MindRuntimeCore {
     let root = temp_project_root(label);
     let runtime = MindRuntimeCore::new(MindRuntimeConfig {
         project_root: root.to_string_lossy().to_string(),
@@ -48,6 +55,7 @@ fn runtime_for_test(label: &str) -> MindRuntimeCore {
         t3_lock_path: root.join("t3.lock"),
         debounce_run_ms: 300,
         t3_max_attempts: 3,
+        clock,
     })
     .expect("runtime");
     runtime
@@ -98,7 +106,7 @@ impl ObserverAdapter for StaticObserverAdapter {
 }
 
 struct SequenceObserverAdapter {
-    scripted_results: RefCell<Vec<Result<ObserverOutput, SemanticAdapterError>>>,
+    scripted_results: Mutex<Vec<Result<ObserverOutput, SemanticAdapterError>>>,
     delay_ms: u64,
 }
 
@@ -113,7 +121,7 @@ impl ObserverAdapter for SequenceObserverAdapter {
             thread::sleep(Duration::from_millis(self.delay_ms));
         }
 
-        let mut scripted = self.scripted_results.borrow_mut();
+        let mut scripted = self.scripted_results.lock().expect("scripted results");
         if scripted.is_empty() {
             return Err(SemanticAdapterError::new(
                 SemanticFailureKind::ProviderError,
@@ -125,6 +133,92 @@ impl ObserverAdapter for SequenceObserverAdapter {
     }
 }
 
+struct RecordingObserverAdapter {
+    seen_lines: Arc<Mutex<Vec<String>>>,
+}
+
+impl ObserverAdapter for RecordingObserverAdapter {
+    fn observe_t1(
+        &self,
+        input: &ObserverInput,
+        _profile: &SemanticModelProfile,
+        _guardrails: &SemanticGuardrails,
+    ) -> Result<ObserverOutput, SemanticAdapterError> {
+        self.seen_lines
+            .lock()
+            .expect("seen lines")
+            .extend(input.compact_payload_lines.iter().cloned());
+        Ok(ObserverOutput {
+            summary: "observed".to_string(),
+            ..ObserverOutput::default()
+        })
+    }
+}
+
+struct DownObserverAdapter {
+    calls: Arc<AtomicUsize>,
+}
+
+impl ObserverAdapter for DownObserverAdapter {
+    fn observe_t1(
+        &self,
+        _input: &ObserverInput,
+        _profile: &SemanticModelProfile,
+        _guardrails: &SemanticGuardrails,
+    ) -> Result<ObserverOutput, SemanticAdapterError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Err(SemanticAdapterError::new(
+            SemanticFailureKind::ProviderError,
+            "connection refused",
+        ))
+    }
+}
+
+/// Answers after moving `clock` forward, standing in for a slow provider.
+struct ClockAdvancingObserverAdapter {
+    clock: Arc<aoc_storage::MockClock>,
+    latency: chrono::Duration,
+}
+
+impl ObserverAdapter for ClockAdvancingObserverAdapter {
+    fn observe_t1(
+        &self,
+        _input: &ObserverInput,
+        _profile: &SemanticModelProfile,
+        _guardrails: &SemanticGuardrails,
+    ) -> Result<ObserverOutput, SemanticAdapterError> {
+        self.clock.advance(self.latency);
+        Ok(ObserverOutput {
+            summary: "answered late".to_string(),
+            ..ObserverOutput::default()
+        })
+    }
+}
+
+#[derive(Default)]
+struct ConcurrentObserverAdapter {
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+}
+
+impl ObserverAdapter for ConcurrentObserverAdapter {
+    fn observe_t1(
+        &self,
+        input: &ObserverInput,
+        _profile: &SemanticModelProfile,
+        _guardrails: &SemanticGuardrails,
+    ) -> Result<ObserverOutput, SemanticAdapterError> {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(30));
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(ObserverOutput {
+            summary: input.compact_payload_lines.join(" "),
+            ..ObserverOutput::default()
+        })
+    }
+}
+
 #[test]
 fn retrieval_compiler_lives_in_aoc_mind() {
     let root = temp_project_root("retrieval-compiler");
@@ -162,6 +256,393 @@ fn retrieval_compiler_lives_in_aoc_mind() {
         .summary_lines
         .iter()
         .any(|line| line.contains("Project canon")));
+    assert_eq!(result.metrics.pipeline, INSIGHT_RETRIEVAL_PIPELINE);
+    assert_eq!(result.metrics.budget.hit_budget, 4);
+    assert_eq!(result.metrics.budget.hits_returned, result.hits.len());
+    assert!(result.metrics.budget.candidates_considered >= result.hits.len());
+    assert!(result.metrics.timings.total_us >= result.metrics.timings.candidate_fetch_us);
+}
+
+#[test]
+fn context_pack_reports_budget_metrics_and_aggregates_them_in_store() {
+    let root = temp_project_root("context-pack-metrics");
+    let root_str = root.to_string_lossy();
+    let store = MindStore::open_in_memory().expect("store");
+
+    let overrides = MindContextPackSourceOverrides {
+        aoc_mem: Some((1..=30).map(|n| format!("decision {n}\n")).collect()),
+        aoc_stm_current: Some("current handoff".to_string()),
+        ..Default::default()
+    };
+    let pack = compile_mind_context_pack(
+        &root_str,
+        Some(&store),
+        MindContextPackRequest {
+            mode: MindContextPackMode::Startup,
+            profile: MindContextPackProfile::Compact,
+            active_tag: None,
+            reason: None,
+            role: None,
+        },
+        Some(&overrides),
+    )
+    .expect("context pack");
+
+    assert_eq!(pack.metrics.pipeline, MIND_CONTEXT_PACK_PIPELINE);
+    assert_eq!(pack.metrics.budget.line_budget, pack.line_budget);
+    assert_eq!(pack.metrics.budget.lines_used, pack.rendered_lines.len());
+    assert_eq!(
+        pack.metrics.budget.candidates_considered,
+        pack.sections.len()
+    );
+    assert_eq!(pack.metrics.budget.truncated, pack.truncated);
+    assert!(pack.metrics.timings.total_us >= pack.metrics.timings.rerank_us);
+
+    let summary = store
+        .retrieval_latency_summary(
+            MIND_CONTEXT_PACK_PIPELINE,
+            None,
+            None,
+            aoc_core::insight_contracts::RETRIEVAL_HANDSHAKE_SLO_MS,
+        )
+        .expect("latency summary");
+    assert_eq!(summary.samples, 1);
+    assert_eq!(summary.max_us, pack.metrics.timings.total_us);
+}
+
+#[test]
+fn pinned_memory_is_budget_reserved_ahead_of_scored_context() {
+    let root = temp_project_root("context-pack-pins");
+    let root_str = root.to_string_lossy();
+    let store = MindStore::open_in_memory().expect("store");
+    let overrides = MindContextPackSourceOverrides {
+        aoc_mem: Some((1..=30).map(|n| format!("decision {n}\n")).collect()),
+        aoc_stm_current: Some("current handoff".to_string()),
+        ..Default::default()
+    };
+    let request = || MindContextPackRequest {
+        mode: MindContextPackMode::Startup,
+        profile: MindContextPackProfile::Compact,
+        active_tag: None,
+        reason: None,
+        role: None,
+    };
+
+    let pin = pin_mind_memory(
+        &store,
+        &root_str,
+        MindPinTarget::Note("  never force-push to main ".to_string()),
+        Some("repo policy".to_string()),
+        ts(9, 0, 0),
+    )
+    .expect("pin note");
+    assert!(pin.pin_id.starts_with("pin:note:"));
+    assert!(matches!(
+        pin_mind_memory(
+            &store,
+            &root_str,
+            MindPinTarget::Artifact("obs:missing".to_string()),
+            None,
+            ts(9, 0, 1),
+        ),
+        Err(err) if err.contains("artifact not found")
+    ));
+
+    let pack = compile_mind_context_pack(&root_str, Some(&store), request(), Some(&overrides))
+        .expect("context pack");
+    assert_eq!(pack.sections[0].source_id, "pinned");
+    assert_eq!(
+        pack.rendered_lines[..2],
+        [
+            "[pinned] Pinned memory".to_string(),
+            "- never force-push to main".to_string()
+        ]
+    );
+
+    for n in 0..30 {
+        pin_mind_memory(
+            &store,
+            &root_str,
+            MindPinTarget::Note(format!("pinned rule {n:02}")),
+            None,
+            ts(9, 1, n),
+        )
+        .expect("pin rule");
+    }
+    let crowded = compile_mind_context_pack(&root_str, Some(&store), request(), Some(&overrides))
+        .expect("crowded pack");
+    assert_eq!(crowded.rendered_lines.len(), crowded.line_budget);
+    assert!(crowded.truncated);
+    assert!(crowded
+        .rendered_lines
+        .iter()
+        .skip(1)
+        .all(|line| line.starts_with("- ")));
+
+    assert!(unpin_mind_memory(&store, &pin.pin_id).expect("unpin"));
+    assert_eq!(list_mind_pins(&store, &root_str).expect("list").len(), 30);
+}
+
+#[test]
+fn pinning_one_artifact_from_two_projects_keeps_a_pin_in_each() {
+    let store = MindStore::open_in_memory().expect("store");
+    store
+        .insert_observation("obs:shared", "conv-pins", ts(9, 0, 0), "parser fix", &[])
+        .expect("observation");
+    let alpha = temp_project_root("pins-a").to_string_lossy().to_string();
+    let beta = temp_project_root("pins-b").to_string_lossy().to_string();
+
+    // A pin stored before ids carried the scope.
+    store
+        .pin_memory(&MindPin {
+            pin_id: "pin:artifact:obs:shared".to_string(),
+            scope_key: project_scope_key(Path::new(&alpha)),
+            target_kind: MindPinTargetKind::Artifact,
+            target_id: Some("obs:shared".to_string()),
+            text: "parser fix".to_string(),
+            reason: None,
+            pinned_at: ts(8, 0, 0),
+        })
+        .expect("legacy pin");
+
+    let pin = |root: &str, sec| {
+        pin_mind_memory(
+            &store,
+            root,
+            MindPinTarget::Artifact("obs:shared".to_string()),
+            None,
+            ts(9, 1, sec),
+        )
+        .expect("pin")
+    };
+    let in_alpha = pin(&alpha, 0);
+    let in_beta = pin(&beta, 1);
+    assert_ne!(in_alpha.pin_id, in_beta.pin_id);
+    assert_eq!(in_alpha.pinned_at, ts(8, 0, 0));
+
+    for (root, pin) in [(&alpha, &in_alpha), (&beta, &in_beta)] {
+        let listed = list_mind_pins(&store, root).expect("list");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].pin_id, pin.pin_id);
+        assert_eq!(listed[0].scope_key, project_scope_key(Path::new(root)));
+    }
+}
+
+#[test]
+fn third_party_exports_import_as_traced_observations_once() {
+    let now = ts(12, 0, 0);
+    let mem0 = serde_json::json!({
+        "results": [
+            {"id": "m-1", "memory": "Prefers tabs over spaces", "user_id": "alex",
+             "created_at": "2026-02-20T10:00:00-08:00"},
+            {"id": "m-2", "memory": "Deploys on Fridays", "user_id": "alex"},
+            {"id": "m-3", "user_id": "alex"}
+        ]
+    })
+    .to_string();
+    let memories =
+        parse_third_party_export(ThirdPartySource::Mem0, &mem0, now).expect("parse mem0");
+    assert_eq!(memories.len(), 2);
+    assert_eq!(memories[0].conversation_id, "mem0:alex");
+    assert_eq!(memories[0].ts.to_rfc3339(), "2026-02-20T18:00:00+00:00");
+    assert_eq!(memories[1].ts, now);
+
+    let letta = serde_json::json!({
+        "agents": [{
+            "id": "agent-7",
+            "created_at": "2026-02-01T09:30:00.123456",
+            "core_memory": [
+                {"label": "persona", "value": "Terse reviewer"},
+                {"label": "human", "value": "  "}
+            ],
+            "passages": [{"id": "p-1", "text": "Release checklist lives in docs/release.md"}]
+        }]
+    })
+    .to_string();
+    let letta_memories =
+        parse_third_party_export(ThirdPartySource::Letta, &letta, now).expect("parse letta");
+    assert_eq!(letta_memories.len(), 2);
+    assert_eq!(letta_memories[0].memory_id, "letta:agent-7:block:persona");
+    assert_eq!(letta_memories[0].text, "[persona] Terse reviewer");
+    assert_eq!(
+        letta_memories[1].ts.to_rfc3339(),
+        "2026-02-01T09:30:00.123456+00:00"
+    );
+    assert!(parse_third_party_export(ThirdPartySource::Mem0, "42", now).is_err());
+
+    let store = MindStore::open_in_memory().expect("store");
+    let preview = import_third_party_memories(&store, ThirdPartySource::Mem0, &memories, true)
+        .expect("dry run");
+    assert_eq!((preview.imported, preview.conversations), (2, 1));
+    assert!(store
+        .artifact_by_id(&memories[0].artifact_id())
+        .expect("lookup")
+        .is_none());
+
+    let report = import_third_party_memories(&store, ThirdPartySource::Mem0, &memories, false)
+        .expect("import");
+    assert_eq!(report.imported, 2);
+    let artifact = store
+        .artifact_by_id(&memories[0].artifact_id())
+        .expect("lookup")
+        .expect("imported artifact");
+    assert_eq!(artifact.trace_ids, vec![memories[0].event_id()]);
+    assert!(!store.check_integrity(now).expect("doctor").has_errors());
+
+    let again = import_third_party_memories(&store, ThirdPartySource::Mem0, &memories, false)
+        .expect("re-import");
+    assert_eq!((again.imported, again.duplicates), (0, 2));
+}
+
+#[test]
+fn compliance_bundle_exports_hashes_and_deletes_exactly_the_subject() {
+    let store = MindStore::open_in_memory().expect("open");
+    let dir = temp_project_root("compliance");
+    for (id, conv, text) in [
+        ("s1", "conv-alice", "mail Alice@Example.com re rollout"),
+        ("s2", "conv-alice", "rollout done"),
+        ("o1", "conv-other", "unrelated work"),
+    ] {
+        let raw = raw_message(id, conv, ts(9, 0, 0), text);
+        store.insert_raw_event(&raw).expect("raw");
+        insert_t0(&store, id, conv, ts(9, 0, 0), text);
+    }
+    store
+        .insert_observation("obs-alice", "conv-alice", ts(9, 1, 0), "alice shipped", &[])
+        .expect("t1");
+    store
+        .insert_observation("obs-other", "conv-other", ts(9, 1, 0), "other work", &[])
+        .expect("t1");
+
+    let subject = DataSubject::Identifier("alice@example.com".to_string());
+    let manifest = export_compliance_bundle(&store, &subject, &dir, ts(10, 0, 0)).expect("export");
+    assert_eq!(manifest.conversation_ids, vec!["conv-alice".to_string()]);
+    assert_eq!(manifest.records["raw_events"].len(), 2);
+    assert_eq!(manifest.records["observations_t1"].len(), 1);
+    assert!(export_compliance_bundle(&store, &subject, &dir, ts(10, 0, 0)).is_err());
+    assert_eq!(verify_compliance_bundle(&dir).expect("verify"), manifest);
+
+    // New data for the subject after export blocks the delete.
+    insert_t0(&store, "s3", "conv-alice", ts(9, 2, 0), "late message");
+    assert!(matches!(
+        delete_compliance_subject(&store, &dir, false, ts(11, 0, 0)),
+        Err(ComplianceError::Stale(_))
+    ));
+    store
+        .delete_subject_rows(&["conv-alice".to_string()])
+        .expect("reset");
+    let dir = temp_project_root("compliance-2");
+    let manifest = export_compliance_bundle(
+        &store,
+        &DataSubject::Conversation("conv-other".to_string()),
+        &dir,
+        ts(10, 0, 0),
+    )
+    .expect("export other");
+
+    let dry_run = delete_compliance_subject(&store, &dir, true, ts(11, 0, 0)).expect("dry run");
+    assert_eq!(dry_run.deleted["raw_events"], 1);
+    assert!(!dir.join(COMPLIANCE_RECEIPT_FILE).exists());
+
+    let receipt = delete_compliance_subject(&store, &dir, false, ts(11, 0, 0)).expect("delete");
+    assert!(receipt.verified);
+    assert_eq!(
+        receipt.deleted.values().sum::<usize>(),
+        manifest.total_records()
+    );
+    assert!(store
+        .raw_events_for_conversation("conv-other")
+        .expect("raw")
+        .is_empty());
+    assert!(store.artifact_by_id("obs-other").expect("lookup").is_none());
+    assert!(dir.join(COMPLIANCE_RECEIPT_FILE).exists());
+
+    std::fs::write(dir.join("raw_events.jsonl"), "{}\n").expect("tamper");
+    assert!(matches!(
+        verify_compliance_bundle(&dir),
+        Err(ComplianceError::Verification(_))
+    ));
+}
+
+#[test]
+fn archival_policy_tiers_aged_artifacts_and_restores_them() {
+    let store = MindStore::open_in_memory().expect("store");
+    let now = ts(12, 0, 0);
+    let days_ago = |days: i64| now - chrono::Duration::days(days);
+    store
+        .insert_observation("obs-stale", "conv-arch", days_ago(30), "stale t1", &[])
+        .expect("stale t1");
+    store
+        .insert_observation("obs-pinned", "conv-arch", days_ago(30), "pinned t1", &[])
+        .expect("pinned t1");
+    store
+        .insert_observation("obs-fresh", "conv-arch", days_ago(1), "fresh t1", &[])
+        .expect("fresh t1");
+    store
+        .insert_reflection(
+            "ref-cited",
+            "conv-arch",
+            days_ago(30),
+            "well-supported reflection",
+            &[
+                "obs-stale".to_string(),
+                "obs-pinned".to_string(),
+                "obs-fresh".to_string(),
+            ],
+        )
+        .expect("t2");
+    pin_mind_memory(
+        &store,
+        "/repo",
+        MindPinTarget::Artifact("obs-pinned".to_string()),
+        None,
+        now,
+    )
+    .expect("pin artifact");
+
+    let policy = ArchivalPolicy {
+        min_age_hours: 14 * 24,
+        half_life_hours: 30 * 24,
+        retention_floor_bps: 2_500,
+    };
+    let preview = run_artifact_archival(&store, policy, now, true).expect("dry run");
+    assert_eq!(preview.scanned, 3);
+    assert_eq!(preview.exempt, 1);
+    assert_eq!(preview.retained, 1);
+    assert_eq!(preview.archived.len(), 1);
+    assert_eq!(preview.archived[0].artifact_id, "obs-stale");
+    assert_eq!(preview.archived[0].retention_bps, 2_000);
+    assert!(store
+        .archived_artifacts(None)
+        .expect("nothing archived yet")
+        .is_empty());
+
+    let report = run_artifact_archival(&store, policy, now, false).expect("archive");
+    assert_eq!(report.archived.len(), 1);
+    let visible = store
+        .artifacts_for_conversation("conv-arch")
+        .expect("default tier")
+        .into_iter()
+        .map(|artifact| artifact.artifact_id)
+        .collect::<Vec<_>>();
+    assert!(!visible.contains(&"obs-stale".to_string()));
+    assert_eq!(visible.len(), 3);
+    assert!(store
+        .artifact_by_id("obs-stale")
+        .expect("explicit")
+        .is_some());
+
+    let restored =
+        restore_archived_artifacts(&store, &["obs-stale".to_string(), "obs-fresh".to_string()])
+            .expect("restore");
+    assert_eq!(restored, 1);
+    assert_eq!(
+        store
+            .artifacts_for_conversation("conv-arch")
+            .expect("restored tier")
+            .len(),
+        4
+    );
 }
 
 #[test]
@@ -474,6 +955,8 @@ fn runtime_owns_runtime_failure_and_completion_policy() {
             jobs_claimed: 2,
             jobs_completed: 1,
             jobs_failed: 1,
+            jobs_requeued: 0,
+            jobs_dead_lettered: 1,
         },
         true,
     );
@@ -510,6 +993,10 @@ fn runtime_owns_runtime_failure_and_completion_policy() {
         reflector_failure.observer_events[0].runtime.as_deref(),
         Some("t2_reflector")
     );
+    assert_eq!(
+        reflector_failure.observer_events[0].error_code,
+        Some(aoc_core::ErrorCode::Internal)
+    );
 
     let t3_failure = runtime.apply_t3_runtime_failure(&mut snapshot, "kaput", now);
     assert_eq!(
@@ -557,6 +1044,8 @@ fn runtime_owns_tick_health_and_observer_effects() {
             jobs_claimed: 2,
             jobs_completed: 1,
             jobs_failed: 1,
+            jobs_requeued: 0,
+            jobs_dead_lettered: 1,
             lock_conflict: true,
         },
         now,
@@ -677,6 +1166,63 @@ fn under_budget_runs_single_pass_t1() {
     assert_eq!(provenance[0].stage, SemanticStage::T1Observer);
 }
 
+#[test]
+fn distillation_checkpoint_skips_already_processed_t0() {
+    let store = MindStore::open_in_memory().expect("open");
+    insert_t0(&store, "e1", "conv-1", ts(12, 0, 0), "short one");
+    insert_t0(&store, "e2", "conv-1", ts(12, 0, 1), "short two");
+
+    let distiller = DeterministicDistiller::new(DistillationConfig {
+        enable_attribution: false,
+        ..DistillationConfig::default()
+    });
+    let first = distiller
+        .distill_conversation(&store, "conv-1")
+        .expect("first distill");
+    assert_eq!(first.t0_events_processed, 2);
+    let checkpoint = store
+        .distillation_checkpoint("conv-1")
+        .expect("checkpoint")
+        .expect("checkpoint written");
+    assert_eq!(checkpoint.t0_events_covered, 2);
+    assert_eq!(checkpoint.last_ts, ts(12, 0, 1));
+
+    let idle = distiller
+        .distill_conversation(&store, "conv-1")
+        .expect("idle distill");
+    assert_eq!(idle, DistillationReport::default());
+
+    insert_t0(&store, "e3", "conv-1", ts(12, 0, 2), "short three");
+    let incremental = distiller
+        .distill_conversation(&store, "conv-1")
+        .expect("incremental distill");
+    assert_eq!(incremental.t0_events_processed, 1);
+    assert_eq!(incremental.t1_batches_planned, 1);
+    let artifacts = store
+        .artifacts_for_conversation("conv-1")
+        .expect("artifacts");
+    let t1 = artifacts
+        .iter()
+        .filter(|artifact| artifact.kind == "t1")
+        .collect::<Vec<_>>();
+    assert_eq!(t1.len(), 2);
+    assert!(t1.iter().any(|artifact| artifact.trace_ids.len() == 1));
+
+    insert_t0(&store, "e0", "conv-1", ts(11, 59, 59), "late arrival");
+    let replanned = distiller
+        .distill_conversation(&store, "conv-1")
+        .expect("replanned distill");
+    assert_eq!(replanned.t0_events_processed, 4);
+    assert_eq!(
+        store
+            .distillation_checkpoint("conv-1")
+            .expect("checkpoint")
+            .expect("checkpoint kept")
+            .t0_events_covered,
+        4
+    );
+}
+
 #[test]
 fn over_budget_chunks_with_deterministic_order_and_traceability() {
     let store = MindStore::open_in_memory().expect("open");
@@ -721,6 +1267,15 @@ fn over_budget_chunks_with_deterministic_order_and_traceability() {
         .filter(|artifact| artifact.kind == "t1")
         .collect::<Vec<_>>();
 
+    let idle = distiller
+        .distill_conversation(&store, "conv-2")
+        .expect("idle distill");
+    assert_eq!(idle.t1_batches_planned, 0);
+
+    // Without the checkpoint the whole conversation is re-planned.
+    assert!(store
+        .clear_distillation_checkpoint("conv-2")
+        .expect("clear checkpoint"));
     let second = distiller
         .distill_conversation(&store, "conv-2")
         .expect("second distill");
@@ -750,6 +1305,72 @@ fn over_budget_chunks_with_deterministic_order_and_traceability() {
     }
 }
 
+#[test]
+fn interrupted_distillation_resumes_from_its_journal() {
+    let store = MindStore::open_in_memory().expect("open");
+    for (id, second, fill) in [("e1", 0, "a"), ("e2", 1, "b"), ("e3", 2, "c")] {
+        insert_t0(&store, id, "conv-r", ts(12, 10, second), &fill.repeat(60));
+    }
+    let config = DistillationConfig {
+        t1_target_tokens: 20,
+        t1_hard_cap_tokens: 32,
+        enable_attribution: false,
+        ..Default::default()
+    };
+    let distiller = DeterministicDistiller::new(config);
+
+    let first = distiller
+        .distill_conversation(&store, "conv-r")
+        .expect("first distill");
+    assert_eq!(first.t1_artifacts_written, 3);
+    assert_eq!(first.t1_artifacts_resumed, 0);
+    assert!(store.interrupted_pipeline_runs().expect("runs").is_empty());
+    let t1_ids = store
+        .artifacts_for_conversation("conv-r")
+        .expect("artifacts")
+        .into_iter()
+        .filter(|artifact| artifact.kind == "t1")
+        .map(|artifact| artifact.artifact_id)
+        .collect::<Vec<_>>();
+
+    // A writer commits one batch, then dies before the rest and before it
+    // advances the checkpoint.
+    store
+        .clear_distillation_checkpoint("conv-r")
+        .expect("clear checkpoint");
+    let now = ts(12, 11, 0);
+    let run = store
+        .begin_pipeline_run(DISTILL_RUN_STAGE, "conv-r", 3, now)
+        .expect("begin");
+    store
+        .complete_pipeline_unit(&run.run_id, &t1_ids[0], now, |store| {
+            store.insert_observation(&t1_ids[0], "conv-r", now, "committed before crash", &[])
+        })
+        .expect("unit");
+    assert_eq!(store.interrupted_pipeline_runs().expect("runs").len(), 1);
+
+    let resumed = distiller
+        .distill_conversation(&store, "conv-r")
+        .expect("resumed distill");
+    assert_eq!(resumed.t1_artifacts_resumed, 1);
+    assert_eq!(resumed.t1_artifacts_written, 2);
+    assert_eq!(
+        store
+            .artifact_by_id(&t1_ids[0])
+            .expect("lookup")
+            .expect("t1")
+            .text,
+        "committed before crash"
+    );
+    let finished = store
+        .pipeline_run(&run.run_id)
+        .expect("run")
+        .expect("present");
+    assert_eq!(finished.status, PipelineRunStatus::Completed);
+    assert_eq!(finished.attempts, 2);
+    assert!(store.interrupted_pipeline_runs().expect("runs").is_empty());
+}
+
 #[test]
 fn planner_uses_hard_cap_when_target_exceeds_hard_cap() {
     let events = vec![
@@ -931,6 +1552,7 @@ fn session_sidecar_runs_semantic_t1_after_debounce() {
             summary: "semantic observer summary".to_string(),
             key_points: vec!["point a".to_string(), "point b".to_string()],
             citations: vec!["t0:e1".to_string()],
+            ..ObserverOutput::default()
         }),
     };
     let mut sidecar =
@@ -963,6 +1585,62 @@ fn session_sidecar_runs_semantic_t1_after_debounce() {
     assert_eq!(provenance[0].stage, SemanticStage::T1Observer);
 }
 
+#[test]
+fn semantic_t1_persists_structured_observer_sections() {
+    let store = MindStore::open_in_memory().expect("open");
+    insert_t0(
+        &store,
+        "e1",
+        "conv-v2",
+        ts(16, 0, 0),
+        "pick sqlite for the queue",
+    );
+
+    let adapter = StaticObserverAdapter {
+        result: Ok(ObserverOutput {
+            summary: "queue storage chosen".to_string(),
+            decisions: vec!["use sqlite".to_string()],
+            open_questions: vec!["how long do leases last?".to_string()],
+            risks: vec!["lock contention".to_string()],
+            action_items: vec![ObserverActionItem {
+                text: "add lease tests".to_string(),
+                task_ref: Some("AOC-12".to_string()),
+            }],
+            ..ObserverOutput::default()
+        }),
+    };
+    let distiller = SemanticObserverDistiller::new(
+        DistillationConfig {
+            enable_attribution: false,
+            t2_trigger_tokens: 9_999,
+            ..DistillationConfig::default()
+        },
+        SemanticObserverConfig::default(),
+        adapter,
+    );
+    let report = distiller
+        .distill_conversation(&store, "conv-v2")
+        .expect("distill");
+    assert_eq!(report.t1_artifacts_written, 1);
+
+    let artifact = store
+        .artifacts_for_conversation("conv-v2")
+        .expect("artifacts")
+        .remove(0);
+    assert!(artifact.text.contains("decision: use sqlite"));
+    assert!(artifact.text.contains("action: add lease tests (AOC-12)"));
+    let stored = store
+        .observation_sections(&artifact.artifact_id)
+        .expect("sections")
+        .expect("sections stored");
+    assert_eq!(stored.prompt_version, OBSERVER_SECTIONS_PROMPT_VERSION);
+    assert_eq!(stored.sections.risks, vec!["lock contention".to_string()]);
+    assert_eq!(
+        stored.sections.action_items[0].task_ref.as_deref(),
+        Some("AOC-12")
+    );
+}
+
 #[test]
 fn semantic_failure_falls_back_to_deterministic_t1() {
     let store = MindStore::open_in_memory().expect("open");
@@ -1017,6 +1695,11 @@ fn semantic_failure_falls_back_to_deterministic_t1() {
     assert_eq!(provenance[1].runtime, SemanticRuntime::Deterministic);
     assert_eq!(provenance[1].attempt_count, 3);
     assert!(provenance[1].fallback_used);
+
+    let usage = store.semantic_usage_report(None).expect("usage report");
+    assert_eq!(usage.total.calls, 1);
+    assert_eq!(usage.total.fallback_rate_bps(), 10_000);
+    assert!(usage.total.cost_micros > 0);
 }
 
 #[test]
@@ -1038,7 +1721,7 @@ fn semantic_observer_retries_and_persists_attempt_count_on_success() {
     semantic_config.guardrails.max_retries = 1;
 
     let adapter = SequenceObserverAdapter {
-        scripted_results: RefCell::new(vec![
+        scripted_results: Mutex::new(vec![
             Err(SemanticAdapterError::new(
                 SemanticFailureKind::ProviderError,
                 "temporary provider outage",
@@ -1047,6 +1730,7 @@ fn semantic_observer_retries_and_persists_attempt_count_on_success() {
                 summary: "retry succeeded".to_string(),
                 key_points: vec!["attempt two".to_string()],
                 citations: vec![],
+                ..ObserverOutput::default()
             }),
         ]),
         delay_ms: 0,
@@ -1075,43 +1759,621 @@ fn semantic_observer_retries_and_persists_attempt_count_on_success() {
 }
 
 #[test]
-fn guardrail_budget_exceeded_falls_back_to_deterministic_t1() {
+fn semantic_observer_sees_redacted_payload_and_records_categories() {
     let store = MindStore::open_in_memory().expect("open");
     insert_t0(
         &store,
         "e1",
-        "conv-budget",
-        ts(16, 16, 0),
-        "this line is intentionally long enough to exceed a tiny budget guardrail",
+        "conv-redact",
+        ts(16, 15, 0),
+        "mail dana@example.com the CUST-424242 export before rotating the key",
     );
 
-    let mut distill_config = DistillationConfig::default();
-    distill_config.enable_attribution = false;
-    distill_config.t2_trigger_tokens = 9_999;
+    let distill_config = DistillationConfig {
+        enable_attribution: false,
+        t2_trigger_tokens: 9_999,
+        ..DistillationConfig::default()
+    };
 
-    let mut semantic_config = SemanticObserverConfig::default();
-    semantic_config.guardrails.max_budget_tokens = 8;
-    semantic_config.guardrails.max_retries = 2;
+    let semantic_config = SemanticObserverConfig {
+        redaction: RedactionConfig {
+            patterns: vec![RedactionPattern {
+                category: "customer_id".to_string(),
+                regex: r"CUST-\d{6}".to_string(),
+            }],
+            ..RedactionConfig::default()
+        },
+        ..SemanticObserverConfig::default()
+    };
 
-    let adapter = StaticObserverAdapter {
-        result: Ok(ObserverOutput {
-            summary: "should never run due to budget preflight".to_string(),
-            key_points: vec![],
-            citations: vec![],
-        }),
+    let seen_lines = Arc::new(Mutex::new(Vec::new()));
+    let adapter = RecordingObserverAdapter {
+        seen_lines: seen_lines.clone(),
     };
-    let mut sidecar = SessionObserverSidecar::new(distill_config, semantic_config, adapter);
+    SemanticObserverDistiller::new(distill_config, semantic_config, adapter)
+        .distill_conversation(&store, "conv-redact")
+        .expect("distill");
 
-    let now = ts(16, 16, 30);
-    sidecar.enqueue_turn("session-budget", "conv-budget", now);
-    let outcomes = sidecar.run_ready(&store, now + chrono::Duration::milliseconds(300));
-    assert_eq!(outcomes.len(), 1);
-    outcomes[0].report.as_ref().expect("report");
+    let seen = seen_lines.lock().expect("seen lines").join("\n");
+    assert!(seen.contains(REDACTED_MARKER), "{seen}");
+    assert!(!seen.contains("dana@example.com"), "{seen}");
+    assert!(!seen.contains("CUST-424242"), "{seen}");
 
     let artifacts = store
-        .artifacts_for_conversation("conv-budget")
+        .artifacts_for_conversation("conv-redact")
         .expect("artifacts");
-    assert_eq!(artifacts.len(), 1);
+    let provenance = store
+        .semantic_provenance_for_artifact(&artifacts[0].artifact_id)
+        .expect("provenance");
+    assert_eq!(provenance[0].redactions, vec!["customer_id", "email"]);
+}
+
+#[test]
+fn semantic_cache_answers_replayed_observer_input_without_a_call() {
+    let store = MindStore::open_in_memory().expect("open");
+    insert_t0(
+        &store,
+        "e1",
+        "conv-cache",
+        ts(16, 16, 0),
+        "replayed batches should not pay twice",
+    );
+
+    let seen_lines = Arc::new(Mutex::new(Vec::new()));
+    let distill = |t1_output_max_chars: usize| {
+        SemanticObserverDistiller::new(
+            DistillationConfig {
+                enable_attribution: false,
+                t2_trigger_tokens: 9_999,
+                t1_output_max_chars,
+                ..DistillationConfig::default()
+            },
+            SemanticObserverConfig::default(),
+            RecordingObserverAdapter {
+                seen_lines: seen_lines.clone(),
+            },
+        )
+        .distill_conversation(&store, "conv-cache")
+        .expect("distill")
+    };
+
+    let first = distill(800);
+    assert_eq!(first.t1_cache_hits, 0);
+    assert!(store
+        .clear_distillation_checkpoint("conv-cache")
+        .expect("clear checkpoint"));
+    let replay = distill(900);
+    assert_eq!(replay.t1_artifacts_written, 1);
+    assert_eq!(replay.t1_cache_hits, 1);
+    assert_eq!(
+        seen_lines.lock().expect("seen lines").len(),
+        1,
+        "second run called the provider"
+    );
+
+    let artifacts = store
+        .artifacts_for_conversation("conv-cache")
+        .expect("artifacts");
+    assert_eq!(artifacts.len(), 2);
+    let replayed = artifacts
+        .iter()
+        .map(|artifact| {
+            store
+                .semantic_provenance_for_artifact(&artifact.artifact_id)
+                .expect("provenance")
+                .remove(0)
+        })
+        .find(|provenance| provenance.attempt_count == 0)
+        .expect("cache-hit provenance");
+    assert_eq!(replayed.latency_ms, None);
+    assert!(!replayed.fallback_used);
+    let usage = store.semantic_usage_report(None).expect("usage");
+    assert_eq!(usage.total.calls, 1);
+}
+
+#[test]
+fn semantic_cache_entries_expire_on_the_injected_clock() {
+    let store = MindStore::open_in_memory().expect("open");
+    insert_t0(
+        &store,
+        "e1",
+        "conv-cache-ttl",
+        ts(16, 16, 0),
+        "a cached answer only lasts its ttl",
+    );
+
+    let clock = Arc::new(aoc_storage::MockClock::new(ts(16, 16, 30)));
+    let seen_lines = Arc::new(Mutex::new(Vec::new()));
+    let distill = |t1_output_max_chars: usize| {
+        SemanticObserverDistiller::new(
+            DistillationConfig {
+                enable_attribution: false,
+                t2_trigger_tokens: 9_999,
+                t1_output_max_chars,
+                ..DistillationConfig::default()
+            },
+            SemanticObserverConfig {
+                cache: SemanticCacheConfig {
+                    ttl_hours: 1,
+                    ..SemanticCacheConfig::default()
+                },
+                ..SemanticObserverConfig::default()
+            },
+            RecordingObserverAdapter {
+                seen_lines: seen_lines.clone(),
+            },
+        )
+        .with_clock(clock.clone())
+        .distill_conversation(&store, "conv-cache-ttl")
+        .expect("distill")
+    };
+
+    assert_eq!(distill(800).t1_cache_hits, 0);
+    assert!(store
+        .clear_distillation_checkpoint("conv-cache-ttl")
+        .expect("clear checkpoint"));
+    clock.advance(chrono::Duration::hours(2));
+    let replay = distill(900);
+    assert_eq!(replay.t1_cache_hits, 0);
+    assert_eq!(
+        seen_lines.lock().expect("seen lines").len(),
+        2,
+        "expired entry answered the replay"
+    );
+}
+
+#[test]
+fn daily_budget_ledger_refuses_calls_once_the_cap_is_spent() {
+    let store = MindStore::open_in_memory().expect("open");
+    insert_t0(
+        &store,
+        "e1",
+        "conv-a",
+        ts(16, 17, 0),
+        "first call fits today",
+    );
+    insert_t0(&store, "e2", "conv-b", ts(16, 17, 5), "second call is over");
+
+    let mut semantic_config = SemanticObserverConfig::default();
+    let provider = semantic_config.profile.provider_name.clone();
+    semantic_config.budget.providers.insert(
+        provider.clone(),
+        ProviderDailyBudget {
+            max_tokens_per_day: 1_000,
+            max_cost_micros_per_day: 0,
+        },
+    );
+    let budget = BudgetManager::new(semantic_config.budget.clone());
+    let seen_lines = Arc::new(Mutex::new(Vec::new()));
+    let distiller = SemanticObserverDistiller::new(
+        DistillationConfig {
+            enable_attribution: false,
+            t2_trigger_tokens: 9_999,
+            ..DistillationConfig::default()
+        },
+        semantic_config,
+        RecordingObserverAdapter {
+            seen_lines: seen_lines.clone(),
+        },
+    );
+
+    distiller
+        .distill_conversation(&store, "conv-a")
+        .expect("distill a");
+    let status = budget.status(&store, Utc::now()).expect("status");
+    assert_eq!(
+        status[0].provider_name,
+        aoc_storage::SEMANTIC_BUDGET_ALL_PROVIDERS
+    );
+    assert_eq!(status[0].remaining_tokens(), None);
+    assert_eq!(status[1].provider_name, provider);
+    assert_eq!(status[1].calls, 1);
+    assert_eq!(status[0].spent_tokens, status[1].spent_tokens);
+    assert!(status[1].remaining_tokens().expect("capped") < 1_000);
+
+    budget
+        .charge(&store, &provider, 1_000, 0, Utc::now())
+        .expect("spend the rest");
+    distiller
+        .distill_conversation(&store, "conv-b")
+        .expect("distill b");
+    assert_eq!(
+        seen_lines.lock().expect("seen lines").len(),
+        1,
+        "refused call reached the provider"
+    );
+
+    let artifact = store
+        .artifacts_for_conversation("conv-b")
+        .expect("artifacts")
+        .remove(0);
+    assert!(artifact.text.starts_with("T1 observation"));
+    let provenance = store
+        .semantic_provenance_for_artifact(&artifact.artifact_id)
+        .expect("provenance");
+    assert_eq!(
+        provenance[0].failure_kind,
+        Some(SemanticFailureKind::BudgetExceeded)
+    );
+    let status = budget.status(&store, Utc::now()).expect("status");
+    assert_eq!(status[1].remaining_tokens(), Some(0));
+    assert_eq!((status[1].calls, status[1].rejected_calls), (2, 1));
+}
+
+#[test]
+fn daily_budget_ledger_rolls_over_with_the_injected_clock() {
+    let store = MindStore::open_in_memory().expect("open");
+    for (index, conversation_id) in ["conv-a", "conv-b", "conv-c"].iter().enumerate() {
+        insert_t0(
+            &store,
+            &format!("e{index}"),
+            conversation_id,
+            ts(16, 17, index as u32),
+            "one call per conversation",
+        );
+    }
+
+    let mut semantic_config = SemanticObserverConfig::default();
+    let provider = semantic_config.profile.provider_name.clone();
+    semantic_config.budget.providers.insert(
+        provider.clone(),
+        ProviderDailyBudget {
+            max_tokens_per_day: 1_000,
+            max_cost_micros_per_day: 0,
+        },
+    );
+    let budget = BudgetManager::new(semantic_config.budget.clone());
+    let clock = Arc::new(aoc_storage::MockClock::new(ts(23, 58, 0)));
+    let seen_lines = Arc::new(Mutex::new(Vec::new()));
+    let distiller = SemanticObserverDistiller::new(
+        DistillationConfig {
+            enable_attribution: false,
+            t2_trigger_tokens: 9_999,
+            ..DistillationConfig::default()
+        },
+        semantic_config,
+        RecordingObserverAdapter {
+            seen_lines: seen_lines.clone(),
+        },
+    )
+    .with_clock(clock.clone());
+
+    budget
+        .charge(&store, &provider, 1_000, 0, clock.now())
+        .expect("spend today");
+    distiller
+        .distill_conversation(&store, "conv-a")
+        .expect("distill a");
+    assert!(seen_lines.lock().expect("seen lines").is_empty());
+
+    clock.advance(chrono::Duration::minutes(5));
+    distiller
+        .distill_conversation(&store, "conv-b")
+        .expect("distill b");
+    assert_eq!(
+        seen_lines.lock().expect("seen lines").len(),
+        1,
+        "the next day's budget refused the call"
+    );
+    let status = budget.status(&store, clock.now()).expect("status");
+    assert_eq!(status[1].calls, 1);
+    assert!(status[1].remaining_tokens().expect("capped") < 1_000);
+}
+
+#[test]
+fn circuit_breaker_skips_a_failing_provider_and_reports_the_trip_in_the_feed() {
+    let store = MindStore::open_in_memory().expect("open");
+    for (index, conversation_id) in ["conv-1", "conv-2", "conv-3"].iter().enumerate() {
+        insert_t0(
+            &store,
+            &format!("e{index}"),
+            conversation_id,
+            ts(16, 19, index as u32),
+            "provider is down for this batch",
+        );
+    }
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let semantic_config = SemanticObserverConfig {
+        guardrails: SemanticGuardrails {
+            max_retries: 0,
+            ..SemanticGuardrails::default()
+        },
+        breaker: CircuitBreakerConfig {
+            failure_threshold: 2,
+            ..CircuitBreakerConfig::default()
+        },
+        ..SemanticObserverConfig::default()
+    };
+    let mut sidecar = SessionObserverSidecar::new(
+        DistillationConfig {
+            enable_attribution: false,
+            t2_trigger_tokens: 9_999,
+            ..DistillationConfig::default()
+        },
+        semantic_config,
+        DownObserverAdapter {
+            calls: calls.clone(),
+        },
+    );
+
+    let now = ts(16, 19, 30);
+    let mut feeds = Vec::new();
+    for conversation_id in ["conv-1", "conv-2", "conv-3"] {
+        sidecar.enqueue_manual("session-down", conversation_id, now);
+        let drained = drain_observer_state(
+            &mut sidecar,
+            &store,
+            Some("session-down"),
+            now + chrono::Duration::seconds(1),
+            now + chrono::Duration::seconds(2),
+        );
+        feeds.push(drained.events);
+    }
+
+    assert_eq!(
+        calls.load(Ordering::SeqCst),
+        2,
+        "open breaker still called the provider"
+    );
+    let breaker_events = feeds
+        .iter()
+        .map(|events| {
+            events
+                .iter()
+                .filter(|event| event.status == MindObserverFeedStatus::Breaker)
+                .count()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(breaker_events, vec![0, 1, 0]);
+    let trip = &feeds[1][1];
+    assert_eq!(trip.status, MindObserverFeedStatus::Breaker);
+    assert_eq!(trip.conversation_id.as_deref(), Some("conv-2"));
+    let reason = trip.reason.as_deref().expect("reason");
+    assert!(reason.contains("closed -> open"), "{reason}");
+    assert_eq!(feeds[1][2].status, MindObserverFeedStatus::Fallback);
+
+    let artifact = store
+        .artifacts_for_conversation("conv-3")
+        .expect("artifacts")
+        .remove(0);
+    assert!(artifact.text.starts_with("T1 observation"));
+    let provenance = store
+        .semantic_provenance_for_artifact(&artifact.artifact_id)
+        .expect("provenance");
+    assert_eq!(provenance[0].latency_ms, None);
+    assert!(provenance[0]
+        .fallback_reason
+        .as_deref()
+        .is_some_and(|reason| reason.starts_with("circuit breaker open")));
+}
+
+#[test]
+fn circuit_breaker_cooldown_follows_the_injected_clock() {
+    let store = MindStore::open_in_memory().expect("open");
+    for (index, conversation_id) in ["conv-1", "conv-2", "conv-3"].iter().enumerate() {
+        insert_t0(
+            &store,
+            &format!("e{index}"),
+            conversation_id,
+            ts(16, 19, index as u32),
+            "provider is down for this batch",
+        );
+    }
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let clock = Arc::new(aoc_storage::MockClock::new(ts(16, 19, 30)));
+    let semantic_config = SemanticObserverConfig {
+        guardrails: SemanticGuardrails {
+            max_retries: 0,
+            ..SemanticGuardrails::default()
+        },
+        breaker: CircuitBreakerConfig {
+            failure_threshold: 1,
+            cooldown_ms: 60_000,
+            ..CircuitBreakerConfig::default()
+        },
+        ..SemanticObserverConfig::default()
+    };
+    let mut sidecar = SessionObserverSidecar::new(
+        DistillationConfig {
+            enable_attribution: false,
+            t2_trigger_tokens: 9_999,
+            ..DistillationConfig::default()
+        },
+        semantic_config,
+        DownObserverAdapter {
+            calls: calls.clone(),
+        },
+    )
+    .with_clock(clock.clone());
+
+    let mut observe = |conversation_id: &str| {
+        let now = clock.now();
+        sidecar.enqueue_manual("session-down", conversation_id, now);
+        sidecar.run_ready(&store, now + chrono::Duration::seconds(1));
+    };
+    observe("conv-1");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    clock.advance(chrono::Duration::seconds(59));
+    observe("conv-2");
+    assert_eq!(
+        calls.load(Ordering::SeqCst),
+        1,
+        "breaker let a call through before its cooldown"
+    );
+
+    clock.advance(chrono::Duration::seconds(1));
+    observe("conv-3");
+    assert_eq!(
+        calls.load(Ordering::SeqCst),
+        2,
+        "breaker skipped the trial call after its cooldown"
+    );
+}
+
+#[test]
+fn observer_workers_call_batches_concurrently_and_write_them_in_order() {
+    let observe = |workers: usize| {
+        let store = MindStore::open_in_memory().expect("open");
+        for (index, fill) in ["a", "b", "c", "d"].iter().enumerate() {
+            insert_t0(
+                &store,
+                &format!("e{index}"),
+                "conv-pool",
+                ts(16, 20, index as u32),
+                &fill.repeat(60),
+            );
+        }
+        let distiller = SemanticObserverDistiller::new(
+            DistillationConfig {
+                t1_target_tokens: 20,
+                t1_hard_cap_tokens: 32,
+                enable_attribution: false,
+                t2_trigger_tokens: 9_999,
+                ..DistillationConfig::default()
+            },
+            SemanticObserverConfig {
+                workers,
+                ..SemanticObserverConfig::default()
+            },
+            ConcurrentObserverAdapter::default(),
+        );
+        let report = distiller
+            .distill_conversation(&store, "conv-pool")
+            .expect("distill");
+        assert_eq!(report.t1_batches_planned, 4);
+        assert_eq!(report.t1_artifacts_written, 4);
+        let artifacts = store
+            .artifacts_for_conversation("conv-pool")
+            .expect("artifacts")
+            .into_iter()
+            .filter(|artifact| artifact.kind == "t1")
+            .collect::<Vec<_>>();
+        (
+            artifacts,
+            distiller.adapter.max_in_flight.load(Ordering::SeqCst),
+        )
+    };
+
+    let (serial, serial_in_flight) = observe(1);
+    let (pooled, pooled_in_flight) = observe(4);
+    assert_eq!(serial_in_flight, 1);
+    assert!(pooled_in_flight > 1, "batches never overlapped");
+    assert_eq!(pooled, serial);
+    assert!(pooled[0].text.contains("aaaa"));
+    assert!(pooled[3].text.contains("dddd"));
+}
+
+#[test]
+fn observer_workers_reserve_the_budget_for_every_call_of_a_wave() {
+    let store = MindStore::open_in_memory().expect("open");
+    for (index, fill) in ["a", "b", "c", "d"].iter().enumerate() {
+        insert_t0(
+            &store,
+            &format!("e{index}"),
+            "conv-pool-budget",
+            ts(16, 21, index as u32),
+            &fill.repeat(60),
+        );
+    }
+    let pending = pending_t0_events(&store, "conv-pool-budget").expect("pending");
+    let batches = plan_t1_batches(&pending.events, 20, 32).expect("plan");
+    assert_eq!(batches.len(), 4);
+
+    let mut semantic_config = SemanticObserverConfig {
+        workers: 4,
+        ..SemanticObserverConfig::default()
+    };
+    let provider = semantic_config.profile.provider_name.clone();
+    // Room for exactly one call projected at the full output allowance.
+    let cap = u64::from(batches[0].estimated_tokens + semantic_config.profile.max_output_tokens);
+    semantic_config.budget.providers.insert(
+        provider.clone(),
+        ProviderDailyBudget {
+            max_tokens_per_day: cap,
+            max_cost_micros_per_day: 0,
+        },
+    );
+    let budget = BudgetManager::new(semantic_config.budget.clone());
+    let seen_lines = Arc::new(Mutex::new(Vec::new()));
+    let distiller = SemanticObserverDistiller::new(
+        DistillationConfig {
+            t1_target_tokens: 20,
+            t1_hard_cap_tokens: 32,
+            enable_attribution: false,
+            t2_trigger_tokens: 9_999,
+            ..DistillationConfig::default()
+        },
+        semantic_config,
+        RecordingObserverAdapter {
+            seen_lines: seen_lines.clone(),
+        },
+    );
+
+    let report = distiller
+        .distill_conversation(&store, "conv-pool-budget")
+        .expect("distill");
+    assert_eq!(report.t1_artifacts_written, 4);
+    assert_eq!(
+        seen_lines.lock().expect("seen lines").len(),
+        1,
+        "a wave overran the daily cap"
+    );
+    let status = budget.status(&store, Utc::now()).expect("status");
+    assert_eq!(status[1].provider_name, provider);
+    assert_eq!((status[1].calls, status[1].rejected_calls), (1, 3));
+    assert!(status[1].spent_tokens <= cap);
+
+    let fallbacks = store
+        .artifacts_for_conversation("conv-pool-budget")
+        .expect("artifacts")
+        .into_iter()
+        .filter(|artifact| artifact.text.starts_with("T1 observation"))
+        .count();
+    assert_eq!(fallbacks, 3);
+}
+
+#[test]
+fn guardrail_budget_exceeded_falls_back_to_deterministic_t1() {
+    let store = MindStore::open_in_memory().expect("open");
+    insert_t0(
+        &store,
+        "e1",
+        "conv-budget",
+        ts(16, 16, 0),
+        "this line is intentionally long enough to exceed a tiny budget guardrail",
+    );
+
+    let mut distill_config = DistillationConfig::default();
+    distill_config.enable_attribution = false;
+    distill_config.t2_trigger_tokens = 9_999;
+
+    let mut semantic_config = SemanticObserverConfig::default();
+    semantic_config.guardrails.max_budget_tokens = 8;
+    semantic_config.guardrails.max_retries = 2;
+
+    let adapter = StaticObserverAdapter {
+        result: Ok(ObserverOutput {
+            summary: "should never run due to budget preflight".to_string(),
+            key_points: vec![],
+            citations: vec![],
+            ..ObserverOutput::default()
+        }),
+    };
+    let mut sidecar = SessionObserverSidecar::new(distill_config, semantic_config, adapter);
+
+    let now = ts(16, 16, 30);
+    sidecar.enqueue_turn("session-budget", "conv-budget", now);
+    let outcomes = sidecar.run_ready(&store, now + chrono::Duration::milliseconds(300));
+    assert_eq!(outcomes.len(), 1);
+    outcomes[0].report.as_ref().expect("report");
+
+    let artifacts = store
+        .artifacts_for_conversation("conv-budget")
+        .expect("artifacts");
+    assert_eq!(artifacts.len(), 1);
     assert!(artifacts[0].text.starts_with("T1 observation"));
 
     let provenance = store
@@ -1125,6 +2387,14 @@ fn guardrail_budget_exceeded_falls_back_to_deterministic_t1() {
     assert_eq!(provenance[0].attempt_count, 1);
     assert_eq!(provenance[1].runtime, SemanticRuntime::Deterministic);
     assert_eq!(provenance[1].attempt_count, 2);
+    assert_eq!(
+        store
+            .semantic_usage_report(None)
+            .expect("usage report")
+            .total
+            .calls,
+        0
+    );
 }
 
 #[test]
@@ -1151,6 +2421,7 @@ fn guardrail_cost_budget_exceeded_falls_back_to_deterministic_t1() {
             summary: "should not execute".to_string(),
             key_points: vec![],
             citations: vec![],
+            ..ObserverOutput::default()
         }),
     };
     let mut sidecar = SessionObserverSidecar::new(distill_config, semantic_config, adapter);
@@ -1198,10 +2469,11 @@ fn guardrail_timeout_converts_slow_success_to_fallback() {
     semantic_config.guardrails.max_retries = 0;
 
     let adapter = SequenceObserverAdapter {
-        scripted_results: RefCell::new(vec![Ok(ObserverOutput {
+        scripted_results: Mutex::new(vec![Ok(ObserverOutput {
             summary: "too slow".to_string(),
             key_points: vec![],
             citations: vec![],
+            ..ObserverOutput::default()
         })]),
         delay_ms: 20,
     };
@@ -1233,6 +2505,55 @@ fn guardrail_timeout_converts_slow_success_to_fallback() {
     assert_eq!(provenance[1].runtime, SemanticRuntime::Deterministic);
 }
 
+#[test]
+fn guardrail_timeout_reads_latency_from_the_injected_clock() {
+    let store = MindStore::open_in_memory().expect("open");
+    insert_t0(
+        &store,
+        "e1",
+        "conv-clock-timeout",
+        ts(16, 18, 0),
+        "provider latency is measured on the mock clock",
+    );
+
+    let distill_config = DistillationConfig {
+        enable_attribution: false,
+        t2_trigger_tokens: 9_999,
+        ..DistillationConfig::default()
+    };
+
+    let mut semantic_config = SemanticObserverConfig::default();
+    semantic_config.guardrails.timeout_ms = 5_000;
+    semantic_config.guardrails.max_retries = 0;
+
+    let clock = Arc::new(aoc_storage::MockClock::new(ts(16, 18, 30)));
+    let adapter = ClockAdvancingObserverAdapter {
+        clock: clock.clone(),
+        latency: chrono::Duration::seconds(6),
+    };
+    let mut sidecar = SessionObserverSidecar::new(distill_config, semantic_config, adapter)
+        .with_clock(clock.clone());
+
+    let now = ts(16, 18, 30);
+    sidecar.enqueue_turn("session-clock-timeout", "conv-clock-timeout", now);
+    let outcomes = sidecar.run_ready(&store, now + chrono::Duration::milliseconds(300));
+    assert_eq!(outcomes.len(), 1);
+    outcomes[0].report.as_ref().expect("report");
+
+    let artifacts = store
+        .artifacts_for_conversation("conv-clock-timeout")
+        .expect("artifacts");
+    let provenance = store
+        .semantic_provenance_for_artifact(&artifacts[0].artifact_id)
+        .expect("provenance");
+    assert_eq!(
+        provenance[0].failure_kind,
+        Some(SemanticFailureKind::Timeout)
+    );
+    assert_eq!(provenance[0].latency_ms, Some(6_000));
+    assert!(provenance[0].fallback_used);
+}
+
 #[test]
 fn manual_trigger_runs_immediately_and_is_reported() {
     let store = MindStore::open_in_memory().expect("open");
@@ -1253,6 +2574,7 @@ fn manual_trigger_runs_immediately_and_is_reported() {
             summary: "manual semantic run".to_string(),
             key_points: vec!["fast path".to_string()],
             citations: vec![],
+            ..ObserverOutput::default()
         }),
     };
     let mut sidecar =
@@ -1290,6 +2612,7 @@ fn task_completed_trigger_upgrades_pending_turn_trigger() {
             summary: "task complete semantic run".to_string(),
             key_points: vec![],
             citations: vec![],
+            ..ObserverOutput::default()
         }),
     };
     let mut sidecar =
@@ -1310,10 +2633,193 @@ fn task_completed_trigger_upgrades_pending_turn_trigger() {
 }
 
 #[test]
-fn session_sidecar_backfills_branch_conversations_within_same_session_tree() {
-    let store = MindStore::open_in_memory().expect("open");
-
-    let mut root = raw_message(
+fn session_tree_scope_reflects_across_branch_conversations() {
+    let seed = || {
+        let store = MindStore::open_in_memory().expect("open");
+        for (event_id, conversation_id, parent, sec, text) in [
+            (
+                "e-root",
+                "conv-root",
+                None,
+                0,
+                "root conversation plans the parser refactor",
+            ),
+            (
+                "e-branch",
+                "conv-branch",
+                Some("conv-root"),
+                1,
+                "branch conversation retries the parser refactor",
+            ),
+        ] {
+            let mut raw = raw_message(event_id, conversation_id, ts(16, 40, sec), text);
+            raw.attrs = canonical_lineage_attrs(&ConversationLineageMetadata {
+                session_id: "session-tree".to_string(),
+                parent_conversation_id: parent.map(str::to_string),
+                root_conversation_id: "conv-root".to_string(),
+            });
+            store.insert_raw_event(&raw).expect("insert raw");
+            let compact = compact_raw_event_to_t0(&raw, &T0CompactionPolicy::default())
+                .expect("compact")
+                .expect("kept");
+            store.upsert_t0_compact_event(&compact).expect("insert t0");
+        }
+        store
+    };
+    let distill = |store: &MindStore, t2_trigger_tokens: u32, t2_scope: ReflectionScope| {
+        let distiller = DeterministicDistiller::new(DistillationConfig {
+            enable_attribution: false,
+            t2_trigger_tokens,
+            t2_scope,
+            ..DistillationConfig::default()
+        });
+        ["conv-root", "conv-branch"]
+            .iter()
+            .map(|conversation_id| {
+                distiller
+                    .distill_conversation(store, conversation_id)
+                    .expect("distill")
+                    .t2_artifacts_written
+            })
+            .collect::<Vec<_>>()
+    };
+    let artifacts = |store: &MindStore, conversation_id: &str, kind: &str| {
+        store
+            .artifacts_for_conversation(conversation_id)
+            .expect("artifacts")
+            .into_iter()
+            .filter(|artifact| artifact.kind == kind)
+            .collect::<Vec<_>>()
+    };
+
+    // Size the trigger so neither conversation reaches it alone.
+    let probe = seed();
+    distill(&probe, 9_999, ReflectionScope::Conversation);
+    let observations = [
+        artifacts(&probe, "conv-root", "t1"),
+        artifacts(&probe, "conv-branch", "t1"),
+    ]
+    .concat();
+    let trigger = observations
+        .iter()
+        .map(|artifact| estimate_tokens(&artifact.text))
+        .sum::<u32>();
+
+    let per_conversation = seed();
+    assert_eq!(
+        distill(&per_conversation, trigger, ReflectionScope::Conversation),
+        vec![0, 0]
+    );
+
+    let session = seed();
+    assert_eq!(
+        distill(&session, trigger, ReflectionScope::SessionTree),
+        vec![0, 1]
+    );
+    assert!(artifacts(&session, "conv-branch", "t2").is_empty());
+    let reflections = artifacts(&session, "conv-root", "t2");
+    assert_eq!(reflections.len(), 1);
+    let mut expected = observations
+        .iter()
+        .map(|artifact| artifact.artifact_id.clone())
+        .collect::<Vec<_>>();
+    expected.sort();
+    let mut traced = reflections[0].trace_ids.clone();
+    traced.sort();
+    assert_eq!(traced, expected);
+}
+
+#[test]
+fn session_tree_scope_leaves_no_stale_reflection_after_a_new_observation() {
+    let insert = |store: &MindStore, event_id: &str, conversation_id: &str, sec, text| {
+        let mut raw = raw_message(event_id, conversation_id, ts(16, 41, sec), text);
+        raw.attrs = canonical_lineage_attrs(&ConversationLineageMetadata {
+            session_id: "session-rerun".to_string(),
+            parent_conversation_id: (conversation_id != "conv-root")
+                .then(|| "conv-root".to_string()),
+            root_conversation_id: "conv-root".to_string(),
+        });
+        store.insert_raw_event(&raw).expect("insert raw");
+        let compact = compact_raw_event_to_t0(&raw, &T0CompactionPolicy::default())
+            .expect("compact")
+            .expect("kept");
+        store.upsert_t0_compact_event(&compact).expect("insert t0");
+    };
+    let seed = || {
+        let store = MindStore::open_in_memory().expect("open");
+        insert(
+            &store,
+            "e-root",
+            "conv-root",
+            0,
+            "root conversation plans the parser refactor across every module of the workspace",
+        );
+        insert(
+            &store,
+            "e-branch",
+            "conv-branch",
+            1,
+            "branch retries the parser refactor",
+        );
+        store
+    };
+    let artifacts = |store: &MindStore, conversation_id: &str, kind: &str| {
+        store
+            .artifacts_for_conversation(conversation_id)
+            .expect("artifacts")
+            .into_iter()
+            .filter(|artifact| artifact.kind == kind)
+            .collect::<Vec<_>>()
+    };
+    let distill = |store: &MindStore, t2_trigger_tokens: u32, conversation_ids: &[&str]| {
+        let distiller = DeterministicDistiller::new(DistillationConfig {
+            enable_attribution: false,
+            t2_trigger_tokens,
+            t2_scope: ReflectionScope::SessionTree,
+            ..DistillationConfig::default()
+        });
+        for conversation_id in conversation_ids {
+            distiller
+                .distill_conversation(store, conversation_id)
+                .expect("distill");
+        }
+    };
+
+    // A trigger that splits root and branch, leaving the branch chunk short.
+    let probe = seed();
+    distill(&probe, 9_999, &["conv-root", "conv-branch"]);
+    let tokens =
+        |conversation_id| estimate_tokens(&artifacts(&probe, conversation_id, "t1")[0].text);
+    let root_tokens = tokens("conv-root");
+    let trigger = root_tokens + tokens("conv-branch") - 1;
+
+    let store = seed();
+    distill(&store, trigger, &["conv-root", "conv-branch"]);
+    assert_eq!(artifacts(&store, "conv-root", "t2").len(), 2);
+
+    insert(&store, "e-branch-2", "conv-branch", 2, "ok");
+    distill(&store, trigger, &["conv-branch"]);
+    let branch_t1 = artifacts(&store, "conv-branch", "t1");
+    assert_eq!(branch_t1.len(), 2);
+    assert!(estimate_tokens(&branch_t1[1].text) < root_tokens);
+
+    let reflections = artifacts(&store, "conv-root", "t2");
+    assert_eq!(reflections.len(), 2, "a superseded chunk was left behind");
+    let mut traced = reflections
+        .iter()
+        .flat_map(|reflection| reflection.trace_ids.clone())
+        .collect::<Vec<_>>();
+    let total = traced.len();
+    traced.sort();
+    traced.dedup();
+    assert_eq!(traced.len(), total, "an observation is reflected twice");
+}
+
+#[test]
+fn session_sidecar_backfills_branch_conversations_within_same_session_tree() {
+    let store = MindStore::open_in_memory().expect("open");
+
+    let mut root = raw_message(
         "e-root",
         "conv-root",
         ts(16, 35, 0),
@@ -1357,6 +2863,7 @@ fn session_sidecar_backfills_branch_conversations_within_same_session_tree() {
             summary: "semantic observer summary".to_string(),
             key_points: vec!["point".to_string()],
             citations: vec![],
+            ..ObserverOutput::default()
         }),
     };
     let mut sidecar =
@@ -1414,10 +2921,11 @@ fn observer_feed_event_maps_trigger_and_fallback_metadata() {
     semantic_config.guardrails.max_retries = 0;
 
     let adapter = SequenceObserverAdapter {
-        scripted_results: RefCell::new(vec![Ok(ObserverOutput {
+        scripted_results: Mutex::new(vec![Ok(ObserverOutput {
             summary: "slow semantic output".to_string(),
             key_points: vec![],
             citations: vec![],
+            ..ObserverOutput::default()
         })]),
         delay_ms: 20,
     };
@@ -1439,6 +2947,7 @@ fn observer_feed_event_maps_trigger_and_fallback_metadata() {
     assert_eq!(event.runtime.as_deref(), Some("deterministic"));
     assert_eq!(event.attempt_count, Some(2));
     assert_eq!(event.failure_kind.as_deref(), Some("timeout"));
+    assert_eq!(event.error_code, Some(aoc_core::ErrorCode::Timeout));
 
     let progress = event.progress.expect("mind progress");
     assert_eq!(progress.t1_target_tokens, expected_target_tokens);
@@ -1491,6 +3000,7 @@ fn process_reflector_job_materializes_t2_reflection() {
         last_error: None,
         created_at: ts(16, 55, 2),
         updated_at: ts(16, 55, 2),
+        retry_after: None,
     };
 
     process_reflector_job(&store, &job, ts(16, 55, 3)).expect("process reflector job");
@@ -1509,6 +3019,139 @@ fn process_reflector_job_materializes_t2_reflection() {
     assert!(reflection.trace_ids.contains(&"obs-r2".to_string()));
 }
 
+struct StaticReflectorInvoker {
+    raw: String,
+}
+
+impl PiReflectorInvoker for StaticReflectorInvoker {
+    fn invoke_reflector(
+        &self,
+        canonical_input_json: &str,
+        _profile: &SemanticModelProfile,
+        _guardrails: &SemanticGuardrails,
+    ) -> Result<String, SemanticAdapterError> {
+        assert!(canonical_input_json.contains("first observation for reflector"));
+        Ok(self.raw.clone())
+    }
+}
+
+fn reflector_job_fixture(store: &MindStore) -> ReflectorJob {
+    for (id, second, text) in [
+        ("obs-r1", 0, "first observation for reflector"),
+        ("obs-r2", 1, "second observation for reflector"),
+    ] {
+        store
+            .insert_observation(id, "conv-reflector", ts(16, 55, second), text, &[])
+            .expect("observation");
+    }
+    ReflectorJob {
+        job_id: "rj-1".to_string(),
+        active_tag: "mind".to_string(),
+        observation_ids: vec!["obs-r1".to_string(), "obs-r2".to_string()],
+        conversation_ids: vec!["conv-reflector".to_string()],
+        estimated_tokens: 120,
+        status: ReflectorJobStatus::Pending,
+        claimed_by: None,
+        claimed_at: None,
+        attempts: 0,
+        last_error: None,
+        created_at: ts(16, 55, 2),
+        updated_at: ts(16, 55, 2),
+        retry_after: None,
+    }
+}
+
+#[test]
+fn semantic_reflector_writes_t2_with_semantic_provenance() {
+    let store = MindStore::open_in_memory().expect("open");
+    let job = reflector_job_fixture(&store);
+    let adapter = PiReflectorAdapter::new(StaticReflectorInvoker {
+        raw: r#"{"reflection":"reflector consolidated both","action_items":["ship it"],"citations":["obs-r1"]}"#
+            .to_string(),
+    });
+    SemanticReflectorDistiller::new(SemanticReflectorConfig::default(), adapter)
+        .process_reflector_job(&store, &job, ts(16, 55, 3))
+        .expect("process reflector job");
+
+    let reflection = store
+        .artifacts_for_conversation("conv-reflector")
+        .expect("artifacts")
+        .into_iter()
+        .find(|artifact| artifact.kind == "t2")
+        .expect("t2 reflection written");
+    assert!(reflection.artifact_id.starts_with("ref:auto:"));
+    assert!(reflection.text.starts_with("reflector consolidated both"));
+    assert!(reflection.text.contains("- ship it"));
+    assert_eq!(reflection.trace_ids, vec!["obs-r1", "obs-r2"]);
+
+    let provenance = store
+        .semantic_provenance_for_artifact(&reflection.artifact_id)
+        .expect("provenance");
+    assert_eq!(provenance.len(), 1);
+    assert_eq!(provenance[0].stage, SemanticStage::T2Reflector);
+    assert_eq!(provenance[0].runtime, SemanticRuntime::PiSemantic);
+    assert_eq!(provenance[0].prompt_version, "pi.reflector.v1");
+    assert!(!provenance[0].fallback_used);
+    let usage = store.semantic_usage_report(None).expect("usage");
+    assert_eq!(usage.total.calls, 1);
+}
+
+#[test]
+fn semantic_reflector_failure_falls_back_to_deterministic_t2() {
+    let store = MindStore::open_in_memory().expect("open");
+    let job = reflector_job_fixture(&store);
+    SemanticReflectorDistiller::new(
+        SemanticReflectorConfig::default(),
+        PiReflectorAdapter::default(),
+    )
+    .process_reflector_job(&store, &job, ts(16, 55, 3))
+    .expect("process reflector job");
+
+    let reflection = store
+        .artifacts_for_conversation("conv-reflector")
+        .expect("artifacts")
+        .into_iter()
+        .find(|artifact| artifact.kind == "t2")
+        .expect("t2 reflection written");
+    assert!(reflection
+        .text
+        .contains("T2 runtime reflection for tag=mind observations=2"));
+
+    let provenance = store
+        .semantic_provenance_for_artifact(&reflection.artifact_id)
+        .expect("provenance");
+    assert_eq!(provenance.len(), 2);
+    assert_eq!(provenance[0].runtime, SemanticRuntime::PiSemantic);
+    assert_eq!(
+        provenance[0].failure_kind,
+        Some(SemanticFailureKind::ProviderError)
+    );
+    assert_eq!(provenance[0].attempt_count, 2);
+    assert_eq!(provenance[1].runtime, SemanticRuntime::Deterministic);
+    assert_eq!(provenance[1].stage, SemanticStage::T2Reflector);
+    assert!(provenance[1].fallback_used);
+
+    let mut semantic = SemanticReflectorConfig::default();
+    semantic.guardrails.max_budget_tokens = 4;
+    let other = MindStore::open_in_memory().expect("open");
+    let job = reflector_job_fixture(&other);
+    SemanticReflectorDistiller::new(semantic, PiReflectorAdapter::default())
+        .process_reflector_job(&other, &job, ts(16, 55, 3))
+        .expect("budget fallback");
+    let provenance = other
+        .semantic_provenance_for_artifact(&reflection.artifact_id)
+        .expect("provenance");
+    assert_eq!(
+        provenance[0].failure_kind,
+        Some(SemanticFailureKind::BudgetExceeded)
+    );
+    let usage = other.semantic_usage_report(None).expect("usage");
+    assert_eq!(
+        usage.total.calls, 0,
+        "budget preflight never calls the provider"
+    );
+}
+
 #[test]
 fn process_t3_backlog_job_updates_canon_and_watermark() {
     let store = MindStore::open_in_memory().expect("open");
@@ -2213,3 +3856,1519 @@ fn finalize_planner_selects_new_t1_t2_artifacts_after_watermark() {
     assert_eq!(plan.slice_end_id, "ref-new");
     assert_eq!(plan.artifact_ids, vec!["obs-new", "ref-new"]);
 }
+
+#[test]
+fn artifact_export_writes_chunks_and_resumes_from_state() {
+    let store = MindStore::open_in_memory().expect("open store");
+    for (idx, sec) in [1, 2, 3].into_iter().enumerate() {
+        store
+            .insert_observation(
+                &format!("obs-export-{idx}"),
+                "conv-export",
+                ts(9, 0, sec),
+                &format!("export observation {idx}"),
+                &[],
+            )
+            .expect("observation");
+    }
+    store
+        .insert_observation("obs-other", "conv-other", ts(9, 0, 0), "other", &[])
+        .expect("other observation");
+
+    let out_dir = temp_project_root("artifact-export");
+    let scope = ArtifactExportScope::Conversation("conv-export".to_string());
+    let options = ArtifactExportOptions { chunk_size: 2 };
+    let first = export_artifacts(
+        &store,
+        &scope,
+        ArtifactExportFormat::Jsonl,
+        &out_dir,
+        options,
+    )
+    .expect("first export");
+    assert!(!first.resumed);
+    assert_eq!(first.artifacts_exported, 3);
+    assert_eq!(first.chunks_written.len(), 2);
+    let first_chunk =
+        std::fs::read_to_string(out_dir.join("artifacts-00000.jsonl")).expect("read first chunk");
+    let ids = first_chunk
+        .lines()
+        .map(|line| {
+            serde_json::from_str::<serde_json::Value>(line).expect("jsonl record")["artifact_id"]
+                .as_str()
+                .expect("artifact id")
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["obs-export-0", "obs-export-1"]);
+
+    store
+        .insert_observation(
+            "obs-export-3",
+            "conv-export",
+            ts(9, 0, 4),
+            "late observation",
+            &[],
+        )
+        .expect("late observation");
+    let resumed = export_artifacts(
+        &store,
+        &scope,
+        ArtifactExportFormat::Jsonl,
+        &out_dir,
+        options,
+    )
+    .expect("resumed export");
+    assert!(resumed.resumed);
+    assert_eq!(resumed.chunks_skipped, 2);
+    assert_eq!(resumed.artifacts_exported, 1);
+    assert_eq!(
+        resumed.chunks_written,
+        vec![out_dir.join("artifacts-00002.jsonl")]
+    );
+
+    let mismatch = export_artifacts(
+        &store,
+        &scope,
+        ArtifactExportFormat::Markdown,
+        &out_dir,
+        options,
+    );
+    assert!(matches!(
+        mismatch,
+        Err(ArtifactExportError::StateMismatch(_))
+    ));
+
+    let markdown_dir = out_dir.join("markdown");
+    export_artifacts(
+        &store,
+        &ArtifactExportScope::All,
+        ArtifactExportFormat::Markdown,
+        &markdown_dir,
+        ArtifactExportOptions::default(),
+    )
+    .expect("markdown export");
+    let markdown = std::fs::read_to_string(markdown_dir.join("artifacts-00000.md"))
+        .expect("read markdown chunk");
+    assert!(markdown.starts_with("# Artifact export: all artifacts (chunk 0)"));
+    assert!(markdown.contains("## obs-other [t1] conv-other"));
+
+    #[cfg(feature = "parquet")]
+    {
+        let parquet_dir = out_dir.join("parquet");
+        export_artifacts(
+            &store,
+            &scope,
+            ArtifactExportFormat::Parquet,
+            &parquet_dir,
+            options,
+        )
+        .expect("parquet export");
+        let bytes = std::fs::read(parquet_dir.join("artifacts-00000.parquet")).expect("parquet");
+        assert!(bytes.starts_with(b"PAR1") && bytes.ends_with(b"PAR1"));
+    }
+
+    let _ = std::fs::remove_dir_all(&out_dir);
+}
+
+#[test]
+fn vault_export_links_notes_and_refreshes_incrementally() {
+    let store = MindStore::open_in_memory().expect("open store");
+    store
+        .insert_observation(
+            "obs:vault",
+            "conv-vault",
+            ts(9, 0, 1),
+            "parser retries",
+            &[],
+        )
+        .expect("observation");
+    store
+        .insert_reflection(
+            "ref:vault",
+            "conv-vault",
+            ts(9, 0, 2),
+            "retry budget needs a cap",
+            &["obs:vault".to_string(), "evt-missing".to_string()],
+        )
+        .expect("reflection");
+    store
+        .upsert_canon_entry_revision(
+            "canon:retries",
+            Some("parser"),
+            "cap parser retries at three",
+            8_000,
+            90,
+            None,
+            &["ref:vault".to_string()],
+            ts(9, 0, 3),
+        )
+        .expect("canon");
+    store
+        .insert_mem_decision(&aoc_storage::MemDecision {
+            decision_id: "dec:1".to_string(),
+            ts: ts(9, 0, 4),
+            project_id: "project:/repo".to_string(),
+            segment_id: Some("core".to_string()),
+            text: "keep retries synchronous".to_string(),
+            supersedes_id: None,
+        })
+        .expect("decision");
+
+    let out_dir = temp_project_root("vault-export");
+    let options = VaultExportOptions {
+        project_id: "project:/repo".to_string(),
+        full: false,
+    };
+    let first = export_vault(&store, &out_dir, &options).expect("first vault export");
+    assert!(!first.incremental);
+    assert_eq!(
+        (
+            first.observations,
+            first.reflections,
+            first.canon_revisions,
+            first.decisions
+        ),
+        (1, 1, 1, 1)
+    );
+    let reflection =
+        std::fs::read_to_string(out_dir.join("reflections/ref-vault.md")).expect("reflection note");
+    assert!(reflection.starts_with("---\naoc_id: \"ref:vault\"\n"));
+    assert!(reflection.contains("tags: [\"aoc/reflection\"]"));
+    assert!(reflection.contains("- [[observations/obs-vault|obs:vault]]"));
+    assert!(reflection.contains("- `evt-missing`"));
+    let canon =
+        std::fs::read_to_string(out_dir.join("canon/canon-retries-r1.md")).expect("canon note");
+    assert!(canon.contains("tags: [\"aoc/canon\",\"topic/parser\"]"));
+    assert!(canon.contains("- [[reflections/ref-vault|ref:vault]]"));
+    let decision =
+        std::fs::read_to_string(out_dir.join("decisions/dec-1.md")).expect("decision note");
+    assert!(decision.contains("segments: [\"core\"]"));
+
+    store
+        .insert_mem_decision(&aoc_storage::MemDecision {
+            decision_id: "dec:2".to_string(),
+            ts: ts(9, 0, 5),
+            project_id: "project:/repo".to_string(),
+            segment_id: None,
+            text: "make retries async after all".to_string(),
+            supersedes_id: Some("dec:1".to_string()),
+        })
+        .expect("superseding decision");
+    let second = export_vault(&store, &out_dir, &options).expect("incremental vault export");
+    assert!(second.incremental);
+    assert_eq!(
+        second.notes_written,
+        vec![out_dir.join("decisions/dec-2.md")]
+    );
+    let superseding =
+        std::fs::read_to_string(out_dir.join("decisions/dec-2.md")).expect("decision note");
+    assert!(superseding.contains("Supersedes: [[decisions/dec-1|dec:1]]"));
+
+    let other_project = export_vault(
+        &store,
+        &out_dir,
+        &VaultExportOptions {
+            project_id: "project:/other".to_string(),
+            full: false,
+        },
+    );
+    assert!(matches!(
+        other_project,
+        Err(ArtifactExportError::StateMismatch(_))
+    ));
+
+    let _ = std::fs::remove_dir_all(&out_dir);
+}
+
+#[test]
+fn star_schema_export_models_facts_dimensions_and_spans() {
+    use aoc_core::mind_contracts::{
+        ArtifactTaskLink, ArtifactTaskRelation, RouteOrigin, SegmentCandidate, SegmentRoute,
+    };
+
+    let store = MindStore::open_in_memory().expect("open store");
+    for (event_id, at, text) in [
+        ("evt-an-1", ts(10, 0, 0), "start the parser work"),
+        ("evt-an-2", ts(10, 30, 0), "parser work is done"),
+    ] {
+        store
+            .insert_raw_event(&raw_message(event_id, "conv-an", at, text))
+            .expect("raw event");
+        insert_t0(&store, event_id, "conv-an", at, text);
+    }
+    let compact_ids = store
+        .t0_events_for_conversation("conv-an")
+        .expect("t0 events")
+        .into_iter()
+        .map(|event| event.compact_id)
+        .collect::<Vec<_>>();
+    store
+        .insert_observation(
+            "obs-an",
+            "conv-an",
+            ts(10, 31, 0),
+            "parser done",
+            &compact_ids,
+        )
+        .expect("observation");
+    store
+        .insert_reflection(
+            "ref-an",
+            "conv-an",
+            ts(10, 40, 0),
+            "parser workstream closed",
+            &["obs-an".to_string()],
+        )
+        .expect("reflection");
+    store
+        .replace_segment_route(&SegmentRoute {
+            artifact_id: "obs-an".to_string(),
+            primary: SegmentCandidate::new("core".to_string(), 9_000).expect("primary"),
+            secondary: vec![SegmentCandidate::new("ui".to_string(), 1_000).expect("secondary")],
+            routed_by: RouteOrigin::Heuristic,
+            reason: "test".to_string(),
+            overridden_by: None,
+        })
+        .expect("route");
+    store
+        .upsert_artifact_task_link(
+            &ArtifactTaskLink::new(
+                "obs-an".to_string(),
+                "42".to_string(),
+                ArtifactTaskRelation::WorkedOn,
+                8_000,
+                vec!["evt-an-1".to_string()],
+                "test".to_string(),
+                ts(10, 0, 0),
+                None,
+            )
+            .expect("link"),
+        )
+        .expect("task link");
+
+    let sql = render_schema_sql();
+    for table in STAR_SCHEMA {
+        assert!(sql.contains(&format!(
+            "CREATE OR REPLACE VIEW {0} AS SELECT * FROM read_parquet('{0}.parquet');",
+            table.name
+        )));
+    }
+
+    let out_dir = temp_project_root("star-schema");
+    let result = export_star_schema(&store, &out_dir);
+    #[cfg(not(feature = "parquet"))]
+    assert!(matches!(
+        result,
+        Err(ArtifactExportError::UnsupportedFormat(_))
+    ));
+    #[cfg(feature = "parquet")]
+    {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+        use parquet::record::RowAccessor;
+
+        let report = result.expect("star schema export");
+        assert_eq!(
+            report.tables,
+            vec![
+                ("fact_events", 2),
+                ("fact_artifacts", 2),
+                ("fact_artifact_tasks", 1),
+                ("fact_artifact_segments", 2),
+                ("dim_conversations", 1),
+                ("dim_tasks", 1),
+                ("dim_segments", 2),
+            ]
+        );
+        assert!(out_dir.join("schema.sql").exists());
+
+        let file = std::fs::File::open(out_dir.join("fact_artifacts.parquet")).expect("open");
+        let reader = SerializedFileReader::new(file).expect("parquet reader");
+        let rows = reader
+            .get_row_iter(None)
+            .expect("rows")
+            .map(|row| {
+                let row = row.expect("row");
+                (
+                    row.get_string(0).expect("artifact_id").clone(),
+                    row.get_long(8).expect("span_seconds"),
+                    row.get_string(9).ok().cloned(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec![
+                ("obs-an".to_string(), 1_800, Some("core".to_string())),
+                ("ref-an".to_string(), 1_800, None),
+            ]
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(&out_dir);
+}
+
+#[test]
+fn knowledge_graph_links_artifacts_tasks_segments_and_supersession() {
+    use aoc_core::mind_contracts::{
+        ArtifactTaskLink, ArtifactTaskRelation, RouteOrigin, SegmentCandidate, SegmentRoute,
+    };
+
+    let store = MindStore::open_in_memory().expect("open store");
+    store
+        .insert_observation(
+            "obs-g",
+            "conv-g",
+            ts(11, 0, 0),
+            "graph \"quoted\" work",
+            &[],
+        )
+        .expect("observation");
+    store
+        .insert_reflection(
+            "ref-g",
+            "conv-g",
+            ts(11, 5, 0),
+            "graph reflection",
+            &["obs-g".to_string(), "obs-missing".to_string()],
+        )
+        .expect("reflection");
+    store
+        .insert_observation("obs-late", "conv-g", ts(15, 0, 0), "later work", &[])
+        .expect("late observation");
+    store
+        .replace_segment_route(&SegmentRoute {
+            artifact_id: "obs-g".to_string(),
+            primary: SegmentCandidate::new("core".to_string(), 9_000).expect("primary"),
+            secondary: Vec::new(),
+            routed_by: RouteOrigin::Heuristic,
+            reason: "test".to_string(),
+            overridden_by: None,
+        })
+        .expect("route");
+    store
+        .upsert_artifact_task_link(
+            &ArtifactTaskLink::new(
+                "obs-g".to_string(),
+                "7".to_string(),
+                ArtifactTaskRelation::Completed,
+                9_000,
+                Vec::new(),
+                "test".to_string(),
+                ts(11, 0, 0),
+                None,
+            )
+            .expect("link"),
+        )
+        .expect("task link");
+    store
+        .upsert_canon_entry_revision(
+            "canon:old",
+            None,
+            "old rule",
+            5_000,
+            50,
+            None,
+            &[],
+            ts(10, 0, 0),
+        )
+        .expect("old canon");
+    store
+        .upsert_canon_entry_revision(
+            "canon:new",
+            None,
+            "new rule",
+            9_000,
+            90,
+            Some("canon:old"),
+            &["ref-g".to_string()],
+            ts(11, 10, 0),
+        )
+        .expect("new canon");
+    for (decision_id, supersedes) in [("dec:a", None), ("dec:b", Some("dec:a"))] {
+        store
+            .insert_mem_decision(&aoc_storage::MemDecision {
+                decision_id: decision_id.to_string(),
+                ts: ts(11, 20, 0),
+                project_id: "project:/repo".to_string(),
+                segment_id: Some("core".to_string()),
+                text: format!("decision {decision_id}"),
+                supersedes_id: supersedes.map(str::to_string),
+            })
+            .expect("decision");
+    }
+
+    let graph = build_knowledge_graph(
+        &store,
+        &GraphScope {
+            project_id: Some("project:/repo".to_string()),
+            ..GraphScope::default()
+        },
+    )
+    .expect("graph");
+    let has_edge = |graph: &KnowledgeGraph, from: &str, to: &str, kind: GraphEdgeKind| {
+        graph
+            .edges
+            .iter()
+            .any(|edge| edge.from == from && edge.to == to && edge.kind == kind)
+    };
+    assert!(has_edge(
+        &graph,
+        "artifact:ref-g",
+        "artifact:obs-g",
+        GraphEdgeKind::Trace
+    ));
+    assert!(has_edge(
+        &graph,
+        "artifact:obs-g",
+        "conversation:conv-g",
+        GraphEdgeKind::InConversation
+    ));
+    assert!(has_edge(
+        &graph,
+        "artifact:obs-g",
+        "task:7",
+        GraphEdgeKind::WorkedOn
+    ));
+    assert!(has_edge(
+        &graph,
+        "artifact:obs-g",
+        "segment:core",
+        GraphEdgeKind::RoutedTo
+    ));
+    assert!(has_edge(
+        &graph,
+        "canon:canon:new",
+        "artifact:ref-g",
+        GraphEdgeKind::Trace
+    ));
+    assert!(has_edge(
+        &graph,
+        "canon:canon:new",
+        "canon:canon:old",
+        GraphEdgeKind::Supersedes
+    ));
+    assert!(has_edge(
+        &graph,
+        "decision:dec:b",
+        "decision:dec:a",
+        GraphEdgeKind::Supersedes
+    ));
+    assert!(graph
+        .edges
+        .iter()
+        .all(|edge| edge.to != "artifact:obs-missing"));
+    let worked_on = graph
+        .edges
+        .iter()
+        .find(|edge| edge.kind == GraphEdgeKind::WorkedOn)
+        .expect("worked_on edge");
+    assert_eq!(worked_on.attrs["relation"], "completed");
+
+    let dot = graph.render(GraphExportFormat::Dot);
+    assert!(dot.starts_with("digraph mind {"));
+    assert!(dot.contains("\"artifact:obs-g\" [label=\"graph \\\"quoted\\\" work\""));
+    assert!(dot.contains("\"artifact:ref-g\" -> \"artifact:obs-g\" [label=trace];"));
+    let graphml = graph.render(GraphExportFormat::Graphml);
+    assert!(graphml.contains("<data key=\"label\">graph &quot;quoted&quot; work</data>"));
+    assert_eq!(
+        graphml.matches("<edge ").count(),
+        graph.edges.len(),
+        "every edge rendered"
+    );
+    let json: KnowledgeGraph =
+        serde_json::from_str(&graph.render(GraphExportFormat::Json)).expect("json graph");
+    assert_eq!(json, graph);
+
+    let windowed = build_knowledge_graph(
+        &store,
+        &GraphScope {
+            since: Some(ts(14, 0, 0)),
+            ..GraphScope::default()
+        },
+    )
+    .expect("windowed graph");
+    let ids = windowed
+        .nodes
+        .iter()
+        .map(|node| node.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["artifact:obs-late", "conversation:conv-g"]);
+}
+
+#[test]
+fn replay_runs_in_shadow_and_reports_policy_diff() {
+    let store = MindStore::open_in_memory().expect("open store");
+    for (event_id, sec, text) in [
+        ("evt-replay-1", 1, "please wire the replay command"),
+        ("evt-replay-2", 2, "and print the diff report for operators"),
+    ] {
+        store
+            .insert_raw_event(&raw_message(event_id, "conv-replay", ts(10, 0, sec), text))
+            .expect("raw event");
+        insert_t0(&store, event_id, "conv-replay", ts(10, 0, sec), text);
+    }
+    DeterministicDistiller::new(DistillationConfig::default())
+        .distill_conversation(&store, "conv-replay")
+        .expect("live distill");
+    let live_artifacts = store
+        .artifacts_for_conversation("conv-replay")
+        .expect("live artifacts");
+
+    let baseline = replay_conversation(&store, "conv-replay", &ReplayPolicy::default())
+        .expect("baseline replay");
+    assert_eq!(baseline.raw_events, 2);
+    assert!(baseline.t0.is_identical() && baseline.t1.is_identical());
+    assert_eq!(baseline.t0.unchanged, 2);
+
+    let narrow: ReplayPolicy =
+        serde_json::from_str(r#"{"distillation": {"t1_output_max_chars": 40}}"#)
+            .expect("policy json");
+    let narrowed = replay_conversation(&store, "conv-replay", &narrow).expect("narrow replay");
+    assert!(narrowed.t0.is_identical());
+    assert_eq!(narrowed.t1.removed.len(), 1);
+    assert_eq!(narrowed.t1.added.len(), 1);
+    assert!(narrowed.shadow_texts[&narrowed.t1.added[0]].chars().count() <= 40);
+
+    let assistant_only = T0CompactionPolicy {
+        keep_roles: [ConversationRole::Assistant].into_iter().collect(),
+        ..T0CompactionPolicy::default()
+    };
+    let dropped = replay_conversation(
+        &store,
+        "conv-replay",
+        &ReplayPolicy {
+            compaction: Some(assistant_only),
+            ..ReplayPolicy::default()
+        },
+    )
+    .expect("dropped replay");
+    assert_eq!(dropped.t0.removed.len(), 2);
+    assert_eq!(dropped.t0.shadow, 0);
+    assert_eq!(dropped.t1.shadow, 0);
+
+    assert_eq!(
+        store
+            .artifacts_for_conversation("conv-replay")
+            .expect("live artifacts after replay"),
+        live_artifacts
+    );
+    assert!(matches!(
+        replay_conversation(&store, "conv-missing", &ReplayPolicy::default()),
+        Err(ReplayError::EmptyConversation(_))
+    ));
+}
+
+struct RecordedWebhookRequest {
+    url: String,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+#[derive(Default)]
+struct ScriptedWebhookTransport {
+    statuses: RefCell<Vec<Result<u16, String>>>,
+    requests: RefCell<Vec<RecordedWebhookRequest>>,
+}
+
+impl WebhookTransport for &ScriptedWebhookTransport {
+    fn post(&self, url: &str, headers: &[(&str, String)], body: &str) -> Result<u16, String> {
+        self.requests.borrow_mut().push(RecordedWebhookRequest {
+            url: url.to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+            body: body.to_string(),
+        });
+        let mut statuses = self.statuses.borrow_mut();
+        if statuses.is_empty() {
+            Ok(200)
+        } else {
+            statuses.remove(0)
+        }
+    }
+}
+
+fn webhook_event(kind: PipelineEventKind, subject: &str) -> PipelineEvent {
+    PipelineEvent {
+        kind,
+        subject: subject.to_string(),
+        at: ts(12, 0, 0),
+        detail: serde_json::json!({ "subject": subject }),
+    }
+}
+
+#[test]
+fn webhook_dispatcher_filters_signs_and_retries_server_errors() {
+    let secret_env = format!("AOC_TEST_WEBHOOK_SECRET_{}", std::process::id());
+    std::env::set_var(&secret_env, "hunter2");
+    let transport = ScriptedWebhookTransport::default();
+    transport.statuses.borrow_mut().extend([
+        Err("connection reset".to_string()),
+        Ok(503),
+        Ok(204),
+        Ok(400),
+    ]);
+    let dispatcher = WebhookDispatcher::with_transport(
+        vec![
+            WebhookEndpoint {
+                events: vec![PipelineEventKind::CanonRevised],
+                secret_env: Some(secret_env.clone()),
+                initial_backoff_ms: 0,
+                ..WebhookEndpoint::new("https://hooks.example/canon")
+            },
+            WebhookEndpoint {
+                initial_backoff_ms: 0,
+                ..WebhookEndpoint::new("https://hooks.example/all")
+            },
+        ],
+        &transport,
+    );
+
+    let event = webhook_event(PipelineEventKind::CanonRevised, "canon:1@2");
+    let deliveries = dispatcher.dispatch(&event);
+    assert_eq!(deliveries.len(), 2);
+    assert!(deliveries[0].delivered);
+    assert_eq!(deliveries[0].attempts, 3);
+    assert_eq!(deliveries[0].status, Some(204));
+    assert!(!deliveries[1].delivered, "4xx responses are not retried");
+    assert_eq!(deliveries[1].attempts, 1);
+    assert_eq!(deliveries[1].error.as_deref(), Some("HTTP 400"));
+
+    let requests = transport.requests.borrow();
+    let signed = &requests[0];
+    assert_eq!(signed.url, "https://hooks.example/canon");
+    let header = |name: &str| {
+        signed
+            .headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+    };
+    assert_eq!(
+        header(WEBHOOK_SIGNATURE_HEADER),
+        Some(webhook_signature(b"hunter2", &signed.body))
+    );
+    assert_eq!(
+        header(WEBHOOK_EVENT_HEADER).as_deref(),
+        Some("canon_revised")
+    );
+    assert_eq!(header(WEBHOOK_DELIVERY_HEADER), Some(event.delivery_id()));
+    assert!(requests[3]
+        .headers
+        .iter()
+        .all(|(key, _)| key != WEBHOOK_SIGNATURE_HEADER));
+    drop(requests);
+
+    let budget = webhook_event(PipelineEventKind::BudgetExhausted, "mind:2026-02-23");
+    let deliveries = dispatcher.dispatch(&budget);
+    assert_eq!(deliveries.len(), 1, "filtered endpoint skips other kinds");
+    assert_eq!(deliveries[0].url, "https://hooks.example/all");
+
+    std::env::remove_var(&secret_env);
+    let deliveries = dispatcher.dispatch(&event);
+    assert!(!deliveries[0].delivered);
+    assert_eq!(deliveries[0].attempts, 0, "unsigned delivery is refused");
+}
+
+#[test]
+fn pipeline_event_watcher_reports_dead_letters_canon_revisions_and_budgets_once() {
+    let store = MindStore::open_in_memory().expect("store");
+    let now = ts(12, 0, 0);
+    let mut watcher = PipelineEventWatcher::default();
+    store
+        .insert_observation("obs:1", "conv-1", now, "parser retries", &[])
+        .expect("observation");
+    let job_id = store
+        .enqueue_reflector_job(
+            "mind",
+            &["obs:1".to_string()],
+            &["conv-1".to_string()],
+            40,
+            now,
+        )
+        .expect("job");
+    store
+        .set_semantic_tag_budget("mind", 100, 0, now)
+        .expect("budget");
+    assert!(watcher.poll(&store, now).expect("prime").is_empty());
+
+    store
+        .apply_mind_job_action(
+            aoc_storage::MindJobQueue::Reflector,
+            &job_id,
+            aoc_storage::MindJobAction::DeadLetter,
+            now,
+        )
+        .expect("dead-letter");
+    store
+        .upsert_canon_entry_revision(
+            "canon:parser",
+            Some("parser"),
+            "Parser retries are bounded",
+            8_000,
+            9_000,
+            None,
+            &["obs:1".to_string()],
+            now,
+        )
+        .expect("canon");
+    store
+        .record_semantic_usage(&aoc_storage::SemanticUsageEntry {
+            artifact_id: "obs:1".to_string(),
+            attempt_count: 1,
+            tag: "mind".to_string(),
+            provider_name: None,
+            model_id: None,
+            input_tokens: 80,
+            output_tokens: 40,
+            cost_micros: 0,
+            fallback_used: false,
+            recorded_at: now,
+        })
+        .expect("usage");
+
+    let events = watcher.poll(&store, now).expect("changes");
+    assert_eq!(
+        events.iter().map(|event| event.kind).collect::<Vec<_>>(),
+        vec![
+            PipelineEventKind::JobDeadLettered,
+            PipelineEventKind::CanonRevised,
+            PipelineEventKind::BudgetExhausted,
+        ]
+    );
+    assert_eq!(events[0].subject, format!("reflector:{job_id}"));
+    assert_eq!(events[1].subject, "canon:parser@1");
+    assert_eq!(events[2].subject, "mind:2026-02-23");
+    assert!(watcher.poll(&store, now).expect("settled").is_empty());
+}
+
+#[test]
+fn pipeline_event_watcher_sends_one_digest_per_day_for_opted_in_chat_endpoints() {
+    use aoc_core::mind_contracts::{ArtifactTaskLink, ArtifactTaskRelation};
+
+    let store = MindStore::open_in_memory().expect("store");
+    let mut watcher = PipelineEventWatcher::default();
+    assert!(watcher.poll(&store, ts(9, 0, 0)).expect("prime").is_empty());
+
+    store
+        .insert_observation("obs:1", "conv-1", ts(10, 0, 0), "parser retries", &[])
+        .expect("observation");
+    store
+        .insert_reflection("ref:1", "conv-1", ts(11, 0, 0), "retries are bounded", &[])
+        .expect("reflection");
+    store
+        .upsert_canon_entry_revision(
+            "canon:parser",
+            Some("parser"),
+            "Parser retries are bounded",
+            8_000,
+            9_000,
+            None,
+            &["obs:1".to_string()],
+            ts(11, 30, 0),
+        )
+        .expect("canon");
+    store
+        .upsert_artifact_task_link(
+            &ArtifactTaskLink::new(
+                "obs:1".to_string(),
+                "149".to_string(),
+                ArtifactTaskRelation::Completed,
+                9_000,
+                vec![],
+                "task_signal".to_string(),
+                ts(12, 0, 0),
+                None,
+            )
+            .expect("link"),
+        )
+        .expect("task link");
+    let same_day = watcher.poll(&store, ts(23, 59, 0)).expect("same day");
+    assert!(
+        same_day
+            .iter()
+            .all(|event| event.kind != PipelineEventKind::DailyDigest),
+        "the digest waits for the day to end"
+    );
+
+    let next_morning = ts(0, 5, 0) + chrono::Duration::days(1);
+    let events = watcher.poll(&store, next_morning).expect("rollover");
+    assert_eq!(events.len(), 1);
+    let digest = &events[0];
+    assert_eq!(digest.kind, PipelineEventKind::DailyDigest);
+    assert_eq!(digest.subject, "digest:2026-02-23");
+    assert_eq!(digest.detail["reflections"], 1);
+    assert_eq!(digest.detail["canon_revisions"], 1);
+    assert_eq!(
+        digest.detail["completed_task_ids"],
+        serde_json::json!(["149"])
+    );
+    assert!(watcher
+        .poll(&store, next_morning)
+        .expect("settled")
+        .is_empty());
+
+    let mut slack = WebhookEndpoint::new("https://hooks.slack.com/services/T/B/x");
+    slack.format = WebhookFormat::Slack;
+    assert!(
+        !slack.accepts(PipelineEventKind::DailyDigest),
+        "digest is opt-in"
+    );
+    slack.events = vec![PipelineEventKind::DailyDigest];
+    assert!(slack.accepts(PipelineEventKind::DailyDigest));
+    let body: serde_json::Value =
+        serde_json::from_str(&slack.format.render(digest)).expect("slack body");
+    let text = body["text"].as_str().expect("slack text");
+    assert!(text.starts_with("AOC digest for 2026-02-23"), "{text}");
+    assert!(text.contains("1 task(s) completed: 149"), "{text}");
+
+    let mut dead_letter = webhook_event(PipelineEventKind::JobDeadLettered, "t3:job-9");
+    dead_letter.detail = serde_json::json!({
+        "queue": "t3",
+        "job_id": "job-9",
+        "active_tag": "mind",
+        "attempts": 3,
+        "last_error": "x".repeat(3_000),
+    });
+    let body: serde_json::Value =
+        serde_json::from_str(&WebhookFormat::Discord.render(&dead_letter)).expect("discord body");
+    let content = body["content"].as_str().expect("discord content");
+    assert!(content.starts_with("AOC: t3 job `job-9` dead-lettered after 3 attempt(s)"));
+    assert_eq!(
+        content.chars().count(),
+        2_000,
+        "truncated to Discord's limit"
+    );
+}
+
+#[test]
+fn nats_and_redis_stream_sinks_publish_pipeline_events() {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    fn read_line(reader: &mut BufReader<std::net::TcpStream>) -> String {
+        let mut line = String::new();
+        reader.read_line(&mut line).expect("read line");
+        line.trim_end().to_string()
+    }
+
+    // NATS: greet, acknowledge each PING, and capture PUB frames.
+    let nats = TcpListener::bind("127.0.0.1:0").expect("bind nats");
+    let nats_port = nats.local_addr().expect("addr").port();
+    let nats_server = thread::spawn(move || {
+        let (stream, _) = nats.accept().expect("accept");
+        let mut writer = stream.try_clone().expect("clone");
+        writer
+            .write_all(b"INFO {\"server_id\":\"test\"}\r\n")
+            .expect("info");
+        let mut reader = BufReader::new(stream);
+        let mut published = Vec::new();
+        let mut connect = String::new();
+        loop {
+            let line = read_line(&mut reader);
+            if line.is_empty() {
+                return (connect, published);
+            }
+            if line.starts_with("CONNECT ") {
+                connect = line;
+            } else if line == "PING" {
+                writer.write_all(b"PONG\r\n").expect("pong");
+            } else if let Some(rest) = line.strip_prefix("PUB ") {
+                let (subject, len) = rest.split_once(' ').expect("pub header");
+                let mut payload = vec![0; len.parse::<usize>().expect("len") + 2];
+                reader.read_exact(&mut payload).expect("payload");
+                payload.truncate(payload.len() - 2);
+                published.push((
+                    subject.to_string(),
+                    String::from_utf8(payload).expect("utf8"),
+                ));
+            }
+        }
+    });
+
+    // Redis: answer AUTH/SELECT with +OK and XADD with an entry id.
+    let redis = TcpListener::bind("127.0.0.1:0").expect("bind redis");
+    let redis_port = redis.local_addr().expect("addr").port();
+    let redis_server = thread::spawn(move || {
+        let (stream, _) = redis.accept().expect("accept");
+        let mut writer = stream.try_clone().expect("clone");
+        let mut reader = BufReader::new(stream);
+        let mut commands = Vec::new();
+        loop {
+            let header = read_line(&mut reader);
+            let Some(count) = header.strip_prefix('*') else {
+                return commands;
+            };
+            let mut args = Vec::new();
+            for _ in 0..count.parse::<usize>().expect("count") {
+                let len = read_line(&mut reader);
+                let mut arg = vec![0; len[1..].parse::<usize>().expect("len") + 2];
+                reader.read_exact(&mut arg).expect("arg");
+                arg.truncate(arg.len() - 2);
+                args.push(String::from_utf8(arg).expect("utf8"));
+            }
+            let reply: &[u8] = if args[0] == "XADD" {
+                b"$15\r\n1771848000000-0\r\n"
+            } else {
+                b"+OK\r\n"
+            };
+            writer.write_all(reply).expect("reply");
+            commands.push(args);
+        }
+    });
+
+    let token_env = format!("AOC_TEST_NATS_TOKEN_{}", std::process::id());
+    std::env::set_var(&token_env, "s3cret");
+    let configs = [
+        EventSinkConfig::Nats {
+            url: format!("nats://127.0.0.1:{nats_port}"),
+            subject: "ops.mind".to_string(),
+            events: Vec::new(),
+            token_env: Some(token_env.clone()),
+        },
+        EventSinkConfig::RedisStream {
+            url: format!("redis://127.0.0.1:{redis_port}/3"),
+            stream: DEFAULT_REDIS_STREAM.to_string(),
+            max_len: Some(1_000),
+            events: vec![PipelineEventKind::CanonRevised],
+            password_env: None,
+        },
+    ];
+    let mut sinks = configs
+        .iter()
+        .map(|config| config.build().expect("build sink"))
+        .collect::<Vec<_>>();
+
+    let canon = webhook_event(PipelineEventKind::CanonRevised, "canon:parser@2");
+    let budget = webhook_event(PipelineEventKind::BudgetExhausted, "mind:2026-02-23");
+    let mut deliveries = Vec::new();
+    for event in [&canon, &budget] {
+        for sink in sinks.iter_mut() {
+            deliveries.extend(sink.publish(event));
+        }
+    }
+    drop(sinks);
+    std::env::remove_var(&token_env);
+
+    assert_eq!(
+        deliveries
+            .iter()
+            .map(|delivery| (delivery.sink, delivery.kind, delivery.delivered))
+            .collect::<Vec<_>>(),
+        vec![
+            ("nats", PipelineEventKind::CanonRevised, true),
+            ("redis_stream", PipelineEventKind::CanonRevised, true),
+            ("nats", PipelineEventKind::BudgetExhausted, true),
+        ],
+        "redis filter skips the budget event: {deliveries:?}"
+    );
+
+    let (connect, published) = nats_server.join().expect("nats server");
+    assert!(connect.contains("\"auth_token\":\"s3cret\""), "{connect}");
+    assert_eq!(published.len(), 2, "one connection serves both events");
+    assert_eq!(published[0].0, "ops.mind.canon_revised");
+    assert_eq!(published[0].1, canon.body());
+    assert_eq!(published[1].0, "ops.mind.budget_exhausted");
+
+    let commands = redis_server.join().expect("redis server");
+    assert_eq!(commands[0], vec!["SELECT", "3"]);
+    let xadd = &commands[1];
+    assert_eq!(
+        &xadd[..6],
+        &["XADD", DEFAULT_REDIS_STREAM, "MAXLEN", "~", "1000", "*"]
+    );
+    assert_eq!(xadd[7], "canon_revised");
+    assert_eq!(xadd[9], canon.delivery_id());
+    assert_eq!(xadd.last(), Some(&canon.body()));
+}
+
+#[test]
+fn taskmaster_state_outranks_command_signals_for_attribution() {
+    let root = temp_project_root("taskmaster-watch");
+    let tasks_dir = root.join(".taskmaster/tasks");
+    std::fs::create_dir_all(&tasks_dir).expect("tasks dir");
+    let write_at = |path: PathBuf, body: serde_json::Value, at: DateTime<Utc>| {
+        std::fs::write(&path, body.to_string()).expect("write taskmaster file");
+        let file = std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .expect("open taskmaster file");
+        file.set_modified(at.into()).expect("set mtime");
+    };
+    let task = |id: &str, status: &str| serde_json::json!({ "id": id, "title": id, "description": "", "status": status });
+    write_at(
+        root.join(".taskmaster/state.json"),
+        serde_json::json!({ "currentTag": "mind" }),
+        ts(12, 0, 1),
+    );
+    write_at(
+        tasks_dir.join("tasks.json"),
+        serde_json::json!({
+            "mind": { "tasks": [task("7", "in-progress"), task("8", "pending")] },
+            "other": { "tasks": [task("99", "in-progress")] },
+        }),
+        ts(12, 0, 1),
+    );
+
+    let store = MindStore::open_in_memory().expect("store");
+    store
+        .append_context_state(&ConversationContextState {
+            conversation_id: "conv-tm".to_string(),
+            ts: ts(12, 0, 30),
+            active_tag: Some("other".to_string()),
+            active_tasks: vec!["99".to_string()],
+            lifecycle: Some("in-progress".to_string()),
+            signal_task_ids: vec!["99".to_string()],
+            signal_source: "task_lifecycle_command".to_string(),
+        })
+        .expect("command-derived context");
+    store
+        .insert_observation("obs-tm", "conv-tm", ts(12, 1, 0), "parser work", &[])
+        .expect("observation");
+
+    let mut watcher = TaskmasterWatcher::new(&root);
+    assert_eq!(watcher.poll(&store, ["conv-tm"]).expect("poll"), 1);
+    assert_eq!(
+        watcher.poll(&store, ["conv-tm"]).expect("poll again"),
+        0,
+        "unchanged files are not restamped"
+    );
+    let snapshot = watcher.snapshot().expect("snapshot");
+    assert_eq!(snapshot.active_tag, "mind");
+    assert_eq!(snapshot.in_progress, vec!["7".to_string()]);
+
+    aoc_task_attribution::TaskAttributionEngine::new(Default::default())
+        .attribute_conversation(&store, "conv-tm")
+        .expect("attribute");
+    let linked = store
+        .artifact_task_links_for_artifact("obs-tm")
+        .expect("links")
+        .into_iter()
+        .map(|link| link.task_id)
+        .collect::<std::collections::BTreeSet<_>>();
+    assert_eq!(
+        linked.into_iter().collect::<Vec<_>>(),
+        vec!["7".to_string()],
+        "taskmaster beats the later tm command"
+    );
+
+    write_at(
+        tasks_dir.join("tasks.json"),
+        serde_json::json!({ "mind": { "tasks": [task("7", "done"), task("8", "in-progress")] } }),
+        ts(12, 30, 0),
+    );
+    assert_eq!(watcher.poll(&store, ["conv-tm"]).expect("poll change"), 1);
+    let latest = store
+        .latest_context_state("conv-tm")
+        .expect("latest")
+        .expect("state");
+    assert_eq!(latest.signal_source, aoc_storage::TASKMASTER_SIGNAL_SOURCE);
+    assert_eq!(latest.lifecycle.as_deref(), Some("taskmaster_done"));
+    assert_eq!(latest.signal_task_ids, vec!["7".to_string()]);
+    assert_eq!(latest.active_tasks, vec!["8".to_string()]);
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[cfg(feature = "semantic")]
+#[test]
+fn gateway_invoker_sends_aliases_and_headers_and_prices_usage_from_the_listing() {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    // One request per connection: capture "METHOD path", headers, and body.
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind gateway");
+    let port = listener.local_addr().expect("addr").port();
+    let gateway = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for _ in 0..2 {
+            let (stream, _) = listener.accept().expect("accept");
+            let mut reader = BufReader::new(stream.try_clone().expect("clone"));
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).expect("request line");
+            let mut headers = Vec::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).expect("header");
+                let line = line.trim_end().to_string();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length: ") {
+                    content_length = len.parse().expect("length");
+                }
+                headers.push(line);
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).expect("body");
+            let request_line = request_line.trim_end().to_string();
+            let response = if request_line.starts_with("GET /api/v1/models") {
+                serde_json::json!({"data": [{
+                    "id": "vendor/observer-large",
+                    "pricing": {"prompt": "0.000002", "completion": "0.00001"},
+                }]})
+            } else {
+                let content = serde_json::json!({
+                    "summary": "gateway observed the parser fix",
+                    "key_points": ["parser"],
+                })
+                .to_string();
+                serde_json::json!({"choices": [{"message": {"content": content}}]})
+            }
+            .to_string();
+            write!(
+                &stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                response.len()
+            )
+            .expect("respond");
+            requests.push((
+                request_line,
+                headers,
+                String::from_utf8(body).expect("utf8"),
+            ));
+        }
+        requests
+    });
+
+    let store = MindStore::open_in_memory().expect("open");
+    insert_t0(
+        &store,
+        "e1",
+        "conv-gateway",
+        ts(16, 10, 0),
+        "fix the flaky parser test before the release",
+    );
+    let distill_config = DistillationConfig {
+        enable_attribution: false,
+        ..Default::default()
+    };
+    let mut semantic = SemanticObserverConfig::default();
+    semantic.profile.model_id = "observer".to_string();
+    let gateway_config = GatewayConfig {
+        base_url: format!("http://127.0.0.1:{port}/api/v1/"),
+        api_key_env: None,
+        headers: [("X-Title".to_string(), "aoc".to_string())].into(),
+        model_aliases: [("observer".to_string(), "vendor/observer-large".to_string())].into(),
+        costs: Default::default(),
+        costs_path: DEFAULT_GATEWAY_COSTS_PATH.to_string(),
+        fetch_costs: true,
+    };
+    semantic.gateway = Some(gateway_config.clone());
+    let distiller = SemanticObserverDistiller::new(
+        distill_config,
+        semantic,
+        PiObserverAdapter::new(GatewayObserverInvoker::new(gateway_config)),
+    );
+    distiller
+        .distill_conversation(&store, "conv-gateway")
+        .expect("distill");
+
+    let artifacts = store
+        .artifacts_for_conversation("conv-gateway")
+        .expect("artifacts");
+    assert!(artifacts[0]
+        .text
+        .contains("gateway observed the parser fix"));
+    let provenance = store
+        .semantic_provenance_for_artifact(&artifacts[0].artifact_id)
+        .expect("provenance");
+    assert!(!provenance[0].fallback_used);
+
+    let requests = gateway.join().expect("gateway thread");
+    let (chat_line, chat_headers, chat_body) = &requests[0];
+    assert_eq!(chat_line, "POST /api/v1/chat/completions HTTP/1.1");
+    assert!(chat_headers.iter().any(|header| header == "X-Title: aoc"));
+    let chat_body: serde_json::Value = serde_json::from_str(chat_body).expect("chat body");
+    assert_eq!(chat_body["model"], "vendor/observer-large");
+    assert!(requests[1].0.starts_with("GET /api/v1/models"));
+
+    // 2 and 10 USD per million tokens, far below the flat estimate.
+    let usage = store.semantic_usage_report(None).expect("usage report");
+    assert_eq!(usage.total.calls, 1);
+    assert!(usage.total.cost_micros > 0);
+    assert!(usage.total.cost_micros <= usage.total.tokens * 10);
+}
+
+#[test]
+fn memory_tool_shim_serves_notes_decisions_canon_and_recall_from_the_store() {
+    let store = MindStore::open_in_memory().expect("open");
+    let root = temp_project_root("memory-tool");
+    let shim = MemoryToolShim::new(&store, &root, "Claude");
+    let run = |command: serde_json::Value, at: DateTime<Utc>| {
+        shim.execute(serde_json::from_value(command).expect("command"), at)
+    };
+
+    run(
+        serde_json::json!({"command": "create", "path": "/memories/notes/parser.md", "file_text": "parser flakes on CRLF\nuse the fixture"}),
+        ts(9, 0, 0),
+    )
+    .expect("create");
+    run(
+        serde_json::json!({"command": "str_replace", "path": "/memories/notes/parser.md", "old_str": "CRLF", "new_str": "CRLF input"}),
+        ts(9, 1, 0),
+    )
+    .expect("str_replace");
+    run(
+        serde_json::json!({"command": "insert", "path": "/memories/notes/parser.md", "insert_line": 1, "insert_text": "seen on windows runners\n"}),
+        ts(9, 2, 0),
+    )
+    .expect("insert");
+    let view = run(
+        serde_json::json!({"command": "view", "path": "/memories/notes/parser.md", "view_range": [2, -1]}),
+        ts(9, 3, 0),
+    )
+    .expect("view");
+    assert!(view.contains("     2\tseen on windows runners"), "{view}");
+    assert!(view.contains("     3\tuse the fixture"), "{view}");
+    assert!(!view.contains("CRLF input"), "{view}");
+
+    let conversation_id = "memory:/memories/notes/parser.md";
+    let notes = store
+        .artifacts_for_conversation(conversation_id)
+        .expect("notes");
+    assert_eq!(notes.len(), 1, "older revisions are archived");
+    assert!(notes[0].text.starts_with("parser flakes on CRLF input"));
+    let raw = store
+        .raw_events_for_conversation(conversation_id)
+        .expect("raw");
+    assert_eq!(raw.len(), 3);
+    assert_eq!(notes[0].trace_ids, vec![raw[2].event_id.clone()]);
+    assert_eq!(raw[0].agent_id, "claude");
+    assert_eq!(raw[0].attrs[MEMORY_TOOL_ATTR]["command"], "create");
+    assert_eq!(
+        store
+            .agent("claude")
+            .expect("agent")
+            .map(|agent| agent.kind),
+        Some(MEMORY_TOOL_AGENT_KIND.to_string())
+    );
+
+    run(
+        serde_json::json!({"command": "rename", "old_path": "/memories/notes/parser.md", "new_path": "/memories/parser.md"}),
+        ts(9, 4, 0),
+    )
+    .expect("rename");
+    let listing = run(
+        serde_json::json!({"command": "view", "path": "/memories"}),
+        ts(9, 5, 0),
+    )
+    .expect("list");
+    assert!(listing.contains("- /memories/parser.md"), "{listing}");
+    assert!(!listing.contains("notes/parser.md"), "{listing}");
+    assert!(store
+        .artifacts_for_conversation(conversation_id)
+        .expect("old notes")
+        .is_empty());
+    run(
+        serde_json::json!({"command": "delete", "path": "/memories/parser.md"}),
+        ts(9, 6, 0),
+    )
+    .expect("delete");
+    assert!(matches!(
+        run(
+            serde_json::json!({"command": "view", "path": "/memories/parser.md"}),
+            ts(9, 7, 0)
+        ),
+        Err(MemoryToolError::NotFound(_))
+    ));
+
+    run(
+        serde_json::json!({"command": "create", "path": "/memories/decisions/storage.md", "file_text": "keep sqlite"}),
+        ts(10, 0, 0),
+    )
+    .expect("decision");
+    run(
+        serde_json::json!({"command": "str_replace", "path": "/memories/decisions/storage.md", "old_str": "keep sqlite", "new_str": "keep sqlite in WAL mode"}),
+        ts(10, 1, 0),
+    )
+    .expect("supersede");
+    let decisions = store
+        .search_mem_decisions("sqlite", None, None, 10)
+        .expect("decisions");
+    assert_eq!(decisions.len(), 2, "the log keeps both revisions");
+    assert_eq!(decisions[0].text, "keep sqlite in WAL mode");
+    assert_eq!(
+        decisions[0].supersedes_id.as_deref(),
+        Some(decisions[1].decision_id.as_str())
+    );
+    assert!(matches!(
+        run(
+            serde_json::json!({"command": "delete", "path": "/memories/decisions/storage.md"}),
+            ts(10, 2, 0)
+        ),
+        Err(MemoryToolError::InvalidInput(_))
+    ));
+
+    store
+        .upsert_canon_entry_revision(
+            "canon-storage",
+            Some("storage"),
+            "sqlite store with WAL",
+            8000,
+            7000,
+            None,
+            &["obs-storage".to_string()],
+            ts(10, 3, 0),
+        )
+        .expect("canon");
+    let canon = run(
+        serde_json::json!({"command": "view", "path": "/memories/canon/storage.md"}),
+        ts(10, 4, 0),
+    )
+    .expect("canon view");
+    assert!(
+        canon.contains("[canon-storage] sqlite store with WAL"),
+        "{canon}"
+    );
+    let recall = run(
+        serde_json::json!({"command": "view", "path": "/memories/recall/sqlite.md"}),
+        ts(10, 5, 0),
+    )
+    .expect("recall");
+    assert!(recall.contains("keep sqlite in WAL mode"), "{recall}");
+    assert_eq!(recall.matches("- decision ").count(), 1, "{recall}");
+    assert!(matches!(
+        run(
+            serde_json::json!({"command": "create", "path": "/memories/canon/storage.md", "file_text": "x"}),
+            ts(10, 6, 0)
+        ),
+        Err(MemoryToolError::ReadOnly(_))
+    ));
+    assert!(matches!(
+        run(
+            serde_json::json!({"command": "view", "path": "/memories/../etc/passwd"}),
+            ts(10, 7, 0)
+        ),
+        Err(MemoryToolError::InvalidPath { .. })
+    ));
+}
+
+#[test]
+fn layered_mind_merges_global_under_project_and_promotes_global_routes() {
+    use aoc_core::mind_contracts::{RouteOrigin, SegmentCandidate, SegmentRoute};
+    use aoc_storage::ArtifactQuery;
+
+    let project = MindStore::open_in_memory().expect("project");
+    let global = MindStore::open_in_memory().expect("global");
+    project
+        .insert_observation("obs-p", "conv-p", ts(10, 0, 0), "prefer rg over grep", &[])
+        .expect("project observation");
+    project
+        .insert_observation(
+            "obs-lesson",
+            "conv-p",
+            ts(11, 0, 0),
+            "always pin toolchains",
+            &[],
+        )
+        .expect("lesson");
+    global
+        .insert_observation("obs-g", "conv-g", ts(10, 0, 0), "prefer small commits", &[])
+        .expect("global observation");
+    global
+        .insert_observation("obs-p", "conv-p", ts(9, 0, 0), "prefer stale copy", &[])
+        .expect("shadowed global copy");
+
+    let mind = LayeredMind::new(&project, Some(&global));
+    let page = mind
+        .query_artifacts(&ArtifactQuery {
+            text: Some("prefer".to_string()),
+            limit: 10,
+            ..ArtifactQuery::default()
+        })
+        .expect("layered query");
+    let hits = page
+        .artifacts
+        .iter()
+        .map(|hit| (hit.artifact.artifact_id.as_str(), hit.layer))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        hits,
+        vec![("obs-p", MindLayer::Project), ("obs-g", MindLayer::Global)],
+        "project copy shadows the global one and wins the timestamp tie"
+    );
+    assert_eq!(page.total, 2);
+    assert_eq!(
+        mind.artifact_by_id("obs-g")
+            .expect("lookup")
+            .map(|hit| hit.layer),
+        Some(MindLayer::Global)
+    );
+    assert_eq!(
+        LayeredMind::new(&project, None)
+            .query_artifacts(&ArtifactQuery {
+                text: Some("prefer".to_string()),
+                limit: 10,
+                ..ArtifactQuery::default()
+            })
+            .expect("project only")
+            .total,
+        1
+    );
+
+    project
+        .replace_segment_route(&SegmentRoute {
+            artifact_id: "obs-lesson".to_string(),
+            primary: SegmentCandidate::new("global".to_string(), 8_000).expect("primary"),
+            secondary: Vec::new(),
+            routed_by: RouteOrigin::Heuristic,
+            reason: "test".to_string(),
+            overridden_by: None,
+        })
+        .expect("route");
+    let report = mind
+        .promote_global_routes("conv-p", "global")
+        .expect("promote");
+    assert_eq!((report.candidates, report.promoted), (1, 1));
+    let promoted = global
+        .artifact_by_id("obs-lesson")
+        .expect("lookup")
+        .expect("promoted artifact");
+    assert_eq!(promoted.text, "always pin toolchains");
+    assert_eq!(
+        global
+            .segment_route_for_artifact("obs-lesson")
+            .expect("route lookup")
+            .map(|route| route.primary.segment_id),
+        Some("global".to_string())
+    );
+    let again = mind
+        .promote_global_routes("conv-p", "global")
+        .expect("re-promote");
+    assert_eq!((again.promoted, again.unchanged), (0, 1));
+    assert!(matches!(
+        LayeredMind::new(&project, None).promote_artifact("obs-lesson"),
+        Err(LayeredMindError::NoGlobalStore)
+    ));
+}
+
+#[test]
+fn runtime_reads_drain_deadlines_and_heartbeats_from_its_clock() {
+    let clock = std::sync::Arc::new(aoc_storage::MockClock::new(ts(12, 0, 0)));
+    let mut runtime = runtime_with_clock("mock-clock", clock.clone());
+    let deadline = ts(12, 0, 10);
+
+    let step = runtime.finalize_drain_step(None, deadline, 1);
+    assert!(!step.settled);
+    assert!(step.timed_out_reason.is_none());
+
+    clock.advance(chrono::Duration::seconds(10));
+    let step = runtime.finalize_drain_step(None, deadline, 1);
+    assert!(!step.settled);
+    assert!(step.timed_out_reason.is_some());
+
+    let mut snapshot = MindServiceHealthSnapshot::default();
+    runtime.heartbeat_service(&mut snapshot).expect("heartbeat");
+    assert_eq!(
+        snapshot.last_heartbeat_ms,
+        Some(ts(12, 0, 10).timestamp_millis())
+    );
+    assert_eq!(
+        snapshot.lease_expires_at_ms,
+        Some(ts(12, 0, 40).timestamp_millis())
+    );
+}
@@ -0,0 +1,473 @@
+use crate::export::{write_atomically, ArtifactExportError};
+use aoc_storage::{ArtifactQuery, CanonEntryRevision, MemDecision, MindStore, StoredArtifact};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+const VAULT_STATE_FILE: &str = ".aoc-vault.json";
+const VAULT_STATE_VERSION: u32 = 1;
+const VAULT_PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultExportOptions {
+    /// Project whose decisions are written (see `project_scope_key`).
+    pub project_id: String,
+    /// Ignore the watermarks and rewrite every note.
+    pub full: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VaultExportReport {
+    pub incremental: bool,
+    pub observations: usize,
+    pub reflections: usize,
+    pub canon_revisions: usize,
+    pub decisions: usize,
+    pub notes_written: Vec<PathBuf>,
+}
+
+/// Newest timestamp exported for one note kind, plus the ids already written
+/// at exactly that timestamp so ties are neither skipped nor repeated.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct VaultWatermark {
+    ts: Option<DateTime<Utc>>,
+    #[serde(default)]
+    ids_at_ts: BTreeSet<String>,
+}
+
+impl VaultWatermark {
+    fn admits(&self, ts: DateTime<Utc>, id: &str) -> bool {
+        match self.ts {
+            None => true,
+            Some(mark) => ts > mark || (ts == mark && !self.ids_at_ts.contains(id)),
+        }
+    }
+
+    fn advance(&mut self, ts: DateTime<Utc>, id: &str) {
+        if self.ts.is_some_and(|mark| mark > ts) {
+            return;
+        }
+        if self.ts != Some(ts) {
+            self.ts = Some(ts);
+            self.ids_at_ts.clear();
+        }
+        self.ids_at_ts.insert(id.to_string());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct VaultState {
+    version: u32,
+    project_id: String,
+    observations: VaultWatermark,
+    reflections: VaultWatermark,
+    canon: VaultWatermark,
+    decisions: VaultWatermark,
+}
+
+/// Writes the mind as an Obsidian-style markdown vault under `out_dir`.
+///
+/// Observations, reflections, canon revisions, and decisions each get a note
+/// with YAML front-matter (`tags`, `tasks`, `segments`), and trace ids,
+/// evidence refs, and supersession become wiki-links between notes. Per-kind
+/// watermarks in `.aoc-vault.json` make reruns write only what is new.
+pub fn export_vault(
+    store: &MindStore,
+    out_dir: &Path,
+    options: &VaultExportOptions,
+) -> Result<VaultExportReport, ArtifactExportError> {
+    fs::create_dir_all(out_dir)?;
+    let state_path = out_dir.join(VAULT_STATE_FILE);
+    let previous = if options.full {
+        None
+    } else {
+        read_vault_state(&state_path)?
+    };
+    if let Some(state) = previous.as_ref() {
+        if state.project_id != options.project_id {
+            return Err(ArtifactExportError::StateMismatch(format!(
+                "{} is a vault for project {}; use a new --out directory",
+                out_dir.display(),
+                state.project_id
+            )));
+        }
+    }
+
+    let mut report = VaultExportReport {
+        incremental: previous.is_some(),
+        ..VaultExportReport::default()
+    };
+    let mut state = previous.unwrap_or_else(|| VaultState {
+        version: VAULT_STATE_VERSION,
+        project_id: options.project_id.clone(),
+        observations: VaultWatermark::default(),
+        reflections: VaultWatermark::default(),
+        canon: VaultWatermark::default(),
+        decisions: VaultWatermark::default(),
+    });
+    let mut links = LinkResolver::default();
+
+    for (kind, watermark) in [
+        ("t1", &mut state.observations),
+        ("t2", &mut state.reflections),
+    ] {
+        let since = watermark.ts;
+        let mut offset = 0;
+        loop {
+            let page = store.query_artifacts(&ArtifactQuery {
+                kind: Some(kind.to_string()),
+                since,
+                oldest_first: true,
+                offset,
+                limit: VAULT_PAGE_SIZE,
+                ..ArtifactQuery::default()
+            })?;
+            offset += page.artifacts.len();
+            let next = page.next_offset();
+            for artifact in page.artifacts {
+                if !watermark.admits(artifact.ts, &artifact.artifact_id) {
+                    continue;
+                }
+                let path = artifact_note_path(out_dir, &artifact);
+                write_note(&path, &render_artifact_note(store, &mut links, &artifact)?)?;
+                watermark.advance(artifact.ts, &artifact.artifact_id);
+                report.notes_written.push(path);
+                if kind == "t1" {
+                    report.observations += 1;
+                } else {
+                    report.reflections += 1;
+                }
+            }
+            if next.is_none() {
+                break;
+            }
+        }
+    }
+
+    let mut revisions = store
+        .canon_revision_history(None, i64::MAX as usize)?
+        .into_iter()
+        .filter(|revision| {
+            state
+                .canon
+                .admits(revision.created_at, &canon_note_id(revision))
+        })
+        .collect::<Vec<_>>();
+    revisions.reverse();
+    for revision in &revisions {
+        let path = out_dir
+            .join("canon")
+            .join(format!("{}.md", canon_note_name(revision)));
+        write_note(&path, &render_canon_note(store, &mut links, revision)?)?;
+        state
+            .canon
+            .advance(revision.created_at, &canon_note_id(revision));
+        report.notes_written.push(path);
+    }
+    report.canon_revisions = revisions.len();
+
+    let mut decisions = store
+        .mem_decisions(&options.project_id, None, true)?
+        .into_iter()
+        .filter(|decision| state.decisions.admits(decision.ts, &decision.decision_id))
+        .collect::<Vec<_>>();
+    decisions.reverse();
+    for decision in &decisions {
+        let path = out_dir
+            .join("decisions")
+            .join(format!("{}.md", note_slug(&decision.decision_id)));
+        write_note(&path, &render_decision_note(decision))?;
+        state.decisions.advance(decision.ts, &decision.decision_id);
+        report.notes_written.push(path);
+    }
+    report.decisions = decisions.len();
+
+    write_vault_state(&state_path, &state)?;
+    Ok(report)
+}
+
+/// Maps trace ids and evidence refs to the note they point at, if any.
+#[derive(Default)]
+struct LinkResolver {
+    folders: HashMap<String, Option<&'static str>>,
+}
+
+impl LinkResolver {
+    fn link(&mut self, store: &MindStore, id: &str) -> Result<String, ArtifactExportError> {
+        if !self.folders.contains_key(id) {
+            let folder = store
+                .artifact_by_id(id)?
+                .map(|artifact| artifact_folder(&artifact.kind));
+            self.folders.insert(id.to_string(), folder);
+        }
+        Ok(match self.folders[id] {
+            Some(folder) => wiki_link(folder, id),
+            None => format!("`{id}`"),
+        })
+    }
+}
+
+fn artifact_folder(kind: &str) -> &'static str {
+    if kind == "t2" {
+        "reflections"
+    } else {
+        "observations"
+    }
+}
+
+fn artifact_note_path(out_dir: &Path, artifact: &StoredArtifact) -> PathBuf {
+    out_dir
+        .join(artifact_folder(&artifact.kind))
+        .join(format!("{}.md", note_slug(&artifact.artifact_id)))
+}
+
+fn canon_note_id(revision: &CanonEntryRevision) -> String {
+    format!("{}#r{}", revision.entry_id, revision.revision)
+}
+
+fn canon_note_name(revision: &CanonEntryRevision) -> String {
+    format!("{}-r{}", note_slug(&revision.entry_id), revision.revision)
+}
+
+/// File-system and wiki-link safe note name (Obsidian rejects `:`, `/`, `#`,
+/// `|`, `[`, `]`, and friends in note names).
+fn note_slug(id: &str) -> String {
+    id.chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+                ch
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn wiki_link(folder: &str, id: &str) -> String {
+    format!("[[{folder}/{}|{id}]]", note_slug(id))
+}
+
+/// Obsidian tags allow letters, digits, `_`, `-`, and `/` for nesting.
+fn tag_value(raw: &str) -> String {
+    raw.trim()
+        .chars()
+        .map(|ch| {
+            if ch.is_alphanumeric() || matches!(ch, '_' | '-' | '/') {
+                ch
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn yaml_string(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+fn yaml_list(values: &[String]) -> String {
+    serde_json::Value::from(values.to_vec()).to_string()
+}
+
+fn front_matter(fields: &[(&str, String)]) -> String {
+    let mut out = String::from("---\n");
+    for (key, value) in fields {
+        out.push_str(&format!("{key}: {value}\n"));
+    }
+    out.push_str("---\n");
+    out
+}
+
+fn timestamp(ts: DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn render_artifact_note(
+    store: &MindStore,
+    links: &mut LinkResolver,
+    artifact: &StoredArtifact,
+) -> Result<String, ArtifactExportError> {
+    let segments = store
+        .segment_route_for_artifact(&artifact.artifact_id)?
+        .map(|route| {
+            std::iter::once(route.primary)
+                .chain(route.secondary)
+                .map(|candidate| candidate.segment_id)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let mut tasks = store
+        .artifact_task_links_for_artifact(&artifact.artifact_id)?
+        .into_iter()
+        .map(|link| link.task_id)
+        .collect::<Vec<_>>();
+    tasks.sort();
+    tasks.dedup();
+
+    let (label, kind_tag) = if artifact.kind == "t2" {
+        ("Reflection", "aoc/reflection")
+    } else {
+        ("Observation", "aoc/observation")
+    };
+    let mut tags = vec![kind_tag.to_string()];
+    if let Some(tag) = store
+        .latest_context_state(&artifact.conversation_id)?
+        .and_then(|state| state.active_tag)
+        .map(|tag| tag_value(&tag))
+        .filter(|tag| !tag.is_empty())
+    {
+        tags.push(tag);
+    }
+    tags.extend(
+        segments
+            .iter()
+            .map(|segment| format!("segment/{}", tag_value(segment))),
+    );
+
+    let mut out = front_matter(&[
+        ("aoc_id", yaml_string(&artifact.artifact_id)),
+        ("aoc_kind", yaml_string(&artifact.kind)),
+        ("conversation", yaml_string(&artifact.conversation_id)),
+        ("created", timestamp(artifact.ts)),
+        ("tags", yaml_list(&tags)),
+        ("tasks", yaml_list(&tasks)),
+        ("segments", yaml_list(&segments)),
+    ]);
+    out.push_str(&format!(
+        "\n# {label} {}\n\n{}\n",
+        artifact.artifact_id,
+        artifact.text.trim()
+    ));
+    if !artifact.trace_ids.is_empty() {
+        out.push_str("\n## Trace\n\n");
+        for trace_id in &artifact.trace_ids {
+            out.push_str(&format!("- {}\n", links.link(store, trace_id)?));
+        }
+    }
+    Ok(out)
+}
+
+fn render_canon_note(
+    store: &MindStore,
+    links: &mut LinkResolver,
+    revision: &CanonEntryRevision,
+) -> Result<String, ArtifactExportError> {
+    let mut tags = vec!["aoc/canon".to_string()];
+    if let Some(topic) = revision
+        .topic
+        .as_deref()
+        .map(tag_value)
+        .filter(|topic| !topic.is_empty())
+    {
+        tags.push(format!("topic/{topic}"));
+    }
+    let mut out = front_matter(&[
+        ("aoc_id", yaml_string(&revision.entry_id)),
+        ("aoc_kind", yaml_string("canon")),
+        ("revision", revision.revision.to_string()),
+        ("state", yaml_string(revision.state.as_str())),
+        (
+            "topic",
+            revision
+                .topic
+                .as_deref()
+                .map_or("null".to_string(), yaml_string),
+        ),
+        ("confidence_bps", revision.confidence_bps.to_string()),
+        ("created", timestamp(revision.created_at)),
+        ("tags", yaml_list(&tags)),
+        ("tasks", "[]".to_string()),
+        ("segments", "[]".to_string()),
+    ]);
+    out.push_str(&format!(
+        "\n# Canon {} r{}\n\n{}\n",
+        revision.entry_id,
+        revision.revision,
+        revision.summary.trim()
+    ));
+    if revision.revision > 1 {
+        let previous = format!(
+            "{}-r{}",
+            note_slug(&revision.entry_id),
+            revision.revision - 1
+        );
+        out.push_str(&format!(
+            "\nPrevious revision: [[canon/{previous}|r{}]]\n",
+            revision.revision - 1
+        ));
+    }
+    if let Some(superseded) = revision.supersedes_entry_id.as_deref() {
+        out.push_str(&format!("\nSupersedes: `{superseded}`\n"));
+    }
+    if !revision.evidence_refs.is_empty() {
+        out.push_str("\n## Evidence\n\n");
+        for evidence in &revision.evidence_refs {
+            out.push_str(&format!("- {}\n", links.link(store, evidence)?));
+        }
+    }
+    Ok(out)
+}
+
+fn render_decision_note(decision: &MemDecision) -> String {
+    let segments = decision.segment_id.iter().cloned().collect::<Vec<_>>();
+    let mut tags = vec!["aoc/decision".to_string()];
+    tags.extend(
+        segments
+            .iter()
+            .map(|segment| format!("segment/{}", tag_value(segment))),
+    );
+    let mut out = front_matter(&[
+        ("aoc_id", yaml_string(&decision.decision_id)),
+        ("aoc_kind", yaml_string("decision")),
+        ("project", yaml_string(&decision.project_id)),
+        ("created", timestamp(decision.ts)),
+        ("tags", yaml_list(&tags)),
+        ("tasks", "[]".to_string()),
+        ("segments", yaml_list(&segments)),
+    ]);
+    out.push_str(&format!(
+        "\n# Decision {}\n\n{}\n",
+        decision.decision_id,
+        decision.text.trim()
+    ));
+    if let Some(superseded) = decision.supersedes_id.as_deref() {
+        out.push_str(&format!(
+            "\nSupersedes: {}\n",
+            wiki_link("decisions", superseded)
+        ));
+    }
+    out
+}
+
+fn write_note(path: &Path, note: &str) -> Result<(), ArtifactExportError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomically(path, note.as_bytes())
+}
+
+fn read_vault_state(path: &Path) -> Result<Option<VaultState>, ArtifactExportError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(path)?;
+    let state: VaultState = serde_json::from_str(&raw)
+        .map_err(|err| ArtifactExportError::Serialization(err.to_string()))?;
+    if state.version != VAULT_STATE_VERSION {
+        return Err(ArtifactExportError::StateMismatch(format!(
+            "unsupported vault state version {}",
+            state.version
+        )));
+    }
+    Ok(Some(state))
+}
+
+fn write_vault_state(path: &Path, state: &VaultState) -> Result<(), ArtifactExportError> {
+    let payload = serde_json::to_vec_pretty(state)
+        .map_err(|err| ArtifactExportError::Serialization(err.to_string()))?;
+    write_atomically(path, &payload)
+}
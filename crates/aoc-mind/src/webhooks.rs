@@ -0,0 +1,554 @@
+//! Webhook notifications for pipeline events.
+//!
+//! [`PipelineEventWatcher`] diffs the store between polls and reports jobs
+//! that were dead-lettered, new canon revisions, and tag budgets that ran out
+//! today, plus a digest of the previous UTC day once the date rolls over.
+//! [`WebhookDispatcher`] posts each event to every endpoint whose filter
+//! matches, either as the AOC JSON envelope or as a Slack/Discord chat
+//! message, signing the body with HMAC-SHA256 when a secret is configured and
+//! retrying transport errors, 429s, and 5xx responses with exponential backoff.
+
+use aoc_storage::{MindStore, ReflectorJobStatus, StorageError, T3BacklogJobStatus};
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::{collections::HashSet, thread, time::Duration};
+
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-AOC-Signature";
+pub const WEBHOOK_EVENT_HEADER: &str = "X-AOC-Event";
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-AOC-Delivery";
+
+/// Jobs and canon revisions compared on each poll.
+const WATCH_WINDOW: usize = 200;
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Discord rejects message content longer than this.
+const DISCORD_CONTENT_LIMIT: usize = 2_000;
+/// Task ids named in a digest line before the rest are summarised.
+const DIGEST_TASK_LIMIT: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineEventKind {
+    JobDeadLettered,
+    CanonRevised,
+    BudgetExhausted,
+    /// Opt-in: only endpoints that list it receive it.
+    DailyDigest,
+}
+
+impl PipelineEventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::JobDeadLettered => "job_dead_lettered",
+            Self::CanonRevised => "canon_revised",
+            Self::BudgetExhausted => "budget_exhausted",
+            Self::DailyDigest => "daily_digest",
+        }
+    }
+
+    /// Whether an `events` filter picks this kind; an empty filter takes
+    /// every kind except the opt-in digest.
+    pub fn selected_by(self, filter: &[Self]) -> bool {
+        if filter.is_empty() {
+            return self != Self::DailyDigest;
+        }
+        filter.contains(&self)
+    }
+}
+
+/// Body shape an endpoint expects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    /// The AOC envelope from [`PipelineEvent::body`].
+    #[default]
+    Json,
+    /// Slack incoming webhook: `{"text": ...}`.
+    Slack,
+    /// Discord channel webhook: `{"content": ...}`.
+    Discord,
+}
+
+impl WebhookFormat {
+    pub fn render(self, event: &PipelineEvent) -> String {
+        match self {
+            Self::Json => event.body(),
+            Self::Slack => json!({ "text": event.message() }).to_string(),
+            Self::Discord => {
+                let mut content = event.message();
+                if content.chars().count() > DISCORD_CONTENT_LIMIT {
+                    content = content.chars().take(DISCORD_CONTENT_LIMIT - 1).collect();
+                    content.push('…');
+                }
+                json!({ "content": content }).to_string()
+            }
+        }
+    }
+}
+
+/// One `[[webhooks]]` entry in aoc.toml.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookEndpoint {
+    pub url: String,
+    #[serde(default)]
+    pub format: WebhookFormat,
+    /// Event kinds to deliver; empty delivers every kind except the digest.
+    #[serde(default)]
+    pub events: Vec<PipelineEventKind>,
+    /// Environment variable holding the HMAC secret, so the secret itself
+    /// never lands in aoc.toml.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_env: Option<String>,
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles per attempt up to 30s.
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+}
+
+fn default_max_attempts() -> u32 {
+    3
+}
+
+fn default_initial_backoff_ms() -> u64 {
+    500
+}
+
+impl WebhookEndpoint {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            format: WebhookFormat::default(),
+            events: Vec::new(),
+            secret_env: None,
+            max_attempts: default_max_attempts(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+        }
+    }
+
+    pub fn accepts(&self, kind: PipelineEventKind) -> bool {
+        kind.selected_by(&self.events)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PipelineEvent {
+    pub kind: PipelineEventKind,
+    /// What the event is about, e.g. `reflector:<job_id>` or `<entry>@<revision>`.
+    pub subject: String,
+    pub at: DateTime<Utc>,
+    pub detail: Value,
+}
+
+impl PipelineEvent {
+    /// Stable per kind and subject, so receivers can drop retried deliveries.
+    pub fn delivery_id(&self) -> String {
+        let digest = Sha256::digest(format!("{}\n{}", self.kind.as_str(), self.subject));
+        digest
+            .iter()
+            .take(16)
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    pub fn body(&self) -> String {
+        json!({
+            "event": self.kind.as_str(),
+            "delivery_id": self.delivery_id(),
+            "subject": self.subject,
+            "at": self.at.to_rfc3339(),
+            "detail": self.detail,
+        })
+        .to_string()
+    }
+
+    /// One human-readable line (or a few, for digests) for chat endpoints.
+    pub fn message(&self) -> String {
+        let text = |key: &str| self.detail[key].as_str().unwrap_or("?").to_string();
+        let number = |key: &str| self.detail[key].as_u64().unwrap_or(0);
+        match self.kind {
+            PipelineEventKind::JobDeadLettered => {
+                let mut line = format!(
+                    "AOC: {} job `{}` dead-lettered after {} attempt(s) (tag `{}`)",
+                    text("queue"),
+                    text("job_id"),
+                    number("attempts"),
+                    text("active_tag"),
+                );
+                if let Some(error) = self.detail["last_error"].as_str() {
+                    line.push_str(&format!(": {error}"));
+                }
+                line
+            }
+            PipelineEventKind::CanonRevised => format!(
+                "AOC: canon `{}` revision {} is {}: {}",
+                text("entry_id"),
+                number("revision"),
+                text("state"),
+                text("summary"),
+            ),
+            PipelineEventKind::BudgetExhausted => {
+                let mut line = format!(
+                    "AOC: budget for tag `{}` exhausted on {}",
+                    text("tag"),
+                    text("day")
+                );
+                if number("budget_tokens") > 0 {
+                    line.push_str(&format!(
+                        ", {}/{} tokens",
+                        number("spent_tokens"),
+                        number("budget_tokens")
+                    ));
+                }
+                if number("budget_cost_micros") > 0 {
+                    line.push_str(&format!(
+                        ", ${:.2}/${:.2}",
+                        number("spent_cost_micros") as f64 / 1_000_000.0,
+                        number("budget_cost_micros") as f64 / 1_000_000.0
+                    ));
+                }
+                line
+            }
+            PipelineEventKind::DailyDigest => {
+                let tasks = self.detail["completed_task_ids"]
+                    .as_array()
+                    .map(|ids| ids.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                let mut lines = vec![
+                    format!("AOC digest for {}", text("day")),
+                    format!(
+                        "- {} new reflection(s) across {} conversation(s)",
+                        number("reflections"),
+                        number("reflection_conversations")
+                    ),
+                    format!(
+                        "- {} canon revision(s) touching {} entry id(s)",
+                        number("canon_revisions"),
+                        self.detail["canon_entry_ids"]
+                            .as_array()
+                            .map_or(0, Vec::len)
+                    ),
+                ];
+                let mut completed = format!("- {} task(s) completed", tasks.len());
+                if !tasks.is_empty() {
+                    let named = tasks
+                        .iter()
+                        .take(DIGEST_TASK_LIMIT)
+                        .copied()
+                        .collect::<Vec<_>>();
+                    completed.push_str(&format!(": {}", named.join(", ")));
+                    if tasks.len() > named.len() {
+                        completed.push_str(&format!(" and {} more", tasks.len() - named.len()));
+                    }
+                }
+                lines.push(completed);
+                lines.join("\n")
+            }
+        }
+    }
+}
+
+/// `sha256=<hex>` over the raw request body.
+pub fn webhook_signature(secret: &[u8], body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("hmac accepts any key length");
+    mac.update(body.as_bytes());
+    let hex = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("sha256={hex}")
+}
+
+/// Sends one POST and returns the HTTP status; `Err` means no response.
+pub trait WebhookTransport {
+    fn post(&self, url: &str, headers: &[(&str, String)], body: &str) -> Result<u16, String>;
+}
+
+pub struct HttpWebhookTransport {
+    agent: ureq::Agent,
+}
+
+impl Default for HttpWebhookTransport {
+    fn default() -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+        }
+    }
+}
+
+impl WebhookTransport for HttpWebhookTransport {
+    fn post(&self, url: &str, headers: &[(&str, String)], body: &str) -> Result<u16, String> {
+        let mut request = self.agent.post(url).set("Content-Type", "application/json");
+        for (name, value) in headers {
+            request = request.set(name, value);
+        }
+        match request.send_string(body) {
+            Ok(response) => Ok(response.status()),
+            Err(ureq::Error::Status(status, _)) => Ok(status),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WebhookDelivery {
+    pub url: String,
+    pub kind: PipelineEventKind,
+    pub subject: String,
+    pub attempts: u32,
+    pub status: Option<u16>,
+    pub delivered: bool,
+    pub error: Option<String>,
+}
+
+pub struct WebhookDispatcher<T = HttpWebhookTransport> {
+    endpoints: Vec<WebhookEndpoint>,
+    transport: T,
+}
+
+impl WebhookDispatcher {
+    pub fn new(endpoints: Vec<WebhookEndpoint>) -> Self {
+        Self::with_transport(endpoints, HttpWebhookTransport::default())
+    }
+}
+
+impl<T: WebhookTransport> WebhookDispatcher<T> {
+    pub fn with_transport(endpoints: Vec<WebhookEndpoint>, transport: T) -> Self {
+        Self {
+            endpoints,
+            transport,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    /// Delivers `event` to each matching endpoint, blocking through retries.
+    pub fn dispatch(&self, event: &PipelineEvent) -> Vec<WebhookDelivery> {
+        self.endpoints
+            .iter()
+            .filter(|endpoint| endpoint.accepts(event.kind))
+            .map(|endpoint| self.deliver(endpoint, event))
+            .collect()
+    }
+
+    fn deliver(&self, endpoint: &WebhookEndpoint, event: &PipelineEvent) -> WebhookDelivery {
+        let mut delivery = WebhookDelivery {
+            url: endpoint.url.clone(),
+            kind: event.kind,
+            subject: event.subject.clone(),
+            attempts: 0,
+            status: None,
+            delivered: false,
+            error: None,
+        };
+        let body = endpoint.format.render(event);
+        let mut headers = vec![
+            (WEBHOOK_EVENT_HEADER, event.kind.as_str().to_string()),
+            (WEBHOOK_DELIVERY_HEADER, event.delivery_id()),
+        ];
+        if let Some(name) = &endpoint.secret_env {
+            // Refuse to send unsigned when the receiver expects a signature.
+            match std::env::var(name) {
+                Ok(secret) if !secret.is_empty() => headers.push((
+                    WEBHOOK_SIGNATURE_HEADER,
+                    webhook_signature(secret.as_bytes(), &body),
+                )),
+                _ => {
+                    delivery.error = Some(format!("secret env {name} is not set"));
+                    return delivery;
+                }
+            }
+        }
+
+        let mut backoff = Duration::from_millis(endpoint.initial_backoff_ms);
+        for attempt in 1..=endpoint.max_attempts.max(1) {
+            if attempt > 1 {
+                thread::sleep(backoff);
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+            delivery.attempts = attempt;
+            match self.transport.post(&endpoint.url, &headers, &body) {
+                Ok(status) if (200..300).contains(&status) => {
+                    delivery.status = Some(status);
+                    delivery.delivered = true;
+                    delivery.error = None;
+                    break;
+                }
+                Ok(status) => {
+                    delivery.status = Some(status);
+                    delivery.error = Some(format!("HTTP {status}"));
+                    if status != 429 && status < 500 {
+                        break;
+                    }
+                }
+                Err(err) => delivery.error = Some(err),
+            }
+        }
+        if !delivery.delivered {
+            tracing::warn!(
+                url = %endpoint.url,
+                event = event.kind.as_str(),
+                attempts = delivery.attempts,
+                error = delivery.error.as_deref().unwrap_or_default(),
+                "webhook delivery failed"
+            );
+        }
+        delivery
+    }
+}
+
+/// Tracks what has already been reported. The first poll only records the
+/// current state, so starting a watcher never replays history.
+#[derive(Debug, Default)]
+pub struct PipelineEventWatcher {
+    primed: bool,
+    dead_lettered: HashSet<String>,
+    canon_revisions: HashSet<(String, i64)>,
+    exhausted_budgets: HashSet<(String, NaiveDate)>,
+    /// Day the last digest covered, or the day the watcher started.
+    digest_day: Option<NaiveDate>,
+}
+
+impl PipelineEventWatcher {
+    pub fn poll(
+        &mut self,
+        store: &MindStore,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<PipelineEvent>, StorageError> {
+        let mut events = Vec::new();
+
+        let mut dead_lettered = HashSet::new();
+        for job in store.list_reflector_jobs(Some(ReflectorJobStatus::Failed), WATCH_WINDOW)? {
+            let subject = format!("reflector:{}", job.job_id);
+            if self.primed && !self.dead_lettered.contains(&subject) {
+                events.push(PipelineEvent {
+                    kind: PipelineEventKind::JobDeadLettered,
+                    subject: subject.clone(),
+                    at: job.updated_at,
+                    detail: json!({
+                        "queue": "reflector",
+                        "job_id": job.job_id,
+                        "active_tag": job.active_tag,
+                        "attempts": job.attempts,
+                        "last_error": job.last_error,
+                    }),
+                });
+            }
+            dead_lettered.insert(subject);
+        }
+        for job in store.list_t3_backlog_jobs(Some(T3BacklogJobStatus::Failed), WATCH_WINDOW)? {
+            let subject = format!("t3:{}", job.job_id);
+            if self.primed && !self.dead_lettered.contains(&subject) {
+                events.push(PipelineEvent {
+                    kind: PipelineEventKind::JobDeadLettered,
+                    subject: subject.clone(),
+                    at: job.updated_at,
+                    detail: json!({
+                        "queue": "t3",
+                        "job_id": job.job_id,
+                        "active_tag": job.active_tag,
+                        "attempts": job.attempts,
+                        "last_error": job.last_error,
+                    }),
+                });
+            }
+            dead_lettered.insert(subject);
+        }
+        self.dead_lettered = dead_lettered;
+
+        let mut canon_revisions = HashSet::new();
+        let mut revised = Vec::new();
+        for revision in store.canon_revision_history(None, WATCH_WINDOW)? {
+            let key = (revision.entry_id.clone(), revision.revision);
+            if self.primed && !self.canon_revisions.contains(&key) {
+                revised.push(PipelineEvent {
+                    kind: PipelineEventKind::CanonRevised,
+                    subject: format!("{}@{}", revision.entry_id, revision.revision),
+                    at: revision.created_at,
+                    detail: json!({
+                        "entry_id": revision.entry_id,
+                        "revision": revision.revision,
+                        "state": revision.state.as_str(),
+                        "topic": revision.topic,
+                        "summary": revision.summary,
+                        "supersedes_entry_id": revision.supersedes_entry_id,
+                    }),
+                });
+            }
+            canon_revisions.insert(key);
+        }
+        // History comes newest first; report in the order revisions landed.
+        events.extend(revised.into_iter().rev());
+        self.canon_revisions = canon_revisions;
+
+        let today = now.date_naive();
+        let midnight = today.and_hms_opt(0, 0, 0).map(|ts| ts.and_utc());
+        let mut exhausted = HashSet::new();
+        for budget in store.semantic_tag_budgets(midnight)? {
+            if budget.used_bps() < 10_000 {
+                continue;
+            }
+            let key = (budget.tag.clone(), today);
+            if self.primed && !self.exhausted_budgets.contains(&key) {
+                events.push(PipelineEvent {
+                    kind: PipelineEventKind::BudgetExhausted,
+                    subject: format!("{}:{today}", budget.tag),
+                    at: now,
+                    detail: json!({
+                        "tag": budget.tag,
+                        "day": today.to_string(),
+                        "budget_tokens": budget.budget_tokens,
+                        "spent_tokens": budget.spent_tokens,
+                        "budget_cost_micros": budget.budget_cost_micros,
+                        "spent_cost_micros": budget.spent_cost_micros,
+                    }),
+                });
+            }
+            exhausted.insert(key);
+        }
+        self.exhausted_budgets = exhausted;
+
+        // One digest per UTC day, sent on the first poll after midnight and
+        // covering only the day that just ended.
+        match self.digest_day {
+            Some(last) if last < today => {
+                if let Some(day) = today.checked_sub_days(Days::new(1)) {
+                    events.push(daily_digest(store, day)?);
+                }
+                self.digest_day = Some(today);
+            }
+            Some(_) => {}
+            None => self.digest_day = Some(today),
+        }
+
+        self.primed = true;
+        Ok(events)
+    }
+}
+
+fn daily_digest(store: &MindStore, day: NaiveDate) -> Result<PipelineEvent, StorageError> {
+    let start = day.and_hms_opt(0, 0, 0).expect("midnight exists").and_utc();
+    let end = start + chrono::Duration::days(1);
+    let summary = store.activity_summary(start, end)?;
+    Ok(PipelineEvent {
+        kind: PipelineEventKind::DailyDigest,
+        subject: format!("digest:{day}"),
+        at: end,
+        detail: json!({
+            "day": day.to_string(),
+            "reflections": summary.reflections,
+            "reflection_conversations": summary.reflection_conversations,
+            "canon_revisions": summary.canon_revisions,
+            "canon_entry_ids": summary.canon_entry_ids,
+            "completed_task_ids": summary.completed_task_ids,
+        }),
+    })
+}
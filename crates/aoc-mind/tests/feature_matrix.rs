@@ -0,0 +1,123 @@
+//! Slim-build feature matrix. Each entry is a configuration embedders rely
+//! on; the ignored test builds and tests every one in its own target dir so
+//! it never contends with the outer `cargo test` for the build lock:
+//!
+//! `cargo test -p aoc-mind --test feature_matrix -- --ignored`
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+struct Combination {
+    name: &'static str,
+    package: &'static str,
+    /// `None` builds with default features.
+    features: Option<&'static str>,
+}
+
+const MATRIX: &[Combination] = &[
+    Combination {
+        name: "storage-only",
+        package: "aoc-storage",
+        features: Some(""),
+    },
+    Combination {
+        name: "deterministic-only",
+        package: "aoc-mind",
+        features: Some(""),
+    },
+    Combination {
+        name: "adapters",
+        package: "aoc-mind",
+        features: Some("adapters"),
+    },
+    Combination {
+        name: "semantic",
+        package: "aoc-mind",
+        features: Some("semantic"),
+    },
+    Combination {
+        name: "render",
+        package: "aoc-mind",
+        features: Some("render"),
+    },
+    Combination {
+        name: "service",
+        package: "aoc-mind",
+        features: Some("service"),
+    },
+    Combination {
+        name: "full",
+        package: "aoc-mind",
+        features: None,
+    },
+];
+
+/// Features left out of the matrix, with the reason.
+const UNMATRIXED: &[(&str, &str)] = &[
+    ("default", "covered by the `full` entry"),
+    ("parquet", "arrow build is too slow for a local matrix; `cargo test -p aoc-mind --features parquet` covers it"),
+];
+
+fn manifest_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn declared_features() -> Vec<String> {
+    let manifest = std::fs::read_to_string(manifest_dir().join("Cargo.toml")).expect("manifest");
+    manifest
+        .lines()
+        .skip_while(|line| line.trim() != "[features]")
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['))
+        .filter_map(|line| line.split_once('=').map(|(name, _)| name.trim()))
+        .filter(|name| !name.is_empty() && !name.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn matrix_covers_every_declared_feature() {
+    let features = declared_features();
+    assert!(features.iter().any(|feature| feature == "default"));
+    for feature in &features {
+        let in_matrix = MATRIX.iter().any(|combo| {
+            combo.package == "aoc-mind"
+                && combo
+                    .features
+                    .is_some_and(|list| list.split(',').any(|entry| entry == feature))
+        });
+        let skipped = UNMATRIXED.iter().any(|(name, _)| name == feature);
+        assert!(
+            in_matrix || skipped,
+            "feature `{feature}` is neither in MATRIX nor UNMATRIXED"
+        );
+    }
+}
+
+#[test]
+#[ignore = "builds the workspace once per combination; run with --ignored"]
+fn slim_builds_compile_and_pass_their_tests() {
+    let workspace = manifest_dir().parent().expect("workspace root");
+    let target_dir: PathBuf = workspace.join("target").join("feature-matrix");
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+
+    let mut failures = Vec::new();
+    for combo in MATRIX {
+        let mut command = Command::new(&cargo);
+        command
+            .current_dir(workspace)
+            .env("CARGO_TARGET_DIR", &target_dir)
+            .args(["test", "-p", combo.package, "--lib"]);
+        if let Some(features) = combo.features {
+            command.arg("--no-default-features");
+            if !features.is_empty() {
+                command.args(["--features", features]);
+            }
+        }
+        let status = command.status().expect("spawn cargo");
+        if !status.success() {
+            failures.push(combo.name);
+        }
+    }
+    assert!(failures.is_empty(), "failing combinations: {failures:?}");
+}
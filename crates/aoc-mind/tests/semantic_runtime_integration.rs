@@ -92,6 +92,7 @@ fn multi_session_sidecar_processes_each_session_independently() {
                     summary: "semantic summary A".to_string(),
                     key_points: vec!["a1".to_string()],
                     citations: vec![],
+                    ..ObserverOutput::default()
                 }),
             ),
             (
@@ -100,6 +101,7 @@ fn multi_session_sidecar_processes_each_session_independently() {
                     summary: "semantic summary B".to_string(),
                     key_points: vec!["b1".to_string()],
                     citations: vec![],
+                    ..ObserverOutput::default()
                 }),
             ),
         ]),
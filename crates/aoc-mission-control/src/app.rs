@@ -51,6 +51,12 @@ impl App {
             mind_search_query: String::new(),
             mind_search_editing: false,
             mind_search_selected: 0,
+            artifact_browser: ArtifactBrowserState::default(),
+            job_queue: JobQueueState::default(),
+            route_review: RouteReviewState::default(),
+            task_timeline: TaskTimelineState::default(),
+            session_tree: SessionTreeState::default(),
+            search: SearchState::default(),
             status_note,
             pending_commands: HashMap::new(),
             pending_consultations: HashMap::new(),
@@ -946,6 +952,13 @@ impl App {
                     "local"
                 }
             }
+            Mode::Artifacts
+            | Mode::Jobs
+            | Mode::Routes
+            | Mode::Tasks
+            | Mode::Sessions
+            | Mode::Usage
+            | Mode::Search => "local",
         }
     }
 
@@ -1163,7 +1176,14 @@ impl App {
                 Mode::Fleet => Mode::Work,
                 Mode::Work => Mode::Diff,
                 Mode::Diff => Mode::Health,
-                Mode::Health => Mode::Overseer,
+                Mode::Health => Mode::Artifacts,
+                Mode::Artifacts => Mode::Jobs,
+                Mode::Jobs => Mode::Routes,
+                Mode::Routes => Mode::Tasks,
+                Mode::Tasks => Mode::Sessions,
+                Mode::Sessions => Mode::Usage,
+                Mode::Usage => Mode::Search,
+                Mode::Search => Mode::Overseer,
             }
         };
     }
@@ -2860,6 +2880,392 @@ impl App {
         });
     }
 
+    pub(crate) fn move_artifact_browser_selection(&mut self, delta: isize) {
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            return;
+        };
+        let len = artifact_browser_selection_len(&store, &self.artifact_browser);
+        if len == 0 {
+            // Leaf records (raw events) have nothing to select; scroll their body instead.
+            self.scroll = if delta > 0 {
+                self.scroll.saturating_add(1)
+            } else {
+                self.scroll.saturating_sub(1)
+            };
+            return;
+        }
+        let state = &mut self.artifact_browser;
+        let current = match state.trail.last() {
+            Some(step) => step.selected,
+            None => state.selected,
+        };
+        let next = current.saturating_add_signed(delta).min(len - 1);
+        match state.trail.last_mut() {
+            Some(step) => step.selected = next,
+            None => state.selected = next,
+        }
+    }
+
+    pub(crate) fn move_artifact_browser_page(&mut self, delta: isize) {
+        if !self.artifact_browser.trail.is_empty() {
+            return;
+        }
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            return;
+        };
+        let state = &mut self.artifact_browser;
+        let total = load_artifact_browser_page(&store, state, Utc::now()).total;
+        let last_page = total.saturating_sub(1) / ARTIFACT_BROWSER_PAGE_SIZE;
+        let page = state.page.saturating_add_signed(delta).min(last_page);
+        if page != state.page {
+            state.page = page;
+            state.selected = 0;
+            self.scroll = 0;
+        }
+        self.status_note = Some(format!("artifact page {}/{}", page + 1, last_page + 1));
+    }
+
+    pub(crate) fn open_artifact_browser_selection(&mut self) {
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            self.status_note = Some("no project Mind store to browse".to_string());
+            return;
+        };
+        match artifact_browser_selected_id(&store, &self.artifact_browser) {
+            Some(id) => {
+                self.status_note = Some(format!("opened {id}"));
+                self.artifact_browser
+                    .trail
+                    .push(ArtifactBrowserStep { id, selected: 0 });
+                self.scroll = 0;
+            }
+            None => {
+                self.status_note = Some("nothing to open at this level".to_string());
+            }
+        }
+    }
+
+    pub(crate) fn close_artifact_browser_entry(&mut self) -> bool {
+        let closed = self.artifact_browser.trail.pop().is_some();
+        if closed {
+            self.scroll = 0;
+        }
+        closed
+    }
+
+    pub(crate) fn move_job_queue_selection(&mut self, delta: isize) {
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            return;
+        };
+        let len = load_job_queue_rows(&store, self.job_queue.show_finished).len();
+        self.job_queue.selected = self
+            .job_queue
+            .selected
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+    }
+
+    pub(crate) fn toggle_job_queue_finished(&mut self) {
+        self.job_queue.show_finished = !self.job_queue.show_finished;
+        self.job_queue.selected = 0;
+        self.scroll = 0;
+        self.status_note = Some(if self.job_queue.show_finished {
+            "job queue: showing finished jobs".to_string()
+        } else {
+            "job queue: hiding finished jobs".to_string()
+        });
+    }
+
+    pub(crate) fn apply_selected_job_action(&mut self, action: aoc_storage::MindJobAction) {
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            self.status_note = Some("no project Mind store for job actions".to_string());
+            return;
+        };
+        let rows = load_job_queue_rows(&store, self.job_queue.show_finished);
+        let Some(row) = rows.get(self.job_queue.selected.min(rows.len().saturating_sub(1))) else {
+            self.status_note = Some("no job selected".to_string());
+            return;
+        };
+        self.status_note = Some(
+            match store.apply_mind_job_action(row.queue, &row.job_id, action, Utc::now()) {
+                Ok(true) => format!("{} {}: ok", action.as_str(), row.job_id),
+                Ok(false) => format!(
+                    "{} {}: not allowed while {}",
+                    action.as_str(),
+                    row.job_id,
+                    row.status
+                ),
+                Err(err) => format!("{} {} failed: {err}", action.as_str(), row.job_id),
+            },
+        );
+    }
+
+    pub(crate) fn move_search_selection(&mut self, delta: isize) {
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            return;
+        };
+        let filter = parse_search_query(&self.search.query, Utc::now());
+        let len = load_search_hits(&store, &filter).len();
+        self.search.selected = self
+            .search
+            .selected
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+    }
+
+    /// Opens the selected artifact hit in the artifact browser; decisions
+    /// only report where to read them in full.
+    pub(crate) fn open_search_selection(&mut self) {
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            self.status_note = Some("no project Mind store to search".to_string());
+            return;
+        };
+        let filter = parse_search_query(&self.search.query, Utc::now());
+        let hits = load_search_hits(&store, &filter);
+        let Some(hit) = hits.get(self.search.selected.min(hits.len().saturating_sub(1))) else {
+            self.status_note = Some("no search hit selected".to_string());
+            return;
+        };
+        if hit.kind == "decision" {
+            self.status_note = Some(format!(
+                "decision {}: run `aoc decisions show {}` for history",
+                hit.id, hit.id
+            ));
+            return;
+        }
+        self.artifact_browser.editing = false;
+        self.artifact_browser.trail = vec![ArtifactBrowserStep {
+            id: hit.id.clone(),
+            selected: 0,
+        }];
+        self.mode = Mode::Artifacts;
+        self.scroll = 0;
+        self.status_note = Some(format!("opened {} from search", hit.id));
+    }
+
+    pub(crate) fn move_session_tree_selection(&mut self, delta: isize) {
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            return;
+        };
+        let len = load_session_tree(&store).len();
+        let tree = &mut self.session_tree;
+        tree.selected = tree
+            .selected
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+    }
+
+    /// Sends `run_observer` for the selected branch, or with `stale_in_session`
+    /// for every backlogged branch in the selected branch's session.
+    pub(crate) fn request_session_tree_backfill(&mut self, stale_in_session: bool) {
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            self.status_note = Some("no project Mind store".to_string());
+            return;
+        };
+        let nodes = load_session_tree(&store);
+        let Some(selected) = nodes.get(
+            self.session_tree
+                .selected
+                .min(nodes.len().saturating_sub(1)),
+        ) else {
+            self.status_note = Some("no conversation selected".to_string());
+            return;
+        };
+        let session_id = selected.lineage.session_id.clone();
+        let conversation_ids = if stale_in_session {
+            nodes
+                .iter()
+                .filter(|node| node.stale && node.lineage.session_id == session_id)
+                .map(|node| node.lineage.conversation_id.clone())
+                .collect::<Vec<_>>()
+        } else {
+            vec![selected.lineage.conversation_id.clone()]
+        };
+        if conversation_ids.is_empty() {
+            self.status_note = Some(format!("session {session_id} has no observer backlog"));
+            return;
+        }
+        // The observer runs inside a wrapper, so prefer one attached to the branch's session.
+        let session_prefix = format!("{session_id}::");
+        let target = self
+            .overview_rows()
+            .into_iter()
+            .find(|row| row.identity_key.starts_with(&session_prefix))
+            .or_else(|| self.mind_target_agent());
+        let Some(target) = target else {
+            self.status_note = Some("no target pane for observer backfill".to_string());
+            return;
+        };
+        for conversation_id in &conversation_ids {
+            self.queue_hub_command(
+                "run_observer",
+                Some(target.identity_key.clone()),
+                serde_json::json!({
+                    "trigger": "manual_shortcut",
+                    "reason": "session_tree_backfill",
+                    "conversation_id": conversation_id,
+                }),
+                format!("{}::{}", target.label, target.pane_id),
+            );
+        }
+        if self.connected && conversation_ids.len() > 1 {
+            self.status_note = Some(format!(
+                "observer backfill queued for {} branches in {session_id}",
+                conversation_ids.len()
+            ));
+        }
+    }
+
+    pub(crate) fn move_task_timeline_selection(&mut self, delta: isize) {
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            return;
+        };
+        let len = task_timeline_selection_len(&store, &self.task_timeline);
+        let timeline = &mut self.task_timeline;
+        timeline.selected = timeline
+            .selected
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+        timeline.expanded = false;
+    }
+
+    /// Opens the selected recent task, or toggles evidence on the selected entry.
+    pub(crate) fn open_task_timeline_selection(&mut self) {
+        if self.task_timeline.task_id.is_some() {
+            self.task_timeline.expanded = !self.task_timeline.expanded;
+            return;
+        }
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            return;
+        };
+        let task_id = store
+            .recent_linked_task_ids(TASK_TIMELINE_RECENT_LIMIT)
+            .unwrap_or_default()
+            .into_iter()
+            .nth(self.task_timeline.selected);
+        if let Some(task_id) = task_id {
+            self.open_task_timeline(task_id);
+        }
+    }
+
+    pub(crate) fn submit_task_timeline_input(&mut self) {
+        self.task_timeline.editing = false;
+        match normalize_task_timeline_id(&self.task_timeline.input) {
+            Some(task_id) => self.open_task_timeline(task_id),
+            None => self.status_note = Some("task timeline: empty task id ignored".to_string()),
+        }
+        self.task_timeline.input.clear();
+    }
+
+    fn open_task_timeline(&mut self, task_id: String) {
+        self.status_note = Some(format!("task timeline: #{task_id}"));
+        self.task_timeline.task_id = Some(task_id);
+        self.task_timeline.selected = 0;
+        self.task_timeline.expanded = false;
+        self.scroll = 0;
+    }
+
+    /// Returns to the recent-task picker; false when already there.
+    pub(crate) fn close_task_timeline(&mut self) -> bool {
+        let timeline = &mut self.task_timeline;
+        if timeline.task_id.take().is_none() {
+            return false;
+        }
+        timeline.selected = 0;
+        timeline.expanded = false;
+        self.scroll = 0;
+        true
+    }
+
+    pub(crate) fn move_route_review_selection(&mut self, delta: isize) {
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            return;
+        };
+        let len = load_route_review_queue(&store).len();
+        let review = &mut self.route_review;
+        review.selected = review
+            .selected
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+        review.candidate = 0;
+    }
+
+    pub(crate) fn move_route_review_candidate(&mut self, delta: isize) {
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            return;
+        };
+        let len = load_selected_route_review(&store, &self.route_review)
+            .map(|review| review.candidates.len())
+            .unwrap_or_default();
+        let review = &mut self.route_review;
+        review.candidate = review
+            .candidate
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+    }
+
+    pub(crate) fn accept_route_review_candidate(&mut self) {
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            return;
+        };
+        let Some(review) = load_selected_route_review(&store, &self.route_review) else {
+            self.status_note = Some("no uncertain route selected".to_string());
+            return;
+        };
+        let Some(candidate) = review
+            .candidates
+            .get(self.route_review.candidate)
+            .or_else(|| review.candidates.last())
+        else {
+            self.status_note = Some("no candidates; type a segment with /".to_string());
+            return;
+        };
+        self.resolve_route_review(
+            &store,
+            &review.artifact.artifact_id,
+            &candidate.segment_id.clone(),
+            "route review: accepted candidate",
+        );
+    }
+
+    pub(crate) fn apply_route_review_segment(&mut self) {
+        let segment = self.route_review.segment_input.trim().to_string();
+        self.route_review.editing = false;
+        if segment.is_empty() {
+            self.status_note = Some("route review: empty segment ignored".to_string());
+            return;
+        }
+        let Some(store) = open_project_mind_store(&self.config.project_root) else {
+            return;
+        };
+        let Some(review) = load_selected_route_review(&store, &self.route_review) else {
+            self.status_note = Some("no uncertain route selected".to_string());
+            return;
+        };
+        self.resolve_route_review(
+            &store,
+            &review.artifact.artifact_id,
+            &segment,
+            "route review: typed segment",
+        );
+        self.route_review.segment_input.clear();
+    }
+
+    fn resolve_route_review(
+        &mut self,
+        store: &aoc_storage::MindStore,
+        artifact_id: &str,
+        segment_id: &str,
+        reason: &str,
+    ) {
+        self.status_note = Some(
+            match apply_route_review_override(store, artifact_id, segment_id, reason, Utc::now()) {
+                Ok(segment) => format!("routed {artifact_id} -> {segment}"),
+                Err(err) => format!("route override failed: {err}"),
+            },
+        );
+        self.route_review.candidate = 0;
+    }
+
     pub(crate) fn viewer_tab_overview_index(
         rows: &[OverviewRow],
         tab_index: Option<usize>,
@@ -0,0 +1,460 @@
+//! Artifact browser surface.
+//!
+//! Lists stored T1/T2 artifacts from the project Mind store, filtered by tag,
+//! segment, time window, and free text, and drills from an artifact through
+//! its trace ids down to the T0 compact events and raw events it cites.
+
+use super::*;
+use aoc_core::mind_contracts::RawEvent;
+use aoc_storage::{ArtifactPage, ArtifactQuery, MindStore, StoredArtifact, StoredCompactEvent};
+
+pub(crate) const ARTIFACT_BROWSER_PAGE_SIZE: usize = 15;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ArtifactBrowserState {
+    pub(crate) filter: String,
+    pub(crate) editing: bool,
+    pub(crate) page: usize,
+    pub(crate) selected: usize,
+    /// Drill path from a listed artifact down through cited trace ids.
+    pub(crate) trail: Vec<ArtifactBrowserStep>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ArtifactBrowserStep {
+    pub(crate) id: String,
+    pub(crate) selected: usize,
+}
+
+/// A trace id resolved against the store, in tier order.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ArtifactBrowserNode {
+    Artifact(StoredArtifact),
+    Compact(StoredCompactEvent),
+    Raw(RawEvent),
+    Missing(String),
+}
+
+impl ArtifactBrowserNode {
+    pub(crate) fn kind_label(&self) -> &'static str {
+        match self {
+            Self::Artifact(artifact) if artifact.kind == "t2" => "t2",
+            Self::Artifact(_) => "t1",
+            Self::Compact(_) => "t0",
+            Self::Raw(_) => "raw",
+            Self::Missing(_) => "missing",
+        }
+    }
+
+    /// Ids one level further down the provenance chain.
+    pub(crate) fn children(&self) -> &[String] {
+        match self {
+            Self::Artifact(artifact) => &artifact.trace_ids,
+            Self::Compact(event) => &event.source_event_ids,
+            Self::Raw(_) | Self::Missing(_) => &[],
+        }
+    }
+
+    pub(crate) fn summary(&self) -> String {
+        match self {
+            Self::Artifact(artifact) => artifact.text.clone(),
+            Self::Compact(event) => compact_event_text(event),
+            Self::Raw(event) => serde_json::to_string(&event.body).unwrap_or_default(),
+            Self::Missing(_) => "not found in this store".to_string(),
+        }
+    }
+}
+
+/// Parses `tag:<tag> seg:<segment> since:<30m|12h|7d|2w>` tokens; the rest is
+/// matched as artifact text.
+pub(crate) fn parse_artifact_browser_filter(filter: &str, now: DateTime<Utc>) -> ArtifactQuery {
+    let mut query = ArtifactQuery::default();
+    let mut text = Vec::new();
+    for token in filter.split_whitespace() {
+        if let Some(tag) = token.strip_prefix("tag:").filter(|value| !value.is_empty()) {
+            query.active_tag = Some(tag.to_string());
+        } else if let Some(segment) = token
+            .strip_prefix("seg:")
+            .or_else(|| token.strip_prefix("segment:"))
+            .filter(|value| !value.is_empty())
+        {
+            query.segment_id = Some(segment.to_string());
+        } else if let Some(since) = token
+            .strip_prefix("since:")
+            .and_then(|value| parse_browser_window(value, now))
+        {
+            query.since = Some(since);
+        } else {
+            text.push(token);
+        }
+    }
+    if !text.is_empty() {
+        query.text = Some(text.join(" "));
+    }
+    query
+}
+
+fn parse_browser_window(value: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let (split, _) = value.char_indices().last()?;
+    let (amount, unit) = value.split_at(split);
+    let amount = amount.parse::<i64>().ok()?;
+    let window = match unit {
+        "m" => chrono::Duration::minutes(amount),
+        "h" => chrono::Duration::hours(amount),
+        "d" => chrono::Duration::days(amount),
+        "w" => chrono::Duration::weeks(amount),
+        _ => return None,
+    };
+    Some(now - window)
+}
+
+pub(crate) fn open_project_mind_store(project_root: &Path) -> Option<MindStore> {
+    let store_path = mind_store_path(project_root);
+    if !store_path.exists() {
+        return None;
+    }
+    MindStore::open(&store_path).ok()
+}
+
+pub(crate) fn load_artifact_browser_page(
+    store: &MindStore,
+    state: &ArtifactBrowserState,
+    now: DateTime<Utc>,
+) -> ArtifactPage {
+    let query = ArtifactQuery {
+        offset: state.page * ARTIFACT_BROWSER_PAGE_SIZE,
+        limit: ARTIFACT_BROWSER_PAGE_SIZE,
+        ..parse_artifact_browser_filter(&state.filter, now)
+    };
+    store.query_artifacts(&query).unwrap_or_default()
+}
+
+pub(crate) fn resolve_artifact_browser_node(store: &MindStore, id: &str) -> ArtifactBrowserNode {
+    if let Ok(Some(artifact)) = store.artifact_by_id(id) {
+        return ArtifactBrowserNode::Artifact(artifact);
+    }
+    if let Ok(Some(event)) = store.compact_event_by_id(id) {
+        return ArtifactBrowserNode::Compact(event);
+    }
+    if let Ok(Some(event)) = store.raw_event_by_id(id) {
+        return ArtifactBrowserNode::Raw(event);
+    }
+    ArtifactBrowserNode::Missing(id.to_string())
+}
+
+fn compact_event_text(event: &StoredCompactEvent) -> String {
+    event.text.clone().unwrap_or_else(|| {
+        event
+            .tool_meta
+            .as_ref()
+            .and_then(|meta| serde_json::to_string(meta).ok())
+            .unwrap_or_default()
+    })
+}
+
+pub(crate) fn render_artifact_browser_lines(
+    app: &App,
+    theme: MissionTheme,
+    compact: bool,
+) -> Vec<Line<'static>> {
+    let state = &app.artifact_browser;
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            "Artifact browser",
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            if state.editing {
+                "[editing filter]"
+            } else if state.trail.is_empty() {
+                "[/ filter · Enter open · [/] page]"
+            } else {
+                "[Enter drill · Bksp back]"
+            },
+            Style::default().fg(theme.muted),
+        ),
+    ])];
+    let filter = state.filter.trim();
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        Span::styled("filter:", Style::default().fg(theme.muted)),
+        Span::raw(" "),
+        Span::styled(
+            match (filter.is_empty(), state.editing) {
+                (true, false) => "(all artifacts)".to_string(),
+                (_, true) => format!("> {filter}_"),
+                (false, false) => format!("> {filter}"),
+            },
+            Style::default().fg(if filter.is_empty() {
+                theme.muted
+            } else {
+                theme.accent
+            }),
+        ),
+    ]));
+
+    let Some(store) = open_project_mind_store(&app.config.project_root) else {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                "No project Mind store yet; run ingest/distill first.",
+                Style::default().fg(theme.warn),
+            ),
+        ]));
+        return lines;
+    };
+
+    if state.trail.is_empty() {
+        render_artifact_list(&mut lines, &store, state, theme, compact);
+    } else {
+        render_artifact_trail(&mut lines, &store, &state.trail, theme, compact);
+    }
+    lines
+}
+
+fn render_artifact_list(
+    lines: &mut Vec<Line<'static>>,
+    store: &MindStore,
+    state: &ArtifactBrowserState,
+    theme: MissionTheme,
+    compact: bool,
+) {
+    let page = load_artifact_browser_page(store, state, Utc::now());
+    if page.artifacts.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                if page.total == 0 {
+                    "No artifacts match this filter.".to_string()
+                } else {
+                    format!(
+                        "Page {} is past the last of {} hits.",
+                        state.page + 1,
+                        page.total
+                    )
+                },
+                Style::default().fg(theme.warn),
+            ),
+        ]));
+        return;
+    }
+    let pages = page.total.div_ceil(ARTIFACT_BROWSER_PAGE_SIZE);
+    let selected = state.selected.min(page.artifacts.len() - 1);
+    lines.push(Line::from(vec![
+        Span::raw("  -> "),
+        Span::styled(
+            format!("{} artifacts", page.total),
+            Style::default().fg(theme.muted),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("page:{}/{}", state.page + 1, pages),
+            Style::default().fg(theme.info),
+        ),
+    ]));
+    let text_budget = if compact { 40 } else { 88 };
+    for (index, artifact) in page.artifacts.iter().enumerate() {
+        let is_selected = index == selected;
+        lines.push(Line::from(vec![
+            Span::styled(
+                if is_selected { "  >> " } else { "  • " },
+                Style::default().fg(if is_selected {
+                    theme.accent
+                } else {
+                    theme.muted
+                }),
+            ),
+            Span::styled(
+                format!("[{}]", artifact.kind),
+                Style::default().fg(theme.info),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                artifact.ts.format("%m-%d %H:%M").to_string(),
+                Style::default().fg(theme.muted),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                ellipsize(&artifact.conversation_id, if compact { 14 } else { 24 }),
+                Style::default().fg(theme.muted),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                ellipsize(&flatten_text(&artifact.text), text_budget),
+                Style::default().fg(if is_selected {
+                    theme.text
+                } else {
+                    theme.accent
+                }),
+            ),
+        ]));
+    }
+}
+
+fn render_artifact_trail(
+    lines: &mut Vec<Line<'static>>,
+    store: &MindStore,
+    trail: &[ArtifactBrowserStep],
+    theme: MissionTheme,
+    compact: bool,
+) {
+    let id_budget = if compact { 18 } else { 32 };
+    let breadcrumb = trail
+        .iter()
+        .map(|step| ellipsize(&step.id, id_budget))
+        .collect::<Vec<_>>()
+        .join(" > ");
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        Span::styled("trail:", Style::default().fg(theme.muted)),
+        Span::raw(" "),
+        Span::styled(breadcrumb, Style::default().fg(theme.info)),
+    ]));
+
+    let step = trail.last().expect("trail is not empty");
+    let node = resolve_artifact_browser_node(store, &step.id);
+    lines.push(Line::from(""));
+    let mut header = vec![
+        Span::styled(
+            format!("[{}]", node.kind_label()),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(step.id.clone(), Style::default().fg(theme.text)),
+    ];
+    let meta = match &node {
+        ArtifactBrowserNode::Artifact(artifact) => Some(format!(
+            "conv:{} @{}",
+            artifact.conversation_id,
+            artifact.ts.to_rfc3339()
+        )),
+        ArtifactBrowserNode::Compact(event) => Some(format!(
+            "conv:{} role:{} policy:{} @{}",
+            event.conversation_id,
+            event
+                .role
+                .map(|role| format!("{role:?}").to_ascii_lowercase())
+                .unwrap_or_else(|| "tool".to_string()),
+            event.policy_version,
+            event.ts.to_rfc3339()
+        )),
+        ArtifactBrowserNode::Raw(event) => Some(format!(
+            "conv:{} agent:{} @{}",
+            event.conversation_id,
+            event.agent_id,
+            event.ts.to_rfc3339()
+        )),
+        ArtifactBrowserNode::Missing(_) => None,
+    };
+    if let Some(meta) = meta {
+        header.push(Span::raw(" "));
+        header.push(Span::styled(meta, Style::default().fg(theme.muted)));
+    }
+    lines.push(Line::from(header));
+
+    let body = match &node {
+        ArtifactBrowserNode::Raw(event) => {
+            serde_json::to_string_pretty(&event.body).unwrap_or_default()
+        }
+        other => other.summary(),
+    };
+    for text_line in body.lines() {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(text_line.to_string(), Style::default().fg(theme.text)),
+        ]));
+    }
+
+    let children = node.children();
+    if children.is_empty() {
+        return;
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        match &node {
+            ArtifactBrowserNode::Compact(_) => format!("  source events ({})", children.len()),
+            _ => format!("  traces ({})", children.len()),
+        },
+        Style::default().fg(theme.title),
+    )));
+    let selected = step.selected.min(children.len() - 1);
+    let text_budget = if compact { 36 } else { 72 };
+    for (index, child_id) in children.iter().enumerate() {
+        let child = resolve_artifact_browser_node(store, child_id);
+        let is_selected = index == selected;
+        lines.push(Line::from(vec![
+            Span::styled(
+                if is_selected { "  >> " } else { "  • " },
+                Style::default().fg(if is_selected {
+                    theme.accent
+                } else {
+                    theme.muted
+                }),
+            ),
+            Span::styled(
+                format!("[{}]", child.kind_label()),
+                Style::default().fg(if matches!(child, ArtifactBrowserNode::Missing(_)) {
+                    theme.critical
+                } else {
+                    theme.info
+                }),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                ellipsize(child_id, id_budget),
+                Style::default().fg(theme.muted),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                ellipsize(&flatten_text(&child.summary()), text_budget),
+                Style::default().fg(if is_selected {
+                    theme.text
+                } else {
+                    theme.accent
+                }),
+            ),
+        ]));
+    }
+}
+
+pub(crate) fn flatten_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Number of selectable rows at the current browser depth.
+pub(crate) fn artifact_browser_selection_len(
+    store: &MindStore,
+    state: &ArtifactBrowserState,
+) -> usize {
+    match state.trail.last() {
+        Some(step) => resolve_artifact_browser_node(store, &step.id)
+            .children()
+            .len(),
+        None => load_artifact_browser_page(store, state, Utc::now())
+            .artifacts
+            .len(),
+    }
+}
+
+/// Id under the cursor at the current browser depth, if any.
+pub(crate) fn artifact_browser_selected_id(
+    store: &MindStore,
+    state: &ArtifactBrowserState,
+) -> Option<String> {
+    match state.trail.last() {
+        Some(step) => resolve_artifact_browser_node(store, &step.id)
+            .children()
+            .get(step.selected)
+            .cloned(),
+        None => load_artifact_browser_page(store, state, Utc::now())
+            .artifacts
+            .into_iter()
+            .nth(state.selected)
+            .map(|artifact| artifact.artifact_id),
+    }
+}
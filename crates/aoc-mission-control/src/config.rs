@@ -98,6 +98,13 @@ pub(crate) fn parse_start_view(value: &str) -> Option<Mode> {
         "work" => Some(Mode::Work),
         "diff" => Some(Mode::Diff),
         "health" => Some(Mode::Health),
+        "artifacts" | "browser" => Some(Mode::Artifacts),
+        "jobs" | "queue" => Some(Mode::Jobs),
+        "routes" | "route-review" => Some(Mode::Routes),
+        "tasks" | "timeline" => Some(Mode::Tasks),
+        "sessions" | "lineage" => Some(Mode::Sessions),
+        "usage" | "cost" | "budget" => Some(Mode::Usage),
+        "search" | "find" => Some(Mode::Search),
         _ => None,
     }
 }
@@ -52,6 +52,7 @@ pub(crate) fn persist_consultation_outcome(
             fallback_reason: None,
             failure_kind: None,
             created_at: ts,
+            redactions: Vec::new(),
         })
         .map_err(|err| format!("persist consultation provenance failed: {err}"))?;
 
@@ -47,6 +47,109 @@ pub(crate) fn handle_key(key: KeyEvent, app: &mut App, refresh_requested: &mut b
         }
     }
 
+    if app.mode == Mode::Artifacts && app.artifact_browser.editing {
+        match key.code {
+            KeyCode::Esc => {
+                app.artifact_browser.editing = false;
+                app.status_note = Some("artifact filter edit cancelled".to_string());
+            }
+            KeyCode::Enter => {
+                let browser = &mut app.artifact_browser;
+                browser.editing = false;
+                browser.page = 0;
+                browser.selected = 0;
+                browser.trail.clear();
+                app.status_note = Some(if browser.filter.trim().is_empty() {
+                    "artifact filter cleared".to_string()
+                } else {
+                    format!("artifact filter: {}", browser.filter.trim())
+                });
+                app.scroll = 0;
+            }
+            KeyCode::Backspace => {
+                app.artifact_browser.filter.pop();
+            }
+            KeyCode::Char(ch)
+                if !key.modifiers.contains(KeyModifiers::CONTROL)
+                    && !key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                app.artifact_browser.filter.push(ch);
+            }
+            _ => {}
+        }
+        return false;
+    }
+
+    if app.mode == Mode::Routes && app.route_review.editing {
+        match key.code {
+            KeyCode::Esc => {
+                app.route_review.editing = false;
+                app.route_review.segment_input.clear();
+                app.status_note = Some("route segment edit cancelled".to_string());
+            }
+            KeyCode::Enter => app.apply_route_review_segment(),
+            KeyCode::Backspace => {
+                app.route_review.segment_input.pop();
+            }
+            KeyCode::Char(ch)
+                if !key.modifiers.contains(KeyModifiers::CONTROL)
+                    && !key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                app.route_review.segment_input.push(ch);
+            }
+            _ => {}
+        }
+        return false;
+    }
+
+    if app.mode == Mode::Tasks && app.task_timeline.editing {
+        match key.code {
+            KeyCode::Esc => {
+                app.task_timeline.editing = false;
+                app.task_timeline.input.clear();
+                app.status_note = Some("task id edit cancelled".to_string());
+            }
+            KeyCode::Enter => app.submit_task_timeline_input(),
+            KeyCode::Backspace => {
+                app.task_timeline.input.pop();
+            }
+            KeyCode::Char(ch)
+                if !key.modifiers.contains(KeyModifiers::CONTROL)
+                    && !key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                app.task_timeline.input.push(ch);
+            }
+            _ => {}
+        }
+        return false;
+    }
+
+    if app.mode == Mode::Search && app.search.editing {
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter => {
+                app.search.editing = false;
+                app.status_note = Some(if app.search.query.trim().is_empty() {
+                    "search cleared".to_string()
+                } else {
+                    format!("search: {}", app.search.query.trim())
+                });
+            }
+            KeyCode::Backspace => {
+                app.search.query.pop();
+                app.search.selected = 0;
+            }
+            KeyCode::Char(ch)
+                if !key.modifiers.contains(KeyModifiers::CONTROL)
+                    && !key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                app.search.query.push(ch);
+                app.search.selected = 0;
+            }
+            _ => {}
+        }
+        return false;
+    }
+
     if matches!(key.code, KeyCode::Char('?') | KeyCode::F(1)) {
         app.help_open = !app.help_open;
         return false;
@@ -58,6 +161,18 @@ pub(crate) fn handle_key(key: KeyEvent, app: &mut App, refresh_requested: &mut b
     if app.help_open {
         return false;
     }
+    if app.mode == Mode::Artifacts
+        && matches!(key.code, KeyCode::Esc | KeyCode::Backspace)
+        && app.close_artifact_browser_entry()
+    {
+        return false;
+    }
+    if app.mode == Mode::Tasks
+        && matches!(key.code, KeyCode::Esc | KeyCode::Backspace)
+        && app.close_task_timeline()
+    {
+        return false;
+    }
 
     match key.code {
         KeyCode::Char('q') => true,
@@ -101,13 +216,57 @@ pub(crate) fn handle_key(key: KeyEvent, app: &mut App, refresh_requested: &mut b
             app.scroll = 0;
             false
         }
+        KeyCode::Char('8') => {
+            app.mode = Mode::Artifacts;
+            app.scroll = 0;
+            false
+        }
+        KeyCode::Char('9') => {
+            app.mode = Mode::Jobs;
+            app.scroll = 0;
+            false
+        }
+        KeyCode::Char('0') => {
+            app.mode = Mode::Routes;
+            app.scroll = 0;
+            false
+        }
+        KeyCode::Char('T') => {
+            app.mode = Mode::Tasks;
+            app.scroll = 0;
+            false
+        }
+        KeyCode::Char('L') => {
+            app.mode = Mode::Sessions;
+            app.scroll = 0;
+            false
+        }
+        KeyCode::Char('U') => {
+            app.mode = Mode::Usage;
+            app.scroll = 0;
+            false
+        }
+        KeyCode::Char('G') => {
+            app.mode = Mode::Search;
+            app.search.editing = true;
+            app.scroll = 0;
+            false
+        }
         KeyCode::Tab => {
             app.cycle_mode();
             app.scroll = 0;
             false
         }
         KeyCode::Enter => {
-            if app.mode == Mode::Fleet {
+            if app.mode == Mode::Artifacts {
+                app.open_artifact_browser_selection();
+            } else if app.mode == Mode::Tasks {
+                app.open_task_timeline_selection();
+            } else if app.mode == Mode::Sessions {
+                app.request_session_tree_backfill(false);
+            } else if app.mode == Mode::Search {
+                app.open_search_selection();
+            } else if app.mode == Mode::Fleet {
                 app.focus_selected_fleet_project();
             } else {
                 app.focus_selected_overview_tab();
@@ -115,7 +274,9 @@ pub(crate) fn handle_key(key: KeyEvent, app: &mut App, refresh_requested: &mut b
             false
         }
         KeyCode::Char('x') => {
-            if app.mode == Mode::Fleet {
+            if app.mode == Mode::Jobs {
+                app.apply_selected_job_action(aoc_storage::MindJobAction::Cancel);
+            } else if app.mode == Mode::Fleet {
                 app.cancel_selected_fleet_job();
             } else {
                 app.stop_selected_overview_agent();
@@ -155,6 +316,8 @@ pub(crate) fn handle_key(key: KeyEvent, app: &mut App, refresh_requested: &mut b
         KeyCode::Char('u') => {
             if app.mode == Mode::Overseer {
                 app.request_overseer_consultation(ConsultationPacketKind::HelpRequest);
+            } else if app.mode == Mode::Jobs {
+                app.apply_selected_job_action(aoc_storage::MindJobAction::Requeue);
             }
             false
         }
@@ -167,6 +330,8 @@ pub(crate) fn handle_key(key: KeyEvent, app: &mut App, refresh_requested: &mut b
         KeyCode::Char('d') => {
             if app.mode == Mode::Overseer {
                 app.request_delegate_worker();
+            } else if app.mode == Mode::Jobs {
+                app.apply_selected_job_action(aoc_storage::MindJobAction::DeadLetter);
             }
             false
         }
@@ -217,7 +382,18 @@ pub(crate) fn handle_key(key: KeyEvent, app: &mut App, refresh_requested: &mut b
             false
         }
         KeyCode::Char('/') => {
-            if app.mode == Mode::Mind {
+            if app.mode == Mode::Tasks {
+                app.task_timeline.editing = true;
+                app.status_note = Some("type a task id".to_string());
+            } else if app.mode == Mode::Routes {
+                app.route_review.editing = true;
+                app.status_note = Some("type a segment for the selected artifact".to_string());
+            } else if app.mode == Mode::Artifacts {
+                app.artifact_browser.editing = true;
+                app.status_note = Some("editing artifact filter".to_string());
+            } else if app.mode == Mode::Search {
+                app.search.editing = true;
+            } else if app.mode == Mode::Mind {
                 app.mind_search_editing = true;
                 app.mind_search_selected = 0;
                 app.status_note = Some("editing mind search query".to_string());
@@ -241,12 +417,16 @@ pub(crate) fn handle_key(key: KeyEvent, app: &mut App, refresh_requested: &mut b
         KeyCode::Char('f') => {
             if app.mode == Mode::Fleet {
                 app.toggle_fleet_plane_filter();
+            } else if app.mode == Mode::Jobs {
+                app.toggle_job_queue_finished();
             }
             false
         }
         KeyCode::Char('A') => {
             if app.mode == Mode::Fleet {
                 app.toggle_fleet_active_only();
+            } else if app.mode == Mode::Sessions {
+                app.request_session_tree_backfill(true);
             }
             false
         }
@@ -259,18 +439,28 @@ pub(crate) fn handle_key(key: KeyEvent, app: &mut App, refresh_requested: &mut b
         KeyCode::Left | KeyCode::Char('[') => {
             if app.mode == Mode::Fleet {
                 app.move_fleet_job_selection(-1);
+            } else if app.mode == Mode::Artifacts {
+                app.move_artifact_browser_page(-1);
+            } else if app.mode == Mode::Routes {
+                app.move_route_review_candidate(-1);
             }
             false
         }
         KeyCode::Right | KeyCode::Char(']') => {
             if app.mode == Mode::Fleet {
                 app.move_fleet_job_selection(1);
+            } else if app.mode == Mode::Artifacts {
+                app.move_artifact_browser_page(1);
+            } else if app.mode == Mode::Routes {
+                app.move_route_review_candidate(1);
             }
             false
         }
         KeyCode::Char('a') => {
             if app.mode == Mode::Overview {
                 app.toggle_overview_sort_mode();
+            } else if app.mode == Mode::Routes {
+                app.accept_route_review_candidate();
             }
             false
         }
@@ -279,6 +469,18 @@ pub(crate) fn handle_key(key: KeyEvent, app: &mut App, refresh_requested: &mut b
                 app.move_overview_selection(1);
             } else if app.mode == Mode::Fleet {
                 app.move_fleet_selection(1);
+            } else if app.mode == Mode::Artifacts {
+                app.move_artifact_browser_selection(1);
+            } else if app.mode == Mode::Jobs {
+                app.move_job_queue_selection(1);
+            } else if app.mode == Mode::Routes {
+                app.move_route_review_selection(1);
+            } else if app.mode == Mode::Tasks {
+                app.move_task_timeline_selection(1);
+            } else if app.mode == Mode::Sessions {
+                app.move_session_tree_selection(1);
+            } else if app.mode == Mode::Search {
+                app.move_search_selection(1);
             } else {
                 app.scroll = app.scroll.saturating_add(1);
             }
@@ -289,6 +491,18 @@ pub(crate) fn handle_key(key: KeyEvent, app: &mut App, refresh_requested: &mut b
                 app.move_overview_selection(-1);
             } else if app.mode == Mode::Fleet {
                 app.move_fleet_selection(-1);
+            } else if app.mode == Mode::Artifacts {
+                app.move_artifact_browser_selection(-1);
+            } else if app.mode == Mode::Jobs {
+                app.move_job_queue_selection(-1);
+            } else if app.mode == Mode::Routes {
+                app.move_route_review_selection(-1);
+            } else if app.mode == Mode::Tasks {
+                app.move_task_timeline_selection(-1);
+            } else if app.mode == Mode::Sessions {
+                app.move_session_tree_selection(-1);
+            } else if app.mode == Mode::Search {
+                app.move_search_selection(-1);
             } else {
                 app.scroll = app.scroll.saturating_sub(1);
             }
@@ -302,6 +516,29 @@ pub(crate) fn handle_key(key: KeyEvent, app: &mut App, refresh_requested: &mut b
                 app.selected_fleet = 0;
                 app.selected_fleet_job = 0;
             }
+            if app.mode == Mode::Jobs {
+                app.job_queue.selected = 0;
+            }
+            if app.mode == Mode::Routes {
+                app.route_review.selected = 0;
+                app.route_review.candidate = 0;
+            }
+            if app.mode == Mode::Tasks {
+                app.task_timeline.selected = 0;
+                app.task_timeline.expanded = false;
+            }
+            if app.mode == Mode::Sessions {
+                app.session_tree.selected = 0;
+            }
+            if app.mode == Mode::Search {
+                app.search.selected = 0;
+            }
+            if app.mode == Mode::Artifacts {
+                let browser = &mut app.artifact_browser;
+                browser.trail.clear();
+                browser.page = 0;
+                browser.selected = 0;
+            }
             app.scroll = 0;
             false
         }
@@ -0,0 +1,239 @@
+//! Job queue surface.
+//!
+//! Lists reflector (T2) and T3 backlog jobs from the project Mind store with
+//! their claim and retry state. Requeue / cancel / dead-letter keys write
+//! straight to the store rather than going through the Pulse hub.
+
+use super::*;
+use aoc_storage::{MindJobQueue, MindStore};
+
+pub(crate) const JOB_QUEUE_ROW_LIMIT: usize = 200;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct JobQueueState {
+    pub(crate) selected: usize,
+    /// Also list completed and cancelled jobs.
+    pub(crate) show_finished: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct JobQueueRow {
+    pub(crate) queue: MindJobQueue,
+    pub(crate) job_id: String,
+    pub(crate) scope: String,
+    pub(crate) status: &'static str,
+    pub(crate) attempts: u16,
+    pub(crate) claimed_by: Option<String>,
+    pub(crate) last_error: Option<String>,
+    pub(crate) created_at: DateTime<Utc>,
+    pub(crate) updated_at: DateTime<Utc>,
+}
+
+/// Both queues merged, most recently touched first.
+pub(crate) fn load_job_queue_rows(store: &MindStore, show_finished: bool) -> Vec<JobQueueRow> {
+    let mut rows = Vec::new();
+    for job in store
+        .list_reflector_jobs(None, JOB_QUEUE_ROW_LIMIT)
+        .unwrap_or_default()
+    {
+        rows.push(JobQueueRow {
+            queue: MindJobQueue::Reflector,
+            job_id: job.job_id,
+            scope: format!("tag:{} obs:{}", job.active_tag, job.observation_ids.len()),
+            status: job.status.as_str(),
+            attempts: job.attempts,
+            claimed_by: job.claimed_by,
+            last_error: job.last_error,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+        });
+    }
+    for job in store
+        .list_t3_backlog_jobs(None, JOB_QUEUE_ROW_LIMIT)
+        .unwrap_or_default()
+    {
+        rows.push(JobQueueRow {
+            queue: MindJobQueue::T3Backlog,
+            job_id: job.job_id,
+            scope: format!(
+                "session:{} refs:{}",
+                job.session_id,
+                job.artifact_refs.len()
+            ),
+            status: job.status.as_str(),
+            attempts: job.attempts,
+            claimed_by: job.claimed_by,
+            last_error: job.last_error,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+        });
+    }
+    if !show_finished {
+        rows.retain(|row| !matches!(row.status, "completed" | "cancelled"));
+    }
+    rows.sort_by(|left, right| {
+        right
+            .updated_at
+            .cmp(&left.updated_at)
+            .then_with(|| left.job_id.cmp(&right.job_id))
+    });
+    rows
+}
+
+/// Queue ages span seconds to days, so pick the largest whole unit.
+pub(crate) fn job_age_label(secs: i64) -> String {
+    let secs = secs.max(0);
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86_399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
+
+fn job_status_color(status: &str, theme: MissionTheme) -> Color {
+    match status {
+        "pending" => theme.info,
+        "claimed" => theme.accent,
+        "completed" => theme.ok,
+        "failed" => theme.critical,
+        _ => theme.muted,
+    }
+}
+
+pub(crate) fn render_job_queue_lines(
+    app: &App,
+    theme: MissionTheme,
+    compact: bool,
+) -> Vec<Line<'static>> {
+    let state = &app.job_queue;
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            "Job queue",
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            "[u requeue · x cancel · d dead-letter · f finished]",
+            Style::default().fg(theme.muted),
+        ),
+    ])];
+
+    let Some(store) = open_project_mind_store(&app.config.project_root) else {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                "No project Mind store yet.",
+                Style::default().fg(theme.warn),
+            ),
+        ]));
+        return lines;
+    };
+    let rows = load_job_queue_rows(&store, state.show_finished);
+    let count = |status: &str| rows.iter().filter(|row| row.status == status).count();
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        Span::styled(
+            format!(
+                "pending:{} claimed:{} failed:{}",
+                count("pending"),
+                count("claimed"),
+                count("failed")
+            ),
+            Style::default().fg(theme.muted),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            if state.show_finished {
+                format!(
+                    "completed:{} cancelled:{}",
+                    count("completed"),
+                    count("cancelled")
+                )
+            } else {
+                "[finished hidden]".to_string()
+            },
+            Style::default().fg(theme.muted),
+        ),
+    ]));
+    if rows.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled("No queued jobs.", Style::default().fg(theme.muted)),
+        ]));
+        return lines;
+    }
+
+    let now = Utc::now();
+    let selected = state.selected.min(rows.len() - 1);
+    for (index, row) in rows.iter().enumerate() {
+        let is_selected = index == selected;
+        let mut spans = vec![
+            Span::styled(
+                if is_selected { "  >> " } else { "  • " },
+                Style::default().fg(if is_selected {
+                    theme.accent
+                } else {
+                    theme.muted
+                }),
+            ),
+            Span::styled(
+                format!("[{}]", row.queue.as_str()),
+                Style::default().fg(theme.info),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                format!("{:<9}", row.status),
+                Style::default().fg(job_status_color(row.status, theme)),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                ellipsize(&row.job_id, if compact { 16 } else { 24 }),
+                Style::default().fg(if is_selected {
+                    theme.text
+                } else {
+                    theme.accent
+                }),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                format!(
+                    "att:{} age:{}",
+                    row.attempts,
+                    job_age_label((now - row.created_at).num_seconds())
+                ),
+                Style::default().fg(theme.muted),
+            ),
+        ];
+        if !compact {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                ellipsize(&row.scope, 32),
+                Style::default().fg(theme.muted),
+            ));
+        }
+        lines.push(Line::from(spans));
+        if let Some(owner) = row.claimed_by.as_deref() {
+            lines.push(Line::from(vec![
+                Span::raw("      "),
+                Span::styled("claimed_by:", Style::default().fg(theme.muted)),
+                Span::raw(" "),
+                Span::styled(owner.to_string(), Style::default().fg(theme.accent)),
+            ]));
+        }
+        if let Some(error) = row.last_error.as_deref() {
+            lines.push(Line::from(vec![
+                Span::raw("      "),
+                Span::styled("last_error:", Style::default().fg(theme.muted)),
+                Span::raw(" "),
+                Span::styled(
+                    ellipsize(error, if compact { 48 } else { 96 }),
+                    Style::default().fg(theme.warn),
+                ),
+            ]));
+        }
+    }
+    lines
+}
@@ -65,6 +65,7 @@ use tokio::{
     sync::mpsc,
 };
 mod app;
+mod artifact_browser;
 mod collectors;
 mod config;
 mod consultation_memory;
@@ -73,6 +74,7 @@ mod fleet;
 mod health;
 mod hub;
 mod input;
+mod job_queue;
 mod mind_artifact_drilldown;
 mod mind_glue;
 mod mind_host_render;
@@ -85,9 +87,14 @@ mod overseer_worker_render;
 mod overview;
 mod overview_support;
 mod render_host;
+mod route_review;
+mod search;
+mod session_tree;
 mod shared_render;
 mod source_parse;
+mod task_timeline;
 mod theme;
+mod usage;
 mod wire;
 mod work;
 
@@ -101,6 +108,7 @@ use aoc_mind::{
     MindLaneFilter, MindObserverRow,
 };
 pub(crate) use app::*;
+pub(crate) use artifact_browser::*;
 use collectors::*;
 use config::*;
 pub(crate) use diff::*;
@@ -108,16 +116,22 @@ pub(crate) use fleet::*;
 pub(crate) use health::*;
 use hub::*;
 use input::*;
+pub(crate) use job_queue::*;
 pub(crate) use mind_glue::*;
 use ops::*;
 pub(crate) use overseer::*;
 pub(crate) use overview::*;
 pub(crate) use overview_support::*;
 pub(crate) use render_host::*;
+pub(crate) use route_review::*;
+pub(crate) use search::*;
+pub(crate) use session_tree::*;
 pub(crate) use shared_render::*;
 pub(crate) use source_parse::*;
+pub(crate) use task_timeline::*;
 pub(crate) use theme::*;
 use tracing::{debug, info, warn};
+pub(crate) use usage::*;
 use wire::*;
 pub(crate) use work::*;
 
@@ -653,6 +667,13 @@ enum Mode {
     Work,
     Diff,
     Health,
+    Artifacts,
+    Jobs,
+    Routes,
+    Tasks,
+    Sessions,
+    Usage,
+    Search,
 }
 
 impl Mode {
@@ -665,6 +686,13 @@ impl Mode {
             Mode::Work => "Work",
             Mode::Diff => "Diff",
             Mode::Health => "Health",
+            Mode::Artifacts => "Artifacts",
+            Mode::Jobs => "Jobs",
+            Mode::Routes => "Routes",
+            Mode::Tasks => "Tasks",
+            Mode::Sessions => "Sessions",
+            Mode::Usage => "Usage",
+            Mode::Search => "Search",
         }
     }
 
@@ -676,7 +704,14 @@ impl Mode {
             Mode::Fleet => Mode::Work,
             Mode::Work => Mode::Diff,
             Mode::Diff => Mode::Health,
-            Mode::Health => Mode::Overview,
+            Mode::Health => Mode::Artifacts,
+            Mode::Artifacts => Mode::Jobs,
+            Mode::Jobs => Mode::Routes,
+            Mode::Routes => Mode::Tasks,
+            Mode::Tasks => Mode::Sessions,
+            Mode::Sessions => Mode::Usage,
+            Mode::Usage => Mode::Search,
+            Mode::Search => Mode::Overview,
         }
     }
 }
@@ -802,6 +837,12 @@ struct App {
     mind_search_query: String,
     mind_search_editing: bool,
     mind_search_selected: usize,
+    artifact_browser: ArtifactBrowserState,
+    job_queue: JobQueueState,
+    route_review: RouteReviewState,
+    task_timeline: TaskTimelineState,
+    session_tree: SessionTreeState,
+    search: SearchState,
     status_note: Option<String>,
     pending_commands: HashMap<String, PendingCommand>,
     pending_consultations: HashMap<String, PendingConsultation>,
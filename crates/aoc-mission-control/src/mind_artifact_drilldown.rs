@@ -367,6 +367,7 @@ pub(crate) fn latest_compaction_t1_state(
         .iter()
         .filter(|row| {
             mind_event_lane(&row.event) == MindLaneFilter::T1
+                && row.event.status != MindObserverFeedStatus::Breaker
                 && row.event.trigger == MindObserverFeedTriggerKind::Compaction
                 && event_observed_ms(&row.event)
                     .map(|ts| ts >= checkpoint_ms.saturating_sub(1_000))
@@ -397,6 +398,7 @@ pub(crate) fn latest_compaction_t1_state(
                 CompactionT1State { label: "pending" }
             }
             MindObserverFeedStatus::Error => CompactionT1State { label: "error" },
+            MindObserverFeedStatus::Breaker => CompactionT1State { label: "unknown" },
         })
         .unwrap_or(CompactionT1State { label: "unknown" })
 }
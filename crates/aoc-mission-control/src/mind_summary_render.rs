@@ -109,7 +109,7 @@ pub(crate) fn render_mind_summary_lines(
         ),
         Span::raw(" "),
         Span::styled(
-            format!("fb:{}", status_rollup.fallback),
+            status_rollup.fallback_label(),
             Style::default().fg(theme.warn),
         ),
         Span::raw(" "),
@@ -34,6 +34,13 @@ fn render_body(app: &App, theme: MissionTheme, width: u16) -> Paragraph<'static>
         Mode::Work => render_work_lines(app, theme, compact),
         Mode::Diff => render_diff_lines(app, theme, compact, width),
         Mode::Health => render_health_lines(app, theme, compact),
+        Mode::Artifacts => render_artifact_browser_lines(app, theme, compact),
+        Mode::Jobs => render_job_queue_lines(app, theme, compact),
+        Mode::Routes => render_route_review_lines(app, theme, compact),
+        Mode::Tasks => render_task_timeline_lines(app, theme, compact),
+        Mode::Sessions => render_session_tree_lines(app, theme, compact),
+        Mode::Usage => render_usage_lines(app, theme, compact),
+        Mode::Search => render_search_lines(app, theme, compact),
     };
     let panel_title = if app.mode == Mode::Mind {
         "✦ Mind / Insight".to_string()
@@ -41,6 +48,20 @@ fn render_body(app: &App, theme: MissionTheme, width: u16) -> Paragraph<'static>
         "Detached Fleet".to_string()
     } else if app.mode == Mode::Overseer {
         "Session Overseer".to_string()
+    } else if app.mode == Mode::Artifacts {
+        "Artifact Browser".to_string()
+    } else if app.mode == Mode::Jobs {
+        "Mind Job Queue".to_string()
+    } else if app.mode == Mode::Routes {
+        "Segment Route Review".to_string()
+    } else if app.mode == Mode::Tasks {
+        "Task Timeline".to_string()
+    } else if app.mode == Mode::Sessions {
+        "Conversation Tree".to_string()
+    } else if app.mode == Mode::Usage {
+        "Semantic Cost & Budget".to_string()
+    } else if app.mode == Mode::Search {
+        "Mind Search".to_string()
     } else {
         app.mode.title().to_string()
     };
@@ -89,10 +110,14 @@ fn render_help_overlay(frame: &mut ratatui::Frame, app: &App, theme: MissionThem
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(if app.config.overview_enabled {
-            "  1/2/3/4/5/6/7 switch mode (Overview/Overseer/Mind/Fleet/Work/Diff/Health)"
+            "  1-9, 0   switch mode (Overview/Overseer/Mind/Fleet/Work/Diff/Health/Artifacts/Jobs/Routes)"
         } else {
-            "  2/3/4/5/6/7 switch mode (Overseer/Mind/Fleet/Work/Diff/Health)"
+            "  2-9, 0   switch mode (Overseer/Mind/Fleet/Work/Diff/Health/Artifacts/Jobs/Routes)"
         }),
+        Line::from("  T        task timeline"),
+        Line::from("  L        conversation/session tree"),
+        Line::from("  U        semantic cost and tag budgets"),
+        Line::from("  G        search artifacts and decisions"),
         Line::from("  Tab      cycle mode"),
         Line::from("  r        refresh local snapshot"),
         Line::from(""),
@@ -257,5 +282,92 @@ fn mode_help_lines(app: &App, theme: MissionTheme) -> Vec<Line<'static>> {
             Line::from("  j/k      scroll dependency checks"),
             Line::from("  g        jump to top"),
         ],
+        Mode::Artifacts => vec![
+            Line::from(Span::styled(
+                "Artifact Browser Mode",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from("  j/k      select artifact or trace row"),
+            Line::from("  Enter    open selected artifact / drill into trace"),
+            Line::from("  Bksp/Esc back up one trace level"),
+            Line::from("  [ / ]    previous / next page of artifacts"),
+            Line::from("  /        edit filter (tag:<tag> seg:<segment> since:7d text)"),
+            Line::from("  g        back to first page of the list"),
+        ],
+        Mode::Jobs => vec![
+            Line::from(Span::styled(
+                "Job Queue Mode",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from("  j/k      select reflector / T3 job"),
+            Line::from("  u        requeue selected failed, cancelled, or stuck job"),
+            Line::from("  x        cancel selected job"),
+            Line::from("  d        move selected job to dead-letter (failed)"),
+            Line::from("  f        toggle completed/cancelled jobs"),
+            Line::from("  g        jump to top"),
+        ],
+        Mode::Routes => vec![
+            Line::from(Span::styled(
+                "Route Review Mode",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from("  j/k      select uncertain artifact"),
+            Line::from("  [ / ]    select segment candidate"),
+            Line::from("  a        accept selected candidate as override"),
+            Line::from("  /        type a segment, Enter to apply as override"),
+            Line::from("  g        jump to top of the queue"),
+        ],
+        Mode::Tasks => vec![
+            Line::from(Span::styled(
+                "Task Timeline Mode",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from("  j/k      select task or timeline entry"),
+            Line::from("  Enter    open task / expand entry evidence"),
+            Line::from("  Esc      back to recent tasks"),
+            Line::from("  /        type a task id (107, #107, task:107)"),
+        ],
+        Mode::Sessions => vec![
+            Line::from(Span::styled(
+                "Session Tree Mode",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from("  j/k      select conversation branch"),
+            Line::from("  Enter    enqueue observer backfill for the branch"),
+            Line::from("  A        enqueue backfill for every stale branch in the session"),
+            Line::from("  g        jump to top"),
+        ],
+        Mode::Usage => vec![
+            Line::from(Span::styled(
+                "Usage Mode",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from("  j/k      scroll usage report"),
+        ],
+        Mode::Search => vec![
+            Line::from(Span::styled(
+                "Search Mode",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from("  /        edit query; results update as you type"),
+            Line::from("  chips    tag:<tag> seg:<segment> kind:t1|t2|decision since:7d"),
+            Line::from("  j/k      select hit"),
+            Line::from("  Enter    open the artifact hit in the artifact browser"),
+            Line::from("  g        jump to top"),
+        ],
     }
 }
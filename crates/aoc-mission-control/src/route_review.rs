@@ -0,0 +1,253 @@
+//! Segment route review surface.
+//!
+//! Works the uncertain-route queue: shows each parked artifact with its scored
+//! segment candidates and their evidence, and resolves it by accepting a
+//! candidate or typing a segment, which stores a manual override route.
+
+use super::*;
+use aoc_segment_routing::{
+    RouteOverridePatch, RouteReview, RoutingError, SegmentRouter, SegmentRoutingConfig,
+};
+use aoc_storage::MindStore;
+
+pub(crate) const ROUTE_REVIEW_QUEUE_LIMIT: usize = 100;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct RouteReviewState {
+    pub(crate) selected: usize,
+    pub(crate) candidate: usize,
+    pub(crate) segment_input: String,
+    pub(crate) editing: bool,
+}
+
+pub(crate) fn route_review_router() -> SegmentRouter {
+    SegmentRouter::new(SegmentRoutingConfig::default())
+}
+
+pub(crate) fn load_route_review_queue(store: &MindStore) -> Vec<String> {
+    route_review_router()
+        .uncertain_queue(store, ROUTE_REVIEW_QUEUE_LIMIT)
+        .unwrap_or_default()
+}
+
+pub(crate) fn load_selected_route_review(
+    store: &MindStore,
+    state: &RouteReviewState,
+) -> Option<RouteReview> {
+    let queue = load_route_review_queue(store);
+    let artifact_id = queue.get(state.selected.min(queue.len().checked_sub(1)?))?;
+    route_review_router()
+        .review_artifact(store, artifact_id)
+        .ok()
+        .flatten()
+}
+
+/// Stores a reviewer decision as a manual override on the artifact's route.
+pub(crate) fn apply_route_review_override(
+    store: &MindStore,
+    artifact_id: &str,
+    segment_id: &str,
+    reason: &str,
+    now: DateTime<Utc>,
+) -> Result<String, RoutingError> {
+    let patch = RouteOverridePatch {
+        patch_id: format!("review:{}", now.format("%Y%m%dT%H%M%SZ")),
+        primary_segment: segment_id.to_string(),
+        reason: reason.to_string(),
+        ..RouteOverridePatch::default()
+    };
+    let route = route_review_router().override_artifact(store, artifact_id, &patch)?;
+    Ok(route.primary.segment_id)
+}
+
+pub(crate) fn render_route_review_lines(
+    app: &App,
+    theme: MissionTheme,
+    compact: bool,
+) -> Vec<Line<'static>> {
+    let state = &app.route_review;
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            "Route review",
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            if state.editing {
+                "[editing segment · Enter apply · Esc cancel]"
+            } else {
+                "[j/k artifact · [/] candidate · a accept · / type segment]"
+            },
+            Style::default().fg(theme.muted),
+        ),
+    ])];
+
+    let Some(store) = open_project_mind_store(&app.config.project_root) else {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                "No project Mind store yet.",
+                Style::default().fg(theme.warn),
+            ),
+        ]));
+        return lines;
+    };
+    let queue = load_route_review_queue(&store);
+    if queue.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                "Uncertain-route queue is empty.",
+                Style::default().fg(theme.ok),
+            ),
+        ]));
+        return lines;
+    }
+    let selected = state.selected.min(queue.len() - 1);
+    lines.push(Line::from(vec![
+        Span::raw("  -> "),
+        Span::styled(
+            format!("{} uncertain artifacts", queue.len()),
+            Style::default().fg(theme.muted),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("selected:{}/{}", selected + 1, queue.len()),
+            Style::default().fg(theme.info),
+        ),
+    ]));
+    for (index, artifact_id) in queue.iter().enumerate() {
+        let is_selected = index == selected;
+        let preview = store
+            .artifact_by_id(artifact_id)
+            .ok()
+            .flatten()
+            .map(|artifact| {
+                artifact
+                    .text
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(
+                if is_selected { "  >> " } else { "  • " },
+                Style::default().fg(if is_selected {
+                    theme.accent
+                } else {
+                    theme.muted
+                }),
+            ),
+            Span::styled(
+                ellipsize(artifact_id, if compact { 18 } else { 28 }),
+                Style::default().fg(if is_selected {
+                    theme.text
+                } else {
+                    theme.accent
+                }),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                ellipsize(&preview, if compact { 36 } else { 80 }),
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+    }
+
+    let Some(review) = load_selected_route_review(&store, state) else {
+        return lines;
+    };
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!("[{}]", review.artifact.kind),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            review.artifact.artifact_id.clone(),
+            Style::default().fg(theme.text),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!(
+                "conv:{} @{}",
+                review.artifact.conversation_id,
+                review.artifact.ts.to_rfc3339()
+            ),
+            Style::default().fg(theme.muted),
+        ),
+    ]));
+    for text_line in review.artifact.text.lines() {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(text_line.to_string(), Style::default().fg(theme.text)),
+        ]));
+    }
+    if let Some(route) = review.route.as_ref() {
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled("route:", Style::default().fg(theme.muted)),
+            Span::raw(" "),
+            Span::styled(
+                ellipsize(&route.reason, if compact { 60 } else { 120 }),
+                Style::default().fg(theme.warn),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(Span::styled(
+        format!("  candidates ({})", review.candidates.len()),
+        Style::default().fg(theme.title),
+    )));
+    let chosen = state
+        .candidate
+        .min(review.candidates.len().saturating_sub(1));
+    for (index, candidate) in review.candidates.iter().enumerate() {
+        let is_chosen = index == chosen;
+        lines.push(Line::from(vec![
+            Span::styled(
+                if is_chosen { "  >> " } else { "  • " },
+                Style::default().fg(if is_chosen { theme.accent } else { theme.muted }),
+            ),
+            Span::styled(
+                format!("{:<12}", candidate.segment_id),
+                Style::default().fg(if is_chosen { theme.text } else { theme.info }),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                format!("score:{}", candidate.confidence_bps),
+                Style::default().fg(theme.muted),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                ellipsize(
+                    &candidate.evidence.join(", "),
+                    if compact { 36 } else { 72 },
+                ),
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+    }
+    if state.editing || !state.segment_input.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled("segment:", Style::default().fg(theme.muted)),
+            Span::raw(" "),
+            Span::styled(
+                if state.editing {
+                    format!("> {}_", state.segment_input)
+                } else {
+                    format!("> {}", state.segment_input)
+                },
+                Style::default().fg(theme.accent),
+            ),
+        ]));
+    }
+    lines
+}
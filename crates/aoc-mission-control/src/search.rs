@@ -0,0 +1,312 @@
+//! Full-text search surface.
+//!
+//! Searches T1/T2 artifacts and the decision log as the query is typed.
+//! `tag:`, `seg:`, `kind:` and `since:` tokens become filter chips; the rest
+//! must all appear in the hit text. Enter on an artifact hit opens it in the
+//! artifact browser with its trace drilldown.
+
+use super::*;
+use aoc_storage::{ArtifactQuery, MindStore};
+
+pub(crate) const SEARCH_RESULT_LIMIT: usize = 40;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct SearchState {
+    pub(crate) query: String,
+    pub(crate) editing: bool,
+    pub(crate) selected: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum SearchKind {
+    #[default]
+    All,
+    Observation,
+    Reflection,
+    Decision,
+}
+
+impl SearchKind {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "t1" | "obs" | "observation" => Some(Self::Observation),
+            "t2" | "ref" | "reflection" => Some(Self::Reflection),
+            "decision" | "dec" => Some(Self::Decision),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+
+    fn artifact_kind(self) -> Option<&'static str> {
+        match self {
+            Self::Observation => Some("t1"),
+            Self::Reflection => Some("t2"),
+            Self::All | Self::Decision => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct SearchFilter {
+    pub(crate) artifacts: ArtifactQuery,
+    pub(crate) kind: SearchKind,
+    /// Recognised filter tokens, shown as chips above the results.
+    pub(crate) chips: Vec<String>,
+}
+
+impl SearchFilter {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.chips.is_empty() && self.artifacts.text.is_none()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SearchHit {
+    /// `t1`, `t2`, or `decision`.
+    pub(crate) kind: &'static str,
+    pub(crate) id: String,
+    pub(crate) ts: DateTime<Utc>,
+    /// Conversation for artifacts, segment for decisions.
+    pub(crate) context: String,
+    pub(crate) text: String,
+    pub(crate) superseded: bool,
+}
+
+pub(crate) fn parse_search_query(query: &str, now: DateTime<Utc>) -> SearchFilter {
+    let mut kind = SearchKind::All;
+    let mut kind_chip = None;
+    let mut rest = Vec::new();
+    for token in query.split_whitespace() {
+        match token.strip_prefix("kind:").and_then(SearchKind::parse) {
+            Some(parsed) => {
+                kind = parsed;
+                kind_chip = (parsed != SearchKind::All).then(|| token.to_string());
+            }
+            None => rest.push(token),
+        }
+    }
+    let mut artifacts = parse_artifact_browser_filter(&rest.join(" "), now);
+    artifacts.kind = kind.artifact_kind().map(str::to_string);
+
+    let mut chips = Vec::new();
+    if let Some(tag) = &artifacts.active_tag {
+        chips.push(format!("tag:{tag}"));
+    }
+    if let Some(segment) = &artifacts.segment_id {
+        chips.push(format!("seg:{segment}"));
+    }
+    chips.extend(kind_chip);
+    if artifacts.since.is_some() {
+        chips.extend(
+            rest.iter()
+                .rev()
+                .find(|token| token.starts_with("since:"))
+                .map(|token| token.to_string()),
+        );
+    }
+    SearchFilter {
+        artifacts,
+        kind,
+        chips,
+    }
+}
+
+/// Artifact and decision hits merged newest first. Decisions carry no tag, so
+/// a `tag:` chip leaves them out.
+pub(crate) fn load_search_hits(store: &MindStore, filter: &SearchFilter) -> Vec<SearchHit> {
+    if filter.is_empty() {
+        return Vec::new();
+    }
+    let mut hits = Vec::new();
+    if filter.kind != SearchKind::Decision {
+        let page = store
+            .query_artifacts(&ArtifactQuery {
+                limit: SEARCH_RESULT_LIMIT,
+                ..filter.artifacts.clone()
+            })
+            .unwrap_or_default();
+        hits.extend(page.artifacts.into_iter().map(|artifact| SearchHit {
+            kind: if artifact.kind == "t2" { "t2" } else { "t1" },
+            id: artifact.artifact_id,
+            ts: artifact.ts,
+            context: artifact.conversation_id,
+            text: artifact.text,
+            superseded: false,
+        }));
+    }
+    if matches!(filter.kind, SearchKind::All | SearchKind::Decision)
+        && filter.artifacts.active_tag.is_none()
+    {
+        let decisions = store
+            .search_mem_decisions(
+                filter.artifacts.text.as_deref().unwrap_or_default(),
+                filter.artifacts.segment_id.as_deref(),
+                filter.artifacts.since,
+                SEARCH_RESULT_LIMIT,
+            )
+            .unwrap_or_default();
+        hits.extend(decisions.into_iter().map(|decision| {
+            SearchHit {
+                kind: "decision",
+                superseded: store
+                    .mem_decision_successor(&decision.decision_id)
+                    .ok()
+                    .flatten()
+                    .is_some(),
+                id: decision.decision_id,
+                ts: decision.ts,
+                context: decision
+                    .segment_id
+                    .unwrap_or_else(|| decision.project_id.clone()),
+                text: decision.text,
+            }
+        }));
+    }
+    hits.sort_by(|left, right| right.ts.cmp(&left.ts).then(left.id.cmp(&right.id)));
+    hits.truncate(SEARCH_RESULT_LIMIT);
+    hits
+}
+
+pub(crate) fn render_search_lines(
+    app: &App,
+    theme: MissionTheme,
+    compact: bool,
+) -> Vec<Line<'static>> {
+    let state = &app.search;
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            "Search",
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            if state.editing {
+                "[typing · Enter/Esc done]"
+            } else {
+                "[/ edit · j/k select · Enter open]"
+            },
+            Style::default().fg(theme.muted),
+        ),
+    ])];
+    let query = state.query.trim();
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        Span::styled("query:", Style::default().fg(theme.muted)),
+        Span::raw(" "),
+        Span::styled(
+            match (query.is_empty(), state.editing) {
+                (true, false) => {
+                    "(tag:<tag> seg:<segment> kind:t1|t2|decision since:7d text)".to_string()
+                }
+                (_, true) => format!("> {query}_"),
+                (false, false) => format!("> {query}"),
+            },
+            Style::default().fg(if query.is_empty() {
+                theme.muted
+            } else {
+                theme.accent
+            }),
+        ),
+    ]));
+
+    let filter = parse_search_query(query, Utc::now());
+    if !filter.chips.is_empty() {
+        let mut chips = vec![Span::raw("  ")];
+        for chip in &filter.chips {
+            chips.push(Span::styled(
+                format!(" {chip} "),
+                Style::default()
+                    .fg(theme.info)
+                    .add_modifier(Modifier::REVERSED),
+            ));
+            chips.push(Span::raw(" "));
+        }
+        lines.push(Line::from(chips));
+    }
+
+    let Some(store) = open_project_mind_store(&app.config.project_root) else {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                "No project Mind store yet.",
+                Style::default().fg(theme.warn),
+            ),
+        ]));
+        return lines;
+    };
+    if filter.is_empty() {
+        return lines;
+    }
+    let hits = load_search_hits(&store, &filter);
+    if hits.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled("No matches.", Style::default().fg(theme.muted)),
+        ]));
+        return lines;
+    }
+
+    lines.push(Line::from(vec![
+        Span::raw("  -> "),
+        Span::styled(
+            if hits.len() == SEARCH_RESULT_LIMIT {
+                format!("{}+ hits", hits.len())
+            } else {
+                format!("{} hits", hits.len())
+            },
+            Style::default().fg(theme.muted),
+        ),
+    ]));
+    let selected = state.selected.min(hits.len() - 1);
+    let text_budget = if compact { 40 } else { 88 };
+    for (index, hit) in hits.iter().enumerate() {
+        let is_selected = index == selected;
+        let mut spans = vec![
+            Span::styled(
+                if is_selected { "  >> " } else { "  • " },
+                Style::default().fg(if is_selected {
+                    theme.accent
+                } else {
+                    theme.muted
+                }),
+            ),
+            Span::styled(
+                format!("[{}]", hit.kind),
+                Style::default().fg(if hit.kind == "decision" {
+                    theme.ok
+                } else {
+                    theme.info
+                }),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                hit.ts.format("%m-%d %H:%M").to_string(),
+                Style::default().fg(theme.muted),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                ellipsize(&hit.context, if compact { 14 } else { 24 }),
+                Style::default().fg(theme.muted),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                ellipsize(&flatten_text(&hit.text), text_budget),
+                Style::default().fg(if is_selected {
+                    theme.text
+                } else {
+                    theme.accent
+                }),
+            ),
+        ];
+        if hit.superseded {
+            spans.push(Span::styled(
+                " (superseded)",
+                Style::default().fg(theme.warn),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
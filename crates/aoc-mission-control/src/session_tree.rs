@@ -0,0 +1,210 @@
+//! Conversation/session tree surface.
+//!
+//! Renders `conversation_lineage` as root → branch trees grouped by session,
+//! with per-conversation tier counts and an observer backlog marker for
+//! branches whose newest T0 is ahead of their newest T1. Backfill keys send
+//! `run_observer` for those branches to an agent in the owning session.
+
+use super::*;
+use aoc_storage::{ConversationActivity, ConversationLineage, MindStore};
+
+pub(crate) const SESSION_TREE_LINEAGE_LIMIT: usize = 500;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct SessionTreeState {
+    pub(crate) selected: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SessionTreeNode {
+    pub(crate) lineage: ConversationLineage,
+    pub(crate) depth: usize,
+    pub(crate) activity: ConversationActivity,
+    /// Newer T0 than T1: the observer has not caught up on this branch.
+    pub(crate) stale: bool,
+}
+
+/// Nodes flattened depth-first, sessions with the newest activity first.
+pub(crate) fn load_session_tree(store: &MindStore) -> Vec<SessionTreeNode> {
+    let lineage = store
+        .list_conversation_lineage(SESSION_TREE_LINEAGE_LIMIT)
+        .unwrap_or_default();
+    let mut sessions = Vec::<(String, Vec<ConversationLineage>)>::new();
+    for row in lineage {
+        match sessions
+            .iter_mut()
+            .find(|(session_id, _)| *session_id == row.session_id)
+        {
+            Some((_, rows)) => rows.push(row),
+            None => sessions.push((row.session_id.clone(), vec![row])),
+        }
+    }
+
+    let mut nodes = Vec::new();
+    for (_, mut rows) in sessions {
+        rows.sort_by(|left, right| left.conversation_id.cmp(&right.conversation_id));
+        let known = rows
+            .iter()
+            .map(|row| row.conversation_id.clone())
+            .collect::<HashSet<_>>();
+        // Branches whose parent fell outside the listing limit are shown as roots.
+        let mut stack = rows
+            .iter()
+            .filter(|row| {
+                row.parent_conversation_id
+                    .as_ref()
+                    .is_none_or(|parent| !known.contains(parent))
+            })
+            .rev()
+            .map(|row| (row.clone(), 0))
+            .collect::<Vec<_>>();
+        while let Some((row, depth)) = stack.pop() {
+            stack.extend(
+                rows.iter()
+                    .filter(|child| {
+                        child.parent_conversation_id.as_deref() == Some(&row.conversation_id)
+                    })
+                    .rev()
+                    .map(|child| (child.clone(), depth + 1)),
+            );
+            let activity = store
+                .conversation_activity(&row.conversation_id)
+                .unwrap_or_default();
+            let stale = store
+                .conversation_needs_observer_run(&row.conversation_id)
+                .unwrap_or(false);
+            nodes.push(SessionTreeNode {
+                lineage: row,
+                depth,
+                activity,
+                stale,
+            });
+        }
+    }
+    nodes
+}
+
+pub(crate) fn render_session_tree_lines(
+    app: &App,
+    theme: MissionTheme,
+    compact: bool,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            "Session tree",
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            "[j/k branch · Enter backfill branch · A backfill stale in session]",
+            Style::default().fg(theme.muted),
+        ),
+    ])];
+
+    let Some(store) = open_project_mind_store(&app.config.project_root) else {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                "No project Mind store yet.",
+                Style::default().fg(theme.warn),
+            ),
+        ]));
+        return lines;
+    };
+    let nodes = load_session_tree(&store);
+    if nodes.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                "No conversation lineage recorded yet.",
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+        return lines;
+    }
+
+    let now = Utc::now();
+    let selected = app.session_tree.selected.min(nodes.len() - 1);
+    let id_budget = if compact { 18 } else { 32 };
+    for (index, node) in nodes.iter().enumerate() {
+        let session_id = &node.lineage.session_id;
+        if index == 0 || nodes[index - 1].lineage.session_id != *session_id {
+            let members = nodes
+                .iter()
+                .filter(|other| other.lineage.session_id == *session_id)
+                .collect::<Vec<_>>();
+            let stale = members.iter().filter(|member| member.stale).count();
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("session {session_id}"),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    format!("conversations:{}", members.len()),
+                    Style::default().fg(theme.muted),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    format!("backlog:{stale}"),
+                    Style::default().fg(if stale > 0 { theme.warn } else { theme.ok }),
+                ),
+            ]));
+        }
+
+        let is_selected = index == selected;
+        let activity = &node.activity;
+        let mut spans = vec![
+            Span::styled(
+                if is_selected { "  >> " } else { "     " },
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(
+                format!(
+                    "{}{}",
+                    "  ".repeat(node.depth),
+                    if node.depth == 0 { "● " } else { "└ " }
+                ),
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled(
+                ellipsize(&node.lineage.conversation_id, id_budget),
+                Style::default().fg(if is_selected { theme.text } else { theme.info }),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                format!(
+                    "raw:{} t0:{} t1:{} t2:{}",
+                    activity.raw_events,
+                    activity.t0_events,
+                    activity.t1_observations,
+                    activity.t2_reflections
+                ),
+                Style::default().fg(theme.muted),
+            ),
+        ];
+        if !compact {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                match activity.last_activity {
+                    Some(last) => format!("last:{}", job_age_label((now - last).num_seconds())),
+                    None => "last:-".to_string(),
+                },
+                Style::default().fg(theme.muted),
+            ));
+        }
+        if node.stale {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                "⚠ observer backlog",
+                Style::default().fg(theme.warn),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
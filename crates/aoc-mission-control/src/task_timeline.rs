@@ -0,0 +1,394 @@
+//! Task attribution timeline surface.
+//!
+//! Answers "what happened on task N": every artifact linked to the task in
+//! time order, with the sessions they came from and completion links
+//! highlighted. Enter expands the selected entry into its full text and
+//! resolved evidence events.
+
+use super::*;
+use aoc_core::mind_contracts::{ArtifactTaskLink, ArtifactTaskRelation};
+use aoc_storage::{MindStore, StoredArtifact};
+
+pub(crate) const TASK_TIMELINE_RECENT_LIMIT: usize = 30;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct TaskTimelineState {
+    /// Task being inspected; `None` shows the recent-task picker.
+    pub(crate) task_id: Option<String>,
+    pub(crate) input: String,
+    pub(crate) editing: bool,
+    pub(crate) selected: usize,
+    pub(crate) expanded: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TaskTimelineEntry {
+    pub(crate) link: ArtifactTaskLink,
+    pub(crate) artifact: Option<StoredArtifact>,
+    pub(crate) session_id: Option<String>,
+}
+
+/// Accepts `107`, `#107` or `task:107`.
+pub(crate) fn normalize_task_timeline_id(value: &str) -> Option<String> {
+    let value = value.trim();
+    let value = value.strip_prefix("task:").unwrap_or(value);
+    let value = value.strip_prefix('#').unwrap_or(value).trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+pub(crate) fn load_task_timeline(store: &MindStore, task_id: &str) -> Vec<TaskTimelineEntry> {
+    let mut sessions = BTreeMap::<String, Option<String>>::new();
+    store
+        .artifact_task_links_for_task(task_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|link| {
+            let artifact = store.artifact_by_id(&link.artifact_id).ok().flatten();
+            let session_id = artifact.as_ref().and_then(|artifact| {
+                sessions
+                    .entry(artifact.conversation_id.clone())
+                    .or_insert_with(|| {
+                        store
+                            .conversation_lineage(&artifact.conversation_id)
+                            .ok()
+                            .flatten()
+                            .map(|lineage| lineage.session_id)
+                    })
+                    .clone()
+            });
+            TaskTimelineEntry {
+                link,
+                artifact,
+                session_id,
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn task_timeline_selection_len(store: &MindStore, state: &TaskTimelineState) -> usize {
+    match state.task_id.as_deref() {
+        Some(task_id) => load_task_timeline(store, task_id).len(),
+        None => store
+            .recent_linked_task_ids(TASK_TIMELINE_RECENT_LIMIT)
+            .map(|ids| ids.len())
+            .unwrap_or_default(),
+    }
+}
+
+fn relation_label(relation: ArtifactTaskRelation) -> &'static str {
+    match relation {
+        ArtifactTaskRelation::Active => "active",
+        ArtifactTaskRelation::WorkedOn => "worked_on",
+        ArtifactTaskRelation::Mentioned => "mentioned",
+        ArtifactTaskRelation::Completed => "completed",
+    }
+}
+
+fn relation_color(relation: ArtifactTaskRelation, theme: MissionTheme) -> Color {
+    match relation {
+        ArtifactTaskRelation::Completed => theme.ok,
+        ArtifactTaskRelation::Active => theme.accent,
+        ArtifactTaskRelation::WorkedOn => theme.info,
+        ArtifactTaskRelation::Mentioned => theme.muted,
+    }
+}
+
+pub(crate) fn render_task_timeline_lines(
+    app: &App,
+    theme: MissionTheme,
+    compact: bool,
+) -> Vec<Line<'static>> {
+    let state = &app.task_timeline;
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            "Task timeline",
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            match (&state.task_id, state.editing) {
+                (_, true) => "[editing task · Enter open · Esc cancel]",
+                (Some(_), false) => "[j/k entry · Enter evidence · Esc tasks · / task]",
+                (None, false) => "[j/k task · Enter open · / task]",
+            },
+            Style::default().fg(theme.muted),
+        ),
+    ])];
+    if state.editing {
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled("task:", Style::default().fg(theme.muted)),
+            Span::raw(" "),
+            Span::styled(
+                format!("> {}_", state.input),
+                Style::default().fg(theme.accent),
+            ),
+        ]));
+    }
+
+    let Some(store) = open_project_mind_store(&app.config.project_root) else {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                "No project Mind store yet.",
+                Style::default().fg(theme.warn),
+            ),
+        ]));
+        return lines;
+    };
+    match state.task_id.as_deref() {
+        Some(task_id) => render_task_entries(&mut lines, &store, state, task_id, theme, compact),
+        None => render_recent_tasks(&mut lines, &store, state, theme),
+    }
+    lines
+}
+
+fn render_recent_tasks(
+    lines: &mut Vec<Line<'static>>,
+    store: &MindStore,
+    state: &TaskTimelineState,
+    theme: MissionTheme,
+) {
+    let task_ids = store
+        .recent_linked_task_ids(TASK_TIMELINE_RECENT_LIMIT)
+        .unwrap_or_default();
+    if task_ids.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                "No task-linked artifacts yet.",
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+        return;
+    }
+    lines.push(Line::from(Span::styled(
+        format!("  recent tasks ({})", task_ids.len()),
+        Style::default().fg(theme.title),
+    )));
+    let selected = state.selected.min(task_ids.len() - 1);
+    for (index, task_id) in task_ids.iter().enumerate() {
+        let is_selected = index == selected;
+        let links = store
+            .artifact_task_links_for_task(task_id)
+            .unwrap_or_default();
+        let completed = links
+            .iter()
+            .any(|link| link.relation == ArtifactTaskRelation::Completed);
+        lines.push(Line::from(vec![
+            Span::styled(
+                if is_selected { "  >> " } else { "  • " },
+                Style::default().fg(if is_selected {
+                    theme.accent
+                } else {
+                    theme.muted
+                }),
+            ),
+            Span::styled(
+                format!("#{task_id}"),
+                Style::default().fg(if is_selected {
+                    theme.text
+                } else {
+                    theme.accent
+                }),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                format!("links:{}", links.len()),
+                Style::default().fg(theme.muted),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                if completed { "completed" } else { "open" },
+                Style::default().fg(if completed { theme.ok } else { theme.info }),
+            ),
+        ]));
+    }
+}
+
+fn render_task_entries(
+    lines: &mut Vec<Line<'static>>,
+    store: &MindStore,
+    state: &TaskTimelineState,
+    task_id: &str,
+    theme: MissionTheme,
+    compact: bool,
+) {
+    let entries = load_task_timeline(store, task_id);
+    if entries.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                format!("No artifacts linked to task #{task_id}."),
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+        return;
+    }
+
+    let mut sessions = BTreeMap::<&str, usize>::new();
+    for entry in &entries {
+        *sessions
+            .entry(entry.session_id.as_deref().unwrap_or("unknown"))
+            .or_default() += 1;
+    }
+    let completions = entries
+        .iter()
+        .filter(|entry| entry.link.relation == ArtifactTaskRelation::Completed)
+        .count();
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        Span::styled(
+            format!("#{task_id}"),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!(
+                "links:{} completions:{} sessions:{}",
+                entries.len(),
+                completions,
+                sessions.len()
+            ),
+            Style::default().fg(theme.muted),
+        ),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        Span::styled("sessions:", Style::default().fg(theme.muted)),
+        Span::raw(" "),
+        Span::styled(
+            sessions
+                .iter()
+                .map(|(session, count)| format!("{session} ({count})"))
+                .collect::<Vec<_>>()
+                .join(", "),
+            Style::default().fg(theme.info),
+        ),
+    ]));
+
+    let selected = state.selected.min(entries.len() - 1);
+    let id_budget = if compact { 18 } else { 28 };
+    let text_budget = if compact { 36 } else { 72 };
+    for (index, entry) in entries.iter().enumerate() {
+        let is_selected = index == selected;
+        let link = &entry.link;
+        let mut spans = vec![
+            Span::styled(
+                if is_selected { "  >> " } else { "  • " },
+                Style::default().fg(if is_selected {
+                    theme.accent
+                } else {
+                    theme.muted
+                }),
+            ),
+            Span::styled(
+                link.start_ts.format("%m-%d %H:%M").to_string(),
+                Style::default().fg(theme.muted),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                format!("{:<9}", relation_label(link.relation)),
+                Style::default().fg(relation_color(link.relation, theme)),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                ellipsize(&link.artifact_id, id_budget),
+                Style::default().fg(if is_selected {
+                    theme.text
+                } else {
+                    theme.accent
+                }),
+            ),
+        ];
+        if !compact {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                format!(
+                    "conf:{} session:{}",
+                    link.confidence_bps,
+                    entry.session_id.as_deref().unwrap_or("?")
+                ),
+                Style::default().fg(theme.muted),
+            ));
+        }
+        lines.push(Line::from(spans));
+        if !(is_selected && state.expanded) {
+            if let Some(artifact) = entry.artifact.as_ref() {
+                lines.push(Line::from(vec![
+                    Span::raw("      "),
+                    Span::styled(
+                        ellipsize(&flatten_text(&artifact.text), text_budget),
+                        Style::default().fg(theme.muted),
+                    ),
+                ]));
+            }
+            continue;
+        }
+
+        lines.push(Line::from(vec![
+            Span::raw("      "),
+            Span::styled(
+                format!(
+                    "source:{} conv:{}",
+                    link.source,
+                    entry
+                        .artifact
+                        .as_ref()
+                        .map(|artifact| artifact.conversation_id.as_str())
+                        .unwrap_or("?")
+                ),
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+        match entry.artifact.as_ref() {
+            Some(artifact) => {
+                for text_line in artifact.text.lines() {
+                    lines.push(Line::from(vec![
+                        Span::raw("      "),
+                        Span::styled(text_line.to_string(), Style::default().fg(theme.text)),
+                    ]));
+                }
+            }
+            None => lines.push(Line::from(vec![
+                Span::raw("      "),
+                Span::styled(
+                    "artifact no longer in store",
+                    Style::default().fg(theme.critical),
+                ),
+            ])),
+        }
+        lines.push(Line::from(Span::styled(
+            format!("      evidence ({})", link.evidence_event_ids.len()),
+            Style::default().fg(theme.title),
+        )));
+        for evidence_id in &link.evidence_event_ids {
+            let node = resolve_artifact_browser_node(store, evidence_id);
+            lines.push(Line::from(vec![
+                Span::raw("      • "),
+                Span::styled(
+                    format!("[{}]", node.kind_label()),
+                    Style::default().fg(if matches!(node, ArtifactBrowserNode::Missing(_)) {
+                        theme.critical
+                    } else {
+                        theme.info
+                    }),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    ellipsize(evidence_id, id_budget),
+                    Style::default().fg(theme.muted),
+                ),
+                Span::raw(" "),
+                Span::styled(
+                    ellipsize(&flatten_text(&node.summary()), text_budget),
+                    Style::default().fg(theme.text),
+                ),
+            ]));
+        }
+    }
+}
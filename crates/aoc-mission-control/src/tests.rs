@@ -1899,6 +1899,7 @@ fn render_mind_activity_summary_and_bridge_for_detached_jobs() {
             latency_ms: None,
             reason: Some("repo event".to_string()),
             failure_kind: None,
+            error_code: None,
             enqueued_at: None,
             started_at: None,
             completed_at: Some("2026-03-27T10:00:00Z".to_string()),
@@ -3114,6 +3115,7 @@ fn overseer_mode_adds_optional_mind_enrichment_without_blocking_base_render() {
                     "semantic observer timed out; using bounded heuristic summary".to_string(),
                 ),
                 failure_kind: Some("timeout".to_string()),
+                error_code: None,
                 enqueued_at: None,
                 started_at: None,
                 completed_at: Some("2026-03-09T10:45:00Z".to_string()),
@@ -3179,3 +3181,694 @@ fn overseer_mode_renders_mission_control_prompt_for_blocked_worker() {
     assert!(rendered.contains("ask for unblock plan + evidence-backed next step"));
     assert!(rendered.contains("src:partial"));
 }
+
+fn render_text(lines: &[Line<'static>]) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.to_string())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn artifact_browser_filters_and_drills_from_artifact_to_raw_event() {
+    use aoc_core::mind_contracts::{
+        compact_raw_event_to_t0, ConversationRole, MessageEvent, RawEvent, RawEventBody,
+        T0CompactionPolicy,
+    };
+
+    let (root, store_path) = fresh_test_mind_store("aoc-mission-control-artifact-browser");
+    let store = aoc_storage::MindStore::open(&store_path).expect("open store");
+    let raw = RawEvent {
+        event_id: "evt-browser-1".to_string(),
+        conversation_id: "conv-browser".to_string(),
+        agent_id: "agent-1".to_string(),
+        ts: Utc
+            .with_ymd_and_hms(2026, 3, 2, 9, 0, 0)
+            .single()
+            .expect("ts"),
+        body: RawEventBody::Message(MessageEvent {
+            role: ConversationRole::User,
+            text: "retry the flaky upload step".to_string(),
+        }),
+        attrs: Default::default(),
+    };
+    store.insert_raw_event(&raw).expect("insert raw");
+    let t0 = compact_raw_event_to_t0(&raw, &T0CompactionPolicy::default())
+        .expect("compact")
+        .expect("kept");
+    store.upsert_t0_compact_event(&t0).expect("upsert t0");
+    store
+        .insert_observation(
+            "obs:browser-retry",
+            "conv-browser",
+            raw.ts + chrono::Duration::minutes(1),
+            "User asked to retry the upload step",
+            std::slice::from_ref(&t0.compact_id),
+        )
+        .expect("insert observation");
+    store
+        .insert_observation(
+            "obs:browser-other",
+            "conv-other",
+            raw.ts,
+            "Unrelated planning note",
+            &[],
+        )
+        .expect("insert other observation");
+
+    let (tx, _rx) = mpsc::channel(4);
+    let mut config = test_config();
+    config.project_root = root.clone();
+    let mut app = App::new(config, tx, empty_local());
+    let mut refresh_requested = false;
+    let mut press = |app: &mut App, code: KeyCode| {
+        handle_key(
+            KeyEvent::new(code, KeyModifiers::NONE),
+            app,
+            &mut refresh_requested,
+        );
+    };
+    let theme = mission_theme(MissionThemeMode::Terminal);
+
+    press(&mut app, KeyCode::Char('8'));
+    assert_eq!(app.mode, Mode::Artifacts);
+    let rendered = render_text(&render_artifact_browser_lines(&app, theme, false));
+    assert!(rendered.contains("2 artifacts page:1/1"));
+    assert!(rendered.contains(">> [t1]"));
+    assert!(rendered.contains("Unrelated planning note"));
+
+    press(&mut app, KeyCode::Char('/'));
+    for ch in "upload".chars() {
+        press(&mut app, KeyCode::Char(ch));
+    }
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.artifact_browser.filter, "upload");
+    let rendered = render_text(&render_artifact_browser_lines(&app, theme, false));
+    assert!(rendered.contains("1 artifacts page:1/1"));
+    assert!(!rendered.contains("Unrelated planning note"));
+
+    press(&mut app, KeyCode::Enter);
+    let rendered = render_text(&render_artifact_browser_lines(&app, theme, false));
+    assert!(rendered.contains("[t1] obs:browser-retry conv:conv-browser"));
+    assert!(rendered.contains("traces (1)"));
+    assert!(rendered.contains(&format!(">> [t0] {}", t0.compact_id)));
+
+    press(&mut app, KeyCode::Enter);
+    let rendered = render_text(&render_artifact_browser_lines(&app, theme, false));
+    assert!(rendered.contains("role:user"));
+    assert!(rendered.contains("source events (1)"));
+    assert!(rendered.contains(">> [raw] evt-browser-1"));
+
+    press(&mut app, KeyCode::Enter);
+    let rendered = render_text(&render_artifact_browser_lines(&app, theme, false));
+    assert!(rendered.contains("[raw] evt-browser-1 conv:conv-browser agent:agent-1"));
+    assert!(rendered.contains("\"text\": \"retry the flaky upload step\""));
+    assert_eq!(app.artifact_browser.trail.len(), 3);
+
+    press(&mut app, KeyCode::Backspace);
+    press(&mut app, KeyCode::Esc);
+    assert_eq!(app.artifact_browser.trail.len(), 1);
+    press(&mut app, KeyCode::Char('g'));
+    assert!(app.artifact_browser.trail.is_empty());
+
+    drop(store);
+    cleanup_test_mind_store(&root, &store_path);
+}
+
+#[test]
+fn artifact_browser_filter_parses_tag_segment_and_window_tokens() {
+    let now = Utc
+        .with_ymd_and_hms(2026, 3, 10, 12, 0, 0)
+        .single()
+        .expect("ts");
+    let query =
+        parse_artifact_browser_filter("tag:mind seg:seg-a since:2d flaky upload since:9y", now);
+    assert_eq!(query.active_tag.as_deref(), Some("mind"));
+    assert_eq!(query.segment_id.as_deref(), Some("seg-a"));
+    assert_eq!(query.since, Some(now - chrono::Duration::days(2)));
+    assert_eq!(query.text.as_deref(), Some("flaky upload since:9y"));
+    assert_eq!(parse_start_view("artifacts"), Some(Mode::Artifacts));
+}
+
+#[test]
+fn job_queue_panel_lists_jobs_and_applies_operator_actions() {
+    let (root, store_path) = fresh_test_mind_store("aoc-mission-control-job-queue");
+    let store = aoc_storage::MindStore::open(&store_path).expect("open store");
+    let created = Utc::now() - chrono::Duration::minutes(10);
+    let reflector_id = store
+        .enqueue_reflector_job(
+            "mind",
+            &["obs:1".to_string()],
+            &["conv-a".to_string()],
+            64,
+            created,
+        )
+        .expect("enqueue reflector");
+    let (t3_id, _) = store
+        .enqueue_t3_backlog_job(
+            "/repo",
+            "session-a",
+            "12",
+            Some("mind"),
+            None,
+            None,
+            &["obs:1".to_string()],
+            created - chrono::Duration::minutes(1),
+        )
+        .expect("enqueue t3");
+
+    let (tx, _rx) = mpsc::channel(4);
+    let mut config = test_config();
+    config.project_root = root.clone();
+    let mut app = App::new(config, tx, empty_local());
+    let mut refresh_requested = false;
+    let mut press = |app: &mut App, code: KeyCode| {
+        handle_key(
+            KeyEvent::new(code, KeyModifiers::NONE),
+            app,
+            &mut refresh_requested,
+        );
+    };
+    let theme = mission_theme(MissionThemeMode::Terminal);
+
+    press(&mut app, KeyCode::Char('9'));
+    assert_eq!(app.mode, Mode::Jobs);
+    let rendered = render_text(&render_job_queue_lines(&app, theme, false));
+    assert!(rendered.contains("pending:2 claimed:0 failed:0"));
+    assert!(rendered.contains(&format!(">> [reflector] pending   {reflector_id}")));
+    assert!(rendered.contains("age:10m"));
+
+    press(&mut app, KeyCode::Char('x'));
+    assert_eq!(
+        app.status_note.as_deref(),
+        Some(format!("cancel {reflector_id}: ok").as_str())
+    );
+    let rendered = render_text(&render_job_queue_lines(&app, theme, false));
+    assert!(!rendered.contains(&reflector_id));
+    assert!(rendered.contains(&format!(">> [t3] pending   {t3_id}")));
+
+    press(&mut app, KeyCode::Char('d'));
+    let rendered = render_text(&render_job_queue_lines(&app, theme, false));
+    assert!(rendered.contains("pending:0 claimed:0 failed:1"));
+    assert!(rendered.contains("last_error: moved to dead-letter by operator"));
+
+    press(&mut app, KeyCode::Char('f'));
+    let rendered = render_text(&render_job_queue_lines(&app, theme, false));
+    assert!(rendered.contains("completed:0 cancelled:1"));
+    press(&mut app, KeyCode::Char('j'));
+    press(&mut app, KeyCode::Char('u'));
+    assert_eq!(
+        store
+            .reflector_job_by_id(&reflector_id)
+            .expect("load job")
+            .expect("job")
+            .status,
+        aoc_storage::ReflectorJobStatus::Pending
+    );
+
+    drop(store);
+    cleanup_test_mind_store(&root, &store_path);
+}
+
+#[test]
+fn route_review_accepts_candidates_and_typed_segments() {
+    let (root, store_path) = fresh_test_mind_store("aoc-mission-control-route-review");
+    let store = aoc_storage::MindStore::open(&store_path).expect("open store");
+    let base = Utc::now() - chrono::Duration::minutes(5);
+    store
+        .insert_observation("obs-ui-api", "conv-a", base, "ui api request parser", &[])
+        .expect("insert observation");
+    store
+        .insert_observation(
+            "obs-css-db",
+            "conv-a",
+            base + chrono::Duration::seconds(5),
+            "css db cache",
+            &[],
+        )
+        .expect("insert observation");
+    route_review_router()
+        .route_conversation(&store, "conv-a")
+        .expect("route conversation");
+    assert_eq!(load_route_review_queue(&store).len(), 2);
+
+    let (tx, _rx) = mpsc::channel(4);
+    let mut config = test_config();
+    config.project_root = root.clone();
+    let mut app = App::new(config, tx, empty_local());
+    let mut refresh_requested = false;
+    let mut press = |app: &mut App, code: KeyCode| {
+        handle_key(
+            KeyEvent::new(code, KeyModifiers::NONE),
+            app,
+            &mut refresh_requested,
+        );
+    };
+    let theme = mission_theme(MissionThemeMode::Terminal);
+
+    press(&mut app, KeyCode::Char('0'));
+    assert_eq!(app.mode, Mode::Routes);
+    press(&mut app, KeyCode::Char('g'));
+    let rendered = render_text(&render_route_review_lines(&app, theme, false));
+    assert!(rendered.contains("2 uncertain artifacts"));
+    assert!(rendered.contains(">> obs-css-db"));
+    assert!(rendered.contains("candidates (3)"));
+    assert!(rendered.contains("keyword_match:db"));
+
+    press(&mut app, KeyCode::Char(']'));
+    let rendered = render_text(&render_route_review_lines(&app, theme, false));
+    assert!(rendered.contains(">> frontend"));
+    press(&mut app, KeyCode::Char('a'));
+    assert_eq!(
+        app.status_note.as_deref(),
+        Some("routed obs-css-db -> frontend")
+    );
+    let route = store
+        .segment_route_for_artifact("obs-css-db")
+        .expect("load route")
+        .expect("route");
+    assert_eq!(route.primary.segment_id, "frontend");
+    assert!(route
+        .overridden_by
+        .as_deref()
+        .is_some_and(|patch| patch.starts_with("review:")));
+
+    press(&mut app, KeyCode::Char('/'));
+    assert!(app.route_review.editing);
+    for ch in "parsing".chars() {
+        press(&mut app, KeyCode::Char(ch));
+    }
+    press(&mut app, KeyCode::Enter);
+    assert!(!app.route_review.editing);
+    assert_eq!(
+        app.status_note.as_deref(),
+        Some("routed obs-ui-api -> parsing")
+    );
+    let rendered = render_text(&render_route_review_lines(&app, theme, false));
+    assert!(rendered.contains("Uncertain-route queue is empty."));
+
+    drop(store);
+    cleanup_test_mind_store(&root, &store_path);
+}
+
+#[test]
+fn task_timeline_lists_links_sessions_and_expands_evidence() {
+    use aoc_core::mind_contracts::{
+        ArtifactTaskLink, ArtifactTaskRelation, ConversationRole, MessageEvent, RawEvent,
+        RawEventBody,
+    };
+
+    let (root, store_path) = fresh_test_mind_store("aoc-mission-control-task-timeline");
+    let store = aoc_storage::MindStore::open(&store_path).expect("open store");
+    let base = Utc
+        .with_ymd_and_hms(2026, 3, 4, 10, 0, 0)
+        .single()
+        .expect("ts");
+    let mut attrs = std::collections::BTreeMap::new();
+    attrs.insert("session_id".to_string(), Value::from("sess-timeline"));
+    store
+        .insert_raw_event(&RawEvent {
+            event_id: "evt-task-1".to_string(),
+            conversation_id: "conv-task".to_string(),
+            agent_id: "agent-1".to_string(),
+            ts: base,
+            body: RawEventBody::Message(MessageEvent {
+                role: ConversationRole::User,
+                text: "wire the timeline keys".to_string(),
+            }),
+            attrs,
+        })
+        .expect("insert raw");
+    for (artifact_id, minutes, text) in [
+        ("obs:task-start", 1, "Started task 107 keybindings"),
+        ("obs:task-done", 30, "Finished task 107"),
+    ] {
+        store
+            .insert_observation(
+                artifact_id,
+                "conv-task",
+                base + chrono::Duration::minutes(minutes),
+                text,
+                &[],
+            )
+            .expect("insert observation");
+    }
+    for (artifact_id, relation, minutes) in [
+        ("obs:task-done", ArtifactTaskRelation::Completed, 30),
+        ("obs:task-start", ArtifactTaskRelation::WorkedOn, 1),
+    ] {
+        store
+            .upsert_artifact_task_link(&ArtifactTaskLink {
+                artifact_id: artifact_id.to_string(),
+                task_id: "107".to_string(),
+                relation,
+                confidence_bps: 9_000,
+                evidence_event_ids: vec!["evt-task-1".to_string()],
+                source: "task_attribution".to_string(),
+                start_ts: base + chrono::Duration::minutes(minutes),
+                end_ts: None,
+            })
+            .expect("task link");
+    }
+
+    let (tx, _rx) = mpsc::channel(4);
+    let mut config = test_config();
+    config.project_root = root.clone();
+    let mut app = App::new(config, tx, empty_local());
+    let mut refresh_requested = false;
+    let mut press = |app: &mut App, code: KeyCode| {
+        handle_key(
+            KeyEvent::new(code, KeyModifiers::NONE),
+            app,
+            &mut refresh_requested,
+        );
+    };
+    let theme = mission_theme(MissionThemeMode::Terminal);
+
+    press(&mut app, KeyCode::Char('T'));
+    assert_eq!(app.mode, Mode::Tasks);
+    let rendered = render_text(&render_task_timeline_lines(&app, theme, false));
+    assert!(rendered.contains(">> #107 links:2 completed"));
+
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.task_timeline.task_id.as_deref(), Some("107"));
+    let rendered = render_text(&render_task_timeline_lines(&app, theme, false));
+    assert!(rendered.contains("links:2 completions:1 sessions:1"));
+    assert!(rendered.contains("sessions: sess-timeline (2)"));
+    let start = rendered.find("obs:task-start").expect("start entry");
+    let done = rendered.find("obs:task-done").expect("done entry");
+    assert!(start < done);
+    assert!(!rendered.contains("evidence (1)"));
+
+    press(&mut app, KeyCode::Enter);
+    let rendered = render_text(&render_task_timeline_lines(&app, theme, false));
+    assert!(rendered.contains("evidence (1)"));
+    assert!(rendered.contains("[raw] evt-task-1"));
+
+    press(&mut app, KeyCode::Esc);
+    assert!(app.task_timeline.task_id.is_none());
+    press(&mut app, KeyCode::Char('/'));
+    for ch in "#42".chars() {
+        press(&mut app, KeyCode::Char(ch));
+    }
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.task_timeline.task_id.as_deref(), Some("42"));
+    let rendered = render_text(&render_task_timeline_lines(&app, theme, false));
+    assert!(rendered.contains("No artifacts linked to task #42."));
+
+    drop(store);
+    cleanup_test_mind_store(&root, &store_path);
+}
+
+#[test]
+fn session_tree_renders_lineage_counts_and_backfills_stale_branches() {
+    use aoc_core::mind_contracts::{
+        compact_raw_event_to_t0, ConversationRole, MessageEvent, RawEvent, RawEventBody,
+        T0CompactionPolicy,
+    };
+
+    let (root, store_path) = fresh_test_mind_store("aoc-mission-control-session-tree");
+    let store = aoc_storage::MindStore::open(&store_path).expect("open store");
+    let base = Utc::now() - chrono::Duration::minutes(20);
+    for (event_id, conversation_id, parent, minutes) in [
+        ("evt-root", "conv-root", None, 0),
+        ("evt-branch", "conv-branch", Some("conv-root"), 5),
+    ] {
+        let mut attrs = std::collections::BTreeMap::new();
+        attrs.insert("session_id".to_string(), Value::from("sess-tree"));
+        if let Some(parent) = parent {
+            attrs.insert("parent_conversation_id".to_string(), Value::from(parent));
+            attrs.insert("root_conversation_id".to_string(), Value::from("conv-root"));
+        }
+        let raw = RawEvent {
+            event_id: event_id.to_string(),
+            conversation_id: conversation_id.to_string(),
+            agent_id: "sess-tree::7".to_string(),
+            ts: base + chrono::Duration::minutes(minutes),
+            body: RawEventBody::Message(MessageEvent {
+                role: ConversationRole::User,
+                text: format!("{conversation_id} planning note"),
+            }),
+            attrs,
+        };
+        store.insert_raw_event(&raw).expect("insert raw");
+        let t0 = compact_raw_event_to_t0(&raw, &T0CompactionPolicy::default())
+            .expect("compact")
+            .expect("kept");
+        store.upsert_t0_compact_event(&t0).expect("upsert t0");
+    }
+    store
+        .insert_observation(
+            "obs:root",
+            "conv-root",
+            base + chrono::Duration::minutes(1),
+            "Root observed",
+            &[],
+        )
+        .expect("insert observation");
+
+    let (tx, mut rx) = mpsc::channel(4);
+    let mut config = test_config();
+    config.project_root = root.clone();
+    let mut app = App::new(config, tx, empty_local());
+    let mut refresh_requested = false;
+    let mut press = |app: &mut App, code: KeyCode| {
+        handle_key(
+            KeyEvent::new(code, KeyModifiers::NONE),
+            app,
+            &mut refresh_requested,
+        );
+    };
+    let theme = mission_theme(MissionThemeMode::Terminal);
+
+    press(&mut app, KeyCode::Char('L'));
+    assert_eq!(app.mode, Mode::Sessions);
+    let rendered = render_text(&render_session_tree_lines(&app, theme, false));
+    assert!(rendered.contains("session sess-tree conversations:2 backlog:1"));
+    assert!(rendered.contains(">> ● conv-root raw:1 t0:1 t1:1 t2:0"));
+    assert!(rendered.contains("  └ conv-branch raw:1 t0:1 t1:0 t2:0 last:15m ⚠ observer backlog"));
+    let root_line = rendered
+        .lines()
+        .find(|line| line.contains("conv-root"))
+        .expect("root line");
+    assert!(!root_line.contains("observer backlog"));
+
+    app.connected = true;
+    app.set_local(LocalSnapshot {
+        overview: vec![OverviewRow {
+            identity_key: "sess-tree::7".to_string(),
+            label: "Pi".to_string(),
+            lifecycle: "running".to_string(),
+            snippet: None,
+            pane_id: "7".to_string(),
+            tab_index: Some(1),
+            tab_name: Some("Agent".to_string()),
+            tab_focused: false,
+            project_root: root.to_string_lossy().to_string(),
+            online: true,
+            age_secs: Some(1),
+            source: "runtime".to_string(),
+            session_title: None,
+            chat_title: None,
+        }],
+        ..empty_local()
+    });
+    press(&mut app, KeyCode::Char('A'));
+    let command = rx.try_recv().expect("backfill command queued");
+    let WireMsg::Command(payload) = command.msg else {
+        panic!("expected command")
+    };
+    assert_eq!(payload.command, "run_observer");
+    assert_eq!(payload.target_agent_id.as_deref(), Some("sess-tree::7"));
+    assert_eq!(
+        payload.args.get("conversation_id").and_then(Value::as_str),
+        Some("conv-branch")
+    );
+    assert!(rx.try_recv().is_err());
+
+    drop(store);
+    cleanup_test_mind_store(&root, &store_path);
+}
+
+#[test]
+fn usage_panel_reports_ledger_spend_and_flags_budgets_near_exhaustion() {
+    use aoc_storage::SemanticUsageEntry;
+
+    let (root, store_path) = fresh_test_mind_store("aoc-mission-control-usage");
+    let store = aoc_storage::MindStore::open(&store_path).expect("open store");
+    let now = Utc::now();
+    for (artifact_id, tag, tokens, fallback) in [
+        ("obs:u1", "mind", 850, false),
+        ("obs:u2", "docs", 300, true),
+        ("obs:u3", "ops", 1_200, false),
+    ] {
+        store
+            .record_semantic_usage(&SemanticUsageEntry {
+                artifact_id: artifact_id.to_string(),
+                attempt_count: 1,
+                tag: tag.to_string(),
+                provider_name: Some("pi".to_string()),
+                model_id: Some("spark".to_string()),
+                input_tokens: tokens,
+                output_tokens: 0,
+                cost_micros: u64::from(tokens) * 100,
+                fallback_used: fallback,
+                recorded_at: now,
+            })
+            .expect("record usage");
+    }
+    for tag in ["mind", "docs", "ops"] {
+        store
+            .set_semantic_tag_budget(tag, 1_000, 0, now)
+            .expect("set budget");
+    }
+    for (provider, tokens, rejected) in [("pi", 2_350, false), ("pi", 0, true)] {
+        store
+            .charge_semantic_budget(&aoc_storage::SemanticBudgetCharge {
+                day: now.date_naive(),
+                provider_name: provider.to_string(),
+                budget_tokens: 2_000,
+                budget_cost_micros: 0,
+                tokens,
+                cost_micros: tokens * 100,
+                rejected,
+                recorded_at: now,
+            })
+            .expect("charge daily budget");
+    }
+
+    let (tx, _rx) = mpsc::channel(4);
+    let mut config = test_config();
+    config.project_root = root.clone();
+    let mut app = App::new(config, tx, empty_local());
+    let mut refresh_requested = false;
+    handle_key(
+        KeyEvent::new(KeyCode::Char('U'), KeyModifiers::NONE),
+        &mut app,
+        &mut refresh_requested,
+    );
+    assert_eq!(app.mode, Mode::Usage);
+
+    let theme = mission_theme(MissionThemeMode::Terminal);
+    let rendered = render_text(&render_usage_lines(&app, theme, false));
+    assert!(rendered.contains("calls:3 tokens:2350 cost:$0.2350 fallback:33.3%"));
+    assert!(rendered.contains("spark"));
+    let line = |tag: &str| {
+        rendered
+            .lines()
+            .find(|line| line.trim_start().starts_with(tag))
+            .expect("budget line")
+            .to_string()
+    };
+    assert!(line("docs").contains("left 700/1000 tok"));
+    assert!(!line("docs").contains('⚠'));
+    assert!(line("mind").contains("used:85.0% left 150/1000 tok ⚠ nearing budget"));
+    assert!(line("ops").contains("left 0/1000 tok ✖ budget exhausted"));
+    assert!(rendered.contains("Daily budget (today)"));
+    assert!(line("pi").contains("used:117.5% left 0/2000 tok · spent 2350 tok $0.2350"));
+    assert!(line("pi").contains("✖ budget exhausted refused:1"));
+
+    drop(store);
+    cleanup_test_mind_store(&root, &store_path);
+}
+
+#[test]
+fn search_updates_while_typing_and_opens_hits_in_artifact_browser() {
+    use aoc_storage::MemDecision;
+
+    let (root, store_path) = fresh_test_mind_store("aoc-mission-control-search");
+    let store = aoc_storage::MindStore::open(&store_path).expect("open store");
+    let now = Utc::now();
+    store
+        .insert_observation(
+            "obs:retry",
+            "conv-search",
+            now - chrono::Duration::hours(2),
+            "Observed flaky retry backoff in the sync worker",
+            &[],
+        )
+        .expect("insert observation");
+    store
+        .insert_reflection(
+            "ref:retry",
+            "conv-search",
+            now - chrono::Duration::hours(1),
+            "Retry policy keeps drifting between services",
+            &[],
+        )
+        .expect("insert reflection");
+    store
+        .insert_mem_decision(&MemDecision {
+            decision_id: "dec:retry".to_string(),
+            ts: now,
+            project_id: root.to_string_lossy().to_string(),
+            segment_id: Some("backend".to_string()),
+            text: "Use exponential retry backoff capped at 30s".to_string(),
+            supersedes_id: None,
+        })
+        .expect("insert decision");
+
+    let (tx, _rx) = mpsc::channel(4);
+    let mut config = test_config();
+    config.project_root = root.clone();
+    let mut app = App::new(config, tx, empty_local());
+    let mut refresh_requested = false;
+    let mut press = |app: &mut App, code: KeyCode| {
+        handle_key(
+            KeyEvent::new(code, KeyModifiers::NONE),
+            app,
+            &mut refresh_requested,
+        );
+    };
+    let theme = mission_theme(MissionThemeMode::Terminal);
+
+    press(&mut app, KeyCode::Char('G'));
+    assert_eq!(app.mode, Mode::Search);
+    assert!(app.search.editing);
+    for ch in "retry".chars() {
+        press(&mut app, KeyCode::Char(ch));
+    }
+    let rendered = render_text(&render_search_lines(&app, theme, false));
+    assert!(rendered.contains("3 hits"));
+    assert!(rendered.contains(">> [decision]"));
+
+    for ch in " kind:t1".chars() {
+        press(&mut app, KeyCode::Char(ch));
+    }
+    let rendered = render_text(&render_search_lines(&app, theme, false));
+    assert!(rendered.contains(" kind:t1 "));
+    assert!(rendered.contains("1 hits"));
+    assert!(!rendered.contains("[decision]"));
+
+    for _ in 0.." kind:t1".len() {
+        press(&mut app, KeyCode::Backspace);
+    }
+    for ch in " kind:t2".chars() {
+        press(&mut app, KeyCode::Char(ch));
+    }
+    press(&mut app, KeyCode::Enter);
+    assert!(!app.search.editing);
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.mode, Mode::Artifacts);
+    assert_eq!(
+        app.artifact_browser
+            .trail
+            .iter()
+            .map(|step| step.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["ref:retry"]
+    );
+
+    let filter = parse_search_query("tag:mind seg:backend since:7d", now);
+    assert_eq!(filter.chips, vec!["tag:mind", "seg:backend", "since:7d"]);
+    assert!(load_search_hits(&store, &filter).is_empty());
+
+    drop(store);
+    cleanup_test_mind_store(&root, &store_path);
+}
@@ -0,0 +1,298 @@
+//! Semantic cost and budget surface.
+//!
+//! Summarises the semantic usage ledger over the last week (tokens, cost, and
+//! fallback rate per UTC day and per model), today's daily budget ledger per
+//! provider, and each budgeted tag with what is left of today's allowance.
+//! Budgets turn amber past [`BUDGET_WARN_BPS`] and red once exhausted.
+
+use super::*;
+use aoc_storage::{
+    SemanticBudgetDay, SemanticTagBudget, SemanticUsageBucket, SEMANTIC_BUDGET_ALL_PROVIDERS,
+};
+
+pub(crate) const USAGE_WINDOW_DAYS: i64 = 7;
+pub(crate) const BUDGET_WARN_BPS: u64 = 8_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BudgetLevel {
+    Ok,
+    Warn,
+    Exhausted,
+}
+
+pub(crate) fn budget_level(used_bps: u64) -> BudgetLevel {
+    match used_bps {
+        bps if bps >= 10_000 => BudgetLevel::Exhausted,
+        bps if bps >= BUDGET_WARN_BPS => BudgetLevel::Warn,
+        _ => BudgetLevel::Ok,
+    }
+}
+
+pub(crate) fn format_cost_micros(micros: u64) -> String {
+    format!("${}.{:04}", micros / 1_000_000, (micros % 1_000_000) / 100)
+}
+
+fn format_bps(bps: u64) -> String {
+    format!("{}.{}%", bps / 100, (bps % 100) / 10)
+}
+
+fn usage_bucket_line(
+    bucket: &SemanticUsageBucket,
+    theme: MissionTheme,
+    key_budget: usize,
+) -> Line<'static> {
+    let fallback_bps = bucket.fallback_rate_bps();
+    Line::from(vec![
+        Span::raw("  "),
+        Span::styled(
+            format!("{:<key_budget$}", ellipsize(&bucket.key, key_budget)),
+            Style::default().fg(theme.info),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!(
+                "calls:{} tokens:{} cost:{}",
+                bucket.calls,
+                bucket.tokens,
+                format_cost_micros(bucket.cost_micros)
+            ),
+            Style::default().fg(theme.text),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("fallback:{}", format_bps(fallback_bps)),
+            Style::default().fg(if fallback_bps >= 2_500 {
+                theme.warn
+            } else {
+                theme.muted
+            }),
+        ),
+    ])
+}
+
+fn budget_remaining_label(
+    remaining_tokens: Option<u64>,
+    budget_tokens: u64,
+    remaining_cost_micros: Option<u64>,
+    budget_cost_micros: u64,
+) -> String {
+    let mut parts = Vec::new();
+    if let Some(tokens) = remaining_tokens {
+        parts.push(format!("{tokens}/{budget_tokens} tok"));
+    }
+    if let Some(micros) = remaining_cost_micros {
+        parts.push(format!(
+            "{}/{}",
+            format_cost_micros(micros),
+            format_cost_micros(budget_cost_micros)
+        ));
+    }
+    if parts.is_empty() {
+        "unlimited".to_string()
+    } else {
+        format!("left {}", parts.join(" · "))
+    }
+}
+
+fn section_title(title: &str, theme: MissionTheme) -> Line<'static> {
+    Line::from(Span::styled(
+        title.to_string(),
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    ))
+}
+
+pub(crate) fn render_usage_lines(
+    app: &App,
+    theme: MissionTheme,
+    compact: bool,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            "Semantic usage",
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("[last {USAGE_WINDOW_DAYS}d · budgets reset at 00:00 UTC]"),
+            Style::default().fg(theme.muted),
+        ),
+    ])];
+
+    let Some(store) = open_project_mind_store(&app.config.project_root) else {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                "No project Mind store yet.",
+                Style::default().fg(theme.warn),
+            ),
+        ]));
+        return lines;
+    };
+
+    let now = Utc::now();
+    let today = now
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .map(|midnight| midnight.and_utc())
+        .unwrap_or(now);
+    let window_start = today - chrono::Duration::days(USAGE_WINDOW_DAYS - 1);
+    let report = store
+        .semantic_usage_report(Some(window_start))
+        .unwrap_or_default();
+    let budgets = store.semantic_tag_budgets(Some(today)).unwrap_or_default();
+    let daily = store
+        .semantic_budget_ledger(today.date_naive())
+        .unwrap_or_default();
+    let key_budget = if compact { 12 } else { 24 };
+
+    if report.total.calls == 0 {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                "No semantic provider calls recorded in this window.",
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+    } else {
+        lines.push(usage_bucket_line(&report.total, theme, key_budget));
+        lines.push(section_title("Per day", theme));
+        lines.extend(
+            report
+                .by_day
+                .iter()
+                .rev()
+                .map(|bucket| usage_bucket_line(bucket, theme, key_budget)),
+        );
+        lines.push(section_title("Per model", theme));
+        lines.extend(
+            report
+                .by_model
+                .iter()
+                .map(|bucket| usage_bucket_line(bucket, theme, key_budget)),
+        );
+    }
+
+    lines.push(section_title("Daily budget (today)", theme));
+    if daily.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                "No provider calls charged today.",
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+    }
+    for row in &daily {
+        let key = if row.provider_name == SEMANTIC_BUDGET_ALL_PROVIDERS {
+            "all providers"
+        } else {
+            row.provider_name.as_str()
+        };
+        let mut line = budget_line(
+            key,
+            row.used_bps(),
+            daily_remaining_label(row),
+            theme,
+            key_budget,
+        );
+        if row.rejected_calls > 0 {
+            line.spans.push(Span::styled(
+                format!(" refused:{}", row.rejected_calls),
+                Style::default().fg(theme.warn),
+            ));
+        }
+        lines.push(line);
+    }
+
+    lines.push(section_title("Tag budgets (today)", theme));
+    if budgets.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw("  -> "),
+            Span::styled(
+                "No tag budgets configured.",
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+        return lines;
+    }
+    for budget in &budgets {
+        lines.push(budget_line(
+            &budget.tag,
+            budget.used_bps(),
+            tag_remaining_label(budget),
+            theme,
+            key_budget,
+        ));
+    }
+    lines
+}
+
+fn tag_remaining_label(budget: &SemanticTagBudget) -> String {
+    budget_remaining_label(
+        budget.remaining_tokens(),
+        budget.budget_tokens,
+        budget.remaining_cost_micros(),
+        budget.budget_cost_micros,
+    )
+}
+
+fn daily_remaining_label(row: &SemanticBudgetDay) -> String {
+    let label = budget_remaining_label(
+        row.remaining_tokens(),
+        row.budget_tokens,
+        row.remaining_cost_micros(),
+        row.budget_cost_micros,
+    );
+    format!(
+        "{label} · spent {} tok {}",
+        row.spent_tokens,
+        format_cost_micros(row.spent_cost_micros)
+    )
+}
+
+fn budget_line(
+    key: &str,
+    used_bps: u64,
+    remaining: String,
+    theme: MissionTheme,
+    key_budget: usize,
+) -> Line<'static> {
+    let level = budget_level(used_bps);
+    let color = match level {
+        BudgetLevel::Ok => theme.ok,
+        BudgetLevel::Warn => theme.warn,
+        BudgetLevel::Exhausted => theme.critical,
+    };
+    let mut spans = vec![
+        Span::raw("  "),
+        Span::styled(
+            format!("{:<key_budget$}", ellipsize(key, key_budget)),
+            Style::default().fg(theme.info),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("used:{}", format_bps(used_bps)),
+            Style::default().fg(color),
+        ),
+        Span::raw(" "),
+        Span::styled(remaining, Style::default().fg(theme.muted)),
+    ];
+    match level {
+        BudgetLevel::Ok => {}
+        BudgetLevel::Warn => spans.push(Span::styled(
+            " ⚠ nearing budget",
+            Style::default().fg(theme.warn),
+        )),
+        BudgetLevel::Exhausted => spans.push(Span::styled(
+            " ✖ budget exhausted",
+            Style::default()
+                .fg(theme.critical)
+                .add_modifier(Modifier::BOLD),
+        )),
+    }
+    Line::from(spans)
+}
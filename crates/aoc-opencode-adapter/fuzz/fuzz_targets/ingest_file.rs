@@ -0,0 +1,39 @@
+#![no_main]
+
+use aoc_opencode_adapter::{IngestionOptions, OpenCodeIngestor};
+use aoc_storage::MindStore;
+use libfuzzer_sys::fuzz_target;
+use std::io::Write;
+
+fuzz_target!(|data: &[u8]| {
+    let store = MindStore::open_in_memory().expect("open store");
+    let mut log = tempfile::NamedTempFile::new().expect("temp log");
+    log.write_all(data).expect("write log");
+    log.flush().expect("flush log");
+
+    let ingestor = OpenCodeIngestor::new(IngestionOptions {
+        max_line_bytes: 4096,
+        ..IngestionOptions::default()
+    });
+    // No line may fail the pass or hold the cursor back: it reaches the last
+    // newline, or the end when the unterminated tail is oversized.
+    let report = ingestor
+        .ingest_conversation_file(&store, "conv-fuzz", "agent-fuzz", log.path())
+        .expect("ingest never fails on file content");
+    let last_line_end = data
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |index| index + 1);
+    let expected = if data.len() - last_line_end > 4096 {
+        data.len()
+    } else {
+        last_line_end
+    };
+    assert_eq!(report.raw_cursor, expected as u64);
+
+    let again = ingestor
+        .ingest_conversation_file(&store, "conv-fuzz", "agent-fuzz", log.path())
+        .expect("re-ingest");
+    assert_eq!(again.raw_cursor, report.raw_cursor);
+    assert_eq!(again.processed_raw_events, 0);
+});
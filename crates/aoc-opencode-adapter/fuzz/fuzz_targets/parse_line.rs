@@ -0,0 +1,17 @@
+#![no_main]
+
+use aoc_opencode_adapter::parse_opencode_line;
+use libfuzzer_sys::fuzz_target;
+
+// Small enough that the fuzzer reaches the oversized-line path.
+const MAX_LINE_BYTES: usize = 4096;
+
+fuzz_target!(|data: &[u8]| {
+    // File ingest hands the parser one line at a time, without its newline.
+    for (index, line) in data.split(|byte| *byte == b'\n').enumerate() {
+        let parsed = parse_opencode_line(line, "conv-fuzz", "agent-fuzz", index, MAX_LINE_BYTES);
+        let skipped = parsed.issues.iter().any(|issue| issue.kind.skips_line());
+        assert_eq!(parsed.event.is_none(), skipped);
+        assert!(parsed.issues.iter().all(|issue| issue.detail.len() < 512));
+    }
+});
@@ -0,0 +1,53 @@
+//! Stdio host for the OpenCode plugin bridge; see `aoc_opencode_adapter::plugin`.
+
+use aoc_opencode_adapter::OpenCodePlugin;
+use aoc_storage::MindStore;
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str =
+    "usage: aoc-opencode-plugin --store <mind.sqlite> --project-root <dir> [--agent-id <id>]";
+
+fn main() -> ExitCode {
+    let mut store_path = None;
+    let mut project_root = None;
+    let mut agent_id = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let slot = match flag.as_str() {
+            "--store" => &mut store_path,
+            "--project-root" => &mut project_root,
+            "--agent-id" => &mut agent_id,
+            _ => {
+                eprintln!("{USAGE}");
+                return ExitCode::from(2);
+            }
+        };
+        *slot = args.next();
+    }
+    let (Some(store_path), Some(project_root)) = (store_path, project_root) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    let store = match MindStore::open(PathBuf::from(&store_path)) {
+        Ok(store) => store,
+        Err(err) => {
+            eprintln!("aoc-opencode-plugin: open {store_path}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // Same key the handshake rebuild persists under.
+    let mut plugin = OpenCodePlugin::new(store, format!("project:{project_root}"));
+    if let Some(agent_id) = agent_id {
+        plugin = plugin.with_agent_id(agent_id);
+    }
+    match plugin.serve(BufReader::new(io::stdin().lock()), io::stdout().lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("aoc-opencode-plugin: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
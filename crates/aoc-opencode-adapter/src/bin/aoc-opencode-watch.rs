@@ -0,0 +1,76 @@
+//! Tails OpenCode log directories into a mind store; see
+//! `aoc_opencode_adapter::watch`.
+
+use aoc_opencode_adapter::{IngestionOptions, OpenCodeIngestor, WatchConfig, WatchIngestor};
+use aoc_storage::MindStore;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+
+const USAGE: &str = "usage: aoc-opencode-watch --store <mind.sqlite> --dir <logs> [--dir <logs>...] [--agent-id <id>] [--debounce-ms <ms>]";
+
+fn main() -> ExitCode {
+    let mut store_path = None;
+    let mut directories = Vec::new();
+    let mut agent_id = "opencode".to_string();
+    let mut debounce = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let Some(value) = args.next() else {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        };
+        match flag.as_str() {
+            "--store" => store_path = Some(value),
+            "--dir" => directories.push(PathBuf::from(value)),
+            "--agent-id" => agent_id = value,
+            "--debounce-ms" => match value.parse::<u64>() {
+                Ok(ms) => debounce = Some(Duration::from_millis(ms)),
+                Err(_) => {
+                    eprintln!("{USAGE}");
+                    return ExitCode::from(2);
+                }
+            },
+            _ => {
+                eprintln!("{USAGE}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+    let Some(store_path) = store_path.filter(|_| !directories.is_empty()) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    let store = match MindStore::open(PathBuf::from(&store_path)) {
+        Ok(store) => store,
+        Err(err) => {
+            eprintln!("aoc-opencode-watch: open {store_path}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut config = WatchConfig::new(directories, agent_id);
+    if let Some(debounce) = debounce {
+        config.debounce = debounce;
+    }
+    let mut watch = WatchIngestor::new(OpenCodeIngestor::new(IngestionOptions::default()), config);
+    let stop = AtomicBool::new(false);
+    let result = watch.run(&store, &stop, |pass| match &pass.outcome {
+        Ok(report) => println!(
+            "{}: +{} raw, +{} t0, {} skipped",
+            pass.conversation_id,
+            report.processed_raw_events,
+            report.produced_t0_events,
+            report.skipped_corrupt_lines
+        ),
+        Err(err) => eprintln!("aoc-opencode-watch: {}: {err}", pass.path.display()),
+    });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("aoc-opencode-watch: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,467 @@
+//! Bridge for an OpenCode plugin that forwards its session hooks to the
+//! cockpit instead of leaving Mind to tail conversation files.
+//!
+//! The OpenCode side spawns the `aoc-opencode-plugin` binary and speaks
+//! newline-delimited JSON over its stdio: one [`PluginRequest`] per line in,
+//! exactly one [`PluginResponse`] per line out. `session_start` answers with
+//! the project's latest handshake pack, every `event` goes through the same
+//! normalization and task attribution as file ingest, and user messages are
+//! answered with canon entries that look relevant to the prompt.
+
+use super::{AttributionState, IngestionOptions, IngestionReport, OpenCodeIngestor};
+use aoc_core::mind_contracts::{normalize_agent_id, ConversationRole, RawEventBody};
+use aoc_storage::{MindStore, StorageError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::io::{BufRead, Write};
+
+pub const PLUGIN_PROTOCOL_VERSION: u32 = 1;
+/// Agent registry `kind` for sessions opened through the plugin.
+pub const OPENCODE_AGENT_KIND: &str = "opencode";
+
+/// Handshake scope read at session start; the key is the project scope key.
+const HANDSHAKE_SCOPE: &str = "project";
+const MAX_SUGGESTIONS: usize = 3;
+/// Prompt and canon terms shorter than this are too common to match on.
+const MIN_TERM_CHARS: usize = 4;
+const MIN_SHARED_TERMS: usize = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PluginRequest {
+    SessionStart {
+        session_id: String,
+        #[serde(default)]
+        agent_id: Option<String>,
+        #[serde(default)]
+        parent_session_id: Option<String>,
+        #[serde(default)]
+        root_session_id: Option<String>,
+    },
+    Event {
+        session_id: String,
+        event: Value,
+    },
+    SessionEnd {
+        session_id: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PluginResponse {
+    Handshake {
+        protocol_version: u32,
+        session_id: String,
+        conversation_id: String,
+        /// Markdown pack to inject as context; `None` until one has been built.
+        payload: Option<String>,
+        token_estimate: u32,
+    },
+    Ack {
+        session_id: String,
+        event_id: String,
+        inserted: bool,
+        suggestions: Vec<MindSuggestion>,
+    },
+    SessionClosed {
+        session_id: String,
+        processed_raw_events: usize,
+        produced_t0_events: usize,
+        captured_task_signals: usize,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// An active canon entry offered back into the session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MindSuggestion {
+    pub entry_id: String,
+    pub revision: i64,
+    pub topic: Option<String>,
+    pub summary: String,
+    /// Prompt terms the entry shares, strongest evidence first.
+    pub matched_terms: Vec<String>,
+}
+
+struct PluginSession {
+    conversation_id: String,
+    agent_id: String,
+    lineage: Option<(String, Option<String>)>,
+    attribution: AttributionState,
+    report: IngestionReport,
+    next_offset: usize,
+    suggested: BTreeSet<String>,
+}
+
+pub struct OpenCodePlugin {
+    store: MindStore,
+    ingestor: OpenCodeIngestor,
+    scope_key: String,
+    default_agent_id: String,
+    sessions: HashMap<String, PluginSession>,
+}
+
+impl OpenCodePlugin {
+    /// `scope_key` selects the handshake pack, e.g. `project:<root>`.
+    pub fn new(store: MindStore, scope_key: impl Into<String>) -> Self {
+        Self {
+            store,
+            ingestor: OpenCodeIngestor::new(IngestionOptions::default()),
+            scope_key: scope_key.into(),
+            default_agent_id: "opencode".to_string(),
+            sessions: HashMap::new(),
+        }
+    }
+
+    pub fn with_agent_id(mut self, agent_id: impl Into<String>) -> Self {
+        self.default_agent_id = agent_id.into();
+        self
+    }
+
+    pub fn store(&self) -> &MindStore {
+        &self.store
+    }
+
+    /// Serves requests until `reader` closes. Bad lines get an `error`
+    /// response; only I/O failures end the loop.
+    pub fn serve(&mut self, reader: impl BufRead, mut writer: impl Write) -> std::io::Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = self.handle_line(&line);
+            let encoded = serde_json::to_string(&response)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+            writeln!(writer, "{encoded}")?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    pub fn handle_line(&mut self, line: &str) -> PluginResponse {
+        match serde_json::from_str::<PluginRequest>(line) {
+            Ok(request) => self.handle(request),
+            Err(err) => PluginResponse::Error {
+                message: format!("invalid plugin request: {err}"),
+            },
+        }
+    }
+
+    pub fn handle(&mut self, request: PluginRequest) -> PluginResponse {
+        let result = match request {
+            PluginRequest::SessionStart {
+                session_id,
+                agent_id,
+                parent_session_id,
+                root_session_id,
+            } => self.start_session(session_id, agent_id, parent_session_id, root_session_id),
+            PluginRequest::Event { session_id, event } => self.record_event(session_id, event),
+            PluginRequest::SessionEnd { session_id } => Ok(self.end_session(session_id)),
+        };
+        result.unwrap_or_else(|err| PluginResponse::Error {
+            message: err.to_string(),
+        })
+    }
+
+    fn start_session(
+        &mut self,
+        session_id: String,
+        agent_id: Option<String>,
+        parent_session_id: Option<String>,
+        root_session_id: Option<String>,
+    ) -> Result<PluginResponse, super::AdapterError> {
+        let conversation_id = conversation_id_for_session(&session_id);
+        let parent = parent_session_id
+            .as_deref()
+            .map(conversation_id_for_session);
+        let root = root_session_id
+            .as_deref()
+            .map(conversation_id_for_session)
+            .or_else(|| parent.clone());
+        let lineage = parent.map(|parent| (parent, root));
+        let agent_id = normalize_agent_id(agent_id.as_deref().unwrap_or(&self.default_agent_id));
+        self.store
+            .ensure_agent(&agent_id, OPENCODE_AGENT_KIND, self.ingestor.now())?;
+        let attribution =
+            AttributionState::from_snapshot(self.store.latest_context_state(&conversation_id)?);
+        self.sessions.insert(
+            session_id.clone(),
+            PluginSession {
+                conversation_id: conversation_id.clone(),
+                agent_id,
+                lineage,
+                attribution,
+                report: IngestionReport::default(),
+                next_offset: 0,
+                suggested: BTreeSet::new(),
+            },
+        );
+
+        let handshake = self
+            .store
+            .latest_handshake_snapshot(HANDSHAKE_SCOPE, &self.scope_key)?;
+        Ok(PluginResponse::Handshake {
+            protocol_version: PLUGIN_PROTOCOL_VERSION,
+            session_id,
+            conversation_id,
+            token_estimate: handshake.as_ref().map_or(0, |pack| pack.token_estimate),
+            payload: handshake.map(|pack| pack.payload_text),
+        })
+    }
+
+    fn record_event(
+        &mut self,
+        session_id: String,
+        mut event: Value,
+    ) -> Result<PluginResponse, super::AdapterError> {
+        let Some(session) = self.sessions.get_mut(&session_id) else {
+            return Ok(PluginResponse::Error {
+                message: format!("unknown session {session_id}; send session_start first"),
+            });
+        };
+        if let Some(object) = event.as_object_mut() {
+            object
+                .entry("session_id")
+                .or_insert_with(|| Value::String(session_id.clone()));
+            if let Some((parent, root)) = &session.lineage {
+                object
+                    .entry("parent_conversation_id")
+                    .or_insert_with(|| Value::String(parent.clone()));
+                if let Some(root) = root {
+                    object
+                        .entry("root_conversation_id")
+                        .or_insert_with(|| Value::String(root.clone()));
+                }
+            }
+        }
+
+        let offset = session.next_offset;
+        session.next_offset += 1;
+        let (raw, inserted) = self.ingestor.ingest_value(
+            &self.store,
+            &session.conversation_id,
+            &session.agent_id,
+            event,
+            offset,
+            &mut session.attribution,
+            &mut session.report,
+        )?;
+
+        let suggestions = match &raw.body {
+            RawEventBody::Message(message) if message.role == ConversationRole::User => {
+                let suggestions = suggest_canon(&self.store, &message.text, &session.suggested)?;
+                session
+                    .suggested
+                    .extend(suggestions.iter().map(|item| item.entry_id.clone()));
+                suggestions
+            }
+            _ => Vec::new(),
+        };
+        Ok(PluginResponse::Ack {
+            session_id,
+            event_id: raw.event_id,
+            inserted,
+            suggestions,
+        })
+    }
+
+    fn end_session(&mut self, session_id: String) -> PluginResponse {
+        let report = self
+            .sessions
+            .remove(&session_id)
+            .map(|session| session.report)
+            .unwrap_or_default();
+        PluginResponse::SessionClosed {
+            session_id,
+            processed_raw_events: report.processed_raw_events,
+            produced_t0_events: report.produced_t0_events,
+            captured_task_signals: report.captured_task_signals,
+        }
+    }
+}
+
+/// Conversation id for an OpenCode session, matching the `pi:` convention.
+pub fn conversation_id_for_session(session_id: &str) -> String {
+    format!("opencode:{session_id}")
+}
+
+fn suggest_canon(
+    store: &MindStore,
+    prompt: &str,
+    already_suggested: &BTreeSet<String>,
+) -> Result<Vec<MindSuggestion>, StorageError> {
+    let prompt_terms = terms(prompt);
+    if prompt_terms.len() < MIN_SHARED_TERMS {
+        return Ok(Vec::new());
+    }
+    let mut scored = store
+        .active_canon_entries(None)?
+        .into_iter()
+        .filter(|entry| !already_suggested.contains(&entry.entry_id))
+        .filter_map(|entry| {
+            let entry_terms = terms(&format!(
+                "{} {}",
+                entry.topic.as_deref().unwrap_or_default(),
+                entry.summary
+            ));
+            let mut shared = prompt_terms
+                .intersection(&entry_terms)
+                .cloned()
+                .collect::<Vec<_>>();
+            if shared.len() < MIN_SHARED_TERMS {
+                return None;
+            }
+            shared.sort_by(|left, right| right.len().cmp(&left.len()).then(left.cmp(right)));
+            Some((entry, shared))
+        })
+        .collect::<Vec<_>>();
+    scored.sort_by(|(left, left_terms), (right, right_terms)| {
+        right_terms
+            .len()
+            .cmp(&left_terms.len())
+            .then(right.confidence_bps.cmp(&left.confidence_bps))
+            .then(left.entry_id.cmp(&right.entry_id))
+    });
+    Ok(scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(entry, matched_terms)| MindSuggestion {
+            entry_id: entry.entry_id,
+            revision: entry.revision,
+            topic: entry.topic,
+            summary: entry.summary,
+            matched_terms,
+        })
+        .collect())
+}
+
+fn terms(text: &str) -> BTreeSet<String> {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|term| term.chars().count() >= MIN_TERM_CHARS)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    fn request(value: Value) -> String {
+        value.to_string()
+    }
+
+    #[test]
+    fn plugin_serves_handshake_ingests_events_and_suggests_canon_once() {
+        let store = MindStore::open_in_memory().expect("store");
+        let now = Utc.with_ymd_and_hms(2026, 2, 23, 12, 0, 0).unwrap();
+        store
+            .upsert_handshake_snapshot(
+                "project",
+                "project:/repo",
+                "# Handshake\n- parser",
+                "h1",
+                42,
+                now,
+            )
+            .expect("handshake");
+        store
+            .upsert_canon_entry_revision(
+                "canon:parser",
+                Some("parser"),
+                "Parser fixtures live under tests/fixtures and must stay sorted",
+                9000,
+                8000,
+                None,
+                &["obs:1".to_string()],
+                now,
+            )
+            .expect("canon");
+        store
+            .upsert_canon_entry_revision(
+                "canon:deploy",
+                Some("deploy"),
+                "Deploys go through the release train",
+                9000,
+                8000,
+                None,
+                &["obs:2".to_string()],
+                now,
+            )
+            .expect("canon");
+
+        let mut plugin = OpenCodePlugin::new(store, "project:/repo");
+        let input = [
+            request(json!({"type": "session_start", "session_id": "s1"})),
+            request(json!({"type": "event", "session_id": "s1", "event": {
+                "event_id": "m1", "timestamp": "2026-02-23T12:00:01Z",
+                "role": "user", "text": "the parser fixtures are failing again"
+            }})),
+            request(json!({"type": "event", "session_id": "s1", "event": {
+                "event_id": "m2", "timestamp": "2026-02-23T12:00:02Z",
+                "role": "user", "text": "re-sort the parser fixtures please"
+            }})),
+            request(json!({"type": "event", "session_id": "s2", "event": {}})),
+            "not json".to_string(),
+            request(json!({"type": "session_end", "session_id": "s1"})),
+        ]
+        .join("\n");
+        let mut output = Vec::new();
+        plugin.serve(input.as_bytes(), &mut output).expect("serve");
+        let responses = String::from_utf8(output)
+            .expect("utf8")
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).expect("json response"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(responses.len(), 6);
+        assert_eq!(responses[0]["type"], "handshake");
+        assert_eq!(responses[0]["conversation_id"], "opencode:s1");
+        assert_eq!(responses[0]["payload"], "# Handshake\n- parser");
+        assert_eq!(responses[0]["token_estimate"], 42);
+
+        assert_eq!(responses[1]["type"], "ack");
+        assert_eq!(responses[1]["inserted"], true);
+        let suggestions = responses[1]["suggestions"].as_array().expect("suggestions");
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0]["entry_id"], "canon:parser");
+        assert_eq!(
+            suggestions[0]["matched_terms"],
+            json!(["fixtures", "parser"])
+        );
+        assert!(
+            responses[2]["suggestions"]
+                .as_array()
+                .expect("suggestions")
+                .is_empty(),
+            "an entry is suggested once per session"
+        );
+
+        assert_eq!(responses[3]["type"], "error");
+        assert_eq!(responses[4]["type"], "error");
+        assert_eq!(responses[5]["type"], "session_closed");
+        assert_eq!(responses[5]["processed_raw_events"], 2);
+
+        let raw = plugin
+            .store()
+            .raw_events_for_conversation("opencode:s1")
+            .expect("raw events");
+        assert_eq!(raw.len(), 2);
+        assert_eq!(
+            raw[0].attrs[aoc_core::mind_contracts::LINEAGE_ATTRS_KEY]["session_id"],
+            "s1"
+        );
+        let agent = plugin
+            .store()
+            .agent(&raw[0].agent_id)
+            .expect("agent")
+            .expect("registered at session start");
+        assert_eq!(agent.kind, OPENCODE_AGENT_KIND);
+    }
+}
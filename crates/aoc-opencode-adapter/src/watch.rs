@@ -0,0 +1,384 @@
+//! Continuous ingest of OpenCode conversation logs.
+//!
+//! [`WatchIngestor::run`] watches the configured directories with `notify`
+//! and ingests each changed `.jsonl` file once it has been quiet for the
+//! debounce window, so a burst of appends costs one pass. Each file is one
+//! conversation, `opencode:<file stem>` like the plugin's sessions, and every
+//! pass resumes from that conversation's checkpoint, so only new lines are
+//! read. The change queue is driven by [`WatchIngestor::note_change`] and
+//! [`WatchIngestor::ingest_settled`], which take no filesystem events and
+//! read time from the ingestor's clock, so the debounce is testable without
+//! sleeping.
+
+use super::{plugin::conversation_id_for_session, AdapterError, IngestionReport, OpenCodeIngestor};
+use aoc_storage::MindStore;
+use chrono::{DateTime, Utc};
+use notify::event::ModifyKind;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// How long a file must go without changes before it is ingested.
+pub const DEFAULT_WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+/// How long [`WatchIngestor::run`] blocks on events before checking its stop
+/// flag and settled files.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone)]
+pub struct WatchConfig {
+    /// Watched recursively.
+    pub directories: Vec<PathBuf>,
+    /// Only files with this extension are ingested.
+    pub extension: String,
+    pub agent_id: String,
+    pub debounce: Duration,
+}
+
+impl WatchConfig {
+    pub fn new(directories: Vec<PathBuf>, agent_id: impl Into<String>) -> Self {
+        Self {
+            directories,
+            extension: "jsonl".to_string(),
+            agent_id: agent_id.into(),
+            debounce: DEFAULT_WATCH_DEBOUNCE,
+        }
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        path.extension()
+            .is_some_and(|extension| extension == self.extension.as_str())
+    }
+}
+
+/// One file ingested by [`WatchIngestor::ingest_settled`]. A failed pass
+/// leaves the file's checkpoint where it was and queues the file again, so it
+/// is retried once another debounce window passes.
+#[derive(Debug)]
+pub struct WatchPass {
+    pub path: PathBuf,
+    pub conversation_id: String,
+    pub outcome: Result<IngestionReport, AdapterError>,
+}
+
+pub struct WatchIngestor {
+    ingestor: OpenCodeIngestor,
+    config: WatchConfig,
+    /// Changed files and when each last changed.
+    pending: BTreeMap<PathBuf, DateTime<Utc>>,
+}
+
+impl WatchIngestor {
+    pub fn new(ingestor: OpenCodeIngestor, config: WatchConfig) -> Self {
+        Self {
+            ingestor,
+            config,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Conversation a log file is ingested into.
+    pub fn conversation_id_for_path(path: &Path) -> Option<String> {
+        path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .filter(|stem| !stem.is_empty())
+            .map(conversation_id_for_session)
+    }
+
+    /// Queues `path` for ingest once it has been quiet for the debounce
+    /// window; every change restarts the window. Other files are ignored.
+    pub fn note_change(&mut self, path: &Path) {
+        if self.config.matches(path) {
+            self.pending.insert(path.to_path_buf(), self.ingestor.now());
+        }
+    }
+
+    /// Queues every matching file already under the watched directories, so
+    /// lines written while nothing was watching are caught up.
+    pub fn queue_existing(&mut self) -> Result<usize, AdapterError> {
+        let mut directories = self.config.directories.clone();
+        let mut queued = 0;
+        while let Some(directory) = directories.pop() {
+            for entry in std::fs::read_dir(&directory)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    directories.push(path);
+                } else if self.config.matches(&path) {
+                    self.note_change(&path);
+                    queued += 1;
+                }
+            }
+        }
+        Ok(queued)
+    }
+
+    pub fn pending_files(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Ingests every queued file whose last change is at least the debounce
+    /// window old, in path order. Files removed since they changed are
+    /// dropped without a pass; files whose pass fails (a busy writer, say)
+    /// are queued again as if they had just changed.
+    pub fn ingest_settled(&mut self, store: &MindStore) -> Vec<WatchPass> {
+        let now = self.ingestor.now();
+        let debounce = chrono::Duration::from_std(self.config.debounce)
+            .unwrap_or_else(|_| chrono::Duration::zero());
+        let settled = self
+            .pending
+            .iter()
+            .filter(|(_, changed_at)| now - **changed_at >= debounce)
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+
+        let mut passes = Vec::new();
+        for path in settled {
+            self.pending.remove(&path);
+            if !path.is_file() {
+                continue;
+            }
+            let Some(conversation_id) = Self::conversation_id_for_path(&path) else {
+                continue;
+            };
+            let outcome = self.ingestor.ingest_conversation_file(
+                store,
+                &conversation_id,
+                &self.config.agent_id,
+                &path,
+            );
+            if outcome.is_err() {
+                self.pending.insert(path.clone(), now);
+            }
+            passes.push(WatchPass {
+                path,
+                conversation_id,
+                outcome,
+            });
+        }
+        passes
+    }
+
+    /// Catches up on existing files, then ingests changes as they settle
+    /// until `stop` is set. `on_pass` sees every pass, failed ones included.
+    pub fn run(
+        &mut self,
+        store: &MindStore,
+        stop: &AtomicBool,
+        mut on_pass: impl FnMut(&WatchPass),
+    ) -> Result<(), AdapterError> {
+        let (tx, rx) = mpsc::channel();
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+                Ok(event) if changes_content(&event.kind) => {
+                    let _ = tx.send(event);
+                }
+                _ => {}
+            })?;
+        for directory in &self.config.directories {
+            watcher.watch(directory, RecursiveMode::Recursive)?;
+        }
+        self.queue_existing()?;
+
+        while !stop.load(Ordering::Relaxed) {
+            match rx.recv_timeout(WATCH_POLL_INTERVAL) {
+                Ok(event) => {
+                    for path in &event.paths {
+                        self.note_change(path);
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+            for pass in self.ingest_settled(store) {
+                on_pass(&pass);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Our own reads raise access events; only writes, creates, and renames
+/// count as changes.
+fn changes_content(kind: &EventKind) -> bool {
+    match kind {
+        EventKind::Create(_) => true,
+        EventKind::Modify(kind) => !matches!(kind, ModifyKind::Metadata(_)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IngestionOptions;
+    use aoc_storage::MockClock;
+    use chrono::TimeZone;
+    use std::io::Write;
+    use std::sync::Arc;
+
+    fn message(id: &str) -> String {
+        format!(
+            "{{\"event_id\":\"{id}\",\"timestamp\":\"2026-02-23T12:00:00Z\",\"role\":\"user\",\"text\":\"{id}\"}}\n"
+        )
+    }
+
+    fn append(path: &Path, line: &str) {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .expect("open log");
+        file.write_all(line.as_bytes()).expect("append");
+    }
+
+    #[test]
+    fn settled_files_ingest_incrementally_after_the_debounce() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let nested = dir.path().join("nested");
+        std::fs::create_dir(&nested).expect("nested dir");
+        let log = nested.join("session-a.jsonl");
+        append(&log, &message("m1"));
+        std::fs::write(dir.path().join("notes.txt"), "ignored").expect("other file");
+
+        let store = MindStore::open_in_memory().expect("store");
+        let clock = Arc::new(MockClock::new(
+            Utc.with_ymd_and_hms(2026, 2, 23, 12, 0, 0).unwrap(),
+        ));
+        let ingestor = OpenCodeIngestor::new(IngestionOptions::default()).with_clock(clock.clone());
+        let mut watch = WatchIngestor::new(
+            ingestor,
+            WatchConfig::new(vec![dir.path().to_path_buf()], "agent-1"),
+        );
+
+        assert_eq!(watch.queue_existing().expect("scan"), 1);
+        assert!(watch.ingest_settled(&store).is_empty());
+        clock.advance(chrono::Duration::milliseconds(300));
+        watch.note_change(&log);
+        clock.advance(chrono::Duration::milliseconds(400));
+        assert!(
+            watch.ingest_settled(&store).is_empty(),
+            "change restarts it"
+        );
+
+        clock.advance(chrono::Duration::milliseconds(100));
+        let passes = watch.ingest_settled(&store);
+        assert_eq!(passes.len(), 1);
+        assert_eq!(passes[0].conversation_id, "opencode:session-a");
+        let report = passes[0].outcome.as_ref().expect("pass");
+        assert_eq!(report.processed_raw_events, 1);
+        assert_eq!(watch.pending_files(), 0);
+
+        append(&log, &message("m2"));
+        watch.note_change(&log);
+        watch.note_change(&dir.path().join("notes.txt"));
+        assert_eq!(watch.pending_files(), 1);
+        clock.advance(chrono::Duration::milliseconds(500));
+        let passes = watch.ingest_settled(&store);
+        assert_eq!(
+            passes[0]
+                .outcome
+                .as_ref()
+                .expect("pass")
+                .processed_raw_events,
+            1
+        );
+        assert_eq!(
+            store
+                .raw_event_count("opencode:session-a")
+                .expect("raw count"),
+            2
+        );
+
+        watch.note_change(&dir.path().join("gone.jsonl"));
+        clock.advance(chrono::Duration::milliseconds(500));
+        assert!(watch.ingest_settled(&store).is_empty());
+        assert_eq!(watch.pending_files(), 0);
+    }
+
+    #[test]
+    fn failed_passes_are_retried_after_another_debounce() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let log = dir.path().join("session-c.jsonl");
+        append(&log, &message("m1"));
+        let db_path = dir.path().join("mind.sqlite");
+        let store = MindStore::open(&db_path).expect("store");
+        let clock = Arc::new(MockClock::new(
+            Utc.with_ymd_and_hms(2026, 2, 23, 12, 0, 0).unwrap(),
+        ));
+        let ingestor = OpenCodeIngestor::new(IngestionOptions::default()).with_clock(clock.clone());
+        let mut watch = WatchIngestor::new(
+            ingestor,
+            WatchConfig::new(vec![dir.path().to_path_buf()], "agent-1"),
+        );
+
+        let sabotage = rusqlite::Connection::open(&db_path).expect("second connection");
+        sabotage
+            .execute_batch(
+                "CREATE TRIGGER fail_t0 BEFORE INSERT ON compact_events_t0
+                 BEGIN SELECT RAISE(ABORT, 'database is locked'); END;",
+            )
+            .expect("install trigger");
+        watch.note_change(&log);
+        clock.advance(chrono::Duration::milliseconds(500));
+        let passes = watch.ingest_settled(&store);
+        assert!(passes[0].outcome.is_err());
+        assert_eq!(watch.pending_files(), 1, "failed pass is requeued");
+
+        sabotage
+            .execute_batch("DROP TRIGGER fail_t0")
+            .expect("drop trigger");
+        assert!(
+            watch.ingest_settled(&store).is_empty(),
+            "retry waits for the debounce"
+        );
+        clock.advance(chrono::Duration::milliseconds(500));
+        let passes = watch.ingest_settled(&store);
+        assert_eq!(
+            passes[0]
+                .outcome
+                .as_ref()
+                .expect("retried pass")
+                .processed_raw_events,
+            1
+        );
+        assert_eq!(watch.pending_files(), 0);
+    }
+
+    #[test]
+    fn run_tails_appends_until_stopped() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let log = dir.path().join("session-b.jsonl");
+        append(&log, &message("m1"));
+        let store = MindStore::open_in_memory().expect("store");
+        let mut config = WatchConfig::new(vec![dir.path().to_path_buf()], "agent-1");
+        config.debounce = Duration::from_millis(50);
+        let mut watch =
+            WatchIngestor::new(OpenCodeIngestor::new(IngestionOptions::default()), config);
+
+        let stop = AtomicBool::new(false);
+        let mut ingested = 0;
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                std::thread::sleep(Duration::from_millis(300));
+                append(&log, &message("m2"));
+                // Give up if the append is never seen.
+                let deadline = std::time::Instant::now() + Duration::from_secs(10);
+                while !stop.load(Ordering::Relaxed) && std::time::Instant::now() < deadline {
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+                stop.store(true, Ordering::Relaxed);
+            });
+            watch
+                .run(&store, &stop, |pass| {
+                    ingested += pass.outcome.as_ref().expect("pass").processed_raw_events;
+                    if ingested == 2 {
+                        stop.store(true, Ordering::Relaxed);
+                    }
+                })
+                .expect("run");
+        });
+        assert_eq!(ingested, 2);
+    }
+}
@@ -1,16 +1,24 @@
 use aoc_core::mind_contracts::{
-    build_compaction_t0_slice, canonical_json, compact_raw_event_to_t0,
-    sanitize_raw_event_for_storage, sha256_hex, ConversationRole, MessageEvent, RawEvent,
-    RawEventBody, T0CompactionPolicy, ToolExecutionStatus, ToolResultEvent, LINEAGE_ATTRS_KEY,
+    build_compaction_t0_slice, canonical_json, compact_raw_event_to_t0, normalize_agent_id,
+    sanitize_raw_event_for_storage, sha256_hex, tool_output_digest, ConversationRole, ErrorEvent,
+    MessageEvent, PlanStep, PlanStepStatus, PlanUpdateEvent, RawEvent, RawEventBody,
+    ReasoningEvent, T0CompactionPolicy, ToolExecutionStatus, ToolResultEvent, LINEAGE_ATTRS_KEY,
+};
+use aoc_core::{ErrorCode, ErrorCoded};
+use aoc_storage::{
+    system_clock, CompactionCheckpoint, IngestionCheckpoint, MindStore, SharedClock, StorageError,
 };
-use aoc_storage::{CompactionCheckpoint, IngestionCheckpoint, MindStore, StorageError};
 use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
 use serde_json::{json, Map, Value};
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 use thiserror::Error;
 
+/// Agent registry `kind` for agents first seen through Pi session files.
+pub const PI_AGENT_KIND: &str = "pi";
+
 #[derive(Debug, Error)]
 pub enum PiAdapterError {
     #[error("io error: {0}")]
@@ -23,6 +31,17 @@ pub enum PiAdapterError {
     InvalidSessionHeader(String),
 }
 
+impl ErrorCoded for PiAdapterError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Io(_) => ErrorCode::Io,
+            Self::Storage(err) => err.error_code(),
+            Self::Serialization(_) => ErrorCode::Serialization,
+            Self::InvalidSessionHeader(_) => ErrorCode::InvalidInput,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IngestionOptions {
     pub policy: T0CompactionPolicy,
@@ -47,7 +66,7 @@ pub struct PiSessionSource {
     pub header_end_cursor: u64,
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
 pub struct IngestionReport {
     pub session_id: String,
     pub conversation_id: String,
@@ -63,11 +82,21 @@ pub struct IngestionReport {
 
 pub struct PiSessionIngestor {
     options: IngestionOptions,
+    clock: SharedClock,
 }
 
 impl PiSessionIngestor {
     pub fn new(options: IngestionOptions) -> Self {
-        Self { options }
+        Self {
+            options,
+            clock: system_clock(),
+        }
+    }
+
+    /// Stamps checkpoints and agent registrations from `clock`.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
     }
 
     pub fn inspect_session_file(
@@ -78,6 +107,11 @@ impl PiSessionIngestor {
         parse_session_source(path.as_ref(), &bytes)
     }
 
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(stage = "ingest", path = %path.as_ref().display(), conversation_id)
+    )]
     pub fn ingest_session_file(
         &self,
         store: &MindStore,
@@ -85,8 +119,10 @@ impl PiSessionIngestor {
         path: impl AsRef<Path>,
     ) -> Result<IngestionReport, PiAdapterError> {
         let path = path.as_ref();
+        let agent_id = &normalize_agent_id(agent_id);
         let bytes = fs::read(path)?;
         let source = parse_session_source(path, &bytes)?;
+        tracing::Span::current().record("conversation_id", source.conversation_id.as_str());
         let checkpoint = store.checkpoint(&source.conversation_id)?;
 
         let mut report = IngestionReport {
@@ -125,7 +161,8 @@ impl PiSessionIngestor {
             let line_offset = start_cursor as usize + (consumed - (newline_index + 1));
             let parsed: Value = match serde_json::from_slice(line) {
                 Ok(parsed) => parsed,
-                Err(_) => {
+                Err(err) => {
+                    tracing::warn!(offset = line_offset, error = %err, "skipping corrupt session line");
                     report.skipped_corrupt_lines += 1;
                     continue;
                 }
@@ -164,12 +201,16 @@ impl PiSessionIngestor {
         report.raw_cursor = new_cursor;
         report.t0_cursor = new_cursor;
 
+        if report.processed_raw_events > 0 {
+            store.ensure_agent(agent_id, PI_AGENT_KIND, self.clock.now())?;
+        }
+
         store.upsert_checkpoint(&IngestionCheckpoint {
             conversation_id: source.conversation_id.clone(),
             raw_cursor: report.raw_cursor,
             t0_cursor: report.t0_cursor,
             policy_version: self.options.policy.policy_version.clone(),
-            updated_at: Utc::now(),
+            updated_at: self.clock.now(),
         })?;
 
         Ok(report)
@@ -298,6 +339,9 @@ fn normalize_body(
         }
         "custom" => {
             enrich_custom_entry_attrs(object, attrs);
+            if let Some(plan) = parse_plan_custom_entry(object) {
+                return Ok(RawEventBody::PlanUpdate(plan));
+            }
             Ok(RawEventBody::Other {
                 payload: original.clone(),
             })
@@ -343,9 +387,30 @@ fn normalize_message_entry(
         }
         "assistant" => {
             enrich_message_content_attrs(message, attrs);
+            let text = collect_text_blocks_only(message.get("content"));
+            if message.get("stopReason").and_then(Value::as_str) == Some("error") {
+                let error_message = message
+                    .get("errorMessage")
+                    .and_then(Value::as_str)
+                    .map(ToString::to_string)
+                    .unwrap_or_else(|| text.clone());
+                return Ok(RawEventBody::Error(ErrorEvent {
+                    source: Some("provider".to_string()),
+                    code: None,
+                    message: error_message,
+                    retryable: false,
+                }));
+            }
+            // A turn with only thinking blocks would compact to an empty
+            // message; keep its reasoning instead.
+            if text.trim().is_empty() {
+                if let Some(reasoning) = collect_thinking_blocks(message.get("content")) {
+                    return Ok(RawEventBody::Reasoning(reasoning));
+                }
+            }
             Ok(RawEventBody::Message(MessageEvent {
                 role: ConversationRole::Assistant,
-                text: collect_text_blocks_only(message.get("content")),
+                text,
             }))
         }
         "toolResult" => {
@@ -368,6 +433,12 @@ fn normalize_message_entry(
                 // metadata only; transcript text is limited to user/assistant messages.
                 output: None,
                 redacted: true,
+                touched_paths: Vec::new(),
+                cwd: None,
+                output_digest: Some(tool_output_digest(&collect_text_content(
+                    message.get("content"),
+                ))),
+                retry_count: 0,
             }))
         }
         "bashExecution" => {
@@ -413,6 +484,13 @@ fn normalize_message_entry(
                 // the Pi session/artifact source of truth, not the Mind store.
                 output: None,
                 redacted: true,
+                touched_paths: Vec::new(),
+                cwd: None,
+                output_digest: message
+                    .get("output")
+                    .and_then(Value::as_str)
+                    .map(tool_output_digest),
+                retry_count: 0,
             }))
         }
         "custom" => {
@@ -807,6 +885,75 @@ fn collect_text_blocks_only(value: Option<&Value>) -> String {
     collect_text_content(value)
 }
 
+fn collect_thinking_blocks(value: Option<&Value>) -> Option<ReasoningEvent> {
+    let blocks = value?
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_object)
+        .filter(|block| block.get("type").and_then(Value::as_str) == Some("thinking"))
+        .collect::<Vec<_>>();
+    if blocks.is_empty() {
+        return None;
+    }
+    let summary = blocks
+        .iter()
+        .filter_map(|block| block.get("thinking").and_then(Value::as_str))
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    // Providers that only return a signature keep their reasoning encrypted.
+    let redacted = summary.is_empty()
+        || blocks
+            .iter()
+            .any(|block| block.get("redacted").and_then(Value::as_bool) == Some(true));
+    Some(ReasoningEvent { summary, redacted })
+}
+
+/// Todo/plan extensions write `custom` entries named `todo`, `todos`, or
+/// `plan` whose payload lists the full set of steps.
+fn parse_plan_custom_entry(object: &Map<String, Value>) -> Option<PlanUpdateEvent> {
+    let name = object
+        .get("name")
+        .or_else(|| object.get("customType"))
+        .and_then(Value::as_str)?;
+    if !matches!(name, "todo" | "todos" | "plan") {
+        return None;
+    }
+    let payload = object
+        .get("payload")
+        .or_else(|| object.get("data"))?
+        .as_object()?;
+    let items = ["todos", "steps", "items"]
+        .iter()
+        .find_map(|key| payload.get(*key).and_then(Value::as_array))?;
+    let steps = items
+        .iter()
+        .filter_map(Value::as_object)
+        .filter_map(|item| {
+            let step = ["content", "step", "text", "title"]
+                .iter()
+                .find_map(|key| item.get(*key).and_then(Value::as_str))?;
+            let status = item
+                .get("status")
+                .and_then(Value::as_str)
+                .and_then(PlanStepStatus::parse)
+                .unwrap_or(PlanStepStatus::Pending);
+            Some(PlanStep {
+                step: step.to_string(),
+                status,
+            })
+        })
+        .collect();
+    Some(PlanUpdateEvent {
+        explanation: payload
+            .get("explanation")
+            .and_then(Value::as_str)
+            .map(ToString::to_string),
+        steps,
+    })
+}
+
 fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
     DateTime::parse_from_rfc3339(value)
         .ok()
@@ -863,6 +1010,39 @@ mod tests {
         assert!(source.header_end_cursor > 0);
     }
 
+    #[test]
+    fn ingest_stamps_checkpoints_from_the_injected_clock() {
+        let file = write_session(
+            r#"{"type":"session","version":3,"id":"sess-clock","timestamp":"2024-12-03T14:00:00.000Z","cwd":"/tmp/proj"}
+{"type":"message","id":"u1","parentId":null,"timestamp":"2024-12-03T14:00:01.000Z","message":{"role":"user","content":"hello"}}
+"#,
+        );
+        let now = Utc.with_ymd_and_hms(2026, 2, 23, 12, 0, 0).unwrap();
+        let clock = std::sync::Arc::new(aoc_storage::MockClock::new(now));
+        let store = MindStore::open_in_memory().expect("open store");
+        let ingestor =
+            PiSessionIngestor::new(IngestionOptions::default()).with_clock(clock.clone());
+
+        ingestor
+            .ingest_session_file(&store, "agent-1", file.path())
+            .expect("ingest");
+        let checkpoint = store
+            .checkpoint("pi:sess-clock")
+            .expect("checkpoint query")
+            .expect("checkpoint");
+        assert_eq!(checkpoint.updated_at, now);
+
+        let later = clock.advance(chrono::Duration::minutes(5));
+        ingestor
+            .ingest_session_file(&store, "agent-1", file.path())
+            .expect("re-ingest");
+        let checkpoint = store
+            .checkpoint("pi:sess-clock")
+            .expect("checkpoint query")
+            .expect("checkpoint");
+        assert_eq!(checkpoint.updated_at, later);
+    }
+
     #[test]
     fn ingest_session_file_is_incremental_and_persists_compaction_checkpoint() {
         let file = write_session(
@@ -907,6 +1087,67 @@ mod tests {
         assert_eq!(store.raw_event_count("pi:sess-2").expect("raw count"), 3);
     }
 
+    #[test]
+    fn errors_plans_and_thinking_only_turns_become_typed_events() {
+        let session = write_session(
+            r#"{"type":"session","version":3,"id":"sess-typed","timestamp":"2024-12-03T14:00:00.000Z","cwd":"/tmp/proj"}
+{"type":"message","id":"a1","parentId":null,"timestamp":"2024-12-03T14:00:01.000Z","message":{"role":"assistant","content":[],"stopReason":"error","errorMessage":"overloaded_error: try again"}}
+{"type":"message","id":"a2","parentId":"a1","timestamp":"2024-12-03T14:00:02.000Z","message":{"role":"assistant","content":[{"type":"thinking","thinking":"Check the migration order first."}]}}
+{"type":"message","id":"a3","parentId":"a2","timestamp":"2024-12-03T14:00:03.000Z","message":{"role":"assistant","content":[{"type":"thinking","thinking":"","redacted":true}]}}
+{"type":"custom","id":"p1","parentId":"a3","timestamp":"2024-12-03T14:00:04.000Z","name":"todos","payload":{"todos":[{"content":"write migration","status":"completed"},{"content":"add tests","status":"in_progress"}]}}
+"#,
+        );
+        let store = MindStore::open_in_memory().expect("open store");
+        let ingestor = PiSessionIngestor::new(IngestionOptions::default());
+        let report = ingestor
+            .ingest_session_file(&store, "agent-1", session.path())
+            .expect("ingest");
+        assert_eq!(report.processed_raw_events, 4);
+        // The redacted reasoning turn is stored raw but never compacted.
+        assert_eq!(report.produced_t0_events, 3);
+
+        let body = |event_id: &str| {
+            store
+                .raw_event_by_id(event_id)
+                .expect("query")
+                .expect("event exists")
+                .body
+        };
+        let RawEventBody::Error(error) = body("pi:a1") else {
+            panic!("expected error event");
+        };
+        assert_eq!(error.source.as_deref(), Some("provider"));
+        assert_eq!(error.message, "overloaded_error: try again");
+        let RawEventBody::Reasoning(reasoning) = body("pi:a2") else {
+            panic!("expected reasoning event");
+        };
+        assert_eq!(reasoning.summary, "Check the migration order first.");
+        assert!(matches!(
+            body("pi:a3"),
+            RawEventBody::Reasoning(ReasoningEvent { redacted: true, .. })
+        ));
+        let RawEventBody::PlanUpdate(plan) = body("pi:p1") else {
+            panic!("expected plan update");
+        };
+        assert_eq!(plan.steps.len(), 2);
+        assert_eq!(plan.steps[1].status, PlanStepStatus::InProgress);
+
+        let texts = store
+            .t0_events_for_conversation("pi:sess-typed")
+            .expect("t0 events")
+            .into_iter()
+            .filter_map(|event| event.text)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            vec![
+                "error[provider]: overloaded_error: try again".to_string(),
+                "reasoning: Check the migration order first.".to_string(),
+                "plan (1/2 done): [x] write migration; [~] add tests".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn tool_output_is_dropped_before_raw_storage() {
         let session = write_session(
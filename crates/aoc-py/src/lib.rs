@@ -0,0 +1,546 @@
+//! Python bindings for notebook analysis of a Mind store.
+//!
+//! Built as the `aoc_mind` extension module with maturin (`maturin develop`
+//! or `maturin build --release` in this directory). The store is always
+//! opened read-only, so a notebook can sit next to `aoc live` without
+//! migrating or locking it.
+//!
+//! List methods return columns (a dict of equal-length lists), which
+//! `pandas.DataFrame(...)` takes as is; timestamps are timezone-aware
+//! `datetime` values.
+//!
+//! ```python
+//! import aoc_mind, pandas as pd
+//! mind = aoc_mind.Mind.open_project(".")
+//! df = pd.DataFrame(mind.artifacts(tag="parser", limit=5000))
+//! ```
+
+use aoc_mind::{
+    export_artifacts, mind_store_path, ArtifactExportFormat, ArtifactExportOptions,
+    ArtifactExportScope,
+};
+use aoc_storage::{ArtifactQuery, CanonRevisionState, MindStore, StorageError};
+use chrono::{DateTime, Utc};
+use pyo3::{
+    create_exception,
+    exceptions::{PyException, PyValueError},
+    prelude::*,
+    types::PyDict,
+};
+use std::path::PathBuf;
+
+create_exception!(
+    aoc_mind,
+    MindError,
+    PyException,
+    "Mind store or export failure."
+);
+
+fn mind_err(err: impl std::fmt::Display) -> PyErr {
+    MindError::new_err(err.to_string())
+}
+
+fn parse_timestamp(name: &str, value: Option<&str>) -> PyResult<Option<DateTime<Utc>>> {
+    value
+        .map(|value| {
+            DateTime::parse_from_rfc3339(value.trim())
+                .map(|ts| ts.with_timezone(&Utc))
+                .map_err(|_| PyValueError::new_err(format!("{name} '{value}' is not RFC3339")))
+        })
+        .transpose()
+}
+
+/// A read-only connection to one Mind store.
+#[pyclass(unsendable, module = "aoc_mind")]
+pub struct Mind {
+    store: MindStore,
+    path: PathBuf,
+}
+
+impl Mind {
+    fn open_path(path: PathBuf) -> PyResult<Self> {
+        if !path.exists() {
+            return Err(mind_err(format!(
+                "no mind store at {}; run `aoc init` first",
+                path.display()
+            )));
+        }
+        let store = MindStore::open_read_only(&path).map_err(mind_err)?;
+        Ok(Self { store, path })
+    }
+}
+
+#[pymethods]
+impl Mind {
+    #[new]
+    fn new(path: PathBuf) -> PyResult<Self> {
+        Self::open_path(path)
+    }
+
+    /// Opens the store `aoc` uses for the project at `project_root`.
+    #[staticmethod]
+    fn open_project(project_root: PathBuf) -> PyResult<Self> {
+        Self::open_path(mind_store_path(&project_root))
+    }
+
+    #[getter]
+    fn path(&self) -> PathBuf {
+        self.path.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Mind({:?})", self.path.display().to_string())
+    }
+
+    /// Schema version, per-table row counts, and pending job totals.
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let stats = self.store.stats(Utc::now()).map_err(mind_err)?;
+        let counts = PyDict::new(py);
+        for (table, count) in &stats.table_counts {
+            counts.set_item(table, count)?;
+        }
+        let out = PyDict::new(py);
+        out.set_item("schema_version", stats.schema_version)?;
+        out.set_item("table_counts", counts)?;
+        out.set_item("pending_reflector_jobs", stats.pending_reflector_jobs)?;
+        out.set_item("pending_t3_jobs", stats.pending_t3_jobs)?;
+        Ok(out)
+    }
+
+    /// Conversations with their lineage and artifact activity, most
+    /// recently updated first.
+    #[pyo3(signature = (limit = 1000))]
+    fn conversations<'py>(&self, py: Python<'py>, limit: usize) -> PyResult<Bound<'py, PyDict>> {
+        let lineage = self
+            .store
+            .list_conversation_lineage(limit)
+            .map_err(mind_err)?;
+        let activity = lineage
+            .iter()
+            .map(|row| self.store.conversation_activity(&row.conversation_id))
+            .collect::<Result<Vec<_>, StorageError>>()
+            .map_err(mind_err)?;
+        let columns = PyDict::new(py);
+        columns.set_item(
+            "conversation_id",
+            lineage
+                .iter()
+                .map(|row| &row.conversation_id)
+                .collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "session_id",
+            lineage
+                .iter()
+                .map(|row| &row.session_id)
+                .collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "parent_conversation_id",
+            lineage
+                .iter()
+                .map(|row| row.parent_conversation_id.as_deref())
+                .collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "root_conversation_id",
+            lineage
+                .iter()
+                .map(|row| &row.root_conversation_id)
+                .collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "updated_at",
+            lineage.iter().map(|row| row.updated_at).collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "raw_events",
+            activity
+                .iter()
+                .map(|row| row.raw_events)
+                .collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "t1_observations",
+            activity
+                .iter()
+                .map(|row| row.t1_observations)
+                .collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "t2_reflections",
+            activity
+                .iter()
+                .map(|row| row.t2_reflections)
+                .collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "last_activity",
+            activity
+                .iter()
+                .map(|row| row.last_activity)
+                .collect::<Vec<_>>(),
+        )?;
+        Ok(columns)
+    }
+
+    /// T1/T2 artifacts with the same filters as `aoc query` and
+    /// `GET /v1/artifacts`; `q` terms must all appear in the text.
+    #[pyo3(signature = (
+        q = None,
+        conversation = None,
+        tag = None,
+        task = None,
+        segment = None,
+        kind = None,
+        since = None,
+        until = None,
+        oldest_first = false,
+        offset = 0,
+        limit = 1000,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn artifacts<'py>(
+        &self,
+        py: Python<'py>,
+        q: Option<String>,
+        conversation: Option<String>,
+        tag: Option<String>,
+        task: Option<String>,
+        segment: Option<String>,
+        kind: Option<String>,
+        since: Option<&str>,
+        until: Option<&str>,
+        oldest_first: bool,
+        offset: usize,
+        limit: usize,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        if let Some(kind) = kind.as_deref() {
+            if !matches!(kind, "t1" | "t2") {
+                return Err(PyValueError::new_err(format!(
+                    "kind '{kind}' must be t1 or t2"
+                )));
+            }
+        }
+        let page = self
+            .store
+            .query_artifacts(&ArtifactQuery {
+                text: q,
+                conversation_id: conversation,
+                active_tag: tag,
+                task_id: task,
+                segment_id: segment,
+                kind,
+                since: parse_timestamp("since", since)?,
+                until: parse_timestamp("until", until)?,
+                oldest_first,
+                offset,
+                limit,
+            })
+            .map_err(mind_err)?;
+        let artifacts = &page.artifacts;
+        let columns = PyDict::new(py);
+        columns.set_item(
+            "artifact_id",
+            artifacts
+                .iter()
+                .map(|row| &row.artifact_id)
+                .collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "conversation_id",
+            artifacts
+                .iter()
+                .map(|row| &row.conversation_id)
+                .collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "kind",
+            artifacts.iter().map(|row| &row.kind).collect::<Vec<_>>(),
+        )?;
+        columns.set_item("ts", artifacts.iter().map(|row| row.ts).collect::<Vec<_>>())?;
+        columns.set_item(
+            "text",
+            artifacts.iter().map(|row| &row.text).collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "trace_ids",
+            artifacts
+                .iter()
+                .map(|row| &row.trace_ids)
+                .collect::<Vec<_>>(),
+        )?;
+        Ok(columns)
+    }
+
+    /// One artifact with its task links and segment route, or `None`.
+    fn artifact<'py>(
+        &self,
+        py: Python<'py>,
+        artifact_id: &str,
+    ) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let Some(artifact) = self.store.artifact_by_id(artifact_id).map_err(mind_err)? else {
+            return Ok(None);
+        };
+        let task_ids = self
+            .store
+            .artifact_task_links_for_artifact(artifact_id)
+            .map_err(mind_err)?
+            .into_iter()
+            .map(|link| link.task_id)
+            .collect::<Vec<_>>();
+        let route = self
+            .store
+            .segment_route_for_artifact(artifact_id)
+            .map_err(mind_err)?;
+        let out = PyDict::new(py);
+        out.set_item("artifact_id", artifact.artifact_id)?;
+        out.set_item("conversation_id", artifact.conversation_id)?;
+        out.set_item("kind", artifact.kind)?;
+        out.set_item("ts", artifact.ts)?;
+        out.set_item("text", artifact.text)?;
+        out.set_item("trace_ids", artifact.trace_ids)?;
+        out.set_item("task_ids", task_ids)?;
+        out.set_item(
+            "segment_id",
+            route
+                .as_ref()
+                .map(|route| route.primary.segment_id.as_str()),
+        )?;
+        out.set_item(
+            "archived",
+            self.store
+                .is_artifact_archived(artifact_id)
+                .map_err(mind_err)?,
+        )?;
+        Ok(Some(out))
+    }
+
+    /// Canon entries in `state` (`active`, `superseded`, or `stale`).
+    #[pyo3(signature = (state = "active", topic = None))]
+    fn canon<'py>(
+        &self,
+        py: Python<'py>,
+        state: &str,
+        topic: Option<&str>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let state = match state {
+            "active" => CanonRevisionState::Active,
+            "superseded" => CanonRevisionState::Superseded,
+            "stale" => CanonRevisionState::Stale,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "state '{other}' must be active, superseded, or stale"
+                )))
+            }
+        };
+        let entries = self
+            .store
+            .canon_entries_by_state(state, topic)
+            .map_err(mind_err)?;
+        let columns = PyDict::new(py);
+        columns.set_item(
+            "entry_id",
+            entries.iter().map(|row| &row.entry_id).collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "revision",
+            entries.iter().map(|row| row.revision).collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "topic",
+            entries
+                .iter()
+                .map(|row| row.topic.as_deref())
+                .collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "summary",
+            entries.iter().map(|row| &row.summary).collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "confidence_bps",
+            entries
+                .iter()
+                .map(|row| row.confidence_bps)
+                .collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "freshness_score",
+            entries
+                .iter()
+                .map(|row| row.freshness_score)
+                .collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "evidence_refs",
+            entries
+                .iter()
+                .map(|row| &row.evidence_refs)
+                .collect::<Vec<_>>(),
+        )?;
+        columns.set_item(
+            "created_at",
+            entries.iter().map(|row| row.created_at).collect::<Vec<_>>(),
+        )?;
+        Ok(columns)
+    }
+
+    /// Payload text of the latest handshake pack, or `None`.
+    fn handshake(&self, scope: &str, scope_key: &str) -> PyResult<Option<String>> {
+        Ok(self
+            .store
+            .latest_handshake_snapshot(scope, scope_key)
+            .map_err(mind_err)?
+            .map(|snapshot| snapshot.payload_text))
+    }
+
+    /// Writes artifacts to chunk files under `out_dir` (resumable, like
+    /// `aoc export artifacts`). `parquet` output loads with
+    /// `pandas.read_parquet(out_dir)`.
+    #[pyo3(signature = (
+        out_dir,
+        format = "parquet",
+        conversation = None,
+        tag = None,
+        segment = None,
+        chunk_size = 5000,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn export<'py>(
+        &self,
+        py: Python<'py>,
+        out_dir: PathBuf,
+        format: &str,
+        conversation: Option<String>,
+        tag: Option<String>,
+        segment: Option<String>,
+        chunk_size: usize,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let format = match format {
+            "jsonl" => ArtifactExportFormat::Jsonl,
+            "parquet" => ArtifactExportFormat::Parquet,
+            "markdown" => ArtifactExportFormat::Markdown,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "format '{other}' must be jsonl, parquet, or markdown"
+                )))
+            }
+        };
+        let scope = match (conversation, tag, segment) {
+            (None, None, None) => ArtifactExportScope::All,
+            (Some(id), None, None) => ArtifactExportScope::Conversation(id),
+            (None, Some(tag), None) => ArtifactExportScope::Tag(tag),
+            (None, None, Some(segment)) => ArtifactExportScope::Segment(segment),
+            _ => {
+                return Err(PyValueError::new_err(
+                    "pass at most one of conversation, tag, or segment",
+                ))
+            }
+        };
+        let report = export_artifacts(
+            &self.store,
+            &scope,
+            format,
+            &out_dir,
+            ArtifactExportOptions { chunk_size },
+        )
+        .map_err(mind_err)?;
+        let out = PyDict::new(py);
+        out.set_item("artifacts_exported", report.artifacts_exported)?;
+        out.set_item("artifacts_total", report.artifacts_total)?;
+        out.set_item("chunks_written", report.chunks_written)?;
+        out.set_item("chunks_skipped", report.chunks_skipped)?;
+        out.set_item("resumed", report.resumed)?;
+        Ok(out)
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "aoc_mind")]
+fn aoc_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Mind>()?;
+    m.add("MindError", m.py().get_type::<MindError>())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyList;
+
+    #[test]
+    fn columns_line_up_for_dataframes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("mind.sqlite");
+        let store = MindStore::open(&path).expect("store");
+        let ts = DateTime::parse_from_rfc3339("2026-03-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        for (id, text) in [("obs:1", "parser retries"), ("obs:2", "ui polish")] {
+            store
+                .insert_observation(id, "conv-py", ts, text, &["raw:1".to_string()])
+                .expect("observation");
+        }
+        drop(store);
+
+        Python::initialize();
+        Python::attach(|py| {
+            let mind = Mind::new(path.clone()).expect("open");
+            let columns = mind
+                .artifacts(
+                    py, None, None, None, None, None, None, None, None, true, 0, 10,
+                )
+                .expect("artifacts");
+            let ids = columns.get_item("artifact_id").unwrap().unwrap();
+            let ids = ids.cast::<PyList>().expect("list");
+            assert_eq!(ids.len(), 2);
+            assert_eq!(
+                ids.get_item(0).unwrap().extract::<String>().unwrap(),
+                "obs:1"
+            );
+            for column in ["conversation_id", "kind", "ts", "text", "trace_ids"] {
+                let values = columns.get_item(column).unwrap().unwrap();
+                assert_eq!(values.len().unwrap(), 2, "{column}");
+            }
+            let first_ts = columns
+                .get_item("ts")
+                .unwrap()
+                .unwrap()
+                .get_item(0)
+                .unwrap();
+            assert_eq!(first_ts.extract::<DateTime<Utc>>().unwrap(), ts);
+
+            let detail = mind
+                .artifact(py, "obs:2")
+                .expect("artifact")
+                .expect("found");
+            assert_eq!(
+                detail
+                    .get_item("text")
+                    .unwrap()
+                    .unwrap()
+                    .extract::<String>()
+                    .unwrap(),
+                "ui polish"
+            );
+            assert!(mind.artifact(py, "obs:404").expect("lookup").is_none());
+            assert!(mind
+                .artifacts(
+                    py,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    Some("t9".into()),
+                    None,
+                    None,
+                    false,
+                    0,
+                    10
+                )
+                .is_err());
+        });
+    }
+}
@@ -2,14 +2,23 @@ use aoc_core::mind_contracts::{
     ArtifactTaskLink, ArtifactTaskRelation, MindContractError, RouteOrigin, SegmentCandidate,
     SegmentRoute,
 };
-use aoc_storage::{ConversationContextState, MindStore, StorageError, StoredArtifact};
+use aoc_core::{ErrorCode, ErrorCoded};
+use aoc_storage::{
+    ContextTimeline, ConversationContextState, MindStore, StorageError, StoredArtifact,
+    StoredRouteOverride,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::cmp::max;
 use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use thiserror::Error;
 
 const ROUTE_CONF_TASKMASTER: u16 = 9_600;
 const ROUTE_CONF_UNCERTAIN: u16 = 5_300;
 const ROUTE_CONF_GLOBAL_FALLBACK: u16 = 5_000;
+const MAX_BPS: u16 = 10_000;
 
 #[derive(Debug, Error)]
 pub enum RoutingError {
@@ -19,13 +28,36 @@ pub enum RoutingError {
     Contract(#[from] MindContractError),
     #[error("invalid override patch for artifact {artifact_id}: {reason}")]
     InvalidOverridePatch { artifact_id: String, reason: String },
+    #[error("unknown artifact: {0}")]
+    UnknownArtifact(String),
+    #[error("`{key}`: {message}")]
+    InvalidConfig { key: String, message: String },
+    #[error("{}: {message}", path.display())]
+    ConfigFile { path: PathBuf, message: String },
 }
 
-#[derive(Debug, Clone)]
+impl ErrorCoded for RoutingError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Contract(err) => err.error_code(),
+            Self::InvalidOverridePatch { .. } => ErrorCode::InvalidInput,
+            Self::UnknownArtifact(_) => ErrorCode::NotFound,
+            Self::InvalidConfig { .. } | Self::ConfigFile { .. } => ErrorCode::Config,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct SegmentRoutingConfig {
     pub tag_to_segment: BTreeMap<String, String>,
     pub task_to_segment: BTreeMap<String, String>,
     pub segment_keywords: BTreeMap<String, Vec<String>>,
+    /// Repo-relative path prefixes per segment, matched against the files a
+    /// tool call touched on the artifact's trace.
+    #[serde(default)]
+    pub segment_paths: BTreeMap<String, Vec<String>>,
     pub low_confidence_threshold_bps: u16,
     pub ambiguous_delta_bps: u16,
     pub default_global_segment: String,
@@ -60,6 +92,7 @@ impl Default for SegmentRoutingConfig {
             tag_to_segment,
             task_to_segment: BTreeMap::new(),
             segment_keywords,
+            segment_paths: BTreeMap::new(),
             low_confidence_threshold_bps: 6_500,
             ambiguous_delta_bps: 350,
             default_global_segment: "global".to_string(),
@@ -69,6 +102,117 @@ impl Default for SegmentRoutingConfig {
     }
 }
 
+impl SegmentRoutingConfig {
+    /// Reads a routing file written like aoc.toml's `[routing]` table. Keys
+    /// it sets replace the defaults, with maps merging entry by entry, and
+    /// the result is validated.
+    pub fn from_path(path: &Path) -> Result<Self, RoutingError> {
+        let file_error = |message: String| RoutingError::ConfigFile {
+            path: path.to_path_buf(),
+            message,
+        };
+        let text = std::fs::read_to_string(path).map_err(|err| file_error(err.to_string()))?;
+        let overlay = text
+            .parse::<toml::Table>()
+            .map_err(|err| file_error(err.message().to_string()))?;
+        let mut table = match toml::Value::try_from(Self::default()) {
+            Ok(toml::Value::Table(table)) => table,
+            _ => unreachable!("default routing config serializes to a table"),
+        };
+        merge_tables(&mut table, &overlay);
+        let config = Self::deserialize(toml::Value::Table(table))
+            .map_err(|err| file_error(err.message().to_string()))?;
+        config
+            .validate()
+            .map_err(|err| file_error(err.to_string()))?;
+        Ok(config)
+    }
+
+    /// Checks thresholds and segment references; errors name the key
+    /// relative to the routing table.
+    pub fn validate(&self) -> Result<(), RoutingError> {
+        let invalid = |key: &str, message: String| RoutingError::InvalidConfig {
+            key: key.to_string(),
+            message,
+        };
+        for (key, bps) in [
+            (
+                "low_confidence_threshold_bps",
+                self.low_confidence_threshold_bps,
+            ),
+            ("ambiguous_delta_bps", self.ambiguous_delta_bps),
+        ] {
+            if bps > MAX_BPS {
+                return Err(invalid(key, format!("must be <= {MAX_BPS}")));
+            }
+        }
+        if self.default_global_segment == self.default_uncertain_segment {
+            return Err(invalid(
+                "default_uncertain_segment",
+                "must differ from default_global_segment".to_string(),
+            ));
+        }
+        for (tag, segment) in &self.tag_to_segment {
+            if !self.segment_keywords.contains_key(segment) {
+                return Err(invalid(
+                    &format!("tag_to_segment.{tag}"),
+                    format!("unknown segment '{segment}'"),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn merge_tables(base: &mut toml::Table, overlay: &toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table)
+            }
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Polls a routing file and hands back a new config whenever its mtime or
+/// size changes, so a long-running pipeline can pick up edits without a
+/// restart.
+pub struct RoutingConfigWatcher {
+    path: PathBuf,
+    seen: Option<(Option<SystemTime>, Option<u64>)>,
+}
+
+impl RoutingConfigWatcher {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            seen: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// `None` while the file is unchanged; the first poll always reads it.
+    /// A file that fails to load is not retried until it changes again.
+    pub fn poll(&mut self) -> Option<Result<SegmentRoutingConfig, RoutingError>> {
+        let metadata = std::fs::metadata(&self.path).ok();
+        let stamp = (
+            metadata.as_ref().and_then(|meta| meta.modified().ok()),
+            metadata.as_ref().map(|meta| meta.len()),
+        );
+        if self.seen == Some(stamp) {
+            return None;
+        }
+        self.seen = Some(stamp);
+        Some(SegmentRoutingConfig::from_path(&self.path))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RouteOverridePatch {
     pub patch_id: String,
@@ -90,7 +234,46 @@ impl Default for RouteOverridePatch {
     }
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+impl RouteOverridePatch {
+    fn to_stored(&self, artifact: &StoredArtifact) -> StoredRouteOverride {
+        StoredRouteOverride {
+            artifact_id: artifact.artifact_id.clone(),
+            conversation_id: artifact.conversation_id.clone(),
+            patch_id: self.patch_id.clone(),
+            primary_segment: self.primary_segment.clone(),
+            secondary_segments: self.secondary_segments.clone(),
+            reason: self.reason.clone(),
+            confidence_bps: self.confidence_bps,
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+impl From<StoredRouteOverride> for RouteOverridePatch {
+    fn from(stored: StoredRouteOverride) -> Self {
+        Self {
+            patch_id: stored.patch_id,
+            primary_segment: stored.primary_segment,
+            secondary_segments: stored.secondary_segments,
+            reason: stored.reason,
+            confidence_bps: stored.confidence_bps,
+        }
+    }
+}
+
+/// Outcome of [`SegmentRouter::reroute_all`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct RerouteReport {
+    pub conversations: usize,
+    /// Routed artifacts whose route was re-computed.
+    pub artifacts_rerouted: usize,
+    /// Routes that differed and were replaced.
+    pub routes_changed: usize,
+    /// Of those, routes whose primary segment moved.
+    pub primaries_changed: usize,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
 pub struct RoutingReport {
     pub artifacts_processed: usize,
     pub routes_written: usize,
@@ -100,6 +283,22 @@ pub struct RoutingReport {
     pub uncertain_fallbacks: usize,
 }
 
+/// A routing option shown to a reviewer, with the evidence behind its score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteReviewCandidate {
+    pub segment_id: String,
+    pub confidence_bps: u16,
+    pub evidence: Vec<String>,
+}
+
+/// Everything needed to resolve one entry of the uncertain-route queue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteReview {
+    pub artifact: StoredArtifact,
+    pub route: Option<SegmentRoute>,
+    pub candidates: Vec<RouteReviewCandidate>,
+}
+
 #[derive(Debug, Clone)]
 struct ScoredSegment {
     segment_id: String,
@@ -107,6 +306,9 @@ struct ScoredSegment {
     reasons: BTreeSet<String>,
 }
 
+/// Routes artifacts to segments. Override patches come from the store, where
+/// [`SegmentRouter::override_artifact`] keeps them, and from the map given to
+/// [`SegmentRouter::with_overrides`], which wins for the same artifact.
 pub struct SegmentRouter {
     config: SegmentRoutingConfig,
     overrides: BTreeMap<String, RouteOverridePatch>,
@@ -127,6 +329,20 @@ impl SegmentRouter {
         Self { config, overrides }
     }
 
+    pub fn config(&self) -> &SegmentRoutingConfig {
+        &self.config
+    }
+
+    /// Swaps in a reloaded config; overrides are kept.
+    pub fn set_config(&mut self, config: SegmentRoutingConfig) {
+        self.config = config;
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(stage = "route", conversation_id = %conversation_id)
+    )]
     pub fn route_conversation(
         &self,
         store: &MindStore,
@@ -139,23 +355,14 @@ impl SegmentRouter {
 
         let contexts = store.context_states(conversation_id)?;
         let mut report = RoutingReport::default();
-        let mut context_cursor = 0usize;
-        let mut current_context: Option<&ConversationContextState> = None;
+        let mut timeline = ContextTimeline::new(&contexts);
+        let stored_overrides = stored_overrides(store, conversation_id)?;
 
         for artifact in artifacts {
             report.artifacts_processed += 1;
-            while context_cursor < contexts.len() && contexts[context_cursor].ts <= artifact.ts {
-                current_context = Some(&contexts[context_cursor]);
-                context_cursor += 1;
-            }
-
-            let task_links = store.artifact_task_links_for_artifact(&artifact.artifact_id)?;
-            let auto_route = self.compute_auto_route(&artifact, current_context, &task_links)?;
-            let route = if let Some(patch) = self.overrides.get(&artifact.artifact_id) {
-                self.apply_override(auto_route, patch)?
-            } else {
-                auto_route
-            };
+            let current_context = timeline.at(artifact.ts);
+            let route =
+                self.route_artifact(store, &artifact, current_context, &stored_overrides)?;
 
             if eq_segment(
                 &route.primary.segment_id,
@@ -177,11 +384,164 @@ impl SegmentRouter {
         Ok(report)
     }
 
+    /// Re-computes the route of every routed active artifact at or after
+    /// `since` (all of them without a bound) under the current config and
+    /// overrides, e.g. after fixing the keyword map. A changed route replaces
+    /// the stored one and the old route goes to `segment_route_history`;
+    /// unchanged routes are not rewritten.
+    #[tracing::instrument(level = "debug", skip_all, fields(stage = "reroute"))]
+    pub fn reroute_all(
+        &self,
+        store: &MindStore,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<RerouteReport, RoutingError> {
+        let mut report = RerouteReport::default();
+        for conversation_id in store.routed_conversation_ids(since)? {
+            report.conversations += 1;
+            let contexts = store.context_states(&conversation_id)?;
+            let mut timeline = ContextTimeline::new(&contexts);
+            let stored_overrides = stored_overrides(store, &conversation_id)?;
+            for artifact in store.artifacts_for_conversation(&conversation_id)? {
+                // Every artifact moves the timeline, in or out of the window.
+                let context = timeline.at(artifact.ts);
+                if since.is_some_and(|since| artifact.ts < since) {
+                    continue;
+                }
+                let Some(previous) = store.segment_route_for_artifact(&artifact.artifact_id)?
+                else {
+                    continue;
+                };
+                report.artifacts_rerouted += 1;
+                let route = self.route_artifact(store, &artifact, context, &stored_overrides)?;
+                if same_route(&previous, &route) {
+                    continue;
+                }
+                if !eq_segment(&previous.primary.segment_id, &route.primary.segment_id) {
+                    report.primaries_changed += 1;
+                }
+                store.record_segment_route_change(&previous, &route, Utc::now())?;
+                report.routes_changed += 1;
+            }
+        }
+        Ok(report)
+    }
+
+    /// Artifacts currently parked on `default_uncertain_segment`, newest first.
+    pub fn uncertain_queue(
+        &self,
+        store: &MindStore,
+        limit: usize,
+    ) -> Result<Vec<String>, RoutingError> {
+        let uncertain_segment = normalize_segment(self.config.default_uncertain_segment.as_str())
+            .unwrap_or_else(|| "uncertain".to_string());
+        Ok(store.artifacts_with_primary_segment(&uncertain_segment, limit)?)
+    }
+
+    /// Loads an artifact with its stored route and scored heuristic candidates.
+    ///
+    /// Candidates keep their keyword/task-link evidence; fallback segments that
+    /// only appear on the stored route are listed after them.
+    pub fn review_artifact(
+        &self,
+        store: &MindStore,
+        artifact_id: &str,
+    ) -> Result<Option<RouteReview>, RoutingError> {
+        let Some(artifact) = store.artifact_by_id(artifact_id)? else {
+            return Ok(None);
+        };
+        let task_links = store.artifact_task_links_for_artifact(artifact_id)?;
+        let touched_paths = artifact_touched_paths(store, &artifact)?;
+        let route = store.segment_route_for_artifact(artifact_id)?;
+
+        let mut candidates = self
+            .heuristic_candidates(&artifact.text, &task_links, &touched_paths)
+            .into_iter()
+            .map(|scored| RouteReviewCandidate {
+                segment_id: scored.segment_id,
+                confidence_bps: scored.confidence_bps,
+                evidence: scored.reasons.into_iter().collect(),
+            })
+            .collect::<Vec<_>>();
+        let uncertain_segment = self.config.default_uncertain_segment.as_str();
+        for candidate in route.iter().flat_map(|route| route.secondary.iter()) {
+            if eq_segment(&candidate.segment_id, uncertain_segment)
+                || candidates
+                    .iter()
+                    .any(|known| eq_segment(&known.segment_id, &candidate.segment_id))
+            {
+                continue;
+            }
+            candidates.push(RouteReviewCandidate {
+                segment_id: candidate.segment_id.clone(),
+                confidence_bps: candidate.confidence_bps,
+                evidence: vec!["route_fallback".to_string()],
+            });
+        }
+
+        Ok(Some(RouteReview {
+            artifact,
+            route,
+            candidates,
+        }))
+    }
+
+    /// Re-routes a single artifact under `patch` and stores the result. The
+    /// patch is kept in the store, so later routing passes re-apply it.
+    pub fn override_artifact(
+        &self,
+        store: &MindStore,
+        artifact_id: &str,
+        patch: &RouteOverridePatch,
+    ) -> Result<SegmentRoute, RoutingError> {
+        let artifact = store
+            .artifact_by_id(artifact_id)?
+            .ok_or_else(|| RoutingError::UnknownArtifact(artifact_id.to_string()))?;
+        let contexts = store.context_states(&artifact.conversation_id)?;
+        let context = ContextTimeline::new(&contexts).at(artifact.ts);
+        let task_links = store.artifact_task_links_for_artifact(artifact_id)?;
+        let touched_paths = artifact_touched_paths(store, &artifact)?;
+        let auto_route =
+            self.compute_auto_route(&artifact, context, &task_links, &touched_paths)?;
+        let route = self.apply_override(auto_route, patch)?;
+        store.upsert_route_override(&patch.to_stored(&artifact))?;
+        store.replace_segment_route(&route)?;
+        Ok(route)
+    }
+
+    /// Segment for free text that has no artifact behind it, such as a
+    /// decision typed in by hand. Only keyword evidence applies. Short text
+    /// rarely clears `low_confidence_threshold_bps`, so any unambiguous match
+    /// is returned with its score; no match or a tie yields `None` and the
+    /// caller picks its own fallback.
+    pub fn route_text(&self, text: &str) -> Option<RouteReviewCandidate> {
+        let candidates = self.heuristic_candidates(text, &[], &[]);
+        let top = candidates.first()?;
+        let ambiguous = candidates.get(1).is_some_and(|second| {
+            top.confidence_bps.saturating_sub(second.confidence_bps)
+                <= self.config.ambiguous_delta_bps
+        });
+        if ambiguous {
+            return None;
+        }
+        Some(RouteReviewCandidate {
+            segment_id: top.segment_id.clone(),
+            confidence_bps: top.confidence_bps,
+            evidence: top.reasons.iter().cloned().collect(),
+        })
+    }
+
+    /// Segment used when nothing more specific applies.
+    pub fn global_segment(&self) -> String {
+        normalize_segment(self.config.default_global_segment.as_str())
+            .unwrap_or_else(|| "global".to_string())
+    }
+
     fn compute_auto_route(
         &self,
         artifact: &StoredArtifact,
         context: Option<&ConversationContextState>,
         task_links: &[ArtifactTaskLink],
+        touched_paths: &[String],
     ) -> Result<SegmentRoute, RoutingError> {
         if let Some(active_tag) = context
             .and_then(|snapshot| snapshot.active_tag.as_deref())
@@ -189,7 +549,8 @@ impl SegmentRouter {
             .filter(|value| !value.is_empty())
         {
             if let Some(segment_id) = lookup_segment(&self.config.tag_to_segment, active_tag) {
-                let candidate_pool = self.heuristic_candidates(artifact, task_links);
+                let candidate_pool =
+                    self.heuristic_candidates(&artifact.text, task_links, touched_paths);
                 let mut secondary = Vec::new();
                 for candidate in candidate_pool {
                     if secondary.len() >= self.config.max_secondary_segments {
@@ -217,15 +578,16 @@ impl SegmentRouter {
             }
         }
 
-        self.compute_heuristic_route(artifact, task_links)
+        self.compute_heuristic_route(artifact, task_links, touched_paths)
     }
 
     fn compute_heuristic_route(
         &self,
         artifact: &StoredArtifact,
         task_links: &[ArtifactTaskLink],
+        touched_paths: &[String],
     ) -> Result<SegmentRoute, RoutingError> {
-        let candidates = self.heuristic_candidates(artifact, task_links);
+        let candidates = self.heuristic_candidates(&artifact.text, task_links, touched_paths);
         let Some(top) = candidates.first() else {
             return self.uncertain_fallback(
                 artifact,
@@ -331,8 +693,9 @@ impl SegmentRouter {
 
     fn heuristic_candidates(
         &self,
-        artifact: &StoredArtifact,
+        text: &str,
         task_links: &[ArtifactTaskLink],
+        touched_paths: &[String],
     ) -> Vec<ScoredSegment> {
         let mut scores = BTreeMap::<String, ScoredSegment>::new();
 
@@ -353,7 +716,7 @@ impl SegmentRouter {
             }
         }
 
-        let lower_text = artifact.text.to_lowercase();
+        let lower_text = text.to_lowercase();
         for (segment_id, keywords) in &self.config.segment_keywords {
             let normalized_segment = normalize_segment(segment_id).unwrap_or_default();
             if normalized_segment.is_empty() {
@@ -379,6 +742,36 @@ impl SegmentRouter {
             );
         }
 
+        for (segment_id, prefixes) in &self.config.segment_paths {
+            let Some(normalized_segment) = normalize_segment(segment_id) else {
+                continue;
+            };
+            let hits = prefixes
+                .iter()
+                .map(|prefix| prefix.trim().trim_start_matches("./").trim_end_matches('/'))
+                .filter(|prefix| {
+                    !prefix.is_empty()
+                        && touched_paths
+                            .iter()
+                            .any(|path| path_under_prefix(path, prefix))
+                })
+                .collect::<BTreeSet<_>>();
+            if hits.is_empty() {
+                continue;
+            }
+
+            let score = path_score(hits.len());
+            upsert_score(
+                &mut scores,
+                normalized_segment,
+                score,
+                format!(
+                    "path_match:{}",
+                    hits.into_iter().collect::<Vec<_>>().join("+")
+                ),
+            );
+        }
+
         let mut ordered = scores.into_values().collect::<Vec<_>>();
         ordered.sort_by(|left, right| {
             right
@@ -389,6 +782,28 @@ impl SegmentRouter {
         ordered
     }
 
+    /// Route for one artifact: the automatic route, under its override patch
+    /// if one applies (an in-memory patch before a stored one).
+    fn route_artifact(
+        &self,
+        store: &MindStore,
+        artifact: &StoredArtifact,
+        context: Option<&ConversationContextState>,
+        stored_overrides: &BTreeMap<String, RouteOverridePatch>,
+    ) -> Result<SegmentRoute, RoutingError> {
+        let task_links = store.artifact_task_links_for_artifact(&artifact.artifact_id)?;
+        let touched_paths = artifact_touched_paths(store, artifact)?;
+        let auto_route = self.compute_auto_route(artifact, context, &task_links, &touched_paths)?;
+        let patch = self
+            .overrides
+            .get(&artifact.artifact_id)
+            .or_else(|| stored_overrides.get(&artifact.artifact_id));
+        match patch {
+            Some(patch) => self.apply_override(auto_route, patch),
+            None => Ok(auto_route),
+        }
+    }
+
     fn apply_override(
         &self,
         auto_route: SegmentRoute,
@@ -490,11 +905,84 @@ fn eq_segment(left: &str, right: &str) -> bool {
     normalized_key(left) == normalized_key(right)
 }
 
+/// Stored overrides of a conversation keyed by artifact id.
+fn stored_overrides(
+    store: &MindStore,
+    conversation_id: &str,
+) -> Result<BTreeMap<String, RouteOverridePatch>, StorageError> {
+    Ok(store
+        .route_overrides_for_conversation(conversation_id)?
+        .into_iter()
+        .map(|stored| (stored.artifact_id.clone(), RouteOverridePatch::from(stored)))
+        .collect())
+}
+
+/// Compares routes the way storage keeps them: secondaries by confidence
+/// then segment id, reasons trimmed.
+fn same_route(stored: &SegmentRoute, computed: &SegmentRoute) -> bool {
+    let secondaries = |route: &SegmentRoute| {
+        let mut secondary = route.secondary.clone();
+        secondary.sort_by(|left, right| {
+            right
+                .confidence_bps
+                .cmp(&left.confidence_bps)
+                .then_with(|| left.segment_id.cmp(&right.segment_id))
+        });
+        secondary
+    };
+    stored.primary == computed.primary
+        && secondaries(stored) == secondaries(computed)
+        && stored.routed_by == computed.routed_by
+        && stored.reason.trim() == computed.reason.trim()
+        && stored.overridden_by == computed.overridden_by
+}
+
 fn keyword_score(hit_count: usize) -> u16 {
     let score = 4_200_u16.saturating_add((hit_count as u16).saturating_mul(550));
     score.min(7_800)
 }
 
+/// Touching a segment's files is stronger evidence than naming its keywords;
+/// a single matching prefix clears the default low-confidence threshold.
+fn path_score(hit_count: usize) -> u16 {
+    let score = 6_200_u16.saturating_add((hit_count as u16).saturating_mul(700));
+    score.min(8_600)
+}
+
+fn path_under_prefix(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Paths touched by the tool calls on an artifact's trace: T0 ids directly,
+/// plus one hop through traced artifacts so reflections see their
+/// observations' paths.
+fn artifact_touched_paths(
+    store: &MindStore,
+    artifact: &StoredArtifact,
+) -> Result<Vec<String>, StorageError> {
+    let mut paths = BTreeSet::new();
+    let mut compact_ids = Vec::new();
+    for trace_id in &artifact.trace_ids {
+        if trace_id == &artifact.artifact_id {
+            continue;
+        }
+        match store.artifact_by_id(trace_id)? {
+            Some(traced) => compact_ids.extend(traced.trace_ids),
+            None => compact_ids.push(trace_id.clone()),
+        }
+    }
+    for compact_id in compact_ids {
+        if let Some(tool_meta) = store
+            .compact_event_by_id(&compact_id)?
+            .and_then(|event| event.tool_meta)
+        {
+            paths.extend(tool_meta.touched_paths);
+        }
+    }
+    Ok(paths.into_iter().collect())
+}
+
 fn task_link_score(relation: ArtifactTaskRelation, confidence_bps: u16) -> u16 {
     let relation_boost = match relation {
         ArtifactTaskRelation::Active => 1_300,
@@ -640,6 +1128,71 @@ mod tests {
             .any(|candidate| candidate.segment_id == config.default_global_segment));
     }
 
+    #[test]
+    fn touched_paths_on_the_trace_route_by_segment_paths() {
+        use aoc_core::mind_contracts::{
+            compact_raw_event_to_t0, RawEvent, RawEventBody, T0CompactionPolicy,
+            ToolExecutionStatus, ToolResultEvent,
+        };
+
+        let store = MindStore::open_in_memory().expect("open store");
+        let raw = RawEvent {
+            event_id: "evt-edit".to_string(),
+            conversation_id: "conv-paths".to_string(),
+            agent_id: "agent-1".to_string(),
+            ts: ts(15, 0, 0),
+            body: RawEventBody::ToolResult(ToolResultEvent {
+                tool_name: "edit".to_string(),
+                status: ToolExecutionStatus::Success,
+                latency_ms: None,
+                exit_code: None,
+                output: None,
+                redacted: false,
+                touched_paths: vec!["/work/repo/web/src/App.tsx".to_string()],
+                cwd: Some("/work/repo".to_string()),
+                output_digest: None,
+                retry_count: 0,
+            }),
+            attrs: Default::default(),
+        };
+        store.insert_raw_event(&raw).expect("insert raw");
+        let compact = compact_raw_event_to_t0(&raw, &T0CompactionPolicy::default())
+            .expect("compact")
+            .expect("tool compacts");
+        store.upsert_t0_compact_event(&compact).expect("upsert t0");
+        store
+            .insert_observation(
+                "obs-paths",
+                "conv-paths",
+                ts(15, 0, 5),
+                "renamed a prop",
+                std::slice::from_ref(&compact.compact_id),
+            )
+            .expect("insert observation");
+
+        let mut config = SegmentRoutingConfig::default();
+        config.tag_to_segment.clear();
+        config.segment_paths.insert(
+            "frontend".to_string(),
+            vec!["web/".to_string(), "web/src/components".to_string()],
+        );
+        config
+            .segment_paths
+            .insert("backend".to_string(), vec!["web/srcs".to_string()]);
+        SegmentRouter::new(config)
+            .route_conversation(&store, "conv-paths")
+            .expect("route conversation");
+
+        let route = store
+            .segment_route_for_artifact("obs-paths")
+            .expect("load route")
+            .expect("route exists");
+        assert_eq!(route.routed_by, RouteOrigin::Heuristic);
+        assert_eq!(route.primary.segment_id, "frontend");
+        assert!(route.reason.contains("path_match:web"));
+        assert!(route.secondary.is_empty());
+    }
+
     #[test]
     fn override_patch_rewrites_primary_and_keeps_provenance() {
         let store = MindStore::open_in_memory().expect("open store");
@@ -697,4 +1250,212 @@ mod tests {
             .iter()
             .any(|candidate| candidate.segment_id == "mind"));
     }
+
+    #[test]
+    fn review_lists_uncertain_artifacts_and_override_clears_them() {
+        let store = MindStore::open_in_memory().expect("open store");
+        store
+            .insert_observation(
+                "obs-4",
+                "conv-4",
+                ts(15, 0, 0),
+                "ui api request parser",
+                &[],
+            )
+            .expect("insert observation");
+        store
+            .insert_observation("obs-5", "conv-4", ts(15, 0, 5), "db migration for api", &[])
+            .expect("insert observation");
+
+        let mut config = SegmentRoutingConfig::default();
+        config.tag_to_segment.clear();
+        config.low_confidence_threshold_bps = 4_000;
+        let router = SegmentRouter::new(config);
+        router
+            .route_conversation(&store, "conv-4")
+            .expect("route conversation");
+        assert_eq!(
+            router.uncertain_queue(&store, 10).expect("queue"),
+            vec!["obs-4".to_string()]
+        );
+
+        let review = router
+            .review_artifact(&store, "obs-4")
+            .expect("review")
+            .expect("artifact exists");
+        assert_eq!(review.artifact.artifact_id, "obs-4");
+        assert_eq!(
+            review.route.as_ref().map(|route| route.routed_by),
+            Some(RouteOrigin::Heuristic)
+        );
+        let segments = review
+            .candidates
+            .iter()
+            .map(|candidate| candidate.segment_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(segments, vec!["backend", "frontend", "global"]);
+        assert_eq!(review.candidates[0].evidence, vec!["keyword_match:api"]);
+        assert_eq!(review.candidates[2].evidence, vec!["route_fallback"]);
+
+        let route = router
+            .override_artifact(
+                &store,
+                "obs-4",
+                &RouteOverridePatch {
+                    patch_id: "review-1".to_string(),
+                    primary_segment: "Frontend".to_string(),
+                    reason: "accepted in review".to_string(),
+                    ..RouteOverridePatch::default()
+                },
+            )
+            .expect("override");
+        assert_eq!(route.primary.segment_id, "frontend");
+        assert_eq!(route.overridden_by.as_deref(), Some("review-1"));
+        assert!(router
+            .uncertain_queue(&store, 10)
+            .expect("queue")
+            .is_empty());
+
+        // A fresh router re-applies the stored patch on the next pass.
+        let stored = store
+            .route_override("obs-4")
+            .expect("load override")
+            .expect("override kept");
+        assert_eq!(stored.conversation_id, "conv-4");
+        let report = SegmentRouter::new(SegmentRoutingConfig::default())
+            .route_conversation(&store, "conv-4")
+            .expect("reroute");
+        assert_eq!(report.routed_override, 1);
+        let route = store
+            .segment_route_for_artifact("obs-4")
+            .expect("load route")
+            .expect("route exists");
+        assert_eq!(route.overridden_by.as_deref(), Some("review-1"));
+
+        assert!(store.delete_route_override("obs-4").expect("delete"));
+        let report = router
+            .route_conversation(&store, "conv-4")
+            .expect("reroute");
+        assert_eq!(report.routed_override, 0);
+        assert!(matches!(
+            router.override_artifact(&store, "obs-missing", &RouteOverridePatch::default()),
+            Err(RoutingError::UnknownArtifact(_))
+        ));
+    }
+
+    #[test]
+    fn reroute_all_replays_routing_and_keeps_history() {
+        let store = MindStore::open_in_memory().expect("open store");
+        store
+            .insert_observation("obs-6", "conv-6", ts(16, 0, 0), "terraform plan", &[])
+            .expect("insert observation");
+        store
+            .insert_observation("obs-7", "conv-6", ts(16, 5, 0), "terraform apply", &[])
+            .expect("insert observation");
+        store
+            .insert_observation("obs-8", "conv-7", ts(16, 10, 0), "unrouted", &[])
+            .expect("insert observation");
+
+        let mut config = SegmentRoutingConfig {
+            low_confidence_threshold_bps: 4_000,
+            ..SegmentRoutingConfig::default()
+        };
+        SegmentRouter::new(config.clone())
+            .route_conversation(&store, "conv-6")
+            .expect("route conversation");
+        let before = store
+            .segment_route_for_artifact("obs-7")
+            .expect("load route")
+            .expect("route exists");
+        assert_ne!(before.primary.segment_id, "infra");
+
+        config
+            .segment_keywords
+            .insert("infra".to_string(), vec!["terraform".to_string()]);
+        let router = SegmentRouter::new(config);
+        let report = router
+            .reroute_all(&store, Some(ts(16, 5, 0)))
+            .expect("reroute");
+        assert_eq!(
+            report,
+            RerouteReport {
+                conversations: 1,
+                artifacts_rerouted: 1,
+                routes_changed: 1,
+                primaries_changed: 1,
+            }
+        );
+        let after = store
+            .segment_route_for_artifact("obs-7")
+            .expect("load route")
+            .expect("route exists");
+        assert_eq!(after.primary.segment_id, "infra");
+        let history = store.segment_route_history("obs-7").expect("history");
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].previous, before);
+        assert!(store
+            .segment_route_history("obs-6")
+            .expect("history")
+            .is_empty());
+
+        let report = router.reroute_all(&store, None).expect("reroute all");
+        assert_eq!(report.artifacts_rerouted, 2);
+        assert_eq!(report.primaries_changed, 1);
+        let report = router.reroute_all(&store, None).expect("reroute again");
+        assert_eq!(report.routes_changed, 0);
+        assert_eq!(
+            store.segment_route_history("obs-7").expect("history").len(),
+            1
+        );
+    }
+
+    #[test]
+    fn route_text_takes_unambiguous_keyword_matches_only() {
+        let router = SegmentRouter::new(SegmentRoutingConfig::default());
+        let routed = router
+            .route_text("Every db migration ships with a rollback")
+            .expect("backend match");
+        assert_eq!(routed.segment_id, "backend");
+        assert!(routed.evidence[0].starts_with("keyword_match:"));
+
+        assert!(router.route_text("Rename the css api").is_none());
+        assert!(router.route_text("Prefer small commits").is_none());
+        assert_eq!(router.global_segment(), "global");
+    }
+
+    #[test]
+    fn from_path_merges_over_defaults_and_watcher_reloads_on_change() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("routing.toml");
+        std::fs::write(
+            &path,
+            "ambiguous_delta_bps = 500\n\n[tag_to_segment]\nops = \"infra\"\n\n[segment_keywords]\ninfra = [\"terraform\"]\n",
+        )
+        .expect("write routing");
+
+        let mut watcher = RoutingConfigWatcher::new(&path);
+        let config = watcher.poll().expect("first poll").expect("config");
+        assert_eq!(config.ambiguous_delta_bps, 500);
+        assert_eq!(config.tag_to_segment["ops"], "infra");
+        assert_eq!(config.tag_to_segment["mind"], "mind", "defaults kept");
+        assert!(config.segment_keywords.contains_key("backend"));
+        assert_eq!(config.low_confidence_threshold_bps, 6_500);
+        assert!(watcher.poll().is_none(), "unchanged file");
+
+        std::fs::write(&path, "[tag_to_segment]\nops = \"missing-segment\"\n").expect("rewrite");
+        let err = watcher
+            .poll()
+            .expect("changed")
+            .expect_err("unknown segment");
+        assert_eq!(err.error_code(), ErrorCode::Config);
+        assert!(
+            err.to_string()
+                .contains("`tag_to_segment.ops`: unknown segment"),
+            "{err}"
+        );
+
+        std::fs::write(&path, "max_secondary_segments = \"three\"\n").expect("rewrite");
+        assert!(watcher.poll().expect("changed").is_err());
+        assert!(SegmentRoutingConfig::from_path(&dir.path().join("absent.toml")).is_err());
+    }
 }
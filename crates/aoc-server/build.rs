@@ -0,0 +1,11 @@
+fn main() {
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto");
+        // Vendored protoc, so the gRPC build needs no system protobuf.
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc");
+        std::env::set_var("PROTOC", protoc);
+        tonic_prost_build::compile_protos("proto/aoc/mind/v1/mind.proto")
+            .expect("compile proto/aoc/mind/v1/mind.proto");
+    }
+}
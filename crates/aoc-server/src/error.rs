@@ -0,0 +1,121 @@
+use aoc_core::{ErrorCode, ErrorCoded};
+use aoc_mind::DistillationError;
+use aoc_storage::StorageError;
+use axum::{
+    extract::rejection::{JsonRejection, QueryRejection},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use thiserror::Error;
+
+/// Failure returned to API clients as `{"error", "code", "severity",
+/// "retryable"}`, with `code` drawn from the shared `aoc_core::ErrorCode`.
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
+    #[error("observer run failed: {0}")]
+    Distillation(#[from] DistillationError),
+    #[error("no mind store at {0}; run `aoc init` first")]
+    StoreMissing(String),
+    #[error("missing or invalid bearer token")]
+    Unauthorized,
+    #[error("server is read-only; restart it with a token to enable writes")]
+    ReadOnly,
+    #[error("{0}")]
+    BadRequest(String),
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    Conflict(String),
+    #[error("store task failed: {0}")]
+    Task(String),
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::BadRequest(rejection.body_text())
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        Self::BadRequest(rejection.body_text())
+    }
+}
+
+impl ErrorCoded for ApiError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Distillation(err) => err.error_code(),
+            Self::StoreMissing(_) => ErrorCode::StoreMissing,
+            Self::Unauthorized => ErrorCode::Unauthorized,
+            Self::ReadOnly => ErrorCode::ReadOnly,
+            Self::BadRequest(_) => ErrorCode::BadRequest,
+            Self::NotFound(_) => ErrorCode::NotFound,
+            Self::Conflict(_) => ErrorCode::Conflict,
+            Self::Task(_) => ErrorCode::Internal,
+        }
+    }
+}
+
+impl ApiError {
+    pub fn code(&self) -> &'static str {
+        self.error_code().as_str()
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self.error_code() {
+            ErrorCode::WriterBusy | ErrorCode::Conflict | ErrorCode::Stale => StatusCode::CONFLICT,
+            ErrorCode::StoreMissing | ErrorCode::StorageBusy => StatusCode::SERVICE_UNAVAILABLE,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::ReadOnly => StatusCode::FORBIDDEN,
+            ErrorCode::BadRequest | ErrorCode::InvalidInput => StatusCode::BAD_REQUEST,
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        if self.status() == StatusCode::INTERNAL_SERVER_ERROR {
+            tracing::warn!(code = self.code(), error = %self, "api request failed");
+        }
+        let info = self.error_info();
+        let body = json!({
+            "error": info.message,
+            "code": info.code,
+            "severity": info.severity,
+            "retryable": info.retryable,
+        });
+        (self.status(), Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storage_failures_keep_their_own_code_and_status() {
+        let busy = ApiError::from(StorageError::WriterBusy("held by cli".to_string()));
+        assert_eq!(busy.code(), "writer_busy");
+        assert_eq!(busy.status(), StatusCode::CONFLICT);
+        assert!(busy.error_info().retryable);
+
+        let schema = ApiError::from(StorageError::UnsupportedSchemaVersion {
+            found: 99,
+            supported: 1,
+        });
+        assert_eq!(schema.code(), "schema_unsupported");
+        assert_eq!(schema.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let task = ApiError::Task("join error".to_string());
+        assert_eq!(task.code(), "internal");
+        assert!(!task.error_info().retryable);
+    }
+}
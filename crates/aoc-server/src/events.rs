@@ -0,0 +1,345 @@
+//! Live feed over server-sent events (`GET /v1/events`) and WebSocket
+//! (`GET /v1/events/ws`), for dashboards and remote Mission Control that
+//! would otherwise poll.
+//!
+//! Each subscriber keeps a [`FeedCursor`] and re-reads the store every poll
+//! interval, receiving only what changed since its previous read:
+//! - `status`: table counts and pending job totals, first as a snapshot;
+//! - `artifact`: T1/T2 artifacts that appeared;
+//! - `job`: reflector/T3 jobs that were enqueued or changed status;
+//! - `observer`: persisted semantic provenance, i.e. observer and reflector
+//!   runs with their provider and fallback outcome.
+//!
+//! SSE uses the kind as the event name; WebSocket text frames carry
+//! `{"event": kind, "data": ...}`. Streams end when the server shuts down so
+//! graceful shutdown is not held open by idle dashboards.
+
+use aoc_storage::{ArtifactQuery, MindStore, StorageError};
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Response,
+    },
+};
+use chrono::{DateTime, Utc};
+use futures_util::{stream, Stream, StreamExt};
+use serde_json::{json, Value};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+};
+
+use crate::{resources::artifact_json, AppState};
+
+/// Newest artifacts and jobs compared on each poll; changes further back
+/// than this are not reported.
+const FEED_WINDOW: usize = 200;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FeedEvent {
+    pub(crate) kind: &'static str,
+    pub(crate) data: Value,
+}
+
+impl FeedEvent {
+    fn new(kind: &'static str, data: Value) -> Self {
+        Self { kind, data }
+    }
+
+    fn frame(&self) -> String {
+        json!({ "event": self.kind, "data": self.data }).to_string()
+    }
+}
+
+/// What a subscriber has already been told. The first poll only records the
+/// current state and reports the status snapshot.
+#[derive(Debug, Default)]
+pub(crate) struct FeedCursor {
+    primed: bool,
+    status: Option<Value>,
+    artifacts: HashSet<String>,
+    /// `queue:job_id` to last reported status.
+    jobs: HashMap<String, &'static str>,
+    observer_since: Option<DateTime<Utc>>,
+}
+
+impl FeedCursor {
+    pub(crate) fn poll(
+        &mut self,
+        store: &MindStore,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<FeedEvent>, StorageError> {
+        let mut events = Vec::new();
+
+        let status = status_snapshot(store, now)?;
+        if self.status.as_ref() != Some(&status) {
+            events.push(FeedEvent::new("status", status.clone()));
+            self.status = Some(status);
+        }
+
+        let recent = store.query_artifacts(&ArtifactQuery {
+            limit: FEED_WINDOW,
+            ..ArtifactQuery::default()
+        })?;
+        if self.primed {
+            events.extend(
+                recent
+                    .artifacts
+                    .iter()
+                    .rev()
+                    .filter(|artifact| !self.artifacts.contains(&artifact.artifact_id))
+                    .map(|artifact| FeedEvent::new("artifact", artifact_json(artifact))),
+            );
+        }
+        self.artifacts = recent
+            .artifacts
+            .into_iter()
+            .map(|artifact| artifact.artifact_id)
+            .collect();
+
+        let mut jobs = HashMap::new();
+        let reflector = store.list_reflector_jobs(None, FEED_WINDOW)?;
+        let t3 = store.list_t3_backlog_jobs(None, FEED_WINDOW)?;
+        let snapshots = reflector
+            .iter()
+            .map(|job| {
+                (
+                    "reflector",
+                    job.job_id.as_str(),
+                    job.status.as_str(),
+                    job.attempts,
+                    job.last_error.as_deref(),
+                    job.updated_at,
+                )
+            })
+            .chain(t3.iter().map(|job| {
+                (
+                    "t3",
+                    job.job_id.as_str(),
+                    job.status.as_str(),
+                    job.attempts,
+                    job.last_error.as_deref(),
+                    job.updated_at,
+                )
+            }));
+        for (queue, job_id, status, attempts, last_error, updated_at) in snapshots {
+            let key = format!("{queue}:{job_id}");
+            let previous = self.jobs.get(&key).copied();
+            if self.primed && previous != Some(status) {
+                events.push(FeedEvent::new(
+                    "job",
+                    json!({
+                        "queue": queue,
+                        "job_id": job_id,
+                        "status": status,
+                        "previous_status": previous,
+                        "attempts": attempts,
+                        "last_error": last_error,
+                        "updated_at": updated_at.to_rfc3339(),
+                    }),
+                ));
+            }
+            jobs.insert(key, status);
+        }
+        self.jobs = jobs;
+
+        match self.observer_since {
+            Some(since) if self.primed => {
+                for provenance in store.semantic_provenance_since(since, FEED_WINDOW)? {
+                    self.observer_since = Some(provenance.created_at);
+                    events.push(FeedEvent::new(
+                        "observer",
+                        serde_json::to_value(&provenance)
+                            .map_err(|err| StorageError::Serialization(err.to_string()))?,
+                    ));
+                }
+            }
+            _ => self.observer_since = Some(now),
+        }
+
+        self.primed = true;
+        Ok(events)
+    }
+}
+
+fn status_snapshot(store: &MindStore, now: DateTime<Utc>) -> Result<Value, StorageError> {
+    let stats = store.stats(now)?;
+    Ok(json!({
+        "table_counts": crate::table_counts_json(&stats),
+        "pending_reflector_jobs": stats.pending_reflector_jobs,
+        "pending_t3_jobs": stats.pending_t3_jobs,
+    }))
+}
+
+/// Polls until there is something to send, or returns `None` on shutdown.
+async fn next_batch(
+    state: &AppState,
+    cursor: &mut Option<FeedCursor>,
+    shutdown: &mut tokio::sync::watch::Receiver<bool>,
+) -> Option<Vec<FeedEvent>> {
+    loop {
+        if cursor.as_ref().is_some_and(|cursor| cursor.primed) {
+            tokio::select! {
+                _ = tokio::time::sleep(state.config.poll_interval) => {}
+                _ = shutdown.wait_for(|stop| *stop) => return None,
+            }
+        }
+        let mut owned = cursor.take().unwrap_or_default();
+        let polled = state
+            .read(move |store| {
+                let events = owned.poll(store, Utc::now());
+                events.map(|events| (owned, events))
+            })
+            .await;
+        match polled {
+            Ok((owned, events)) => {
+                *cursor = Some(owned);
+                if !events.is_empty() {
+                    return Some(events);
+                }
+            }
+            Err(err) => {
+                tracing::debug!(error = %err, "feed poll failed");
+                // The cursor moved into the failed read is gone; a fresh one
+                // re-primes on the next poll instead of replaying the window.
+                *cursor = Some(FeedCursor::default());
+                tokio::select! {
+                    _ = tokio::time::sleep(state.config.poll_interval) => {}
+                    _ = shutdown.wait_for(|stop| *stop) => return None,
+                }
+            }
+        }
+    }
+}
+
+/// The feed for one subscriber, ending on shutdown.
+pub(crate) fn feed_stream(state: AppState) -> impl Stream<Item = FeedEvent> {
+    let shutdown = state.shutdown.subscribe();
+    let batches = stream::unfold(
+        (state, None, shutdown),
+        |(state, mut cursor, mut shutdown)| async move {
+            let batch = next_batch(&state, &mut cursor, &mut shutdown).await?;
+            Some((stream::iter(batch), (state, cursor, shutdown)))
+        },
+    );
+    batches.flatten()
+}
+
+pub(crate) async fn sse_feed(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = feed_stream(state).map(|event| {
+        Ok(Event::default()
+            .event(event.kind)
+            .data(event.data.to_string()))
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+pub(crate) async fn ws_feed(State(state): State<AppState>, upgrade: WebSocketUpgrade) -> Response {
+    upgrade.on_upgrade(move |socket| ws_session(state, socket))
+}
+
+async fn ws_session(state: AppState, mut socket: WebSocket) {
+    let mut shutdown = state.shutdown.subscribe();
+    let mut cursor = None;
+    loop {
+        let batch = tokio::select! {
+            batch = next_batch(&state, &mut cursor, &mut shutdown) => batch,
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => continue,
+            },
+        };
+        let Some(batch) = batch else {
+            let _ = socket.send(Message::Close(None)).await;
+            return;
+        };
+        for event in batch {
+            if socket.send(Message::Text(event.frame())).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::mind_contracts::{SemanticProvenance, SemanticRuntime, SemanticStage};
+
+    #[test]
+    fn cursor_reports_new_artifacts_job_transitions_and_observer_runs_once() {
+        let store = MindStore::open_in_memory().expect("store");
+        let now = Utc::now();
+        let mut cursor = FeedCursor::default();
+        let primed = cursor.poll(&store, now).expect("prime");
+        assert_eq!(
+            primed.iter().map(|event| event.kind).collect::<Vec<_>>(),
+            vec!["status"]
+        );
+        assert!(cursor.poll(&store, now).expect("idle").is_empty());
+
+        store
+            .insert_observation("obs:1", "conv-1", now, "parser retries", &[])
+            .expect("observation");
+        let job_id = store
+            .enqueue_reflector_job(
+                "mind",
+                &["obs:1".to_string()],
+                &["conv-1".to_string()],
+                40,
+                now,
+            )
+            .expect("job");
+        store
+            .upsert_semantic_provenance(&SemanticProvenance {
+                artifact_id: "obs:1".to_string(),
+                stage: SemanticStage::T1Observer,
+                runtime: SemanticRuntime::Deterministic,
+                provider_name: None,
+                model_id: None,
+                prompt_version: "observer.v1".to_string(),
+                input_hash: "in".to_string(),
+                output_hash: None,
+                latency_ms: None,
+                attempt_count: 1,
+                fallback_used: true,
+                fallback_reason: Some("provider timeout".to_string()),
+                failure_kind: None,
+                created_at: now + chrono::Duration::seconds(1),
+                redactions: Vec::new(),
+            })
+            .expect("provenance");
+
+        let events = cursor.poll(&store, now).expect("changes");
+        let kinds = events.iter().map(|event| event.kind).collect::<Vec<_>>();
+        assert_eq!(kinds, vec!["status", "artifact", "job", "observer"]);
+        assert_eq!(events[1].data["artifact_id"], "obs:1");
+        assert_eq!(events[2].data["job_id"], job_id);
+        assert_eq!(events[2].data["status"], "pending");
+        assert_eq!(events[2].data["previous_status"], Value::Null);
+        assert_eq!(events[3].data["fallback_used"], true);
+        assert!(cursor.poll(&store, now).expect("settled").is_empty());
+
+        store
+            .apply_mind_job_action(
+                aoc_storage::MindJobQueue::Reflector,
+                &job_id,
+                aoc_storage::MindJobAction::Cancel,
+                now,
+            )
+            .expect("cancel");
+        let events = cursor.poll(&store, now).expect("transition");
+        let job = events
+            .iter()
+            .find(|event| event.kind == "job")
+            .expect("job event");
+        assert_eq!(job.data["status"], "cancelled");
+        assert_eq!(job.data["previous_status"], "pending");
+    }
+}
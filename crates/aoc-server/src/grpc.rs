@@ -0,0 +1,414 @@
+//! gRPC mirror of the HTTP API (`--features grpc`) for integrations that
+//! push many events per second, such as an agent farm.
+//!
+//! The service is defined in `proto/aoc/mind/v1/mind.proto`. It shares
+//! [`AppState`] with the HTTP server, so reads use the read-only store, the
+//! upload needs the bearer token (as `authorization` metadata), and
+//! `Subscribe` runs the same [`FeedCursor`](crate::events) as `/v1/events`.
+//! An upload opens the store once per batch of events rather than once per
+//! event.
+
+use aoc_core::mind_contracts::RawEvent;
+use aoc_storage::{ArtifactQuery, StoredArtifact};
+use chrono::{DateTime, Utc};
+use futures_util::{Stream, StreamExt};
+use std::{future::Future, pin::Pin};
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::{
+    events::feed_stream,
+    ingest::{ingest_batch, MAX_REPORTED_ERRORS},
+    paging::PageParams,
+    resources::{parse_since, parse_timestamp},
+    ApiError, AppState,
+};
+
+pub mod proto {
+    tonic::include_proto!("aoc.mind.v1");
+}
+
+use proto::{
+    mind_server::{Mind, MindServer},
+    Artifact, ArtifactPage, ArtifactQueryRequest, FeedEvent, GetArtifactRequest, Handshake,
+    HandshakeRequest, HealthReply, HealthRequest, RawEventMessage, SubscribeRequest, UploadSummary,
+};
+
+/// Events written per store open during an upload.
+const UPLOAD_BATCH: usize = 256;
+
+impl From<ApiError> for Status {
+    fn from(err: ApiError) -> Self {
+        let message = err.to_string();
+        match err {
+            ApiError::Storage(_) | ApiError::Distillation(_) | ApiError::Task(_) => {
+                Status::internal(message)
+            }
+            ApiError::StoreMissing(_) => Status::unavailable(message),
+            ApiError::Unauthorized => Status::unauthenticated(message),
+            ApiError::ReadOnly => Status::permission_denied(message),
+            ApiError::BadRequest(_) => Status::invalid_argument(message),
+            ApiError::NotFound(_) => Status::not_found(message),
+            ApiError::Conflict(_) => Status::failed_precondition(message),
+        }
+    }
+}
+
+pub struct MindService {
+    state: AppState,
+}
+
+/// Serves gRPC on `listener` until `shutdown` resolves, ending open
+/// subscriptions first.
+pub async fn serve(
+    listener: TcpListener,
+    config: crate::ServerConfig,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<(), tonic::transport::Error> {
+    let state = AppState::new(config);
+    let stop = state.shutdown.clone();
+    tracing::info!(
+        addr = ?listener.local_addr().ok(),
+        store = %state.config.store_path.display(),
+        read_only = state.config.read_only(),
+        "mind grpc listening"
+    );
+    tonic::transport::Server::builder()
+        .add_service(MindServer::new(MindService { state }))
+        .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async move {
+            shutdown.await;
+            stop.send_replace(true);
+        })
+        .await
+}
+
+fn artifact_message(artifact: &StoredArtifact) -> Artifact {
+    Artifact {
+        artifact_id: artifact.artifact_id.clone(),
+        conversation_id: artifact.conversation_id.clone(),
+        kind: artifact.kind.clone(),
+        ts: artifact.ts.to_rfc3339(),
+        text: artifact.text.clone(),
+        trace_ids: artifact.trace_ids.clone(),
+    }
+}
+
+fn raw_event_from_message(message: RawEventMessage) -> Result<RawEvent, String> {
+    let ts = DateTime::parse_from_rfc3339(message.ts.trim())
+        .map_err(|_| format!("ts '{}' is not RFC3339", message.ts))?
+        .with_timezone(&Utc);
+    let body =
+        serde_json::from_str(&message.body_json).map_err(|err| format!("body_json: {err}"))?;
+    let attrs = if message.attrs_json.trim().is_empty() {
+        Default::default()
+    } else {
+        serde_json::from_str(&message.attrs_json).map_err(|err| format!("attrs_json: {err}"))?
+    };
+    if message.event_id.trim().is_empty() || message.conversation_id.trim().is_empty() {
+        return Err("event_id and conversation_id are required".to_string());
+    }
+    Ok(RawEvent {
+        event_id: message.event_id,
+        conversation_id: message.conversation_id,
+        agent_id: message.agent_id,
+        ts,
+        body,
+        attrs,
+    })
+}
+
+fn reject(summary: &mut UploadSummary, event_id: &str, reason: String) {
+    summary.rejected += 1;
+    if summary.errors.len() < MAX_REPORTED_ERRORS {
+        summary.errors.push(format!("{event_id}: {reason}"));
+    }
+}
+
+impl MindService {
+    async fn flush(
+        &self,
+        metadata: &tonic::metadata::MetadataMap,
+        batch: &mut Vec<RawEvent>,
+        summary: &mut UploadSummary,
+    ) -> Result<(), Status> {
+        let events = std::mem::take(batch);
+        let ids = events
+            .iter()
+            .map(|raw| raw.event_id.clone())
+            .collect::<Vec<_>>();
+        let outcomes = self
+            .state
+            .write(&metadata.clone().into_headers(), move |store| {
+                ingest_batch(store, &events)
+            })
+            .await?;
+        for (event_id, outcome) in ids.iter().zip(outcomes) {
+            match outcome {
+                Ok(true) => summary.inserted += 1,
+                Ok(false) => summary.duplicates += 1,
+                Err(reason) => reject(summary, event_id, reason),
+            }
+        }
+        Ok(())
+    }
+}
+
+type FeedStream = Pin<Box<dyn Stream<Item = Result<FeedEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl Mind for MindService {
+    async fn health(&self, _: Request<HealthRequest>) -> Result<Response<HealthReply>, Status> {
+        let schema_version = self.state.read(|store| store.schema_version()).await.ok();
+        Ok(Response::new(HealthReply {
+            ok: schema_version.is_some(),
+            read_only: self.state.config.read_only(),
+            schema_version,
+        }))
+    }
+
+    async fn upload_events(
+        &self,
+        request: Request<Streaming<RawEventMessage>>,
+    ) -> Result<Response<UploadSummary>, Status> {
+        // Refuse before reading the stream, not after the first batch.
+        self.state
+            .authorize(&request.metadata().clone().into_headers())?;
+        let metadata = request.metadata().clone();
+        let mut messages = request.into_inner();
+        let mut summary = UploadSummary::default();
+        let mut batch = Vec::with_capacity(UPLOAD_BATCH);
+        while let Some(message) = messages.message().await? {
+            summary.received += 1;
+            let event_id = message.event_id.clone();
+            match raw_event_from_message(message) {
+                Ok(raw) => batch.push(raw),
+                Err(reason) => reject(&mut summary, &event_id, reason),
+            }
+            if batch.len() >= UPLOAD_BATCH {
+                self.flush(&metadata, &mut batch, &mut summary).await?;
+            }
+        }
+        if !batch.is_empty() {
+            self.flush(&metadata, &mut batch, &mut summary).await?;
+        }
+        tracing::info!(
+            received = summary.received,
+            inserted = summary.inserted,
+            rejected = summary.rejected,
+            "grpc upload finished"
+        );
+        Ok(Response::new(summary))
+    }
+
+    async fn query_artifacts(
+        &self,
+        request: Request<ArtifactQueryRequest>,
+    ) -> Result<Response<ArtifactPage>, Status> {
+        let request = request.into_inner();
+        if let Some(kind) = request.kind.as_deref() {
+            if !matches!(kind, "t1" | "t2") {
+                return Err(Status::invalid_argument(format!(
+                    "kind '{kind}' must be t1 or t2"
+                )));
+            }
+        }
+        let offset = request.offset as usize;
+        let query = ArtifactQuery {
+            since: parse_since(request.since.as_deref())?,
+            until: parse_timestamp("until", request.until.as_deref())?,
+            text: request.q,
+            conversation_id: request.conversation_id,
+            active_tag: request.tag,
+            task_id: request.task_id,
+            segment_id: request.segment_id,
+            kind: request.kind,
+            oldest_first: request.oldest_first,
+            offset,
+            limit: PageParams {
+                offset,
+                limit: request.limit.map(|limit| limit as usize),
+            }
+            .limit(),
+        };
+        let page = self
+            .state
+            .read(move |store| store.query_artifacts(&query))
+            .await?;
+        Ok(Response::new(ArtifactPage {
+            items: page.artifacts.iter().map(artifact_message).collect(),
+            offset: page.offset as u64,
+            next_offset: page.next_offset().map(|next| next as u64),
+            total: page.total as u64,
+        }))
+    }
+
+    async fn get_artifact(
+        &self,
+        request: Request<GetArtifactRequest>,
+    ) -> Result<Response<Artifact>, Status> {
+        let artifact_id = request.into_inner().artifact_id;
+        let id = artifact_id.clone();
+        self.state
+            .read(move |store| store.artifact_by_id(&id))
+            .await?
+            .map(|artifact| Response::new(artifact_message(&artifact)))
+            .ok_or_else(|| Status::not_found(format!("no artifact {artifact_id}")))
+    }
+
+    async fn get_handshake(
+        &self,
+        request: Request<HandshakeRequest>,
+    ) -> Result<Response<Handshake>, Status> {
+        let HandshakeRequest { scope, scope_key } = request.into_inner();
+        let (lookup_scope, lookup_key) = (scope.clone(), scope_key.clone());
+        let snapshot = self
+            .state
+            .read(move |store| store.latest_handshake_snapshot(&lookup_scope, &lookup_key))
+            .await?
+            .ok_or_else(|| Status::not_found(format!("no {scope} handshake for {scope_key}")))?;
+        Ok(Response::new(Handshake {
+            snapshot_id: snapshot.snapshot_id,
+            scope: snapshot.scope,
+            scope_key: snapshot.scope_key,
+            payload_text: snapshot.payload_text,
+            payload_hash: snapshot.payload_hash,
+            token_estimate: snapshot.token_estimate,
+            created_at: snapshot.created_at.to_rfc3339(),
+        }))
+    }
+
+    type SubscribeStream = FeedStream;
+
+    async fn subscribe(
+        &self,
+        _: Request<SubscribeRequest>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let events = feed_stream(self.state.clone()).map(|event| {
+            Ok(FeedEvent {
+                kind: event.kind.to_string(),
+                data_json: event.data.to_string(),
+            })
+        });
+        Ok(Response::new(Box::pin(events)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_storage::MindStore;
+    use proto::mind_client::MindClient;
+    use tonic::{metadata::MetadataValue, transport::Channel};
+
+    fn message(event_id: &str, body_json: &str) -> RawEventMessage {
+        RawEventMessage {
+            event_id: event_id.to_string(),
+            conversation_id: "conv-grpc".to_string(),
+            agent_id: "farm-1".to_string(),
+            ts: "2026-03-01T10:00:00Z".to_string(),
+            body_json: body_json.to_string(),
+            attrs_json: String::new(),
+        }
+    }
+
+    fn upload(
+        messages: Vec<RawEventMessage>,
+        token: Option<&str>,
+    ) -> Request<impl tokio_stream::Stream<Item = RawEventMessage>> {
+        let mut request = Request::new(tokio_stream::iter(messages));
+        if let Some(token) = token {
+            request.metadata_mut().insert(
+                "authorization",
+                MetadataValue::try_from(format!("Bearer {token}")).expect("metadata"),
+            );
+        }
+        request
+    }
+
+    #[tokio::test]
+    async fn uploads_need_the_token_and_reads_mirror_rest() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("mind.sqlite");
+        let store = MindStore::open(&path).expect("store");
+        store
+            .insert_observation("obs:1", "conv-grpc", Utc::now(), "parser retries", &[])
+            .expect("observation");
+        drop(store);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let config = crate::ServerConfig::new(&path).with_write_token(Some("s3cret".into()));
+        let server = tokio::spawn(serve(listener, config, async {
+            let _ = stopped.await;
+        }));
+        let channel = Channel::from_shared(format!("http://{addr}"))
+            .expect("uri")
+            .connect()
+            .await
+            .expect("connect");
+        let mut client = MindClient::new(channel);
+
+        let user = r#"{"kind":"message","role":"user","text":"hello farm"}"#;
+        let denied = client
+            .upload_events(upload(vec![message("e1", user)], None))
+            .await
+            .expect_err("no token");
+        assert_eq!(denied.code(), tonic::Code::Unauthenticated);
+
+        let batch = vec![
+            message("e1", user),
+            message("e2", r#"{"kind":"message","role":"assistant","text":"hi"}"#),
+            message("e3", r#"{"kind":"nope"}"#),
+        ];
+        let summary = client
+            .upload_events(upload(batch, Some("s3cret")))
+            .await
+            .expect("upload")
+            .into_inner();
+        assert_eq!(
+            (summary.received, summary.inserted, summary.rejected),
+            (3, 2, 1)
+        );
+        assert!(summary.errors[0].starts_with("e3: body_json"));
+        let again = client
+            .upload_events(upload(vec![message("e1", user)], Some("s3cret")))
+            .await
+            .expect("re-upload")
+            .into_inner();
+        assert_eq!(again.duplicates, 1);
+
+        let page = client
+            .query_artifacts(ArtifactQueryRequest {
+                q: Some("parser".to_string()),
+                ..Default::default()
+            })
+            .await
+            .expect("query")
+            .into_inner();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items[0].artifact_id, "obs:1");
+        let missing = client
+            .get_artifact(GetArtifactRequest {
+                artifact_id: "obs:404".to_string(),
+            })
+            .await
+            .expect_err("missing artifact");
+        assert_eq!(missing.code(), tonic::Code::NotFound);
+
+        let mut feed = client
+            .subscribe(SubscribeRequest {})
+            .await
+            .expect("subscribe")
+            .into_inner();
+        let first = feed.message().await.expect("feed").expect("status event");
+        assert_eq!(first.kind, "status");
+        let status: serde_json::Value = serde_json::from_str(&first.data_json).expect("json");
+        assert_eq!(status["table_counts"]["raw_events"], 2);
+
+        stop.send(()).expect("stop");
+        assert!(feed.message().await.expect("clean end").is_none());
+        server.await.expect("join").expect("serve");
+    }
+}
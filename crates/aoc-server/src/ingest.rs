@@ -0,0 +1,125 @@
+//! `POST /v1/raw-events`: raw events pushed by first-party recorders such as
+//! `aoc-agent-sdk`, written through the same T0 ingest as session files.
+//! Each event stands alone: a contract failure rejects that event and the
+//! rest of the batch still lands, while a SQLite failure fails the request.
+//! Agent ids are normalized on the way in, and agents the registry does not
+//! know yet are registered with kind `api`.
+
+use aoc_core::mind_contracts::{normalize_agent_id, RawEvent, T0CompactionPolicy};
+use aoc_mind::{ingest_raw_event, DistillationConfig, T0IngestConfig, T0IngestError};
+use aoc_storage::{MindStore, StorageError};
+use axum::{extract::rejection::JsonRejection, extract::State, http::HeaderMap, Json};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::{ApiError, AppState};
+
+/// Events accepted per request.
+pub(crate) const MAX_INGEST_EVENTS: usize = 1_000;
+pub(crate) const INGEST_BODY_LIMIT: usize = 16 * 1024 * 1024;
+/// Rejection reasons echoed back per request or upload.
+pub(crate) const MAX_REPORTED_ERRORS: usize = 16;
+/// Registry `kind` for agents first seen through the API.
+const API_AGENT_KIND: &str = "api";
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct IngestRequest {
+    events: Vec<RawEvent>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct IngestSummary {
+    received: usize,
+    inserted: usize,
+    duplicates: usize,
+    rejected: usize,
+    errors: Vec<String>,
+}
+
+/// Ingests one batch on an open store. `Ok(false)` marks a duplicate.
+pub(crate) fn ingest_batch(
+    store: &MindStore,
+    events: &[RawEvent],
+) -> Result<Vec<Result<bool, String>>, StorageError> {
+    let distillation = DistillationConfig::default();
+    let config = T0IngestConfig {
+        policy: T0CompactionPolicy::default(),
+        t1_target_tokens: distillation.t1_target_tokens,
+        t1_hard_cap_tokens: distillation.t1_hard_cap_tokens,
+    };
+    events
+        .iter()
+        .map(|raw| {
+            if raw.event_id.trim().is_empty() || raw.conversation_id.trim().is_empty() {
+                return Ok(Err("event_id and conversation_id are required".to_string()));
+            }
+            let agent_id = normalize_agent_id(&raw.agent_id);
+            let normalized;
+            let raw = if agent_id == raw.agent_id {
+                raw
+            } else {
+                normalized = RawEvent {
+                    agent_id,
+                    ..raw.clone()
+                };
+                &normalized
+            };
+            match ingest_raw_event(store, raw, &config) {
+                Ok(report) => {
+                    if report.inserted_raw {
+                        store.ensure_agent(&raw.agent_id, API_AGENT_KIND, Utc::now())?;
+                    }
+                    Ok(Ok(report.inserted_raw))
+                }
+                Err(T0IngestError::Storage(err @ StorageError::Sqlite(_))) => Err(err),
+                Err(err) => Ok(Err(err.to_string())),
+            }
+        })
+        .collect()
+}
+
+pub(crate) async fn raw_events(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Result<Json<IngestRequest>, JsonRejection>,
+) -> Result<Json<IngestSummary>, ApiError> {
+    state.authorize(&headers)?;
+    let Json(request) = body?;
+    if request.events.len() > MAX_INGEST_EVENTS {
+        return Err(ApiError::BadRequest(format!(
+            "at most {MAX_INGEST_EVENTS} events per request"
+        )));
+    }
+    let ids = request
+        .events
+        .iter()
+        .map(|raw| raw.event_id.clone())
+        .collect::<Vec<_>>();
+    let events = request.events;
+    let outcomes = state
+        .write(&headers, move |store| ingest_batch(store, &events))
+        .await?;
+    let mut summary = IngestSummary {
+        received: ids.len(),
+        ..IngestSummary::default()
+    };
+    for (event_id, outcome) in ids.iter().zip(outcomes) {
+        match outcome {
+            Ok(true) => summary.inserted += 1,
+            Ok(false) => summary.duplicates += 1,
+            Err(reason) => {
+                summary.rejected += 1;
+                if summary.errors.len() < MAX_REPORTED_ERRORS {
+                    summary.errors.push(format!("{event_id}: {reason}"));
+                }
+            }
+        }
+    }
+    tracing::info!(
+        received = summary.received,
+        inserted = summary.inserted,
+        rejected = summary.rejected,
+        "raw events ingested over api"
+    );
+    Ok(Json(summary))
+}
@@ -0,0 +1,802 @@
+//! HTTP API over a project Mind store.
+//!
+//! Every request opens its own connection on a blocking thread. Reads go
+//! through [`MindStore::open_read_only`], so the server never migrates the
+//! store or holds a write lock the pipeline needs. Mutating routes require
+//! `Authorization: Bearer <token>` and are refused outright when the server
+//! starts without a token.
+
+mod error;
+mod events;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+mod ingest;
+mod observe;
+mod paging;
+mod resources;
+mod rpc;
+mod sync;
+
+pub use error::ApiError;
+pub use paging::{DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
+pub use rpc::{serve_rpc, serve_stdio};
+
+use aoc_mind::ObserverFeedPublisher;
+use aoc_storage::{
+    MindJobAction, MindJobQueue, MindStore, MindStoreStats, MindWriterGuard, StorageError,
+    WriterClaim, DEFAULT_WRITER_LEASE_TTL_MS,
+};
+use axum::{
+    extract::{DefaultBodyLimit, Path, State},
+    http::{header::AUTHORIZATION, HeaderMap},
+    middleware,
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::{future::Future, path::PathBuf, sync::Arc, time::Duration};
+use tokio::{net::TcpListener, sync::watch};
+
+pub const DEFAULT_ADDR: &str = "127.0.0.1:7700";
+pub const TOKEN_ENV: &str = "AOC_SERVER_TOKEN";
+/// How long a write request waits for the CLI or daemon to release the
+/// store before answering 409 `writer_busy`.
+const WRITE_LEASE_WAIT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub store_path: PathBuf,
+    /// Bearer token for mutating endpoints; `None` serves read-only.
+    pub write_token: Option<String>,
+    /// How often `/v1/events` re-reads the store.
+    pub poll_interval: Duration,
+}
+
+impl ServerConfig {
+    pub fn new(store_path: impl Into<PathBuf>) -> Self {
+        Self {
+            store_path: store_path.into(),
+            write_token: None,
+            poll_interval: Duration::from_secs(2),
+        }
+    }
+
+    pub fn with_write_token(mut self, token: Option<String>) -> Self {
+        self.write_token = token.filter(|token| !token.trim().is_empty());
+        self
+    }
+
+    pub fn read_only(&self) -> bool {
+        self.write_token.is_none()
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct AppState {
+    pub(crate) config: Arc<ServerConfig>,
+    /// Flipped to `true` on shutdown so long-lived streams finish.
+    pub(crate) shutdown: Arc<watch::Sender<bool>>,
+    /// Observer runs started over the API, for `/v1/observer/feed`.
+    pub(crate) observer_feed: ObserverFeedPublisher,
+}
+
+impl AppState {
+    fn new(config: ServerConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            shutdown: Arc::new(watch::channel(false).0),
+            observer_feed: ObserverFeedPublisher::new(),
+        }
+    }
+
+    pub(crate) async fn read<T, F>(&self, op: F) -> Result<T, ApiError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&MindStore) -> Result<T, StorageError> + Send + 'static,
+    {
+        let path = self.config.store_path.clone();
+        run_blocking(move || {
+            if !path.exists() {
+                return Err(ApiError::StoreMissing(path.display().to_string()));
+            }
+            Ok(op(&MindStore::open_read_only(&path)?)?)
+        })
+        .await
+    }
+
+    pub(crate) async fn write<T, F>(&self, headers: &HeaderMap, op: F) -> Result<T, ApiError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&MindStore) -> Result<T, StorageError> + Send + 'static,
+    {
+        self.authorize(headers)?;
+        let path = self.config.store_path.clone();
+        run_blocking(move || {
+            if !path.exists() {
+                return Err(ApiError::StoreMissing(path.display().to_string()));
+            }
+            let store = MindWriterGuard::acquire_waiting(
+                &path,
+                WriterClaim::for_current_process("server"),
+                DEFAULT_WRITER_LEASE_TTL_MS,
+                WRITE_LEASE_WAIT,
+            )?;
+            Ok(op(&store)?)
+        })
+        .await
+    }
+
+    pub(crate) fn authorize(&self, headers: &HeaderMap) -> Result<(), ApiError> {
+        let Some(expected) = &self.config.write_token else {
+            return Err(ApiError::ReadOnly);
+        };
+        let given = headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .unwrap_or_default();
+        if token_matches(expected, given.trim()) {
+            Ok(())
+        } else {
+            Err(ApiError::Unauthorized)
+        }
+    }
+}
+
+async fn run_blocking<T, F>(op: F) -> Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, ApiError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(op)
+        .await
+        .map_err(|err| ApiError::Task(err.to_string()))?
+}
+
+/// Compares without short-circuiting so response timing does not leak how
+/// much of a guessed token was right.
+fn token_matches(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0u8, |acc, (left, right)| acc | (left ^ right))
+            == 0
+}
+
+pub fn router(config: ServerConfig) -> Router {
+    routes(AppState::new(config))
+}
+
+fn routes(state: AppState) -> Router {
+    let reads = Router::new()
+        .route("/v1/status", get(status))
+        .route("/v1/conversations", get(resources::list_conversations))
+        .route("/v1/conversations/:id", get(resources::get_conversation))
+        .route("/v1/artifacts", get(resources::list_artifacts))
+        .route("/v1/artifacts/:id", get(resources::get_artifact))
+        .route("/v1/search", get(resources::search))
+        .route("/v1/tasks", get(resources::list_tasks))
+        .route("/v1/tasks/:id/timeline", get(resources::task_timeline))
+        .route("/v1/routes/:artifact_id", get(resources::get_route))
+        .route(
+            "/v1/segments/:id/artifacts",
+            get(resources::segment_artifacts),
+        )
+        .route("/v1/canon", get(resources::list_canon))
+        .route("/v1/canon/:entry_id", get(resources::canon_entry))
+        .route("/v1/handshake/:scope/:scope_key", get(resources::handshake))
+        .route("/v1/agents", get(resources::list_agents))
+        .route("/v1/audit", get(resources::verify_audit))
+        .route("/v1/jobs/:queue", get(resources::list_jobs))
+        .route("/v1/sync", get(sync::identity))
+        .route("/v1/sync/bundle", get(sync::bundle))
+        .layer(middleware::from_fn(paging::etag_layer));
+    Router::new()
+        .route("/health", get(health))
+        .route("/v1/events", get(events::sse_feed))
+        .route("/v1/events/ws", get(events::ws_feed))
+        .route("/v1/observer/feed", get(observe::observer_feed))
+        .route("/v1/jobs/:queue/:job_id/:action", post(job_action))
+        .route(
+            "/v1/conversations/:id/observe",
+            post(observe::observe_conversation),
+        )
+        .route(
+            "/v1/raw-events",
+            post(ingest::raw_events).layer(DefaultBodyLimit::max(ingest::INGEST_BODY_LIMIT)),
+        )
+        .route(
+            "/v1/sync/apply",
+            post(sync::apply).layer(DefaultBodyLimit::max(sync::SYNC_BODY_LIMIT)),
+        )
+        .merge(reads)
+        .with_state(state)
+}
+
+/// Serves the API on `listener` until `shutdown` resolves, then ends open
+/// event streams and drains in-flight requests.
+pub async fn serve(
+    listener: TcpListener,
+    config: ServerConfig,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> std::io::Result<()> {
+    let state = AppState::new(config);
+    let stop = state.shutdown.clone();
+    tracing::info!(
+        addr = ?listener.local_addr().ok(),
+        store = %state.config.store_path.display(),
+        read_only = state.config.read_only(),
+        "mind api listening"
+    );
+    axum::serve(listener, routes(state))
+        .with_graceful_shutdown(async move {
+            shutdown.await;
+            stop.send_replace(true);
+        })
+        .await
+}
+
+async fn health(State(state): State<AppState>) -> Json<Value> {
+    let schema_version = state.read(|store| store.schema_version()).await.ok();
+    Json(json!({
+        "status": if schema_version.is_some() { "ok" } else { "degraded" },
+        "read_only": state.config.read_only(),
+        "schema_version": schema_version,
+    }))
+}
+
+async fn status(State(state): State<AppState>) -> Result<Json<Value>, ApiError> {
+    let stats = state.read(|store| store.stats(Utc::now())).await?;
+    Ok(Json(json!({
+        "schema_version": stats.schema_version,
+        "table_counts": table_counts_json(&stats),
+        "pending_reflector_jobs": stats.pending_reflector_jobs,
+        "pending_t3_jobs": stats.pending_t3_jobs,
+        "watermarks": stats.watermarks.iter().map(|watermark| json!({
+            "scope_key": watermark.scope_key,
+            "last_artifact_ts": watermark.last_artifact_ts.map(|ts| ts.to_rfc3339()),
+            "pending_artifacts": watermark.pending_artifacts,
+            "lag_seconds": watermark.lag_seconds,
+        })).collect::<Vec<_>>(),
+    })))
+}
+
+pub(crate) fn table_counts_json(stats: &MindStoreStats) -> Value {
+    Value::Object(
+        stats
+            .table_counts
+            .iter()
+            .map(|(table, count)| (table.clone(), json!(count)))
+            .collect(),
+    )
+}
+
+async fn job_action(
+    State(state): State<AppState>,
+    Path((queue, job_id, action)): Path<(String, String, String)>,
+    headers: HeaderMap,
+) -> Result<Json<Value>, ApiError> {
+    let queue = match queue.as_str() {
+        "reflector" => MindJobQueue::Reflector,
+        "t3" => MindJobQueue::T3Backlog,
+        other => return Err(ApiError::NotFound(format!("unknown job queue '{other}'"))),
+    };
+    let action = match action.as_str() {
+        "requeue" => MindJobAction::Requeue,
+        "cancel" => MindJobAction::Cancel,
+        "dead_letter" => MindJobAction::DeadLetter,
+        other => {
+            return Err(ApiError::BadRequest(format!(
+                "unknown job action '{other}'"
+            )))
+        }
+    };
+    let id = job_id.clone();
+    let applied = state
+        .write(&headers, move |store| {
+            store.apply_mind_job_action(queue, &id, action, Utc::now())
+        })
+        .await?;
+    if !applied {
+        return Err(ApiError::Conflict(format!(
+            "{} job {job_id} is missing or its status does not allow {}",
+            queue.as_str(),
+            action.as_str()
+        )));
+    }
+    tracing::info!(
+        queue = queue.as_str(),
+        job_id = %job_id,
+        action = action.as_str(),
+        "job action applied over api"
+    );
+    Ok(Json(json!({
+        "queue": queue.as_str(),
+        "job_id": job_id,
+        "action": action.as_str(),
+        "applied": true,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::{to_bytes, Body},
+        http::{Request, StatusCode},
+    };
+    use futures_util::StreamExt;
+    use tower::ServiceExt;
+
+    async fn call(app: &Router, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app.clone().oneshot(request).await.expect("response");
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body");
+        (status, serde_json::from_slice(&body).expect("json body"))
+    }
+
+    fn post(uri: &str, token: Option<&str>) -> Request<Body> {
+        let mut request = Request::post(uri);
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+        request.body(Body::empty()).expect("request")
+    }
+
+    #[tokio::test]
+    async fn reads_are_open_and_writes_need_the_token() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("mind.sqlite");
+        MindStore::open(&path).expect("store");
+
+        let read_only = router(ServerConfig::new(&path));
+        let (status, body) = call(
+            &read_only,
+            Request::get("/health").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["read_only"], true);
+        assert_eq!(body["status"], "ok");
+        let (status, body) = call(
+            &read_only,
+            Request::get("/v1/status").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["table_counts"]["raw_events"], 0);
+        let (status, body) =
+            call(&read_only, post("/v1/jobs/t3/job-1/cancel", Some("s3cret"))).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["code"], "read_only");
+
+        let writable =
+            router(ServerConfig::new(&path).with_write_token(Some("s3cret".to_string())));
+        let (status, _) = call(&writable, post("/v1/jobs/t3/job-1/cancel", None)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = call(&writable, post("/v1/jobs/t3/job-1/cancel", Some("s3creT"))).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, body) =
+            call(&writable, post("/v1/jobs/t3/job-1/cancel", Some("s3cret"))).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["code"], "conflict");
+        let cli = MindWriterGuard::acquire(
+            &path,
+            WriterClaim::for_current_process("cli"),
+            DEFAULT_WRITER_LEASE_TTL_MS,
+        )
+        .expect("cli writer");
+        let (status, body) =
+            call(&writable, post("/v1/jobs/t3/job-1/cancel", Some("s3cret"))).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["code"], "writer_busy");
+        assert_eq!(body["severity"], "warning");
+        assert_eq!(body["retryable"], true);
+        assert!(body["error"]
+            .as_str()
+            .is_some_and(|error| error.contains("another writer holds the mind")));
+        drop(cli);
+        let (status, _) = call(
+            &writable,
+            post("/v1/jobs/nope/job-1/cancel", Some("s3cret")),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let missing = router(ServerConfig::new(dir.path().join("absent.sqlite")));
+        let (status, body) = call(
+            &missing,
+            Request::get("/v1/status").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["code"], "store_missing");
+    }
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::get(uri).body(Body::empty()).expect("request")
+    }
+
+    #[tokio::test]
+    async fn artifact_routes_page_search_and_honour_etags() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("mind.sqlite");
+        let store = MindStore::open(&path).expect("store");
+        let ts = |minute: u32| {
+            chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 3, 1, 10, minute, 0).unwrap()
+        };
+        for (index, text) in ["parser retries flaky", "parser cache warm", "ui polish"]
+            .iter()
+            .enumerate()
+        {
+            store
+                .insert_observation(
+                    &format!("obs:{index}"),
+                    "conv-1",
+                    ts(index as u32),
+                    text,
+                    &[],
+                )
+                .expect("observation");
+        }
+        drop(store);
+        let app = router(ServerConfig::new(&path));
+
+        let (status, body) = call(&app, get("/v1/artifacts?q=parser&limit=1")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total"], 2);
+        assert_eq!(body["items"][0]["artifact_id"], "obs:1");
+        assert_eq!(body["next_offset"], 1);
+        let (_, body) = call(&app, get("/v1/artifacts?q=parser&limit=1&offset=1")).await;
+        assert_eq!(body["items"][0]["artifact_id"], "obs:0");
+        assert_eq!(body["next_offset"], Value::Null);
+
+        let (status, body) = call(&app, get("/v1/artifacts/obs:2")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["text"], "ui polish");
+        assert_eq!(body["archived"], false);
+        let (status, _) = call(&app, get("/v1/artifacts/obs:9")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (_, body) = call(&app, get("/v1/search?q=cache")).await;
+        assert_eq!(body["hits"][0]["artifact"]["artifact_id"], "obs:1");
+        let (status, _) = call(&app, get("/v1/search?q=%20")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = call(&app, get("/v1/artifacts?since=yesterday")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, body) = call(&app, get("/v1/jobs/reflector?status=pending")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["items"], json!([]));
+
+        let first = app
+            .clone()
+            .oneshot(get("/v1/artifacts/obs:0"))
+            .await
+            .expect("response");
+        let etag = first.headers()[axum::http::header::ETAG].clone();
+        let revalidated = app
+            .clone()
+            .oneshot(
+                Request::get("/v1/artifacts/obs:0")
+                    .header(axum::http::header::IF_NONE_MATCH, etag.clone())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("response");
+        assert_eq!(revalidated.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(revalidated.headers()[axum::http::header::ETAG], etag);
+    }
+
+    #[tokio::test]
+    async fn sync_routes_page_the_journal_and_apply_with_the_token() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("mind.sqlite");
+        let store = MindStore::open(&path).expect("store");
+        let ts = chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 3, 1, 10, 0, 0).unwrap();
+        for id in ["obs:a", "obs:b"] {
+            store
+                .insert_observation(id, "conv-1", ts, "parser retries", &[])
+                .expect("observation");
+        }
+        store.enable_audit_chain(ts).expect("audit chain");
+        drop(store);
+        let app = router(ServerConfig::new(&path).with_write_token(Some("s3cret".to_string())));
+
+        let (status, body) = call(&app, get("/v1/sync")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["head_seq"], 2);
+        let (status, body) = call(&app, get("/v1/sync/bundle?since=0&limit=1")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["more"], true);
+        assert_eq!(body["groups"][0]["key"], json!(["obs:a"]));
+        let (_, body) = call(&app, get("/v1/sync/bundle?since=1")).await;
+        assert_eq!(body["groups"][0]["key"], json!(["obs:b"]));
+        assert_eq!(body["more"], false);
+        let (status, _) = call(&app, get("/v1/sync/bundle?since=-1")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let peer = MindStore::open_in_memory().expect("peer");
+        peer.insert_observation("obs:peer", "conv-2", ts, "ui polish", &[])
+            .expect("peer observation");
+        let bundle = serde_json::to_vec(&peer.sync_bundle_since(0, 10).expect("bundle"))
+            .expect("encode bundle");
+        let apply = |token: Option<&str>| {
+            let mut request = Request::post("/v1/sync/apply")
+                .header(axum::http::header::CONTENT_TYPE, "application/json");
+            if let Some(token) = token {
+                request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+            }
+            request.body(Body::from(bundle.clone())).expect("request")
+        };
+        let (status, _) = call(&app, apply(None)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, body) = call(&app, apply(Some("s3cret"))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["inserted"], 1);
+        let (_, body) = call(&app, apply(Some("s3cret"))).await;
+        assert_eq!(body["unchanged"], 1);
+        let (_, body) = call(&app, get("/v1/artifacts/obs:peer")).await;
+        assert_eq!(body["text"], "ui polish");
+
+        let (status, body) = call(&app, get("/v1/audit")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["intact"], true);
+        assert_eq!(body["entries"], 3);
+        let (_, body) = call(&app, get("/v1/audit?anchor_seq=3&anchor_root=abc")).await;
+        assert_eq!(body["findings"][0]["finding"], "anchor_mismatch");
+        let (status, _) = call(&app, get("/v1/audit?anchor_seq=3")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn remote_store_reads_through_the_api_and_serves_its_cache_offline() {
+        use aoc_core::mind_contracts::{ArtifactTaskLink, ArtifactTaskRelation};
+        use aoc_storage::{ArtifactQuery, RemoteMindStore};
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("mind.sqlite");
+        let store = MindStore::open(&path).expect("store");
+        let ts = chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 3, 1, 10, 0, 0).unwrap();
+        store
+            .insert_observation("obs:1", "conv 1", ts, "parser retries", &[])
+            .expect("observation");
+        store
+            .upsert_artifact_task_link(&ArtifactTaskLink {
+                artifact_id: "obs:1".to_string(),
+                task_id: "42".to_string(),
+                relation: ArtifactTaskRelation::WorkedOn,
+                confidence_bps: 9_000,
+                evidence_event_ids: vec![],
+                source: "test".to_string(),
+                start_ts: ts,
+                end_ts: None,
+            })
+            .expect("task link");
+        drop(store);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let base = format!("http://{}", listener.local_addr().expect("addr"));
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(serve(listener, ServerConfig::new(&path), async move {
+            let _ = stopped.await;
+        }));
+
+        let client = RemoteMindStore::new(base).with_fresh_for(Duration::from_secs(60));
+        let client = tokio::task::spawn_blocking(move || {
+            let page = client
+                .query_artifacts(&ArtifactQuery {
+                    text: Some("parser".to_string()),
+                    limit: 10,
+                    ..ArtifactQuery::default()
+                })
+                .expect("query");
+            assert_eq!(page.total, 1);
+            assert_eq!(page.artifacts[0].conversation_id, "conv 1");
+            assert_eq!(page.artifacts[0].ts, ts);
+            assert!(client.artifact_by_id("obs:9").expect("missing").is_none());
+            let links = client.artifact_task_links_for_task("42").expect("links");
+            assert_eq!(links[0].relation, ArtifactTaskRelation::WorkedOn);
+            client.artifact_by_id("obs:1").expect("artifact");
+            client.artifact_by_id("obs:1").expect("cached artifact");
+            assert_eq!(client.cache_stats().hits, 1);
+            let client = client.with_fresh_for(Duration::ZERO);
+            client
+                .artifact_by_id("obs:1")
+                .expect("revalidated artifact");
+            assert_eq!(client.cache_stats().revalidated, 1);
+            client
+        })
+        .await
+        .expect("client");
+
+        stop.send(()).expect("stop server");
+        server.await.expect("join").expect("serve");
+        tokio::task::spawn_blocking(move || {
+            let offline = RemoteMindStore::new(client.base_url().to_string());
+            assert!(offline.artifact_by_id("obs:1").is_err());
+            let artifact = client
+                .artifact_by_id("obs:1")
+                .expect("stale artifact")
+                .expect("cached");
+            assert_eq!(artifact.text, "parser retries");
+            assert_eq!(client.cache_stats().served_offline, 1);
+        })
+        .await
+        .expect("offline client");
+    }
+
+    #[tokio::test]
+    async fn observe_runs_the_observer_over_a_conversation() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("mind.sqlite");
+        MindStore::open(&path).expect("store");
+        let app = router(ServerConfig::new(&path).with_write_token(Some("s3cret".to_string())));
+        let batch = json!({ "events": [{
+            "event_id": "evt-1",
+            "conversation_id": "conv-observe",
+            "agent_id": "script",
+            "ts": "2026-03-01T10:00:00Z",
+            "body": { "kind": "message", "role": "user", "text": "fix the parser" },
+        }]});
+        let (status, _) = call(
+            &app,
+            Request::post("/v1/raw-events")
+                .header(AUTHORIZATION, "Bearer s3cret")
+                .header("content-type", "application/json")
+                .body(Body::from(batch.to_string()))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let feed = app
+            .clone()
+            .oneshot(
+                Request::get("/v1/observer/feed")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("feed");
+        assert_eq!(feed.status(), StatusCode::OK);
+        let mut frames = feed.into_body().into_data_stream();
+
+        let (status, _) = call(&app, post("/v1/conversations/conv-observe/observe", None)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, body) = call(
+            &app,
+            post("/v1/conversations/conv-observe/observe", Some("s3cret")),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["conversation_id"], "conv-observe");
+        assert_eq!(body["report"]["t0_events_processed"], 1);
+        assert_eq!(body["report"]["t1_artifacts_written"], 1);
+
+        let mut streamed = String::new();
+        while streamed.matches("event: observer").count() < 2 {
+            let chunk = tokio::time::timeout(Duration::from_secs(5), frames.next())
+                .await
+                .expect("feed frame")
+                .expect("open stream")
+                .expect("chunk");
+            streamed.push_str(std::str::from_utf8(&chunk).expect("utf8"));
+        }
+        let statuses = streamed
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .map(|data| serde_json::from_str::<Value>(data).expect("event json")["status"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(statuses, vec![json!("running"), json!("success")]);
+        assert!(streamed.contains("\"conversation_id\":\"conv-observe\""));
+        let (status, body) = call(
+            &app,
+            Request::get("/v1/artifacts?conversation=conv-observe&kind=t1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total"], 1);
+
+        let (status, body) = call(
+            &app,
+            post("/v1/conversations/conv-missing/observe", Some("s3cret")),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "not_found");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn raw_events_ingest_per_event_and_back_the_agent_sdk() {
+        use aoc_agent_sdk::{ConversationRole, EventRecorder, RecorderError, ToolExecutionStatus};
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("mind.sqlite");
+        MindStore::open(&path).expect("store");
+        let config = ServerConfig::new(&path).with_write_token(Some("s3cret".to_string()));
+
+        let app = router(config.clone());
+        let batch = json!({ "events": [
+            {
+                "event_id": "evt-1",
+                "conversation_id": "conv-raw",
+                "agent_id": "script",
+                "ts": "2026-03-01T10:00:00Z",
+                "body": { "kind": "message", "role": "user", "text": "hello" },
+            },
+            {
+                "event_id": "",
+                "conversation_id": "conv-raw",
+                "agent_id": "script",
+                "ts": "2026-03-01T10:00:01Z",
+                "body": { "kind": "message", "role": "user", "text": "no id" },
+            },
+        ]});
+        let request = |token: &str| {
+            Request::post("/v1/raw-events")
+                .header(AUTHORIZATION, format!("Bearer {token}"))
+                .header("content-type", "application/json")
+                .body(Body::from(batch.to_string()))
+                .expect("request")
+        };
+        let (status, _) = call(&app, request("wrong")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, body) = call(&app, request("s3cret")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["inserted"], 1);
+        assert_eq!(body["rejected"], 1);
+        let (_, body) = call(&app, request("s3cret")).await;
+        assert_eq!(body["duplicates"], 1, "re-sent events are not stored twice");
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let base = format!("http://{}", listener.local_addr().expect("addr"));
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(serve(listener, config, async move {
+            let _ = stopped.await;
+        }));
+        tokio::task::spawn_blocking(move || {
+            let mut anonymous = EventRecorder::remote(&base, None, "sdk-agent", "conv-sdk");
+            let denied = anonymous.record_message(ConversationRole::User, "hi");
+            assert!(
+                matches!(&denied, Err(RecorderError::Http(message)) if message.starts_with("HTTP 401")),
+                "{denied:?}"
+            );
+            let mut recorder =
+                EventRecorder::remote(&base, Some("s3cret".to_string()), "sdk-agent", "conv-sdk");
+            let recorded = recorder
+                .record_message(ConversationRole::User, "fix the parser")
+                .expect("message");
+            assert!(recorded.inserted);
+            recorder
+                .record_tool_result("cargo test", ToolExecutionStatus::Success, Some("ok"), None)
+                .expect("tool result");
+        })
+        .await
+        .expect("sdk client");
+        stop.send(()).expect("stop server");
+        server.await.expect("join").expect("serve");
+
+        let store = MindStore::open(&path).expect("reopen");
+        assert_eq!(
+            store
+                .raw_events_for_conversation("conv-sdk")
+                .expect("raw")
+                .len(),
+            2
+        );
+        assert_eq!(
+            store
+                .t0_events_for_conversation("conv-sdk")
+                .expect("t0")
+                .len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! `POST /v1/conversations/:id/observe`: a manual observer run over one
+//! conversation, for tooling that pushes raw events and wants T1/T2 without
+//! waiting for `aoc live`. The run is deterministic with the same default
+//! distillation budgets ingest uses; semantic observer runs stay with the
+//! CLI and daemon, which own the gateway config. Re-running is cheap because
+//! committed T1 batches are reused.
+//!
+//! `GET /v1/observer/feed` streams observer feed events over server-sent
+//! events as runs start and finish, so the cockpit and dashboards see
+//! progress live instead of polling `/v1/events` for provenance. Each SSE
+//! event is named `observer` and carries one `MindObserverFeedEvent`; a
+//! subscriber that falls behind by a full buffer misses events rather than
+//! holding up the run.
+
+use aoc_core::mind_observer_feed::{
+    MindObserverFeedEvent, MindObserverFeedStatus, MindObserverFeedTriggerKind,
+};
+use aoc_mind::{
+    observer_feed_event_from_outcome, DeterministicDistiller, DistillationConfig,
+    DistillationError, ObserverTrigger, SessionObserverRunOutcome, DEFAULT_OBSERVER_FEED_CAPACITY,
+};
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use futures_util::{stream, Stream};
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+use crate::{ApiError, AppState};
+
+/// Session id recorded on runs started over the API.
+const API_OBSERVER_SESSION: &str = "api";
+
+pub(crate) async fn observe_conversation(
+    State(state): State<AppState>,
+    Path(conversation_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<Value>, ApiError> {
+    let id = conversation_id.clone();
+    let feed = state.observer_feed.clone();
+    let outcome = state
+        .write(&headers, move |store| {
+            if store.raw_event_count(&id)? == 0 {
+                return Ok(None);
+            }
+            let started_at = Utc::now();
+            feed.publish(&running_event(&id, started_at));
+            let outcome = SessionObserverRunOutcome {
+                session_id: API_OBSERVER_SESSION.to_string(),
+                conversation_id: id.clone(),
+                trigger: ObserverTrigger::manual_shortcut(),
+                enqueued_at: started_at,
+                started_at,
+                report: DeterministicDistiller::new(DistillationConfig::default())
+                    .distill_conversation(store, &id),
+                progress: None,
+                breaker_transitions: Vec::new(),
+            };
+            feed.publish(&observer_feed_event_from_outcome(
+                store,
+                &outcome,
+                Utc::now(),
+            ));
+            // Storage failures keep their own code (`writer_busy`, ...);
+            // anything else is reported as a failed run.
+            match outcome.report {
+                Err(DistillationError::Storage(err)) => Err(err),
+                report => Ok(Some(report)),
+            }
+        })
+        .await?;
+    let Some(outcome) = outcome else {
+        return Err(ApiError::NotFound(format!(
+            "conversation '{conversation_id}' has no raw events"
+        )));
+    };
+    let report = outcome?;
+    tracing::info!(
+        conversation_id = %conversation_id,
+        t1_written = report.t1_artifacts_written,
+        t2_written = report.t2_artifacts_written,
+        "observer run over api"
+    );
+    Ok(Json(json!({
+        "conversation_id": conversation_id,
+        "report": report,
+    })))
+}
+
+fn running_event(conversation_id: &str, started_at: DateTime<Utc>) -> MindObserverFeedEvent {
+    MindObserverFeedEvent {
+        status: MindObserverFeedStatus::Running,
+        trigger: MindObserverFeedTriggerKind::ManualShortcut,
+        conversation_id: Some(conversation_id.to_string()),
+        runtime: None,
+        attempt_count: None,
+        latency_ms: None,
+        reason: None,
+        failure_kind: None,
+        error_code: None,
+        enqueued_at: Some(started_at.to_rfc3339()),
+        started_at: Some(started_at.to_rfc3339()),
+        completed_at: None,
+        progress: None,
+    }
+}
+
+pub(crate) async fn observer_feed(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let (sender, receiver) = mpsc::channel(DEFAULT_OBSERVER_FEED_CAPACITY);
+    state
+        .observer_feed
+        .subscribe_with(move |event| match sender.try_send(event.clone()) {
+            Ok(()) | Err(TrySendError::Full(_)) => true,
+            Err(TrySendError::Closed(_)) => false,
+        });
+    let shutdown = state.shutdown.subscribe();
+    let events = stream::unfold(
+        (receiver, shutdown),
+        |(mut receiver, mut shutdown)| async move {
+            let event = tokio::select! {
+                event = receiver.recv() => event?,
+                _ = shutdown.wait_for(|stop| *stop) => return None,
+            };
+            let frame = Event::default()
+                .event("observer")
+                .data(json!(event).to_string());
+            Some((Ok(frame), (receiver, shutdown)))
+        },
+    );
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
@@ -0,0 +1,116 @@
+//! Pagination parameters and conditional GET.
+//!
+//! List endpoints take `offset`/`limit` and answer with
+//! `{items, offset, next_offset}` (plus `total` where the store can count
+//! cheaply). Every successful read carries a strong `ETag` over the response
+//! body; a matching `If-None-Match` gets an empty 304 instead.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{
+        header::{ETAG, IF_NONE_MATCH},
+        HeaderValue, Method, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+pub const DEFAULT_PAGE_LIMIT: usize = 50;
+pub const MAX_PAGE_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub(crate) struct PageParams {
+    #[serde(default)]
+    pub(crate) offset: usize,
+    pub(crate) limit: Option<usize>,
+}
+
+impl PageParams {
+    pub(crate) fn limit(self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_LIMIT)
+            .clamp(1, MAX_PAGE_LIMIT)
+    }
+
+    /// Slices an over-fetched list (at least `offset + limit + 1` rows when
+    /// more exist) into one page.
+    pub(crate) fn page<T>(self, rows: Vec<T>) -> (Vec<T>, Option<usize>) {
+        let limit = self.limit();
+        let mut rows = rows.into_iter().skip(self.offset);
+        let page = rows.by_ref().take(limit).collect();
+        let next = rows.next().is_some().then_some(self.offset + limit);
+        (page, next)
+    }
+
+    /// Rows to request so [`PageParams::page`] can tell whether more follow.
+    pub(crate) fn fetch_limit(self) -> usize {
+        self.offset + self.limit() + 1
+    }
+}
+
+pub(crate) fn page_json(
+    items: Vec<Value>,
+    offset: usize,
+    next_offset: Option<usize>,
+    total: Option<usize>,
+) -> Value {
+    let mut page = json!({
+        "items": items,
+        "offset": offset,
+        "next_offset": next_offset,
+    });
+    if let Some(total) = total {
+        page["total"] = json!(total);
+    }
+    page
+}
+
+pub(crate) fn body_etag(body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+    let hex = digest
+        .iter()
+        .take(12)
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("\"{hex}\"")
+}
+
+fn etag_matches(header: &HeaderValue, etag: &str) -> bool {
+    header.to_str().is_ok_and(|value| {
+        value
+            .split(',')
+            .map(str::trim)
+            .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+    })
+}
+
+/// Middleware for JSON read routes. Streaming routes must not sit behind it
+/// since it buffers the whole body.
+pub(crate) async fn etag_layer(request: Request, next: Next) -> Response {
+    let is_get = request.method() == Method::GET;
+    let if_none_match = request.headers().get(IF_NONE_MATCH).cloned();
+    let response = next.run(request).await;
+    if !is_get || response.status() != StatusCode::OK {
+        return response;
+    }
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    let etag = body_etag(&bytes);
+    if if_none_match.is_some_and(|header| etag_matches(&header, &etag)) {
+        let mut not_modified = StatusCode::NOT_MODIFIED.into_response();
+        if let Ok(value) = HeaderValue::from_str(&etag) {
+            not_modified.headers_mut().insert(ETAG, value);
+        }
+        return not_modified;
+    }
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        parts.headers.insert(ETAG, value);
+    }
+    Response::from_parts(parts, Body::from(bytes))
+}
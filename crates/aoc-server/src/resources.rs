@@ -0,0 +1,643 @@
+//! Read-only resource routes: conversations, artifacts, search, task
+//! timelines, segment routes, canon, handshake packs, agents, job queues, and
+//! audit chain verification.
+//!
+//! Bodies are built with `json!` from storage rows so the wire format stays
+//! independent of storage struct layout.
+
+use aoc_storage::{
+    AgentRollup, ArtifactQuery, AuditChainHead, CanonEntryRevision, CanonRevisionState,
+    MemDecision, ReflectorJob, ReflectorJobStatus, StorageError, StoredArtifact, T3BacklogJob,
+    T3BacklogJobStatus,
+};
+use axum::{
+    extract::{rejection::QueryRejection, Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{
+    paging::{page_json, PageParams},
+    ApiError, AppState,
+};
+
+type ApiResult = Result<Json<Value>, ApiError>;
+
+pub(crate) fn artifact_json(artifact: &StoredArtifact) -> Value {
+    json!({
+        "artifact_id": artifact.artifact_id,
+        "conversation_id": artifact.conversation_id,
+        "kind": artifact.kind,
+        "ts": artifact.ts.to_rfc3339(),
+        "text": artifact.text,
+        "trace_ids": artifact.trace_ids,
+    })
+}
+
+fn decision_json(decision: &MemDecision) -> Value {
+    json!({
+        "decision_id": decision.decision_id,
+        "ts": decision.ts.to_rfc3339(),
+        "project_id": decision.project_id,
+        "segment_id": decision.segment_id,
+        "text": decision.text,
+        "supersedes_id": decision.supersedes_id,
+    })
+}
+
+fn canon_json(entry: &CanonEntryRevision) -> Value {
+    json!({
+        "entry_id": entry.entry_id,
+        "revision": entry.revision,
+        "state": entry.state.as_str(),
+        "topic": entry.topic,
+        "summary": entry.summary,
+        "confidence_bps": entry.confidence_bps,
+        "freshness_score": entry.freshness_score,
+        "supersedes_entry_id": entry.supersedes_entry_id,
+        "evidence_refs": entry.evidence_refs,
+        "created_at": entry.created_at.to_rfc3339(),
+    })
+}
+
+fn reflector_job_json(job: &ReflectorJob) -> Value {
+    json!({
+        "job_id": job.job_id,
+        "status": job.status.as_str(),
+        "active_tag": job.active_tag,
+        "observation_ids": job.observation_ids,
+        "conversation_ids": job.conversation_ids,
+        "estimated_tokens": job.estimated_tokens,
+        "attempts": job.attempts,
+        "claimed_by": job.claimed_by,
+        "last_error": job.last_error,
+        "created_at": job.created_at.to_rfc3339(),
+        "updated_at": job.updated_at.to_rfc3339(),
+    })
+}
+
+fn t3_job_json(job: &T3BacklogJob) -> Value {
+    json!({
+        "job_id": job.job_id,
+        "status": job.status.as_str(),
+        "active_tag": job.active_tag,
+        "session_id": job.session_id,
+        "pane_id": job.pane_id,
+        "artifact_refs": job.artifact_refs,
+        "attempts": job.attempts,
+        "claimed_by": job.claimed_by,
+        "last_error": job.last_error,
+        "created_at": job.created_at.to_rfc3339(),
+        "updated_at": job.updated_at.to_rfc3339(),
+    })
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<Value, StorageError> {
+    serde_json::to_value(value).map_err(|err| StorageError::Serialization(err.to_string()))
+}
+
+pub(crate) fn parse_since(value: Option<&str>) -> Result<Option<DateTime<Utc>>, ApiError> {
+    parse_timestamp("since", value)
+}
+
+pub(crate) fn parse_timestamp(
+    name: &str,
+    value: Option<&str>,
+) -> Result<Option<DateTime<Utc>>, ApiError> {
+    value
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| {
+            DateTime::parse_from_rfc3339(value.trim())
+                .map(|ts| ts.with_timezone(&Utc))
+                .map_err(|_| ApiError::BadRequest(format!("{name} '{value}' is not RFC3339")))
+        })
+        .transpose()
+}
+
+pub(crate) async fn list_conversations(
+    State(state): State<AppState>,
+    page: Result<Query<PageParams>, QueryRejection>,
+) -> ApiResult {
+    let Query(page) = page?;
+    let body = state
+        .read(move |store| {
+            let (rows, next) = page.page(store.list_conversation_lineage(page.fetch_limit())?);
+            let mut items = Vec::with_capacity(rows.len());
+            for lineage in rows {
+                let activity = store.conversation_activity(&lineage.conversation_id)?;
+                items.push(json!({
+                    "conversation_id": lineage.conversation_id,
+                    "session_id": lineage.session_id,
+                    "parent_conversation_id": lineage.parent_conversation_id,
+                    "root_conversation_id": lineage.root_conversation_id,
+                    "updated_at": lineage.updated_at.to_rfc3339(),
+                    "t1_observations": activity.t1_observations,
+                    "t2_reflections": activity.t2_reflections,
+                    "last_activity": activity.last_activity.map(|ts| ts.to_rfc3339()),
+                }));
+            }
+            Ok(page_json(items, page.offset, next, None))
+        })
+        .await?;
+    Ok(Json(body))
+}
+
+pub(crate) async fn get_conversation(
+    State(state): State<AppState>,
+    Path(conversation_id): Path<String>,
+) -> ApiResult {
+    let id = conversation_id.clone();
+    let body = state
+        .read(move |store| {
+            let lineage = store.conversation_lineage(&id)?;
+            let activity = store.conversation_activity(&id)?;
+            if lineage.is_none() && activity.raw_events == 0 && activity.t0_events == 0 {
+                return Ok(None);
+            }
+            let artifacts = store.artifacts_for_conversation(&id)?;
+            Ok(Some(json!({
+                "conversation_id": id,
+                "session_id": lineage.as_ref().map(|lineage| lineage.session_id.clone()),
+                "parent_conversation_id": lineage
+                    .as_ref()
+                    .and_then(|lineage| lineage.parent_conversation_id.clone()),
+                "root_conversation_id": lineage
+                    .as_ref()
+                    .map(|lineage| lineage.root_conversation_id.clone()),
+                "activity": {
+                    "raw_events": activity.raw_events,
+                    "t0_events": activity.t0_events,
+                    "t1_observations": activity.t1_observations,
+                    "t2_reflections": activity.t2_reflections,
+                    "last_activity": activity.last_activity.map(|ts| ts.to_rfc3339()),
+                },
+                "artifacts": artifacts.iter().map(artifact_json).collect::<Vec<_>>(),
+            })))
+        })
+        .await?;
+    body.map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("no conversation {conversation_id}")))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct ArtifactParams {
+    q: Option<String>,
+    conversation: Option<String>,
+    tag: Option<String>,
+    task: Option<String>,
+    segment: Option<String>,
+    kind: Option<String>,
+    since: Option<String>,
+    until: Option<String>,
+    #[serde(default)]
+    oldest_first: bool,
+    #[serde(default)]
+    offset: usize,
+    limit: Option<usize>,
+}
+
+pub(crate) async fn list_artifacts(
+    State(state): State<AppState>,
+    params: Result<Query<ArtifactParams>, QueryRejection>,
+) -> ApiResult {
+    let Query(params) = params?;
+    if let Some(kind) = params.kind.as_deref() {
+        if !matches!(kind, "t1" | "t2") {
+            return Err(ApiError::BadRequest(format!(
+                "kind '{kind}' must be t1 or t2"
+            )));
+        }
+    }
+    let query = ArtifactQuery {
+        text: params.q,
+        conversation_id: params.conversation,
+        active_tag: params.tag,
+        task_id: params.task,
+        segment_id: params.segment,
+        kind: params.kind,
+        since: parse_since(params.since.as_deref())?,
+        until: parse_timestamp("until", params.until.as_deref())?,
+        oldest_first: params.oldest_first,
+        offset: params.offset,
+        limit: PageParams {
+            offset: params.offset,
+            limit: params.limit,
+        }
+        .limit(),
+    };
+    let body = state
+        .read(move |store| {
+            let page = store.query_artifacts(&query)?;
+            Ok(page_json(
+                page.artifacts.iter().map(artifact_json).collect(),
+                page.offset,
+                page.next_offset(),
+                Some(page.total),
+            ))
+        })
+        .await?;
+    Ok(Json(body))
+}
+
+pub(crate) async fn get_artifact(
+    State(state): State<AppState>,
+    Path(artifact_id): Path<String>,
+) -> ApiResult {
+    let id = artifact_id.clone();
+    let body = state
+        .read(move |store| {
+            let Some(artifact) = store.artifact_by_id(&id)? else {
+                return Ok(None);
+            };
+            let mut body = artifact_json(&artifact);
+            body["task_links"] = to_json(&store.artifact_task_links_for_artifact(&id)?)?;
+            body["route"] = to_json(&store.segment_route_for_artifact(&id)?)?;
+            body["provenance"] = to_json(&store.semantic_provenance_for_artifact(&id)?)?;
+            body["archived"] = json!(store.is_artifact_archived(&id)?);
+            Ok(Some(body))
+        })
+        .await?;
+    body.map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("no artifact {artifact_id}")))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct SearchParams {
+    q: Option<String>,
+    segment: Option<String>,
+    since: Option<String>,
+    limit: Option<usize>,
+}
+
+/// Artifacts and decisions matching every word of `q`, newest first.
+pub(crate) async fn search(
+    State(state): State<AppState>,
+    params: Result<Query<SearchParams>, QueryRejection>,
+) -> ApiResult {
+    let Query(params) = params?;
+    let text = params.q.unwrap_or_default().trim().to_string();
+    if text.is_empty() {
+        return Err(ApiError::BadRequest(
+            "search needs a non-empty q".to_string(),
+        ));
+    }
+    let since = parse_since(params.since.as_deref())?;
+    let limit = PageParams {
+        offset: 0,
+        limit: params.limit,
+    }
+    .limit();
+    let body = state
+        .read(move |store| {
+            let artifacts = store.query_artifacts(&ArtifactQuery {
+                text: Some(text.clone()),
+                segment_id: params.segment.clone(),
+                since,
+                limit,
+                ..ArtifactQuery::default()
+            })?;
+            let decisions =
+                store.search_mem_decisions(&text, params.segment.as_deref(), since, limit)?;
+            let mut hits = artifacts
+                .artifacts
+                .iter()
+                .map(|artifact| {
+                    (
+                        artifact.ts,
+                        json!({ "type": "artifact", "artifact": artifact_json(artifact) }),
+                    )
+                })
+                .chain(decisions.iter().map(|decision| {
+                    (
+                        decision.ts,
+                        json!({ "type": "decision", "decision": decision_json(decision) }),
+                    )
+                }))
+                .collect::<Vec<_>>();
+            hits.sort_by_key(|hit| std::cmp::Reverse(hit.0));
+            hits.truncate(limit);
+            Ok(json!({
+                "q": text,
+                "hits": hits.into_iter().map(|(_, hit)| hit).collect::<Vec<_>>(),
+            }))
+        })
+        .await?;
+    Ok(Json(body))
+}
+
+pub(crate) async fn list_tasks(
+    State(state): State<AppState>,
+    page: Result<Query<PageParams>, QueryRejection>,
+) -> ApiResult {
+    let Query(page) = page?;
+    let body = state
+        .read(move |store| {
+            let (task_ids, next) = page.page(store.recent_linked_task_ids(page.fetch_limit())?);
+            Ok(page_json(
+                task_ids.into_iter().map(Value::String).collect(),
+                page.offset,
+                next,
+                None,
+            ))
+        })
+        .await?;
+    Ok(Json(body))
+}
+
+/// Everything linked to a task, oldest first, with the artifact inlined.
+pub(crate) async fn task_timeline(
+    State(state): State<AppState>,
+    Path(task_id): Path<String>,
+    page: Result<Query<PageParams>, QueryRejection>,
+) -> ApiResult {
+    let Query(page) = page?;
+    let body = state
+        .read(move |store| {
+            let links = store.artifact_task_links_for_task(&task_id)?;
+            let total = links.len();
+            let (links, next) = page.page(links);
+            let mut items = Vec::with_capacity(links.len());
+            for link in links {
+                let mut item = to_json(&link)?;
+                item["artifact"] = store
+                    .artifact_by_id(&link.artifact_id)?
+                    .as_ref()
+                    .map(artifact_json)
+                    .unwrap_or(Value::Null);
+                items.push(item);
+            }
+            let mut body = page_json(items, page.offset, next, Some(total));
+            body["task_id"] = json!(task_id);
+            Ok(body)
+        })
+        .await?;
+    Ok(Json(body))
+}
+
+pub(crate) async fn get_route(
+    State(state): State<AppState>,
+    Path(artifact_id): Path<String>,
+) -> ApiResult {
+    let id = artifact_id.clone();
+    let route = state
+        .read(move |store| {
+            store
+                .segment_route_for_artifact(&id)?
+                .map(|route| to_json(&route))
+                .transpose()
+        })
+        .await?;
+    route
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("artifact {artifact_id} has no route")))
+}
+
+pub(crate) async fn segment_artifacts(
+    State(state): State<AppState>,
+    Path(segment_id): Path<String>,
+    page: Result<Query<PageParams>, QueryRejection>,
+) -> ApiResult {
+    let Query(page) = page?;
+    let body = state
+        .read(move |store| {
+            let ids = store.artifacts_with_primary_segment(&segment_id, page.fetch_limit())?;
+            let (ids, next) = page.page(ids);
+            let mut items = Vec::with_capacity(ids.len());
+            for id in ids {
+                if let Some(artifact) = store.artifact_by_id(&id)? {
+                    items.push(artifact_json(&artifact));
+                }
+            }
+            let mut body = page_json(items, page.offset, next, None);
+            body["segment_id"] = json!(segment_id);
+            Ok(body)
+        })
+        .await?;
+    Ok(Json(body))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct CanonParams {
+    state: Option<String>,
+    topic: Option<String>,
+}
+
+pub(crate) async fn list_canon(
+    State(state): State<AppState>,
+    params: Result<Query<CanonParams>, QueryRejection>,
+) -> ApiResult {
+    let Query(params) = params?;
+    let revision_state = match params.state.as_deref().unwrap_or("active") {
+        "active" => CanonRevisionState::Active,
+        "superseded" => CanonRevisionState::Superseded,
+        "stale" => CanonRevisionState::Stale,
+        other => {
+            return Err(ApiError::BadRequest(format!(
+                "canon state '{other}' must be active, superseded, or stale"
+            )))
+        }
+    };
+    let entries = state
+        .read(move |store| store.canon_entries_by_state(revision_state, params.topic.as_deref()))
+        .await?;
+    Ok(Json(json!({
+        "state": revision_state.as_str(),
+        "items": entries.iter().map(canon_json).collect::<Vec<_>>(),
+    })))
+}
+
+pub(crate) async fn canon_entry(
+    State(state): State<AppState>,
+    Path(entry_id): Path<String>,
+) -> ApiResult {
+    let id = entry_id.clone();
+    let revisions = state
+        .read(move |store| store.canon_entry_revisions(&id))
+        .await?;
+    if revisions.is_empty() {
+        return Err(ApiError::NotFound(format!("no canon entry {entry_id}")));
+    }
+    Ok(Json(json!({
+        "entry_id": entry_id,
+        "revisions": revisions.iter().map(canon_json).collect::<Vec<_>>(),
+    })))
+}
+
+/// Latest handshake pack for `/v1/handshake/{scope}/{scope_key}`.
+pub(crate) async fn handshake(
+    State(state): State<AppState>,
+    Path((scope, scope_key)): Path<(String, String)>,
+) -> ApiResult {
+    let (lookup_scope, lookup_key) = (scope.clone(), scope_key.clone());
+    let snapshot = state
+        .read(move |store| store.latest_handshake_snapshot(&lookup_scope, &lookup_key))
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("no {scope} handshake for {scope_key}")))?;
+    Ok(Json(json!({
+        "snapshot_id": snapshot.snapshot_id,
+        "scope": snapshot.scope,
+        "scope_key": snapshot.scope_key,
+        "payload_text": snapshot.payload_text,
+        "payload_hash": snapshot.payload_hash,
+        "token_estimate": snapshot.token_estimate,
+        "created_at": snapshot.created_at.to_rfc3339(),
+    })))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct AuditParams {
+    anchor_seq: Option<i64>,
+    anchor_root: Option<String>,
+}
+
+/// Verifies the audit chain, optionally against a head recorded earlier.
+pub(crate) async fn verify_audit(
+    State(state): State<AppState>,
+    params: Result<Query<AuditParams>, QueryRejection>,
+) -> ApiResult {
+    let Query(params) = params?;
+    let anchor = match (params.anchor_seq, params.anchor_root) {
+        (Some(seq), Some(root)) => Some(AuditChainHead { seq, root }),
+        (None, None) => None,
+        _ => {
+            return Err(ApiError::BadRequest(
+                "anchor_seq and anchor_root go together".to_string(),
+            ))
+        }
+    };
+    let report = state
+        .read(move |store| store.verify_audit_chain(anchor.as_ref()))
+        .await?;
+    Ok(Json(json!({
+        "enabled": report.enabled,
+        "intact": report.intact(),
+        "entries": report.entries,
+        "head": report.head,
+        "findings": report.findings,
+    })))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct AgentParams {
+    since: Option<String>,
+}
+
+/// Per-agent rollups so a fleet can be compared side by side.
+pub(crate) async fn list_agents(
+    State(state): State<AppState>,
+    params: Result<Query<AgentParams>, QueryRejection>,
+) -> ApiResult {
+    let Query(params) = params?;
+    let since = parse_since(params.since.as_deref())?;
+    let rollups = state.read(move |store| store.agent_rollups(since)).await?;
+    Ok(Json(json!({
+        "items": rollups.iter().map(agent_json).collect::<Vec<_>>(),
+    })))
+}
+
+fn agent_json(rollup: &AgentRollup) -> Value {
+    json!({
+        "agent_id": rollup.agent_id,
+        "kind": rollup.kind,
+        "model": rollup.model,
+        "owner": rollup.owner,
+        "conversations": rollup.conversations,
+        "raw_events": rollup.raw_events,
+        "last_seen": rollup.last_seen,
+        "artifacts": rollup.artifacts,
+        "semantic_artifacts": rollup.semantic_artifacts,
+        "fallback_artifacts": rollup.fallback_artifacts,
+        "fallback_rate_bps": rollup.fallback_rate_bps(),
+        "tasks_worked": rollup.tasks_worked,
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct JobParams {
+    status: Option<String>,
+    #[serde(default)]
+    offset: usize,
+    limit: Option<usize>,
+}
+
+pub(crate) async fn list_jobs(
+    State(state): State<AppState>,
+    Path(queue): Path<String>,
+    params: Result<Query<JobParams>, QueryRejection>,
+) -> ApiResult {
+    let Query(params) = params?;
+    let page = PageParams {
+        offset: params.offset,
+        limit: params.limit,
+    };
+    let status = params.status.filter(|status| status != "all");
+    let bad_status = |status: &str| {
+        ApiError::BadRequest(format!(
+            "job status '{status}' must be pending, claimed, completed, failed, cancelled, or all"
+        ))
+    };
+    let body = match queue.as_str() {
+        "reflector" => {
+            let status = status
+                .as_deref()
+                .map(|status| parse_reflector_status(status).ok_or_else(|| bad_status(status)))
+                .transpose()?;
+            state
+                .read(move |store| {
+                    let (jobs, next) =
+                        page.page(store.list_reflector_jobs(status, page.fetch_limit())?);
+                    Ok(page_json(
+                        jobs.iter().map(reflector_job_json).collect(),
+                        page.offset,
+                        next,
+                        None,
+                    ))
+                })
+                .await?
+        }
+        "t3" => {
+            let status = status
+                .as_deref()
+                .map(|status| parse_t3_status(status).ok_or_else(|| bad_status(status)))
+                .transpose()?;
+            state
+                .read(move |store| {
+                    let (jobs, next) =
+                        page.page(store.list_t3_backlog_jobs(status, page.fetch_limit())?);
+                    Ok(page_json(
+                        jobs.iter().map(t3_job_json).collect(),
+                        page.offset,
+                        next,
+                        None,
+                    ))
+                })
+                .await?
+        }
+        other => return Err(ApiError::NotFound(format!("unknown job queue '{other}'"))),
+    };
+    Ok(Json(body))
+}
+
+fn parse_reflector_status(value: &str) -> Option<ReflectorJobStatus> {
+    match value {
+        "pending" => Some(ReflectorJobStatus::Pending),
+        "claimed" => Some(ReflectorJobStatus::Claimed),
+        "completed" => Some(ReflectorJobStatus::Completed),
+        "failed" => Some(ReflectorJobStatus::Failed),
+        "cancelled" => Some(ReflectorJobStatus::Cancelled),
+        _ => None,
+    }
+}
+
+fn parse_t3_status(value: &str) -> Option<T3BacklogJobStatus> {
+    match value {
+        "pending" => Some(T3BacklogJobStatus::Pending),
+        "claimed" => Some(T3BacklogJobStatus::Claimed),
+        "completed" => Some(T3BacklogJobStatus::Completed),
+        "failed" => Some(T3BacklogJobStatus::Failed),
+        "cancelled" => Some(T3BacklogJobStatus::Cancelled),
+        _ => None,
+    }
+}
@@ -0,0 +1,551 @@
+//! JSON-RPC 2.0 over stdio for editor integrations (VS Code, Neovim) that
+//! want cockpit panels without running `aoc serve` on a port.
+//!
+//! Messages use LSP framing (`Content-Length: N\r\n\r\n{json}`), so
+//! `vim.lsp.rpc` and `vscode-jsonrpc` can talk to it unchanged. Methods map
+//! onto the read routes and answer with the same bodies:
+//! - `initialize`: server info and the method list;
+//! - `search`: `/v1/search` (`q`, `segment`, `since`, `limit`);
+//! - `artifacts/query`: `/v1/artifacts`;
+//! - `tasks/list` and `tasks/timeline` (`task_id`, `offset`, `limit`);
+//! - `handshake`: latest pack for `scope`/`scope_key`;
+//! - `feed/subscribe` / `feed/unsubscribe`: the `/v1/events` feed pushed as
+//!   `feed/event` notifications carrying `{subscription, event, data}`;
+//! - `shutdown` then the `exit` notification, as in LSP.
+//!
+//! The session is read-only; there is no token to present over stdio.
+
+use aoc_core::ErrorCoded;
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use futures_util::StreamExt;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use std::{collections::HashMap, io};
+use tokio::{
+    io::{
+        AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt,
+        BufReader,
+    },
+    sync::mpsc,
+    task::JoinHandle,
+};
+
+use crate::{
+    events::feed_stream,
+    paging::PageParams,
+    resources::{self, ArtifactParams, SearchParams},
+    ApiError, AppState, ServerConfig,
+};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// Store and lookup failures; `data.code` carries the [`ApiError::code`].
+const SERVER_ERROR: i64 = -32000;
+
+/// Largest message body accepted; anything bigger ends the session.
+const MAX_MESSAGE_BYTES: usize = 4 * 1024 * 1024;
+
+const METHODS: &[&str] = &[
+    "initialize",
+    "search",
+    "artifacts/query",
+    "tasks/list",
+    "tasks/timeline",
+    "handshake",
+    "feed/subscribe",
+    "feed/unsubscribe",
+    "shutdown",
+];
+
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+    data: Option<Value>,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let mut error = json!({ "code": self.code, "message": self.message });
+        if let Some(data) = &self.data {
+            error["data"] = data.clone();
+        }
+        error
+    }
+}
+
+impl From<ApiError> for RpcError {
+    fn from(err: ApiError) -> Self {
+        let code = match err {
+            ApiError::BadRequest(_) => INVALID_PARAMS,
+            _ => SERVER_ERROR,
+        };
+        let info = err.error_info();
+        Self {
+            code,
+            message: info.message,
+            data: Some(json!({ "code": info.code, "retryable": info.retryable })),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TimelineParams {
+    task_id: String,
+    #[serde(default)]
+    offset: usize,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HandshakeParams {
+    scope: String,
+    scope_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UnsubscribeParams {
+    subscription: u64,
+}
+
+/// Serves JSON-RPC on the process's stdin and stdout until `exit` or EOF.
+pub async fn serve_stdio(config: ServerConfig) -> io::Result<()> {
+    serve_rpc(tokio::io::stdin(), tokio::io::stdout(), config).await
+}
+
+/// Serves JSON-RPC on any byte stream pair until `exit` or EOF, then ends
+/// open feed subscriptions and flushes pending notifications.
+pub async fn serve_rpc<R, W>(reader: R, writer: W, config: ServerConfig) -> io::Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let state = AppState::new(config);
+    let (outgoing, receiver) = mpsc::unbounded_channel();
+    let writer = tokio::spawn(write_messages(writer, receiver));
+    let mut session = Session {
+        state: state.clone(),
+        outgoing,
+        subscriptions: HashMap::new(),
+        next_subscription: 1,
+        shutting_down: false,
+    };
+    tracing::info!(
+        store = %state.config.store_path.display(),
+        "mind json-rpc session started"
+    );
+
+    let mut reader = BufReader::new(reader);
+    let result = loop {
+        let body = match read_message(&mut reader).await {
+            Ok(Some(body)) => body,
+            Ok(None) => break Ok(()),
+            Err(err) => break Err(err),
+        };
+        if !session.handle(&body).await {
+            break Ok(());
+        }
+    };
+
+    state.shutdown.send_replace(true);
+    for (_, task) in session.subscriptions.drain() {
+        task.abort();
+    }
+    drop(session);
+    writer.await.map_err(io::Error::other)??;
+    result
+}
+
+struct Session {
+    state: AppState,
+    outgoing: mpsc::UnboundedSender<Value>,
+    subscriptions: HashMap<u64, JoinHandle<()>>,
+    next_subscription: u64,
+    shutting_down: bool,
+}
+
+impl Session {
+    /// Handles one message; `false` ends the session.
+    async fn handle(&mut self, body: &[u8]) -> bool {
+        let message = match serde_json::from_slice::<Value>(body) {
+            Ok(message) => message,
+            Err(err) => {
+                self.reply(
+                    Value::Null,
+                    Err(RpcError::new(PARSE_ERROR, err.to_string())),
+                );
+                return true;
+            }
+        };
+        let id = message.get("id").cloned();
+        let method = message.get("method").and_then(Value::as_str);
+        let (Some(method), Some("2.0")) = (method, message.get("jsonrpc").and_then(Value::as_str))
+        else {
+            if id.is_some() || !message.is_object() {
+                self.reply(
+                    id.unwrap_or(Value::Null),
+                    Err(RpcError::new(INVALID_REQUEST, "not a JSON-RPC 2.0 request")),
+                );
+            }
+            return true;
+        };
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let Some(id) = id else {
+            // Notifications get no reply; only `exit` means anything.
+            return method != "exit";
+        };
+        let result = if self.shutting_down {
+            Err(RpcError::new(INVALID_REQUEST, "server is shutting down"))
+        } else {
+            self.call(method, params).await
+        };
+        self.reply(id, result);
+        true
+    }
+
+    async fn call(&mut self, method: &str, params: Value) -> Result<Value, RpcError> {
+        let state = self.state.clone();
+        match method {
+            "initialize" => Ok(json!({
+                "serverInfo": { "name": "aoc", "version": env!("CARGO_PKG_VERSION") },
+                "store_path": state.config.store_path,
+                "read_only": true,
+                "methods": METHODS,
+            })),
+            "search" => {
+                let params: SearchParams = parse_params(params)?;
+                let Json(body) = resources::search(State(state), Ok(Query(params))).await?;
+                Ok(body)
+            }
+            "artifacts/query" => {
+                let params: ArtifactParams = parse_params(params)?;
+                let Json(body) = resources::list_artifacts(State(state), Ok(Query(params))).await?;
+                Ok(body)
+            }
+            "tasks/list" => {
+                let page: PageParams = parse_params(params)?;
+                let Json(body) = resources::list_tasks(State(state), Ok(Query(page))).await?;
+                Ok(body)
+            }
+            "tasks/timeline" => {
+                let params: TimelineParams = parse_params(params)?;
+                let page = PageParams {
+                    offset: params.offset,
+                    limit: params.limit,
+                };
+                let Json(body) =
+                    resources::task_timeline(State(state), Path(params.task_id), Ok(Query(page)))
+                        .await?;
+                Ok(body)
+            }
+            "handshake" => {
+                let params: HandshakeParams = parse_params(params)?;
+                let Json(body) =
+                    resources::handshake(State(state), Path((params.scope, params.scope_key)))
+                        .await?;
+                Ok(body)
+            }
+            "feed/subscribe" => Ok(json!({ "subscription": self.subscribe() })),
+            "feed/unsubscribe" => {
+                let params: UnsubscribeParams = parse_params(params)?;
+                let task = self.subscriptions.remove(&params.subscription);
+                let unsubscribed = task.is_some();
+                if let Some(task) = task {
+                    task.abort();
+                }
+                Ok(json!({ "unsubscribed": unsubscribed }))
+            }
+            "shutdown" => {
+                self.shutting_down = true;
+                Ok(Value::Null)
+            }
+            other => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("unknown method '{other}'"),
+            )),
+        }
+    }
+
+    fn subscribe(&mut self) -> u64 {
+        let subscription = self.next_subscription;
+        self.next_subscription += 1;
+        let outgoing = self.outgoing.clone();
+        let events = feed_stream(self.state.clone());
+        let task = tokio::spawn(async move {
+            let mut events = Box::pin(events);
+            while let Some(event) = events.next().await {
+                let notification = json!({
+                    "jsonrpc": "2.0",
+                    "method": "feed/event",
+                    "params": {
+                        "subscription": subscription,
+                        "event": event.kind,
+                        "data": event.data,
+                    },
+                });
+                if outgoing.send(notification).is_err() {
+                    return;
+                }
+            }
+        });
+        self.subscriptions.insert(subscription, task);
+        subscription
+    }
+
+    fn reply(&self, id: Value, result: Result<Value, RpcError>) {
+        let response = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(err) => {
+                if err.code == SERVER_ERROR {
+                    tracing::debug!(error = %err.message, "json-rpc call failed");
+                }
+                json!({ "jsonrpc": "2.0", "id": id, "error": err.to_json() })
+            }
+        };
+        // The writer only stops once every sender is gone.
+        let _ = self.outgoing.send(response);
+    }
+}
+
+/// Absent params decode as an empty object so every field can default.
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|err| RpcError::new(INVALID_PARAMS, err.to_string()))
+}
+
+/// Reads one framed message body, or `None` at a clean EOF.
+async fn read_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> io::Result<Option<Vec<u8>>> {
+    let mut content_length = None;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return match content_length {
+                None => Ok(None),
+                Some(_) => Err(io::ErrorKind::UnexpectedEof.into()),
+            };
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                let length = value.trim().parse::<usize>().map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("bad Content-Length '{}'", value.trim()),
+                    )
+                })?;
+                content_length = Some(length);
+            }
+        }
+    }
+    let length = content_length.unwrap_or_default();
+    if length > MAX_MESSAGE_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("message of {length} bytes exceeds {MAX_MESSAGE_BYTES}"),
+        ));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(body))
+}
+
+async fn write_messages<W: AsyncWrite + Unpin>(
+    mut writer: W,
+    mut messages: mpsc::UnboundedReceiver<Value>,
+) -> io::Result<()> {
+    while let Some(message) = messages.recv().await {
+        let body = message.to_string();
+        writer
+            .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+            .await?;
+        writer.write_all(body.as_bytes()).await?;
+        writer.flush().await?;
+    }
+    writer.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::mind_contracts::{ArtifactTaskLink, ArtifactTaskRelation};
+    use aoc_storage::MindStore;
+    use chrono::{TimeZone, Utc};
+    use std::time::Duration;
+    use tokio::io::{duplex, DuplexStream};
+
+    async fn send(client: &mut DuplexStream, message: Value) {
+        let body = message.to_string();
+        client
+            .write_all(format!("Content-Length: {}\r\n\r\n{body}", body.len()).as_bytes())
+            .await
+            .expect("write request");
+    }
+
+    async fn recv(responses: &mut BufReader<DuplexStream>) -> Value {
+        let body = tokio::time::timeout(Duration::from_secs(10), read_message(responses))
+            .await
+            .expect("message in time")
+            .expect("read")
+            .expect("message");
+        serde_json::from_slice(&body).expect("json message")
+    }
+
+    fn request(id: u64, method: &str, params: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn stdio_session_answers_reads_and_pushes_feed_events() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("mind.sqlite");
+        let store = MindStore::open(&path).expect("store");
+        let ts = Utc.with_ymd_and_hms(2026, 3, 1, 10, 0, 0).unwrap();
+        store
+            .insert_observation("obs:1", "conv-1", ts, "parser retries flaky", &[])
+            .expect("observation");
+        store
+            .upsert_artifact_task_link(&ArtifactTaskLink {
+                artifact_id: "obs:1".to_string(),
+                task_id: "42".to_string(),
+                relation: ArtifactTaskRelation::WorkedOn,
+                confidence_bps: 9_000,
+                evidence_event_ids: vec![],
+                source: "test".to_string(),
+                start_ts: ts,
+                end_ts: None,
+            })
+            .expect("task link");
+
+        let mut config = ServerConfig::new(&path);
+        config.poll_interval = Duration::from_millis(20);
+        let (mut requests, server_in) = duplex(64 * 1024);
+        let (server_out, client_out) = duplex(64 * 1024);
+        let session = tokio::spawn(serve_rpc(server_in, server_out, config));
+        let mut responses = BufReader::new(client_out);
+
+        send(&mut requests, request(1, "initialize", json!({}))).await;
+        let reply = recv(&mut responses).await;
+        assert_eq!(reply["id"], 1);
+        assert_eq!(reply["result"]["read_only"], true);
+
+        send(
+            &mut requests,
+            request(2, "search", json!({ "q": "parser" })),
+        )
+        .await;
+        let reply = recv(&mut responses).await;
+        assert_eq!(
+            reply["result"]["hits"][0]["artifact"]["artifact_id"],
+            "obs:1"
+        );
+
+        send(
+            &mut requests,
+            request(3, "tasks/timeline", json!({ "task_id": "42" })),
+        )
+        .await;
+        let reply = recv(&mut responses).await;
+        assert_eq!(reply["result"]["total"], 1);
+        assert_eq!(
+            reply["result"]["items"][0]["artifact"]["text"],
+            "parser retries flaky"
+        );
+
+        send(
+            &mut requests,
+            request(
+                4,
+                "handshake",
+                json!({ "scope": "project", "scope_key": "x" }),
+            ),
+        )
+        .await;
+        let reply = recv(&mut responses).await;
+        assert_eq!(reply["error"]["code"], SERVER_ERROR);
+        assert_eq!(reply["error"]["data"]["code"], "not_found");
+
+        send(&mut requests, request(5, "search", json!({ "q": " " }))).await;
+        assert_eq!(recv(&mut responses).await["error"]["code"], INVALID_PARAMS);
+        send(&mut requests, request(6, "nope", Value::Null)).await;
+        assert_eq!(
+            recv(&mut responses).await["error"]["code"],
+            METHOD_NOT_FOUND
+        );
+        requests
+            .write_all(b"Content-Length: 5\r\n\r\n{oops")
+            .await
+            .expect("write garbage");
+        assert_eq!(recv(&mut responses).await["error"]["code"], PARSE_ERROR);
+
+        send(&mut requests, request(7, "feed/subscribe", Value::Null)).await;
+        let reply = recv(&mut responses).await;
+        let subscription = reply["result"]["subscription"].clone();
+        let status = recv(&mut responses).await;
+        assert_eq!(status["method"], "feed/event");
+        assert_eq!(status["params"]["event"], "status");
+        store
+            .insert_observation("obs:2", "conv-1", ts, "cache warm", &[])
+            .expect("second observation");
+        let artifact = loop {
+            let event = recv(&mut responses).await;
+            if event["params"]["event"] == "artifact" {
+                break event;
+            }
+        };
+        assert_eq!(artifact["params"]["subscription"], subscription);
+        assert_eq!(artifact["params"]["data"]["artifact_id"], "obs:2");
+
+        send(
+            &mut requests,
+            request(
+                8,
+                "feed/unsubscribe",
+                json!({ "subscription": subscription }),
+            ),
+        )
+        .await;
+        let reply = loop {
+            let message = recv(&mut responses).await;
+            if message["id"] == 8 {
+                break message;
+            }
+        };
+        assert_eq!(reply["result"]["unsubscribed"], true);
+
+        send(&mut requests, request(9, "shutdown", Value::Null)).await;
+        assert_eq!(recv(&mut responses).await["result"], Value::Null);
+        send(
+            &mut requests,
+            request(10, "search", json!({ "q": "parser" })),
+        )
+        .await;
+        assert_eq!(recv(&mut responses).await["error"]["code"], INVALID_REQUEST);
+        send(&mut requests, json!({ "jsonrpc": "2.0", "method": "exit" })).await;
+        tokio::time::timeout(Duration::from_secs(10), session)
+            .await
+            .expect("session ends")
+            .expect("join")
+            .expect("serve");
+    }
+}
@@ -0,0 +1,84 @@
+//! Store-to-store sync for `aoc sync <url>`: `GET /v1/sync` names the store,
+//! `GET /v1/sync/bundle` pages through its change journal, and
+//! `POST /v1/sync/apply` applies a peer's bundle. Watermarks live with the
+//! caller; the server only answers.
+
+use aoc_storage::{SyncApplyReport, SyncBundle};
+use axum::{
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        Query, State,
+    },
+    http::HeaderMap,
+    Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{paging::MAX_PAGE_LIMIT, ApiError, AppState};
+
+/// Request body cap for `/v1/sync/apply`; a page of raw events can outgrow
+/// axum's 2 MiB default.
+pub(crate) const SYNC_BODY_LIMIT: usize = 64 * 1024 * 1024;
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct BundleParams {
+    #[serde(default)]
+    since: i64,
+    limit: Option<usize>,
+}
+
+pub(crate) async fn identity(State(state): State<AppState>) -> Result<Json<Value>, ApiError> {
+    let (store_id, head_seq) = state
+        .read(|store| Ok((store.sync_store_id()?, store.sync_head_seq()?)))
+        .await?;
+    Ok(Json(json!({
+        "store_id": store_id,
+        "head_seq": head_seq,
+        "schema_version": aoc_storage::MIND_SCHEMA_VERSION,
+    })))
+}
+
+pub(crate) async fn bundle(
+    State(state): State<AppState>,
+    params: Result<Query<BundleParams>, QueryRejection>,
+) -> Result<Json<SyncBundle>, ApiError> {
+    let Query(params) = params?;
+    if params.since < 0 {
+        return Err(ApiError::BadRequest(
+            "since must not be negative".to_string(),
+        ));
+    }
+    let limit = params
+        .limit
+        .unwrap_or(MAX_PAGE_LIMIT)
+        .clamp(1, MAX_PAGE_LIMIT);
+    let bundle = state
+        .read(move |store| store.sync_bundle_since(params.since, limit))
+        .await?;
+    Ok(Json(bundle))
+}
+
+pub(crate) async fn apply(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Result<Json<SyncBundle>, JsonRejection>,
+) -> Result<Json<SyncApplyReport>, ApiError> {
+    state.authorize(&headers)?;
+    let Json(bundle) = body?;
+    let peer = bundle.store_id.clone();
+    let report = state
+        .write(&headers, move |store| {
+            store.apply_sync_bundle(&bundle, Utc::now())
+        })
+        .await?;
+    tracing::info!(
+        peer = %peer,
+        groups = report.groups,
+        inserted = report.inserted,
+        conflicts = report.conflicts.len(),
+        "sync bundle applied over api"
+    );
+    Ok(Json(report))
+}
@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS retrieval_metrics (
+    sample_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    pipeline TEXT NOT NULL,
+    scope_key TEXT NOT NULL,
+    candidate_fetch_us INTEGER NOT NULL,
+    rerank_us INTEGER NOT NULL,
+    assembly_us INTEGER NOT NULL,
+    total_us INTEGER NOT NULL,
+    candidates_considered INTEGER NOT NULL,
+    hit_budget INTEGER NOT NULL,
+    hits_returned INTEGER NOT NULL,
+    line_budget INTEGER NOT NULL,
+    lines_used INTEGER NOT NULL,
+    truncated INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_retrieval_metrics_pipeline_created
+    ON retrieval_metrics(pipeline, created_at DESC);
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS mind_pins (
+    pin_id TEXT PRIMARY KEY,
+    scope_key TEXT NOT NULL,
+    target_kind TEXT NOT NULL,
+    target_id TEXT,
+    text TEXT NOT NULL,
+    reason TEXT,
+    pinned_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_mind_pins_scope_pinned
+    ON mind_pins(scope_key, pinned_at ASC);
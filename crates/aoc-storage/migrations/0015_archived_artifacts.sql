@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS archived_artifacts (
+    artifact_id TEXT PRIMARY KEY,
+    conversation_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    retention_bps INTEGER NOT NULL,
+    reason TEXT NOT NULL,
+    archived_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_archived_artifacts_conversation
+    ON archived_artifacts(conversation_id, archived_at DESC);
@@ -0,0 +1,26 @@
+CREATE TABLE IF NOT EXISTS semantic_usage_ledger (
+    artifact_id TEXT NOT NULL,
+    attempt_count INTEGER NOT NULL,
+    tag TEXT NOT NULL,
+    provider_name TEXT,
+    model_id TEXT,
+    input_tokens INTEGER NOT NULL,
+    output_tokens INTEGER NOT NULL,
+    cost_micros INTEGER NOT NULL,
+    fallback_used INTEGER NOT NULL DEFAULT 0,
+    recorded_at TEXT NOT NULL,
+    PRIMARY KEY (artifact_id, attempt_count)
+);
+
+CREATE INDEX IF NOT EXISTS idx_semantic_usage_ledger_recorded
+    ON semantic_usage_ledger(recorded_at);
+
+CREATE INDEX IF NOT EXISTS idx_semantic_usage_ledger_tag
+    ON semantic_usage_ledger(tag, recorded_at);
+
+CREATE TABLE IF NOT EXISTS semantic_tag_budgets (
+    tag TEXT PRIMARY KEY,
+    budget_tokens INTEGER NOT NULL,
+    budget_cost_micros INTEGER NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,235 @@
+CREATE TABLE IF NOT EXISTS mind_sync_identity (
+    singleton INTEGER PRIMARY KEY CHECK (singleton = 1),
+    store_id TEXT NOT NULL
+);
+
+INSERT OR IGNORE INTO mind_sync_identity(singleton, store_id)
+VALUES (1, lower(hex(randomblob(16))));
+
+CREATE TABLE IF NOT EXISTS mind_sync_journal (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    table_name TEXT NOT NULL,
+    row_id INTEGER NOT NULL,
+    UNIQUE (table_name, row_id)
+);
+
+CREATE TABLE IF NOT EXISTS mind_sync_peers (
+    peer_store_id TEXT PRIMARY KEY,
+    pulled_seq INTEGER NOT NULL DEFAULT 0,
+    pushed_seq INTEGER NOT NULL DEFAULT 0,
+    last_synced_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS mind_sync_conflicts (
+    conflict_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    peer_store_id TEXT NOT NULL,
+    table_name TEXT NOT NULL,
+    group_key_json TEXT NOT NULL,
+    kept_hash TEXT NOT NULL,
+    discarded_hash TEXT NOT NULL,
+    discarded_rows_json TEXT NOT NULL,
+    detected_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_mind_sync_conflicts_detected
+    ON mind_sync_conflicts(detected_at DESC);
+
+-- Every insert or update of a synced row moves it to the head of the
+-- journal. The delete-then-insert pair avoids a conflict clause, which an
+-- outer INSERT OR IGNORE would otherwise override.
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_raw_events_insert
+AFTER INSERT ON raw_events
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'raw_events' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('raw_events', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_raw_events_update
+AFTER UPDATE ON raw_events
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'raw_events' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('raw_events', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_compact_events_t0_insert
+AFTER INSERT ON compact_events_t0
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'compact_events_t0' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('compact_events_t0', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_compact_events_t0_update
+AFTER UPDATE ON compact_events_t0
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'compact_events_t0' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('compact_events_t0', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_observations_t1_insert
+AFTER INSERT ON observations_t1
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'observations_t1' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('observations_t1', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_observations_t1_update
+AFTER UPDATE ON observations_t1
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'observations_t1' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('observations_t1', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_reflections_t2_insert
+AFTER INSERT ON reflections_t2
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'reflections_t2' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('reflections_t2', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_reflections_t2_update
+AFTER UPDATE ON reflections_t2
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'reflections_t2' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('reflections_t2', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_artifact_task_links_insert
+AFTER INSERT ON artifact_task_links
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'artifact_task_links' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('artifact_task_links', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_artifact_task_links_update
+AFTER UPDATE ON artifact_task_links
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'artifact_task_links' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('artifact_task_links', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_conversation_context_state_insert
+AFTER INSERT ON conversation_context_state
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'conversation_context_state' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('conversation_context_state', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_conversation_context_state_update
+AFTER UPDATE ON conversation_context_state
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'conversation_context_state' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('conversation_context_state', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_segment_routes_insert
+AFTER INSERT ON segment_routes
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'segment_routes' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('segment_routes', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_segment_routes_update
+AFTER UPDATE ON segment_routes
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'segment_routes' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('segment_routes', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_aoc_mem_decisions_insert
+AFTER INSERT ON aoc_mem_decisions
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'aoc_mem_decisions' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('aoc_mem_decisions', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_aoc_mem_decisions_update
+AFTER UPDATE ON aoc_mem_decisions
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'aoc_mem_decisions' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('aoc_mem_decisions', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_conversation_lineage_insert
+AFTER INSERT ON conversation_lineage
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'conversation_lineage' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('conversation_lineage', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_conversation_lineage_update
+AFTER UPDATE ON conversation_lineage
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'conversation_lineage' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('conversation_lineage', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_project_canon_revisions_insert
+AFTER INSERT ON project_canon_revisions
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'project_canon_revisions' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('project_canon_revisions', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_project_canon_revisions_update
+AFTER UPDATE ON project_canon_revisions
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'project_canon_revisions' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('project_canon_revisions', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_artifact_file_links_insert
+AFTER INSERT ON artifact_file_links
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'artifact_file_links' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('artifact_file_links', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_artifact_file_links_update
+AFTER UPDATE ON artifact_file_links
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'artifact_file_links' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('artifact_file_links', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_archived_artifacts_insert
+AFTER INSERT ON archived_artifacts
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'archived_artifacts' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('archived_artifacts', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_archived_artifacts_update
+AFTER UPDATE ON archived_artifacts
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'archived_artifacts' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('archived_artifacts', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_mind_pins_insert
+AFTER INSERT ON mind_pins
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'mind_pins' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('mind_pins', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_mind_pins_update
+AFTER UPDATE ON mind_pins
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'mind_pins' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('mind_pins', NEW.rowid);
+END;
+
+-- Existing rows are journaled once so a first sync sends the whole store.
+INSERT OR IGNORE INTO mind_sync_journal(table_name, row_id) SELECT 'raw_events', rowid FROM raw_events;
+INSERT OR IGNORE INTO mind_sync_journal(table_name, row_id) SELECT 'compact_events_t0', rowid FROM compact_events_t0;
+INSERT OR IGNORE INTO mind_sync_journal(table_name, row_id) SELECT 'observations_t1', rowid FROM observations_t1;
+INSERT OR IGNORE INTO mind_sync_journal(table_name, row_id) SELECT 'reflections_t2', rowid FROM reflections_t2;
+INSERT OR IGNORE INTO mind_sync_journal(table_name, row_id) SELECT 'artifact_task_links', rowid FROM artifact_task_links;
+INSERT OR IGNORE INTO mind_sync_journal(table_name, row_id) SELECT 'conversation_context_state', rowid FROM conversation_context_state;
+INSERT OR IGNORE INTO mind_sync_journal(table_name, row_id) SELECT 'segment_routes', rowid FROM segment_routes;
+INSERT OR IGNORE INTO mind_sync_journal(table_name, row_id) SELECT 'aoc_mem_decisions', rowid FROM aoc_mem_decisions;
+INSERT OR IGNORE INTO mind_sync_journal(table_name, row_id) SELECT 'conversation_lineage', rowid FROM conversation_lineage;
+INSERT OR IGNORE INTO mind_sync_journal(table_name, row_id) SELECT 'project_canon_revisions', rowid FROM project_canon_revisions;
+INSERT OR IGNORE INTO mind_sync_journal(table_name, row_id) SELECT 'artifact_file_links', rowid FROM artifact_file_links;
+INSERT OR IGNORE INTO mind_sync_journal(table_name, row_id) SELECT 'archived_artifacts', rowid FROM archived_artifacts;
+INSERT OR IGNORE INTO mind_sync_journal(table_name, row_id) SELECT 'mind_pins', rowid FROM mind_pins;
@@ -0,0 +1,25 @@
+CREATE TABLE IF NOT EXISTS agents (
+    agent_id TEXT PRIMARY KEY,
+    kind TEXT NOT NULL,
+    model TEXT,
+    owner TEXT,
+    registered_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_raw_events_agent
+    ON raw_events(agent_id, conversation_id);
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_agents_insert
+AFTER INSERT ON agents
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'agents' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('agents', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_agents_update
+AFTER UPDATE ON agents
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'agents' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('agents', NEW.rowid);
+END;
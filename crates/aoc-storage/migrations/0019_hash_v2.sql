@@ -0,0 +1,7 @@
+-- Hash v2 (BLAKE3) is stored next to the v1 SHA-256 compact_hash while stores
+-- migrate. compact_id stays derived from v1; rows written before this
+-- migration are backfilled by MindStore::migrate.
+ALTER TABLE compact_events_t0 ADD COLUMN compact_hash_v2 TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_compact_events_t0_hash_v2
+    ON compact_events_t0(compact_hash_v2);
@@ -0,0 +1,6 @@
+-- Schema version of each JSON blob column. Rows written before this
+-- migration, and rows copied in by a legacy import, stay at version 1 and
+-- are upgraded on read until `aoc migrate --upgrade-blobs` rewrites them.
+ALTER TABLE raw_events ADD COLUMN payload_schema_version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE raw_events ADD COLUMN attrs_schema_version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE compact_events_t0 ADD COLUMN tool_meta_schema_version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,20 @@
+-- Per-conversation reads order by (ts, id). Indexing the id as well lets
+-- SQLite walk the index in output order instead of sorting, and makes the
+-- id-only and feed-summary reads index-only. The (conversation_id, ts)
+-- indexes from 0001 are prefixes of these and are dropped.
+DROP INDEX IF EXISTS idx_raw_events_conversation_ts;
+DROP INDEX IF EXISTS idx_compact_events_t0_conversation_ts;
+DROP INDEX IF EXISTS idx_observations_t1_conversation_ts;
+DROP INDEX IF EXISTS idx_reflections_t2_conversation_ts;
+
+CREATE INDEX IF NOT EXISTS idx_raw_events_conversation_ts_id
+    ON raw_events(conversation_id, ts, event_id);
+
+CREATE INDEX IF NOT EXISTS idx_compact_events_t0_conversation_ts_id
+    ON compact_events_t0(conversation_id, ts, compact_id);
+
+CREATE INDEX IF NOT EXISTS idx_observations_t1_conversation_ts_id
+    ON observations_t1(conversation_id, ts, artifact_id);
+
+CREATE INDEX IF NOT EXISTS idx_reflections_t2_conversation_ts_id
+    ON reflections_t2(conversation_id, ts, artifact_id);
@@ -0,0 +1,16 @@
+-- Store-wide settings shared by every process that opens the store.
+CREATE TABLE IF NOT EXISTS mind_settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- Monthly raw event partitions. While `raw_events.partitioning` is set to
+-- `monthly`, new raw events land in the `raw_events_pYYYY_MM` table for their
+-- UTC month; rows written before that stay in `raw_events`, and reads union
+-- every table listed here with it.
+CREATE TABLE IF NOT EXISTS raw_event_partitions (
+    month TEXT PRIMARY KEY,
+    table_name TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL
+);
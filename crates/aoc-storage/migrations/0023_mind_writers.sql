@@ -0,0 +1,12 @@
+-- The process currently allowed to write the store. The row backs the
+-- `<store>.writer.lock` file lock: it names the holder for anyone refused,
+-- and it outlives a crashed holder until it expires or is taken over.
+CREATE TABLE IF NOT EXISTS mind_writers (
+    singleton INTEGER PRIMARY KEY CHECK (singleton = 1),
+    owner_id TEXT NOT NULL,
+    label TEXT NOT NULL,
+    owner_pid INTEGER,
+    acquired_at TEXT NOT NULL,
+    heartbeat_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
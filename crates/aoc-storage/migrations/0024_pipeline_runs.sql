@@ -0,0 +1,28 @@
+-- Journal of multi-step pipeline runs (e.g. distilling one conversation).
+-- A unit row is written in the same savepoint as the artifacts it produced,
+-- so after a crash the journal says exactly which units are durable and a
+-- restarted run skips them instead of redoing or losing work.
+CREATE TABLE IF NOT EXISTS pipeline_runs (
+    run_id TEXT PRIMARY KEY,
+    stage TEXT NOT NULL,
+    scope TEXT NOT NULL,
+    status TEXT NOT NULL,
+    total_units INTEGER NOT NULL DEFAULT 0,
+    completed_units INTEGER NOT NULL DEFAULT 0,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    last_unit TEXT,
+    last_error TEXT,
+    started_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    finished_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_pipeline_runs_stage_scope_status
+    ON pipeline_runs(stage, scope, status);
+
+CREATE TABLE IF NOT EXISTS pipeline_run_units (
+    run_id TEXT NOT NULL,
+    unit_id TEXT NOT NULL,
+    completed_at TEXT NOT NULL,
+    PRIMARY KEY (run_id, unit_id)
+);
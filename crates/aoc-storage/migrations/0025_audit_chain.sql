@@ -0,0 +1,28 @@
+-- Optional tamper-evident history of T1/T2 artifacts and canon revisions.
+-- Each entry commits to the content hash of one artifact version and to the
+-- previous entry's root, so rewriting any entry breaks every root after it.
+-- Entries are only written while the `audit_chain` setting is enabled.
+CREATE TABLE IF NOT EXISTS mind_audit_chain (
+    seq INTEGER PRIMARY KEY,
+    entry_kind TEXT NOT NULL,
+    entry_id TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    prev_root TEXT NOT NULL,
+    root TEXT NOT NULL,
+    recorded_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_mind_audit_chain_entry
+    ON mind_audit_chain(entry_kind, entry_id, seq DESC);
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_audit_chain_no_update
+BEFORE UPDATE ON mind_audit_chain
+BEGIN
+    SELECT RAISE(ABORT, 'mind_audit_chain is append-only');
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_audit_chain_no_delete
+BEFORE DELETE ON mind_audit_chain
+BEGIN
+    SELECT RAISE(ABORT, 'mind_audit_chain is append-only');
+END;
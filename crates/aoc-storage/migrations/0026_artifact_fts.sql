@@ -0,0 +1,80 @@
+-- Keyword search over T1 observations and T2 reflections. artifact_fts is a
+-- standalone FTS5 index kept in step by triggers; artifact_fts_rows maps each
+-- artifact to its index row, since REPLACE into the source tables does not
+-- fire delete triggers and the source rowids move on VACUUM.
+CREATE VIRTUAL TABLE IF NOT EXISTS artifact_fts USING fts5(
+    artifact_id UNINDEXED,
+    kind UNINDEXED,
+    text,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TABLE IF NOT EXISTS artifact_fts_rows (
+    kind TEXT NOT NULL,
+    artifact_id TEXT NOT NULL,
+    fts_rowid INTEGER NOT NULL,
+    PRIMARY KEY (kind, artifact_id)
+);
+
+CREATE TRIGGER IF NOT EXISTS trg_artifact_fts_t1_insert
+AFTER INSERT ON observations_t1
+BEGIN
+    DELETE FROM artifact_fts WHERE rowid = (
+        SELECT fts_rowid FROM artifact_fts_rows WHERE kind = 't1' AND artifact_id = NEW.artifact_id
+    );
+    INSERT INTO artifact_fts(artifact_id, kind, text) VALUES (NEW.artifact_id, 't1', NEW.text);
+    INSERT OR REPLACE INTO artifact_fts_rows(kind, artifact_id, fts_rowid)
+    VALUES ('t1', NEW.artifact_id, last_insert_rowid());
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_artifact_fts_t1_update
+AFTER UPDATE OF text ON observations_t1
+BEGIN
+    UPDATE artifact_fts SET text = NEW.text WHERE rowid = (
+        SELECT fts_rowid FROM artifact_fts_rows WHERE kind = 't1' AND artifact_id = NEW.artifact_id
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_artifact_fts_t1_delete
+AFTER DELETE ON observations_t1
+BEGIN
+    DELETE FROM artifact_fts WHERE rowid = (
+        SELECT fts_rowid FROM artifact_fts_rows WHERE kind = 't1' AND artifact_id = OLD.artifact_id
+    );
+    DELETE FROM artifact_fts_rows WHERE kind = 't1' AND artifact_id = OLD.artifact_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_artifact_fts_t2_insert
+AFTER INSERT ON reflections_t2
+BEGIN
+    DELETE FROM artifact_fts WHERE rowid = (
+        SELECT fts_rowid FROM artifact_fts_rows WHERE kind = 't2' AND artifact_id = NEW.artifact_id
+    );
+    INSERT INTO artifact_fts(artifact_id, kind, text) VALUES (NEW.artifact_id, 't2', NEW.text);
+    INSERT OR REPLACE INTO artifact_fts_rows(kind, artifact_id, fts_rowid)
+    VALUES ('t2', NEW.artifact_id, last_insert_rowid());
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_artifact_fts_t2_update
+AFTER UPDATE OF text ON reflections_t2
+BEGIN
+    UPDATE artifact_fts SET text = NEW.text WHERE rowid = (
+        SELECT fts_rowid FROM artifact_fts_rows WHERE kind = 't2' AND artifact_id = NEW.artifact_id
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_artifact_fts_t2_delete
+AFTER DELETE ON reflections_t2
+BEGIN
+    DELETE FROM artifact_fts WHERE rowid = (
+        SELECT fts_rowid FROM artifact_fts_rows WHERE kind = 't2' AND artifact_id = OLD.artifact_id
+    );
+    DELETE FROM artifact_fts_rows WHERE kind = 't2' AND artifact_id = OLD.artifact_id;
+END;
+
+INSERT INTO artifact_fts(artifact_id, kind, text)
+SELECT artifact_id, 't1', text FROM observations_t1;
+INSERT INTO artifact_fts(artifact_id, kind, text)
+SELECT artifact_id, 't2', text FROM reflections_t2;
+INSERT OR REPLACE INTO artifact_fts_rows(kind, artifact_id, fts_rowid)
+SELECT kind, artifact_id, rowid FROM artifact_fts;
@@ -0,0 +1,15 @@
+-- One embedding vector per T1/T2 artifact for similarity retrieval across
+-- conversations. The vector is a JSON array of floats like the other
+-- *_json columns; `norm` is stored so a scan only needs one dot product per
+-- row. Re-embedding an artifact (e.g. with a new model) replaces its row.
+CREATE TABLE IF NOT EXISTS artifact_embeddings (
+    artifact_id TEXT PRIMARY KEY,
+    model TEXT NOT NULL,
+    dims INTEGER NOT NULL,
+    norm REAL NOT NULL,
+    embedding_json TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_artifact_embeddings_dims
+    ON artifact_embeddings(dims, artifact_id);
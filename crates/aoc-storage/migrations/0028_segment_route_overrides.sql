@@ -0,0 +1,18 @@
+-- Manual route override patches, one per artifact. `segment_routes` holds
+-- the routes a pass wrote; these rows are the patches that produced the
+-- manual ones, kept so the next routing pass re-applies them instead of
+-- overwriting them with the automatic route. Secondary segments are a JSON
+-- array of segment ids like the other *_json columns.
+CREATE TABLE IF NOT EXISTS segment_route_overrides (
+    artifact_id TEXT PRIMARY KEY,
+    conversation_id TEXT NOT NULL,
+    patch_id TEXT NOT NULL,
+    primary_segment TEXT NOT NULL,
+    secondary_segments_json TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    confidence_bps INTEGER NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_segment_route_overrides_conversation
+    ON segment_route_overrides(conversation_id, artifact_id);
@@ -0,0 +1,15 @@
+-- Routes replaced by a re-route pass, newest last per artifact. The previous
+-- route is kept whole as JSON (`SegmentRoute`), with the primary segments
+-- before and after pulled out so "what moved out of segment X" needs no JSON
+-- parsing.
+CREATE TABLE IF NOT EXISTS segment_route_history (
+    history_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    artifact_id TEXT NOT NULL,
+    previous_primary TEXT NOT NULL,
+    new_primary TEXT NOT NULL,
+    route_json TEXT NOT NULL,
+    replaced_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_segment_route_history_artifact
+    ON segment_route_history(artifact_id, history_id);
@@ -0,0 +1,12 @@
+-- How far the observer has distilled each conversation: the last T0 event
+-- (by ts, then compact_id) a committed run covered, and how many T0 events
+-- sat at or before it then. A later run plans T1 batches only over events
+-- past the cursor; a changed count means events landed behind it, and the
+-- run re-plans the whole conversation instead.
+CREATE TABLE IF NOT EXISTS distillation_checkpoints (
+    conversation_id TEXT PRIMARY KEY,
+    last_compact_id TEXT NOT NULL,
+    last_ts TEXT NOT NULL,
+    t0_events_covered INTEGER NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,10 @@
+-- Typed sections (decisions, open questions, risks, action items) of a T1
+-- observation produced under a structured observer prompt. The T1 text keeps
+-- a rendered copy; this row is what reflection and canon stages read. One
+-- row per artifact; re-observing an artifact replaces it.
+CREATE TABLE IF NOT EXISTS observation_sections (
+    artifact_id TEXT PRIMARY KEY,
+    prompt_version TEXT NOT NULL,
+    sections_json TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,4 @@
+-- Earliest time a pending reflector job may be claimed again. Workers set it
+-- when they requeue a failed job with backoff, so a provider outage is not
+-- retried in a tight loop; claiming clears it. NULL means claimable now.
+ALTER TABLE reflector_jobs_t2 ADD COLUMN retry_after TEXT;
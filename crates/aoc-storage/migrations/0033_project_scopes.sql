@@ -0,0 +1,74 @@
+-- Project namespaces inside one store. Every conversation belongs to one
+-- project; '' is the default project that unscoped stores read and write.
+-- Artifact and canon rows carry their project so store-wide reads filter
+-- without joining back to the conversation. Ownership rows carry no
+-- timestamp, so two stores that claimed a conversation for the same project
+-- sync without a conflict.
+CREATE TABLE IF NOT EXISTS conversation_projects (
+    conversation_id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_conversation_projects_project
+    ON conversation_projects(project_id, conversation_id);
+
+ALTER TABLE compact_events_t0 ADD COLUMN project_id TEXT NOT NULL DEFAULT '';
+ALTER TABLE observations_t1 ADD COLUMN project_id TEXT NOT NULL DEFAULT '';
+ALTER TABLE reflections_t2 ADD COLUMN project_id TEXT NOT NULL DEFAULT '';
+ALTER TABLE project_canon_revisions ADD COLUMN project_id TEXT NOT NULL DEFAULT '';
+
+CREATE INDEX IF NOT EXISTS idx_observations_t1_project_ts
+    ON observations_t1(project_id, ts);
+
+CREATE INDEX IF NOT EXISTS idx_reflections_t2_project_ts
+    ON reflections_t2(project_id, ts);
+
+CREATE INDEX IF NOT EXISTS idx_project_canon_revisions_project_state_topic
+    ON project_canon_revisions(project_id, state, topic, created_at DESC);
+
+-- Watermarks are keyed per project, so the table is rebuilt with the
+-- project in its primary key. It is machine-local and never synced.
+CREATE TABLE project_watermarks_scoped (
+    project_id TEXT NOT NULL DEFAULT '',
+    scope_key TEXT NOT NULL,
+    last_artifact_ts TEXT,
+    last_artifact_id TEXT,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (project_id, scope_key)
+);
+
+INSERT INTO project_watermarks_scoped (
+    scope_key, last_artifact_ts, last_artifact_id, updated_at
+)
+SELECT scope_key, last_artifact_ts, last_artifact_id, updated_at
+FROM project_watermarks;
+
+DROP TABLE project_watermarks;
+ALTER TABLE project_watermarks_scoped RENAME TO project_watermarks;
+
+-- Everything stored so far belongs to the default project.
+INSERT OR IGNORE INTO conversation_projects (conversation_id, project_id)
+SELECT conversation_id, ''
+FROM (
+    SELECT conversation_id FROM conversation_lineage
+    UNION SELECT conversation_id FROM compact_events_t0
+    UNION SELECT conversation_id FROM observations_t1
+    UNION SELECT conversation_id FROM reflections_t2
+);
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_conversation_projects_insert
+AFTER INSERT ON conversation_projects
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'conversation_projects' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('conversation_projects', NEW.rowid);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_mind_sync_conversation_projects_update
+AFTER UPDATE ON conversation_projects
+BEGIN
+    DELETE FROM mind_sync_journal WHERE table_name = 'conversation_projects' AND row_id = NEW.rowid;
+    INSERT INTO mind_sync_journal(table_name, row_id) VALUES ('conversation_projects', NEW.rowid);
+END;
+
+INSERT OR IGNORE INTO mind_sync_journal(table_name, row_id)
+SELECT 'conversation_projects', rowid FROM conversation_projects;
@@ -0,0 +1,4 @@
+-- Categories the observer scrubbed from an input before sending it to a
+-- provider, as a JSON array of names. Rows written before redaction, and
+-- calls that never reached a provider, keep '[]'.
+ALTER TABLE semantic_runtime_provenance ADD COLUMN redactions_json TEXT NOT NULL DEFAULT '[]';
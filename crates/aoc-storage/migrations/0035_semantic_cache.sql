@@ -0,0 +1,23 @@
+-- Provider responses keyed by what was sent, so a replayed observer input
+-- reuses the earlier output instead of paying for another call. artifact_id
+-- is the artifact that first produced the response; compliance deletion of
+-- that artifact drops the entry with it.
+CREATE TABLE IF NOT EXISTS semantic_cache (
+    stage TEXT NOT NULL,
+    prompt_version TEXT NOT NULL,
+    input_hash TEXT NOT NULL,
+    artifact_id TEXT NOT NULL,
+    output_json TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    PRIMARY KEY (stage, prompt_version, input_hash)
+);
+
+CREATE INDEX IF NOT EXISTS idx_semantic_cache_expires
+    ON semantic_cache(expires_at);
+
+CREATE INDEX IF NOT EXISTS idx_semantic_cache_created
+    ON semantic_cache(created_at);
+
+CREATE INDEX IF NOT EXISTS idx_semantic_cache_artifact
+    ON semantic_cache(artifact_id);
@@ -0,0 +1,17 @@
+-- Semantic spend per UTC day and provider, accumulated by the budget
+-- manager as calls are charged. provider_name '*' is the all-providers
+-- total. budget_* hold the daily cap in force when the row was last
+-- written (0 = unlimited), so readers without the config can show what
+-- is left.
+CREATE TABLE IF NOT EXISTS semantic_budget_ledger (
+    day TEXT NOT NULL,
+    provider_name TEXT NOT NULL,
+    budget_tokens INTEGER NOT NULL DEFAULT 0,
+    budget_cost_micros INTEGER NOT NULL DEFAULT 0,
+    spent_tokens INTEGER NOT NULL DEFAULT 0,
+    spent_cost_micros INTEGER NOT NULL DEFAULT 0,
+    calls INTEGER NOT NULL DEFAULT 0,
+    rejected_calls INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (day, provider_name)
+);
@@ -0,0 +1,123 @@
+-- Postgres port of the tables `MindStoreBackend` reads and writes, at SQLite
+-- schema 33. Timestamps are TIMESTAMPTZ instead of rfc3339 text; JSON blobs
+-- stay text with their `*_schema_version`, so `decode_blob` reads them the
+-- same way on both backends. Local-only SQLite state (FTS, sync journal,
+-- partitions, audit chain) has no counterpart here.
+CREATE TABLE IF NOT EXISTS mind_schema_migrations (
+    version BIGINT PRIMARY KEY,
+    applied_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS conversation_projects (
+    conversation_id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_conversation_projects_project
+    ON conversation_projects(project_id, conversation_id);
+
+CREATE TABLE IF NOT EXISTS raw_events (
+    event_id TEXT PRIMARY KEY,
+    conversation_id TEXT NOT NULL,
+    agent_id TEXT NOT NULL,
+    ts TIMESTAMPTZ NOT NULL,
+    kind TEXT NOT NULL,
+    payload_json TEXT NOT NULL,
+    attrs_json TEXT NOT NULL DEFAULT '{}',
+    payload_schema_version BIGINT NOT NULL DEFAULT 1,
+    attrs_schema_version BIGINT NOT NULL DEFAULT 1
+);
+
+CREATE INDEX IF NOT EXISTS idx_raw_events_conversation_ts
+    ON raw_events(conversation_id, ts);
+
+CREATE TABLE IF NOT EXISTS conversation_lineage (
+    conversation_id TEXT PRIMARY KEY,
+    session_id TEXT NOT NULL,
+    parent_conversation_id TEXT,
+    root_conversation_id TEXT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_conversation_lineage_session
+    ON conversation_lineage(session_id, root_conversation_id);
+
+CREATE TABLE IF NOT EXISTS compact_events_t0 (
+    compact_id TEXT PRIMARY KEY,
+    compact_hash TEXT NOT NULL,
+    compact_hash_v2 TEXT,
+    schema_version BIGINT NOT NULL,
+    conversation_id TEXT NOT NULL,
+    ts TIMESTAMPTZ NOT NULL,
+    role TEXT,
+    text TEXT,
+    snippet TEXT,
+    source_event_ids_json TEXT NOT NULL,
+    tool_meta_json TEXT,
+    tool_meta_schema_version BIGINT NOT NULL DEFAULT 1,
+    policy_version TEXT NOT NULL,
+    project_id TEXT NOT NULL DEFAULT ''
+);
+
+CREATE INDEX IF NOT EXISTS idx_compact_events_t0_conversation_ts_id
+    ON compact_events_t0(conversation_id, ts, compact_id);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_compact_events_t0_hash_policy
+    ON compact_events_t0(compact_hash, policy_version);
+
+CREATE TABLE IF NOT EXISTS observations_t1 (
+    artifact_id TEXT PRIMARY KEY,
+    conversation_id TEXT NOT NULL,
+    ts TIMESTAMPTZ NOT NULL,
+    importance BIGINT,
+    text TEXT NOT NULL,
+    trace_ids_json TEXT NOT NULL DEFAULT '[]',
+    project_id TEXT NOT NULL DEFAULT ''
+);
+
+CREATE INDEX IF NOT EXISTS idx_observations_t1_conversation_ts_id
+    ON observations_t1(conversation_id, ts, artifact_id);
+
+CREATE TABLE IF NOT EXISTS reflections_t2 (
+    artifact_id TEXT PRIMARY KEY,
+    conversation_id TEXT NOT NULL,
+    ts TIMESTAMPTZ NOT NULL,
+    text TEXT NOT NULL,
+    trace_ids_json TEXT NOT NULL DEFAULT '[]',
+    project_id TEXT NOT NULL DEFAULT ''
+);
+
+CREATE INDEX IF NOT EXISTS idx_reflections_t2_conversation_ts
+    ON reflections_t2(conversation_id, ts);
+
+CREATE TABLE IF NOT EXISTS reflector_runtime_leases (
+    scope_id TEXT PRIMARY KEY,
+    owner_id TEXT NOT NULL,
+    owner_pid BIGINT,
+    acquired_at TIMESTAMPTZ NOT NULL,
+    heartbeat_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    metadata_json TEXT NOT NULL DEFAULT '{}'
+);
+
+CREATE TABLE IF NOT EXISTS reflector_jobs_t2 (
+    job_id TEXT PRIMARY KEY,
+    active_tag TEXT NOT NULL,
+    observation_ids_json TEXT NOT NULL,
+    conversation_ids_json TEXT NOT NULL,
+    estimated_tokens BIGINT NOT NULL,
+    status TEXT NOT NULL,
+    claimed_by TEXT,
+    claimed_at TIMESTAMPTZ,
+    attempts BIGINT NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    retry_after TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_reflector_jobs_t2_status_created
+    ON reflector_jobs_t2(status, created_at);
+
+INSERT INTO mind_schema_migrations(version) VALUES (33)
+ON CONFLICT (version) DO NOTHING;
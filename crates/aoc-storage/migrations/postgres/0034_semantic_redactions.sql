@@ -0,0 +1,4 @@
+-- Schema 34 only changes semantic_runtime_provenance, which the Postgres
+-- backend does not port; the step keeps both backends on one version.
+INSERT INTO mind_schema_migrations(version) VALUES (34)
+ON CONFLICT (version) DO NOTHING;
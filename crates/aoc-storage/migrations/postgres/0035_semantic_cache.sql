@@ -0,0 +1,5 @@
+-- Schema 35 adds semantic_cache, which only the distillers read and write.
+-- They run over SQLite (see backend.rs), so Postgres gets no table; the step
+-- keeps both backends on one version.
+INSERT INTO mind_schema_migrations(version) VALUES (35)
+ON CONFLICT (version) DO NOTHING;
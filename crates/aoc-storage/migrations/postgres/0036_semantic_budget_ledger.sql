@@ -0,0 +1,5 @@
+-- Schema 36 adds semantic_budget_ledger, which only the distillers charge.
+-- They run over SQLite (see backend.rs), so Postgres gets no table; the step
+-- keeps both backends on one version.
+INSERT INTO mind_schema_migrations(version) VALUES (36)
+ON CONFLICT (version) DO NOTHING;
@@ -0,0 +1,79 @@
+//! One transaction around a run of ingest writes.
+//!
+//! Each store write commits on its own, which costs a WAL sync per row and
+//! dominates ingest of large logs. An [`IngestBatch`] opens one transaction
+//! (or a savepoint when the caller already has one open) and derefs to the
+//! store, so the usual `insert_raw_event`/`upsert_t0_compact_event`/...
+//! calls land in it. Nothing is visible to other connections until
+//! [`IngestBatch::commit`]; dropping the batch rolls everything back.
+
+use crate::{MindStore, StorageError};
+use std::ops::Deref;
+
+pub struct IngestBatch<'store> {
+    store: &'store MindStore,
+    /// Opened as a savepoint inside the caller's transaction.
+    nested: bool,
+    finished: bool,
+}
+
+impl MindStore {
+    /// Starts an [`IngestBatch`]. At top level it takes the write lock up
+    /// front (`BEGIN IMMEDIATE`), so writes inside never hit a busy upgrade.
+    pub fn begin_ingest_batch(&self) -> Result<IngestBatch<'_>, StorageError> {
+        let nested = !self.conn.is_autocommit();
+        if nested {
+            self.conn.execute_batch("SAVEPOINT ingest_batch")?;
+        } else {
+            self.with_busy_retry(|| Ok(self.conn.execute_batch("BEGIN IMMEDIATE")?))?;
+        }
+        Ok(IngestBatch {
+            store: self,
+            nested,
+            finished: false,
+        })
+    }
+}
+
+impl IngestBatch<'_> {
+    pub fn commit(mut self) -> Result<(), StorageError> {
+        self.finished = true;
+        let sql = if self.nested {
+            "RELEASE ingest_batch"
+        } else {
+            "COMMIT"
+        };
+        if let Err(err) = self.store.conn.execute_batch(sql) {
+            self.rollback();
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    fn rollback(&self) {
+        let sql = if self.nested {
+            "ROLLBACK TO ingest_batch; RELEASE ingest_batch"
+        } else {
+            "ROLLBACK"
+        };
+        if let Err(err) = self.store.conn.execute_batch(sql) {
+            tracing::warn!(error = %err, "failed to roll back ingest batch");
+        }
+    }
+}
+
+impl Deref for IngestBatch<'_> {
+    type Target = MindStore;
+
+    fn deref(&self) -> &MindStore {
+        self.store
+    }
+}
+
+impl Drop for IngestBatch<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.rollback();
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! Schema versions for the JSON blobs stored in TEXT columns.
+//!
+//! `payload_json`, `attrs_json`, and `tool_meta_json` hold serde output of
+//! aoc-core structs that keep evolving. Each blob has a sibling
+//! `*_schema_version` column; rows written before versioning, and rows copied
+//! in by a legacy import, read as version 1. Readers pass the stored version
+//! to [`decode_blob`], which runs the registered upgrades up to
+//! [`BlobKind::current_version`] before deserializing, and
+//! [`MindStore::upgrade_json_blobs`](crate::MindStore::upgrade_json_blobs)
+//! applies the same chain eagerly and rewrites the rows.
+//!
+//! Upgrades must be idempotent, and a `tool_meta` upgrade must not change a
+//! field an already-valid row carries: T0 `compact_hash` covers the decoded
+//! tool line and is never recomputed.
+
+use crate::StorageError;
+use aoc_core::mind_contracts::tool_output_digest;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlobKind {
+    /// `raw_events.payload_json`: a serialized `RawEventBody`.
+    RawPayload,
+    /// `raw_events.attrs_json`: the raw event attribute map.
+    RawAttrs,
+    /// `compact_events_t0.tool_meta_json`: a serialized `ToolMetadataLine`.
+    ToolMeta,
+}
+
+impl BlobKind {
+    pub const ALL: [Self; 3] = [Self::RawPayload, Self::RawAttrs, Self::ToolMeta];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::RawPayload => "raw_payload",
+            Self::RawAttrs => "raw_attrs",
+            Self::ToolMeta => "tool_meta",
+        }
+    }
+
+    /// Version new writes are stamped with.
+    pub fn current_version(self) -> u32 {
+        match self {
+            Self::RawPayload => 2,
+            Self::RawAttrs => 1,
+            Self::ToolMeta => 2,
+        }
+    }
+
+    pub(crate) fn table(self) -> &'static str {
+        match self {
+            Self::RawPayload | Self::RawAttrs => "raw_events",
+            Self::ToolMeta => "compact_events_t0",
+        }
+    }
+
+    pub(crate) fn key_column(self) -> &'static str {
+        match self {
+            Self::RawPayload | Self::RawAttrs => "event_id",
+            Self::ToolMeta => "compact_id",
+        }
+    }
+
+    pub(crate) fn blob_column(self) -> &'static str {
+        match self {
+            Self::RawPayload => "payload_json",
+            Self::RawAttrs => "attrs_json",
+            Self::ToolMeta => "tool_meta_json",
+        }
+    }
+
+    pub(crate) fn version_column(self) -> &'static str {
+        match self {
+            Self::RawPayload => "payload_schema_version",
+            Self::RawAttrs => "attrs_schema_version",
+            Self::ToolMeta => "tool_meta_schema_version",
+        }
+    }
+}
+
+/// Rewrites a blob of `kind` from version `from` to `from + 1`.
+pub struct BlobUpgrade {
+    pub kind: BlobKind,
+    pub from: u32,
+    pub upgrade: fn(&mut Value),
+}
+
+/// Every upgrade step; each kind needs one step per version below its current.
+pub const BLOB_UPGRADES: &[BlobUpgrade] = &[
+    BlobUpgrade {
+        kind: BlobKind::RawPayload,
+        from: 1,
+        upgrade: fill_tool_output_digest,
+    },
+    BlobUpgrade {
+        kind: BlobKind::ToolMeta,
+        from: 1,
+        upgrade: fill_required_tool_meta_fields,
+    },
+];
+
+/// Upgrades `value` in place from `version` to the current version of `kind`
+/// and returns the version it ended at. Blobs from a newer build are left
+/// alone, since serde ignores fields it does not know.
+pub fn upgrade_blob(kind: BlobKind, version: u32, value: &mut Value) -> Result<u32, StorageError> {
+    let mut version = version.max(1);
+    while version < kind.current_version() {
+        let step = BLOB_UPGRADES
+            .iter()
+            .find(|step| step.kind == kind && step.from == version)
+            .ok_or_else(|| {
+                StorageError::Serialization(format!(
+                    "no {} blob upgrade registered from version {version}",
+                    kind.as_str()
+                ))
+            })?;
+        (step.upgrade)(value);
+        version += 1;
+    }
+    Ok(version)
+}
+
+/// Deserializes a blob stored at `version`, upgrading it first when stale.
+pub fn decode_blob<T: DeserializeOwned>(
+    kind: BlobKind,
+    json: &str,
+    version: u32,
+) -> Result<T, StorageError> {
+    let serde_error = |err: serde_json::Error| StorageError::Serialization(err.to_string());
+    if version >= kind.current_version() {
+        return serde_json::from_str(json).map_err(serde_error);
+    }
+    let mut value: Value = serde_json::from_str(json).map_err(serde_error)?;
+    upgrade_blob(kind, version, &mut value)?;
+    serde_json::from_value(value).map_err(serde_error)
+}
+
+/// Payload v1 -> v2: tool results stored before `output_digest` existed get
+/// the digest of the output they still carry.
+fn fill_tool_output_digest(value: &mut Value) {
+    let Some(body) = value.as_object_mut() else {
+        return;
+    };
+    if body.get("kind").and_then(Value::as_str) != Some("tool_result")
+        || body.contains_key("output_digest")
+    {
+        return;
+    }
+    if let Some(output) = body.get("output").and_then(Value::as_str) {
+        let digest = tool_output_digest(output);
+        body.insert("output_digest".to_string(), Value::String(digest));
+    }
+}
+
+/// Tool meta v1 -> v2: legacy imports could omit `output_bytes` and
+/// `redacted`, which `ToolMetadataLine` requires.
+fn fill_required_tool_meta_fields(value: &mut Value) {
+    let Some(line) = value.as_object_mut() else {
+        return;
+    };
+    line.entry("output_bytes").or_insert(Value::from(0));
+    line.entry("redacted").or_insert(Value::Bool(false));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::mind_contracts::{RawEventBody, ToolMetadataLine};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn every_kind_has_an_upgrade_chain_to_its_current_version() {
+        for kind in BlobKind::ALL {
+            for from in 1..kind.current_version() {
+                let steps = BLOB_UPGRADES
+                    .iter()
+                    .filter(|step| step.kind == kind && step.from == from)
+                    .count();
+                assert_eq!(steps, 1, "{} from v{from}", kind.as_str());
+            }
+        }
+        assert!(BLOB_UPGRADES
+            .iter()
+            .all(|step| step.from >= 1 && step.from < step.kind.current_version()));
+    }
+
+    #[test]
+    fn v1_fixtures_still_deserialize() {
+        let payload: RawEventBody = decode_blob(
+            BlobKind::RawPayload,
+            r#"{"kind":"tool_result","tool_name":"bash","status":"success","latency_ms":12,"exit_code":0,"output":"ok"}"#,
+            1,
+        )
+        .expect("v1 tool result");
+        let RawEventBody::ToolResult(tool) = payload else {
+            panic!("expected tool result");
+        };
+        assert_eq!(tool.output_digest, Some(tool_output_digest("ok")));
+        assert!(!tool.redacted);
+
+        let message: RawEventBody = decode_blob(
+            BlobKind::RawPayload,
+            r#"{"kind":"message","role":"user","text":"hello"}"#,
+            1,
+        )
+        .expect("v1 message");
+        assert!(matches!(message, RawEventBody::Message(_)));
+
+        let attrs: BTreeMap<String, Value> =
+            decode_blob(BlobKind::RawAttrs, r#"{"session_id":"s-1"}"#, 1).expect("v1 attrs");
+        assert_eq!(attrs["session_id"], "s-1");
+
+        let line: ToolMetadataLine = decode_blob(
+            BlobKind::ToolMeta,
+            r#"{"tool_name":"read","status":"failure","latency_ms":null,"exit_code":1}"#,
+            1,
+        )
+        .expect("v1 tool meta");
+        assert_eq!(line.output_bytes, 0);
+        assert!(!line.redacted);
+        assert!(line.touched_paths.is_empty());
+    }
+
+    #[test]
+    fn upgrades_are_idempotent_and_keep_existing_fields() {
+        let mut line = serde_json::json!({
+            "tool_name": "edit",
+            "status": "success",
+            "latency_ms": 4,
+            "exit_code": 0,
+            "output_bytes": 90,
+            "redacted": true,
+        });
+        let before = line.clone();
+        assert_eq!(
+            upgrade_blob(BlobKind::ToolMeta, 1, &mut line).expect("upgrade"),
+            2
+        );
+        assert_eq!(line, before);
+
+        let mut payload = serde_json::json!({
+            "kind": "tool_result",
+            "tool_name": "bash",
+            "status": "success",
+            "output": "ok",
+            "output_digest": "kept",
+        });
+        let before = payload.clone();
+        upgrade_blob(BlobKind::RawPayload, 1, &mut payload).expect("upgrade");
+        assert_eq!(payload, before);
+
+        // Blobs from a newer build decode without upgrades.
+        assert_eq!(
+            upgrade_blob(BlobKind::RawAttrs, 7, &mut Value::Null).expect("newer"),
+            7
+        );
+    }
+}
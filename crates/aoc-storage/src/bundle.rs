@@ -0,0 +1,355 @@
+//! Portable memory bundles.
+//!
+//! [`MindStore::export_bundle`] writes a project's memory as JSONL: a header
+//! line naming the format, bundle version, and schema version, then one
+//! `{"table", "row"}` line per stored row, parents before the rows that
+//! reference them. [`MindStore::import_bundle`] replays it with
+//! `INSERT OR IGNORE`, so importing the same bundle twice adds nothing.
+//!
+//! Bundles carry what a project remembers (raw events, T0/T1/T2 artifacts,
+//! routes, task and file links, provenance), not machine-local state such as
+//! checkpoints, job queues, usage, or embeddings.
+
+use crate::{
+    conversation_in_project, AuditEntryKind, MindStore, StorageError, DEFAULT_PROJECT_ID,
+    MIND_SCHEMA_VERSION,
+};
+use aoc_core::mind_contracts::text_contains_unredacted_secret;
+use chrono::{DateTime, Utc};
+use rusqlite::params_from_iter;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+pub const BUNDLE_FORMAT: &str = "aoc-mind-bundle";
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Tables a bundle carries, in insert order.
+const BUNDLE_TABLES: &[&str] = &[
+    "conversation_projects",
+    "conversation_lineage",
+    "conversation_context_state",
+    "raw_events",
+    "compact_events_t0",
+    "compaction_slices_t0",
+    "observations_t1",
+    "reflections_t2",
+    "observation_sections",
+    "segment_route_overrides",
+    "segment_routes",
+    "artifact_task_links",
+    "artifact_file_links",
+    "semantic_runtime_provenance",
+];
+
+/// Narrows [`MindStore::export_bundle`]. Empty `conversation_ids` means every
+/// conversation of the store's project.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BundleFilter {
+    pub conversation_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleHeader {
+    pub format: String,
+    pub version: u32,
+    pub schema_version: i64,
+    pub exported_at: DateTime<Utc>,
+    pub conversation_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BundleReport {
+    pub conversation_ids: Vec<String>,
+    /// Rows written (export) or inserted (import) per table; tables with none
+    /// are omitted.
+    pub rows_by_table: BTreeMap<String, usize>,
+    /// Import only: rows already present in the store.
+    pub rows_skipped: usize,
+}
+
+impl BundleReport {
+    pub fn rows(&self) -> usize {
+        self.rows_by_table.values().sum()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BundleLine {
+    table: String,
+    row: serde_json::Map<String, serde_json::Value>,
+}
+
+impl MindStore {
+    /// Writes the rows of `filter`'s conversations to a new JSONL bundle at
+    /// `path`. Refuses to overwrite an existing file; the bundle is written
+    /// under a `.partial` name and renamed once complete.
+    pub fn export_bundle(
+        &self,
+        path: impl AsRef<Path>,
+        filter: &BundleFilter,
+    ) -> Result<BundleReport, StorageError> {
+        self.export_bundle_in(DEFAULT_PROJECT_ID, path, filter)
+    }
+
+    pub(crate) fn export_bundle_in(
+        &self,
+        project_id: &str,
+        path: impl AsRef<Path>,
+        filter: &BundleFilter,
+    ) -> Result<BundleReport, StorageError> {
+        let path = path.as_ref();
+        if path.exists() {
+            return Err(StorageError::Serialization(format!(
+                "bundle target {} already exists",
+                path.display()
+            )));
+        }
+        let conversation_ids = if filter.conversation_ids.is_empty() {
+            self.bundle_conversation_ids(project_id)?
+        } else {
+            filter.conversation_ids.clone()
+        };
+
+        let mut rows = self
+            .subject_rows(&conversation_ids)?
+            .into_iter()
+            .filter_map(|row| {
+                let order = BUNDLE_TABLES.iter().position(|table| *table == row.table)?;
+                Some((order, row))
+            })
+            .collect::<Vec<_>>();
+        rows.sort_by_key(|(order, _)| *order);
+
+        let header = BundleHeader {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            schema_version: MIND_SCHEMA_VERSION,
+            exported_at: Utc::now(),
+            conversation_ids: conversation_ids.clone(),
+        };
+        let mut report = BundleReport {
+            conversation_ids,
+            ..BundleReport::default()
+        };
+
+        let partial = path.with_extension("jsonl.partial");
+        let file = fs::File::create(&partial).map_err(|err| io_error(&partial, err))?;
+        let mut writer = BufWriter::new(file);
+        write_line(&mut writer, &header, &partial)?;
+        for (_, row) in rows {
+            *report.rows_by_table.entry(row.table.clone()).or_insert(0) += 1;
+            let line = BundleLine {
+                table: row.table,
+                row: row.row,
+            };
+            write_line(&mut writer, &line, &partial)?;
+        }
+        writer.flush().map_err(|err| io_error(&partial, err))?;
+        drop(writer);
+        fs::rename(&partial, path).map_err(|err| io_error(path, err))?;
+        Ok(report)
+    }
+
+    /// Inserts the rows of the bundle at `path`, keeping rows the store
+    /// already has. All or nothing: an unknown table or column, a row with
+    /// unredacted secrets, or a bundle from a newer schema rolls back the
+    /// whole import.
+    pub fn import_bundle(&self, path: impl AsRef<Path>) -> Result<BundleReport, StorageError> {
+        let path = path.as_ref();
+        let file = fs::File::open(path).map_err(|err| io_error(path, err))?;
+        let mut lines = BufReader::new(file).lines();
+        let header = match lines.next() {
+            Some(line) => {
+                serde_json::from_str::<BundleHeader>(&line.map_err(|err| io_error(path, err))?)
+                    .map_err(|err| {
+                        StorageError::Serialization(format!(
+                            "bundle {} header: {err}",
+                            path.display()
+                        ))
+                    })?
+            }
+            None => {
+                return Err(StorageError::Serialization(format!(
+                    "bundle {} is empty",
+                    path.display()
+                )))
+            }
+        };
+        if header.format != BUNDLE_FORMAT || header.version != BUNDLE_VERSION {
+            return Err(StorageError::Serialization(format!(
+                "unsupported bundle {} v{}",
+                header.format, header.version
+            )));
+        }
+        if header.schema_version > MIND_SCHEMA_VERSION {
+            return Err(StorageError::Serialization(format!(
+                "bundle schema v{} is newer than this store (v{MIND_SCHEMA_VERSION})",
+                header.schema_version
+            )));
+        }
+
+        let mut report = BundleReport {
+            conversation_ids: header.conversation_ids,
+            ..BundleReport::default()
+        };
+        self.conn.execute_batch("SAVEPOINT bundle_import")?;
+        let result = (|| {
+            let mut columns_by_table = HashMap::new();
+            let mut observations = Vec::new();
+            let mut reflections = Vec::new();
+            for (index, line) in lines.enumerate() {
+                let line = line.map_err(|err| io_error(path, err))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let line: BundleLine = serde_json::from_str(&line).map_err(|err| {
+                    StorageError::Serialization(format!(
+                        "bundle {} line {}: {err}",
+                        path.display(),
+                        index + 2
+                    ))
+                })?;
+                if !columns_by_table.contains_key(&line.table) {
+                    let columns = self.bundle_table_columns(&line.table)?;
+                    columns_by_table.insert(line.table.clone(), columns);
+                }
+                let known = &columns_by_table[&line.table];
+                if self.import_bundle_row(&line, known)? {
+                    *report.rows_by_table.entry(line.table.clone()).or_insert(0) += 1;
+                    let artifact_id = line.row.get("artifact_id").and_then(|id| id.as_str());
+                    match (line.table.as_str(), artifact_id) {
+                        ("observations_t1", Some(id)) => observations.push(id.to_string()),
+                        ("reflections_t2", Some(id)) => reflections.push(id.to_string()),
+                        _ => {}
+                    }
+                } else {
+                    report.rows_skipped += 1;
+                }
+            }
+            let now = Utc::now();
+            self.chain_audit_entries(AuditEntryKind::Observation, &observations, now)?;
+            self.chain_audit_entries(AuditEntryKind::Reflection, &reflections, now)?;
+            Ok(())
+        })();
+        match result {
+            Ok(()) => {
+                self.conn.execute_batch("RELEASE bundle_import")?;
+                Ok(report)
+            }
+            Err(err) => {
+                self.conn
+                    .execute_batch("ROLLBACK TO bundle_import; RELEASE bundle_import")?;
+                Err(err)
+            }
+        }
+    }
+
+    /// Every conversation of the store's project with raw events,
+    /// artifacts, or lineage.
+    fn bundle_conversation_ids(&self, project_id: &str) -> Result<Vec<String>, StorageError> {
+        let raw_events = self.raw_events_source()?;
+        let mut statement = self.conn.prepare(&format!(
+            "
+            SELECT conversation_id FROM (
+                SELECT conversation_id FROM {raw_events}
+                UNION SELECT conversation_id FROM compact_events_t0
+                UNION SELECT conversation_id FROM observations_t1
+                UNION SELECT conversation_id FROM reflections_t2
+                UNION SELECT conversation_id FROM conversation_lineage
+            ) AS conversation
+            WHERE {}
+            ORDER BY conversation_id ASC
+            ",
+            conversation_in_project("conversation.conversation_id", 1)
+        ))?;
+        let ids = statement
+            .query_map([project_id], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ids)
+    }
+
+    fn bundle_table_columns(&self, table: &str) -> Result<BTreeSet<String>, StorageError> {
+        if !BUNDLE_TABLES.contains(&table) {
+            return Err(StorageError::Serialization(format!(
+                "bundle table {table} is not importable"
+            )));
+        }
+        let mut statement = self
+            .conn
+            .prepare("SELECT name FROM pragma_table_info(?1)")?;
+        let columns = statement
+            .query_map([table], |row| row.get::<_, String>(0))?
+            .collect::<Result<BTreeSet<_>, _>>()?;
+        Ok(columns)
+    }
+
+    /// Inserts one bundle row; `false` when the store already had it.
+    fn import_bundle_row(
+        &self,
+        line: &BundleLine,
+        known: &BTreeSet<String>,
+    ) -> Result<bool, StorageError> {
+        for (column, value) in &line.row {
+            if !known.contains(column) {
+                return Err(StorageError::Serialization(format!(
+                    "bundle column {}.{column} does not exist",
+                    line.table
+                )));
+            }
+            if value.as_str().is_some_and(text_contains_unredacted_secret) {
+                return Err(StorageError::SecurityViolation(format!(
+                    "bundle {}.{column} contains unredacted secret-bearing content",
+                    line.table
+                )));
+            }
+        }
+
+        let table = if line.table == "raw_events" {
+            let event_id = line.row.get("event_id").and_then(|id| id.as_str());
+            let ts = line.row.get("ts").and_then(|ts| ts.as_str());
+            let (Some(event_id), Some(ts)) = (event_id, ts) else {
+                return Err(StorageError::Serialization(
+                    "bundle raw event lacks event_id or ts".to_string(),
+                ));
+            };
+            // Partitions only dedup their own month.
+            if self.has_raw_event(event_id)? {
+                return Ok(false);
+            }
+            self.raw_event_insert_table(crate::parse_timestamp(ts.to_string())?, Utc::now())?
+        } else {
+            line.table.clone()
+        };
+        let columns = line.row.keys().cloned().collect::<Vec<_>>();
+        let values = line.row.values().cloned().collect::<Vec<_>>();
+        let placeholders = (1..=columns.len())
+            .map(|index| format!("?{index}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let inserted = self.conn.execute(
+            &format!(
+                "INSERT OR IGNORE INTO {table} ({}) VALUES ({placeholders})",
+                columns.join(", ")
+            ),
+            params_from_iter(crate::sql_values(&values)?),
+        )?;
+        Ok(inserted > 0)
+    }
+}
+
+fn write_line(
+    writer: &mut impl Write,
+    value: &impl Serialize,
+    path: &Path,
+) -> Result<(), StorageError> {
+    serde_json::to_writer(&mut *writer, value)
+        .map_err(|err| StorageError::Serialization(err.to_string()))?;
+    writer.write_all(b"\n").map_err(|err| io_error(path, err))
+}
+
+fn io_error(path: &Path, err: std::io::Error) -> StorageError {
+    StorageError::Serialization(format!("bundle {}: {err}", path.display()))
+}
@@ -0,0 +1,117 @@
+//! Connection settings for stores shared between processes.
+//!
+//! The ingestor, reflector worker, daemon, and cockpit UI each open their own
+//! connection to the same file. WAL lets readers keep reading while one
+//! writer commits, and `busy_timeout` makes SQLite wait for a competing
+//! writer instead of failing at once. A write can still get `SQLITE_BUSY`
+//! when the timeout runs out, or immediately when a read transaction has to
+//! upgrade after another process committed (WAL never waits there), so the
+//! hot write paths run through [`MindStore::with_busy_retry`].
+
+use crate::{MindStore, StorageError};
+use aoc_core::{ErrorCode, ErrorCoded};
+use rusqlite::Connection;
+use std::time::Duration;
+
+/// How long one statement waits on a locked database before `SQLITE_BUSY`.
+pub const DEFAULT_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Extra attempts [`MindStore::with_busy_retry`] makes after a busy failure.
+pub const DEFAULT_BUSY_RETRIES: u32 = 3;
+/// Pause before the first retry; each later retry waits one step longer.
+pub const DEFAULT_BUSY_BACKOFF: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MindStoreOptions {
+    /// Switch file-backed stores to `journal_mode=WAL`. The mode is stored
+    /// in the file, so every later connection uses it too.
+    pub wal: bool,
+    pub busy_timeout: Duration,
+    pub busy_retries: u32,
+    pub busy_backoff: Duration,
+}
+
+impl Default for MindStoreOptions {
+    fn default() -> Self {
+        Self {
+            wal: true,
+            busy_timeout: DEFAULT_BUSY_TIMEOUT,
+            busy_retries: DEFAULT_BUSY_RETRIES,
+            busy_backoff: DEFAULT_BUSY_BACKOFF,
+        }
+    }
+}
+
+impl MindStoreOptions {
+    /// Read-only connections can't change the journal mode, so they only
+    /// get the busy timeout.
+    pub(crate) fn apply(&self, conn: &Connection, read_only: bool) -> Result<(), StorageError> {
+        conn.busy_timeout(self.busy_timeout)?;
+        if self.wal && !read_only {
+            // In-memory databases answer "memory" and stay that way.
+            conn.query_row("PRAGMA journal_mode = WAL", [], |row| {
+                row.get::<_, String>(0)
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl MindStore {
+    pub fn options(&self) -> &MindStoreOptions {
+        &self.options
+    }
+
+    /// `wal`, `delete`, `memory`, ... as SQLite reports it.
+    pub fn journal_mode(&self) -> Result<String, StorageError> {
+        Ok(self
+            .conn
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))?)
+    }
+
+    /// Runs `write`, repeating it up to `busy_retries` more times while it
+    /// fails with [`ErrorCode::StorageBusy`]. `write` must be safe to run
+    /// again after it failed: single statements, upserts, and
+    /// savepoint-wrapped steps are, since a busy statement changes nothing.
+    pub fn with_busy_retry<T>(
+        &self,
+        mut write: impl FnMut() -> Result<T, StorageError>,
+    ) -> Result<T, StorageError> {
+        let mut attempt = 0;
+        loop {
+            match write() {
+                Err(err)
+                    if err.error_code() == ErrorCode::StorageBusy
+                        && attempt < self.options.busy_retries =>
+                {
+                    attempt += 1;
+                    tracing::debug!(attempt, "mind store busy; retrying write");
+                    std::thread::sleep(self.options.busy_backoff * attempt);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// [`Self::with_busy_retry`] for writes of several statements: each
+    /// attempt runs in its own savepoint and is rolled back on failure, so a
+    /// retry never sees half of the previous attempt. Outside a transaction
+    /// the rollback also drops the stale read snapshot that made WAL refuse
+    /// the write.
+    pub(crate) fn retry_in_savepoint<T>(
+        &self,
+        mut write: impl FnMut() -> Result<T, StorageError>,
+    ) -> Result<T, StorageError> {
+        self.with_busy_retry(|| {
+            self.conn.execute_batch("SAVEPOINT busy_retry")?;
+            let result = write().and_then(|value| {
+                self.conn.execute_batch("RELEASE busy_retry")?;
+                Ok(value)
+            });
+            if result.is_err() {
+                self.conn
+                    .execute_batch("ROLLBACK TO busy_retry; RELEASE busy_retry")?;
+            }
+            result
+        })
+    }
+}
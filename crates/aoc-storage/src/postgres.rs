@@ -0,0 +1,805 @@
+//! [`MindStoreBackend`] over a shared Postgres (feature `postgres`).
+//!
+//! [`PostgresMindStore`] keeps the SQLite semantics of the runtime surface on
+//! a database several hosts can reach: the same secret checks, project
+//! claims, idempotent upserts, and lease and job transitions, with claims
+//! taken by `FOR UPDATE SKIP LOCKED` so concurrent reflectors never share a
+//! job. The schema in `migrations/postgres` ports the backend's tables at the
+//! matching [`MIND_SCHEMA_VERSION`]; local-only SQLite features (FTS, sync,
+//! partitions, the audit chain) stay on [`MindStore`](crate::MindStore).
+//!
+//! Calls block on a private current-thread runtime, so they must not be made
+//! from inside another tokio runtime.
+
+use crate::{
+    decode_blob, ensure_no_secrets_in_optional_text, ensure_no_secrets_in_text,
+    parse_reflector_job_status, parse_role, raw_event_kind, reflector_job_payload,
+    reflector_job_status_as_str, role_as_str, BlobKind, MindStoreBackend, ReflectorJob,
+    ReflectorJobStatus, StorageError, StoredCompactEvent, DEFAULT_PROJECT_ID, MIND_SCHEMA_VERSION,
+};
+use aoc_core::mind_contracts::{
+    parse_conversation_lineage_metadata, raw_event_contains_unredacted_secret, HashVersion,
+    RawEvent, T0CompactEvent,
+};
+use chrono::{DateTime, Utc};
+use sqlx::postgres::{PgPool, PgPoolOptions, PgRow};
+use sqlx::{PgConnection, Row};
+
+/// Ported schema steps, applied in order by [`PostgresMindStore::migrate`].
+const POSTGRES_MIGRATIONS: &[(i64, &str)] = &[
+    (
+        33,
+        include_str!("../migrations/postgres/0033_mind_backend.sql"),
+    ),
+    (
+        34,
+        include_str!("../migrations/postgres/0034_semantic_redactions.sql"),
+    ),
+    (
+        35,
+        include_str!("../migrations/postgres/0035_semantic_cache.sql"),
+    ),
+    (
+        36,
+        include_str!("../migrations/postgres/0036_semantic_budget_ledger.sql"),
+    ),
+];
+
+const DEFAULT_MAX_CONNECTIONS: u32 = 4;
+
+pub struct PostgresMindStore {
+    pool: PgPool,
+    runtime: tokio::runtime::Runtime,
+    project_id: String,
+}
+
+impl From<sqlx::Error> for StorageError {
+    fn from(err: sqlx::Error) -> Self {
+        Self::Postgres(err.to_string())
+    }
+}
+
+impl PostgresMindStore {
+    /// Connects to `url` (e.g. `postgres://aoc@db/mind`) and applies pending
+    /// schema steps.
+    pub fn connect(url: &str) -> Result<Self, StorageError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| StorageError::Postgres(err.to_string()))?;
+        let pool = runtime.block_on(
+            PgPoolOptions::new()
+                .max_connections(DEFAULT_MAX_CONNECTIONS)
+                .connect(url),
+        )?;
+        let store = Self {
+            pool,
+            runtime,
+            project_id: DEFAULT_PROJECT_ID.to_string(),
+        };
+        store.migrate()?;
+        Ok(store)
+    }
+
+    /// Reads and writes `project_id` instead of the default project, the
+    /// counterpart of [`MindStore::project_scope`](crate::MindStore::project_scope).
+    pub fn with_project(mut self, project_id: &str) -> Self {
+        self.project_id = project_id.trim().to_string();
+        self
+    }
+
+    pub fn project_id(&self) -> &str {
+        &self.project_id
+    }
+
+    pub fn migrate(&self) -> Result<(), StorageError> {
+        let current = MindStoreBackend::schema_version(self)?;
+        if current > MIND_SCHEMA_VERSION {
+            return Err(StorageError::UnsupportedSchemaVersion {
+                found: current,
+                supported: MIND_SCHEMA_VERSION,
+            });
+        }
+        for (version, sql) in POSTGRES_MIGRATIONS {
+            if current < *version {
+                tracing::info!(to = version, "applying postgres mind schema");
+                self.runtime
+                    .block_on(sqlx::raw_sql(sql).execute(&self.pool))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Project a write for `conversation_id` lands in; see
+    /// `MindStore::claim_conversation_project`.
+    async fn claim_conversation_project(
+        &self,
+        conn: &mut PgConnection,
+        conversation_id: &str,
+    ) -> Result<String, StorageError> {
+        let owner_query = "SELECT project_id FROM conversation_projects WHERE conversation_id = $1";
+        let owner: Option<String> = sqlx::query_scalar(owner_query)
+            .bind(conversation_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+        let owner = match owner {
+            Some(owner) => owner,
+            None if self.project_id == DEFAULT_PROJECT_ID => DEFAULT_PROJECT_ID.to_string(),
+            None => {
+                let has_rows: bool = sqlx::query_scalar(
+                    "
+                    SELECT EXISTS (SELECT 1 FROM raw_events WHERE conversation_id = $1)
+                        OR EXISTS (SELECT 1 FROM compact_events_t0 WHERE conversation_id = $1)
+                        OR EXISTS (SELECT 1 FROM observations_t1 WHERE conversation_id = $1)
+                        OR EXISTS (SELECT 1 FROM reflections_t2 WHERE conversation_id = $1)
+                    ",
+                )
+                .bind(conversation_id)
+                .fetch_one(&mut *conn)
+                .await?;
+                if has_rows {
+                    DEFAULT_PROJECT_ID.to_string()
+                } else {
+                    sqlx::query(
+                        "
+                        INSERT INTO conversation_projects (conversation_id, project_id)
+                        VALUES ($1, $2)
+                        ON CONFLICT (conversation_id) DO NOTHING
+                        ",
+                    )
+                    .bind(conversation_id)
+                    .bind(&self.project_id)
+                    .execute(&mut *conn)
+                    .await?;
+                    sqlx::query_scalar(owner_query)
+                        .bind(conversation_id)
+                        .fetch_one(&mut *conn)
+                        .await?
+                }
+            }
+        };
+        if owner == self.project_id || self.project_id == DEFAULT_PROJECT_ID {
+            Ok(owner)
+        } else {
+            Err(StorageError::SecurityViolation(format!(
+                "conversation {conversation_id} belongs to project {owner:?}, not {:?}",
+                self.project_id
+            )))
+        }
+    }
+
+    async fn reflector_lease_holder(
+        &self,
+        scope_id: &str,
+    ) -> Result<Option<(String, DateTime<Utc>)>, StorageError> {
+        let row = sqlx::query(
+            "SELECT owner_id, expires_at FROM reflector_runtime_leases WHERE scope_id = $1",
+        )
+        .bind(scope_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        row.map(|row| Ok((row.try_get(0)?, row.try_get(1)?)))
+            .transpose()
+    }
+}
+
+impl MindStoreBackend for PostgresMindStore {
+    fn schema_version(&self) -> Result<i64, StorageError> {
+        self.runtime.block_on(async {
+            let exists: bool =
+                sqlx::query_scalar("SELECT to_regclass('mind_schema_migrations') IS NOT NULL")
+                    .fetch_one(&self.pool)
+                    .await?;
+            if !exists {
+                return Ok(0);
+            }
+            let version: Option<i64> =
+                sqlx::query_scalar("SELECT MAX(version) FROM mind_schema_migrations")
+                    .fetch_one(&self.pool)
+                    .await?;
+            Ok(version.unwrap_or(0))
+        })
+    }
+
+    fn insert_raw_event(&self, event: &RawEvent) -> Result<bool, StorageError> {
+        if raw_event_contains_unredacted_secret(event) {
+            return Err(StorageError::SecurityViolation(
+                "raw event contains unredacted secret-bearing content".to_string(),
+            ));
+        }
+        let payload_json = serde_json::to_string(&event.body)
+            .map_err(|err| StorageError::Serialization(err.to_string()))?;
+        let attrs_json = serde_json::to_string(&event.attrs)
+            .map_err(|err| StorageError::Serialization(err.to_string()))?;
+        let lineage = parse_conversation_lineage_metadata(
+            &event.attrs,
+            &event.conversation_id,
+            &event.agent_id,
+        )
+        .map_err(|err| StorageError::Serialization(err.to_string()))?;
+
+        self.runtime.block_on(async {
+            let mut tx = self.pool.begin().await?;
+            self.claim_conversation_project(&mut tx, &event.conversation_id)
+                .await?;
+            let inserted = sqlx::query(
+                "
+                INSERT INTO raw_events (
+                    event_id, conversation_id, agent_id, ts, kind, payload_json, attrs_json,
+                    payload_schema_version, attrs_schema_version
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                ON CONFLICT (event_id) DO NOTHING
+                ",
+            )
+            .bind(&event.event_id)
+            .bind(&event.conversation_id)
+            .bind(&event.agent_id)
+            .bind(event.ts)
+            .bind(raw_event_kind(&event.body))
+            .bind(payload_json)
+            .bind(attrs_json)
+            .bind(i64::from(BlobKind::RawPayload.current_version()))
+            .bind(i64::from(BlobKind::RawAttrs.current_version()))
+            .execute(&mut *tx)
+            .await?
+            .rows_affected()
+                > 0;
+
+            if let (true, Some(lineage)) = (inserted, lineage) {
+                sqlx::query(
+                    "
+                    INSERT INTO conversation_lineage (
+                        conversation_id, session_id, parent_conversation_id,
+                        root_conversation_id, updated_at
+                    ) VALUES ($1, $2, $3, $4, $5)
+                    ON CONFLICT (conversation_id) DO UPDATE SET
+                        session_id = excluded.session_id,
+                        parent_conversation_id = excluded.parent_conversation_id,
+                        root_conversation_id = excluded.root_conversation_id,
+                        updated_at = excluded.updated_at
+                    ",
+                )
+                .bind(&event.conversation_id)
+                .bind(lineage.session_id)
+                .bind(lineage.parent_conversation_id)
+                .bind(lineage.root_conversation_id)
+                .bind(event.ts)
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+            Ok(inserted)
+        })
+    }
+
+    fn upsert_t0_compact_event(&self, event: &T0CompactEvent) -> Result<(), StorageError> {
+        ensure_no_secrets_in_optional_text(event.text.as_deref(), "compact_events_t0.text")?;
+        ensure_no_secrets_in_optional_text(event.snippet.as_deref(), "compact_events_t0.snippet")?;
+        let source_event_ids_json = serde_json::to_string(&event.source_event_ids)
+            .map_err(|err| StorageError::Serialization(err.to_string()))?;
+        let tool_meta_json = event
+            .tool_meta
+            .as_ref()
+            .map(|tool_meta| {
+                serde_json::to_string(tool_meta)
+                    .map_err(|err| StorageError::Serialization(err.to_string()))
+            })
+            .transpose()?;
+        let compact_hash_v2 = event
+            .content_hash(HashVersion::V2)
+            .map_err(|err| StorageError::Serialization(err.to_string()))?;
+
+        self.runtime.block_on(async {
+            let mut tx = self.pool.begin().await?;
+            let project_id = self
+                .claim_conversation_project(&mut tx, &event.conversation_id)
+                .await?;
+            sqlx::query(
+                "
+                INSERT INTO compact_events_t0 (
+                    compact_id, compact_hash, compact_hash_v2, schema_version, conversation_id,
+                    ts, role, text, snippet, source_event_ids_json, tool_meta_json,
+                    tool_meta_schema_version, policy_version, project_id
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                ON CONFLICT (compact_id) DO UPDATE SET
+                    compact_hash = excluded.compact_hash,
+                    compact_hash_v2 = excluded.compact_hash_v2,
+                    schema_version = excluded.schema_version,
+                    conversation_id = excluded.conversation_id,
+                    ts = excluded.ts,
+                    role = excluded.role,
+                    text = excluded.text,
+                    snippet = excluded.snippet,
+                    source_event_ids_json = excluded.source_event_ids_json,
+                    tool_meta_json = excluded.tool_meta_json,
+                    tool_meta_schema_version = excluded.tool_meta_schema_version,
+                    policy_version = excluded.policy_version,
+                    project_id = excluded.project_id
+                ",
+            )
+            .bind(&event.compact_id)
+            .bind(&event.compact_hash)
+            .bind(compact_hash_v2)
+            .bind(i64::from(event.schema_version))
+            .bind(&event.conversation_id)
+            .bind(event.ts)
+            .bind(event.role.map(role_as_str))
+            .bind(&event.text)
+            .bind(&event.snippet)
+            .bind(source_event_ids_json)
+            .bind(tool_meta_json)
+            .bind(i64::from(BlobKind::ToolMeta.current_version()))
+            .bind(&event.policy_version)
+            .bind(project_id)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            Ok(())
+        })
+    }
+
+    fn t0_events_for_conversation(
+        &self,
+        conversation_id: &str,
+    ) -> Result<Vec<StoredCompactEvent>, StorageError> {
+        let rows = self.runtime.block_on(
+            sqlx::query(
+                "
+                SELECT compact_id, conversation_id, ts, role, text, tool_meta_json,
+                       source_event_ids_json, policy_version, tool_meta_schema_version
+                FROM compact_events_t0
+                WHERE conversation_id = $1
+                ORDER BY ts ASC, compact_id ASC
+                ",
+            )
+            .bind(conversation_id)
+            .fetch_all(&self.pool),
+        )?;
+        rows.iter().map(parse_compact_event_row).collect()
+    }
+
+    fn conversation_needs_observer_run(&self, conversation_id: &str) -> Result<bool, StorageError> {
+        let (latest_t0, latest_t1): (Option<DateTime<Utc>>, Option<DateTime<Utc>>) =
+            self.runtime.block_on(
+                sqlx::query_as(
+                    "
+                    SELECT
+                        (SELECT MAX(ts) FROM compact_events_t0 WHERE conversation_id = $1),
+                        (SELECT MAX(ts) FROM observations_t1 WHERE conversation_id = $1)
+                    ",
+                )
+                .bind(conversation_id)
+                .fetch_one(&self.pool),
+            )?;
+        Ok(match (latest_t0, latest_t1) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(t0), Some(t1)) => t0 > t1,
+        })
+    }
+
+    fn insert_observation(
+        &self,
+        artifact_id: &str,
+        conversation_id: &str,
+        ts: DateTime<Utc>,
+        text: &str,
+        trace_ids: &[String],
+    ) -> Result<(), StorageError> {
+        ensure_no_secrets_in_text(text, "observations_t1.text")?;
+        let trace_ids_json = serde_json::to_string(trace_ids)
+            .map_err(|err| StorageError::Serialization(err.to_string()))?;
+        self.runtime.block_on(async {
+            let mut tx = self.pool.begin().await?;
+            let project_id = self
+                .claim_conversation_project(&mut tx, conversation_id)
+                .await?;
+            sqlx::query(
+                "
+                INSERT INTO observations_t1 (
+                    artifact_id, conversation_id, ts, importance, text, trace_ids_json, project_id
+                ) VALUES ($1, $2, $3, 0, $4, $5, $6)
+                ON CONFLICT (artifact_id) DO UPDATE SET
+                    conversation_id = excluded.conversation_id,
+                    ts = excluded.ts,
+                    importance = excluded.importance,
+                    text = excluded.text,
+                    trace_ids_json = excluded.trace_ids_json,
+                    project_id = excluded.project_id
+                ",
+            )
+            .bind(artifact_id)
+            .bind(conversation_id)
+            .bind(ts)
+            .bind(text)
+            .bind(trace_ids_json)
+            .bind(project_id)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            Ok(())
+        })
+    }
+
+    fn insert_reflection(
+        &self,
+        artifact_id: &str,
+        conversation_id: &str,
+        ts: DateTime<Utc>,
+        text: &str,
+        trace_ids: &[String],
+    ) -> Result<(), StorageError> {
+        ensure_no_secrets_in_text(text, "reflections_t2.text")?;
+        let trace_ids_json = serde_json::to_string(trace_ids)
+            .map_err(|err| StorageError::Serialization(err.to_string()))?;
+        self.runtime.block_on(async {
+            let mut tx = self.pool.begin().await?;
+            let project_id = self
+                .claim_conversation_project(&mut tx, conversation_id)
+                .await?;
+            sqlx::query(
+                "
+                INSERT INTO reflections_t2 (
+                    artifact_id, conversation_id, ts, text, trace_ids_json, project_id
+                ) VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (artifact_id) DO UPDATE SET
+                    conversation_id = excluded.conversation_id,
+                    ts = excluded.ts,
+                    text = excluded.text,
+                    trace_ids_json = excluded.trace_ids_json,
+                    project_id = excluded.project_id
+                ",
+            )
+            .bind(artifact_id)
+            .bind(conversation_id)
+            .bind(ts)
+            .bind(text)
+            .bind(trace_ids_json)
+            .bind(project_id)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            Ok(())
+        })
+    }
+
+    fn try_acquire_reflector_lease(
+        &self,
+        scope_id: &str,
+        owner_id: &str,
+        owner_pid: Option<i64>,
+        now: DateTime<Utc>,
+        ttl_ms: u64,
+    ) -> Result<bool, StorageError> {
+        let result = self.runtime.block_on(
+            sqlx::query(
+                "
+                INSERT INTO reflector_runtime_leases (
+                    scope_id, owner_id, owner_pid, acquired_at, heartbeat_at, expires_at,
+                    metadata_json
+                ) VALUES ($1, $2, $3, $4, $4, $5, '{}')
+                ON CONFLICT (scope_id) DO UPDATE SET
+                    owner_id = excluded.owner_id,
+                    owner_pid = excluded.owner_pid,
+                    acquired_at = CASE
+                        WHEN reflector_runtime_leases.owner_id = excluded.owner_id
+                        THEN reflector_runtime_leases.acquired_at
+                        ELSE excluded.acquired_at
+                    END,
+                    heartbeat_at = excluded.heartbeat_at,
+                    expires_at = excluded.expires_at
+                WHERE reflector_runtime_leases.owner_id = excluded.owner_id
+                   OR reflector_runtime_leases.expires_at <= excluded.acquired_at
+                ",
+            )
+            .bind(scope_id)
+            .bind(owner_id)
+            .bind(owner_pid)
+            .bind(now)
+            .bind(lease_expiry(now, ttl_ms))
+            .execute(&self.pool),
+        )?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn heartbeat_reflector_lease(
+        &self,
+        scope_id: &str,
+        owner_id: &str,
+        now: DateTime<Utc>,
+        ttl_ms: u64,
+    ) -> Result<bool, StorageError> {
+        let result = self.runtime.block_on(
+            sqlx::query(
+                "
+                UPDATE reflector_runtime_leases
+                SET heartbeat_at = $3,
+                    expires_at = $4
+                WHERE scope_id = $1
+                  AND owner_id = $2
+                ",
+            )
+            .bind(scope_id)
+            .bind(owner_id)
+            .bind(now)
+            .bind(lease_expiry(now, ttl_ms))
+            .execute(&self.pool),
+        )?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn release_reflector_lease(&self, scope_id: &str, owner_id: &str) -> Result<(), StorageError> {
+        self.runtime.block_on(
+            sqlx::query(
+                "DELETE FROM reflector_runtime_leases WHERE scope_id = $1 AND owner_id = $2",
+            )
+            .bind(scope_id)
+            .bind(owner_id)
+            .execute(&self.pool),
+        )?;
+        Ok(())
+    }
+
+    fn enqueue_reflector_job(
+        &self,
+        active_tag: &str,
+        observation_ids: &[String],
+        conversation_ids: &[String],
+        estimated_tokens: u32,
+        now: DateTime<Utc>,
+    ) -> Result<String, StorageError> {
+        let (job_id, observation_ids, conversation_ids) =
+            reflector_job_payload(active_tag, observation_ids, conversation_ids)?;
+        let observation_ids_json = serde_json::to_string(&observation_ids)
+            .map_err(|err| StorageError::Serialization(err.to_string()))?;
+        let conversation_ids_json = serde_json::to_string(&conversation_ids)
+            .map_err(|err| StorageError::Serialization(err.to_string()))?;
+        self.runtime.block_on(
+            sqlx::query(
+                "
+                INSERT INTO reflector_jobs_t2 (
+                    job_id, active_tag, observation_ids_json, conversation_ids_json,
+                    estimated_tokens, status, attempts, created_at, updated_at
+                ) VALUES ($1, $2, $3, $4, $5, $6, 0, $7, $7)
+                ON CONFLICT (job_id) DO NOTHING
+                ",
+            )
+            .bind(&job_id)
+            .bind(active_tag)
+            .bind(observation_ids_json)
+            .bind(conversation_ids_json)
+            .bind(i64::from(estimated_tokens))
+            .bind(reflector_job_status_as_str(ReflectorJobStatus::Pending))
+            .bind(now)
+            .execute(&self.pool),
+        )?;
+        Ok(job_id)
+    }
+
+    fn claim_next_reflector_job(
+        &self,
+        scope_id: &str,
+        owner_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<ReflectorJob>, StorageError> {
+        self.runtime.block_on(async {
+            match self.reflector_lease_holder(scope_id).await? {
+                Some((holder, expires_at)) if holder == owner_id && expires_at >= now => {}
+                _ => return Ok(None),
+            }
+            let row = sqlx::query(
+                "
+                UPDATE reflector_jobs_t2
+                SET status = $3,
+                    claimed_by = $1,
+                    claimed_at = $2,
+                    attempts = attempts + 1,
+                    retry_after = NULL,
+                    updated_at = $2
+                WHERE job_id = (
+                    SELECT job_id
+                    FROM reflector_jobs_t2
+                    WHERE status = $4
+                      AND (retry_after IS NULL OR retry_after <= $2)
+                    ORDER BY created_at ASC, job_id ASC
+                    LIMIT 1
+                    FOR UPDATE SKIP LOCKED
+                )
+                RETURNING job_id, active_tag, observation_ids_json, conversation_ids_json,
+                          estimated_tokens, status, claimed_by, claimed_at, attempts,
+                          last_error, created_at, updated_at, retry_after
+                ",
+            )
+            .bind(owner_id)
+            .bind(now)
+            .bind(reflector_job_status_as_str(ReflectorJobStatus::Claimed))
+            .bind(reflector_job_status_as_str(ReflectorJobStatus::Pending))
+            .fetch_optional(&self.pool)
+            .await?;
+            row.as_ref().map(parse_reflector_job_row).transpose()
+        })
+    }
+
+    fn complete_reflector_job(
+        &self,
+        job_id: &str,
+        owner_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let result = self.runtime.block_on(
+            sqlx::query(
+                "
+                UPDATE reflector_jobs_t2
+                SET status = $4,
+                    updated_at = $3
+                WHERE job_id = $1
+                  AND claimed_by = $2
+                  AND status = $5
+                ",
+            )
+            .bind(job_id)
+            .bind(owner_id)
+            .bind(now)
+            .bind(reflector_job_status_as_str(ReflectorJobStatus::Completed))
+            .bind(reflector_job_status_as_str(ReflectorJobStatus::Claimed))
+            .execute(&self.pool),
+        )?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn fail_reflector_job(
+        &self,
+        job_id: &str,
+        owner_id: &str,
+        error: &str,
+        now: DateTime<Utc>,
+        requeue: bool,
+    ) -> Result<bool, StorageError> {
+        let (status, claimed_by, claimed_at) = if requeue {
+            (ReflectorJobStatus::Pending, None, None)
+        } else {
+            (ReflectorJobStatus::Failed, Some(owner_id), Some(now))
+        };
+        let result = self.runtime.block_on(
+            sqlx::query(
+                "
+                UPDATE reflector_jobs_t2
+                SET status = $4,
+                    claimed_by = $5,
+                    claimed_at = $6,
+                    last_error = $3,
+                    updated_at = $2
+                WHERE job_id = $1
+                  AND status = $7
+                  AND claimed_by = $8
+                ",
+            )
+            .bind(job_id)
+            .bind(now)
+            .bind(error)
+            .bind(reflector_job_status_as_str(status))
+            .bind(claimed_by)
+            .bind(claimed_at)
+            .bind(reflector_job_status_as_str(ReflectorJobStatus::Claimed))
+            .bind(owner_id)
+            .execute(&self.pool),
+        )?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn retry_reflector_job(
+        &self,
+        job_id: &str,
+        owner_id: &str,
+        error: &str,
+        now: DateTime<Utc>,
+        retry_after: DateTime<Utc>,
+    ) -> Result<bool, StorageError> {
+        let result = self.runtime.block_on(
+            sqlx::query(
+                "
+                UPDATE reflector_jobs_t2
+                SET status = $4,
+                    claimed_by = NULL,
+                    claimed_at = NULL,
+                    last_error = $3,
+                    retry_after = $5,
+                    updated_at = $2
+                WHERE job_id = $1
+                  AND status = $6
+                  AND claimed_by = $7
+                ",
+            )
+            .bind(job_id)
+            .bind(now)
+            .bind(error)
+            .bind(reflector_job_status_as_str(ReflectorJobStatus::Pending))
+            .bind(retry_after)
+            .bind(reflector_job_status_as_str(ReflectorJobStatus::Claimed))
+            .bind(owner_id)
+            .execute(&self.pool),
+        )?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn lease_expiry(now: DateTime<Utc>, ttl_ms: u64) -> DateTime<Utc> {
+    now + chrono::Duration::milliseconds(ttl_ms.min(i64::MAX as u64) as i64)
+}
+
+fn parse_compact_event_row(row: &PgRow) -> Result<StoredCompactEvent, StorageError> {
+    let tool_meta = row
+        .try_get::<Option<String>, _>("tool_meta_json")?
+        .map(|json| {
+            let version: i64 = row.try_get("tool_meta_schema_version")?;
+            decode_blob(BlobKind::ToolMeta, &json, version as u32)
+        })
+        .transpose()?;
+    let source_event_ids_json: String = row.try_get("source_event_ids_json")?;
+    let mut source_event_ids: Vec<String> = serde_json::from_str(&source_event_ids_json)
+        .map_err(|err| StorageError::Serialization(err.to_string()))?;
+    source_event_ids.sort();
+    source_event_ids.dedup();
+
+    Ok(StoredCompactEvent {
+        compact_id: row.try_get("compact_id")?,
+        conversation_id: row.try_get("conversation_id")?,
+        ts: row.try_get("ts")?,
+        role: row
+            .try_get::<Option<String>, _>("role")?
+            .and_then(|value| parse_role(&value)),
+        text: row.try_get("text")?,
+        tool_meta,
+        source_event_ids,
+        policy_version: row.try_get("policy_version")?,
+    })
+}
+
+fn parse_reflector_job_row(row: &PgRow) -> Result<ReflectorJob, StorageError> {
+    let ids = |column: &str| -> Result<Vec<String>, StorageError> {
+        let json: String = row.try_get(column)?;
+        let mut ids: Vec<String> = serde_json::from_str(&json)
+            .map_err(|err| StorageError::Serialization(err.to_string()))?;
+        ids.sort();
+        ids.dedup();
+        Ok(ids)
+    };
+    let status: String = row.try_get("status")?;
+    Ok(ReflectorJob {
+        job_id: row.try_get("job_id")?,
+        active_tag: row.try_get("active_tag")?,
+        observation_ids: ids("observation_ids_json")?,
+        conversation_ids: ids("conversation_ids_json")?,
+        estimated_tokens: row.try_get::<i64, _>("estimated_tokens")?.max(0) as u32,
+        status: parse_reflector_job_status(&status).ok_or_else(|| {
+            StorageError::Serialization(format!("invalid reflector job status: {status}"))
+        })?,
+        claimed_by: row.try_get("claimed_by")?,
+        claimed_at: row.try_get("claimed_at")?,
+        attempts: row
+            .try_get::<i64, _>("attempts")?
+            .clamp(0, i64::from(u16::MAX)) as u16,
+        last_error: row.try_get("last_error")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+        retry_after: row.try_get("retry_after")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs against the database named by `AOC_TEST_POSTGRES_URL`; skipped
+    /// when it is unset.
+    #[test]
+    fn postgres_backend_matches_the_sqlite_contract() {
+        let Ok(url) = std::env::var("AOC_TEST_POSTGRES_URL") else {
+            return;
+        };
+        let store = PostgresMindStore::connect(&url).expect("connect");
+        store.migrate().expect("migrate twice");
+        let suffix = Utc::now().timestamp_nanos_opt().expect("nanos").to_string();
+        crate::backend::exercise_backend(&store, &suffix);
+    }
+}
@@ -0,0 +1,378 @@
+//! Project namespaces inside one store.
+//!
+//! Every conversation belongs to one project (`conversation_projects`), and
+//! T0/T1/T2 artifacts and canon revisions carry their `project_id`, as do
+//! project watermarks. A [`MindStore`] reads and writes the default project
+//! ([`DEFAULT_PROJECT_ID`]); [`MindStore::project_scope`] hands out a
+//! [`ProjectScope`] that carries another project id and passes it to every
+//! store-wide query (artifact listing, search and similarity, canon,
+//! watermarks, conversation listings) and write, which claims new
+//! conversations for it. Handles are independent values: any number of them,
+//! for any projects, can live next to the unscoped store. Reads keyed by a
+//! conversation or artifact id are not filtered, since the id already names
+//! one project; the handle derefs to the store for those.
+
+use crate::{
+    ArtifactPage, ArtifactQuery, ArtifactSearchHit, BundleFilter, BundleReport, CanonEntryRevision,
+    CanonRevisionState, ConversationLineage, MindStore, MindStoreStats, ProjectWatermark,
+    SimilarArtifact, StorageError,
+};
+use aoc_core::mind_contracts::{RawEvent, T0CompactEvent};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use std::ops::Deref;
+use std::path::Path;
+
+/// Project of unscoped stores and of everything stored before projects.
+pub const DEFAULT_PROJECT_ID: &str = "";
+
+/// A [`MindStore`] seen through one project. Its store-wide reads and its
+/// writes take the project from the handle; everything else derefs to the
+/// store.
+pub struct ProjectScope<'store> {
+    store: &'store MindStore,
+    project_id: String,
+}
+
+impl MindStore {
+    /// A handle on `project_id` within this store.
+    pub fn project_scope(&self, project_id: &str) -> Result<ProjectScope<'_>, StorageError> {
+        let project_id = project_id.trim();
+        if project_id.is_empty() {
+            return Err(StorageError::Serialization(
+                "project id must not be empty".to_string(),
+            ));
+        }
+        Ok(ProjectScope {
+            store: self,
+            project_id: project_id.to_string(),
+        })
+    }
+
+    /// Project owning `conversation_id`; `None` until something claims it.
+    pub fn conversation_project(
+        &self,
+        conversation_id: &str,
+    ) -> Result<Option<String>, StorageError> {
+        self.conn
+            .query_row(
+                "SELECT project_id FROM conversation_projects WHERE conversation_id = ?1",
+                [conversation_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(StorageError::from)
+    }
+
+    /// Every project with conversations or canon, default first.
+    pub fn project_ids(&self) -> Result<Vec<String>, StorageError> {
+        let mut statement = self.conn.prepare(
+            "
+            SELECT project_id FROM conversation_projects
+            UNION
+            SELECT project_id FROM project_canon_revisions
+            ORDER BY project_id ASC
+            ",
+        )?;
+        let ids = statement
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ids)
+    }
+
+    /// Moves `conversation_id` and its T0/T1/T2 rows into `project_id`,
+    /// e.g. to split conversations stored before projects out of the
+    /// default project. Returns whether the owner changed.
+    pub fn assign_conversation_project(
+        &self,
+        conversation_id: &str,
+        project_id: &str,
+    ) -> Result<bool, StorageError> {
+        let project_id = project_id.trim();
+        if self.conversation_project(conversation_id)?.as_deref() == Some(project_id) {
+            return Ok(false);
+        }
+        self.retry_in_savepoint(|| {
+            self.conn.execute(
+                "
+                INSERT INTO conversation_projects (conversation_id, project_id)
+                VALUES (?1, ?2)
+                ON CONFLICT(conversation_id) DO UPDATE SET project_id = excluded.project_id
+                ",
+                params![conversation_id, project_id],
+            )?;
+            for table in ["compact_events_t0", "observations_t1", "reflections_t2"] {
+                self.conn.execute(
+                    &format!(
+                        "UPDATE {table} SET project_id = ?2
+                         WHERE conversation_id = ?1 AND project_id <> ?2"
+                    ),
+                    params![conversation_id, project_id],
+                )?;
+            }
+            Ok(())
+        })?;
+        Ok(true)
+    }
+
+    /// Project a write for `conversation_id` from `project_id` lands in. An
+    /// unowned conversation is the default project's: an unscoped write
+    /// leaves it unowned, and a scoped write claims it only while nothing is
+    /// stored for it yet. A scoped write refuses another project's
+    /// conversation; an unscoped one follows the owner.
+    pub(crate) fn claim_conversation_project(
+        &self,
+        project_id: &str,
+        conversation_id: &str,
+    ) -> Result<String, StorageError> {
+        let owner = match self.conversation_project(conversation_id)? {
+            Some(owner) => Some(owner),
+            None if project_id == DEFAULT_PROJECT_ID => return Ok(DEFAULT_PROJECT_ID.to_string()),
+            None if self.conversation_has_rows(conversation_id)? => {
+                Some(DEFAULT_PROJECT_ID.to_string())
+            }
+            None => None,
+        };
+        match owner {
+            Some(owner) if owner == project_id || project_id == DEFAULT_PROJECT_ID => Ok(owner),
+            Some(owner) => Err(StorageError::SecurityViolation(format!(
+                "conversation {conversation_id} belongs to project {owner:?}, not {project_id:?}"
+            ))),
+            None => {
+                self.conn.execute(
+                    "
+                    INSERT OR IGNORE INTO conversation_projects (conversation_id, project_id)
+                    VALUES (?1, ?2)
+                    ",
+                    params![conversation_id, project_id],
+                )?;
+                Ok(project_id.to_string())
+            }
+        }
+    }
+
+    /// Whether any raw event or T0/T1/T2 row is stored for `conversation_id`.
+    fn conversation_has_rows(&self, conversation_id: &str) -> Result<bool, StorageError> {
+        let raw_events = self.raw_events_source()?;
+        let exists = self.conn.query_row(
+            &format!(
+                "
+                SELECT EXISTS (SELECT 1 FROM {raw_events} WHERE conversation_id = ?1)
+                    OR EXISTS (SELECT 1 FROM compact_events_t0 WHERE conversation_id = ?1)
+                    OR EXISTS (SELECT 1 FROM observations_t1 WHERE conversation_id = ?1)
+                    OR EXISTS (SELECT 1 FROM reflections_t2 WHERE conversation_id = ?1)
+                "
+            ),
+            [conversation_id],
+            |row| row.get(0),
+        )?;
+        Ok(exists)
+    }
+}
+
+impl ProjectScope<'_> {
+    pub fn project_id(&self) -> &str {
+        &self.project_id
+    }
+
+    pub fn insert_raw_event(&self, event: &RawEvent) -> Result<bool, StorageError> {
+        self.store.insert_raw_event_in(&self.project_id, event)
+    }
+
+    pub fn upsert_t0_compact_event(&self, event: &T0CompactEvent) -> Result<(), StorageError> {
+        self.store
+            .upsert_t0_compact_event_in(&self.project_id, event)
+    }
+
+    pub fn insert_observation(
+        &self,
+        artifact_id: &str,
+        conversation_id: &str,
+        ts: DateTime<Utc>,
+        text: &str,
+        trace_ids: &[String],
+    ) -> Result<(), StorageError> {
+        self.store.insert_observation_in(
+            &self.project_id,
+            artifact_id,
+            conversation_id,
+            ts,
+            text,
+            trace_ids,
+        )
+    }
+
+    pub fn insert_reflection(
+        &self,
+        artifact_id: &str,
+        conversation_id: &str,
+        ts: DateTime<Utc>,
+        text: &str,
+        trace_ids: &[String],
+    ) -> Result<(), StorageError> {
+        self.store.insert_reflection_in(
+            &self.project_id,
+            artifact_id,
+            conversation_id,
+            ts,
+            text,
+            trace_ids,
+        )
+    }
+
+    pub fn list_conversation_lineage(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<ConversationLineage>, StorageError> {
+        self.store
+            .list_conversation_lineage_in(&self.project_id, limit)
+    }
+
+    pub fn conversation_ids_for_session(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<String>, StorageError> {
+        self.store
+            .conversation_ids_for_session_in(&self.project_id, session_id)
+    }
+
+    pub fn query_artifacts(&self, query: &ArtifactQuery) -> Result<ArtifactPage, StorageError> {
+        self.store.query_artifacts_in(&self.project_id, query)
+    }
+
+    pub fn search_artifacts(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<ArtifactSearchHit>, StorageError> {
+        self.store
+            .search_artifacts_in(&self.project_id, query, limit)
+    }
+
+    pub fn similar_artifacts(
+        &self,
+        embedding: &[f32],
+        k: usize,
+    ) -> Result<Vec<SimilarArtifact>, StorageError> {
+        self.store
+            .similar_artifacts_in(&self.project_id, embedding, k)
+    }
+
+    pub fn project_watermark(
+        &self,
+        scope_key: &str,
+    ) -> Result<Option<ProjectWatermark>, StorageError> {
+        self.store.project_watermark_in(&self.project_id, scope_key)
+    }
+
+    pub fn advance_project_watermark(
+        &self,
+        scope_key: &str,
+        last_artifact_ts: Option<DateTime<Utc>>,
+        last_artifact_id: Option<&str>,
+        updated_at: DateTime<Utc>,
+    ) -> Result<(), StorageError> {
+        self.store.advance_project_watermark_in(
+            &self.project_id,
+            scope_key,
+            last_artifact_ts,
+            last_artifact_id,
+            updated_at,
+        )
+    }
+
+    pub fn latest_canon_revision(
+        &self,
+        entry_id: &str,
+    ) -> Result<Option<CanonEntryRevision>, StorageError> {
+        self.store
+            .latest_canon_revision_in(&self.project_id, entry_id)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn upsert_canon_entry_revision(
+        &self,
+        entry_id: &str,
+        topic: Option<&str>,
+        summary: &str,
+        confidence_bps: u16,
+        freshness_score: u16,
+        supersedes_entry_id: Option<&str>,
+        evidence_refs: &[String],
+        created_at: DateTime<Utc>,
+    ) -> Result<CanonEntryRevision, StorageError> {
+        self.store.upsert_canon_entry_revision_in(
+            &self.project_id,
+            entry_id,
+            topic,
+            summary,
+            confidence_bps,
+            freshness_score,
+            supersedes_entry_id,
+            evidence_refs,
+            created_at,
+        )
+    }
+
+    pub fn canon_entries_by_state(
+        &self,
+        state: CanonRevisionState,
+        topic: Option<&str>,
+    ) -> Result<Vec<CanonEntryRevision>, StorageError> {
+        self.store
+            .canon_entries_by_state_in(&self.project_id, state, topic)
+    }
+
+    pub fn active_canon_entries(
+        &self,
+        topic: Option<&str>,
+    ) -> Result<Vec<CanonEntryRevision>, StorageError> {
+        self.canon_entries_by_state(CanonRevisionState::Active, topic)
+    }
+
+    pub fn canon_entry_revisions(
+        &self,
+        entry_id: &str,
+    ) -> Result<Vec<CanonEntryRevision>, StorageError> {
+        self.store
+            .canon_entry_revisions_in(&self.project_id, entry_id)
+    }
+
+    pub fn canon_entry_revision(
+        &self,
+        entry_id: &str,
+        revision: i64,
+    ) -> Result<Option<CanonEntryRevision>, StorageError> {
+        self.store
+            .canon_entry_revision_in(&self.project_id, entry_id, revision)
+    }
+
+    pub fn canon_revision_history(
+        &self,
+        topic: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<CanonEntryRevision>, StorageError> {
+        self.store
+            .canon_revision_history_in(&self.project_id, topic, limit)
+    }
+
+    pub fn stats(&self, now: DateTime<Utc>) -> Result<MindStoreStats, StorageError> {
+        self.store.stats_in(&self.project_id, now)
+    }
+
+    pub fn export_bundle(
+        &self,
+        path: impl AsRef<Path>,
+        filter: &BundleFilter,
+    ) -> Result<BundleReport, StorageError> {
+        self.store.export_bundle_in(&self.project_id, path, filter)
+    }
+}
+
+impl Deref for ProjectScope<'_> {
+    type Target = MindStore;
+
+    fn deref(&self) -> &MindStore {
+        self.store
+    }
+}
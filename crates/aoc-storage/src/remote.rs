@@ -0,0 +1,491 @@
+//! Read replica backed by a remote `aoc serve` instance.
+//!
+//! [`RemoteMindStore`] answers the read queries editor plugins and remote
+//! TUIs need with the same types [`MindStore`](crate::MindStore) returns, so
+//! callers can switch between a local file and a server URL. Responses are
+//! cached in memory: a fresh entry is served without a request, a stale one is
+//! revalidated with its `ETag`, and when the server cannot be reached a cached
+//! body is served rather than failing.
+
+use crate::{
+    parse_canon_revision_state, parse_timestamp, ArtifactPage, ArtifactQuery, CanonEntryRevision,
+    CanonRevisionState, HandshakeSnapshot, StorageError, StoredArtifact,
+};
+use aoc_core::mind_contracts::{ArtifactTaskLink, SegmentRoute};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// How long a cached response is served without asking the server.
+pub const DEFAULT_REMOTE_FRESH_FOR: Duration = Duration::from_secs(5);
+pub const DEFAULT_REMOTE_CACHE_ENTRIES: usize = 512;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Largest page the server hands out; see `aoc_server::MAX_PAGE_LIMIT`.
+const SERVER_PAGE_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RemoteCacheStats {
+    /// Served from a fresh entry without a request.
+    pub hits: u64,
+    /// Stale entries the server confirmed with 304.
+    pub revalidated: u64,
+    /// Requests that fetched a new body.
+    pub fetched: u64,
+    /// Stale entries served because the server could not be reached.
+    pub served_offline: u64,
+}
+
+struct CacheEntry {
+    etag: Option<String>,
+    body: Value,
+    checked_at: Instant,
+}
+
+pub struct RemoteMindStore {
+    base_url: String,
+    agent: ureq::Agent,
+    bearer_token: Option<String>,
+    fresh_for: Duration,
+    max_entries: usize,
+    cache: Mutex<HashMap<String, CacheEntry>>,
+    stats: Mutex<RemoteCacheStats>,
+}
+
+impl RemoteMindStore {
+    /// Client for the server at `base_url`, e.g. `http://127.0.0.1:7700`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+            bearer_token: None,
+            fresh_for: DEFAULT_REMOTE_FRESH_FOR,
+            max_entries: DEFAULT_REMOTE_CACHE_ENTRIES,
+            cache: Mutex::new(HashMap::new()),
+            stats: Mutex::new(RemoteCacheStats::default()),
+        }
+    }
+
+    /// Zero revalidates every read; a longer window trades staleness for
+    /// fewer round trips.
+    pub fn with_fresh_for(mut self, fresh_for: Duration) -> Self {
+        self.fresh_for = fresh_for;
+        self
+    }
+
+    pub fn with_cache_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Sent on every request, for servers behind a proxy that checks it.
+    /// Reads never need it from `aoc serve` itself.
+    pub fn with_bearer_token(mut self, token: Option<String>) -> Self {
+        self.bearer_token = token.filter(|token| !token.trim().is_empty());
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn cache_stats(&self) -> RemoteCacheStats {
+        *self.stats.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    pub fn clear_cache(&self) {
+        self.cache
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clear();
+    }
+
+    pub fn schema_version(&self) -> Result<i64, StorageError> {
+        let body = self.require("/v1/status")?;
+        body["schema_version"]
+            .as_i64()
+            .ok_or_else(|| malformed("/v1/status", "schema_version"))
+    }
+
+    pub fn query_artifacts(&self, query: &ArtifactQuery) -> Result<ArtifactPage, StorageError> {
+        let mut params = vec![
+            ("offset", query.offset.to_string()),
+            ("limit", query.limit.to_string()),
+        ];
+        for (name, value) in [
+            ("q", &query.text),
+            ("conversation", &query.conversation_id),
+            ("tag", &query.active_tag),
+            ("task", &query.task_id),
+            ("segment", &query.segment_id),
+            ("kind", &query.kind),
+        ] {
+            if let Some(value) = value {
+                params.push((name, value.clone()));
+            }
+        }
+        if let Some(since) = query.since {
+            params.push(("since", since.to_rfc3339()));
+        }
+        if let Some(until) = query.until {
+            params.push(("until", until.to_rfc3339()));
+        }
+        if query.oldest_first {
+            params.push(("oldest_first", "true".to_string()));
+        }
+        let path = format!("/v1/artifacts?{}", encode_query(&params));
+        let body = self.require(&path)?;
+        Ok(ArtifactPage {
+            artifacts: artifacts_from(&path, &body["items"])?,
+            total: body["total"]
+                .as_u64()
+                .ok_or_else(|| malformed(&path, "total"))? as usize,
+            offset: body["offset"].as_u64().unwrap_or(query.offset as u64) as usize,
+        })
+    }
+
+    pub fn artifact_by_id(
+        &self,
+        artifact_id: &str,
+    ) -> Result<Option<StoredArtifact>, StorageError> {
+        let path = format!("/v1/artifacts/{}", encode(artifact_id));
+        self.get(&path)?
+            .map(|body| artifact_from(&path, &body))
+            .transpose()
+    }
+
+    pub fn artifacts_for_conversation(
+        &self,
+        conversation_id: &str,
+    ) -> Result<Vec<StoredArtifact>, StorageError> {
+        let path = format!("/v1/conversations/{}", encode(conversation_id));
+        match self.get(&path)? {
+            Some(body) => artifacts_from(&path, &body["artifacts"]),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub fn recent_linked_task_ids(&self, limit: usize) -> Result<Vec<String>, StorageError> {
+        let items = self.collect_pages("/v1/tasks?", limit)?;
+        items
+            .iter()
+            .map(|item| {
+                item.as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| malformed("/v1/tasks", "items"))
+            })
+            .collect()
+    }
+
+    pub fn artifact_task_links_for_task(
+        &self,
+        task_id: &str,
+    ) -> Result<Vec<ArtifactTaskLink>, StorageError> {
+        let path = format!("/v1/tasks/{}/timeline?", encode(task_id));
+        self.collect_pages(&path, usize::MAX)?
+            .into_iter()
+            .map(|mut item| {
+                if let Some(object) = item.as_object_mut() {
+                    object.remove("artifact");
+                }
+                serde_json::from_value(item).map_err(|err| decode_error(&path, err))
+            })
+            .collect()
+    }
+
+    pub fn segment_route_for_artifact(
+        &self,
+        artifact_id: &str,
+    ) -> Result<Option<SegmentRoute>, StorageError> {
+        let path = format!("/v1/routes/{}", encode(artifact_id));
+        self.get(&path)?
+            .map(|body| serde_json::from_value(body).map_err(|err| decode_error(&path, err)))
+            .transpose()
+    }
+
+    pub fn artifacts_with_primary_segment(
+        &self,
+        segment_id: &str,
+        limit: usize,
+    ) -> Result<Vec<String>, StorageError> {
+        let path = format!("/v1/segments/{}/artifacts?", encode(segment_id));
+        self.collect_pages(&path, limit)?
+            .iter()
+            .map(|item| {
+                item["artifact_id"]
+                    .as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| malformed(&path, "artifact_id"))
+            })
+            .collect()
+    }
+
+    pub fn canon_entries_by_state(
+        &self,
+        state: CanonRevisionState,
+        topic: Option<&str>,
+    ) -> Result<Vec<CanonEntryRevision>, StorageError> {
+        let mut params = vec![("state", state.as_str().to_string())];
+        if let Some(topic) = topic {
+            params.push(("topic", topic.to_string()));
+        }
+        let path = format!("/v1/canon?{}", encode_query(&params));
+        let body = self.require(&path)?;
+        canon_revisions_from(&path, &body["items"])
+    }
+
+    pub fn canon_entry_revisions(
+        &self,
+        entry_id: &str,
+    ) -> Result<Vec<CanonEntryRevision>, StorageError> {
+        let path = format!("/v1/canon/{}", encode(entry_id));
+        match self.get(&path)? {
+            Some(body) => canon_revisions_from(&path, &body["revisions"]),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub fn latest_handshake_snapshot(
+        &self,
+        scope: &str,
+        scope_key: &str,
+    ) -> Result<Option<HandshakeSnapshot>, StorageError> {
+        let path = format!("/v1/handshake/{}/{}", encode(scope), encode(scope_key));
+        let Some(body) = self.get(&path)? else {
+            return Ok(None);
+        };
+        let text = |field: &str| {
+            body[field]
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| malformed(&path, field))
+        };
+        Ok(Some(HandshakeSnapshot {
+            snapshot_id: text("snapshot_id")?,
+            scope: text("scope")?,
+            scope_key: text("scope_key")?,
+            payload_text: text("payload_text")?,
+            payload_hash: text("payload_hash")?,
+            token_estimate: body["token_estimate"]
+                .as_u64()
+                .ok_or_else(|| malformed(&path, "token_estimate"))?
+                as u32,
+            created_at: parse_timestamp(text("created_at")?)?,
+        }))
+    }
+
+    /// Follows `next_offset` until `limit` items are collected. `path` must
+    /// end in `?` or `&`.
+    fn collect_pages(&self, path: &str, limit: usize) -> Result<Vec<Value>, StorageError> {
+        let mut items = Vec::new();
+        let mut offset = 0;
+        while items.len() < limit {
+            let page_limit = (limit - items.len()).min(SERVER_PAGE_LIMIT);
+            let page_path = format!("{path}offset={offset}&limit={page_limit}");
+            let Some(body) = self.get(&page_path)? else {
+                break;
+            };
+            let Some(page) = body["items"].as_array() else {
+                return Err(malformed(&page_path, "items"));
+            };
+            items.extend(page.iter().cloned());
+            match body["next_offset"].as_u64() {
+                Some(next) if !page.is_empty() => offset = next as usize,
+                _ => break,
+            }
+        }
+        items.truncate(limit);
+        Ok(items)
+    }
+
+    fn require(&self, path: &str) -> Result<Value, StorageError> {
+        self.get(path)?
+            .ok_or_else(|| StorageError::Remote(format!("{}{path} answered 404", self.base_url)))
+    }
+
+    /// Cached GET; `None` means the server answered 404.
+    fn get(&self, path: &str) -> Result<Option<Value>, StorageError> {
+        let (etag, cached) = {
+            let cache = self.cache.lock().unwrap_or_else(|err| err.into_inner());
+            match cache.get(path) {
+                Some(entry) if entry.checked_at.elapsed() < self.fresh_for => {
+                    self.bump(|stats| stats.hits += 1);
+                    return Ok(Some(entry.body.clone()));
+                }
+                Some(entry) => (entry.etag.clone(), Some(entry.body.clone())),
+                None => (None, None),
+            }
+        };
+
+        let url = format!("{}{path}", self.base_url);
+        let mut request = self.agent.get(&url);
+        if let Some(token) = &self.bearer_token {
+            request = request.set("Authorization", &format!("Bearer {token}"));
+        }
+        if let (Some(etag), Some(_)) = (&etag, &cached) {
+            request = request.set("If-None-Match", etag);
+        }
+        match request.call() {
+            Ok(response) if response.status() == 304 => {
+                let body = cached.ok_or_else(|| {
+                    StorageError::Remote(format!("{url} answered 304 to an uncached request"))
+                })?;
+                self.store(path, etag, body.clone());
+                self.bump(|stats| stats.revalidated += 1);
+                Ok(Some(body))
+            }
+            Ok(response) => {
+                let etag = response.header("ETag").map(str::to_string);
+                let text = response
+                    .into_string()
+                    .map_err(|err| StorageError::Remote(format!("read {url}: {err}")))?;
+                let body: Value =
+                    serde_json::from_str(&text).map_err(|err| decode_error(&url, err))?;
+                self.store(path, etag, body.clone());
+                self.bump(|stats| stats.fetched += 1);
+                Ok(Some(body))
+            }
+            Err(ureq::Error::Status(404, _)) => {
+                self.cache
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner())
+                    .remove(path);
+                Ok(None)
+            }
+            Err(ureq::Error::Status(status, response)) => {
+                let text = response.into_string().unwrap_or_default();
+                let message = serde_json::from_str::<Value>(&text)
+                    .ok()
+                    .and_then(|body| body["error"].as_str().map(str::to_string))
+                    .unwrap_or(text);
+                Err(StorageError::Remote(format!(
+                    "{url} answered {status}: {message}"
+                )))
+            }
+            Err(err) => match cached {
+                Some(body) => {
+                    tracing::warn!(url = %url, error = %err, "mind server unreachable; serving cached response");
+                    self.bump(|stats| stats.served_offline += 1);
+                    Ok(Some(body))
+                }
+                None => Err(StorageError::Remote(format!("request {url}: {err}"))),
+            },
+        }
+    }
+
+    fn store(&self, path: &str, etag: Option<String>, body: Value) {
+        let mut cache = self.cache.lock().unwrap_or_else(|err| err.into_inner());
+        if !cache.contains_key(path) && cache.len() >= self.max_entries {
+            let oldest = cache
+                .iter()
+                .min_by_key(|(_, entry)| entry.checked_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                cache.remove(&oldest);
+            }
+        }
+        cache.insert(
+            path.to_string(),
+            CacheEntry {
+                etag,
+                body,
+                checked_at: Instant::now(),
+            },
+        );
+    }
+
+    fn bump(&self, update: impl FnOnce(&mut RemoteCacheStats)) {
+        update(&mut self.stats.lock().unwrap_or_else(|err| err.into_inner()));
+    }
+}
+
+fn malformed(path: &str, field: &str) -> StorageError {
+    StorageError::Remote(format!("{path}: missing or malformed `{field}`"))
+}
+
+fn decode_error(path: &str, err: serde_json::Error) -> StorageError {
+    StorageError::Remote(format!("{path}: {err}"))
+}
+
+fn artifacts_from(path: &str, items: &Value) -> Result<Vec<StoredArtifact>, StorageError> {
+    items
+        .as_array()
+        .ok_or_else(|| malformed(path, "items"))?
+        .iter()
+        .map(|item| artifact_from(path, item))
+        .collect()
+}
+
+fn artifact_from(path: &str, body: &Value) -> Result<StoredArtifact, StorageError> {
+    let text = |field: &str| {
+        body[field]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| malformed(path, field))
+    };
+    Ok(StoredArtifact {
+        artifact_id: text("artifact_id")?,
+        conversation_id: text("conversation_id")?,
+        ts: parse_timestamp(text("ts")?)?,
+        text: text("text")?,
+        kind: text("kind")?,
+        trace_ids: serde_json::from_value(body["trace_ids"].clone())
+            .map_err(|err| decode_error(path, err))?,
+    })
+}
+
+fn canon_revisions_from(
+    path: &str,
+    items: &Value,
+) -> Result<Vec<CanonEntryRevision>, StorageError> {
+    let items = items.as_array().ok_or_else(|| malformed(path, "items"))?;
+    let mut revisions = Vec::with_capacity(items.len());
+    for item in items {
+        let text = |field: &str| {
+            item[field]
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| malformed(path, field))
+        };
+        let number = |field: &str| item[field].as_i64().ok_or_else(|| malformed(path, field));
+        revisions.push(CanonEntryRevision {
+            entry_id: text("entry_id")?,
+            revision: number("revision")?,
+            state: parse_canon_revision_state(&text("state")?)
+                .ok_or_else(|| malformed(path, "state"))?,
+            topic: item["topic"].as_str().map(str::to_string),
+            summary: text("summary")?,
+            confidence_bps: number("confidence_bps")? as u16,
+            freshness_score: number("freshness_score")? as u16,
+            supersedes_entry_id: item["supersedes_entry_id"].as_str().map(str::to_string),
+            evidence_refs: serde_json::from_value(item["evidence_refs"].clone())
+                .map_err(|err| decode_error(path, err))?,
+            created_at: parse_timestamp(text("created_at")?)?,
+        });
+    }
+    Ok(revisions)
+}
+
+fn encode_query(params: &[(&str, String)]) -> String {
+    params
+        .iter()
+        .map(|(name, value)| format!("{name}={}", encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Percent-encodes everything outside RFC 3986's unreserved set.
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
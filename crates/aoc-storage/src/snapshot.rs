@@ -0,0 +1,123 @@
+//! Online copies of an open store.
+//!
+//! [`MindStore::backup_to`] writes a consistent copy with `VACUUM INTO`, which
+//! reads one snapshot of the database and does not block writers on other
+//! connections, so the ingestion daemon keeps running. [`SnapshotRotation`]
+//! builds daily snapshots on top of it: at most one per UTC day, named
+//! `mind-YYYY-MM-DD.sqlite`, keeping the newest `keep_daily`.
+
+use crate::{MindStore, StorageError};
+use chrono::{DateTime, NaiveDate, Utc};
+use rusqlite::params;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SNAPSHOT_PREFIX: &str = "mind-";
+const SNAPSHOT_SUFFIX: &str = ".sqlite";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotRotation {
+    pub dir: PathBuf,
+    /// Daily snapshots to keep; at least one is always kept.
+    pub keep_daily: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotReport {
+    /// Snapshot written this call; `None` when today's already existed.
+    pub created: Option<PathBuf>,
+    /// Older snapshots removed to stay within `keep_daily`.
+    pub pruned: Vec<PathBuf>,
+}
+
+impl SnapshotRotation {
+    pub fn new(dir: impl Into<PathBuf>, keep_daily: usize) -> Self {
+        Self {
+            dir: dir.into(),
+            keep_daily,
+        }
+    }
+
+    pub fn snapshot_path(&self, day: NaiveDate) -> PathBuf {
+        self.dir.join(format!(
+            "{SNAPSHOT_PREFIX}{}{SNAPSHOT_SUFFIX}",
+            day.format("%Y-%m-%d")
+        ))
+    }
+
+    /// Daily snapshots in `dir`, oldest first.
+    pub fn snapshots(&self) -> Result<Vec<(NaiveDate, PathBuf)>, StorageError> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(io_error(&self.dir, err)),
+        };
+        let mut snapshots = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|err| io_error(&self.dir, err))?.path();
+            let day = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(SNAPSHOT_PREFIX))
+                .and_then(|name| name.strip_suffix(SNAPSHOT_SUFFIX))
+                .and_then(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok());
+            if let Some(day) = day {
+                snapshots.push((day, path));
+            }
+        }
+        snapshots.sort();
+        Ok(snapshots)
+    }
+}
+
+impl MindStore {
+    /// Writes a consistent copy of this store to `dest` with `VACUUM INTO`;
+    /// other connections may keep writing meanwhile. Refuses to overwrite an
+    /// existing `dest`.
+    pub fn backup_to(&self, dest: impl AsRef<Path>) -> Result<(), StorageError> {
+        let dest = dest.as_ref();
+        if dest.exists() {
+            return Err(StorageError::Serialization(format!(
+                "backup target {} already exists",
+                dest.display()
+            )));
+        }
+        self.conn
+            .execute("VACUUM INTO ?1", params![dest.to_string_lossy()])?;
+        Ok(())
+    }
+
+    /// Writes today's snapshot unless it exists, then prunes the oldest
+    /// beyond `rotation.keep_daily`. The copy lands under a temporary name
+    /// first, so an interrupted backup never passes for a snapshot.
+    pub fn rotate_daily_snapshot(
+        &self,
+        rotation: &SnapshotRotation,
+        now: DateTime<Utc>,
+    ) -> Result<SnapshotReport, StorageError> {
+        let mut report = SnapshotReport::default();
+        let today = rotation.snapshot_path(now.date_naive());
+        if !today.exists() {
+            fs::create_dir_all(&rotation.dir).map_err(|err| io_error(&rotation.dir, err))?;
+            let partial = today.with_extension("sqlite.partial");
+            if partial.exists() {
+                fs::remove_file(&partial).map_err(|err| io_error(&partial, err))?;
+            }
+            self.backup_to(&partial)?;
+            fs::rename(&partial, &today).map_err(|err| io_error(&today, err))?;
+            report.created = Some(today);
+        }
+
+        let snapshots = rotation.snapshots()?;
+        let excess = snapshots.len().saturating_sub(rotation.keep_daily.max(1));
+        for (_, path) in snapshots.into_iter().take(excess) {
+            fs::remove_file(&path).map_err(|err| io_error(&path, err))?;
+            report.pruned.push(path);
+        }
+        Ok(report)
+    }
+}
+
+fn io_error(path: &Path, err: std::io::Error) -> StorageError {
+    StorageError::Serialization(format!("snapshot {}: {err}", path.display()))
+}
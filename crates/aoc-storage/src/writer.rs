@@ -0,0 +1,281 @@
+//! Cross-process single-writer coordination.
+//!
+//! The CLI, the mind daemon, and the HTTP/stdio server can all open the same
+//! store. SQLite serializes individual transactions, but multi-step writes
+//! (pipeline ticks, imports, prunes, sync pulls) interleave badly, so every
+//! writer goes through a [`MindWriterGuard`] first.
+//!
+//! Two pieces back the guard:
+//!
+//! - an advisory `fs2` lock on `<store>.writer.lock`, which the OS drops when
+//!   the holder exits, however it exits;
+//! - the singleton `mind_writers` row, which names the holder for anyone who
+//!   is refused and survives a crash.
+//!
+//! A free lock file with an unexpired row owned by someone else therefore
+//! means the previous writer died mid-lease (or locks a different path, e.g.
+//! over a network mount). Acquisition refuses until the row expires;
+//! [`MindWriterGuard::take_over`] replaces it immediately.
+
+use crate::{system_clock, MindStore, MindWriter, SharedClock, StorageError};
+use fs2::FileExt;
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How long a writer row stays authoritative without a heartbeat.
+pub const DEFAULT_WRITER_LEASE_TTL_MS: u64 = 120_000;
+/// How long interactive commands wait for a busy writer before giving up.
+pub const DEFAULT_WRITER_WAIT: Duration = Duration::from_secs(10);
+
+const WRITER_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Identity a process writes into the lock file and the `mind_writers` row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriterClaim {
+    pub owner_id: String,
+    pub label: String,
+    pub owner_pid: Option<i64>,
+}
+
+impl WriterClaim {
+    pub fn for_current_process(label: impl Into<String>) -> Self {
+        let label = label.into();
+        let pid = std::process::id();
+        let nanos = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+        Self {
+            owner_id: format!("{label}:{pid}:{nanos:x}"),
+            label,
+            owner_pid: Some(pid as i64),
+        }
+    }
+}
+
+/// `<store>.writer.lock`, next to the database file.
+pub fn writer_lock_path(store_path: &Path) -> PathBuf {
+    let mut name = store_path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_else(|| "mind.sqlite".into());
+    name.push(".writer.lock");
+    store_path.with_file_name(name)
+}
+
+/// An open store plus the exclusive right to write it. Releases the row and
+/// the file lock on drop.
+pub struct MindWriterGuard {
+    store: MindStore,
+    lock_file: File,
+    claim: WriterClaim,
+    ttl_ms: u64,
+    clock: SharedClock,
+}
+
+impl MindWriterGuard {
+    /// Fails fast with [`StorageError::WriterBusy`] if another writer holds
+    /// the store.
+    pub fn acquire(
+        store_path: &Path,
+        claim: WriterClaim,
+        ttl_ms: u64,
+    ) -> Result<Self, StorageError> {
+        Self::acquire_with_clock(store_path, claim, ttl_ms, system_clock())
+    }
+
+    /// [`acquire`](Self::acquire), timing the lease and its heartbeats by
+    /// `clock`.
+    pub fn acquire_with_clock(
+        store_path: &Path,
+        claim: WriterClaim,
+        ttl_ms: u64,
+        clock: SharedClock,
+    ) -> Result<Self, StorageError> {
+        let lock_file = lock_writer_file(store_path)?;
+        let store = MindStore::open(store_path)?;
+        if !store.try_acquire_writer_lease(&claim, clock.now(), ttl_ms)? {
+            let _ = lock_file.unlock();
+            let holder = store
+                .mind_writer()?
+                .map(|writer| describe_writer(&writer))
+                .unwrap_or_else(|| "unknown writer".to_string());
+            return Err(StorageError::WriterBusy(format!(
+                "{holder} for {}; no process holds {}, so if it crashed run `aoc writer --takeover`",
+                store_path.display(),
+                writer_lock_path(store_path).display()
+            )));
+        }
+        Ok(Self::finish(store, lock_file, claim, ttl_ms, clock))
+    }
+
+    /// Like [`acquire`](Self::acquire), retrying while another writer is
+    /// busy until `wait` elapses.
+    pub fn acquire_waiting(
+        store_path: &Path,
+        claim: WriterClaim,
+        ttl_ms: u64,
+        wait: Duration,
+    ) -> Result<Self, StorageError> {
+        let deadline = Instant::now() + wait;
+        loop {
+            match Self::acquire(store_path, claim.clone(), ttl_ms) {
+                Err(StorageError::WriterBusy(_)) if Instant::now() < deadline => {
+                    std::thread::sleep(WRITER_RETRY_INTERVAL);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Replaces a writer whose process is gone but whose row has not expired.
+    /// Still refuses while a live process holds the lock file. Returns the
+    /// guard and the writer it replaced, if any.
+    pub fn take_over(
+        store_path: &Path,
+        claim: WriterClaim,
+        ttl_ms: u64,
+    ) -> Result<(Self, Option<MindWriter>), StorageError> {
+        let lock_file = lock_writer_file(store_path)?;
+        let store = MindStore::open(store_path)?;
+        let clock = system_clock();
+        let previous = store.take_over_writer_lease(&claim, clock.now(), ttl_ms)?;
+        if let Some(previous) = &previous {
+            tracing::warn!(
+                previous = %describe_writer(previous),
+                owner_id = %claim.owner_id,
+                "took over mind writer lease"
+            );
+        }
+        Ok((
+            Self::finish(store, lock_file, claim, ttl_ms, clock),
+            previous,
+        ))
+    }
+
+    fn finish(
+        store: MindStore,
+        mut lock_file: File,
+        claim: WriterClaim,
+        ttl_ms: u64,
+        clock: SharedClock,
+    ) -> Self {
+        let metadata = format!(
+            "owner_id={}\nlabel={}\nowner_pid={}\nacquired_at={}\n",
+            claim.owner_id,
+            claim.label,
+            claim
+                .owner_pid
+                .map(|pid| pid.to_string())
+                .unwrap_or_default(),
+            clock.now().to_rfc3339()
+        );
+        // Metadata is informational; the lock itself is what matters.
+        let _ = lock_file
+            .set_len(0)
+            .and_then(|_| lock_file.seek(SeekFrom::Start(0)))
+            .and_then(|_| lock_file.write_all(metadata.as_bytes()));
+        Self {
+            store,
+            lock_file,
+            claim,
+            ttl_ms,
+            clock,
+        }
+    }
+
+    pub fn owner_id(&self) -> &str {
+        &self.claim.owner_id
+    }
+
+    /// Extends the row for holders that keep the guard longer than the TTL.
+    pub fn heartbeat(&self) -> Result<bool, StorageError> {
+        self.store
+            .heartbeat_writer_lease(&self.claim.owner_id, self.clock.now(), self.ttl_ms)
+    }
+}
+
+impl Deref for MindWriterGuard {
+    type Target = MindStore;
+
+    fn deref(&self) -> &MindStore {
+        &self.store
+    }
+}
+
+impl DerefMut for MindWriterGuard {
+    fn deref_mut(&mut self) -> &mut MindStore {
+        &mut self.store
+    }
+}
+
+impl Drop for MindWriterGuard {
+    fn drop(&mut self) {
+        if let Err(err) = self.store.release_writer_lease(&self.claim.owner_id) {
+            tracing::warn!(error = %err, "failed to release mind writer lease");
+        }
+        let _ = self.lock_file.set_len(0);
+        let _ = self.lock_file.unlock();
+    }
+}
+
+fn lock_writer_file(store_path: &Path) -> Result<File, StorageError> {
+    let lock_path = writer_lock_path(store_path);
+    let io_error = |err: std::io::Error| {
+        StorageError::Serialization(format!("writer lock {}: {err}", lock_path.display()))
+    };
+    if let Some(parent) = lock_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(io_error)?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .read(true)
+        .write(true)
+        .open(&lock_path)
+        .map_err(io_error)?;
+    if file.try_lock_exclusive().is_err() {
+        let holder = std::fs::read_to_string(&lock_path)
+            .ok()
+            .and_then(|text| describe_lock_file(&text))
+            .or_else(|| {
+                MindStore::open(store_path)
+                    .ok()
+                    .and_then(|store| store.mind_writer().ok().flatten())
+                    .map(|writer| describe_writer(&writer))
+            })
+            .unwrap_or_else(|| "another process".to_string());
+        return Err(StorageError::WriterBusy(format!(
+            "{holder} is writing {}",
+            store_path.display()
+        )));
+    }
+    Ok(file)
+}
+
+fn describe_writer(writer: &MindWriter) -> String {
+    let pid = writer
+        .owner_pid
+        .map(|pid| format!(" pid {pid}"))
+        .unwrap_or_default();
+    format!(
+        "{}{pid} (owner {}, lease until {})",
+        writer.label,
+        writer.owner_id,
+        writer.expires_at.to_rfc3339()
+    )
+}
+
+fn describe_lock_file(text: &str) -> Option<String> {
+    let field = |key: &str| {
+        text.lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+            .filter(|value| !value.is_empty())
+    };
+    let owner_id = field("owner_id")?;
+    let label = field("label").unwrap_or("writer");
+    let pid = field("owner_pid")
+        .map(|pid| format!(" pid {pid}"))
+        .unwrap_or_default();
+    Some(format!("{label}{pid} (owner {owner_id})"))
+}
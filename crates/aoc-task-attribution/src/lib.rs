@@ -1,9 +1,12 @@
 use aoc_core::mind_contracts::{ArtifactTaskLink, ArtifactTaskRelation, MindContractError};
+use aoc_core::{ErrorCode, ErrorCoded};
 use aoc_storage::{
-    ConversationContextState, MindStore, StorageError, StoredArtifact, StoredCompactEvent,
+    ContextTimeline, ConversationContextState, MindStore, StorageError, StoredArtifact,
+    StoredCompactEvent,
 };
 use chrono::{DateTime, Duration, Utc};
 use regex::Regex;
+use serde::Serialize;
 use std::collections::{BTreeMap, BTreeSet};
 use thiserror::Error;
 
@@ -12,6 +15,16 @@ const CONF_MENTIONED_BPS: u16 = 7_200;
 const CONF_WORKED_ON_BPS: u16 = 8_800;
 const CONF_BACKFILL_BPS: u16 = 9_300;
 const CONF_COMPLETED_BPS: u16 = 9_600;
+/// Longest task id kept from a pattern match; longer captures are noise.
+const MAX_TASK_ID_CHARS: usize = 64;
+
+/// Mention forms recognized out of the box: `task 12`, `task #PROJ-12`,
+/// `tm status 12`, and `[PROJ-12]`. Ids are numeric or a `KEY-123` issue key.
+pub const DEFAULT_TASK_ID_PATTERNS: &[&str] = &[
+    r"(?i)\btask\s*#?([0-9]+|[a-z][a-z0-9]*-[0-9]+)\b",
+    r"(?i)\b(?:tm|aoc-task)\s+(?:status|done|start|resume|show)\s+([0-9]+|[a-z][a-z0-9]*-[0-9]+)\b",
+    r"\[([0-9]+|[A-Z][A-Z0-9]*-[0-9]+)\]",
+];
 
 #[derive(Debug, Error)]
 pub enum AttributionError {
@@ -19,12 +32,27 @@ pub enum AttributionError {
     Storage(#[from] StorageError),
     #[error("contract error: {0}")]
     Contract(#[from] MindContractError),
+    #[error("invalid task id pattern `{pattern}`: {message}")]
+    InvalidTaskIdPattern { pattern: String, message: String },
+}
+
+impl ErrorCoded for AttributionError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Storage(err) => err.error_code(),
+            Self::Contract(err) => err.error_code(),
+            Self::InvalidTaskIdPattern { .. } => ErrorCode::InvalidInput,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct AttributionConfig {
     pub mention_window_before: Duration,
     pub mention_window_after: Duration,
+    /// Mention patterns; the first capture group (or the whole match) is the
+    /// task id. Build with [`compile_task_id_patterns`].
+    pub task_id_patterns: Vec<Regex>,
 }
 
 impl Default for AttributionConfig {
@@ -32,11 +60,41 @@ impl Default for AttributionConfig {
         Self {
             mention_window_before: Duration::minutes(30),
             mention_window_after: Duration::minutes(5),
+            task_id_patterns: compile_task_id_patterns(DEFAULT_TASK_ID_PATTERNS)
+                .expect("default task id patterns are valid"),
+        }
+    }
+}
+
+/// Compiles task id patterns, dropping repeats. A pattern must compile, have
+/// at most one capture group, and not match the empty string.
+pub fn compile_task_id_patterns<S: AsRef<str>>(
+    patterns: &[S],
+) -> Result<Vec<Regex>, AttributionError> {
+    let mut seen = BTreeSet::new();
+    let mut compiled = Vec::new();
+    for pattern in patterns {
+        let pattern = pattern.as_ref().trim();
+        if !seen.insert(pattern.to_string()) {
+            continue;
+        }
+        let invalid = |message: &str| AttributionError::InvalidTaskIdPattern {
+            pattern: pattern.to_string(),
+            message: message.to_string(),
+        };
+        let regex = Regex::new(pattern).map_err(|err| invalid(&err.to_string()))?;
+        if regex.captures_len() > 2 {
+            return Err(invalid("use at most one capture group for the task id"));
+        }
+        if regex.is_match("") {
+            return Err(invalid("must not match empty text"));
         }
+        compiled.push(regex);
     }
+    Ok(compiled)
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
 pub struct AttributionReport {
     pub artifacts_processed: usize,
     pub links_written: usize,
@@ -75,6 +133,11 @@ impl TaskAttributionEngine {
         Self { config }
     }
 
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(stage = "attribute", conversation_id = %conversation_id)
+    )]
     pub fn attribute_conversation(
         &self,
         store: &MindStore,
@@ -90,30 +153,34 @@ impl TaskAttributionEngine {
         let completions = completion_signals(&contexts);
 
         let mut report = AttributionReport::default();
-        let mut context_cursor = 0usize;
-        let mut current_context: Option<&ConversationContextState> = None;
+        let mut timeline = ContextTimeline::new(&contexts);
 
         for artifact in artifacts {
             report.artifacts_processed += 1;
 
-            while context_cursor < contexts.len() && contexts[context_cursor].ts <= artifact.ts {
-                current_context = Some(&contexts[context_cursor]);
-                context_cursor += 1;
-            }
+            let current_context = timeline.at(artifact.ts);
 
             let mut drafts: BTreeMap<(String, String), LinkDraft> = BTreeMap::new();
             let active_tasks = current_context
                 .map(|snapshot| snapshot.active_tasks.clone())
                 .unwrap_or_default();
 
-            let mut mentioned_tasks = mention_tasks_from_artifact(&artifact);
+            let patterns = &self.config.task_id_patterns;
+            let mut mentioned_tasks = mention_tasks_from_artifact(&artifact, patterns);
             let mention_from_t0 = mention_tasks_from_t0(
                 artifact.ts,
                 &t0_events,
                 self.config.mention_window_before,
                 self.config.mention_window_after,
+                patterns,
             );
             merge_mentions(&mut mentioned_tasks, mention_from_t0);
+            let file_work = file_work_from_t0(
+                artifact.ts,
+                &t0_events,
+                self.config.mention_window_before,
+                self.config.mention_window_after,
+            );
 
             for task_id in &active_tasks {
                 upsert_draft(
@@ -166,6 +233,7 @@ impl TaskAttributionEngine {
                         "ctx:{conversation_id}:{}",
                         artifact.ts.to_rfc3339()
                     ));
+                    worked_on_evidence.extend(file_work.iter().cloned());
                 }
                 if let Some(mention_evidence) = mention_evidence {
                     worked_on_evidence.extend(mention_evidence.iter().cloned());
@@ -300,9 +368,12 @@ fn last_completion_before_or_at(
         .cloned()
 }
 
-fn mention_tasks_from_artifact(artifact: &StoredArtifact) -> BTreeMap<String, BTreeSet<String>> {
+fn mention_tasks_from_artifact(
+    artifact: &StoredArtifact,
+    patterns: &[Regex],
+) -> BTreeMap<String, BTreeSet<String>> {
     let mut out = BTreeMap::new();
-    for task_id in extract_task_ids(&artifact.text) {
+    for task_id in extract_task_ids(&artifact.text, patterns) {
         out.entry(task_id)
             .or_insert_with(BTreeSet::new)
             .insert(format!("artifact:{}:text", artifact.artifact_id));
@@ -315,6 +386,7 @@ fn mention_tasks_from_t0(
     events: &[StoredCompactEvent],
     before: Duration,
     after: Duration,
+    patterns: &[Regex],
 ) -> BTreeMap<String, BTreeSet<String>> {
     let start = ts - before;
     let end = ts + after;
@@ -326,7 +398,7 @@ fn mention_tasks_from_t0(
         }
 
         if let Some(text) = event.text.as_deref() {
-            for task_id in extract_task_ids(text) {
+            for task_id in extract_task_ids(text, patterns) {
                 out.entry(task_id)
                     .or_insert_with(BTreeSet::new)
                     .insert(format!("t0:{}", event.compact_id));
@@ -337,6 +409,29 @@ fn mention_tasks_from_t0(
     out
 }
 
+/// Tool calls in the window that touched files: evidence that an active task
+/// was actually worked on rather than just open.
+fn file_work_from_t0(
+    ts: DateTime<Utc>,
+    events: &[StoredCompactEvent],
+    before: Duration,
+    after: Duration,
+) -> BTreeSet<String> {
+    let start = ts - before;
+    let end = ts + after;
+    events
+        .iter()
+        .filter(|event| event.ts >= start && event.ts <= end)
+        .filter(|event| {
+            event
+                .tool_meta
+                .as_ref()
+                .is_some_and(|tool_meta| !tool_meta.touched_paths.is_empty())
+        })
+        .map(|event| format!("t0:{}", event.compact_id))
+        .collect()
+}
+
 fn merge_mentions(
     base: &mut BTreeMap<String, BTreeSet<String>>,
     extra: BTreeMap<String, BTreeSet<String>>,
@@ -395,19 +490,15 @@ fn relation_key(relation: ArtifactTaskRelation) -> String {
     .to_string()
 }
 
-fn extract_task_ids(text: &str) -> BTreeSet<String> {
-    let patterns = [
-        Regex::new(r"(?i)\btask\s*#?([0-9]+)\b").expect("valid regex"),
-        Regex::new(r"(?i)\b(?:tm|aoc-task)\s+(?:status|done|start|resume|show)\s+([0-9]+)\b")
-            .expect("valid regex"),
-        Regex::new(r"\[([0-9]+)\]").expect("valid regex"),
-    ];
-
+fn extract_task_ids(text: &str, patterns: &[Regex]) -> BTreeSet<String> {
     let mut task_ids = BTreeSet::new();
     for pattern in patterns {
         for captures in pattern.captures_iter(text) {
-            if let Some(task_id) = captures.get(1) {
-                task_ids.insert(task_id.as_str().to_string());
+            let Some(task_id) = captures.get(1).or_else(|| captures.get(0)) else {
+                continue;
+            };
+            if let Some(task_id) = normalize_task_id(task_id.as_str()) {
+                task_ids.insert(task_id);
             }
         }
     }
@@ -415,12 +506,24 @@ fn extract_task_ids(text: &str) -> BTreeSet<String> {
     task_ids
 }
 
+/// Issue keys are upper-cased so `task proj-12` and `[PROJ-12]` link to the
+/// same task. Ids outside `[A-Za-z0-9_-]` or over the length cap are dropped.
+fn normalize_task_id(raw: &str) -> Option<String> {
+    let task_id = raw.trim();
+    let valid = !task_id.is_empty()
+        && task_id.len() <= MAX_TASK_ID_CHARS
+        && task_id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_');
+    valid.then(|| task_id.to_ascii_uppercase())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use aoc_core::mind_contracts::{
         compact_raw_event_to_t0, ConversationRole, MessageEvent, RawEvent, RawEventBody,
-        T0CompactionPolicy,
+        T0CompactionPolicy, ToolExecutionStatus, ToolResultEvent,
     };
     use chrono::TimeZone;
     use tempfile::NamedTempFile;
@@ -541,4 +644,128 @@ mod tests {
             link.task_id == "102" && link.relation == ArtifactTaskRelation::WorkedOn
         }));
     }
+
+    #[test]
+    fn file_touching_tool_calls_count_as_worked_on_evidence() {
+        let db_file = NamedTempFile::new().expect("temp db");
+        let store = MindStore::open(db_file.path()).expect("open store");
+
+        store
+            .append_context_state(&ConversationContextState {
+                conversation_id: "conv-3".to_string(),
+                ts: ts(14, 0, 0),
+                active_tag: Some("mind".to_string()),
+                active_tasks: vec!["104".to_string()],
+                lifecycle: Some("in-progress".to_string()),
+                signal_task_ids: vec!["104".to_string()],
+                signal_source: "task_lifecycle_command".to_string(),
+            })
+            .expect("append active");
+
+        let mut compact_ids = Vec::new();
+        for (event_id, touched_paths) in [
+            ("evt-edit", vec!["src/parser.rs".to_string()]),
+            ("evt-ls", Vec::new()),
+        ] {
+            let raw = RawEvent {
+                event_id: event_id.to_string(),
+                conversation_id: "conv-3".to_string(),
+                agent_id: "agent-1".to_string(),
+                ts: ts(14, 1, 0),
+                body: RawEventBody::ToolResult(ToolResultEvent {
+                    tool_name: "edit".to_string(),
+                    status: ToolExecutionStatus::Success,
+                    latency_ms: None,
+                    exit_code: Some(0),
+                    output: None,
+                    redacted: false,
+                    touched_paths,
+                    cwd: None,
+                    output_digest: None,
+                    retry_count: 0,
+                }),
+                attrs: Default::default(),
+            };
+            let compact = compact_raw_event_to_t0(&raw, &T0CompactionPolicy::default())
+                .expect("compact")
+                .expect("kept");
+            store
+                .upsert_t0_compact_event(&compact)
+                .expect("insert compact");
+            compact_ids.push(compact.compact_id);
+        }
+
+        store
+            .insert_observation("obs-3", "conv-3", ts(14, 2, 0), "parser edits", &[])
+            .expect("insert artifact");
+
+        let engine = TaskAttributionEngine::new(AttributionConfig::default());
+        engine
+            .attribute_conversation(&store, "conv-3")
+            .expect("attribute");
+
+        let links = store
+            .artifact_task_links_for_artifact("obs-3")
+            .expect("load links");
+        let worked_on = links
+            .iter()
+            .find(|link| link.task_id == "104" && link.relation == ArtifactTaskRelation::WorkedOn)
+            .expect("worked_on link");
+        let tool_evidence = worked_on
+            .evidence_event_ids
+            .iter()
+            .filter(|id| id.starts_with("t0:"))
+            .collect::<Vec<_>>();
+        assert_eq!(tool_evidence, [&format!("t0:{}", compact_ids[0])]);
+    }
+
+    #[test]
+    fn task_id_patterns_are_configurable_and_keys_normalized() {
+        let db_file = NamedTempFile::new().expect("temp db");
+        let store = MindStore::open(db_file.path()).expect("open store");
+        store
+            .insert_observation(
+                "obs-keys",
+                "conv-keys",
+                ts(14, 0, 0),
+                "Picked up task proj-42, see [PROJ-42]; LIN-7 is related, UTF-8 is not",
+                &[],
+            )
+            .expect("insert artifact");
+        let mentioned = |store: &MindStore| {
+            store
+                .artifact_task_links_for_artifact("obs-keys")
+                .expect("load links")
+                .into_iter()
+                .map(|link| link.task_id)
+                .collect::<BTreeSet<_>>()
+        };
+
+        TaskAttributionEngine::new(AttributionConfig::default())
+            .attribute_conversation(&store, "conv-keys")
+            .expect("attribute");
+        assert_eq!(mentioned(&store), BTreeSet::from(["PROJ-42".to_string()]));
+
+        let linear = TaskAttributionEngine::new(AttributionConfig {
+            task_id_patterns: compile_task_id_patterns(&[
+                r"\b(?:LIN|PROJ)-[0-9]+\b",
+                r"\b(?:LIN|PROJ)-[0-9]+\b",
+            ])
+            .expect("patterns"),
+            ..AttributionConfig::default()
+        });
+        assert_eq!(linear.config.task_id_patterns.len(), 1, "repeats dropped");
+        linear
+            .attribute_conversation(&store, "conv-keys")
+            .expect("attribute");
+        assert_eq!(
+            mentioned(&store),
+            BTreeSet::from(["LIN-7".to_string(), "PROJ-42".to_string()])
+        );
+
+        for bad in [r"task (\d+", r"(\w+)-(\d+)", r"\d*"] {
+            let err = compile_task_id_patterns(&[bad]).expect_err(bad);
+            assert_eq!(err.error_code(), ErrorCode::InvalidInput);
+        }
+    }
 }